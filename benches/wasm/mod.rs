@@ -86,7 +86,8 @@ pub fn add_fields(criterion: &mut Criterion) {
                     timezone: shared::TimeZone::default(),
                     drop_on_error: true,
                     drop_on_abort: true,
-                })
+                    reroute_dropped: false,
+                }, &vector::config::TransformContext::default())
                 .unwrap(),
             ),
         ),
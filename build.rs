@@ -122,6 +122,28 @@ fn main() {
             .unwrap();
     }
 
+    #[cfg(any(feature = "sources-opentelemetry", feature = "sinks-opentelemetry"))]
+    {
+        println!("cargo:rerun-if-changed=proto/opentelemetry");
+
+        tonic_build::configure()
+            .compile(
+                &[
+                    "proto/opentelemetry/proto/collector/logs/v1/logs_service.proto",
+                    "proto/opentelemetry/proto/collector/metrics/v1/metrics_service.proto",
+                ],
+                &["proto/"],
+            )
+            .unwrap();
+    }
+
+    #[cfg(feature = "sinks-datadog")]
+    {
+        println!("cargo:rerun-if-changed=proto/ddsketch.proto");
+
+        prost_build::compile_protos(&["proto/ddsketch.proto"], &["proto/"]).unwrap();
+    }
+
     // We keep track of which environment variables we slurp in, and then emit stanzas at the end to
     // inform Cargo when it needs to rerun this build script.  This allows us to avoid rerunning it
     // every single time unless something _actually_ changes.
@@ -18,9 +18,9 @@ _values: {
 //
 // * `none` - compression is not applied
 // * `gzip` - gzip compression applied
-#CompressionAlgorithm: "none" | "gzip" | "lz4" | "snappy" | "zstd"
+#CompressionAlgorithm: "none" | "gzip" | "lz4" | "snappy" | "zlib" | "zstd"
 
-#CompressionLevel: "none" | "fast" | "default" | "best" | >=0 & <=9
+#CompressionLevel: "none" | "fast" | "default" | "best" | >=0 & <=21
 
 #Date: =~"^\\d{4}-\\d{2}-\\d{2}"
 
@@ -51,7 +51,7 @@ _values: {
 // * `deprecated` - The component will be removed in a future version.
 #DevelopmentStatus: "beta" | "stable" | "deprecated"
 
-#EncodingCodec: "json" | "ndjson" | "text"
+#EncodingCodec: "avro" | "csv" | "json" | "logfmt" | "native" | "ndjson" | "parquet" | "text"
 
 #Endpoint: {
 	description: string
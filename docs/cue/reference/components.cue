@@ -347,6 +347,14 @@ components: {
 						enum:    [#EncodingCodec, ...#EncodingCodec] | null
 					}
 				}
+
+				// `framing` is set for sinks that use the shared serializer and
+				// therefore accept the `encoding.framing` option.
+				framing?: enabled: bool
+
+				// `parquet` is set for object storage sinks, which can write each
+				// batch as a Parquet file.
+				parquet?: enabled: bool
 			}
 		}
 
@@ -401,6 +409,7 @@ components: {
 	#Input: {
 		logs:    bool
 		metrics: #MetricInput | null
+		traces:  *false | bool
 	}
 
 	#LogOutput: [Name=string]: {
@@ -415,6 +424,7 @@ components: {
 		gauge:        *false | bool
 		histogram:    *false | bool
 		set:          *false | bool
+		sketch:       *false | bool
 		summary:      *false | bool
 	}
 
@@ -521,12 +531,129 @@ components: {
 		configuration: {
 			_acknowledgements: {
 				common:      true
-				description: "Controls if the source will wait for destination sinks to deliver the events before acknowledging receipt."
+				description: "Controls if the source will wait for destination sinks to deliver the events before acknowledging receipt. Defaults to the global `acknowledgements` option."
 				warnings: ["Disabling this option may lead to loss of data, as destination sinks may reject events after the source acknowledges their successful receipt."]
 				required: false
 				type: bool: default: false
 			}
 
+			_decoding: {
+				_args: default_codec: string | null
+				let Args = _args
+
+				common:      false
+				description: "Configures how frames are parsed into events."
+				required:    false
+				warnings: []
+				type: object: options: codec: {
+					common:      true
+					description: "The codec used to parse each frame into one or more events."
+					required:    false
+					warnings: []
+					type: string: {
+						default: Args.default_codec
+						enum: {
+							bytes:  "Uses the raw bytes of the frame as the `message` field."
+							json:   "Parses the frame as a JSON object, or an array of JSON objects, and creates one event per object."
+							native: "Parses the frame as a Vector native protobuf encoded event, as produced by the `vector` sink."
+							syslog: "Parses the frame as a RFC 3164 or RFC 5424 syslog message."
+						}
+						syntax: "literal"
+					}
+				}
+			}
+
+			_framing: {
+				_args: default_method: string | null
+				let Args = _args
+
+				common:      false
+				description: "Configures how the incoming byte stream or datagrams are split into frames."
+				required:    false
+				warnings: []
+				type: object: options: {
+					method: {
+						common:      true
+						description: "The framing method."
+						required:    false
+						warnings: []
+						type: string: {
+							default: Args.default_method
+							enum: {
+								bytes:               "Uses the whole received payload as a single frame."
+								character_delimited: "Splits frames on the configured `character_delimited.delimiter`."
+								length_delimited:    "Expects each frame to be prefixed by its length as a 32-bit big-endian integer."
+								newline_delimited:   "Splits frames on newline characters."
+								octet_counting:      "Uses octet counting as described in RFC 6587, falling back to newline delimiting for frames that aren't prefixed by their length."
+							}
+							syntax: "literal"
+						}
+					}
+					character_delimited: {
+						common:        false
+						description:   "Options for the character delimited framing method."
+						relevant_when: "method = `character_delimited`"
+						required:      true
+						warnings: []
+						type: object: options: {
+							delimiter: {
+								description: "The ASCII character used to delimit frames."
+								required:    true
+								warnings: []
+								type: string: {
+									examples: [",", ";"]
+									syntax: "literal"
+								}
+							}
+							max_length: {
+								common:      false
+								description: "The maximum bytes size of a frame before it is discarded."
+								required:    false
+								warnings: []
+								type: uint: {
+									default: null
+									unit:    "bytes"
+								}
+							}
+						}
+					}
+					newline_delimited: {
+						common:        false
+						description:   "Options for the newline delimited framing method."
+						relevant_when: "method = `newline_delimited`"
+						required:      false
+						warnings: []
+						type: object: options: max_length: {
+							common:      false
+							description: "The maximum bytes size of a frame before it is discarded."
+							required:    false
+							warnings: []
+							type: uint: {
+								default: null
+								unit:    "bytes"
+							}
+						}
+					}
+					octet_counting: {
+						common:        false
+						description:   "Options for the octet counting framing method."
+						relevant_when: "method = `octet_counting`"
+						required:      false
+						warnings: []
+						type: object: options: max_length: {
+							common:      false
+							description: "The maximum bytes size of a frame before it is discarded."
+							required:    false
+							warnings: []
+							type: uint: {
+								default: null
+								unit:    "bytes"
+							}
+						}
+					}
+				}
+			}
+
 			_tls_accept: {
 				_args: {
 					can_enable:             bool
@@ -852,6 +979,11 @@ components: {
 						A list of upstream [source](\(urls.vector_sources)) or [transform](\(urls.vector_transforms))
 						IDs. Wildcards (`*`) are supported but _must_ be the last character in the ID.
 
+						Some transforms, such as `route`, write to named outputs besides their default one. These
+						can be used as `<component_id>.<output_id>`. The `dropped` output of a component with
+						`reroute_dropped` enabled is used the same way, as `<component_id>.dropped`, but wildcards
+						never match `dropped` outputs.
+
 						See [configuration](\(urls.vector_configuration)) for more info.
 						"""
 					required:    true
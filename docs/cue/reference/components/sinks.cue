@@ -61,7 +61,10 @@ components: sinks: [Name=string]: {
 		if features.buffer.enabled {
 			buffer: {
 				common:      false
-				description: "Configures the sink specific buffer behavior."
+				description: """
+					Configures the sink specific buffer behavior. Can also be an array of buffer stages,
+					where each stage but the last overflows into the next one when it's full.
+					"""
 				required:    false
 				type: object: {
 					examples: []
@@ -113,6 +116,7 @@ components: sinks: [Name=string]: {
 								enum: {
 									block:       "Applies back pressure when the buffer is full. This prevents data loss, but will cause data to pile up on the edge."
 									drop_newest: "Drops new data as it's received. This data is lost. This should be used when performance is the highest priority."
+									overflow:    "Sends new data to the next buffer stage, which is read from alongside this one. Only memory buffers that are followed by another stage can overflow, and data may be delivered out of order once they do."
 								}
 								syntax: "literal"
 							}
@@ -143,6 +147,15 @@ components: sinks: [Name=string]: {
 							if list.Contains(features.send.compression.algorithms, "gzip") {
 								gzip: "[Gzip](\(urls.gzip)) standard DEFLATE compression."
 							}
+							if list.Contains(features.send.compression.algorithms, "zlib") {
+								zlib: "[Zlib](\(urls.zlib)) DEFLATE compression, sent with the `deflate` content encoding."
+							}
+							if list.Contains(features.send.compression.algorithms, "zstd") {
+								zstd: "[Zstandard](\(urls.zstd)) compression."
+							}
+							if list.Contains(features.send.compression.algorithms, "snappy") {
+								snappy: "[Snappy](\(urls.snappy)) compression, written in the [framing format](\(urls.snappy_framing))."
+							}
 						}
 						syntax: "literal"
 					}
@@ -167,6 +180,40 @@ components: sinks: [Name=string]: {
 							}
 						}
 
+						if features.send.encoding.framing != _|_ {
+							if features.send.encoding.framing.enabled {
+								framing: {
+									common:      false
+									description: "Configures how encoded events are delimited from each other when written to a byte stream. Defaults to `length_delimited` for the `avro` and `native` codecs and to `newline_delimited` otherwise."
+									required:    false
+									type: object: options: {
+										method: {
+											description: "The framing method."
+											required:    true
+											type: string: {
+												enum: {
+													bytes:               "Events are written back to back without any delimiter."
+													character_delimited: "Each event is followed by the configured delimiter character."
+													length_delimited:    "Each event is prefixed with its length as a 32-bit big-endian integer."
+													newline_delimited:   "Each event is followed by a newline character."
+												}
+												syntax: "literal"
+											}
+										}
+										delimiter: {
+											description:   "The character that follows each event."
+											relevant_when: "method = `character_delimited`"
+											required:      true
+											type: string: {
+												examples: [",", "\t"]
+												syntax: "literal"
+											}
+										}
+									}
+								}
+							}
+						}
+
 						except_fields: {
 							common:      false
 							description: "Prevent the sink from encoding the specified fields."
@@ -180,6 +227,36 @@ components: sinks: [Name=string]: {
 							}
 						}
 
+						if features.send.encoding.parquet != _|_ {
+							if features.send.encoding.parquet.enabled {
+								parquet: {
+									common:        false
+									description:   "Configures the Parquet file written for each batch. Pages are compressed with the sink's `compression`, which can't be `zlib` as Parquet has no zlib codec, the object itself is not compressed."
+									relevant_when: "codec = `parquet`"
+									required:      false
+									type: object: options: {
+										schema: {
+											description: "The columns of the file, mapping field paths to column types. Values that don't match the column type are written as nulls. `timestamp` columns hold milliseconds since the Unix epoch; integer values, such as those produced by `timestamp_format = \"unix\"`, are read as seconds."
+											required:    true
+											type: object: {
+												examples: [{"message": "string", "status": "int64", "timestamp": "timestamp"}]
+												options: {}
+											}
+										}
+										row_group_size: {
+											common:      false
+											description: "The maximum number of rows in a row group. Defaults to the batch's `max_events`, so each batch is written as a single row group."
+											required:    false
+											type: uint: {
+												default: null
+												unit:    "events"
+											}
+										}
+									}
+								}
+							}
+						}
+
 						only_fields: {
 							common:      false
 							description: "Makes the sink encode only the specified fields."
@@ -232,6 +309,17 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		reroute_dropped: {
+			common:      false
+			description: """
+				Send the events this sink rejects as unrecoverable, including events it fails to encode, to the
+				`<component_id>.dropped` output instead of discarding them, with the reason they were dropped under
+				`metadata.dropped`. Sources are acknowledged based on the delivery of the rerouted events.
+				"""
+			required: false
+			type: bool: default: false
+		}
+
 		if features.send != _|_ {
 			if features.send.request.enabled {
 				request: {
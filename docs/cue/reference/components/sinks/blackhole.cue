@@ -67,6 +67,7 @@ components: sinks: blackhole: {
 	input: {
 		logs:    true
 		metrics: null
+		traces:  true
 	}
 
 	telemetry: metrics: {
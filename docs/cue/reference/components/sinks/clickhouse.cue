@@ -25,8 +25,8 @@ components: sinks: clickhouse: {
 			compression: {
 				enabled: true
 				default: "gzip"
-				algorithms: ["none", "gzip"]
-				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+				algorithms: ["none", "gzip", "zlib", "zstd", "snappy"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21]
 			}
 			encoding: {
 				enabled: true
@@ -77,6 +77,7 @@ components: sinks: console: {
 			set:          true
 			summary:      true
 		}
+		traces: true
 	}
 
 	telemetry: metrics: {
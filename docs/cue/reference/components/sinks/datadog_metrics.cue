@@ -66,6 +66,16 @@ components: sinks: datadog_metrics: {
 				syntax: "literal"
 			}
 		}
+		send_distributions_as_sketches: {
+			common: false
+			description: """
+				Send distributions with the `summary` statistic as sketches to the beta sketches API
+				instead of sending every sample to the distribution points API.
+				"""
+			required: false
+			warnings: []
+			type: bool: default: false
+		}
 	}
 
 	input: {
@@ -76,6 +86,7 @@ components: sinks: datadog_metrics: {
 			gauge:        true
 			histogram:    false
 			set:          false
+			sketch:       true
 			summary:      false
 		}
 	}
@@ -25,16 +25,17 @@ components: sinks: http: {
 			compression: {
 				enabled: true
 				default: "none"
-				algorithms: ["none", "gzip"]
-				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+				algorithms: ["none", "gzip", "zlib", "zstd", "snappy"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21]
 			}
 			encoding: {
 				enabled: true
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "ndjson", "text"]
+					enum: ["avro", "csv", "json", "logfmt", "native", "ndjson", "text"]
 				}
+				framing: enabled: true
 			}
 			request: {
 				enabled:                    true
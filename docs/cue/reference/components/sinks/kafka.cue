@@ -34,7 +34,7 @@ components: sinks: kafka: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "text"]
+					enum: ["avro", "csv", "json", "logfmt", "native", "ndjson", "text"]
 				}
 			}
 			request: enabled: false
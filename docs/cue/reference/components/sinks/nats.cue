@@ -22,7 +22,7 @@ components: sinks: nats: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "text"]
+					enum: ["avro", "csv", "json", "logfmt", "native", "ndjson", "text"]
 				}
 			}
 			request: enabled: false
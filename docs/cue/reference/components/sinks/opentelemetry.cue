@@ -0,0 +1,158 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	title: "OpenTelemetry"
+
+	description: """
+		Sends logs and metrics to any backend or collector that accepts the
+		OpenTelemetry protocol (OTLP) over gRPC or HTTP.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: false
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   1000
+				timeout_secs: 1
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			request: {
+				enabled:                    true
+				adaptive_concurrency:       true
+				concurrency:                5
+				rate_limit_duration_secs:   1
+				rate_limit_num:             5
+				retry_initial_backoff_secs: 1
+				retry_max_duration_secs:    10
+				timeout_secs:               60
+				headers:                    false
+			}
+			tls: {
+				enabled:                true
+				can_enable:             false
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						api: {
+							title: "OpenTelemetry protocol"
+							url:   urls.opentelemetry_protocol
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		endpoint: {
+			description: "The base URL of the OTLP receiver. For the `http` protocol, the `/v1/logs` and `/v1/metrics` paths are appended to it."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["http://127.0.0.1:4317", "https://otlp.example.com:4318"]
+				syntax: "literal"
+			}
+		}
+		protocol: {
+			common:      true
+			description: "The transport used to send export requests."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "grpc"
+				enum: {
+					grpc: "Send requests to the OTLP/gRPC logs and metrics services."
+					http: "Send protobuf encoded requests over OTLP/HTTP."
+				}
+				syntax: "literal"
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: false
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          false
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		metric_types: {
+			title: "Metric type mapping"
+			body: """
+				Counters are sent as monotonic sums and incremental gauges
+				as non-monotonic sums, with delta temporality for
+				incremental metrics and cumulative temporality otherwise.
+				Absolute gauges are sent as gauges, and aggregated
+				histograms and summaries as histograms and summaries. Other
+				metric types can't be represented in OTLP and are rejected.
+				"""
+		}
+		log_records: {
+			title: "Log record mapping"
+			body: """
+				The `message` field becomes the body of the log record and
+				the `resources` field its resource. Fields produced by the
+				`opentelemetry` source, such as `severity_text` and
+				`trace_id`, are mapped back onto the record, and any other
+				fields are sent as attributes.
+				"""
+		}
+		requests: {
+			title: "Export requests"
+			body: """
+				Logs and metrics are batched separately, so each request
+				exports only one of them and is retried on its own. Trace
+				events aren't exported yet and are rejected.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+	}
+}
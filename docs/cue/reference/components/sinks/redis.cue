@@ -27,7 +27,7 @@ components: sinks: redis: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "text"]
+					enum: ["avro", "csv", "json", "logfmt", "native", "ndjson", "text"]
 				}
 			}
 			request: {
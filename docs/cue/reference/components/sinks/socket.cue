@@ -22,8 +22,9 @@ components: sinks: socket: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "text"]
+					enum: ["avro", "csv", "json", "logfmt", "native", "ndjson", "text"]
 				}
+				framing: enabled: true
 			}
 			send_buffer_bytes: {
 				enabled:       true
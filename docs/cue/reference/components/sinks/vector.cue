@@ -75,6 +75,7 @@ components: sinks: vector: {
 			summary:      true
 			set:          true
 		}
+		traces: true
 	}
 
 	configuration: {
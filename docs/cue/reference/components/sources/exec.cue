@@ -75,6 +75,21 @@ components: sources: exec: {
 				syntax:  "literal"
 			}
 		}
+		decoding: {
+			common:      false
+			description: "How the standard output of the command is decoded. See [Output Decoding](#output-decoding) for details."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "lines"
+				enum: {
+					lines:      "Each line, or the whole output when `event_per_line` is disabled, is the message of a log event."
+					json:       "Each line, or the whole output when `event_per_line` is disabled, is a JSON object whose fields are added to a log event."
+					prometheus: "The whole output is in the Prometheus text exposition format, and each of its samples is a metric event."
+				}
+				syntax: "literal"
+			}
+		}
 		include_stderr: {
 			common:      false
 			description: "Include the output of stderr when generating events."
@@ -96,6 +111,12 @@ components: sources: exec: {
 				unit:    "bytes"
 			}
 		}
+		run_metadata: {
+			common:      false
+			description: "Add the ID of the run, and for scheduled runs its exit code and duration, to each log event, add them as tags to each metric event, and emit a summary event at the end of each run. See [Run Metadata](#run-metadata) for details."
+			required:    false
+			type: bool: default: false
+		}
 		scheduled: {
 			common:      true
 			description: "The scheduled options."
@@ -182,6 +203,77 @@ components: sources: exec: {
 					}
 				}
 			}
+			run_id: {
+				common:        false
+				description:   "The unique ID of the run of the command which generated this event."
+				relevant_when: "run_metadata = true"
+				required:      false
+				type: string: {
+					default: null
+					examples: ["2c6e2e36-9c3e-4d4a-9f0e-0c5b5a8b3e1d"]
+					syntax: "literal"
+				}
+			}
+			exit_code: {
+				common:        false
+				description:   "The exit code of the command, for scheduled runs. Absent when the command was killed."
+				relevant_when: "run_metadata = true"
+				required:      false
+				type: uint: {
+					default: null
+					examples: [0, 1]
+					unit: null
+				}
+			}
+			run_duration_seconds: {
+				common:        false
+				description:   "How long the command ran, for scheduled runs."
+				relevant_when: "run_metadata = true"
+				required:      false
+				type: float: {
+					default: null
+					examples: [0.25]
+				}
+			}
+		}
+	}
+
+	output: logs: summary: {
+		description: "The summary of a run of the command, emitted when it ends if `run_metadata` is enabled."
+		fields: {
+			host: fields._local_host
+			message: {
+				description: "A constant message."
+				required:    true
+				type: string: {
+					examples: ["Finished command run."]
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp
+			pid:       output.logs.line.fields.pid
+			command:   output.logs.line.fields.command
+			run_summary: {
+				description: "Always `true`, to tell summaries apart from the output of the command."
+				required:    true
+				type: bool: {}
+			}
+			run_id:               output.logs.line.fields.run_id
+			exit_code:            output.logs.line.fields.exit_code
+			run_duration_seconds: output.logs.line.fields.run_duration_seconds
+			event_count: {
+				description: "The number of events generated from the output of the run."
+				required:    true
+				type: uint: {
+					examples: [3]
+					unit: null
+				}
+			}
+			timed_out: {
+				description: "Whether the command was killed because it was still running at the next scheduled run."
+				required:    true
+				type: bool: {}
+			}
 		}
 	}
 
@@ -214,6 +306,34 @@ components: sources: exec: {
 				[`maximum_buffer_size_bytes`](#maximum_buffer_size_bytes) is reached.
 				"""
 		}
+		output_decoding: {
+			title: "Output Decoding"
+			body: """
+				The `decoding` option only applies to the standard output of the command, the lines of its
+				standard error are always the messages of log events.
+
+				With `json`, the fields of each JSON object replace the `message` of the event. Output which
+				isn't a JSON object is kept as the `message`, and counted as a parsing error.
+
+				With `prometheus`, the output is parsed once the command exits, so this is best suited to
+				scheduled runs. Each sample becomes a metric event with its own labels as tags.
+				"""
+		}
+		run_metadata: {
+			title: "Run Metadata"
+			body: """
+				When `run_metadata` is enabled, every log event generated by a run of the command has a
+				`run_id` field, unique to the run. Since the exit code and duration of a run are only known
+				when the command exits, the log events of scheduled runs are held until then, and get
+				`exit_code` and `run_duration_seconds` fields. The log events of streaming runs are
+				forwarded as soon as they're read, with their `run_id` only.
+
+				At the end of each run, a summary event is emitted with the same `run_id`, the exit code
+				and duration of the run, the number of events it generated, and whether it timed out.
+				Metrics decoded from the Prometheus text output of a run are only emitted once the
+				command exits, and get the `run_id`, `exit_code` and `run_duration_seconds` as tags.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
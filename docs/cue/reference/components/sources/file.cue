@@ -58,6 +58,7 @@ components: sources: file: {
 
 	configuration: {
 		acknowledgements: configuration._acknowledgements
+		decoding: configuration._decoding & {_args: default_codec: "bytes"}
 		exclude: {
 			common:      false
 			description: "Array of file patterns to exclude. [Globbing](#globbing) is supported.*Takes precedence over the [`include` option](#include).*"
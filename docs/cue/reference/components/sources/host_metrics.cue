@@ -79,7 +79,7 @@ components: sources: host_metrics: {
 
 	configuration: {
 		collectors: {
-			description: "The list of host metric collector services to use. Defaults to all collectors."
+			description: "The list of host metric collector services to use. Defaults to all collectors, except `process` and `cgroups`."
 			common:      true
 			required:    false
 			type: array: {
@@ -93,6 +93,8 @@ components: sources: host_metrics: {
 						host:       "Metrics related to host"
 						memory:     "Metrics related to memory utilization."
 						network:    "Metrics related to network utilization."
+						process:    "Metrics related to the resource utilization of each process (Linux only)."
+						cgroups:    "Metrics related to the resource accounting of each control group (Linux only)."
 					}
 					syntax: "literal"
 				}
@@ -286,6 +288,53 @@ components: sources: host_metrics: {
 				}
 			}
 		}
+		cgroups: {
+			common:      false
+			description: #"Options for the "cgroups" metrics collector."#
+			required:    false
+			type: object: options: {
+				groups: {
+					common:      false
+					required:    false
+					description: "Lists of control group path patterns to include or exclude."
+					type: object: options: {
+						includes: {
+							required: false
+							common:   false
+							description: """
+								The list of control group path patterns for which to gather metrics.
+								The paths are relative to the root of the hierarchy, such as `/system.slice/vector.service`.
+								Defaults to including all groups.
+								The patterns are matched using [globbing](#globbing).
+								"""
+							type: array: {
+								default: ["*"]
+								items: type: string: {
+									examples: ["/", "/system.slice/*"]
+									syntax: "literal"
+								}
+							}
+						}
+						excludes: {
+							required: false
+							common:   false
+							description: """
+								The list of control group path patterns for which to gather metrics.
+								Defaults to excluding no groups.
+								The patterns are matched using [globbing](#globbing).
+								"""
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["/user.slice/*"]
+									syntax: "literal"
+								}
+							}
+						}
+					}
+				}
+			}
+		}
 		network: {
 			common:      false
 			description: #"Options for the "network" metrics collector."#
@@ -332,6 +381,52 @@ components: sources: host_metrics: {
 				}
 			}
 		}
+		process: {
+			common:      false
+			description: #"Options for the "process" metrics collector."#
+			required:    false
+			type: object: options: {
+				names: {
+					common:      false
+					required:    false
+					description: "Lists of process name patterns to include or exclude."
+					type: object: options: {
+						includes: {
+							required: false
+							common:   false
+							description: """
+								The list of process name patterns for which to gather metrics.
+								Defaults to including all processes.
+								The patterns are matched using [globbing](#globbing).
+								"""
+							type: array: {
+								default: ["*"]
+								items: type: string: {
+									examples: ["nginx", "postgres*"]
+									syntax: "literal"
+								}
+							}
+						}
+						excludes: {
+							required: false
+							common:   false
+							description: """
+								The list of process name patterns for which to gather metrics.
+								Defaults to excluding no processes.
+								The patterns are matched using [globbing](#globbing).
+								"""
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["kworker*"]
+									syntax: "literal"
+								}
+							}
+						}
+					}
+				}
+			}
+		}
 	}
 
 	output: metrics: {
@@ -416,6 +511,28 @@ components: sources: host_metrics: {
 		network_transmit_packets_drop_total: _host & _network_nomac & {description: "The number of packets dropped during transmits on this interface."}
 		network_transmit_packets_total:      _host & _network_nomac & {description: "The number of packets transmitted on this interface."}
 
+		// Host processes
+		process_cpu_seconds_total: _host & _process & {
+			description: "The number of CPU seconds used by the process."
+			type:        "counter"
+			tags: mode: {
+				description: "Which mode the CPU was running in during the given time."
+				required:    true
+				examples: ["system", "user"]
+			}
+		}
+		process_memory_rss_bytes: _host & _process & _process_gauge & {description: "The number of bytes of main memory used by the process."}
+		process_open_fds:         _host & _process & _process_gauge & {description: "The number of file descriptors opened by the process."}
+		process_threads:          _host & _process & _process_gauge & {description: "The number of threads of the process."}
+
+		// Host cgroups
+		cgroup_cpu_usage_seconds_total:  _host & _cgroup_counter & {description: "The number of CPU seconds used by the tasks of the control group."}
+		cgroup_cpu_user_seconds_total:   _host & _cgroup_counter & {description: "The number of CPU seconds used by the tasks of the control group in user mode."}
+		cgroup_cpu_system_seconds_total: _host & _cgroup_counter & {description: "The number of CPU seconds used by the tasks of the control group in kernel mode."}
+		cgroup_memory_current_bytes:     _host & _cgroup_gauge & {description:   "The number of bytes of memory used by the tasks of the control group."}
+		cgroup_memory_anon_bytes:        _host & _cgroup_gauge & {description:   "The number of bytes of anonymous memory used by the tasks of the control group."}
+		cgroup_memory_file_bytes:        _host & _cgroup_gauge & {description:   "The number of bytes of memory used by the tasks of the control group to cache files."}
+
 		// Helpers
 		_host: {
 			default_namespace: "host"
@@ -485,6 +602,36 @@ components: sources: host_metrics: {
 			}
 		}
 		_network_nomac: _network_gauge & {relevant_when: "OS is not macOS"}
+		_process: {
+			tags: _host_metrics_tags & {
+				collector: examples: ["process"]
+				pid: {
+					description: "The ID of the process."
+					required:    true
+					examples: ["1234"]
+				}
+				name: {
+					description: "The name of the process."
+					required:    true
+					examples: ["nginx"]
+				}
+			}
+			relevant_when: "OS is Linux"
+		}
+		_process_gauge: {type: "gauge"}
+		_cgroup: {
+			tags: _host_metrics_tags & {
+				collector: examples: ["cgroups"]
+				cgroup: {
+					description: "The path of the control group, relative to the root of its hierarchy."
+					required:    true
+					examples: ["/", "/system.slice/vector.service"]
+				}
+			}
+			relevant_when: "OS is Linux"
+		}
+		_cgroup_counter: _cgroup & {type: "counter"}
+		_cgroup_gauge:   _cgroup & {type: "gauge"}
 	}
 
 	telemetry: metrics: {
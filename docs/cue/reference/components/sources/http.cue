@@ -69,9 +69,10 @@ components: sources: http: {
 				syntax: "literal"
 			}
 		}
+		decoding: configuration._decoding & {_args: default_codec: null}
 		encoding: {
 			common:      true
-			description: "The expected encoding of received data. Note that for `json` and `ndjson` encodings, the fields of the JSON objects are output as separate fields."
+			description: "The expected encoding of received data. Note that for `json` and `ndjson` encodings, the fields of the JSON objects are output as separate fields. The `framing` and `decoding` options take precedence over the framing and codec implied by the encoding when set."
 			required:    false
 			type: string: {
 				default: "text"
@@ -84,6 +85,7 @@ components: sources: http: {
 				syntax: "literal"
 			}
 		}
+		framing: configuration._framing & {_args: default_method: null}
 		headers: {
 			common:      false
 			description: "A list of HTTP headers to include in the log event. These will override any values included in the JSON payload with conflicting names."
@@ -170,6 +170,16 @@ components: sources: internal_metrics: {
 			type:              "histogram"
 			default_namespace: "vector"
 		}
+		buffer_byte_size: {
+			description:       "The number of bytes of unacknowledged events in a sink's disk buffer."
+			type:              "gauge"
+			default_namespace: "vector"
+		}
+		buffer_corrupted_records_total: {
+			description:       "The total number of corrupted or truncated records skipped while reading a sink's disk buffer."
+			type:              "counter"
+			default_namespace: "vector"
+		}
 		checkpoint_write_errors_total: {
 			description:       "The total number of errors writing checkpoints."
 			type:              "counter"
@@ -266,6 +276,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		decoder_framing_errors_total: {
+			description:       "The total number of errors splitting incoming data into frames in a source component."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		decoder_parse_errors_total: {
+			description:       "The total number of errors parsing frames into events in a source component."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		k8s_format_picker_edge_cases_total: {
 			description:       "The total number of edge cases encountered while picking format of the Kubernetes log message."
 			type:              "counter"
@@ -700,6 +722,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		service_discovery_errors_total: {
+			description:       "The total number of errors discovering the targets of this component."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		requests_received_total: {
 			description:       "The total number of requests received by this component."
 			type:              "counter"
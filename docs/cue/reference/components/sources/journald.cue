@@ -49,6 +49,7 @@ components: sources: journald: {
 	}
 
 	configuration: {
+		acknowledgements: configuration._acknowledgements
 		batch_size: {
 			common:      false
 			description: "The systemd journal is read in batches, and a checkpoint is set at the end of each batch. This option limits the size of the batch."
@@ -59,6 +60,17 @@ components: sources: journald: {
 				unit:    null
 			}
 		}
+		boot: {
+			common:      false
+			description: "Include only entries from the given boot, either a boot ID or an offset relative to the current boot, as accepted by the `--boot` option of `journalctl`. Can't be used with `current_boot_only`."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["-1", "124c781146e841ae8d9b4590df8b9231"]
+				syntax: "literal"
+			}
+		}
 		current_boot_only: {
 			common:      true
 			description: "Include only entries from the current boot."
@@ -79,6 +91,17 @@ components: sources: journald: {
 				}
 			}
 		}
+		from_cursor: {
+			common:      false
+			description: "The journal cursor to start reading after when there is no checkpoint yet. Can't be used with `since_now`."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["s=5b5f9ad3a7b54bd6a0b4b7b7d3bf6b82;i=1a2b;b=124c781146e841ae8d9b4590df8b9231;m=16e4f7b7;t=5a0f6b3c5e1a2;x=9f1c7a2d0e3b4c5d"]
+				syntax: "literal"
+			}
+		}
 		include_units: {
 			common:      true
 			description: "The list of unit names to monitor. If empty or not present, all units are accepted. Unit names lacking a `\".\"` will have `\".service\"` appended to make them a valid service unit name."
@@ -92,6 +115,17 @@ components: sources: journald: {
 				}
 			}
 		}
+		journal_directory: {
+			common:      false
+			description: "The directory of the journal files to read, instead of the journal of the local system. This is useful to read exported journals, or the journals of containers."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["/var/lib/machines/container/var/log/journal"]
+				syntax: "literal"
+			}
+		}
 		journalctl_path: {
 			common:      false
 			description: "The full path of the `journalctl` executable. If not set, Vector will search the path for `journalctl`."
@@ -103,6 +137,26 @@ components: sources: journald: {
 				syntax: "literal"
 			}
 		}
+		matches: {
+			common:      false
+			description: "The list of match expressions journal entries must satisfy, made of a field name, an operator among `=`, `!=`, `<`, `<=`, `>` and `>=`, and a value. See [Match Expressions](#match-expressions) for details."
+			required:    false
+			warnings: []
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["_SYSTEMD_UNIT=sshd.service", "PRIORITY<=3", "_TRANSPORT!=kernel"]
+					syntax: "literal"
+				}
+			}
+		}
+		since_now: {
+			common:      false
+			description: "When there is no checkpoint yet, only include entries added after Vector starts, rather than the whole journal. Can't be used with `from_cursor`."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
 	}
 
 	output: logs: {
@@ -186,6 +240,34 @@ components: sources: journald: {
 				[issue #1473](\(urls.vector_issues)/1437).
 				"""
 		}
+		match_expressions: {
+			title: "Match Expressions"
+			body:  """
+				The `matches` option selects journal entries on the value of any of
+				their fields. As with `journalctl`, an entry is kept when, for every
+				field named in the expressions, it satisfies at least one of the
+				expressions on that field: `["_SYSTEMD_UNIT=a.service",
+				"_SYSTEMD_UNIT=b.service", "PRIORITY<=3"]` keeps the errors of either
+				unit.
+
+				The `<`, `<=`, `>` and `>=` operators compare numbers, such as
+				`PRIORITY`, and require a numeric value in the expression; an entry
+				whose field isn't a number doesn't satisfy them. `=` and `!=` compare
+				numbers by value and other values as strings. An entry without the
+				field only satisfies `!=` expressions. Matching happens before the
+				`remap_priority` option is applied.
+				"""
+		}
+		start_position: {
+			title: "Start Position"
+			body:  """
+				Vector checkpoints the cursor of the last entry it has processed in
+				its `data_dir`, and resumes from it when restarted. When there is no
+				checkpoint yet, Vector reads the whole journal, unless `since_now` is
+				set, in which case it only reads the new entries, or `from_cursor` is
+				set, in which case it starts after the given cursor.
+				"""
+		}
 		non_ascii: {
 			title: "Non-ASCII Messages"
 			body: """
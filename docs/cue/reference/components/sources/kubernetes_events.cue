@@ -0,0 +1,209 @@
+package metadata
+
+components: sources: kubernetes_events: {
+	title: "Kubernetes Events"
+
+	description: """
+		Collects the Kubernetes `Event` resources of the cluster via the
+		Kubernetes API, emitting each of them as a log event.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			from: service: services.kubernetes
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: [
+			"""
+				Vector must be allowed to `watch` the `events` resource of the
+				watched namespaces.
+				""",
+		]
+		warnings: [
+			"""
+				Every instance of this source emits all the watched `Event`s, so
+				run a single instance per cluster to avoid duplicates.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: "kubernetes"
+	}
+
+	configuration: {
+		namespace: {
+			common:      true
+			description: "The namespace to watch the `Event`s at. All the namespaces are watched if unset."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["default"]
+				syntax: "literal"
+			}
+		}
+		field_selector: {
+			common:      false
+			description: "Specifies the field selector to filter `Event`s with."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["type=Warning", "involvedObject.kind=Pod"]
+				syntax: "literal"
+			}
+		}
+		label_selector: {
+			common:      false
+			description: "Specifies the label selector to filter `Event`s with."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["my_custom_label=my_value"]
+				syntax: "literal"
+			}
+		}
+		kube_config_file: {
+			common:      false
+			description: "Optional path to a kubeconfig file readable by Vector. If not set, Vector will try to connect to Kubernetes using in-cluster configuration."
+			required:    false
+			type: string: {
+				default: null
+				syntax:  "literal"
+			}
+		}
+	}
+
+	output: logs: event: {
+		description: "A Kubernetes `Event`."
+		fields: {
+			message: {
+				description: "The human-readable description of the `Event`."
+				required:    true
+				type: string: {
+					examples: ["Started container nginx"]
+					syntax: "literal"
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["kubernetes_events"]
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the `Event` was last observed, falling back to its creation time."
+			}
+			reason: {
+				description: "The machine-readable reason of the `Event`."
+				required:    false
+				common:      true
+				type: string: {
+					examples: ["Started", "BackOff"]
+					default: null
+					syntax:  "literal"
+				}
+			}
+			type: {
+				description: "The type of the `Event`."
+				required:    false
+				common:      true
+				type: string: {
+					examples: ["Normal", "Warning"]
+					default: null
+					syntax:  "literal"
+				}
+			}
+			count: {
+				description: "The number of times the `Event` occurred."
+				required:    false
+				common:      true
+				type: uint: {
+					examples: [1]
+					default: null
+					unit:    null
+				}
+			}
+			"kubernetes.event_name": {
+				description: "The name of the `Event`."
+				required:    false
+				common:      true
+				type: string: {
+					examples: ["nginx-6799fc88d8-x7bqb.1693f2a8b2b1c3d4"]
+					default: null
+					syntax:  "literal"
+				}
+			}
+			"kubernetes.event_namespace": {
+				description: "The namespace of the `Event`."
+				required:    false
+				common:      true
+				type: string: {
+					examples: ["default"]
+					default: null
+					syntax:  "literal"
+				}
+			}
+			"kubernetes.involved_object": {
+				description: "The object the `Event` is about: its `api_version`, `kind`, `name`, `namespace`, `uid` and `field_path`."
+				required:    false
+				common:      true
+				type: object: {
+					examples: [{"kind": "Pod", "name": "nginx-6799fc88d8-x7bqb", "namespace": "default"}]
+					options: {}
+				}
+			}
+			source: {
+				description: "The `component` and `host` that reported the `Event`."
+				required:    false
+				common:      false
+				type: object: {
+					examples: [{"component": "kubelet", "host": "minikube"}]
+					options: {}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		deduplication: {
+			title: "Deduplication"
+			body: """
+				The source watches the `Event`s using the same resource version
+				tracking as the `kubernetes_logs` source. When the watch has to be
+				restarted from scratch, all the existing `Event`s are listed again;
+				the ones that were already emitted at the same resource version are
+				skipped.
+
+				The `Event`s that last occurred before Vector started, which the
+				cluster still retains, aren't emitted, so restarting Vector doesn't
+				emit them again.
+				"""
+		}
+	}
+}
@@ -147,6 +147,44 @@ components: sources: kubernetes_logs: {
 				}
 			}
 		}
+		namespace_annotation_fields: {
+			common:      false
+			description: "Configuration for how the events are annotated with Namespace metadata. Namespaces are only watched, and events annotated with their labels, when this is set, which requires access to the `/api/v1/namespaces` endpoint."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					namespace_labels: {
+						common:      false
+						description: "Event field for Namespace labels."
+						required:    false
+						type: string: {
+							default: "kubernetes.namespace_labels"
+							syntax:  "literal"
+						}
+					}
+				}
+			}
+		}
+		node_annotation_fields: {
+			common:      false
+			description: "Configuration for how the events are annotated with Node metadata. The Node is only watched, and events annotated with its labels, when this is set, which requires access to the `/api/v1/nodes` endpoint."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					node_labels: {
+						common:      false
+						description: "Event field for Node labels."
+						required:    false
+						type: string: {
+							default: "kubernetes.node_labels"
+							syntax:  "literal"
+						}
+					}
+				}
+			}
+		}
 		auto_partial_merge: {
 			common:      false
 			description: "Automatically merge partial messages into a single event. Partial here is in respect to messages that were split by the Kubernetes Container Runtime log driver."
@@ -308,6 +346,24 @@ components: sources: kubernetes_logs: {
 					syntax:  "literal"
 				}
 			}
+			"kubernetes.namespace_labels": {
+				description: "Set of labels attached to the Namespace of the Pod, when `namespace_annotation_fields` is set."
+				required:    false
+				common:      true
+				type: object: {
+					examples: [{"team": "observability"}]
+					options: {}
+				}
+			}
+			"kubernetes.node_labels": {
+				description: "Set of labels attached to the Node Vector runs at, when `node_annotation_fields` is set."
+				required:    false
+				common:      true
+				type: object: {
+					examples: [{"topology.kubernetes.io/zone": "us-east-1a"}]
+					options: {}
+				}
+			}
 			"kubernetes.pod_labels": {
 				description: "Set of labels attached to the Pod."
 				required:    false
@@ -591,7 +647,11 @@ components: sources: kubernetes_logs: {
 				Vector requires access to the Kubernetes API.
 				Specifically, the [`kubernetes_logs` source](\(urls.vector_kubernetes_logs_source))
 				uses the `/api/v1/pods` endpoint to "watch" the pods from
-				all namespaces.
+				all namespaces. When `namespace_annotation_fields` or
+				`node_annotation_fields` are set, it also uses the
+				`/api/v1/namespaces` or `/api/v1/nodes` endpoints to "watch" the
+				namespaces or the local node for metadata enrichment. Without the
+				access to those, events are not annotated with their labels.
 
 				Modern Kubernetes clusters run with RBAC (role-based access control)
 				scheme. RBAC-enabled clusters require some configuration to grant Vector
@@ -60,6 +60,7 @@ components: sources: logstash: {
 	}
 
 	configuration: {
+		acknowledgements: configuration._acknowledgements
 		address: {
 			description: "The address to listen for TCP connections on."
 			required:    true
@@ -0,0 +1,253 @@
+package metadata
+
+components: sources: opentelemetry: {
+	title: "OpenTelemetry"
+
+	description: """
+		Receives logs and metrics from applications and collectors using the
+		OpenTelemetry protocol (OTLP) over gRPC or HTTP.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.opentelemetry
+
+				interface: socket: {
+					api: {
+						title: "OpenTelemetry protocol"
+						url:   urls.opentelemetry_protocol
+					}
+					direction: "incoming"
+					port:      4317
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			tls: enabled: false
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._acknowledgements
+		grpc: {
+			common:      true
+			description: "Configuration for the OTLP/gRPC server. At least one of `grpc` or `http` must be configured."
+			required:    false
+			type: object: options: {
+				address: {
+					description: "The address to accept gRPC connections on. The address _must_ include a port."
+					required:    true
+					type: string: {
+						examples: ["0.0.0.0:4317"]
+						syntax: "literal"
+					}
+				}
+				tls: {
+					common:      false
+					description: "Configures mutual TLS for incoming gRPC connections."
+					required:    false
+					type: object: options: {
+						ca_file: {
+							description: "Absolute path to the CA certificate used to verify client certificates, in PEM format."
+							required:    true
+							type: string: {
+								examples: ["/path/to/certificate_authority.crt"]
+								syntax: "literal"
+							}
+						}
+						crt_file: {
+							description: "Absolute path to the certificate file used to identify this server, in PEM format."
+							required:    true
+							type: string: {
+								examples: ["/path/to/host_certificate.crt"]
+								syntax: "literal"
+							}
+						}
+						key_file: {
+							description: "Absolute path to the private key file used to identify this server, in PEM format."
+							required:    true
+							type: string: {
+								examples: ["/path/to/host_certificate.key"]
+								syntax: "literal"
+							}
+						}
+					}
+				}
+			}
+		}
+		http: {
+			common:      true
+			description: "Configuration for the OTLP/HTTP server. At least one of `grpc` or `http` must be configured. Accepted requests are answered with the protobuf encoded `Export*ServiceResponse` of the matching gRPC service."
+			required:    false
+			type: object: options: {
+				address: {
+					description: "The address to accept HTTP connections on. The address _must_ include a port."
+					required:    true
+					type: string: {
+						examples: ["0.0.0.0:4318"]
+						syntax: "literal"
+					}
+				}
+				auth: configuration._http_basic_auth
+				tls: configuration._tls_accept & {_args: {
+					can_enable:             true
+					can_verify_certificate: true
+					enabled_default:        false
+				}}
+			}
+		}
+	}
+
+	output: {
+		logs: record: {
+			description: "An OTLP log record."
+			fields: {
+				attributes: {
+					common:      false
+					description: "The attributes of the log record."
+					required:    false
+					type: object: {
+						examples: [{"http.status_code": 500}]
+						options: {}
+					}
+				}
+				message: {
+					description: "The body of the log record."
+					required:    false
+					type: "*": {}
+				}
+				observed_timestamp: {
+					common:      false
+					description: "The time the log record was observed by the collection system, if set."
+					required:    false
+					type: timestamp: {}
+				}
+				resources: {
+					common:      true
+					description: "The attributes of the resource which produced the log record."
+					required:    false
+					type: object: {
+						examples: [{"service.name": "checkout"}]
+						options: {}
+					}
+				}
+				severity_number: {
+					common:      false
+					description: "The numerical severity of the log record, if set."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [9]
+						unit: null
+					}
+				}
+				severity_text: {
+					common:      true
+					description: "The severity of the log record as it was known at the source, if set."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["INFO"]
+						syntax: "literal"
+					}
+				}
+				span_id: {
+					common:      false
+					description: "The hex encoded span ID the log record belongs to, if set."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["0123456789abcdef"]
+						syntax: "literal"
+					}
+				}
+				timestamp: {
+					description: "The time the event occurred. Falls back to the observed time, and then to the time the record was received, when not set."
+					required:    true
+					type: timestamp: {}
+				}
+				trace_id: {
+					common:      false
+					description: "The hex encoded trace ID the log record belongs to, if set."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["0123456789abcdef0123456789abcdef"]
+						syntax: "literal"
+					}
+				}
+			}
+		}
+		metrics: {
+			counter:   output._passthrough_counter
+			gauge:     output._passthrough_gauge
+			histogram: output._passthrough_histogram
+			summary:   output._passthrough_summary
+		}
+	}
+
+	how_it_works: {
+		metric_types: {
+			title: "Metric type interpretation"
+			body: """
+				Each OTLP data point is emitted as its own metric, tagged
+				with the attributes of both the resource and the data
+				point.
+
+				Gauges are emitted as absolute gauges. Monotonic sums are
+				emitted as counters, and non-monotonic sums as gauges, which
+				are incremental when the sum uses delta temporality and
+				absolute otherwise. Histograms and summaries are emitted as
+				aggregated histograms and summaries.
+				"""
+		}
+		transports: {
+			title: "Transports"
+			body: """
+				OTLP requests are accepted over gRPC on the `grpc.address`
+				and over HTTP on the `/v1/logs` and `/v1/metrics` paths of
+				the `http.address`. Only protobuf encoded HTTP requests are
+				supported.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_in_total:              components.sources.internal_metrics.output.metrics.events_in_total
+		http_error_response_total:    components.sources.internal_metrics.output.metrics.http_error_response_total
+		http_request_errors_total:    components.sources.internal_metrics.output.metrics.http_request_errors_total
+		processed_bytes_total:        components.sources.internal_metrics.output.metrics.processed_bytes_total
+		protobuf_decode_errors_total: components.sources.internal_metrics.output.metrics.protobuf_decode_errors_total
+	}
+}
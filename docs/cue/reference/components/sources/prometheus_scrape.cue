@@ -62,16 +62,149 @@ components: sources: prometheus_scrape: {
 
 	configuration: {
 		endpoints: {
-			description: "Endpoints to scrape metrics from."
-			required:    true
+			description: "Endpoints to scrape metrics from. Required unless targets are discovered with `file_sd` or `dns_sd`."
+			common:      true
+			required:    false
 			warnings: ["You must explicitly add the path to your endpoints. Vector will _not_ automatically add `/metics`."]
 			type: array: {
+				default: []
 				items: type: string: {
 					examples: ["http://localhost:9090/metrics"]
 					syntax: "literal"
 				}
 			}
 		}
+		file_sd: {
+			common:      false
+			description: "Discovers the targets to scrape from files, in the [`file_sd`](\(urls.prometheus_file_sd)) format of Prometheus."
+			required:    false
+			type: object: options: {
+				files: {
+					description: """
+						The files listing the targets. The patterns are matched using [globbing](#globbing),
+						and files are parsed as JSON or YAML depending on their extension.
+						"""
+					required: true
+					type: array: items: type: string: {
+						examples: ["/etc/vector/targets/*.json", "/etc/vector/targets.yml"]
+						syntax: "literal"
+					}
+				}
+				refresh_interval_secs: {
+					common:      false
+					description: "The interval between reads of the files, in seconds. Must be greater than zero."
+					required:    false
+					type: uint: {
+						default: 30
+						unit:    "seconds"
+					}
+				}
+			}
+		}
+		dns_sd: {
+			common:      false
+			description: "Discovers the targets to scrape from DNS records."
+			required:    false
+			type: object: options: {
+				names: {
+					description: "The names to look up."
+					required:    true
+					type: array: items: type: string: {
+						examples: ["_metrics._tcp.example.com"]
+						syntax: "literal"
+					}
+				}
+				type: {
+					common:      false
+					description: "The type of DNS records to look up."
+					required:    false
+					type: string: {
+						default: "srv"
+						enum: {
+							srv: "Look up SRV records, which provide both the host and the port of each target."
+							a:   "Look up the addresses of the names, and scrape them on `port`."
+						}
+						syntax: "literal"
+					}
+				}
+				port: {
+					common:        false
+					description:   "The port to scrape the targets on."
+					relevant_when: "type = \"a\""
+					required:      false
+					type: uint: {
+						default: null
+						examples: [9100]
+						unit: null
+					}
+				}
+				scheme: {
+					common:      false
+					description: "The scheme used to scrape the targets."
+					required:    false
+					type: string: {
+						default: "http"
+						enum: {
+							http:  "HTTP"
+							https: "HTTPS"
+						}
+						syntax: "literal"
+					}
+				}
+				path: {
+					common:      false
+					description: "The path to scrape the targets on."
+					required:    false
+					type: string: {
+						default: "/metrics"
+						syntax:  "literal"
+					}
+				}
+				refresh_interval_secs: {
+					common:      false
+					description: "The interval between lookups, in seconds. Must be greater than zero."
+					required:    false
+					type: uint: {
+						default: 30
+						unit:    "seconds"
+					}
+				}
+			}
+		}
+		honor_labels: {
+			common:      false
+			description: """
+				Controls how conflicts between the labels of the scraped metrics and the labels of the target are
+				resolved. If `true`, the labels of the scraped metrics are kept and the conflicting target labels are
+				ignored. If `false`, the conflicting labels of the scraped metrics are renamed to `exported_<label>`.
+				"""
+			required: false
+			type: bool: default: false
+		}
+		job: {
+			common:      false
+			description: "The value of the `job` tag added to the scraped metrics. Defaults to the name of the component."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["node"]
+				syntax: "literal"
+			}
+		}
+		query: {
+			common:      false
+			description: "Query parameters added to the URL of every target, such as the module of an exporter. A parameter can have several values."
+			required:    false
+			type: object: {
+				examples: [
+					{
+						"module": ["http_2xx"]
+						"target": ["https://example.com"]
+					},
+				]
+				options: {}
+			}
+		}
 		scrape_interval_secs: {
 			common:      true
 			description: "The interval between scrapes, in seconds."
@@ -93,16 +226,65 @@ components: sources: prometheus_scrape: {
 		gauge:     output._passthrough_gauge
 		histogram: output._passthrough_histogram
 		summary:   output._passthrough_summary
+
+		_target_tags: {
+			instance: {
+				description: "The host and port of the target."
+				required:    true
+				examples: ["localhost:9090"]
+			}
+			job: {
+				description: "The job of the target, set with the `job` option."
+				required:    true
+				examples: ["node"]
+			}
+		}
+		up: {
+			description: "Whether the last scrape of the target succeeded (`1`) or failed (`0`)."
+			type:        "gauge"
+			tags:        _target_tags
+		}
+		scrape_duration_seconds: {
+			description: "The duration of the last scrape of the target, in seconds."
+			type:        "gauge"
+			tags:        _target_tags
+		}
+	}
+
+	how_it_works: {
+		target_labels: {
+			title: "Target labels"
+			body: """
+				Like Prometheus, the metrics scraped from a target are tagged with its `instance`, the host
+				and port of the target, and its `job`. Targets found with `file_sd` are also tagged with the
+				labels of their group, which can override these two tags. Along with the scraped metrics, the
+				`up` and `scrape_duration_seconds` metrics describe the health of each scrape.
+				"""
+		}
+		service_discovery: {
+			title: "Service discovery"
+			body: """
+				Besides the static `endpoints`, the targets to scrape can be discovered from files with `file_sd`,
+				or from DNS records with `dns_sd`. The discovered targets are refreshed periodically, and targets
+				are added or removed without restarting the source. If a refresh fails, the previous targets are kept.
+
+				The files use the same format as Prometheus: a list of groups, each with `targets` in the
+				`host:port` form and optional `labels`, which are added as tags to the metrics scraped from
+				those targets. The `__scheme__` and `__metrics_path__` labels set the scheme and path used to
+				scrape the targets of a group, while the other labels starting with `__` are ignored.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
-		events_in_total:           components.sources.internal_metrics.output.metrics.events_in_total
-		http_error_response_total: components.sources.internal_metrics.output.metrics.http_error_response_total
-		http_request_errors_total: components.sources.internal_metrics.output.metrics.http_request_errors_total
-		parse_errors_total:        components.sources.internal_metrics.output.metrics.parse_errors_total
-		processed_bytes_total:     components.sources.internal_metrics.output.metrics.processed_bytes_total
-		processed_events_total:    components.sources.internal_metrics.output.metrics.processed_events_total
-		requests_completed_total:  components.sources.internal_metrics.output.metrics.requests_completed_total
-		request_duration_seconds:  components.sources.internal_metrics.output.metrics.request_duration_seconds
+		events_in_total:                components.sources.internal_metrics.output.metrics.events_in_total
+		http_error_response_total:      components.sources.internal_metrics.output.metrics.http_error_response_total
+		http_request_errors_total:      components.sources.internal_metrics.output.metrics.http_request_errors_total
+		parse_errors_total:             components.sources.internal_metrics.output.metrics.parse_errors_total
+		processed_bytes_total:          components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:         components.sources.internal_metrics.output.metrics.processed_events_total
+		requests_completed_total:       components.sources.internal_metrics.output.metrics.requests_completed_total
+		request_duration_seconds:       components.sources.internal_metrics.output.metrics.request_duration_seconds
+		service_discovery_errors_total: components.sources.internal_metrics.output.metrics.service_discovery_errors_total
 	}
 }
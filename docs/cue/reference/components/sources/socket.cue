@@ -71,6 +71,8 @@ components: sources: socket: {
 				syntax: "literal"
 			}
 		}
+		decoding: configuration._decoding & {_args: default_codec: "bytes"}
+		framing: configuration._framing & {_args: default_method: "newline_delimited"}
 		host_key: {
 			category:    "Context"
 			common:      false
@@ -169,5 +171,7 @@ components: sources: socket: {
 		connection_send_errors_total:     components.sources.internal_metrics.output.metrics.connection_send_errors_total
 		connection_send_ack_errors_total: components.sources.internal_metrics.output.metrics.connection_send_ack_errors_total
 		connection_shutdown_total:        components.sources.internal_metrics.output.metrics.connection_shutdown_total
+		decoder_framing_errors_total:     components.sources.internal_metrics.output.metrics.decoder_framing_errors_total
+		decoder_parse_errors_total:       components.sources.internal_metrics.output.metrics.decoder_parse_errors_total
 	}
 }
@@ -62,6 +62,7 @@ components: sources: splunk_hec: {
 	}
 
 	configuration: {
+		acknowledgements: configuration._acknowledgements
 		address: {
 			common:      true
 			description: "The address to accept connections on."
@@ -42,6 +42,17 @@ components: transforms: dedupe: {
 			warnings: []
 			type: object: {
 				options: {
+					max_age_secs: {
+						common:      false
+						description: "When set, an Event is only considered a duplicate of Events seen within this many seconds, at most 31536000 (a year). `num_events` still bounds the size of the cache; once it is full, the entry closest to expiring is evicted to make room for new Events."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: null
+							examples: [300]
+							unit: "seconds"
+						}
+					}
 					num_events: {
 						common:      true
 						description: "The number of recent Events to cache and compare new incoming Events against."
@@ -52,6 +63,36 @@ components: transforms: dedupe: {
 							unit:    null
 						}
 					}
+					persistence: {
+						common:      false
+						description: "Persists the cache to disk every minute and when the transform stops, and restores it when the transform starts, so duplicates are still detected across restarts and reloads."
+						required:    false
+						warnings: []
+						type: object: {
+							options: {
+								data_dir: {
+									common:      false
+									description: "The directory used to persist the cache. Defaults to the global `data_dir` option."
+									required:    false
+									warnings: []
+									type: string: {
+										default: null
+										examples: ["/var/lib/vector"]
+										syntax: "literal"
+									}
+								}
+								key: {
+									description: "A name identifying this cache within the data directory. Must be unique across `dedupe` transforms sharing a data directory, and must be a plain directory name without path separators."
+									required:    true
+									warnings: []
+									type: string: {
+										examples: ["my_dedupe"]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+					}
 				}
 			}
 		}
@@ -62,6 +62,13 @@ components: transforms: json_parser: {
 			warnings: []
 			type: bool: {}
 		}
+		reroute_dropped: {
+			common:      false
+			description: "If `true` events dropped by `drop_invalid` are sent to the `<component_id>.dropped` output instead, with the reason they were dropped under `metadata.dropped`."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
 		field: {
 			common:      true
 			description: "The log field to decode as JSON. Must be a `string` value type."
@@ -79,6 +79,16 @@ components: transforms: "remap": {
 				"""
 			type: bool: default: true
 		}
+		reroute_dropped: {
+			common:   false
+			required: false
+			description: """
+				Send the events dropped by `drop_on_error` or `drop_on_abort` to the `<component_id>.dropped` output
+				instead of discarding them. The events are sent in their original state, with the reason they were
+				dropped under `metadata.dropped`.
+				"""
+			type: bool: default: false
+		}
 	}
 
 	input: {
@@ -41,7 +41,8 @@ components: transforms: route: {
 		route: {
 			description: """
 				A table of route identifiers to logical conditions representing the filter of the route. Each route
-				can then be referenced as an input by other components with the name `<transform_name>.<route_id>`.
+				is a named output of the transform, and can then be referenced as an input by other components with
+				the name `<transform_name>.<route_id>`. An event is sent to every route whose condition it matches.
 				"""
 			required: true
 			warnings: []
@@ -0,0 +1,114 @@
+package metadata
+
+components: transforms: throttle: {
+	title: "Throttle"
+
+	description: """
+		Rate limits one or more log streams to limit load on downstream services, or to enforce usage quotas on users.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		exclude: {
+			common: true
+			description: """
+				The set of logical conditions to exclude events from rate limiting.
+				"""
+			required: false
+			warnings: []
+			type: string: {
+				default: null
+				examples: [
+					#".status_code != 200 && !includes(["info", "debug"], .severity)"#,
+				]
+				syntax: "remap_boolean_expression"
+			}
+		}
+		key_field: {
+			common: false
+			description: """
+				The value to group events into separate buckets to be rate limited independently. If left unspecified,
+				or if the template fails to render, events share a single bucket.
+				"""
+			required: false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["{{ message }}", "{{ hostname }}"]
+				syntax: "template"
+			}
+		}
+		threshold: {
+			description: """
+				The number of events allowed for a given bucket per configured `window_secs`.
+
+				Each unique key will have its own `threshold`.
+				"""
+			required: true
+			warnings: []
+			type: uint: {
+				examples: [100, 10000]
+				unit: null
+			}
+		}
+		window_secs: {
+			description: """
+				The time frame in which the configured `threshold` is applied.
+				"""
+			required: true
+			warnings: []
+			type: float: {
+				examples: [1.0, 60.0, 86400.0]
+				unit: "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	how_it_works: {
+		rate_limiting: {
+			title: "Rate Limiting"
+			body: """
+				The `throttle` transform spreads load across the configured `window_secs`, ensuring that each bucket's
+				throughput averages out to the `threshold` per `window_secs`. It utilizes a token bucket algorithm:
+				each bucket starts full with `threshold` tokens, every event passing through consumes one, and tokens
+				are replenished continuously so that an empty bucket is full again after `window_secs`. Events arriving
+				while their bucket is empty are dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+	}
+}
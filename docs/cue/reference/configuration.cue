@@ -7,6 +7,19 @@ configuration: {
 
 configuration: {
 	configuration: {
+		acknowledgements: {
+			common: false
+			description: """
+				Controls if the sources wait for all of their destination sinks
+				to deliver the events before acknowledging receipt, for the
+				sources that support it. Sources can override this default
+				with their own `acknowledgements` option.
+				"""
+			required: false
+			warnings: []
+			type: bool: default: false
+		}
+
 		data_dir: {
 			common: false
 			description: """
@@ -91,6 +104,110 @@ configuration: {
 			}
 		}
 
+		enrichment_tables: {
+			common: false
+			description: """
+				Tables of reference data, keyed by table name, that events can be
+				enriched from by searching them in the `remap` transform with the
+				`get_enrichment_table_record` and `find_enrichment_table_records`
+				functions. Tables are reloaded whenever the configuration is reloaded.
+				"""
+			required: false
+			warnings: []
+			type: object: {
+				examples: [
+					{
+						users: {
+							type: "file"
+							file: {
+								path: "/etc/vector/users.csv"
+								encoding: type: "csv"
+							}
+						}
+					},
+				]
+				options: {
+					"*": {
+						description: "An enrichment table."
+						required:    true
+						warnings: []
+						type: object: {
+							examples: []
+							options: {
+								type: {
+									description: "The type of the enrichment table."
+									required:    true
+									warnings: []
+									type: string: {
+										enum: file: "Loads the table from a file on disk."
+										syntax: "literal"
+									}
+								}
+								file: {
+									description: "Where and how the table is loaded from."
+									required:    true
+									warnings: []
+									type: object: {
+										examples: []
+										options: {
+											path: {
+												description: "The path of the file to load the table from."
+												required:    true
+												warnings: []
+												type: string: {
+													examples: ["/etc/vector/users.csv"]
+													syntax: "literal"
+												}
+											}
+											encoding: {
+												common:      true
+												description: "How the file is encoded."
+												required:    false
+												warnings: []
+												type: object: {
+													examples: []
+													options: {
+														type: {
+															common:      true
+															description: "The encoding of the file."
+															required:    false
+															warnings: []
+															type: string: {
+																default: "csv"
+																enum: csv: "A CSV file, where every value is loaded as a string."
+																syntax: "literal"
+															}
+														}
+														include_headers: {
+															common:      true
+															description: "Whether the first row of the file names the columns. When false, columns are named by their position, starting at `0`."
+															required:    false
+															warnings: []
+															type: bool: default: true
+														}
+														delimiter: {
+															common:      false
+															description: "The character separating the values of a row."
+															required:    false
+															warnings: []
+															type: string: {
+																default: ","
+																syntax:  "literal"
+															}
+														}
+													}
+												}
+											}
+										}
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
 		healthchecks: {
 			common: false
 			description: """
@@ -130,6 +247,72 @@ configuration: {
 			}
 		}
 
+		pipelines: {
+			common: false
+			description: """
+				Reusable chains of transforms, keyed by pipeline name. A pipeline is used by
+				a transform of type `pipeline`, which is replaced by the pipeline's transforms
+				when the configuration is loaded. See [pipelines](#pipelines) for more info.
+				"""
+			required: false
+			warnings: []
+			type: object: {
+				examples: [
+					{
+						tagging: {
+							params: env: "production"
+							transforms: {
+								service: {
+									type:   "remap"
+									source: #".service = "${params.service}""#
+								}
+								env: {
+									type:   "remap"
+									source: #".env = "${params.env}""#
+								}
+							}
+						}
+					},
+				]
+				options: {
+					"*": {
+						description: "A pipeline."
+						required:    true
+						warnings: []
+						type: object: {
+							examples: []
+							options: {
+								params: {
+									common:      false
+									description: "Default values of the pipeline's parameters."
+									required:    false
+									warnings: []
+									type: object: {
+										examples: [{env: "production"}]
+										options: {}
+									}
+								}
+								transforms: {
+									description: """
+										The transforms of the pipeline, configured like any other transform
+										but without `inputs`: they are chained in the order they are defined.
+										`${params.<name>}` in any of their string options is replaced with the
+										value of the parameter.
+										"""
+									required: true
+									warnings: []
+									type: object: {
+										examples: []
+										options: {}
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
 		timezone: {
 			common:      false
 			description: """
@@ -185,6 +368,40 @@ configuration: {
 				},
 			]
 		}
+		pipelines: {
+			title: "Pipelines"
+			body: """
+				Chains of transforms repeated across a configuration can be defined once as a
+				pipeline, and used wherever they're needed by a transform of type `pipeline`:
+
+				```toml title="vector.toml"
+				[pipelines.tagging]
+				  params.env = "production" # default value
+
+				  [pipelines.tagging.transforms.service]
+				    type = "remap"
+				    source = '.service = "${params.service}"'
+
+				  [pipelines.tagging.transforms.env]
+				    type = "remap"
+				    source = '.env = "${params.env}"'
+
+				[transforms.nginx_tagging]
+				  type = "pipeline"
+				  inputs = ["nginx"]
+				  pipeline = "tagging"
+				  params.service = "nginx"
+				```
+
+				When the configuration is loaded, `nginx_tagging` is replaced with the transforms
+				`nginx_tagging.service` and `nginx_tagging.env`. The first one takes the inputs of
+				`nginx_tagging`, and components using `nginx_tagging` as an input read from the
+				last one. Run `vector graph` to inspect the expanded pipelines.
+
+				Parameters are referenced with `${params.<name>}`, which is left alone by
+				environment variable interpolation.
+				"""
+		}
 		formats: {
 			title: "Formats"
 			body:  """
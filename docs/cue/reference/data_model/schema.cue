@@ -284,6 +284,41 @@ data_model: schema: {
 					}
 				}
 
+				sketch: {
+					common: false
+					description: """
+						A sketch is a compact, mergeable summary of a
+						set of observations (a [DDSketch](https://www.vldb.org/pvldb/vol12/p2195-masson.pdf)).
+						Unlike a summary, sketches from many hosts can
+						be merged together and any quantile can still
+						be estimated within a fixed relative error.
+						"""
+					required: false
+					warnings: []
+					type: object: {
+						examples: []
+						options: {
+							count: {
+								description: "The total number of values contained within the sketch."
+								required:    true
+								warnings: []
+								type: uint: {
+									examples: [54]
+									unit: null
+								}
+							}
+							sum: {
+								description: "The sum of all values contained within the sketch."
+								required:    true
+								warnings: []
+								type: float: {
+									examples: [1.0, 10.0, 25.0, 100.0]
+								}
+							}
+						}
+					}
+				}
+
 				summary: {
 					common: true
 					description: """
@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "109": {
+	title:       "Unexpected function closure"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#regular-expression) passes a closure to a
+		function that doesn't accept one.
+		"""
+	resolution: """
+		Remove the closure from the function call.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				upcase(.message) -> |value| { value }
+				"""#
+			diff: #"""
+				-upcase(.message) -> |value| { value }
+				+upcase(.message)
+				"""#
+		},
+	]
+}
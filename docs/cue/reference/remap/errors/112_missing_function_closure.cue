@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "112": {
+	title:       "Missing function closure"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#regular-expression) fails to pass a closure
+		to a function that requires one.
+		"""
+	resolution: """
+		Supply a closure to the function call, as documented in the function's signature.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				map_values(.)
+				"""#
+			diff: #"""
+				-map_values(.)
+				+map_values(.) -> |value| { value }
+				"""#
+		},
+	]
+}
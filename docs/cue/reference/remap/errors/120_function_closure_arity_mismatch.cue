@@ -0,0 +1,26 @@
+package metadata
+
+remap: errors: "120": {
+	title:       "Function closure arity mismatch"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#regular-expression) passes a closure that
+		defines a different number of variables than the function supplies to it.
+		"""
+	resolution: """
+		Define exactly the variables the function documents for its closure. Use a leading underscore for
+		variables you don't need.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				for_each(.) -> |value| { log(value) }
+				"""#
+			diff: #"""
+				-for_each(.) -> |value| { log(value) }
+				+for_each(.) -> |_key, value| { log(value) }
+				"""#
+		},
+	]
+}
@@ -0,0 +1,27 @@
+package metadata
+
+remap: errors: "122": {
+	title:       "Invalid function closure output"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#regular-expression) passes a closure whose
+		return type doesn't match the type the function expects from it.
+		"""
+	resolution: """
+		Ensure the last expression of the closure block returns the expected type, using the appropriate
+		[type](\(urls.vrl_functions)/#type-functions) or [coercion](\(urls.vrl_functions)/#coerce-functions)
+		function where necessary.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				filter(.) -> |_key, value| { value }
+				"""#
+			diff: #"""
+				-filter(.) -> |_key, value| { value }
+				+filter(.) -> |_key, value| { is_string(value) }
+				"""#
+		},
+	]
+}
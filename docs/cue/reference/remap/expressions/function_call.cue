@@ -15,7 +15,7 @@ remap: expressions: function_call: {
 
 	grammar: {
 		source: """
-			function ~ abort? ~ "(" ~ arguments? ~ ")"
+			function ~ abort? ~ "(" ~ arguments? ~ ")" ~ closure?
 			"""
 		definitions: {
 			function: {
@@ -95,6 +95,20 @@ remap: expressions: function_call: {
 					}
 				}
 			}
+			closure: {
+				description: """
+					The `closure` is an optional piece of code resolved by the function call. It is primarily used
+					in functions that iterate over collections. Its syntax is as follows:
+
+					```vrl
+					for_each([]) -> |index, value| { ... }
+					```
+
+					The variables between the pipes (`|`) are scoped to the closure block and hold the values the
+					function passes to it. Which variables are available, and the type the closure must return,
+					are defined by the function's documentation.
+					"""
+			}
 		}
 	}
 
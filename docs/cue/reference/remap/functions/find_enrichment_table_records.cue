@@ -0,0 +1,60 @@
+package metadata
+
+remap: functions: find_enrichment_table_records: {
+	category: "Enrichment"
+	description: """
+		Searches an [enrichment table](\(urls.vector_configuration)#enrichment_tables) for all rows
+		meeting all of the `condition`s, returning the rows as an array of objects.
+
+		The table and the fields of the condition are checked when the program is compiled, and the table
+		is indexed on the fields of the condition so that searches don't go through every row.
+		"""
+
+	arguments: [
+		{
+			name:        "table"
+			description: "The name of the enrichment table to search."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "condition"
+			description: """
+				The fields and values that the rows have to equal. The values can be any expression, while the
+				object itself has to be a literal.
+				"""
+			required: true
+			type: ["object"]
+		},
+		{
+			name:        "case_sensitive"
+			description: "Whether string values should be compared case sensitively."
+			required:    false
+			type: ["boolean"]
+			default: true
+		},
+	]
+	internal_failure_reasons: [
+		"The enrichment table isn't loaded",
+	]
+	return: types: ["array"]
+
+	examples: [
+		{
+			title: "Exact match"
+			source: #"""
+				find_enrichment_table_records!("users", { "city": .city })
+				"""#
+			return: [{"id": "1", "name": "Bob", "city": "London"}, {"id": "3", "name": "Alice", "city": "London"}]
+			skip_test: true
+		},
+		{
+			title: "Case insensitive match"
+			source: #"""
+				find_enrichment_table_records!("users", { "name": "bob" }, case_sensitive: false)
+				"""#
+			return: [{"id": "1", "name": "Bob", "city": "London"}, {"id": "2", "name": "bob", "city": "Paris"}]
+			skip_test: true
+		},
+	]
+}
@@ -0,0 +1,56 @@
+package metadata
+
+remap: functions: for_each: {
+	category: "Enumerate"
+	description: """
+		Iterate over a collection.
+
+		This function currently _does not_ support recursive iteration.
+
+		The function uses the "function closure syntax" to allow reading
+		the key/value or index/value combination for each item in the
+		collection.
+
+		The same scoping rules apply to closure blocks as they do for
+		regular blocks, meaning, any variable defined in parent scopes
+		are accessible, and mutations to those variables are preserved,
+		but any new variables instantiated in the closure block are
+		unavailable outside of the block.
+
+		Check out the examples below to learn about the closure syntax.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The array or object to iterate."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["null"]
+	}
+	examples: [
+		{
+			title: "Sum values"
+			input: log: {
+				counts: {
+					foo: 2
+					bar: 1
+				}
+			}
+			source: #"""
+				total = 0
+				for_each(object!(.counts)) -> |_key, value| {
+				    # Add the `value` to the running total.
+				    total = total + int!(value)
+				}
+
+				total
+				"""#
+			return: 3
+		},
+	]
+}
@@ -0,0 +1,62 @@
+package metadata
+
+remap: functions: get_enrichment_table_record: {
+	category: "Enrichment"
+	description: """
+		Searches an [enrichment table](\(urls.vector_configuration)#enrichment_tables) for the single row
+		meeting all of the `condition`s, returning the row as an object.
+
+		The table and the fields of the condition are checked when the program is compiled, and the table
+		is indexed on the fields of the condition so that searches don't go through every row.
+		"""
+
+	arguments: [
+		{
+			name:        "table"
+			description: "The name of the enrichment table to search."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "condition"
+			description: """
+				The fields and values that the row has to equal. The values can be any expression, while the
+				object itself has to be a literal.
+				"""
+			required: true
+			type: ["object"]
+		},
+		{
+			name:        "case_sensitive"
+			description: "Whether string values should be compared case sensitively."
+			required:    false
+			type: ["boolean"]
+			default: true
+		},
+	]
+	internal_failure_reasons: [
+		"The enrichment table isn't loaded",
+		"No row meets the conditions",
+		"More than one row meets the conditions",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Exact match"
+			source: #"""
+				get_enrichment_table_record!("users", { "id": .user_id })
+				"""#
+			return: {"id": "1", "name": "Bob", "city": "London"}
+			skip_test: true
+		},
+		{
+			title: "Case insensitive match"
+			source: #"""
+				get_enrichment_table_record!("users", { "name": "bob", "city": .city }, case_sensitive: false)
+				"""#
+			return: {"id": "1", "name": "Bob", "city": "London"}
+			skip_test: true
+		},
+	]
+}
@@ -0,0 +1,50 @@
+package metadata
+
+remap: functions: map_values: {
+	category: "Enumerate"
+	description: """
+		Map the values within a collection.
+
+		This function currently _does not_ support recursive iteration.
+
+		The function uses the "function closure syntax" to allow mutating
+		the value for each item in the collection.
+
+		The same scoping rules apply to closure blocks as they do for
+		regular blocks, meaning, any variable defined in parent scopes
+		are accessible, and mutations to those variables are preserved,
+		but any new variables instantiated in the closure block are
+		unavailable outside of the block.
+
+		Check out the examples below to learn about the closure syntax.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object or array to iterate."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["array", "object"]
+		rules: [
+			"The return type matches the `value` type.",
+		]
+	}
+	examples: [
+		{
+			title: "Upcase values"
+			input: log: {
+				foo: "foo"
+				bar: "bar"
+			}
+			source: #"""
+				map_values(.) -> |value| { upcase!(value) }
+				"""#
+			return: {"foo": "FOO", "bar": "BAR"}
+		},
+	]
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: opentelemetry: {
+	name:     "OpenTelemetry"
+	thing:    "an \(name) instrumented application or collector"
+	url:      urls.opentelemetry
+	versions: null
+
+	description: "[OpenTelemetry](\(urls.opentelemetry)) is a collection of tools, APIs, and SDKs used to instrument, generate, collect, and export telemetry data such as logs, metrics, and traces."
+}
@@ -335,6 +335,8 @@ urls: {
 	nix:                                                      "https://nixos.org/nix/"
 	nixos:                                                    "https://nixos.org/"
 	nixpkgs_9682:                                             "\(github)/NixOS/nixpkgs/issues/9682"
+	opentelemetry:                                            "https://opentelemetry.io/"
+	opentelemetry_protocol:                                   "https://opentelemetry.io/docs/reference/specification/protocol/otlp/"
 	openssl:                                                  "https://www.openssl.org/"
 	order_of_ops:                                             "\(wikipedia)/wiki/Order_of_operations"
 	papertrail:                                               "https://www.papertrail.com/"
@@ -356,6 +358,7 @@ urls: {
 	prometheus:                                               "https://prometheus.io/"
 	prometheus_client:                                        "https://prometheus.io/docs/instrumenting/clientlibs/"
 	prometheus_counter:                                       "https://prometheus.io/docs/concepts/metric_types/#counter"
+	prometheus_file_sd:                                       "https://prometheus.io/docs/prometheus/latest/configuration/configuration/#file_sd_config"
 	prometheus_gauge:                                         "https://prometheus.io/docs/concepts/metric_types/#gauge"
 	prometheus_high_cardinality:                              "https://prometheus.io/docs/practices/naming/#labels"
 	prometheus_histogram:                                     "https://prometheus.io/docs/concepts/metric_types/#histogram"
@@ -415,6 +418,7 @@ urls: {
 	signal:                                                   "\(wikipedia)/wiki/Signal_(IPC)"
 	snake_case:                                               "\(wikipedia)/wiki/Snake_case"
 	snappy:                                                   "https://google.github.io/snappy/"
+	snappy_framing:                                           "https://github.com/google/snappy/blob/main/framing_format.txt"
 	socket:                                                   "\(wikipedia)/wiki/Network_socket"
 	splunk:                                                   "https://www.splunk.com"
 	splunk_hec:                                               "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"
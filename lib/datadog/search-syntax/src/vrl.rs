@@ -147,6 +147,7 @@ fn make_function_call<T: IntoIterator<Item = ast::Expr>>(tag: &str, arguments: T
                 })
             })
             .collect(),
+        closure: None,
     }))
 }
 
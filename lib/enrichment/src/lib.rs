@@ -0,0 +1,70 @@
+//! Enrichment tables hold reference data loaded from outside of Vector, which
+//! events can be joined against from within VRL.
+
+pub mod tables;
+
+use std::collections::BTreeMap;
+use std::fmt;
+pub use tables::{TableRegistry, TableSearch};
+use vrl::Value;
+
+/// How string values are compared when searching a table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Case {
+    Sensitive,
+    Insensitive,
+}
+
+/// A condition the rows returned by a search have to meet.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Condition<'a> {
+    /// The value of `field` in the row equals `value`.
+    Equals { field: &'a str, value: Value },
+}
+
+/// Refers to an index added to a table with [`Table::add_index`].
+///
+/// The handle identifies the index by what it indexes, so that it remains
+/// valid for a reloaded table that has been given the same index.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IndexHandle {
+    pub case: Case,
+    pub fields: Vec<String>,
+}
+
+pub trait Table: fmt::Debug + Send + Sync {
+    /// Search the table for the single row meeting all of the `condition`s.
+    ///
+    /// # Errors
+    ///
+    /// Errors if no row, or more than one row, meets the conditions.
+    fn find_table_row(
+        &self,
+        case: Case,
+        condition: &[Condition],
+        index: Option<&IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String>;
+
+    /// Search the table for all rows meeting all of the `condition`s.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the search itself fails, finding no rows isn't an error.
+    fn find_table_rows(
+        &self,
+        case: Case,
+        condition: &[Condition],
+        index: Option<&IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String>;
+
+    /// Index the table on the given `fields`, so that searches with
+    /// conditions on these fields don't have to go through every row.
+    ///
+    /// # Errors
+    ///
+    /// Errors if any of the fields isn't a column of the table.
+    fn add_index(&mut self, case: Case, fields: &[&str]) -> Result<IndexHandle, String>;
+
+    /// The indexes that have been added to the table.
+    fn indexes(&self) -> Vec<IndexHandle>;
+}
@@ -0,0 +1,274 @@
+//! Sharing of the loaded enrichment tables between the VRL programs searching
+//! them.
+//!
+//! Loading happens in two phases. Tables are first handed to the registry with
+//! [`TableRegistry::load`], while VRL programs are compiled and add the indexes
+//! they need. Once compilation is done, [`TableRegistry::finish_load`] makes the
+//! tables available to the [`TableSearch`]es held by the compiled programs,
+//! replacing any tables loaded before.
+use crate::{Case, Condition, IndexHandle, Table};
+use arc_swap::ArcSwap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use vrl::Value;
+
+pub type TableMap = HashMap<String, Box<dyn Table>>;
+
+#[derive(Clone, Default)]
+pub struct TableRegistry {
+    loading: Arc<Mutex<Option<TableMap>>>,
+    tables: Arc<ArcSwap<Option<TableMap>>>,
+}
+
+impl TableRegistry {
+    /// Start loading the given tables.
+    ///
+    /// Tables replacing ones of the same name are given the indexes of the
+    /// replaced tables, so that programs compiled against those keep finding
+    /// their indexes.
+    pub fn load(&self, mut tables: TableMap) {
+        if let Some(current) = &**self.tables.load() {
+            for (name, table) in &mut tables {
+                if let Some(replaced) = current.get(name) {
+                    for index in replaced.indexes() {
+                        let fields = index.fields.iter().map(String::as_str).collect::<Vec<_>>();
+                        // Searches fall back to going through every row if
+                        // the index can't be added to the new table.
+                        let _ = table.add_index(index.case, &fields);
+                    }
+                }
+            }
+        }
+
+        *self.loading.lock().unwrap() = Some(tables);
+    }
+
+    /// Make the tables being loaded available to searches.
+    pub fn finish_load(&self) {
+        if let Some(tables) = self.loading.lock().unwrap().take() {
+            self.tables.store(Arc::new(Some(tables)));
+        }
+    }
+
+    /// Names of the tables being loaded.
+    pub fn table_ids(&self) -> Vec<String> {
+        let mut ids = self
+            .loading
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|tables| tables.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        ids.sort();
+        ids
+    }
+
+    /// Add an index to one of the tables being loaded.
+    ///
+    /// # Errors
+    ///
+    /// Errors if there is no table of that name being loaded, or if the table
+    /// can't be indexed on the given fields.
+    pub fn add_index(
+        &self,
+        table: &str,
+        case: Case,
+        fields: &[&str],
+    ) -> Result<IndexHandle, String> {
+        match self.loading.lock().unwrap().as_mut() {
+            Some(tables) => match tables.get_mut(table) {
+                Some(table) => table.add_index(case, fields),
+                None => Err(format!("enrichment table \"{}\" does not exist", table)),
+            },
+            None => Err("enrichment tables are not being loaded".to_string()),
+        }
+    }
+
+    /// A handle for searching the tables once they have been loaded.
+    pub fn as_readonly(&self) -> TableSearch {
+        TableSearch(Arc::clone(&self.tables))
+    }
+}
+
+impl fmt::Debug for TableRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TableRegistry")
+            .field("loading", &self.table_ids())
+            .finish()
+    }
+}
+
+/// Searches the tables of a [`TableRegistry`], always using the tables loaded
+/// most recently.
+#[derive(Clone, Default)]
+pub struct TableSearch(Arc<ArcSwap<Option<TableMap>>>);
+
+impl TableSearch {
+    /// Search `table` for the single row meeting all of the `condition`s.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the table hasn't been loaded, or if no row, or more than one
+    /// row, meets the conditions.
+    pub fn find_table_row(
+        &self,
+        table: &str,
+        case: Case,
+        condition: &[Condition],
+        index: Option<&IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String> {
+        match &**self.0.load() {
+            Some(tables) => match tables.get(table) {
+                Some(table) => table.find_table_row(case, condition, index),
+                None => Err(format!("enrichment table \"{}\" is not loaded", table)),
+            },
+            None => Err("enrichment tables are not loaded".to_string()),
+        }
+    }
+
+    /// Search `table` for all rows meeting all of the `condition`s.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the table hasn't been loaded.
+    pub fn find_table_rows(
+        &self,
+        table: &str,
+        case: Case,
+        condition: &[Condition],
+        index: Option<&IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        match &**self.0.load() {
+            Some(tables) => match tables.get(table) {
+                Some(table) => table.find_table_rows(case, condition, index),
+                None => Err(format!("enrichment table \"{}\" is not loaded", table)),
+            },
+            None => Err("enrichment tables are not loaded".to_string()),
+        }
+    }
+}
+
+impl fmt::Debug for TableSearch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tables = self
+            .0
+            .load()
+            .as_ref()
+            .as_ref()
+            .map(|tables| tables.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        tables.sort();
+
+        f.debug_struct("TableSearch")
+            .field("tables", &tables)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A table of a single row, counting the searches that used an index.
+    #[derive(Debug, Default)]
+    struct SingleRow {
+        indexes: Vec<IndexHandle>,
+        indexed_searches: Arc<Mutex<usize>>,
+    }
+
+    impl Table for SingleRow {
+        fn find_table_row(
+            &self,
+            _case: Case,
+            _condition: &[Condition],
+            index: Option<&IndexHandle>,
+        ) -> Result<BTreeMap<String, Value>, String> {
+            if index.map_or(false, |index| self.indexes.contains(index)) {
+                *self.indexed_searches.lock().unwrap() += 1;
+            }
+            let mut row = BTreeMap::new();
+            row.insert("field".to_string(), Value::from("value"));
+            Ok(row)
+        }
+
+        fn find_table_rows(
+            &self,
+            case: Case,
+            condition: &[Condition],
+            index: Option<&IndexHandle>,
+        ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+            self.find_table_row(case, condition, index)
+                .map(|row| vec![row])
+        }
+
+        fn add_index(&mut self, case: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+            let index = IndexHandle {
+                case,
+                fields: fields.iter().map(ToString::to_string).collect(),
+            };
+            self.indexes.push(index.clone());
+            Ok(index)
+        }
+
+        fn indexes(&self) -> Vec<IndexHandle> {
+            self.indexes.clone()
+        }
+    }
+
+    fn tables(table: SingleRow) -> TableMap {
+        let mut tables = TableMap::new();
+        tables.insert("table".to_string(), Box::new(table) as _);
+        tables
+    }
+
+    #[test]
+    fn searches_only_once_loaded() {
+        let registry = TableRegistry::default();
+        let search = registry.as_readonly();
+
+        registry.load(tables(SingleRow::default()));
+        assert_eq!(registry.table_ids(), vec!["table".to_string()]);
+        assert_eq!(
+            search.find_table_row("table", Case::Sensitive, &[], None),
+            Err("enrichment tables are not loaded".to_string())
+        );
+
+        registry.finish_load();
+        assert!(registry.table_ids().is_empty());
+        assert!(search
+            .find_table_row("table", Case::Sensitive, &[], None)
+            .is_ok());
+        assert_eq!(
+            search.find_table_rows("missing", Case::Sensitive, &[], None),
+            Err("enrichment table \"missing\" is not loaded".to_string())
+        );
+    }
+
+    #[test]
+    fn reloaded_tables_keep_indexes() {
+        let registry = TableRegistry::default();
+        let search = registry.as_readonly();
+
+        registry.load(tables(SingleRow::default()));
+        let index = registry
+            .add_index("table", Case::Insensitive, &["field"])
+            .unwrap();
+        assert!(registry
+            .add_index("missing", Case::Insensitive, &["field"])
+            .is_err());
+        registry.finish_load();
+
+        let indexed_searches = Arc::new(Mutex::new(0));
+        registry.load(tables(SingleRow {
+            indexes: Vec::new(),
+            indexed_searches: Arc::clone(&indexed_searches),
+        }));
+        registry.finish_load();
+
+        search
+            .find_table_row("table", Case::Insensitive, &[], Some(&index))
+            .unwrap();
+        assert_eq!(*indexed_searches.lock().unwrap(), 1);
+    }
+}
@@ -1,11 +1,20 @@
 use futures::task::AtomicWaker;
 use metrics::counter;
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Clone)]
 pub enum Acker {
     Disk(Arc<AtomicUsize>, Arc<AtomicWaker>),
+    /// Acker of an overflow buffer, passing acks on to the stage each event
+    /// was read from. `origins` holds, in read order, whether an event came
+    /// from the `overflow` stage rather than the `primary` one.
+    Overflow {
+        origins: Arc<Mutex<VecDeque<bool>>>,
+        primary: Box<Acker>,
+        overflow: Box<Acker>,
+    },
     Null,
 }
 
@@ -19,13 +28,7 @@ impl Acker {
     pub fn ack(&self, num: usize) {
         // Only ack items if the amount to ack is larger than zero.
         if num > 0 {
-            match self {
-                Acker::Null => {}
-                Acker::Disk(counter, notifier) => {
-                    counter.fetch_add(num, Ordering::Relaxed);
-                    notifier.wake();
-                }
-            }
+            self.ack_stages(num);
 
             // WARN this string "events_out_total" is a duplicate of the metric
             // name in `ROOT/src/internal_events/topology.rs`. `Acker` had a
@@ -39,6 +42,36 @@ impl Acker {
         }
     }
 
+    fn ack_stages(&self, num: usize) {
+        match self {
+            Acker::Null => {}
+            Acker::Disk(counter, notifier) => {
+                counter.fetch_add(num, Ordering::Relaxed);
+                notifier.wake();
+            }
+            Acker::Overflow {
+                origins,
+                primary,
+                overflow,
+            } => {
+                let overflowed = {
+                    let mut origins = origins.lock().unwrap();
+                    assert!(num <= origins.len(), "Tried to ack beyond read position");
+                    origins
+                        .drain(..num)
+                        .filter(|&overflowed| overflowed)
+                        .count()
+                };
+                if num > overflowed {
+                    primary.ack_stages(num - overflowed);
+                }
+                if overflowed > 0 {
+                    overflow.ack_stages(overflowed);
+                }
+            }
+        }
+    }
+
     #[must_use]
     pub fn new_for_testing() -> (Self, Arc<AtomicUsize>) {
         let ack_counter = Arc::new(AtomicUsize::new(0));
@@ -11,7 +11,7 @@ use std::{
     task::{Context, Poll},
 };
 
-pub mod leveldb_buffer;
+pub mod segmented_buffer;
 
 #[derive(Debug, Snafu)]
 pub enum DataDirError {
@@ -26,9 +26,22 @@ pub enum DataDirError {
     },
     #[snafu(display("Unable to open data_dir {:?}: {:?}", data_dir, source))]
     Open {
+        data_dir: PathBuf,
+        source: std::io::Error,
+    },
+    #[cfg(feature = "disk-buffer-leveldb-migration")]
+    #[snafu(display(
+        "Unable to migrate the disk buffer written by a previous version of Vector in data_dir {:?}: {:?}",
+        data_dir,
+        source
+    ))]
+    Migrate {
         data_dir: PathBuf,
         source: leveldb::database::error::Error,
     },
+    #[cfg(not(feature = "disk-buffer-leveldb-migration"))]
+    #[snafu(display("Found a disk buffer written by a previous version of Vector in data_dir {:?}, which this build can't migrate. Use a build with the `disk-buffer-leveldb-migration` feature to migrate it", data_dir))]
+    Unmigrated { data_dir: PathBuf },
 }
 
 #[pin_project]
@@ -40,7 +53,7 @@ where
     <T as DecodeBytes<T>>::Error: Debug,
 {
     #[pin]
-    inner: leveldb_buffer::Writer<T>,
+    inner: segmented_buffer::Writer<T>,
 }
 
 impl<T> Sink<T> for Writer<T>
@@ -67,7 +80,7 @@ where
     }
 }
 
-/// Open a [`leveldb_buffer::Buffer`]
+/// Open a [`segmented_buffer::Buffer`]
 ///
 /// # Errors
 ///
@@ -116,6 +129,6 @@ where
             }
         })?;
 
-    let (writer, reader, acker) = leveldb_buffer::Buffer::build(&path, max_size)?;
+    let (writer, reader, acker) = segmented_buffer::Buffer::build(&path, max_size)?;
     Ok((Writer { inner: writer }, Box::new(reader), acker))
 }
@@ -0,0 +1,50 @@
+//! The ledger records the position after the last acknowledged record, so
+//! that acknowledged records aren't delivered again after a restart.
+use super::segment::Position;
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+const LEDGER: &str = "ledger.dat";
+const LEDGER_TMP: &str = "ledger.dat.tmp";
+
+/// Read the ledger in `dir`, if there is a readable one.
+pub(crate) fn read(dir: &Path) -> io::Result<Option<Position>> {
+    let bytes = match fs::read(dir.join(LEDGER)) {
+        Ok(bytes) => bytes,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error),
+    };
+
+    if bytes.len() == 16 {
+        let mut segment = [0; 8];
+        let mut offset = [0; 8];
+        segment.copy_from_slice(&bytes[..8]);
+        offset.copy_from_slice(&bytes[8..]);
+        Ok(Some(Position::new(
+            u64::from_be_bytes(segment),
+            u64::from_be_bytes(offset),
+        )))
+    } else {
+        warn!(
+            message = "Disk buffer ledger is malformed, reading from the oldest segment.",
+            ledger = ?dir.join(LEDGER)
+        );
+        Ok(None)
+    }
+}
+
+/// Replace the ledger in `dir`.
+///
+/// The new ledger is written next to the old one and then renamed over it, so
+/// a crash leaves either of the two behind rather than a partial write. Being
+/// a little behind only means some acked records are delivered again.
+pub(crate) fn write(dir: &Path, position: Position) -> io::Result<()> {
+    let tmp = dir.join(LEDGER_TMP);
+    let mut file = fs::File::create(&tmp)?;
+    file.write_all(&position.segment.to_be_bytes())?;
+    file.write_all(&position.offset.to_be_bytes())?;
+    fs::rename(tmp, dir.join(LEDGER))
+}
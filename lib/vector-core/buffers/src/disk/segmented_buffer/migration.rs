@@ -0,0 +1,74 @@
+//! Migration of the LevelDB based disk buffers written by previous versions of
+//! Vector.
+//!
+//! The LevelDB buffer lived in the same directory as the segments do now, with
+//! every unacked record stored under its offset as a big endian key.
+use super::{segment::encode_record, writer::SegmentWriter};
+use crate::disk::{DataDirError, Migrate, Open};
+use leveldb::database::{
+    iterator::Iterable,
+    management,
+    options::{Options, ReadOptions},
+    Database,
+};
+use snafu::ResultExt;
+use std::{convert::TryInto, mem, path::Path};
+
+/// Key of a record in the LevelDB buffer.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Key(pub(crate) usize);
+
+impl db_key::Key for Key {
+    fn from_u8(key: &[u8]) -> Self {
+        let bytes: [u8; mem::size_of::<usize>()] =
+            key.try_into().expect("Key should be the right size");
+
+        Self(usize::from_be_bytes(bytes))
+    }
+
+    fn as_slice<T, F: Fn(&[u8]) -> T>(&self, f: F) -> T {
+        let bytes = self.0.to_be_bytes();
+        f(&bytes)
+    }
+}
+
+/// Move the records of the LevelDB buffer in `dir` to `segment_writer`, in
+/// order, and delete the LevelDB buffer, returning the size of the appended
+/// records.
+///
+/// The LevelDB buffer is only deleted once its records are flushed to the
+/// segments, so a crash in between delivers them twice rather than losing them.
+/// Records that were acked but not yet deleted from LevelDB are delivered
+/// again, just like they would have been by the LevelDB buffer itself.
+pub(crate) fn migrate(
+    dir: &Path,
+    segment_writer: &mut SegmentWriter,
+) -> Result<usize, DataDirError> {
+    let data_dir = dir.parent().unwrap_or(dir);
+
+    let mut size = 0;
+    let mut count = 0;
+    {
+        let db: Database<Key> =
+            Database::open(dir, Options::new()).with_context(|| Migrate { data_dir })?;
+        for value in db.value_iter(ReadOptions::new()) {
+            let record = encode_record(&value);
+            segment_writer
+                .append(&record)
+                .with_context(|| Open { data_dir })?;
+            size += record.len();
+            count += 1;
+        }
+        segment_writer.flush().with_context(|| Open { data_dir })?;
+    }
+
+    management::destroy(dir, Options::new()).with_context(|| Migrate { data_dir })?;
+
+    info!(
+        message = "Migrated disk buffer written by a previous version of Vector.",
+        ?dir,
+        count
+    );
+
+    Ok(size)
+}
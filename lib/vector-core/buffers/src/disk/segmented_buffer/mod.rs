@@ -0,0 +1,180 @@
+mod ledger;
+#[cfg(feature = "disk-buffer-leveldb-migration")]
+pub(crate) mod migration;
+mod reader;
+mod segment;
+mod writer;
+
+use super::{DataDirError, Open};
+use crate::bytes::{DecodeBytes, EncodeBytes};
+use crate::Acker;
+use futures::task::AtomicWaker;
+use metrics::gauge;
+use reader::Reader;
+use segment::{list_segments, segment_path, Position};
+use snafu::ResultExt;
+use std::fmt::Debug;
+use std::{
+    collections::VecDeque,
+    fs,
+    marker::PhantomData,
+    path::Path,
+    sync::{atomic::AtomicUsize, Arc, Mutex},
+};
+use writer::SegmentWriter;
+pub use writer::Writer;
+
+/// Which part of the buffer's size is used for a single segment.
+const MAX_SEGMENT_DENOMINATOR: usize = 10;
+
+/// Upper limit on the size of a segment, so that fully acked records are
+/// deleted in a timely manner even with large buffers.
+const MAX_SEGMENT_SIZE: usize = 128 * 1024 * 1024;
+
+#[derive(Default)]
+pub struct Buffer<T> {
+    phantom: PhantomData<T>,
+}
+
+/// Whether there is a LevelDB buffer written by a previous version of Vector
+/// in `dir`.
+fn has_leveldb_buffer(dir: &Path) -> bool {
+    dir.join("CURRENT").exists()
+}
+
+/// Report the size of the unacked records in the buffer.
+#[allow(clippy::cast_precision_loss)]
+fn emit_byte_size(size: usize) {
+    gauge!("buffer_byte_size", size as f64);
+}
+
+impl<T> Buffer<T>
+where
+    T: Send + Sync + Unpin + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    /// Build a new segmented disk buffer rooted at `path`
+    ///
+    /// Reading resumes after the last acked record of a previous buffer at
+    /// `path`, if there is one. A LevelDB buffer written by a previous version
+    /// of Vector at `path` is migrated to the segments when the
+    /// `disk-buffer-leveldb-migration` feature is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Function will fail if the permissions of `path` are not correct, if
+    /// there is no space available on disk, if there is a LevelDB buffer at
+    /// `path` that this build can't migrate etc.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn build(
+        path: &Path,
+        max_size: usize,
+    ) -> Result<(Writer<T>, Reader<T>, Acker), DataDirError> {
+        // Records are only deleted a segment at a time, so a segment's worth
+        // of acked records may still be on disk. The rest of `max_size` is
+        // used for storing the unacked records.
+        let max_segment_size = (max_size / MAX_SEGMENT_DENOMINATOR).clamp(1, MAX_SEGMENT_SIZE);
+        let max_size = max_size.saturating_sub(max_segment_size);
+
+        let data_dir = path.parent().unwrap_or(path);
+        fs::create_dir_all(path).with_context(|| Open { data_dir })?;
+
+        // Starting without its records would silently drop them.
+        #[cfg(not(feature = "disk-buffer-leveldb-migration"))]
+        if has_leveldb_buffer(path) {
+            return Err(DataDirError::Unmigrated {
+                data_dir: data_dir.to_path_buf(),
+            });
+        }
+
+        let segments = list_segments(path).with_context(|| Open { data_dir })?;
+        let start = ledger::read(path)
+            .with_context(|| Open { data_dir })?
+            .unwrap_or_else(|| Position::new(segments.first().copied().unwrap_or(0), 0));
+
+        let mut initial_size = 0;
+        for &segment in &segments {
+            let segment_path = segment_path(path, segment);
+            if segment < start.segment {
+                // Left behind by a crash between writing the ledger and
+                // deleting the segments it made obsolete.
+                fs::remove_file(segment_path).with_context(|| Open { data_dir })?;
+            } else {
+                let len = fs::metadata(segment_path)
+                    .with_context(|| Open { data_dir })?
+                    .len();
+                initial_size += if segment == start.segment {
+                    len.saturating_sub(start.offset)
+                } else {
+                    len
+                };
+            }
+        }
+
+        // Writers always start a new segment, leaving any truncated record at
+        // the end of the previous one to the reader.
+        let next_segment = segments
+            .last()
+            .map_or(start.segment, |&last| last.max(start.segment))
+            + 1;
+        let committed = Arc::new(Mutex::new(Position::new(next_segment, 0)));
+        let segment_writer = SegmentWriter::open(
+            path.to_path_buf(),
+            next_segment,
+            max_segment_size as u64,
+            Arc::clone(&committed),
+        )
+        .with_context(|| Open { data_dir })?;
+        let segment_writer = Arc::new(Mutex::new(segment_writer));
+
+        // The records of a LevelDB buffer are read after those already in the
+        // segments, and before any new ones.
+        let initial_size = initial_size as usize;
+        #[cfg(feature = "disk-buffer-leveldb-migration")]
+        let initial_size = if has_leveldb_buffer(path) {
+            initial_size + migration::migrate(path, &mut segment_writer.lock().unwrap())?
+        } else {
+            initial_size
+        };
+        let current_size = Arc::new(AtomicUsize::new(initial_size));
+        emit_byte_size(initial_size);
+
+        let write_notifier = Arc::new(AtomicWaker::new());
+
+        let blocked_write_tasks = Arc::new(Mutex::new(Vec::new()));
+
+        let ack_counter = Arc::new(AtomicUsize::new(0));
+        let acker = Acker::Disk(Arc::clone(&ack_counter), Arc::clone(&write_notifier));
+
+        let writer = Writer {
+            segment: Some(Arc::clone(&segment_writer)),
+            write_notifier: Arc::clone(&write_notifier),
+            blocked_write_tasks: Arc::clone(&blocked_write_tasks),
+            batch_size: 0,
+            max_size,
+            current_size: Arc::clone(&current_size),
+            slot: None,
+        };
+
+        let reader = Reader {
+            dir: path.to_path_buf(),
+            segment_writer,
+            committed,
+            file: None,
+            segment_len: None,
+            read_position: start,
+            acked_position: start,
+            oldest_segment: start.segment,
+            acked: 0,
+            write_notifier,
+            blocked_write_tasks,
+            current_size,
+            ack_counter,
+            unacked: VecDeque::new(),
+            phantom: PhantomData,
+        };
+
+        Ok((writer, reader, acker))
+    }
+}
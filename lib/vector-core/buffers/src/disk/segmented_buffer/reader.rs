@@ -0,0 +1,293 @@
+use super::{
+    emit_byte_size, ledger,
+    segment::{self, segment_path, Position, Record},
+    writer::SegmentWriter,
+};
+use crate::bytes::DecodeBytes;
+use bytes::Bytes;
+use futures::{task::AtomicWaker, Stream};
+use metrics::counter;
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Seek, SeekFrom};
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+use std::task::{Context, Poll, Waker};
+
+/// The reader side of N to 1 channel through the segments.
+///
+/// Records move through several stages.
+/// Unread -> Read -> Acked -> Deleted
+///
+/// Records are deleted a segment at a time, once every record in the segment
+/// has been acked. So the buffer is separated into following regions.
+/// |--Deleted--|--Acked--|--Read--|--Unread--|
+///              ^         ^        ^          ^
+///              |  `acked_position`|          |
+///      `oldest_segment`   `read_position`    |
+///                                       `committed`
+pub struct Reader<T>
+where
+    T: Send + Sync + Unpin,
+{
+    /// Directory holding the segments.
+    pub(crate) dir: PathBuf,
+    /// The newest segment, only held to find out whether any Writers remain.
+    /// Shared with Writers.
+    pub(crate) segment_writer: Arc<Mutex<SegmentWriter>>,
+    /// Position up to which Writers have flushed records.
+    /// Shared with Writers.
+    pub(crate) committed: Arc<Mutex<Position>>,
+    /// The segment being read.
+    pub(crate) file: Option<BufReader<File>>,
+    /// Length of the segment being read, once Writers have moved past it.
+    pub(crate) segment_len: Option<u64>,
+    /// Position of the first unread record.
+    pub(crate) read_position: Position,
+    /// Position after the last acked record.
+    pub(crate) acked_position: Position,
+    /// Oldest segment that hasn't been deleted.
+    pub(crate) oldest_segment: u64,
+    /// Number of acked events since the ledger was last written. Used for
+    /// batching ledger writes and deletes.
+    pub(crate) acked: usize,
+    /// Reader is notified by Writers through this Waker.
+    /// Shared with Writers.
+    pub(crate) write_notifier: Arc<AtomicWaker>,
+    /// Writers blocked by disk being full.
+    /// Shared with Writers.
+    pub(crate) blocked_write_tasks: Arc<Mutex<Vec<Waker>>>,
+    /// Size of unacked records in bytes.
+    /// Shared with Writers.
+    pub(crate) current_size: Arc<AtomicUsize>,
+    /// Number of oldest read, not acked, events that have been acked by the consumer.
+    /// Shared with consumer.
+    pub(crate) ack_counter: Arc<AtomicUsize>,
+    /// Positions after, and sizes in bytes of, read but not acked records.
+    pub(crate) unacked: VecDeque<(Position, usize)>,
+    pub(crate) phantom: PhantomData<T>,
+}
+
+impl<T> Stream for Reader<T>
+where
+    T: Send + Sync + Unpin + DecodeBytes<T>,
+    <T as DecodeBytes<T>>::Error: Display,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        // If there's no record at read_position, we return NotReady and rely
+        // on Writer using write_notifier to wake this task up after the next
+        // flush.
+        this.write_notifier.register(cx.waker());
+
+        this.delete_acked();
+
+        if this.acked >= 100 {
+            this.flush();
+        }
+
+        // Checked ahead of reading, as the last Writer flushes its records
+        // right before going away.
+        let writers_gone = Arc::strong_count(&this.segment_writer) == 1;
+
+        loop {
+            // This will usually complete instantly, as the segment has been
+            // recently written and is still in the page cache, but with a
+            // large backlog (or a fresh launch of the app) this will have to
+            // go to disk.
+            match tokio::task::block_in_place(|| this.read_record()) {
+                Ok(Some(payload)) => match T::decode(payload) {
+                    Ok(event) => return Poll::Ready(Some(event)),
+                    Err(error) => {
+                        error!(message = "Error deserializing event.", %error);
+                        debug_assert!(false);
+                        let (position, size) = this.unacked.pop_back().expect("just read");
+                        this.discard(position, size);
+                    }
+                },
+                Ok(None) => break,
+                Err(error) => {
+                    error!(message = "Error reading disk buffer.", %error);
+                    break;
+                }
+            }
+        }
+
+        if writers_gone {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<T> Drop for Reader<T>
+where
+    T: Send + Sync + Unpin,
+{
+    fn drop(&mut self) {
+        self.delete_acked();
+        self.flush();
+    }
+}
+
+impl<T> Reader<T>
+where
+    T: Send + Sync + Unpin,
+{
+    /// Read the next valid record, skipping over any that are corrupted.
+    #[allow(clippy::cast_possible_truncation)]
+    fn read_record(&mut self) -> io::Result<Option<Bytes>> {
+        loop {
+            let committed = *self.committed.lock().unwrap();
+            let complete = self.read_position.segment < committed.segment;
+
+            if self.file.is_none() {
+                match File::open(segment_path(&self.dir, self.read_position.segment)) {
+                    Ok(mut file) => {
+                        file.seek(SeekFrom::Start(self.read_position.offset))?;
+                        self.file = Some(BufReader::new(file));
+                    }
+                    Err(error) if error.kind() == io::ErrorKind::NotFound && complete => {
+                        self.next_segment();
+                        continue;
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+            let file = self.file.as_mut().expect("just opened");
+
+            let limit = if complete {
+                if let Some(len) = self.segment_len {
+                    len
+                } else {
+                    let len = file.get_ref().metadata()?.len();
+                    self.segment_len = Some(len);
+                    len
+                }
+            } else {
+                committed.offset
+            };
+            let remaining = limit.saturating_sub(self.read_position.offset);
+
+            let (record, size) = segment::read_record(file, remaining)?;
+            let position =
+                Position::new(self.read_position.segment, self.read_position.offset + size);
+            match record {
+                Record::Valid(payload) => {
+                    self.read_position = position;
+                    self.unacked.push_back((position, size as usize));
+                    return Ok(Some(payload));
+                }
+                Record::Corrupted => {
+                    error!(
+                        message = "Skipping corrupted record in disk buffer.",
+                        segment = ?segment_path(&self.dir, position.segment),
+                        internal_log_rate_secs = 10
+                    );
+                    counter!("buffer_corrupted_records_total", 1);
+                    // Resume at the next record found after a corrupted header.
+                    file.seek(SeekFrom::Start(position.offset))?;
+                    self.discard(position, size as usize);
+                }
+                Record::Truncated => {
+                    error!(
+                        message = "Skipping truncated record in disk buffer.",
+                        segment = ?segment_path(&self.dir, position.segment),
+                        internal_log_rate_secs = 10
+                    );
+                    counter!("buffer_corrupted_records_total", 1);
+                    file.seek(SeekFrom::Start(position.offset))?;
+                    self.discard(position, size as usize);
+                }
+                Record::End if complete => self.next_segment(),
+                Record::End => return Ok(None),
+            }
+        }
+    }
+
+    fn next_segment(&mut self) {
+        self.file = None;
+        self.segment_len = None;
+        self.read_position = Position::new(self.read_position.segment + 1, 0);
+    }
+
+    /// Skip a record that won't be handed to the consumer, and so won't be
+    /// acked either.
+    fn discard(&mut self, position: Position, size: usize) {
+        self.read_position = position;
+        if let Some(last) = self.unacked.back_mut() {
+            // Acked along with the preceding record.
+            last.0 = position;
+            last.1 += size;
+        } else {
+            let unacked_size = self.current_size.fetch_sub(size, Ordering::Release) - size;
+            emit_byte_size(unacked_size);
+            self.acked_position = position;
+        }
+    }
+
+    fn delete_acked(&mut self) {
+        let num_to_delete = self.ack_counter.swap(0, Ordering::Relaxed);
+
+        if num_to_delete > 0 {
+            assert!(
+                num_to_delete <= self.unacked.len(),
+                "Tried to ack beyond read position"
+            );
+
+            let mut size_deleted = 0;
+            for (position, size) in self.unacked.drain(..num_to_delete) {
+                self.acked_position = position;
+                size_deleted += size;
+            }
+            let unacked_size =
+                self.current_size.fetch_sub(size_deleted, Ordering::Release) - size_deleted;
+            emit_byte_size(unacked_size);
+
+            self.acked += num_to_delete;
+        }
+
+        if self.unacked.is_empty() {
+            // Also covers moving on to a new segment after the last acked
+            // record.
+            self.acked_position = self.read_position;
+        }
+
+        for task in self.blocked_write_tasks.lock().unwrap().drain(..) {
+            task.wake();
+        }
+    }
+
+    /// Record the acked position and delete the segments before it.
+    fn flush(&mut self) {
+        if let Err(error) = ledger::write(&self.dir, self.acked_position) {
+            error!(message = "Error writing disk buffer ledger.", %error);
+            return;
+        }
+
+        while self.oldest_segment < self.acked_position.segment {
+            let path = segment_path(&self.dir, self.oldest_segment);
+            match fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+                Err(error) => {
+                    error!(message = "Error deleting disk buffer segment.", segment = ?path, %error);
+                    break;
+                }
+            }
+            self.oldest_segment += 1;
+        }
+
+        self.acked = 0;
+    }
+}
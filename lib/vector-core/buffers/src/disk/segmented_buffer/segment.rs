@@ -0,0 +1,150 @@
+//! On-disk layout of the segmented buffer.
+//!
+//! The buffer is a directory of append-only segment files, named after their
+//! ever increasing id. Each segment holds a sequence of records laid out as
+//!
+//! |--magic (u32)--|--length (u32)--|--checksum (u32)--|--header checksum (u32)--|--payload (length bytes)--|
+//!
+//! where the checksum is the CRC32 of the payload, the header checksum is the
+//! CRC32 of the preceding header fields and all integers are big endian. The
+//! header checksum guards the length, so a corrupted length is never followed,
+//! and the magic lets the reader find the next record after a corrupted
+//! header.
+use bytes::Bytes;
+use std::{
+    convert::TryFrom,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Length of the record header preceding every payload.
+pub(crate) const HEADER_LEN: usize = 16;
+
+/// Marks the start of every record.
+const MAGIC: u32 = 0x5642_5546;
+
+const SEGMENT_PREFIX: &str = "segment-";
+const SEGMENT_SUFFIX: &str = ".dat";
+
+/// A byte offset into a segment.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Position {
+    pub(crate) segment: u64,
+    pub(crate) offset: u64,
+}
+
+impl Position {
+    pub(crate) fn new(segment: u64, offset: u64) -> Self {
+        Self { segment, offset }
+    }
+}
+
+pub(crate) fn segment_path(dir: &Path, segment: u64) -> PathBuf {
+    dir.join(format!(
+        "{}{:020}{}",
+        SEGMENT_PREFIX, segment, SEGMENT_SUFFIX
+    ))
+}
+
+/// Ids of the segments in `dir`, in ascending order.
+pub(crate) fn list_segments(dir: &Path) -> io::Result<Vec<u64>> {
+    let mut segments = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let segment = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(SEGMENT_PREFIX))
+            .and_then(|name| name.strip_suffix(SEGMENT_SUFFIX))
+            .and_then(|id| id.parse().ok());
+        if let Some(segment) = segment {
+            segments.push(segment);
+        }
+    }
+    segments.sort_unstable();
+    Ok(segments)
+}
+
+/// Frame `payload` as a record.
+pub(crate) fn encode_record(payload: &[u8]) -> Vec<u8> {
+    let length = u32::try_from(payload.len()).expect("Record should be smaller than 4GiB");
+    let mut record = Vec::with_capacity(HEADER_LEN + payload.len());
+    record.extend_from_slice(&MAGIC.to_be_bytes());
+    record.extend_from_slice(&length.to_be_bytes());
+    record.extend_from_slice(&crc32fast::hash(payload).to_be_bytes());
+    let header_checksum = crc32fast::hash(&record);
+    record.extend_from_slice(&header_checksum.to_be_bytes());
+    record.extend_from_slice(payload);
+    record
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+/// Whether `header` starts a record, as opposed to being corrupted or not
+/// being at the start of a record at all.
+fn is_valid_header(header: &[u8; HEADER_LEN]) -> bool {
+    read_u32(&header[..4]) == MAGIC && read_u32(&header[12..]) == crc32fast::hash(&header[..12])
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Record {
+    /// A complete record with a matching checksum.
+    Valid(Bytes),
+    /// A complete record whose checksum doesn't match its payload, or the
+    /// bytes up to the next record after a corrupted header.
+    Corrupted,
+    /// The segment ends partway through a record, as happens when the process
+    /// is killed in the middle of a write.
+    Truncated,
+    /// There are no more records.
+    End,
+}
+
+/// Read the next record from `reader`, which has `remaining` readable bytes
+/// left, returning it along with the number of bytes it occupied.
+///
+/// Truncated records consume all the remaining bytes, since there is no way to
+/// tell where the next record would start. After a corrupted header `reader`
+/// may have been read past the bytes returned as `Record::Corrupted`, so it has
+/// to be moved to the start of the next record before reading it.
+pub(crate) fn read_record(reader: &mut impl Read, remaining: u64) -> io::Result<(Record, u64)> {
+    if remaining == 0 {
+        return Ok((Record::End, 0));
+    }
+    if remaining < HEADER_LEN as u64 {
+        return Ok((Record::Truncated, remaining));
+    }
+
+    let mut header = [0; HEADER_LEN];
+    reader.read_exact(&mut header)?;
+    if !is_valid_header(&header) {
+        // Look for the next record a byte at a time.
+        let mut skipped = 1;
+        while skipped + HEADER_LEN as u64 <= remaining {
+            header.copy_within(1.., 0);
+            reader.read_exact(&mut header[HEADER_LEN - 1..])?;
+            if is_valid_header(&header) {
+                return Ok((Record::Corrupted, skipped));
+            }
+            skipped += 1;
+        }
+        return Ok((Record::Corrupted, remaining));
+    }
+    let length = read_u32(&header[4..8]);
+    let checksum = read_u32(&header[8..12]);
+
+    let size = HEADER_LEN as u64 + u64::from(length);
+    if size > remaining {
+        return Ok((Record::Truncated, remaining));
+    }
+
+    let mut payload = vec![0; length as usize];
+    reader.read_exact(&mut payload)?;
+    if crc32fast::hash(&payload) == checksum {
+        Ok((Record::Valid(payload.into()), size))
+    } else {
+        Ok((Record::Corrupted, size))
+    }
+}
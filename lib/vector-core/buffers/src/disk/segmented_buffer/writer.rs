@@ -0,0 +1,279 @@
+use super::{
+    emit_byte_size,
+    segment::{encode_record, segment_path, Position},
+};
+use crate::bytes::{DecodeBytes, EncodeBytes};
+use bytes::BytesMut;
+use futures::{task::AtomicWaker, Sink};
+use std::fmt::Debug;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+use std::task::{Context, Poll, Waker};
+
+/// Appends records to the newest segment, moving on to a new segment once the
+/// current one would grow past `max_segment_size`.
+pub(crate) struct SegmentWriter {
+    dir: PathBuf,
+    file: BufWriter<File>,
+    /// Position after the last appended record.
+    position: Position,
+    max_segment_size: u64,
+    /// Position up to which records have been flushed to the segments.
+    /// Shared with Reader.
+    committed: Arc<Mutex<Position>>,
+}
+
+impl SegmentWriter {
+    pub(crate) fn open(
+        dir: PathBuf,
+        segment: u64,
+        max_segment_size: u64,
+        committed: Arc<Mutex<Position>>,
+    ) -> io::Result<Self> {
+        let file = create_segment(&dir, segment)?;
+        *committed.lock().unwrap() = Position::new(segment, 0);
+
+        Ok(Self {
+            dir,
+            file,
+            position: Position::new(segment, 0),
+            max_segment_size,
+            committed,
+        })
+    }
+
+    pub(crate) fn append(&mut self, record: &[u8]) -> io::Result<()> {
+        let size = record.len() as u64;
+        if self.position.offset > 0 && self.position.offset + size > self.max_segment_size {
+            // The reader treats a segment as complete as soon as the committed
+            // position is past it, so it has to be fully flushed by then.
+            self.file.flush()?;
+
+            let segment = self.position.segment + 1;
+            self.file = create_segment(&self.dir, segment)?;
+            self.position = Position::new(segment, 0);
+        }
+
+        self.file.write_all(record)?;
+        self.position.offset += size;
+        Ok(())
+    }
+
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        // This doesn't sync the segment to disk, but does hand it to the OS
+        // which will write it out even in the case of a process crash.
+        self.file.flush()?;
+        *self.committed.lock().unwrap() = self.position;
+        Ok(())
+    }
+}
+
+fn create_segment(dir: &Path, segment: u64) -> io::Result<BufWriter<File>> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(segment_path(dir, segment))
+        .map(BufWriter::new)
+}
+
+/// The writer side of N to 1 channel through the segments.
+pub struct Writer<T>
+where
+    T: Send + Sync + Unpin + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    /// The newest segment.
+    /// Shared with other Writers and Reader.
+    pub(crate) segment: Option<Arc<Mutex<SegmentWriter>>>,
+    /// Writers notify Reader through this Waker.
+    /// Shared with Reader.
+    pub(crate) write_notifier: Arc<AtomicWaker>,
+    /// Waiting queue for when the disk is full.
+    /// Shared with Reader.
+    pub(crate) blocked_write_tasks: Arc<Mutex<Vec<Waker>>>,
+    /// Records appended by this writer since it last flushed.
+    pub(crate) batch_size: usize,
+    /// Max size of unacked records in bytes.
+    pub(crate) max_size: usize,
+    /// Size of unacked records in bytes.
+    /// Shared with Reader.
+    pub(crate) current_size: Arc<AtomicUsize>,
+    /// Buffer for internal use.
+    pub(crate) slot: Option<T>,
+}
+
+impl<T> Clone for Writer<T>
+where
+    T: Send + Sync + Unpin + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    fn clone(&self) -> Self {
+        Self {
+            segment: self.segment.as_ref().map(Arc::clone),
+            write_notifier: Arc::clone(&self.write_notifier),
+            blocked_write_tasks: Arc::clone(&self.blocked_write_tasks),
+            batch_size: 0,
+            max_size: self.max_size,
+            current_size: Arc::clone(&self.current_size),
+            slot: None,
+        }
+    }
+}
+
+impl<T> Sink<T> for Writer<T>
+where
+    T: Send + Sync + Unpin + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    type Error = ();
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if self.slot.is_none() {
+            Poll::Ready(Ok(()))
+        } else {
+            // Assumes that flush will only succeed if it has also emptied the
+            // slot, hence we don't need to recheck if the slot is empty.
+            self.poll_flush(cx)
+        }
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        if let Some(event) = self.try_send(item)? {
+            debug_assert!(self.slot.is_none());
+            self.slot = Some(event);
+        }
+        Ok(())
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if let Some(event) = self.slot.take() {
+            if let Some(event) = self.try_send(event)? {
+                self.slot = Some(event);
+
+                self.blocked_write_tasks
+                    .lock()
+                    .unwrap()
+                    .push(cx.waker().clone());
+
+                if self.current_size.load(Ordering::Acquire) == 0 {
+                    // This is a rare case where the reader managed to consume
+                    // and ack all events in the buffer. In this case there is
+                    // a scenario where the reader won't be polled again hence
+                    // this sink will never be notified again so this will
+                    // stall.
+                    //
+                    // To avoid this we notify the reader to notify this writer.
+                    self.write_notifier.wake();
+                }
+
+                return Poll::Pending;
+            }
+        }
+
+        Poll::Ready(self.flush())
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl<T> Writer<T>
+where
+    T: Send + Sync + Unpin + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    /// Append `event` to the buffer, handing it back if the buffer is full.
+    fn try_send(&mut self, event: T) -> Result<Option<T>, ()> {
+        let mut buffer: BytesMut = BytesMut::with_capacity(64);
+        T::encode(event, &mut buffer).unwrap();
+
+        let record = encode_record(&buffer);
+        let record_size = record.len();
+
+        let size = self.current_size.fetch_add(record_size, Ordering::Relaxed) + record_size;
+        if size > self.max_size {
+            self.current_size.fetch_sub(record_size, Ordering::Relaxed);
+
+            self.flush()?;
+
+            return Ok(Some(T::decode(buffer).unwrap()));
+        }
+
+        let appended = self
+            .segment
+            .as_ref()
+            .expect("Segment is only dropped along with the writer")
+            .lock()
+            .unwrap()
+            .append(&record);
+        if let Err(error) = appended {
+            self.current_size.fetch_sub(record_size, Ordering::Relaxed);
+            error!(message = "Error writing to disk buffer.", %error);
+            return Err(());
+        }
+        emit_byte_size(size);
+
+        self.batch_size += 1;
+        if self.batch_size >= 100 {
+            self.flush()?;
+        }
+
+        Ok(None)
+    }
+
+    fn flush(&mut self) -> Result<(), ()> {
+        if self.batch_size > 0 {
+            self.segment
+                .as_ref()
+                .expect("Segment is only dropped along with the writer")
+                .lock()
+                .unwrap()
+                .flush()
+                .map_err(|error| error!(message = "Error flushing disk buffer.", %error))?;
+            self.batch_size = 0;
+            self.write_notifier.wake();
+        }
+        Ok(())
+    }
+}
+
+impl<T> Drop for Writer<T>
+where
+    T: Send + Sync + Unpin + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    fn drop(&mut self) {
+        if let Some(event) = self.slot.take() {
+            // This can happen if poll_close wasn't called which is a bug
+            // or we are unwinding the stack.
+            //
+            // We can't be picky at the moment so we will allow
+            // for the buffer to exceed configured limit.
+            self.max_size = usize::MAX;
+            let _ = self.try_send(event);
+        }
+
+        let _ = self.flush();
+
+        // We drop the segment Arc before notifying reader to avoid the case
+        // where we notify the reader, the reader reacts and checks
+        // Arc::strong_count to be > 1 and then we drop the Arc which would
+        // cause a stall.
+        self.segment.take();
+        // We need to wake up the reader so it can return None if there are no
+        // more writers
+        self.write_notifier.wake();
+    }
+}
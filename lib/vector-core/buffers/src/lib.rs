@@ -1,7 +1,8 @@
 //! The Vector Core buffer
 //!
 //! This library implements a channel like functionality, one variant which is
-//! solely in-memory and the other that is on-disk. Both variants are bounded.
+//! solely in-memory and the other that is on-disk. Both variants are bounded,
+//! and can be chained so that one overflows into the next.
 
 #![deny(clippy::all)]
 #![deny(clippy::pedantic)]
@@ -15,6 +16,7 @@ mod acker;
 pub mod bytes;
 #[cfg(feature = "disk-buffer")]
 pub mod disk;
+mod overflow;
 #[cfg(test)]
 mod test;
 mod variant;
@@ -22,12 +24,15 @@ mod variant;
 use crate::bytes::{DecodeBytes, EncodeBytes};
 pub use acker::Acker;
 use futures::{channel::mpsc, Sink, SinkExt, Stream};
+pub use overflow::{OverflowReader, OverflowSink};
 use pin_project::pin_project;
 #[cfg(test)]
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::fmt::{Debug, Display};
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 pub use variant::*;
 
@@ -81,11 +86,64 @@ where
     }
 }
 
+/// Build a chain of buffers based on the passed `Variant`s
+///
+/// Events go into the first buffer of the chain. Every buffer but the last one
+/// passes the events it has no room for on to the next buffer, so they should
+/// be configured with `WhenFull::Overflow`. The events of all buffers are read
+/// from the returned stream, and are not guaranteed to be read in the order
+/// they were sent once a buffer has overflowed.
+///
+/// # Errors
+///
+/// This function will fail if `variants` is empty or when creating a new disk
+/// buffer fails.
+pub fn build_chain<'a, T>(
+    variants: Vec<Variant>,
+) -> Result<
+    (
+        BufferInputCloner<T>,
+        Box<dyn Stream<Item = T> + 'a + Unpin + Send>,
+        Acker,
+    ),
+    String,
+>
+where
+    T: 'a + Send + Sync + Unpin + Clone + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug + Display,
+{
+    // The chain is built from its end, wrapping the buffers built so far as
+    // the overflow of the buffer in front of them.
+    let mut variants = variants.into_iter().rev();
+    let last = variants
+        .next()
+        .ok_or_else(|| "A buffer needs at least one stage.".to_string())?;
+    let (mut tx, mut rx, mut acker) = build(last)?;
+
+    for variant in variants {
+        let (primary_tx, primary_rx, primary_acker) = build(variant)?;
+        let origins = Arc::new(Mutex::new(VecDeque::new()));
+
+        tx = BufferInputCloner::Overflow(Box::new(primary_tx), Box::new(tx));
+        rx = Box::new(OverflowReader::new(primary_rx, rx, Arc::clone(&origins)));
+        acker = Acker::Overflow {
+            origins,
+            primary: Box::new(primary_acker),
+            overflow: Box::new(acker),
+        };
+    }
+
+    Ok((tx, rx, acker))
+}
+
 #[derive(Deserialize, Serialize, Debug, PartialEq, Copy, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum WhenFull {
     Block,
     DropNewest,
+    /// Pass events on to the next buffer in the chain, see [`build_chain`].
+    Overflow,
 }
 
 impl Default for WhenFull {
@@ -119,6 +177,8 @@ where
     Memory(mpsc::Sender<T>, WhenFull),
     #[cfg(feature = "disk-buffer")]
     Disk(disk::Writer<T>, WhenFull),
+    /// A buffer overflowing into the next one, built by [`build_chain`].
+    Overflow(Box<BufferInputCloner<T>>, Box<BufferInputCloner<T>>),
 }
 
 impl<'a, T> BufferInputCloner<T>
@@ -150,6 +210,10 @@ where
                     Box::new(inner)
                 }
             }
+
+            BufferInputCloner::Overflow(primary, overflow) => {
+                Box::new(OverflowSink::new(primary.get(), overflow.get()))
+            }
         }
     }
 }
@@ -0,0 +1,119 @@
+//! Layering of buffers, where events that don't fit into a primary buffer
+//! overflow into a secondary one instead of applying back pressure.
+use futures::{stream, Sink, Stream, StreamExt};
+use pin_project::pin_project;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+/// Sends into the `primary` sink while it has room, and into the `overflow`
+/// sink otherwise.
+#[pin_project]
+pub struct OverflowSink<P, O> {
+    #[pin]
+    primary: P,
+    #[pin]
+    overflow: O,
+    overflowing: bool,
+}
+
+impl<P, O> OverflowSink<P, O> {
+    pub fn new(primary: P, overflow: O) -> Self {
+        Self {
+            primary,
+            overflow,
+            overflowing: false,
+        }
+    }
+}
+
+impl<T, P, O> Sink<T> for OverflowSink<P, O>
+where
+    P: Sink<T>,
+    O: Sink<T, Error = P::Error>,
+{
+    type Error = P::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.project();
+        match this.primary.poll_ready(cx) {
+            Poll::Ready(Ok(())) => {
+                *this.overflowing = false;
+                Poll::Ready(Ok(()))
+            }
+            Poll::Pending => {
+                *this.overflowing = true;
+                this.overflow.poll_ready(cx)
+            }
+            error @ Poll::Ready(..) => error,
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        let this = self.project();
+        if *this.overflowing {
+            this.overflow.start_send(item)
+        } else {
+            this.primary.start_send(item)
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.project();
+        match this.primary.poll_flush(cx) {
+            Poll::Ready(Ok(())) => this.overflow.poll_flush(cx),
+            other => other,
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.project();
+        match this.primary.poll_close(cx) {
+            Poll::Ready(Ok(())) => this.overflow.poll_close(cx),
+            other => other,
+        }
+    }
+}
+
+/// Reads from both the primary and the overflow buffer, alternating between
+/// the two so that neither is starved, and so draining the overflow buffer
+/// while the primary one keeps receiving events.
+///
+/// The buffer each event came from is recorded in `origins`, so that acks can
+/// be passed on to the right buffer, see [`crate::Acker::Overflow`].
+pub struct OverflowReader<'a, T> {
+    inner: stream::Select<
+        stream::Map<Box<dyn Stream<Item = T> + 'a + Unpin + Send>, fn(T) -> (bool, T)>,
+        stream::Map<Box<dyn Stream<Item = T> + 'a + Unpin + Send>, fn(T) -> (bool, T)>,
+    >,
+    origins: Arc<Mutex<VecDeque<bool>>>,
+}
+
+impl<'a, T> OverflowReader<'a, T> {
+    pub fn new(
+        primary: Box<dyn Stream<Item = T> + 'a + Unpin + Send>,
+        overflow: Box<dyn Stream<Item = T> + 'a + Unpin + Send>,
+        origins: Arc<Mutex<VecDeque<bool>>>,
+    ) -> Self {
+        let primary = primary.map((|item| (false, item)) as fn(T) -> (bool, T));
+        let overflow = overflow.map((|item| (true, item)) as fn(T) -> (bool, T));
+        Self {
+            inner: stream::select(primary, overflow),
+            origins,
+        }
+    }
+}
+
+impl<T> Stream for OverflowReader<'_, T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx).map(|item| {
+            item.map(|(overflowed, item)| {
+                self.origins.lock().unwrap().push_back(overflowed);
+                item
+            })
+        })
+    }
+}
@@ -0,0 +1,209 @@
+#[cfg(feature = "disk-buffer-leveldb-migration")]
+use crate::bytes::EncodeBytes;
+#[cfg(feature = "disk-buffer-leveldb-migration")]
+use crate::disk::segmented_buffer::migration::Key;
+use crate::disk::segmented_buffer::Buffer;
+use crate::test::common::Message;
+#[cfg(feature = "disk-buffer-leveldb-migration")]
+use bytes::BytesMut;
+use futures::{SinkExt, StreamExt};
+#[cfg(feature = "disk-buffer-leveldb-migration")]
+use leveldb::database::{
+    batch::{Batch, Writebatch},
+    options::{Options, WriteOptions},
+    Database,
+};
+use std::fs::{self, OpenOptions};
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use tempdir::TempDir;
+
+/// Size of a `Message` record on disk, header included.
+const RECORD_SIZE: usize = 24;
+
+async fn write(path: &Path, ids: std::ops::Range<u64>) {
+    let (mut writer, _reader, _acker) = Buffer::<Message>::build(path, 10_000).unwrap();
+    for id in ids {
+        writer.send(Message::new(id)).await.unwrap();
+    }
+}
+
+fn segments(path: &Path) -> Vec<String> {
+    let mut segments = fs::read_dir(path)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|name| name.starts_with("segment-"))
+        .collect::<Vec<_>>();
+    segments.sort();
+    segments
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reads_across_segments_and_deletes_acked_ones() {
+    let dir = TempDir::new("reads_across_segments").unwrap();
+    // Segments of 100 bytes hold four records each.
+    let (mut writer, mut reader, acker) = Buffer::<Message>::build(dir.path(), 1_000).unwrap();
+
+    for id in 0..20 {
+        writer.send(Message::new(id)).await.unwrap();
+    }
+    assert_eq!(
+        reader.current_size.load(Ordering::Relaxed),
+        20 * RECORD_SIZE
+    );
+    assert_eq!(segments(dir.path()).len(), 5);
+
+    for id in 0..20 {
+        assert_eq!(reader.next().await, Some(Message::new(id)));
+    }
+    acker.ack(20);
+    drop(writer);
+    assert_eq!(reader.next().await, None);
+
+    assert_eq!(reader.current_size.load(Ordering::Relaxed), 0);
+    drop(reader);
+    assert_eq!(segments(dir.path()).len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn resumes_after_acked_events() {
+    let dir = TempDir::new("resumes_after_acked").unwrap();
+    write(dir.path(), 0..10).await;
+
+    {
+        let (_writer, mut reader, acker) = Buffer::<Message>::build(dir.path(), 10_000).unwrap();
+        assert_eq!(
+            reader.current_size.load(Ordering::Relaxed),
+            10 * RECORD_SIZE
+        );
+        for id in 0..6 {
+            assert_eq!(reader.next().await, Some(Message::new(id)));
+        }
+        // Only the first four are acked, the other two are delivered again.
+        acker.ack(4);
+    }
+
+    let (writer, reader, _acker) = Buffer::<Message>::build(dir.path(), 10_000).unwrap();
+    assert_eq!(reader.current_size.load(Ordering::Relaxed), 6 * RECORD_SIZE);
+    drop(writer);
+    let ids = reader.collect::<Vec<_>>().await;
+    assert_eq!(ids, (4..10).map(Message::new).collect::<Vec<_>>());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn skips_corrupted_records() {
+    let dir = TempDir::new("skips_corrupted").unwrap();
+    write(dir.path(), 0..3).await;
+
+    // Flip a bit in the payload of the second record.
+    let segment = dir.path().join(&segments(dir.path())[0]);
+    let mut bytes = fs::read(&segment).unwrap();
+    bytes[RECORD_SIZE + 20] ^= 1;
+    fs::write(&segment, bytes).unwrap();
+
+    let (writer, reader, _acker) = Buffer::<Message>::build(dir.path(), 10_000).unwrap();
+    drop(writer);
+    let ids = reader.collect::<Vec<_>>().await;
+    assert_eq!(ids, vec![Message::new(0), Message::new(2)]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn resyncs_after_corrupted_length() {
+    let dir = TempDir::new("resyncs_after_corrupted_length").unwrap();
+    write(dir.path(), 0..5).await;
+
+    // Make the length of the second record point past the end of the segment.
+    let segment = dir.path().join(&segments(dir.path())[0]);
+    let mut bytes = fs::read(&segment).unwrap();
+    bytes[RECORD_SIZE + 4] ^= 0x80;
+    fs::write(&segment, bytes).unwrap();
+
+    let (writer, reader, _acker) = Buffer::<Message>::build(dir.path(), 10_000).unwrap();
+    assert_eq!(reader.current_size.load(Ordering::Relaxed), 5 * RECORD_SIZE);
+    drop(writer);
+    let ids = reader.collect::<Vec<_>>().await;
+    assert_eq!(
+        ids,
+        vec![
+            Message::new(0),
+            Message::new(2),
+            Message::new(3),
+            Message::new(4)
+        ]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn skips_truncated_records() {
+    let dir = TempDir::new("skips_truncated").unwrap();
+    write(dir.path(), 0..2).await;
+
+    // As if the process was killed partway through writing the second record.
+    let segment = dir.path().join(&segments(dir.path())[0]);
+    let file = OpenOptions::new().write(true).open(&segment).unwrap();
+    file.set_len(RECORD_SIZE as u64 + 5).unwrap();
+
+    let (mut writer, mut reader, acker) = Buffer::<Message>::build(dir.path(), 10_000).unwrap();
+    writer.send(Message::new(2)).await.unwrap();
+
+    assert_eq!(reader.next().await, Some(Message::new(0)));
+    assert_eq!(reader.next().await, Some(Message::new(2)));
+    acker.ack(2);
+    drop(writer);
+    assert_eq!(reader.next().await, None);
+    assert_eq!(reader.current_size.load(Ordering::Relaxed), 0);
+}
+
+#[cfg(feature = "disk-buffer-leveldb-migration")]
+#[tokio::test(flavor = "multi_thread")]
+async fn migrates_leveldb_buffer() {
+    let dir = TempDir::new("migrates_leveldb").unwrap();
+
+    // Populate the buffer the way the LevelDB based buffer of previous
+    // versions did, where keys before the first one were acked and deleted.
+    {
+        let mut options = Options::new();
+        options.create_if_missing = true;
+        let db: Database<Key> = Database::open(dir.path(), options).unwrap();
+        let mut batch = Writebatch::new();
+        for key in 3..8 {
+            let mut buffer = BytesMut::new();
+            Message::new(key as u64).encode(&mut buffer).unwrap();
+            batch.put(Key(key), &buffer);
+        }
+        db.write(WriteOptions::new(), &batch).unwrap();
+    }
+
+    {
+        let (mut writer, mut reader, acker) = Buffer::<Message>::build(dir.path(), 10_000).unwrap();
+        assert!(!dir.path().join("CURRENT").exists());
+        assert_eq!(reader.current_size.load(Ordering::Relaxed), 5 * RECORD_SIZE);
+
+        // New records are read after the migrated ones.
+        writer.send(Message::new(8)).await.unwrap();
+        for id in 3..6 {
+            assert_eq!(reader.next().await, Some(Message::new(id)));
+        }
+        acker.ack(3);
+    }
+
+    // The migrated records are tracked by the segmented buffer from now on.
+    let (writer, reader, _acker) = Buffer::<Message>::build(dir.path(), 10_000).unwrap();
+    drop(writer);
+    let ids = reader.collect::<Vec<_>>().await;
+    assert_eq!(ids, (6..9).map(Message::new).collect::<Vec<_>>());
+}
+
+#[cfg(not(feature = "disk-buffer-leveldb-migration"))]
+#[test]
+fn rejects_unmigrated_leveldb_buffer() {
+    let dir = TempDir::new("rejects_leveldb").unwrap();
+    fs::write(dir.path().join("CURRENT"), "MANIFEST-000001\n").unwrap();
+
+    let result = Buffer::<Message>::build(dir.path(), 10_000);
+    assert!(matches!(
+        result,
+        Err(crate::disk::DataDirError::Unmigrated { .. })
+    ));
+    assert!(dir.path().join("CURRENT").exists());
+}
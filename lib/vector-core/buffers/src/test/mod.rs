@@ -1,5 +1,8 @@
 mod common;
+#[cfg(feature = "disk-buffer")]
+mod disk;
 mod model;
+mod overflow;
 
 use crate::{Acker, DropWhenFull};
 use futures::task::Poll;
@@ -0,0 +1,71 @@
+use crate::test::common::Message;
+use crate::{build_chain, Acker, Variant, WhenFull};
+use futures::task::Poll;
+use futures::{future, Sink, Stream};
+use std::collections::VecDeque;
+use std::sync::{atomic::Ordering, Arc, Mutex};
+
+#[tokio::test]
+async fn overflows_into_next_stage() {
+    let (tx, rx, _acker) = build_chain::<Message>(vec![
+        Variant::Memory {
+            max_events: 2,
+            when_full: WhenFull::Overflow,
+        },
+        Variant::Memory {
+            max_events: 2,
+            when_full: WhenFull::Block,
+        },
+    ])
+    .unwrap();
+
+    future::lazy(|cx| {
+        let mut tx = Box::pin(tx.get());
+        // Each memory stage holds its capacity plus one event per sender.
+        for id in 0..6 {
+            assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
+            assert_eq!(tx.as_mut().start_send(Message::new(id)), Ok(()));
+        }
+        assert_eq!(tx.as_mut().poll_ready(cx), Poll::Pending);
+
+        let mut rx = Box::pin(rx);
+        let mut messages = Vec::new();
+        for _ in 0..6 {
+            match rx.as_mut().poll_next(cx) {
+                Poll::Ready(Some(message)) => messages.push(message),
+                other => panic!("unexpected poll result: {:?}", other),
+            }
+        }
+        assert_eq!(rx.as_mut().poll_next(cx), Poll::Pending);
+
+        // Stages are read from in turns, so the order isn't preserved.
+        for id in 0..6 {
+            assert!(messages.contains(&Message::new(id)));
+        }
+    })
+    .await;
+}
+
+#[test]
+fn acks_are_passed_on_to_originating_stage() {
+    let (primary, primary_counter) = Acker::new_for_testing();
+    let (overflow, overflow_counter) = Acker::new_for_testing();
+    let origins = Arc::new(Mutex::new(VecDeque::from(vec![
+        false, true, true, false, true,
+    ])));
+    let acker = Acker::Overflow {
+        origins: Arc::clone(&origins),
+        primary: Box::new(primary),
+        overflow: Box::new(overflow),
+    };
+
+    acker.ack(0);
+    acker.ack(2);
+    assert_eq!(primary_counter.load(Ordering::Relaxed), 1);
+    assert_eq!(overflow_counter.load(Ordering::Relaxed), 1);
+
+    acker.ack(3);
+    assert_eq!(primary_counter.load(Ordering::Relaxed), 2);
+    assert_eq!(overflow_counter.load(Ordering::Relaxed), 3);
+    assert!(origins.lock().unwrap().is_empty());
+}
@@ -1,5 +1,5 @@
 use super::util::type_name;
-use crate::event::{Event, LogEvent, Metric};
+use crate::event::{Event, LogEvent, Metric, TraceEvent};
 use rlua::prelude::*;
 
 impl<'a> ToLua<'a> for Event {
@@ -9,6 +9,7 @@ impl<'a> ToLua<'a> for Event {
         match self {
             Event::Log(log) => table.set("log", log.to_lua(ctx)?)?,
             Event::Metric(metric) => table.set("metric", metric.to_lua(ctx)?)?,
+            Event::Trace(trace) => table.set("trace", trace.into_log().to_lua(ctx)?)?,
         }
         Ok(LuaValue::Table(table))
     }
@@ -26,19 +27,21 @@ impl<'a> FromLua<'a> for Event {
                 })
             }
         };
-        match (table.get("log")?, table.get("metric")?) {
-            (LuaValue::Table(log), LuaValue::Nil) => {
+        match (table.get("log")?, table.get("metric")?, table.get("trace")?) {
+            (LuaValue::Table(log), LuaValue::Nil, LuaValue::Nil) => {
                 Ok(Event::Log(LogEvent::from_lua(LuaValue::Table(log), ctx)?))
             }
-            (LuaValue::Nil, LuaValue::Table(metric)) => Ok(Event::Metric(Metric::from_lua(
-                LuaValue::Table(metric),
-                ctx,
-            )?)),
+            (LuaValue::Nil, LuaValue::Table(metric), LuaValue::Nil) => Ok(Event::Metric(
+                Metric::from_lua(LuaValue::Table(metric), ctx)?,
+            )),
+            (LuaValue::Nil, LuaValue::Nil, LuaValue::Table(trace)) => Ok(Event::Trace(
+                TraceEvent::from(LogEvent::from_lua(LuaValue::Table(trace), ctx)?),
+            )),
             _ => Err(LuaError::FromLuaConversionError {
                 from: type_name(&value),
                 to: "Event",
                 message: Some(
-                    "Event should contain either \"log\" or \"metric\" key at the top level"
+                    "Event should contain either \"log\", \"metric\" or \"trace\" key at the top level"
                         .to_string(),
                 ),
             }),
@@ -82,6 +85,22 @@ mod test {
         assert_event(event, assertions);
     }
 
+    #[test]
+    fn to_lua_trace() {
+        let mut trace = TraceEvent::default();
+        trace.insert("span_id", "abc123");
+        let event = Event::Trace(trace);
+
+        let assertions = vec![
+            "type(event) == 'table'",
+            "event.log == nil",
+            "type(event.trace) == 'table'",
+            "event.trace.span_id == 'abc123'",
+        ];
+
+        assert_event(event, assertions);
+    }
+
     #[test]
     fn to_lua_metric() {
         let event = Event::Metric(Metric::new(
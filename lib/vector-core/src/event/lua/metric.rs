@@ -1,5 +1,8 @@
 use super::util::{table_to_set, table_to_timestamp, timestamp_to_table, type_name};
-use crate::event::{metric, Metric, MetricKind, MetricValue, StatisticKind};
+use crate::{
+    event::{metric, Metric, MetricKind, MetricValue, StatisticKind},
+    metrics::{Bin, DDSketch},
+};
 use rlua::prelude::*;
 use std::collections::BTreeMap;
 
@@ -126,6 +129,19 @@ impl<'a> ToLua<'a> for Metric {
                 aggregated_summary.set("sum", sum)?;
                 tbl.set("aggregated_summary", aggregated_summary)?;
             }
+            MetricValue::Sketch { sketch } => {
+                let lua_sketch = ctx.create_table()?;
+                let keys: Vec<_> = sketch.bins().iter().map(|b| b.k).collect();
+                let counts: Vec<_> = sketch.bins().iter().map(|b| b.n).collect();
+                lua_sketch.set("keys", keys)?;
+                lua_sketch.set("counts", counts)?;
+                lua_sketch.set("count", sketch.count())?;
+                lua_sketch.set("min", sketch.min())?;
+                lua_sketch.set("max", sketch.max())?;
+                lua_sketch.set("sum", sketch.sum())?;
+                lua_sketch.set("avg", sketch.avg())?;
+                tbl.set("sketch", lua_sketch)?;
+            }
         }
 
         Ok(LuaValue::Table(tbl))
@@ -196,11 +212,29 @@ impl<'a> FromLua<'a> for Metric {
                 count: aggregated_summary.get("count")?,
                 sum: aggregated_summary.get("sum")?,
             }
+        } else if let Some(sketch) = table.get::<_, Option<LuaTable>>("sketch")? {
+            let keys: Vec<i16> = sketch.get("keys")?;
+            let counts: Vec<u32> = sketch.get("counts")?;
+            let bins = keys
+                .into_iter()
+                .zip(counts.into_iter())
+                .map(|(k, n)| Bin { k, n })
+                .collect();
+            MetricValue::Sketch {
+                sketch: DDSketch::from_parts(
+                    bins,
+                    sketch.get("count")?,
+                    sketch.get::<_, Option<f64>>("min")?.unwrap_or(f64::MAX),
+                    sketch.get::<_, Option<f64>>("max")?.unwrap_or(f64::MIN),
+                    sketch.get("sum")?,
+                    sketch.get("avg")?,
+                ),
+            }
         } else {
             return Err(LuaError::FromLuaConversionError {
                 from: type_name(&value),
                 to: "Metric",
-                message: Some("Cannot find metric value, expected presence one of \"counter\", \"gauge\", \"set\", \"distribution\", \"aggregated_histogram\", \"aggregated_summary\"".to_string()),
+                message: Some("Cannot find metric value, expected presence one of \"counter\", \"gauge\", \"set\", \"distribution\", \"aggregated_histogram\", \"aggregated_summary\", \"sketch\"".to_string()),
             });
         };
 
@@ -386,6 +420,28 @@ mod test {
         assert_metric(metric, assertions)
     }
 
+    #[test]
+    fn to_lua_sketch() {
+        let metric = Metric::new(
+            "example sketch",
+            MetricKind::Incremental,
+            MetricValue::Sketch {
+                sketch: DDSketch::from_samples(&crate::samples![1.0 => 3, 2.0 => 1]),
+            },
+        );
+        let assertions = vec![
+            "type(metric.sketch) == 'table'",
+            "#metric.sketch.keys == 2",
+            "#metric.sketch.counts == 2",
+            "metric.sketch.counts[1] == 3",
+            "metric.sketch.count == 4",
+            "metric.sketch.min == 1.0",
+            "metric.sketch.max == 2.0",
+            "metric.sketch.sum == 5.0",
+        ];
+        assert_metric(metric, assertions)
+    }
+
     #[test]
     fn from_lua_counter_minimal() {
         let value = r#"{
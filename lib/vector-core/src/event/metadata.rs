@@ -55,6 +55,16 @@ impl EventMetadata {
     pub fn add_finalizer(&mut self, finalizer: EventFinalizer) {
         self.finalizers.add(finalizer);
     }
+
+    /// Take the finalizers out of this metadata, leaving it with none.
+    pub fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+
+    /// Merge the given finalizers into this metadata.
+    pub fn merge_finalizers(&mut self, finalizers: EventFinalizers) {
+        self.finalizers.merge(finalizers);
+    }
 }
 
 impl EventDataEq for EventMetadata {
@@ -1,5 +1,5 @@
 use super::{BatchNotifier, EventFinalizer, EventMetadata};
-use crate::metrics::Handle;
+use crate::metrics::{DDSketch, Handle};
 use chrono::{DateTime, Utc};
 use getset::{Getters, MutGetters};
 use serde::{Deserialize, Serialize};
@@ -139,6 +139,11 @@ pub enum MetricValue {
         count: u32,
         sum: f64,
     },
+    /// A Sketch is a mergeable summary of a set of observations, from
+    /// which any quantile can be estimated within a bounded relative
+    /// error. Unlike a Distribution its size does not grow with the
+    /// number of observations.
+    Sketch { sketch: DDSketch },
 }
 
 /// A single sample from a `MetricValue::Distribution`, containing the
@@ -237,6 +242,7 @@ impl From<MetricValue> for vrl_core::Value {
             MetricValue::Distribution { .. } => "distribution",
             MetricValue::AggregatedHistogram { .. } => "aggregated histogram",
             MetricValue::AggregatedSummary { .. } => "aggregated summary",
+            MetricValue::Sketch { .. } => "sketch",
         }
         .into()
     }
@@ -615,6 +621,18 @@ impl MetricValue {
                 *count = 0;
                 *sum = 0.0;
             }
+            Self::Sketch { sketch } => sketch.clear(),
+        }
+    }
+
+    /// Convert a Distribution into a Sketch of the same samples. Other
+    /// values are returned unchanged.
+    pub fn distribution_to_sketch(self) -> Self {
+        match self {
+            Self::Distribution { samples, .. } => Self::Sketch {
+                sketch: DDSketch::from_samples(&samples),
+            },
+            value => value,
         }
     }
 
@@ -668,6 +686,10 @@ impl MetricValue {
                 *sum += sum2;
                 true
             }
+            (Self::Sketch { ref mut sketch }, Self::Sketch { sketch: sketch2 }) => {
+                sketch.merge(sketch2);
+                true
+            }
 
             _ => false,
         }
@@ -808,6 +830,12 @@ impl Display for Metric {
                     write!(fmt, "{}@{}", quantile.upper_limit, quantile.value)
                 })
             }
+            MetricValue::Sketch { sketch } => {
+                write!(fmt, "count={} sum={} ", sketch.count(), sketch.sum())?;
+                write_list(fmt, " ", [0.5, 0.9, 0.99].iter(), |fmt, q| {
+                    write!(fmt, "{}@{}", q, sketch.quantile(*q).unwrap_or(0.0))
+                })
+            }
         }
     }
 }
@@ -1000,6 +1028,40 @@ mod test {
         assert_eq!(dist, expected);
     }
 
+    #[test]
+    fn merge_sketches() {
+        let mut sketch = Metric::new(
+            "sketch",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: samples![1.0 => 10],
+                statistic: StatisticKind::Summary,
+            }
+            .distribution_to_sketch(),
+        );
+
+        let delta = Metric::new(
+            "sketch",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: samples![2.0 => 20],
+                statistic: StatisticKind::Summary,
+            }
+            .distribution_to_sketch(),
+        )
+        .with_timestamp(Some(ts()));
+
+        let expected = sketch
+            .clone()
+            .with_value(MetricValue::Sketch {
+                sketch: DDSketch::from_samples(&samples![1.0 => 10, 2.0 => 20]),
+            })
+            .with_timestamp(Some(ts()));
+
+        assert!(sketch.data.add(&delta.data));
+        assert_eq!(sketch, expected);
+    }
+
     #[test]
     // `too_many_lines` is mostly just useful for production code but we're not
     // able to flag the lint on only for non-test.
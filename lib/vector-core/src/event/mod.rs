@@ -1,3 +1,4 @@
+use crate::metrics::{Bin, DDSketch, MAX_KEY};
 use buffers::bytes::{DecodeBytes, EncodeBytes};
 use bytes::{Buf, BufMut, Bytes};
 use chrono::{DateTime, SecondsFormat, Utc};
@@ -14,6 +15,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::convert::{TryFrom, TryInto};
 use std::fmt::Debug;
 use std::sync::Arc;
+pub use trace::TraceEvent;
 use tracing::field::{Field, Visit};
 pub use util::log::PathComponent;
 pub use util::log::PathIter;
@@ -34,6 +36,7 @@ pub mod metric;
 pub mod proto;
 #[cfg(test)]
 mod test;
+mod trace;
 pub mod util;
 mod value;
 #[cfg(feature = "vrl")]
@@ -45,6 +48,7 @@ pub const PARTIAL: &str = "_partial";
 pub enum Event {
     Log(LogEvent),
     Metric(Metric),
+    Trace(TraceEvent),
 }
 
 impl Event {
@@ -125,10 +129,47 @@ impl Event {
         }
     }
 
+    /// Return self as a `TraceEvent`
+    ///
+    /// # Panics
+    ///
+    /// This function panics if self is anything other than an `Event::Trace`.
+    pub fn as_trace(&self) -> &TraceEvent {
+        match self {
+            Event::Trace(trace) => trace,
+            _ => panic!("Failed type coercion, {:?} is not a trace event", self),
+        }
+    }
+
+    /// Return self as a mutable `TraceEvent`
+    ///
+    /// # Panics
+    ///
+    /// This function panics if self is anything other than an `Event::Trace`.
+    pub fn as_mut_trace(&mut self) -> &mut TraceEvent {
+        match self {
+            Event::Trace(trace) => trace,
+            _ => panic!("Failed type coercion, {:?} is not a trace event", self),
+        }
+    }
+
+    /// Coerces self into a `TraceEvent`
+    ///
+    /// # Panics
+    ///
+    /// This function panics if self is anything other than an `Event::Trace`.
+    pub fn into_trace(self) -> TraceEvent {
+        match self {
+            Event::Trace(trace) => trace,
+            _ => panic!("Failed type coercion, {:?} is not a trace event", self),
+        }
+    }
+
     pub fn metadata(&self) -> &EventMetadata {
         match self {
             Self::Log(log) => log.metadata(),
             Self::Metric(metric) => metric.metadata(),
+            Self::Trace(trace) => trace.metadata(),
         }
     }
 
@@ -136,6 +177,7 @@ impl Event {
         match self {
             Self::Log(log) => log.metadata_mut(),
             Self::Metric(metric) => metric.metadata_mut(),
+            Self::Trace(trace) => trace.metadata_mut(),
         }
     }
 
@@ -144,6 +186,7 @@ impl Event {
         match self {
             Self::Log(log) => log.into_parts().1,
             Self::Metric(metric) => metric.into_parts().2,
+            Self::Trace(trace) => trace.into_parts().1,
         }
     }
 
@@ -152,6 +195,7 @@ impl Event {
         match self {
             Self::Log(log) => log.add_finalizer(finalizer),
             Self::Metric(metric) => metric.add_finalizer(finalizer),
+            Self::Trace(trace) => trace.add_finalizer(finalizer),
         }
     }
 
@@ -159,6 +203,7 @@ impl Event {
         match self {
             Self::Log(log) => log.with_batch_notifier(batch).into(),
             Self::Metric(metric) => metric.with_batch_notifier(batch).into(),
+            Self::Trace(trace) => trace.with_batch_notifier(batch).into(),
         }
     }
 }
@@ -168,6 +213,7 @@ impl EventDataEq for Event {
         match (self, other) {
             (Self::Log(a), Self::Log(b)) => a.event_data_eq(b),
             (Self::Metric(a), Self::Metric(b)) => a.event_data_eq(b),
+            (Self::Trace(a), Self::Trace(b)) => a.event_data_eq(b),
             _ => false,
         }
     }
@@ -276,6 +322,7 @@ impl TryInto<serde_json::Value> for Event {
         match self {
             Event::Log(fields) => serde_json::to_value(fields),
             Event::Metric(metric) => serde_json::to_value(metric),
+            Event::Trace(trace) => serde_json::to_value(trace),
         }
     }
 }
@@ -343,6 +390,51 @@ impl From<proto::SummaryQuantile> for metric::Quantile {
     }
 }
 
+impl From<proto::Sketch> for DDSketch {
+    fn from(sketch: proto::Sketch) -> Self {
+        // Keys beyond the range of the sketch are clamped to its outermost
+        // bins, which is where the sketch itself maps out of range values.
+        let bins = sketch
+            .keys
+            .into_iter()
+            .zip(sketch.counts.into_iter())
+            .map(|(k, n)| {
+                let k = i16::try_from(k)
+                    .unwrap_or(if k < 0 { -MAX_KEY } else { MAX_KEY })
+                    .max(-MAX_KEY);
+                Bin { k, n }
+            })
+            .collect();
+        Self::from_parts(
+            bins,
+            sketch.count,
+            sketch.min,
+            sketch.max,
+            sketch.sum,
+            sketch.avg,
+        )
+    }
+}
+
+impl From<DDSketch> for proto::Sketch {
+    fn from(sketch: DDSketch) -> Self {
+        let (keys, counts) = sketch
+            .bins()
+            .iter()
+            .map(|bin| (i32::from(bin.k), bin.n))
+            .unzip();
+        Self {
+            keys,
+            counts,
+            count: sketch.count(),
+            min: sketch.min().unwrap_or(f64::MAX),
+            max: sketch.max().unwrap_or(f64::MIN),
+            sum: sketch.sum(),
+            avg: sketch.avg(),
+        }
+    }
+}
+
 impl From<Bytes> for Event {
     fn from(message: Bytes) -> Self {
         Event::Log(LogEvent::from(message))
@@ -373,12 +465,19 @@ impl From<Metric> for Event {
     }
 }
 
+impl From<TraceEvent> for Event {
+    fn from(trace: TraceEvent) -> Self {
+        Event::Trace(trace)
+    }
+}
+
 /// A wrapper for references to inner event types, where reconstituting
 /// a full `Event` from a `LogEvent` or `Metric` might be inconvenient.
 #[derive(Clone, Copy, Debug)]
 pub enum EventRef<'a> {
     Log(&'a LogEvent),
     Metric(&'a Metric),
+    Trace(&'a TraceEvent),
 }
 
 impl<'a> From<&'a Event> for EventRef<'a> {
@@ -386,6 +485,7 @@ impl<'a> From<&'a Event> for EventRef<'a> {
         match event {
             Event::Log(log) => log.into(),
             Event::Metric(metric) => metric.into(),
+            Event::Trace(trace) => trace.into(),
         }
     }
 }
@@ -402,6 +502,12 @@ impl<'a> From<&'a Metric> for EventRef<'a> {
     }
 }
 
+impl<'a> From<&'a TraceEvent> for EventRef<'a> {
+    fn from(trace: &'a TraceEvent) -> Self {
+        Self::Trace(trace)
+    }
+}
+
 impl EncodeBytes<Event> for Event {
     type Error = EncodeError;
 
@@ -23,6 +23,12 @@ impl From<Metric> for Event {
     }
 }
 
+impl From<Trace> for Event {
+    fn from(trace: Trace) -> Self {
+        Self::Trace(trace)
+    }
+}
+
 impl From<Log> for event::LogEvent {
     fn from(log: Log) -> Self {
         let fields = log
@@ -35,6 +41,18 @@ impl From<Log> for event::LogEvent {
     }
 }
 
+impl From<Trace> for event::TraceEvent {
+    fn from(trace: Trace) -> Self {
+        let fields = trace
+            .fields
+            .into_iter()
+            .filter_map(|(k, v)| decode_value(v).map(|value| (k, value)))
+            .collect::<BTreeMap<_, _>>();
+
+        Self::from(fields)
+    }
+}
+
 impl From<Metric> for event::Metric {
     fn from(metric: Metric) -> Self {
         let kind = match metric.kind() {
@@ -96,6 +114,9 @@ impl From<Metric> for event::Metric {
                 count: summary.count,
                 sum: summary.sum,
             },
+            MetricValue::Sketch(sketch) => event::MetricValue::Sketch {
+                sketch: sketch.into(),
+            },
         };
 
         Self::new(name, kind, value)
@@ -112,6 +133,7 @@ impl From<EventWrapper> for event::Event {
         match event {
             Event::Log(proto) => Self::Log(proto.into()),
             Event::Metric(proto) => Self::Metric(proto.into()),
+            Event::Trace(proto) => Self::Trace(proto.into()),
         }
     }
 }
@@ -135,6 +157,25 @@ impl From<event::LogEvent> for WithMetadata<Log> {
     }
 }
 
+impl From<event::TraceEvent> for Trace {
+    fn from(trace: event::TraceEvent) -> Self {
+        WithMetadata::<Self>::from(trace).data
+    }
+}
+
+impl From<event::TraceEvent> for WithMetadata<Trace> {
+    fn from(trace: event::TraceEvent) -> Self {
+        let (fields, metadata) = trace.into_parts();
+        let fields = fields
+            .into_iter()
+            .map(|(k, v)| (k, encode_value(v)))
+            .collect::<BTreeMap<_, _>>();
+
+        let data = Trace { fields };
+        Self { data, metadata }
+    }
+}
+
 impl From<event::Metric> for Metric {
     fn from(metric: event::Metric) -> Self {
         WithMetadata::<Self>::from(metric).data
@@ -194,6 +235,7 @@ impl From<event::Metric> for WithMetadata<Metric> {
                 count,
                 sum,
             }),
+            event::MetricValue::Sketch { sketch } => MetricValue::Sketch(sketch.into()),
         };
 
         let data = Metric {
@@ -219,6 +261,7 @@ impl From<event::Event> for WithMetadata<Event> {
         match event {
             event::Event::Log(log_event) => WithMetadata::<Log>::from(log_event).into(),
             event::Event::Metric(metric) => WithMetadata::<Metric>::from(metric).into(),
+            event::Event::Trace(trace) => WithMetadata::<Trace>::from(trace).into(),
         }
     }
 }
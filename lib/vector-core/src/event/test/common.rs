@@ -1,6 +1,9 @@
-use crate::event::{
-    metric::{Bucket, MetricData, MetricName, MetricSeries, Quantile, Sample},
-    Event, EventMetadata, LogEvent, Metric, MetricKind, MetricValue, StatisticKind, Value,
+use crate::{
+    event::{
+        metric::{Bucket, MetricData, MetricName, MetricSeries, Quantile, Sample},
+        Event, EventMetadata, LogEvent, Metric, MetricKind, MetricValue, StatisticKind, Value,
+    },
+    metrics::DDSketch,
 };
 use bytes::Bytes;
 use chrono::{DateTime, NaiveDateTime, Utc};
@@ -52,10 +55,10 @@ impl Arbitrary for Event {
         let choice: u8 = u8::arbitrary(g);
         // Quickcheck can't derive Arbitrary for enums, see
         // https://github.com/BurntSushi/quickcheck/issues/98
-        if choice % 2 == 0 {
-            Event::Log(LogEvent::arbitrary(g))
-        } else {
-            Event::Metric(Metric::arbitrary(g))
+        match choice % 3 {
+            0 => Event::Log(LogEvent::arbitrary(g)),
+            1 => Event::Metric(Metric::arbitrary(g)),
+            _ => Event::Trace(LogEvent::arbitrary(g).into()),
         }
     }
 
@@ -63,6 +66,9 @@ impl Arbitrary for Event {
         match self {
             Event::Log(log_event) => Box::new(log_event.shrink().map(Event::Log)),
             Event::Metric(metric) => Box::new(metric.shrink().map(Event::Metric)),
+            Event::Trace(trace) => {
+                Box::new(trace.as_log().shrink().map(|log| Event::Trace(log.into())))
+            }
         }
     }
 }
@@ -150,7 +156,7 @@ impl Arbitrary for MetricValue {
         // constant here are the number of fields in `MetricValue`. Because the
         // field total is not a power of two we introduce a bias into choice
         // here toward `MetricValue::Counter` and `MetricValue::Gauge`.
-        match u8::arbitrary(g) % 6 {
+        match u8::arbitrary(g) % 7 {
             0 => MetricValue::Counter {
                 value: f64::arbitrary(g) % MAX_F64_SIZE,
             },
@@ -174,6 +180,9 @@ impl Arbitrary for MetricValue {
                 count: u32::arbitrary(g),
                 sum: f64::arbitrary(g) % MAX_F64_SIZE,
             },
+            6 => MetricValue::Sketch {
+                sketch: DDSketch::from_samples(&Vec::<Sample>::arbitrary(g)),
+            },
             _ => unreachable!(),
         }
     }
@@ -301,6 +310,9 @@ impl Arbitrary for MetricValue {
                         }),
                 )
             }
+            // Sketches are only ever built up from samples, so there is no
+            // meaningful way to shrink one while keeping it consistent.
+            MetricValue::Sketch { .. } => empty_shrinker(),
         }
     }
 }
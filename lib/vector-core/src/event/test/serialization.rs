@@ -74,3 +74,19 @@ fn type_serialization() {
     assert_eq!(map["bool"], json!(true));
     assert_eq!(map["string"], json!("thisisastring"));
 }
+
+#[test]
+fn sketch_keys_out_of_range_are_clamped() {
+    let sketch = DDSketch::from(proto::Sketch {
+        keys: vec![i32::MIN, -1, 1, i32::MAX],
+        counts: vec![1, 2, 3, 4],
+        count: 10,
+        min: -1.0,
+        max: 1.0,
+        sum: 0.0,
+        avg: 0.0,
+    });
+
+    let keys = sketch.bins().iter().map(|bin| bin.k).collect::<Vec<_>>();
+    assert_eq!(keys, vec![-MAX_KEY, -1, 1, MAX_KEY]);
+}
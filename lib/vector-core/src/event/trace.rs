@@ -0,0 +1,107 @@
+use super::{
+    finalization::{BatchNotifier, EventFinalizer},
+    metadata::EventMetadata,
+    LogEvent, Value,
+};
+use serde::{Deserialize, Serialize};
+use shared::EventDataEq;
+use std::{collections::BTreeMap, fmt::Debug, sync::Arc};
+
+/// A trace span. Spans carry an open-ended set of fields, so they share their
+/// representation with `LogEvent` but are kept as a distinct type so that
+/// topology type-checking can tell them apart.
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct TraceEvent(LogEvent);
+
+impl TraceEvent {
+    ///  Create a `TraceEvent` from a tuple of its components
+    pub fn from_parts(map: BTreeMap<String, Value>, metadata: EventMetadata) -> Self {
+        Self(LogEvent::from_parts(map, metadata))
+    }
+
+    /// Convert a `TraceEvent` into a tuple of its components
+    pub fn into_parts(self) -> (BTreeMap<String, Value>, EventMetadata) {
+        self.0.into_parts()
+    }
+
+    pub fn metadata(&self) -> &EventMetadata {
+        self.0.metadata()
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut EventMetadata {
+        self.0.metadata_mut()
+    }
+
+    pub fn with_batch_notifier(self, batch: &Arc<BatchNotifier>) -> Self {
+        Self(self.0.with_batch_notifier(batch))
+    }
+
+    pub fn add_finalizer(&mut self, finalizer: EventFinalizer) {
+        self.0.add_finalizer(finalizer);
+    }
+
+    pub fn get(&self, key: impl AsRef<str>) -> Option<&Value> {
+        self.0.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: impl AsRef<str>) -> Option<&mut Value> {
+        self.0.get_mut(key)
+    }
+
+    pub fn contains(&self, key: impl AsRef<str>) -> bool {
+        self.0.contains(key)
+    }
+
+    pub fn insert(
+        &mut self,
+        key: impl AsRef<str>,
+        value: impl Into<Value> + Debug,
+    ) -> Option<Value> {
+        self.0.insert(key, value)
+    }
+
+    pub fn remove(&mut self, key: impl AsRef<str>) -> Option<Value> {
+        self.0.remove(key)
+    }
+
+    pub fn as_map(&self) -> &BTreeMap<String, Value> {
+        self.0.as_map()
+    }
+
+    pub fn as_map_mut(&mut self) -> &mut BTreeMap<String, Value> {
+        self.0.as_map_mut()
+    }
+
+    /// Views the span's fields as a `LogEvent`, for codecs and sinks which
+    /// only need to serialize the fields.
+    pub fn as_log(&self) -> &LogEvent {
+        &self.0
+    }
+
+    pub fn as_mut_log(&mut self) -> &mut LogEvent {
+        &mut self.0
+    }
+
+    pub fn into_log(self) -> LogEvent {
+        self.0
+    }
+}
+
+impl EventDataEq for TraceEvent {
+    fn event_data_eq(&self, other: &Self) -> bool {
+        self.0.event_data_eq(&other.0)
+    }
+}
+
+impl From<LogEvent> for TraceEvent {
+    fn from(log: LogEvent) -> Self {
+        Self(log)
+    }
+}
+
+impl From<BTreeMap<String, Value>> for TraceEvent {
+    fn from(map: BTreeMap<String, Value>) -> Self {
+        Self(LogEvent::from(map))
+    }
+}
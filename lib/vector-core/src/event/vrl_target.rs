@@ -1,4 +1,4 @@
-use super::{Event, EventMetadata, LogEvent, Metric, MetricKind, Value};
+use super::{Event, EventMetadata, LogEvent, Metric, MetricKind, TraceEvent, Value};
 use crate::config::log_schema;
 use lookup::LookupBuf;
 use snafu::Snafu;
@@ -22,6 +22,8 @@ pub enum VrlTarget {
     // that `fields` must always be a `Map` variant.
     LogEvent(Value, EventMetadata),
     Metric(Metric),
+    // Traces share the shape of log events, but are turned back into trace events.
+    Trace(Value, EventMetadata),
 }
 
 impl VrlTarget {
@@ -32,6 +34,10 @@ impl VrlTarget {
                 VrlTarget::LogEvent(Value::Map(fields), metadata)
             }
             Event::Metric(event) => VrlTarget::Metric(event),
+            Event::Trace(event) => {
+                let (fields, metadata) = event.into_parts();
+                VrlTarget::Trace(Value::Map(fields), metadata)
+            }
         }
     }
 
@@ -42,11 +48,17 @@ impl VrlTarget {
     pub fn into_events(self) -> impl Iterator<Item = Event> {
         match self {
             VrlTarget::LogEvent(value, metadata) => {
-                Box::new(value_into_log_events(value, metadata)) as Box<dyn Iterator<Item = Event>>
+                Box::new(value_into_events(value, metadata, Event::Log))
+                    as Box<dyn Iterator<Item = Event>>
             }
             VrlTarget::Metric(metric) => {
                 Box::new(std::iter::once(Event::Metric(metric))) as Box<dyn Iterator<Item = Event>>
             }
+            VrlTarget::Trace(value, metadata) => {
+                Box::new(value_into_events(value, metadata, |log| {
+                    Event::Trace(TraceEvent::from(log))
+                })) as Box<dyn Iterator<Item = Event>>
+            }
         }
     }
 }
@@ -54,7 +66,7 @@ impl VrlTarget {
 impl vrl_core::Target for VrlTarget {
     fn insert(&mut self, path: &LookupBuf, value: vrl_core::Value) -> Result<(), String> {
         match self {
-            VrlTarget::LogEvent(ref mut log, _) => log
+            VrlTarget::LogEvent(ref mut log, _) | VrlTarget::Trace(ref mut log, _) => log
                 .insert(path.clone(), value)
                 .map(|_| ())
                 .map_err(|err| err.to_string()),
@@ -127,7 +139,7 @@ impl vrl_core::Target for VrlTarget {
 
     fn get(&self, path: &LookupBuf) -> std::result::Result<Option<vrl_core::Value>, String> {
         match self {
-            VrlTarget::LogEvent(log, _) => log
+            VrlTarget::LogEvent(log, _) | VrlTarget::Trace(log, _) => log
                 .get(path)
                 .map(|val| val.map(|val| val.clone().into()))
                 .map_err(|err| err.to_string()),
@@ -202,7 +214,7 @@ impl vrl_core::Target for VrlTarget {
         compact: bool,
     ) -> Result<Option<vrl_core::Value>, String> {
         match self {
-            VrlTarget::LogEvent(ref mut log, _) => {
+            VrlTarget::LogEvent(ref mut log, _) | VrlTarget::Trace(ref mut log, _) => {
                 if path.is_root() {
                     Ok(Some({
                         let mut map = Value::Map(BTreeMap::new());
@@ -256,29 +268,33 @@ impl From<Event> for VrlTarget {
     }
 }
 
-// Turn a `Value` back into `LogEvents`:
+// Turn a `Value` back into `LogEvents` (or `TraceEvent`s, via `into_event`):
 // * In the common case, where `.` is a map, just create an event using it as the event fields.
-// * If `.` is an array, map over all of the values to create log events:
+// * If `.` is an array, map over all of the values to create events:
 //   * If an element is an object, create an event using that as fields.
 //   * If an element is anything else, assign to the `message` key.
 // * If `.` is anything else, assign to the `message` key.
-fn value_into_log_events(value: Value, metadata: EventMetadata) -> impl Iterator<Item = Event> {
+fn value_into_events(
+    value: Value,
+    metadata: EventMetadata,
+    into_event: fn(LogEvent) -> Event,
+) -> impl Iterator<Item = Event> {
     match value {
-        Value::Map(object) => Box::new(std::iter::once(Event::from(LogEvent::from_parts(
+        Value::Map(object) => Box::new(std::iter::once(into_event(LogEvent::from_parts(
             object, metadata,
         )))) as Box<dyn Iterator<Item = Event>>,
         Value::Array(values) => Box::new(values.into_iter().map(move |v| match v {
-            Value::Map(object) => Event::from(LogEvent::from_parts(object, metadata.clone())),
+            Value::Map(object) => into_event(LogEvent::from_parts(object, metadata.clone())),
             v => {
                 let mut log = LogEvent::new_with_metadata(metadata.clone());
                 log.insert(log_schema().message_key(), v);
-                Event::from(log)
+                into_event(log)
             }
         })) as Box<dyn Iterator<Item = Event>>,
         v => {
             let mut log = LogEvent::new_with_metadata(metadata);
             log.insert(log_schema().message_key(), v);
-            Box::new(std::iter::once(Event::from(log))) as Box<dyn Iterator<Item = Event>>
+            Box::new(std::iter::once(into_event(log))) as Box<dyn Iterator<Item = Event>>
         }
     }
 }
@@ -609,6 +625,30 @@ mod test {
         }
     }
 
+    #[test]
+    fn trace_stays_trace() {
+        let mut trace = TraceEvent::default();
+        trace.insert("span_id", "abc123");
+        let mut target = VrlTarget::new(Event::Trace(trace));
+
+        assert_eq!(
+            vrl_core::Target::get(&target, &LookupBuf::from("span_id")),
+            Ok(Some("abc123".into()))
+        );
+
+        vrl_core::Target::insert(&mut target, &LookupBuf::from("name"), "query".into()).unwrap();
+
+        let events = target.into_events().collect::<Vec<_>>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].as_trace().as_map(),
+            &btreemap! {
+                "span_id" => "abc123",
+                "name" => "query",
+            }
+        );
+    }
+
     #[test]
     fn metric_all_fields() {
         let metric = Metric::new(
@@ -0,0 +1,362 @@
+//! A mergeable quantile sketch with relative error guarantees.
+//!
+//! This is an implementation of [DDSketch][ddsketch] using the same key
+//! mapping and defaults as the sketches produced by the Datadog Agent, which
+//! means the bins can be shipped to Datadog as-is without any re-mapping.
+//!
+//! [ddsketch]: https://www.vldb.org/pvldb/vol12/p2195-masson.pdf
+
+use crate::event::metric::Sample;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+const AGENT_DEFAULT_BIN_LIMIT: usize = 4096;
+const AGENT_DEFAULT_EPS: f64 = 1.0 / 128.0;
+const AGENT_DEFAULT_MIN_VALUE: f64 = 1.0e-9;
+
+/// The largest key of a sketch, holding the values too large to be mapped.
+/// Negative keys go down to `-MAX_KEY`.
+pub const MAX_KEY: i16 = i16::MAX;
+
+static CONFIG: Lazy<Config> = Lazy::new(Config::default);
+
+/// The parameters of the logarithmic mapping from values to bin keys.
+#[derive(Debug)]
+struct Config {
+    bin_limit: usize,
+    gamma_v: f64,
+    gamma_ln: f64,
+    norm_min: f64,
+    norm_bias: i32,
+}
+
+impl Default for Config {
+    #[allow(clippy::cast_possible_truncation)]
+    fn default() -> Self {
+        let gamma_v = 1.0 + 2.0 * AGENT_DEFAULT_EPS;
+        let gamma_ln = (2.0 * AGENT_DEFAULT_EPS).ln_1p();
+        let norm_emin = (AGENT_DEFAULT_MIN_VALUE.ln() / gamma_ln).floor() as i32;
+        let norm_bias = -norm_emin + 1;
+
+        let mut config = Self {
+            bin_limit: AGENT_DEFAULT_BIN_LIMIT,
+            gamma_v,
+            gamma_ln,
+            norm_min: 0.0,
+            norm_bias,
+        };
+        // The smallest value that maps to a non-zero key.
+        config.norm_min = config.lower_bound(1);
+        config
+    }
+}
+
+impl Config {
+    #[allow(clippy::cast_possible_truncation)]
+    fn key(&self, v: f64) -> i16 {
+        if v < 0.0 {
+            return -self.key(-v);
+        }
+        if v == 0.0 || v < self.norm_min {
+            return 0;
+        }
+
+        let rounded = (v.ln() / self.gamma_ln).round() as i32;
+        rounded
+            .saturating_add(self.norm_bias)
+            .clamp(1, i32::from(MAX_KEY)) as i16
+    }
+
+    fn lower_bound(&self, k: i16) -> f64 {
+        match k.cmp(&0) {
+            Ordering::Less => -self.lower_bound(-k),
+            Ordering::Equal => 0.0,
+            Ordering::Greater if k == MAX_KEY => f64::INFINITY,
+            Ordering::Greater => self.gamma_v.powi(i32::from(k) - self.norm_bias),
+        }
+    }
+}
+
+/// A single bin of a sketch: the number of values `n` that mapped to the key
+/// `k`.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, PartialOrd, Serialize)]
+pub struct Bin {
+    pub k: i16,
+    pub n: u32,
+}
+
+/// A DDSketch, holding the bins and summary statistics for a set of observed
+/// values.
+///
+/// Sketches can be merged without losing accuracy, and any quantile can be
+/// estimated with a relative error of at most 1/128th of the true value.
+#[derive(Clone, Debug, Deserialize, PartialEq, PartialOrd, Serialize)]
+pub struct DDSketch {
+    bins: Vec<Bin>,
+    count: u32,
+    min: f64,
+    max: f64,
+    sum: f64,
+    avg: f64,
+}
+
+impl Default for DDSketch {
+    fn default() -> Self {
+        Self {
+            bins: Vec::new(),
+            count: 0,
+            min: f64::MAX,
+            max: f64::MIN,
+            sum: 0.0,
+            avg: 0.0,
+        }
+    }
+}
+
+impl DDSketch {
+    /// Builds a sketch from the samples of a distribution, counting each
+    /// sample `rate` times.
+    pub fn from_samples(samples: &[Sample]) -> Self {
+        let mut sketch = Self::default();
+        for sample in samples {
+            sketch.insert_n(sample.value, sample.rate);
+        }
+        sketch
+    }
+
+    /// Rebuilds a sketch from its raw parts, as found in an encoded sketch.
+    /// Bins are sorted and merged by key.
+    pub fn from_parts(bins: Vec<Bin>, count: u32, min: f64, max: f64, sum: f64, avg: f64) -> Self {
+        let mut sketch = Self {
+            bins: Vec::with_capacity(bins.len()),
+            count,
+            min,
+            max,
+            sum,
+            avg,
+        };
+        for bin in bins {
+            sketch.insert_key(bin.k, bin.n);
+        }
+        sketch.trim();
+        sketch
+    }
+
+    pub fn bins(&self) -> &[Bin] {
+        &self.bins
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.min)
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.max)
+    }
+
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    pub fn avg(&self) -> f64 {
+        self.avg
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Empties the sketch.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn insert(&mut self, value: f64) {
+        self.insert_n(value, 1);
+    }
+
+    /// Records `value` as having been observed `n` times.
+    pub fn insert_n(&mut self, value: f64, n: u32) {
+        if n == 0 || !value.is_finite() {
+            return;
+        }
+
+        self.update_stats(value, value, value * f64::from(n), n);
+        self.insert_key(CONFIG.key(value), n);
+        self.trim();
+    }
+
+    /// Merges the contents of `other` into this sketch.
+    pub fn merge(&mut self, other: &Self) {
+        if other.is_empty() {
+            return;
+        }
+
+        self.update_stats(other.min, other.max, other.sum, other.count);
+        for bin in &other.bins {
+            self.insert_key(bin.k, bin.n);
+        }
+        self.trim();
+    }
+
+    /// Estimates the value at quantile `q`, where `0 <= q <= 1`. Returns
+    /// `None` if the sketch is empty.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        if q <= 0.0 {
+            return Some(self.min);
+        }
+        if q >= 1.0 {
+            return Some(self.max);
+        }
+
+        let rank = q * f64::from(self.count - 1);
+        let mut seen = 0u64;
+        let bin = self.bins.iter().find(|bin| {
+            seen += u64::from(bin.n);
+            seen as f64 > rank
+        })?;
+
+        Some(CONFIG.lower_bound(bin.k).max(self.min).min(self.max))
+    }
+
+    fn update_stats(&mut self, min: f64, max: f64, sum: f64, count: u32) {
+        self.count = self.count.saturating_add(count);
+        self.min = self.min.min(min);
+        self.max = self.max.max(max);
+        self.sum += sum;
+        self.avg = self.sum / f64::from(self.count);
+    }
+
+    fn insert_key(&mut self, k: i16, n: u32) {
+        match self.bins.binary_search_by_key(&k, |bin| bin.k) {
+            Ok(index) => self.bins[index].n = self.bins[index].n.saturating_add(n),
+            Err(index) => self.bins.insert(index, Bin { k, n }),
+        }
+    }
+
+    /// Collapses the lowest bins together until the sketch is within the bin
+    /// limit, trading accuracy on the smallest values for bounded size.
+    fn trim(&mut self) {
+        if self.bins.len() <= CONFIG.bin_limit {
+            return;
+        }
+
+        let excess = self.bins.len() - CONFIG.bin_limit;
+        let collapsed = self.bins[..=excess]
+            .iter()
+            .fold(0u32, |n, bin| n.saturating_add(bin.n));
+        self.bins.drain(..excess);
+        self.bins[0].n = collapsed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_relative_eq(expected: f64, actual: f64) {
+        let error = ((expected - actual) / expected).abs();
+        assert!(
+            error <= AGENT_DEFAULT_EPS,
+            "expected {} to be within {} of {}",
+            actual,
+            AGENT_DEFAULT_EPS,
+            expected
+        );
+    }
+
+    #[test]
+    fn empty_sketch() {
+        let sketch = DDSketch::default();
+        assert!(sketch.is_empty());
+        assert_eq!(sketch.quantile(0.5), None);
+        assert_eq!(sketch.min(), None);
+        assert_eq!(sketch.max(), None);
+    }
+
+    #[test]
+    fn quantiles_within_relative_error() {
+        let mut sketch = DDSketch::default();
+        for i in 1..=1000 {
+            sketch.insert(f64::from(i));
+        }
+
+        assert_eq!(sketch.count(), 1000);
+        assert_eq!(sketch.min(), Some(1.0));
+        assert_eq!(sketch.max(), Some(1000.0));
+        assert_eq!(sketch.sum(), 500_500.0);
+        assert_eq!(sketch.avg(), 500.5);
+        for &(q, expected) in &[(0.5, 500.0), (0.9, 900.0), (0.99, 990.0)] {
+            assert_relative_eq(expected, sketch.quantile(q).unwrap());
+        }
+        assert_eq!(sketch.quantile(0.0), Some(1.0));
+        assert_eq!(sketch.quantile(1.0), Some(1000.0));
+    }
+
+    #[test]
+    fn negative_and_zero_values() {
+        let mut sketch = DDSketch::default();
+        sketch.insert(-10.0);
+        sketch.insert(0.0);
+        sketch.insert(10.0);
+
+        assert_relative_eq(-10.0, sketch.quantile(0.0).unwrap());
+        assert_eq!(sketch.quantile(0.5), Some(0.0));
+        assert_relative_eq(10.0, sketch.quantile(1.0).unwrap());
+    }
+
+    #[test]
+    fn merge_matches_single_sketch() {
+        let mut all = DDSketch::default();
+        let mut low = DDSketch::default();
+        let mut high = DDSketch::default();
+        for i in 1..=100 {
+            all.insert(f64::from(i));
+            if i <= 50 {
+                low.insert(f64::from(i));
+            } else {
+                high.insert(f64::from(i));
+            }
+        }
+
+        low.merge(&high);
+        assert_eq!(low, all);
+    }
+
+    #[test]
+    fn from_samples_respects_rate() {
+        let sketch = DDSketch::from_samples(&crate::samples![1.0 => 3, 2.0 => 1]);
+        assert_eq!(sketch.count(), 4);
+        assert_eq!(sketch.sum(), 5.0);
+        assert_eq!(sketch.quantile(0.5), Some(1.0));
+    }
+
+    #[test]
+    fn bins_are_bounded() {
+        let mut sketch = DDSketch::default();
+        // Spread values over far more keys than the bin limit allows.
+        for i in 0..10_000 {
+            sketch.insert(1.02f64.powi(i));
+        }
+
+        assert_eq!(sketch.bins().len(), AGENT_DEFAULT_BIN_LIMIT);
+        assert_eq!(
+            sketch
+                .bins()
+                .iter()
+                .map(|bin| u64::from(bin.n))
+                .sum::<u64>(),
+            10_000
+        );
+        assert_relative_eq(sketch.max().unwrap(), sketch.quantile(1.0).unwrap());
+    }
+}
@@ -103,10 +103,97 @@ impl Transform {
 ///   `TaskTransform` or vice versa.
 pub trait FunctionTransform: Send + dyn_clone::DynClone + Sync {
     fn transform(&mut self, output: &mut Vec<Event>, event: Event);
+
+    /// Transform an event, writing the results to any of the transform's
+    /// outputs.
+    ///
+    /// This is only called for transforms that declare named outputs. By
+    /// default, everything is written to the default output.
+    fn transform_outputs(&mut self, outputs: &mut TransformOutputs, event: Event) {
+        self.transform(outputs.primary_mut(), event)
+    }
 }
 
 dyn_clone::clone_trait_object!(FunctionTransform);
 
+/// Buffers the events written by a function transform to its default output
+/// and to the named outputs it declares.
+#[derive(Debug, Default)]
+pub struct TransformOutputs {
+    primary: Vec<Event>,
+    named: Vec<(String, Vec<Event>)>,
+}
+
+impl TransformOutputs {
+    /// Create the buffers for the default output and the given named outputs.
+    pub fn new(named: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            primary: Vec::new(),
+            named: named.into_iter().map(|name| (name, Vec::new())).collect(),
+        }
+    }
+
+    /// Write an event to the default output.
+    pub fn push(&mut self, event: Event) {
+        self.primary.push(event);
+    }
+
+    /// Write an event to a named output.
+    ///
+    /// # Panics
+    ///
+    /// If the transform didn't declare an output with this name.
+    pub fn push_named(&mut self, name: &str, event: Event) {
+        self.named_mut(name).push(event);
+    }
+
+    /// Mutably borrow the buffer of the default output.
+    pub fn primary_mut(&mut self) -> &mut Vec<Event> {
+        &mut self.primary
+    }
+
+    /// Mutably borrow the buffer of a named output.
+    ///
+    /// # Panics
+    ///
+    /// If the transform didn't declare an output with this name.
+    pub fn named_mut(&mut self, name: &str) -> &mut Vec<Event> {
+        self.named
+            .iter_mut()
+            .find(|(output, _)| output == name)
+            .map(|(_, events)| events)
+            .unwrap_or_else(|| panic!("Transform has no output named {:?}.", name))
+    }
+
+    /// The number of events buffered across all outputs.
+    pub fn len(&self) -> usize {
+        self.primary.len()
+            + self
+                .named
+                .iter()
+                .map(|(_, events)| events.len())
+                .sum::<usize>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drain the events buffered for the default output.
+    pub fn drain(&mut self) -> std::vec::Drain<'_, Event> {
+        self.primary.drain(..)
+    }
+
+    /// Drain the events buffered for a named output.
+    ///
+    /// # Panics
+    ///
+    /// If the transform didn't declare an output with this name.
+    pub fn drain_named(&mut self, name: &str) -> std::vec::Drain<'_, Event> {
+        self.named_mut(name).drain(..)
+    }
+}
+
 /// Transforms that tend to be more complicated runtime style components.
 ///
 /// These require coordination and map a stream of some `T` to some `U`.
@@ -1,4 +1,5 @@
 use crate::expression::*;
+use crate::function::FunctionClosure;
 use crate::{Function, Program, State, Value};
 use chrono::{TimeZone, Utc};
 use diagnostic::DiagnosticError;
@@ -329,22 +330,35 @@ impl<'a> Compiler<'a> {
             ident,
             abort_on_error,
             arguments,
+            closure,
         } = node.into_inner();
 
         let arguments = arguments
             .into_iter()
             .map(|node| Node::new(node.span(), self.compile_function_argument(node)))
-            .collect();
+            .collect::<Vec<_>>();
 
         if abort_on_error {
             self.fallible = true;
         }
 
+        let closure = match closure
+            .map(|node| self.compile_function_closure(&ident, &arguments, node))
+            .transpose()
+        {
+            Ok(closure) => closure,
+            Err(err) => {
+                self.errors.push(Box::new(err));
+                return FunctionCall::noop();
+            }
+        };
+
         FunctionCall::new(
             call_span,
             ident,
             abort_on_error,
             arguments,
+            closure,
             self.fns,
             self.state,
         )
@@ -354,6 +368,62 @@ impl<'a> Compiler<'a> {
         })
     }
 
+    /// Compile the closure attached to a function call.
+    ///
+    /// The closure variables are typed after the argument the function
+    /// iterates while the closure block is compiled. Any variables they shadow
+    /// are restored afterwards.
+    fn compile_function_closure(
+        &mut self,
+        ident: &Node<ast::Ident>,
+        arguments: &[Node<FunctionArgument>],
+        node: Node<ast::FunctionClosure>,
+    ) -> Result<FunctionClosure, function_call::Error> {
+        let closure_span = node.span();
+        let ast::FunctionClosure { variables, block } = node.into_inner();
+
+        let type_defs = FunctionCall::closure_variables(
+            ident,
+            arguments,
+            &variables,
+            closure_span,
+            self.fns,
+            self.state,
+        )?;
+
+        let variables = variables
+            .into_iter()
+            .map(Node::into_inner)
+            .collect::<Vec<_>>();
+
+        let shadowed = variables
+            .iter()
+            .zip(type_defs)
+            .map(|(ident, type_def)| {
+                let previous = self.state.variable(ident).cloned();
+                let details = assignment::Details {
+                    type_def,
+                    value: None,
+                };
+                self.state.insert_variable(ident.clone(), details);
+
+                previous
+            })
+            .collect::<Vec<_>>();
+
+        let block = self.compile_block(block);
+        let type_def = block.type_def(self.state);
+
+        for (ident, previous) in variables.iter().zip(shadowed) {
+            match previous {
+                Some(details) => self.state.insert_variable(ident.clone(), details),
+                None => self.state.remove_variable(ident),
+            }
+        }
+
+        Ok(FunctionClosure::new(variables, block, type_def))
+    }
+
     fn compile_function_argument(&mut self, node: Node<ast::FunctionArgument>) -> FunctionArgument {
         let ast::FunctionArgument { ident, expr } = node.into_inner();
         let expr = Node::new(expr.span(), self.compile_expr(expr));
@@ -1,5 +1,5 @@
 use crate::expression::{levenstein, ExpressionError, FunctionArgument, Noop};
-use crate::function::{ArgumentList, Parameter};
+use crate::function::{ArgumentList, FunctionClosure, Parameter};
 use crate::parser::{Ident, Node};
 use crate::{value::Kind, Context, Expression, Function, Resolved, Span, State, TypeDef};
 use diagnostic::{DiagnosticError, Label, Note, Urls};
@@ -22,6 +22,7 @@ pub struct FunctionCall {
     // This allows us to keep the arguments non-cloneable.
     arguments_fmt: Vec<String>,
     arguments_dbg: Vec<String>,
+    closure_fmt: Option<String>,
 
     // used for equality check
     ident: &'static str,
@@ -33,6 +34,7 @@ impl FunctionCall {
         ident: Node<Ident>,
         abort_on_error: bool,
         arguments: Vec<Node<FunctionArgument>>,
+        closure: Option<FunctionClosure>,
         funcs: &[Box<dyn Function>],
         state: &State,
     ) -> Result<Self, Error> {
@@ -70,12 +72,6 @@ impl FunctionCall {
             });
         }
 
-        // Keeps track of positional argument indices.
-        //
-        // Used to map a positional argument to its keyword. Keyword arguments
-        // can be used in any order, and don't count towards the index of
-        // positional arguments.
-        let mut index = 0;
         let mut list = ArgumentList::default();
 
         let arguments_fmt = arguments
@@ -88,32 +84,13 @@ impl FunctionCall {
             .map(|arg| format!("{:?}", arg))
             .collect::<Vec<_>>();
 
+        let parameters = argument_parameters(function.parameters(), &arguments);
+
         let mut maybe_fallible_arguments = false;
-        for node in arguments {
+        for (node, parameter) in arguments.into_iter().zip(parameters) {
             let (argument_span, argument) = node.take();
 
-            let parameter = match argument.keyword() {
-                // positional argument
-                None => {
-                    index += 1;
-                    function.parameters().get(index - 1)
-                }
-
-                // keyword argument
-                Some(k) => function
-                    .parameters()
-                    .iter()
-                    .enumerate()
-                    .find(|(_, param)| param.keyword == k)
-                    .map(|(pos, param)| {
-                        if pos == index {
-                            index += 1;
-                        }
-
-                        param
-                    }),
-            }
-            .ok_or_else(|| Error::UnknownKeyword {
+            let parameter = parameter.ok_or_else(|| Error::UnknownKeyword {
                 keyword_span: argument.keyword_span().expect("exists"),
                 ident_span,
                 keywords: function.parameters().iter().map(|p| p.keyword).collect(),
@@ -162,8 +139,40 @@ impl FunctionCall {
                 })
             })?;
 
+        // Check the closure attached to the call against the one the function
+        // accepts.
+        let closure_fmt = closure.as_ref().map(ToString::to_string);
+        match (function.closure(), closure) {
+            (Some(definition), Some(closure)) => {
+                let got = closure.type_def().kind();
+
+                if !definition.output.contains(got) {
+                    return Err(Error::InvalidClosureOutput {
+                        call_span,
+                        expected: definition.output,
+                        got,
+                    });
+                }
+
+                // A closure that can fail makes the function call fallible.
+                if closure.type_def().is_fallible() {
+                    maybe_fallible_arguments = true;
+                }
+
+                list.set_closure(closure);
+            }
+            (Some(_), None) => return Err(Error::MissingClosure { call_span }),
+            (None, Some(_)) => {
+                return Err(Error::UnexpectedClosure {
+                    ident_span,
+                    closure_span: call_span,
+                })
+            }
+            (None, None) => {}
+        }
+
         let expr = function
-            .compile(list)
+            .compile_with_state(state, list)
             .map_err(|error| Error::Compilation { call_span, error })?;
 
         // Asking for an infallible function to abort on error makes no sense.
@@ -183,10 +192,100 @@ impl FunctionCall {
             span: call_span,
             arguments_fmt,
             arguments_dbg,
+            closure_fmt,
             ident: function.identifier(),
         })
     }
 
+    /// Type the variables of a closure attached to a call to the function,
+    /// after the argument the function iterates.
+    ///
+    /// The compiler assigns these type definitions to the closure variables
+    /// while compiling the closure block. Any error with the call itself is
+    /// left for [`FunctionCall::new`] to report, in which case the variables
+    /// are of unknown type.
+    pub(crate) fn closure_variables(
+        ident: &Node<Ident>,
+        arguments: &[Node<FunctionArgument>],
+        variables: &[Node<Ident>],
+        closure_span: Span,
+        funcs: &[Box<dyn Function>],
+        state: &State,
+    ) -> Result<Vec<TypeDef>, Error> {
+        let unknown = vec![TypeDef::new().unknown(); variables.len()];
+
+        let function = match funcs
+            .iter()
+            .find(|f| f.identifier() == ident.inner().as_ref())
+        {
+            Some(function) => function,
+            None => return Ok(unknown),
+        };
+
+        let definition = function.closure().ok_or(Error::UnexpectedClosure {
+            ident_span: ident.span(),
+            closure_span,
+        })?;
+
+        let parameters = argument_parameters(function.parameters(), arguments);
+        let target = |keyword: &str| {
+            arguments
+                .iter()
+                .zip(&parameters)
+                .find(|(_, parameter)| parameter.map(|p| p.keyword) == Some(keyword))
+                .map(|(argument, _)| argument.type_def(state))
+        };
+
+        // The inputs applying to the kinds the iterated argument can be.
+        let inputs = definition
+            .inputs
+            .iter()
+            .filter_map(|input| {
+                target(input.parameter_keyword)
+                    .filter(|target| input.kind.intersects(target.kind()))
+                    .map(|target| (input, target))
+            })
+            .collect::<Vec<_>>();
+
+        let expected = match inputs.first() {
+            Some((input, _)) => input.variables.len(),
+            None => return Ok(unknown),
+        };
+
+        if variables.len() != expected {
+            return Err(Error::ClosureArityMismatch {
+                closure_span,
+                expected,
+                got: variables.len(),
+            });
+        }
+
+        let type_defs = (0..expected)
+            .map(|position| {
+                inputs
+                    .iter()
+                    .filter_map(|(input, target)| {
+                        input
+                            .variables
+                            .get(position)
+                            .map(|variable| variable.type_def(target))
+                    })
+                    .fold(None, |acc: Option<TypeDef>, type_def| {
+                        Some(match acc {
+                            None => type_def,
+                            Some(acc) if acc.is_unknown() || type_def.is_unknown() => {
+                                TypeDef::new().unknown()
+                            }
+                            Some(acc) => acc.merge(type_def),
+                        })
+                    })
+                    .unwrap_or_else(|| TypeDef::new().unknown())
+            })
+            .collect();
+
+        Ok(type_defs)
+    }
+
     pub fn noop() -> Self {
         let expr = Box::new(Noop) as _;
 
@@ -197,6 +296,7 @@ impl FunctionCall {
             span: Span::default(),
             arguments_fmt: vec![],
             arguments_dbg: vec![],
+            closure_fmt: None,
             ident: "noop",
         }
     }
@@ -315,7 +415,13 @@ impl fmt::Display for FunctionCall {
             }
         }
 
-        f.write_str(")")
+        f.write_str(")")?;
+
+        if let Some(closure) = &self.closure_fmt {
+            write!(f, " {}", closure)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -335,10 +441,52 @@ impl fmt::Debug for FunctionCall {
             }
         }
 
-        f.write_str("))")
+        f.write_str(")")?;
+
+        if let Some(closure) = &self.closure_fmt {
+            write!(f, " {}", closure)?;
+        }
+
+        f.write_str(")")
     }
 }
 
+/// Map each argument to the parameter it is passed for, if any.
+///
+/// Positional arguments map to the parameter at their index. Keyword arguments
+/// can be used in any order, and don't count towards the index of positional
+/// arguments.
+fn argument_parameters(
+    parameters: &'static [Parameter],
+    arguments: &[Node<FunctionArgument>],
+) -> Vec<Option<&'static Parameter>> {
+    let mut index = 0;
+
+    arguments
+        .iter()
+        .map(|argument| match argument.keyword() {
+            // positional argument
+            None => {
+                index += 1;
+                parameters.get(index - 1)
+            }
+
+            // keyword argument
+            Some(k) => parameters
+                .iter()
+                .enumerate()
+                .find(|(_, param)| param.keyword == k)
+                .map(|(pos, param)| {
+                    if pos == index {
+                        index += 1;
+                    }
+
+                    param
+                }),
+        })
+        .collect()
+}
+
 impl PartialEq for FunctionCall {
     fn eq(&self, other: &Self) -> bool {
         self.ident == other.ident
@@ -396,6 +544,29 @@ pub enum Error {
 
     #[error("fallible argument")]
     FallibleArgument { expr_span: Span },
+
+    #[error("unexpected closure")]
+    UnexpectedClosure {
+        ident_span: Span,
+        closure_span: Span,
+    },
+
+    #[error("missing closure")]
+    MissingClosure { call_span: Span },
+
+    #[error("wrong number of closure variables")]
+    ClosureArityMismatch {
+        closure_span: Span,
+        expected: usize,
+        got: usize,
+    },
+
+    #[error("invalid closure output type")]
+    InvalidClosureOutput {
+        call_span: Span,
+        expected: Kind,
+        got: Kind,
+    },
 }
 
 impl DiagnosticError for Error {
@@ -411,6 +582,10 @@ impl DiagnosticError for Error {
             AbortInfallible { .. } => 620,
             InvalidArgumentKind { .. } => 110,
             FallibleArgument { .. } => 630,
+            UnexpectedClosure { .. } => 109,
+            MissingClosure { .. } => 112,
+            ClosureArityMismatch { .. } => 120,
+            InvalidClosureOutput { .. } => 122,
         }
     }
 
@@ -559,6 +734,54 @@ impl DiagnosticError for Error {
                     expr_span,
                 ),
             ],
+
+            UnexpectedClosure {
+                ident_span,
+                closure_span,
+            } => vec![
+                Label::primary("unexpected closure", closure_span),
+                Label::context("this function does not accept a closure", ident_span),
+            ],
+
+            MissingClosure { call_span } => vec![Label::primary(
+                "this function must be called with a closure",
+                call_span,
+            )],
+
+            ClosureArityMismatch {
+                closure_span,
+                expected,
+                got,
+            } => {
+                let variable = |n: usize| if n == 1 { "variable" } else { "variables" };
+
+                vec![
+                    Label::primary(
+                        format!("this closure takes {} {}", got, variable(*got)),
+                        closure_span,
+                    ),
+                    Label::context(
+                        format!(
+                            "but the function calls it with {} {}",
+                            expected,
+                            variable(*expected)
+                        ),
+                        closure_span,
+                    ),
+                ]
+            }
+
+            InvalidClosureOutput {
+                call_span,
+                expected,
+                got,
+            } => vec![
+                Label::primary(
+                    format!("the closure of this function resolves to {}", got),
+                    call_span,
+                ),
+                Label::context(format!("but the function expects {}", expected), call_span),
+            ],
         }
     }
 
@@ -3,11 +3,15 @@ use crate::expression::{
 };
 use crate::parser::Node;
 use crate::value::Kind;
-use crate::{Span, Value};
+use crate::{Span, State, Value};
 use diagnostic::{DiagnosticError, Label, Note};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
+pub mod closure;
+
+pub use closure::FunctionClosure;
+
 pub type Compiled = Result<Box<dyn Expression>, Box<dyn DiagnosticError>>;
 
 pub trait Function: Sync + fmt::Debug {
@@ -41,6 +45,16 @@ pub trait Function: Sync + fmt::Debug {
     /// resolved to its final [`Value`].
     fn compile(&self, arguments: ArgumentList) -> Compiled;
 
+    /// Compile a [`Function`] with access to the compiler [`State`], such as
+    /// the [external context](State::get_external_context) provided by the
+    /// program embedding VRL.
+    ///
+    /// The compiler calls this function, which defaults to
+    /// [`Function::compile`] for functions that only need their arguments.
+    fn compile_with_state(&self, _state: &State, arguments: ArgumentList) -> Compiled {
+        self.compile(arguments)
+    }
+
     /// An optional list of parameters the function accepts.
     ///
     /// This list is used at compile-time to check function arity, keyword names
@@ -48,6 +62,14 @@ pub trait Function: Sync + fmt::Debug {
     fn parameters(&self) -> &'static [Parameter] {
         &[]
     }
+
+    /// The closure the function accepts, if any.
+    ///
+    /// Functions defining a closure can only be called with one attached, which
+    /// they receive through [`ArgumentList::required_closure`].
+    fn closure(&self) -> Option<closure::Definition> {
+        None
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -91,7 +113,10 @@ impl Parameter {
 // -----------------------------------------------------------------------------
 
 #[derive(Debug, Default)]
-pub struct ArgumentList(HashMap<&'static str, Expr>);
+pub struct ArgumentList {
+    arguments: HashMap<&'static str, Expr>,
+    closure: Option<FunctionClosure>,
+}
 
 impl ArgumentList {
     pub fn optional(&mut self, keyword: &'static str) -> Option<Box<dyn Expression>> {
@@ -211,16 +236,53 @@ impl ArgumentList {
         Ok(required(self.optional_array(keyword)?))
     }
 
+    pub fn optional_object(
+        &mut self,
+        keyword: &'static str,
+    ) -> Result<Option<BTreeMap<String, Expr>>, Error> {
+        self.optional_expr(keyword)
+            .map(|expr| match expr {
+                Expr::Container(Container {
+                    variant: Variant::Object(object),
+                }) => Ok((*object).clone()),
+                expr => Err(Error::UnexpectedExpression {
+                    keyword,
+                    expected: "object",
+                    expr,
+                }),
+            })
+            .transpose()
+    }
+
+    pub fn required_object(
+        &mut self,
+        keyword: &'static str,
+    ) -> Result<BTreeMap<String, Expr>, Error> {
+        Ok(required(self.optional_object(keyword)?))
+    }
+
+    pub fn optional_closure(&mut self) -> Option<FunctionClosure> {
+        self.closure.take()
+    }
+
+    pub fn required_closure(&mut self) -> FunctionClosure {
+        required(self.optional_closure())
+    }
+
     pub(crate) fn keywords(&self) -> Vec<&'static str> {
-        self.0.keys().copied().collect::<Vec<_>>()
+        self.arguments.keys().copied().collect::<Vec<_>>()
     }
 
     pub(crate) fn insert(&mut self, k: &'static str, v: Expr) {
-        self.0.insert(k, v);
+        self.arguments.insert(k, v);
+    }
+
+    pub(crate) fn set_closure(&mut self, closure: FunctionClosure) {
+        self.closure = Some(closure);
     }
 
     fn optional_expr(&mut self, keyword: &'static str) -> Option<Expr> {
-        self.0.remove(keyword)
+        self.arguments.remove(keyword)
     }
 
     fn required_expr(&mut self, keyword: &'static str) -> Expr {
@@ -234,11 +296,13 @@ fn required<T>(argument: Option<T>) -> T {
 
 impl From<HashMap<&'static str, Value>> for ArgumentList {
     fn from(map: HashMap<&'static str, Value>) -> Self {
-        Self(
-            map.into_iter()
+        Self {
+            arguments: map
+                .into_iter()
                 .map(|(k, v)| (k, v.into_expr()))
                 .collect::<HashMap<_, _>>(),
-        )
+            closure: None,
+        }
     }
 }
 
@@ -256,7 +320,10 @@ impl From<Vec<Node<FunctionArgument>>> for ArgumentList {
             })
             .collect::<HashMap<_, _>>();
 
-        Self(arguments)
+        Self {
+            arguments,
+            closure: None,
+        }
     }
 }
 
@@ -0,0 +1,149 @@
+use crate::expression::{Block, Resolved};
+use crate::parser::Ident;
+use crate::value::Kind;
+use crate::{Context, Expression, TypeDef, Value};
+use std::fmt;
+
+/// The definition of the closure a function accepts.
+///
+/// The compiler uses it to type the variables of the closure attached to a
+/// function call, and to check the kind of value the closure resolves to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Definition {
+    /// The inputs the closure can be called with, one for each kind of
+    /// argument the function can iterate.
+    pub inputs: Vec<Input>,
+
+    /// The kinds of value the closure has to resolve to.
+    pub output: Kind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input {
+    /// The keyword of the parameter whose argument the closure iterates.
+    pub parameter_keyword: &'static str,
+
+    /// The kind of argument for which this input applies.
+    pub kind: Kind,
+
+    /// The variables the closure receives for each element of the argument.
+    pub variables: Vec<Variable>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Variable {
+    /// The variable holds a value of the given kind, such as the key of an
+    /// object field, or the index of an array element.
+    Exact(Kind),
+
+    /// The variable holds an element of the iterated argument, typed after the
+    /// values the argument is known to contain.
+    TargetInnerValue,
+}
+
+impl Variable {
+    pub(crate) fn type_def(&self, target: &TypeDef) -> TypeDef {
+        match self {
+            Variable::Exact(kind) => TypeDef::from(*kind),
+            Variable::TargetInnerValue => target.collection_values(),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A closure attached to a function call, compiled to be run by the function
+/// for each element of the value it iterates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionClosure {
+    variables: Vec<Ident>,
+    block: Block,
+    type_def: TypeDef,
+}
+
+impl FunctionClosure {
+    pub(crate) fn new(variables: Vec<Ident>, block: Block, type_def: TypeDef) -> Self {
+        Self {
+            variables,
+            block,
+            type_def,
+        }
+    }
+
+    /// The type definition of the value the closure resolves to.
+    ///
+    /// This is determined at compile-time, with the closure variables typed
+    /// after the argument the function iterates.
+    pub fn type_def(&self) -> &TypeDef {
+        &self.type_def
+    }
+
+    /// Run the closure with a value of the collection it iterates.
+    pub fn run_value(&self, ctx: &mut Context, value: &Value) -> Resolved {
+        self.run(ctx, vec![value.clone()])
+    }
+
+    /// Run the closure with the key of an object field.
+    pub fn run_key(&self, ctx: &mut Context, key: &str) -> Resolved {
+        self.run(ctx, vec![key.into()])
+    }
+
+    /// Run the closure with the key and value of an object field.
+    pub fn run_key_value(&self, ctx: &mut Context, key: &str, value: &Value) -> Resolved {
+        self.run(ctx, vec![key.into(), value.clone()])
+    }
+
+    /// Run the closure with the index and value of an array element.
+    pub fn run_index_value(&self, ctx: &mut Context, index: usize, value: &Value) -> Resolved {
+        self.run(ctx, vec![(index as i64).into(), value.clone()])
+    }
+
+    /// Resolve the closure block with the given values assigned to the closure
+    /// variables.
+    ///
+    /// Variables shadowed by the closure get their previous values back once
+    /// the block is resolved.
+    fn run(&self, ctx: &mut Context, values: Vec<Value>) -> Resolved {
+        let shadowed = self
+            .variables
+            .iter()
+            .zip(values)
+            .map(|(ident, value)| {
+                let state = ctx.state_mut();
+                let previous = state.variable(ident).cloned();
+                state.insert_variable(ident.clone(), value);
+
+                previous
+            })
+            .collect::<Vec<_>>();
+
+        let resolved = self.block.resolve(ctx);
+
+        let state = ctx.state_mut();
+        for (ident, previous) in self.variables.iter().zip(shadowed) {
+            match previous {
+                Some(value) => state.insert_variable(ident.clone(), value),
+                None => state.remove_variable(ident),
+            }
+        }
+
+        resolved
+    }
+}
+
+impl fmt::Display for FunctionClosure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("-> |")?;
+
+        let mut iter = self.variables.iter().peekable();
+        while let Some(variable) = iter.next() {
+            variable.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        write!(f, "| {}", self.block)
+    }
+}
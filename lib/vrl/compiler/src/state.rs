@@ -1,6 +1,8 @@
 use crate::expression::assignment;
 use crate::{parser::ast::Ident, TypeDef, Value};
+use std::any::Any;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// The state held by the compiler.
 ///
@@ -14,6 +16,10 @@ pub struct Compiler {
     // stored internal variable type definitions
     variables: HashMap<Ident, assignment::Details>,
 
+    /// Context provided by the program embedding VRL, which functions can use
+    /// at compile-time.
+    external_context: Option<Arc<dyn Any + Send + Sync>>,
+
     /// On request, the compiler can store its state in this field, which can
     /// later be used to revert the compiler state to the previously stored
     /// state.
@@ -36,6 +42,7 @@ impl Compiler {
                 value: None,
             }),
             variables: HashMap::new(),
+            external_context: None,
             snapshot: None,
         }
     }
@@ -48,6 +55,10 @@ impl Compiler {
         self.variables.insert(ident, details);
     }
 
+    pub(crate) fn remove_variable(&mut self, ident: &Ident) {
+        self.variables.remove(ident);
+    }
+
     pub(crate) fn target(&self) -> Option<&assignment::Details> {
         self.target.as_ref()
     }
@@ -62,10 +73,12 @@ impl Compiler {
     pub(crate) fn snapshot(&mut self) {
         let target = self.target.clone();
         let variables = self.variables.clone();
+        let external_context = self.external_context.clone();
 
         let snapshot = Self {
             target,
             variables,
+            external_context,
             snapshot: None,
         };
 
@@ -83,6 +96,18 @@ impl Compiler {
     pub fn target_type_def(&self) -> Option<&TypeDef> {
         self.target.as_ref().map(|assignment| &assignment.type_def)
     }
+
+    /// Sets the external context, replacing any set before.
+    pub fn set_external_context<T: Any + Send + Sync>(&mut self, context: T) {
+        self.external_context = Some(Arc::new(context));
+    }
+
+    /// Returns the external context, if one of type `T` has been set.
+    pub fn get_external_context<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.external_context
+            .as_ref()
+            .and_then(|context| context.downcast_ref())
+    }
 }
 
 /// The state used at runtime to track changes as they happen.
@@ -104,4 +129,8 @@ impl Runtime {
     pub(crate) fn insert_variable(&mut self, ident: Ident, value: Value) {
         self.variables.insert(ident, value);
     }
+
+    pub(crate) fn remove_variable(&mut self, ident: &Ident) {
+        self.variables.remove(ident);
+    }
 }
@@ -813,6 +813,27 @@ impl TypeDef {
         }
     }
 
+    /// Keep only the arrays and objects of this type definition.
+    ///
+    /// If the type definition is unknown, it becomes an array or object of
+    /// unknown values.
+    #[inline]
+    pub fn restrict_collections(mut self) -> Self {
+        match self.kind {
+            KindInfo::Known(set) => {
+                self.kind = KindInfo::Known(
+                    set.into_iter()
+                        .filter(|k| matches!(k, TypeKind::Array(_) | TypeKind::Object(_)))
+                        .collect(),
+                );
+                self
+            }
+            KindInfo::Unknown => self
+                .array_mapped::<(), Kind>(map! { (): Kind::all() })
+                .add_object::<(), Kind>(map! { (): Kind::all() }),
+        }
+    }
+
     fn add_container(mut self, kind: TypeKind) -> Self {
         debug_assert!(matches!(kind, TypeKind::Array(_) | TypeKind::Object(_)));
 
@@ -863,6 +884,42 @@ impl TypeDef {
         self
     }
 
+    /// The type of the values held by the arrays and objects of this type
+    /// definition, collected into a single type.
+    ///
+    /// Used for the elements a closure receives when iterating a collection.
+    /// If any of the values are of unknown type, or no values are known at
+    /// all, the values can be of any type.
+    pub fn collection_values(&self) -> Self {
+        let values = match &self.kind {
+            KindInfo::Unknown => return Self::new().unknown(),
+            KindInfo::Known(set) => set
+                .iter()
+                .flat_map(|kind| match kind {
+                    TypeKind::Array(array) => array.values().collect::<Vec<_>>(),
+                    TypeKind::Object(object) => object.values().collect(),
+                    _ => vec![],
+                })
+                .cloned()
+                .collect::<Vec<_>>(),
+        };
+
+        if values.is_empty() || values.contains(&KindInfo::Unknown) {
+            return Self::new().unknown();
+        }
+
+        let kind = values
+            .into_iter()
+            .fold(KindInfo::Known(BTreeSet::new()), |acc, kind| {
+                acc.merge(kind, false, true)
+            });
+
+        Self {
+            fallible: false,
+            kind,
+        }
+    }
+
     #[inline]
     pub fn is_unknown(&self) -> bool {
         matches!(self.kind, KindInfo::Unknown)
@@ -1172,6 +1229,28 @@ impl TypeDef {
             kind: newkind,
         }
     }
+
+    /// For any object defined by this type def, allows you to map the field
+    /// kind to a new kind.
+    pub fn map_object<F>(&self, f: F) -> Self
+    where
+        F: Fn(&KindInfo) -> KindInfo,
+    {
+        let newkind = self.kind.map(|k| match k {
+            TypeKind::Object(object) => TypeKind::Object(
+                object
+                    .iter()
+                    .map(|(field, kind)| (field.clone(), f(kind)))
+                    .collect(),
+            ),
+            k => k.clone(),
+        });
+
+        Self {
+            fallible: self.fallible,
+            kind: newkind,
+        }
+    }
 }
 
 impl Default for TypeDef {
@@ -1228,6 +1307,44 @@ mod tests {
         assert_eq!(kind, expected);
     }
 
+    #[test]
+    fn collection_values() {
+        let type_def = TypeDef::new()
+            .array::<TypeDef>(vec![Kind::Integer.into(), Kind::Bytes.into()])
+            .add_object::<&'static str, Kind>(btreemap! { "foo" => Kind::Float });
+        assert_eq!(
+            type_def.collection_values(),
+            TypeDef::from(Kind::Integer | Kind::Bytes | Kind::Float)
+        );
+
+        let type_def = TypeDef::new().object::<&'static str, TypeDef>(btreemap! {
+            "foo" => TypeDef::from(Kind::Boolean),
+            "bar" => TypeDef::new().unknown(),
+        });
+        assert!(type_def.collection_values().is_unknown());
+
+        assert!(TypeDef::new().bytes().collection_values().is_unknown());
+    }
+
+    #[test]
+    fn restrict_collections() {
+        let type_def = TypeDef::new()
+            .bytes()
+            .add_null()
+            .add_array::<TypeDef>(vec![Kind::Integer.into()]);
+        assert_eq!(
+            type_def.restrict_collections(),
+            TypeDef::new().array::<TypeDef>(vec![Kind::Integer.into()])
+        );
+
+        assert_eq!(
+            TypeDef::new().unknown().restrict_collections(),
+            TypeDef::new()
+                .array_mapped::<(), Kind>(map! { (): Kind::all() })
+                .add_object::<(), Kind>(map! { (): Kind::all() })
+        );
+    }
+
     #[test]
     fn update_path() {
         struct TestCase {
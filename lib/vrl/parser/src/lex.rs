@@ -1659,6 +1659,31 @@ mod test {
         );
     }
 
+    #[test]
+    fn function_call_closure() {
+        test(
+            data(r#"foo(.a) -> |k, v| { v }"#),
+            vec![
+                (r#"~~~                    "#, FunctionCall("foo")),
+                (r#"   ~                   "#, LParen),
+                (r#"    ~                  "#, LQuery),
+                (r#"    ~                  "#, Dot),
+                (r#"     ~                 "#, Identifier("a")),
+                (r#"     ~                 "#, RQuery),
+                (r#"      ~                "#, RParen),
+                (r#"        ~~             "#, Operator("->")),
+                (r#"           ~           "#, Operator("|")),
+                (r#"            ~          "#, Identifier("k")),
+                (r#"             ~         "#, Comma),
+                (r#"               ~       "#, Identifier("v")),
+                (r#"                ~      "#, Operator("|")),
+                (r#"                  ~    "#, LBrace),
+                (r#"                    ~  "#, Identifier("v")),
+                (r#"                      ~"#, RBrace),
+            ],
+        );
+    }
+
     #[test]
     fn function_call_queries() {
         use StringLiteral as S;
@@ -0,0 +1,271 @@
+use enrichment::{Case, Condition, IndexHandle, TableRegistry, TableSearch};
+use std::collections::BTreeMap;
+use std::fmt;
+use vrl::{
+    diagnostic::{Label, Note, Span},
+    prelude::{expression::Expr, *},
+};
+
+pub(crate) const PARAMETERS: &[Parameter] = &[
+    Parameter {
+        keyword: "table",
+        kind: kind::BYTES,
+        required: true,
+    },
+    Parameter {
+        keyword: "condition",
+        kind: kind::OBJECT,
+        required: true,
+    },
+    Parameter {
+        keyword: "case_sensitive",
+        kind: kind::BOOLEAN,
+        required: false,
+    },
+];
+
+#[derive(Debug)]
+pub(crate) enum Error {
+    TablesUnavailable,
+    InvalidCondition { table: String, error: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::TablesUnavailable => f.write_str("enrichment tables aren't available"),
+            Error::InvalidCondition { table, error } => write!(
+                f,
+                r#"invalid condition for enrichment table "{}": {}"#,
+                table, error
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl DiagnosticError for Error {
+    fn code(&self) -> usize {
+        111
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        vec![Label::primary(self.to_string(), Span::default())]
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        match self {
+            Error::TablesUnavailable => vec![Note::Hint(
+                "enrichment tables can only be searched by programs run within Vector".to_owned(),
+            )],
+            Error::InvalidCondition { .. } => vec![],
+        }
+    }
+}
+
+/// A search of an enrichment table, with its arguments checked against the
+/// table at compile-time.
+#[derive(Debug, Clone)]
+pub(crate) struct EnrichmentTableSearch {
+    pub(crate) table: String,
+    pub(crate) condition: BTreeMap<String, Expr>,
+    pub(crate) case: Case,
+    pub(crate) index: IndexHandle,
+    pub(crate) search: TableSearch,
+}
+
+impl EnrichmentTableSearch {
+    pub(crate) fn compile(
+        state: &state::Compiler,
+        arguments: &mut ArgumentList,
+    ) -> std::result::Result<Self, Box<dyn DiagnosticError>> {
+        let registry = state
+            .get_external_context::<TableRegistry>()
+            .ok_or_else(|| Box::new(Error::TablesUnavailable) as Box<dyn DiagnosticError>)?;
+
+        let tables = registry
+            .table_ids()
+            .into_iter()
+            .map(Value::from)
+            .collect::<Vec<_>>();
+        let table = arguments
+            .required_enum("table", &tables)?
+            .try_bytes_utf8_lossy()
+            .expect("table not bytes")
+            .into_owned();
+
+        let condition = arguments.required_object("condition")?;
+
+        let case = match arguments.optional_literal("case_sensitive")? {
+            Some(literal) if !literal.to_value().try_boolean().expect("not boolean") => {
+                Case::Insensitive
+            }
+            _ => Case::Sensitive,
+        };
+
+        let fields = condition.keys().map(String::as_str).collect::<Vec<_>>();
+        let index = registry.add_index(&table, case, &fields).map_err(|error| {
+            Box::new(Error::InvalidCondition {
+                table: table.clone(),
+                error,
+            }) as Box<dyn DiagnosticError>
+        })?;
+
+        Ok(Self {
+            table,
+            condition,
+            case,
+            index,
+            search: registry.as_readonly(),
+        })
+    }
+
+    pub(crate) fn conditions(&self, ctx: &mut Context) -> Result<Vec<Condition>> {
+        self.condition
+            .iter()
+            .map(|(field, expr)| {
+                Ok(Condition::Equals {
+                    field,
+                    value: expr.resolve(ctx)?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use enrichment::{tables::TableMap, Table};
+    use shared::TimeZone;
+
+    /// A table of the rows `{"id": "1", "name": "one"}` and
+    /// `{"id": "1", "name": "uno"}`.
+    #[derive(Debug)]
+    struct Numbers;
+
+    impl Table for Numbers {
+        fn find_table_row(
+            &self,
+            case: Case,
+            condition: &[Condition],
+            index: Option<&IndexHandle>,
+        ) -> std::result::Result<BTreeMap<String, Value>, String> {
+            let mut rows = self.find_table_rows(case, condition, index)?;
+            match rows.len() {
+                1 => Ok(rows.remove(0)),
+                _ => Err("no single row found".to_owned()),
+            }
+        }
+
+        fn find_table_rows(
+            &self,
+            _case: Case,
+            condition: &[Condition],
+            _index: Option<&IndexHandle>,
+        ) -> std::result::Result<Vec<BTreeMap<String, Value>>, String> {
+            let rows = vec![
+                map! { "id": "1", "name": "one" },
+                map! { "id": "1", "name": "uno" },
+            ];
+            Ok(rows
+                .into_iter()
+                .filter(|row| {
+                    condition
+                        .iter()
+                        .all(|Condition::Equals { field, value }| row.get(*field) == Some(value))
+                })
+                .collect())
+        }
+
+        fn add_index(
+            &mut self,
+            case: Case,
+            fields: &[&str],
+        ) -> std::result::Result<IndexHandle, String> {
+            match fields.iter().find(|field| !["id", "name"].contains(field)) {
+                Some(field) => Err(format!("field \"{}\" doesn't exist", field)),
+                None => Ok(IndexHandle {
+                    case,
+                    fields: fields.iter().map(ToString::to_string).collect(),
+                }),
+            }
+        }
+
+        fn indexes(&self) -> Vec<IndexHandle> {
+            vec![]
+        }
+    }
+
+    fn run(source: &str) -> std::result::Result<Value, String> {
+        let registry = TableRegistry::default();
+        let mut tables = TableMap::new();
+        tables.insert("numbers".to_owned(), Box::new(Numbers) as _);
+        registry.load(tables);
+
+        let mut state = state::Compiler::default();
+        state.set_external_context(registry.clone());
+        let program = vrl::compile_with_state(source, &crate::all(), &mut state)
+            .map_err(|diagnostics| diagnostics[0].message())?;
+        registry.finish_load();
+
+        let mut target = Value::from(map! { "id": "1" });
+        vrl::Runtime::default()
+            .resolve(&mut target, &program, &TimeZone::default())
+            .map_err(|error| error.to_string())
+    }
+
+    #[test]
+    fn get_record() {
+        assert_eq!(
+            run(r#"get_enrichment_table_record!("numbers", { "id": .id, "name": "uno" })"#),
+            Ok(value!({ "id": "1", "name": "uno" }))
+        );
+        assert!(
+            run(r#"get_enrichment_table_record!("numbers", { "id": .id })"#)
+                .unwrap_err()
+                .contains("no single row found")
+        );
+    }
+
+    #[test]
+    fn find_records() {
+        assert_eq!(
+            run(r#"find_enrichment_table_records!("numbers", { "id": .id })"#),
+            Ok(value!([{ "id": "1", "name": "one" }, { "id": "1", "name": "uno" }]))
+        );
+        assert_eq!(
+            run(r#"find_enrichment_table_records!("numbers", { "name": "two" })"#),
+            Ok(value!([]))
+        );
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert_eq!(
+            run(r#"find_enrichment_table_records!("letters", { "id": .id })"#),
+            Err(r#"function compilation error: error[E401] invalid enum variant""#.to_owned())
+        );
+        assert_eq!(
+            run(r#"find_enrichment_table_records!("numbers", { "number": .id })"#),
+            Err(
+                r#"function compilation error: error[E111] invalid condition for enrichment table "numbers": field "number" doesn't exist"#
+                    .to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn tables_unavailable() {
+        let error = vrl::compile(
+            r#"get_enrichment_table_record!("numbers", { "id": .id })"#,
+            &crate::all(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error[0].message(),
+            "function compilation error: error[E111] enrichment tables aren't available"
+        );
+    }
+}
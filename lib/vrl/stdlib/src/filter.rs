@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Filter;
+
+impl Function for Filter {
+    fn identifier(&self) -> &'static str {
+        "filter"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "filter object",
+                source: r#"filter({ "a": 1, "b": 2 }) -> |key, _value| { key != "a" }"#,
+                result: Ok(r#"{ "b": 2 }"#),
+            },
+            Example {
+                title: "filter array",
+                source: r#"filter([1, 2, 3, 4]) -> |_index, value| { value > 2 }"#,
+                result: Ok("[3, 4]"),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(FilterFn { value, closure }))
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Variable};
+
+        Some(Definition {
+            inputs: vec![
+                Input {
+                    parameter_keyword: "value",
+                    kind: Kind::Object,
+                    variables: vec![Variable::Exact(Kind::Bytes), Variable::TargetInnerValue],
+                },
+                Input {
+                    parameter_keyword: "value",
+                    kind: Kind::Array,
+                    variables: vec![Variable::Exact(Kind::Integer), Variable::TargetInnerValue],
+                },
+            ],
+            output: Kind::Boolean,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FilterFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl Expression for FilterFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        match self.value.resolve(ctx)? {
+            Value::Object(object) => {
+                let mut filtered = BTreeMap::new();
+
+                for (key, value) in object {
+                    if self
+                        .closure
+                        .run_key_value(ctx, &key, &value)?
+                        .try_boolean()?
+                    {
+                        filtered.insert(key, value);
+                    }
+                }
+
+                Ok(filtered.into())
+            }
+            Value::Array(array) => {
+                let mut filtered = Vec::new();
+
+                for (index, value) in array.into_iter().enumerate() {
+                    if self
+                        .closure
+                        .run_index_value(ctx, index, &value)?
+                        .try_boolean()?
+                    {
+                        filtered.push(value);
+                    }
+                }
+
+                Ok(filtered.into())
+            }
+            value => Err(value::Error::Expected {
+                got: value.kind(),
+                expected: Kind::Object | Kind::Array,
+            }
+            .into()),
+        }
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.value
+            .type_def(state)
+            .restrict_collections()
+            .collect_subtypes()
+            .infallible()
+    }
+}
@@ -0,0 +1,58 @@
+use crate::enrichment_util::{EnrichmentTableSearch, PARAMETERS};
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct FindEnrichmentTableRecords;
+
+impl Function for FindEnrichmentTableRecords {
+    fn identifier(&self) -> &'static str {
+        "find_enrichment_table_records"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        // Searching needs an enrichment table configured in Vector.
+        &[]
+    }
+
+    fn compile(&self, arguments: ArgumentList) -> Compiled {
+        self.compile_with_state(&state::Compiler::default(), arguments)
+    }
+
+    fn compile_with_state(&self, state: &state::Compiler, mut arguments: ArgumentList) -> Compiled {
+        let search = EnrichmentTableSearch::compile(state, &mut arguments)?;
+
+        Ok(Box::new(FindEnrichmentTableRecordsFn { search }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FindEnrichmentTableRecordsFn {
+    search: EnrichmentTableSearch,
+}
+
+impl Expression for FindEnrichmentTableRecordsFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let condition = self.search.conditions(ctx)?;
+
+        self.search
+            .search
+            .find_table_rows(
+                &self.search.table,
+                self.search.case,
+                &condition,
+                Some(&self.search.index),
+            )
+            .map(|rows| Value::Array(rows.into_iter().map(Value::Object).collect()))
+            .map_err(Into::into)
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new()
+            .fallible()
+            .array_mapped::<(), Kind>(map! { (): Kind::Object })
+    }
+}
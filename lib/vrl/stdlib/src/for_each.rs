@@ -0,0 +1,108 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ForEach;
+
+impl Function for ForEach {
+    fn identifier(&self) -> &'static str {
+        "for_each"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "iterate object",
+                source: indoc! {r#"
+                    count = 0
+                    for_each({ "a": 1, "b": 2 }) -> |_key, value| {
+                        count = count + value
+                    }
+                    count
+                "#},
+                result: Ok("3"),
+            },
+            Example {
+                title: "iterate array",
+                source: indoc! {r#"
+                    indexes = []
+                    for_each(["a", "b"]) -> |index, _value| {
+                        indexes = push(indexes, index)
+                    }
+                    indexes
+                "#},
+                result: Ok("[0, 1]"),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(ForEachFn { value, closure }))
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Variable};
+
+        Some(Definition {
+            inputs: vec![
+                Input {
+                    parameter_keyword: "value",
+                    kind: Kind::Object,
+                    variables: vec![Variable::Exact(Kind::Bytes), Variable::TargetInnerValue],
+                },
+                Input {
+                    parameter_keyword: "value",
+                    kind: Kind::Array,
+                    variables: vec![Variable::Exact(Kind::Integer), Variable::TargetInnerValue],
+                },
+            ],
+            output: Kind::all(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ForEachFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl Expression for ForEachFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        match self.value.resolve(ctx)? {
+            Value::Object(object) => {
+                for (key, value) in &object {
+                    self.closure.run_key_value(ctx, key, value)?;
+                }
+            }
+            Value::Array(array) => {
+                for (index, value) in array.iter().enumerate() {
+                    self.closure.run_index_value(ctx, index, value)?;
+                }
+            }
+            value => {
+                return Err(value::Error::Expected {
+                    got: value.kind(),
+                    expected: Kind::Object | Kind::Array,
+                }
+                .into())
+            }
+        }
+
+        Ok(Value::Null)
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().null()
+    }
+}
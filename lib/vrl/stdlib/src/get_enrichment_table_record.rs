@@ -0,0 +1,58 @@
+use crate::enrichment_util::{EnrichmentTableSearch, PARAMETERS};
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetEnrichmentTableRecord;
+
+impl Function for GetEnrichmentTableRecord {
+    fn identifier(&self) -> &'static str {
+        "get_enrichment_table_record"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        // Searching needs an enrichment table configured in Vector.
+        &[]
+    }
+
+    fn compile(&self, arguments: ArgumentList) -> Compiled {
+        self.compile_with_state(&state::Compiler::default(), arguments)
+    }
+
+    fn compile_with_state(&self, state: &state::Compiler, mut arguments: ArgumentList) -> Compiled {
+        let search = EnrichmentTableSearch::compile(state, &mut arguments)?;
+
+        Ok(Box::new(GetEnrichmentTableRecordFn { search }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GetEnrichmentTableRecordFn {
+    search: EnrichmentTableSearch,
+}
+
+impl Expression for GetEnrichmentTableRecordFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let condition = self.search.conditions(ctx)?;
+
+        self.search
+            .search
+            .find_table_row(
+                &self.search.table,
+                self.search.case,
+                &condition,
+                Some(&self.search.index),
+            )
+            .map(Value::Object)
+            .map_err(Into::into)
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().object::<(), Kind>(map! {
+            (): Kind::all()
+        })
+    }
+}
@@ -36,20 +36,33 @@ mod encode_logfmt;
 mod encode_percent;
 #[cfg(feature = "ends_with")]
 mod ends_with;
+#[cfg(any(
+    feature = "find_enrichment_table_records",
+    feature = "get_enrichment_table_record"
+))]
+mod enrichment_util;
 #[cfg(feature = "exists")]
 mod exists;
+#[cfg(feature = "filter")]
+mod filter;
+#[cfg(feature = "find_enrichment_table_records")]
+mod find_enrichment_table_records;
 #[cfg(feature = "flatten")]
 mod flatten;
 #[cfg(feature = "float")]
 mod float;
 #[cfg(feature = "floor")]
 mod floor;
+#[cfg(feature = "for_each")]
+mod for_each;
 #[cfg(feature = "format_int")]
 mod format_int;
 #[cfg(feature = "format_number")]
 mod format_number;
 #[cfg(feature = "format_timestamp")]
 mod format_timestamp;
+#[cfg(feature = "get_enrichment_table_record")]
+mod get_enrichment_table_record;
 #[cfg(feature = "get_env_var")]
 mod get_env_var;
 #[cfg(feature = "get_hostname")]
@@ -102,6 +115,10 @@ mod log;
     feature = "parse_nginx_log"
 ))]
 mod log_util;
+#[cfg(feature = "map_keys")]
+mod map_keys;
+#[cfg(feature = "map_values")]
+mod map_values;
 #[cfg(feature = "match")]
 mod r#match;
 #[cfg(feature = "match_any")]
@@ -271,18 +288,26 @@ pub use encode_percent::EncodePercent;
 pub use ends_with::EndsWith;
 #[cfg(feature = "exists")]
 pub use exists::Exists;
+#[cfg(feature = "filter")]
+pub use filter::Filter;
+#[cfg(feature = "find_enrichment_table_records")]
+pub use find_enrichment_table_records::FindEnrichmentTableRecords;
 #[cfg(feature = "flatten")]
 pub use flatten::Flatten;
 #[cfg(feature = "float")]
 pub use float::Float;
 #[cfg(feature = "floor")]
 pub use floor::Floor;
+#[cfg(feature = "for_each")]
+pub use for_each::ForEach;
 #[cfg(feature = "format_int")]
 pub use format_int::FormatInt;
 #[cfg(feature = "format_number")]
 pub use format_number::FormatNumber;
 #[cfg(feature = "format_timestamp")]
 pub use format_timestamp::FormatTimestamp;
+#[cfg(feature = "get_enrichment_table_record")]
+pub use get_enrichment_table_record::GetEnrichmentTableRecord;
 #[cfg(feature = "get_env_var")]
 pub use get_env_var::GetEnvVar;
 #[cfg(feature = "get_hostname")]
@@ -329,6 +354,10 @@ pub use join::Join;
 pub use length::Length;
 #[cfg(feature = "log")]
 pub use log::Log;
+#[cfg(feature = "map_keys")]
+pub use map_keys::MapKeys;
+#[cfg(feature = "map_values")]
+pub use map_values::MapValues;
 #[cfg(feature = "match_any")]
 pub use match_any::MatchAny;
 #[cfg(feature = "match_array")]
@@ -490,18 +519,26 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(EndsWith),
         #[cfg(feature = "exists")]
         Box::new(Exists),
+        #[cfg(feature = "filter")]
+        Box::new(Filter),
+        #[cfg(feature = "find_enrichment_table_records")]
+        Box::new(FindEnrichmentTableRecords),
         #[cfg(feature = "flatten")]
         Box::new(Flatten),
         #[cfg(feature = "float")]
         Box::new(Float),
         #[cfg(feature = "floor")]
         Box::new(Floor),
+        #[cfg(feature = "for_each")]
+        Box::new(ForEach),
         #[cfg(feature = "format_int")]
         Box::new(FormatInt),
         #[cfg(feature = "format_number")]
         Box::new(FormatNumber),
         #[cfg(feature = "format_timestamp")]
         Box::new(FormatTimestamp),
+        #[cfg(feature = "get_enrichment_table_record")]
+        Box::new(GetEnrichmentTableRecord),
         #[cfg(feature = "get_env_var")]
         Box::new(GetEnvVar),
         #[cfg(feature = "get_hostname")]
@@ -548,6 +585,10 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Length),
         #[cfg(feature = "log")]
         Box::new(Log),
+        #[cfg(feature = "map_keys")]
+        Box::new(MapKeys),
+        #[cfg(feature = "map_values")]
+        Box::new(MapValues),
         #[cfg(feature = "match")]
         Box::new(Match),
         #[cfg(feature = "match_any")]
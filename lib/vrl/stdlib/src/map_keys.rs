@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MapKeys;
+
+impl Function for MapKeys {
+    fn identifier(&self) -> &'static str {
+        "map_keys"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "recursive",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "upcase keys",
+                source: r#"map_keys({ "foo": 1, "bar": 2 }) -> |key| { upcase(key) }"#,
+                result: Ok(r#"{ "FOO": 1, "BAR": 2 }"#),
+            },
+            Example {
+                title: "recursive",
+                source: r#"map_keys({ "a": { "b": 1 } }, recursive: true) -> |key| { upcase(key) }"#,
+                result: Ok(r#"{ "A": { "B": 1 } }"#),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let recursive = arguments.optional("recursive");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(MapKeysFn {
+            value,
+            recursive,
+            closure,
+        }))
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Variable};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::Object,
+                variables: vec![Variable::Exact(Kind::Bytes)],
+            }],
+            output: Kind::Bytes,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MapKeysFn {
+    value: Box<dyn Expression>,
+    recursive: Option<Box<dyn Expression>>,
+    closure: FunctionClosure,
+}
+
+impl MapKeysFn {
+    fn map_object(
+        &self,
+        ctx: &mut Context,
+        object: BTreeMap<String, Value>,
+        recursive: bool,
+    ) -> Result<BTreeMap<String, Value>> {
+        object
+            .into_iter()
+            .map(|(key, value)| {
+                let key = self
+                    .closure
+                    .run_key(ctx, &key)?
+                    .try_bytes_utf8_lossy()?
+                    .into_owned();
+                let value = if recursive {
+                    self.map_value(ctx, value)?
+                } else {
+                    value
+                };
+
+                Ok((key, value))
+            })
+            .collect()
+    }
+
+    /// Map the keys of the objects nested in the given value.
+    fn map_value(&self, ctx: &mut Context, value: Value) -> Resolved {
+        match value {
+            Value::Object(object) => Ok(self.map_object(ctx, object, true)?.into()),
+            Value::Array(array) => Ok(array
+                .into_iter()
+                .map(|value| self.map_value(ctx, value))
+                .collect::<Result<Vec<_>>>()?
+                .into()),
+            value => Ok(value),
+        }
+    }
+}
+
+impl Expression for MapKeysFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let recursive = match &self.recursive {
+            Some(expr) => expr.resolve(ctx)?.try_boolean()?,
+            None => false,
+        };
+        let object = self.value.resolve(ctx)?.try_object()?;
+
+        Ok(self.map_object(ctx, object, recursive)?.into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        // Keys of nested objects are unknown once mapped recursively.
+        if self.recursive.is_some() {
+            return TypeDef::new().object::<(), Kind>(map! { (): Kind::all() });
+        }
+
+        self.value
+            .type_def(state)
+            .restrict_object()
+            .collect_subtypes()
+            .infallible()
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MapValues;
+
+impl Function for MapValues {
+    fn identifier(&self) -> &'static str {
+        "map_values"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "object",
+                source: r#"map_values({ "a": 1, "b": 2 }) -> |value| { value * 10 }"#,
+                result: Ok(r#"{ "a": 10, "b": 20 }"#),
+            },
+            Example {
+                title: "array",
+                source: r#"map_values(["foo", "bar"]) -> |value| { upcase(value) }"#,
+                result: Ok(r#"["FOO", "BAR"]"#),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(MapValuesFn { value, closure }))
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Variable};
+
+        Some(Definition {
+            inputs: vec![
+                Input {
+                    parameter_keyword: "value",
+                    kind: Kind::Object,
+                    variables: vec![Variable::TargetInnerValue],
+                },
+                Input {
+                    parameter_keyword: "value",
+                    kind: Kind::Array,
+                    variables: vec![Variable::TargetInnerValue],
+                },
+            ],
+            output: Kind::all(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MapValuesFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl Expression for MapValuesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        match self.value.resolve(ctx)? {
+            Value::Object(object) => object
+                .into_iter()
+                .map(|(key, value)| Ok((key, self.closure.run_value(ctx, &value)?)))
+                .collect::<Result<BTreeMap<_, _>>>()
+                .map(Into::into),
+            Value::Array(array) => array
+                .iter()
+                .map(|value| self.closure.run_value(ctx, value))
+                .collect::<Result<Vec<_>>>()
+                .map(Into::into),
+            value => Err(value::Error::Expected {
+                got: value.kind(),
+                expected: Kind::Object | Kind::Array,
+            }
+            .into()),
+        }
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        let inner = self.closure.type_def().kind.clone();
+
+        self.value
+            .type_def(state)
+            .restrict_collections()
+            .collect_subtypes()
+            .map_array(|_| inner.clone())
+            .map_object(|_| inner.clone())
+            .infallible()
+    }
+}
@@ -16,6 +16,7 @@ pub enum SourceOutputType {
     Any,
     Log,
     Metric,
+    Trace,
 }
 
 impl From<DataType> for SourceOutputType {
@@ -24,6 +25,7 @@ impl From<DataType> for SourceOutputType {
             DataType::Metric => SourceOutputType::Metric,
             DataType::Log => SourceOutputType::Log,
             DataType::Any => SourceOutputType::Any,
+            DataType::Trace => SourceOutputType::Trace,
         }
     }
 }
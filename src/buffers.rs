@@ -1,16 +1,19 @@
 use crate::config::Resource;
 use crate::event::Event;
 use futures::Stream;
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
 use std::path::PathBuf;
 pub use vector_core::buffers::*;
 
+/// A single stage of a sink's buffer.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
-pub enum BufferConfig {
+pub enum BufferType {
     Memory {
-        #[serde(default = "BufferConfig::memory_max_events")]
+        #[serde(default = "BufferType::memory_max_events")]
         max_events: usize,
         #[serde(default)]
         when_full: WhenFull,
@@ -23,31 +26,24 @@ pub enum BufferConfig {
     },
 }
 
-impl Default for BufferConfig {
-    fn default() -> Self {
-        BufferConfig::Memory {
-            max_events: BufferConfig::memory_max_events(),
-            when_full: Default::default(),
-        }
-    }
-}
-
-pub(crate) type EventStream = Box<dyn Stream<Item = Event> + Unpin + Send>;
-
-impl BufferConfig {
+impl BufferType {
     #[inline]
     const fn memory_max_events() -> usize {
         500
     }
 
+    const fn when_full(&self) -> WhenFull {
+        match self {
+            BufferType::Memory { when_full, .. } => *when_full,
+            #[cfg(feature = "disk-buffer")]
+            BufferType::Disk { when_full, .. } => *when_full,
+        }
+    }
+
     #[cfg_attr(not(feature = "disk-buffer"), allow(unused))]
-    pub fn build(
-        &self,
-        data_dir: &Option<PathBuf>,
-        sink_name: &str,
-    ) -> Result<(BufferInputCloner<Event>, EventStream, Acker), String> {
-        let variant = match &self {
-            BufferConfig::Memory {
+    fn variant(&self, data_dir: &Option<PathBuf>, sink_name: &str) -> Result<Variant, String> {
+        Ok(match self {
+            BufferType::Memory {
                 max_events,
                 when_full,
             } => Variant::Memory {
@@ -55,7 +51,7 @@ impl BufferConfig {
                 when_full: *when_full,
             },
             #[cfg(feature = "disk-buffer")]
-            BufferConfig::Disk {
+            BufferType::Disk {
                 max_size,
                 when_full,
             } => Variant::Disk {
@@ -67,40 +63,185 @@ impl BufferConfig {
                     .to_path_buf(),
                 name: sink_name.to_string(),
             },
+        })
+    }
+}
+
+/// The buffer of a sink, made of one or more stages. Each stage but the last
+/// one overflows into the next stage when it's full.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "BufferStages", into = "BufferStages")]
+pub struct BufferConfig {
+    pub stages: Vec<BufferType>,
+}
+
+/// How a buffer is configured, either a table for a single stage or an array
+/// of tables for chained stages.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum BufferStages {
+    Single(BufferType),
+    Chained(Vec<BufferType>),
+}
+
+// Not derived with `untagged`, as that would hide the errors of the stages.
+impl<'de> Deserialize<'de> for BufferStages {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct StagesVisitor;
+
+        impl<'de> de::Visitor<'de> for StagesVisitor {
+            type Value = BufferStages;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("map or sequence of maps")
+            }
+
+            fn visit_map<M>(self, map: M) -> Result<Self::Value, M::Error>
+            where
+                M: de::MapAccess<'de>,
+            {
+                Deserialize::deserialize(de::value::MapAccessDeserializer::new(map))
+                    .map(BufferStages::Single)
+            }
+
+            fn visit_seq<S>(self, seq: S) -> Result<Self::Value, S::Error>
+            where
+                S: de::SeqAccess<'de>,
+            {
+                Deserialize::deserialize(de::value::SeqAccessDeserializer::new(seq))
+                    .map(BufferStages::Chained)
+            }
+        }
+
+        deserializer.deserialize_any(StagesVisitor)
+    }
+}
+
+impl TryFrom<BufferStages> for BufferConfig {
+    type Error = String;
+
+    fn try_from(stages: BufferStages) -> Result<Self, Self::Error> {
+        let stages = match stages {
+            BufferStages::Single(stage) => vec![stage],
+            BufferStages::Chained(stages) => stages,
         };
-        build(variant)
+
+        let (last, rest) = stages
+            .split_last()
+            .ok_or_else(|| "A buffer needs at least one stage.".to_string())?;
+        if last.when_full() == WhenFull::Overflow {
+            return Err(
+                "The last buffer stage has no stage to overflow into, `when_full` can't be `overflow`."
+                    .to_string(),
+            );
+        }
+        for stage in rest {
+            match stage {
+                BufferType::Memory { when_full, .. } if *when_full != WhenFull::Overflow => {
+                    return Err(
+                        "All buffer stages but the last must have `when_full` set to `overflow`."
+                            .to_string(),
+                    );
+                }
+                BufferType::Memory { .. } => {}
+                #[cfg(feature = "disk-buffer")]
+                BufferType::Disk { .. } => {
+                    return Err("Only memory buffer stages can overflow.".to_string());
+                }
+            }
+        }
+
+        Ok(BufferConfig { stages })
+    }
+}
+
+impl From<BufferConfig> for BufferStages {
+    fn from(config: BufferConfig) -> Self {
+        let mut stages = config.stages;
+        if stages.len() == 1 {
+            BufferStages::Single(stages.remove(0))
+        } else {
+            BufferStages::Chained(stages)
+        }
+    }
+}
+
+impl Default for BufferConfig {
+    fn default() -> Self {
+        BufferConfig {
+            stages: vec![BufferType::Memory {
+                max_events: BufferType::memory_max_events(),
+                when_full: Default::default(),
+            }],
+        }
+    }
+}
+
+pub(crate) type EventStream = Box<dyn Stream<Item = Event> + Unpin + Send>;
+
+impl BufferConfig {
+    pub fn build(
+        &self,
+        data_dir: &Option<PathBuf>,
+        sink_name: &str,
+    ) -> Result<(BufferInputCloner<Event>, EventStream, Acker), String> {
+        let variants = self
+            .stages
+            .iter()
+            .map(|stage| stage.variant(data_dir, sink_name))
+            .collect::<Result<Vec<_>, _>>()?;
+        build_chain(variants)
     }
 
     /// Resources that the sink is using.
     #[cfg_attr(not(feature = "disk-buffer"), allow(unused))]
     pub fn resources(&self, sink_name: &str) -> Vec<Resource> {
-        match self {
-            BufferConfig::Memory { .. } => Vec::new(),
-            #[cfg(feature = "disk-buffer")]
-            BufferConfig::Disk { .. } => vec![Resource::DiskBuffer(sink_name.to_string())],
+        #[cfg(feature = "disk-buffer")]
+        if self
+            .stages
+            .iter()
+            .any(|stage| matches!(stage, BufferType::Disk { .. }))
+        {
+            return vec![Resource::DiskBuffer(sink_name.to_string())];
         }
+        Vec::new()
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::buffers::{BufferConfig, WhenFull};
+    use crate::buffers::{BufferConfig, BufferType, WhenFull};
+    use serde::Deserialize;
 
-    #[test]
-    fn config_default_values() {
-        fn check(source: &str, config: BufferConfig) {
-            let conf: BufferConfig = toml::from_str(source).unwrap();
-            assert_eq!(toml::to_string(&conf), toml::to_string(&config));
+    #[derive(Deserialize, Debug)]
+    struct Sink {
+        buffer: BufferConfig,
+    }
+
+    fn check(source: &str, config: BufferConfig) {
+        let conf: BufferConfig = toml::from_str(source).unwrap();
+        assert_eq!(toml::to_string(&conf), toml::to_string(&config));
+    }
+
+    fn single(stage: BufferType) -> BufferConfig {
+        BufferConfig {
+            stages: vec![stage],
         }
+    }
 
+    #[test]
+    fn config_default_values() {
         check(
             r#"
           type = "memory"
           "#,
-            BufferConfig::Memory {
+            single(BufferType::Memory {
                 max_events: 500,
                 when_full: WhenFull::Block,
-            },
+            }),
         );
 
         check(
@@ -108,10 +249,10 @@ mod test {
           type = "memory"
           max_events = 100
           "#,
-            BufferConfig::Memory {
+            single(BufferType::Memory {
                 max_events: 100,
                 when_full: WhenFull::Block,
-            },
+            }),
         );
 
         check(
@@ -119,10 +260,10 @@ mod test {
           type = "memory"
           when_full = "drop_newest"
           "#,
-            BufferConfig::Memory {
+            single(BufferType::Memory {
                 max_events: 500,
                 when_full: WhenFull::DropNewest,
-            },
+            }),
         );
 
         #[cfg(feature = "disk-buffer")]
@@ -131,10 +272,93 @@ mod test {
           type = "disk"
           max_size = 1024
           "#,
-            BufferConfig::Disk {
+            single(BufferType::Disk {
                 max_size: 1024,
                 when_full: WhenFull::Block,
-            },
+            }),
+        );
+    }
+
+    #[test]
+    fn config_chained_stages() {
+        let sink: Sink = toml::from_str(
+            r#"
+          [[buffer]]
+          type = "memory"
+          max_events = 100
+          when_full = "overflow"
+
+          [[buffer]]
+          type = "memory"
+          when_full = "drop_newest"
+          "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            sink.buffer,
+            BufferConfig {
+                stages: vec![
+                    BufferType::Memory {
+                        max_events: 100,
+                        when_full: WhenFull::Overflow,
+                    },
+                    BufferType::Memory {
+                        max_events: 500,
+                        when_full: WhenFull::DropNewest,
+                    },
+                ],
+            }
         );
     }
+
+    #[test]
+    fn config_invalid_stages() {
+        fn error(source: &str) -> String {
+            toml::from_str::<Sink>(source).unwrap_err().to_string()
+        }
+
+        assert!(error(
+            r#"
+          [buffer]
+          type = "memory"
+          when_full = "overflow"
+          "#
+        )
+        .contains("no stage to overflow into"));
+
+        assert!(error(
+            r#"
+          [[buffer]]
+          type = "memory"
+
+          [[buffer]]
+          type = "memory"
+          "#
+        )
+        .contains("must have `when_full` set to `overflow`"));
+
+        assert!(error(
+            r#"
+          [buffer]
+          type = "memory"
+          when_full = "sometimes"
+          "#
+        )
+        .contains("unknown variant `sometimes`"));
+
+        #[cfg(feature = "disk-buffer")]
+        assert!(error(
+            r#"
+          [[buffer]]
+          type = "disk"
+          max_size = 1024
+          when_full = "overflow"
+
+          [[buffer]]
+          type = "memory"
+          "#
+        )
+        .contains("Only memory buffer stages can overflow"));
+    }
 }
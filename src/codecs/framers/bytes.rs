@@ -0,0 +1,69 @@
+use crate::codecs::{BoxedFramer, BoxedFramingError, FramingConfig};
+use bytes::{Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Decoder;
+
+/// Config used to build a `BytesDecoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct BytesDecoderConfig {}
+
+impl BytesDecoderConfig {
+    /// Creates a new `BytesDecoderConfig`.
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[typetag::serde(name = "bytes")]
+impl FramingConfig for BytesDecoderConfig {
+    fn build(&self) -> crate::Result<BoxedFramer> {
+        Ok(Box::new(BytesDecoder::new()))
+    }
+}
+
+/// A decoder for passing through bytes as-is.
+///
+/// This is basically a no-op and is used to convert from `BytesMut` to `Bytes`.
+/// The whole byte stream / byte message is emitted as a single frame once the
+/// end of input is reached.
+#[derive(Debug, Clone, Default)]
+pub struct BytesDecoder;
+
+impl BytesDecoder {
+    /// Creates a new `BytesDecoder`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Decoder for BytesDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, _src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(src.split().freeze()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_frame_at_eof() {
+        let mut input = BytesMut::from("foo\nbar");
+        let mut decoder = BytesDecoder::new();
+
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+        assert_eq!(decoder.decode_eof(&mut input).unwrap().unwrap(), "foo\nbar");
+        assert_eq!(decoder.decode_eof(&mut input).unwrap(), None);
+    }
+}
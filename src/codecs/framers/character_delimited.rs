@@ -0,0 +1,160 @@
+use crate::codecs::{BoxedFramer, BoxedFramingError, FramingConfig};
+use bytes::{Bytes, BytesMut};
+use codec::BytesDelimitedCodec;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Decoder;
+
+/// Config used to build a `CharacterDelimitedDecoder`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CharacterDelimitedDecoderConfig {
+    /// Options for the character delimited decoder.
+    character_delimited: CharacterDelimitedDecoderOptions,
+}
+
+/// Options for building a `CharacterDelimitedDecoder`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CharacterDelimitedDecoderOptions {
+    /// The character that delimits byte sequences.
+    delimiter: char,
+    /// The maximum length of the byte buffer.
+    ///
+    /// This length does *not* include the trailing delimiter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_length: Option<usize>,
+}
+
+impl CharacterDelimitedDecoderConfig {
+    /// Creates a `CharacterDelimitedDecoderConfig` with the specified delimiter
+    /// and no maximum length.
+    pub const fn new(delimiter: char) -> Self {
+        Self {
+            character_delimited: CharacterDelimitedDecoderOptions {
+                delimiter,
+                max_length: None,
+            },
+        }
+    }
+
+    /// Creates a `CharacterDelimitedDecoderConfig` with the specified delimiter
+    /// and maximum frame length.
+    pub const fn new_with_max_length(delimiter: char, max_length: usize) -> Self {
+        Self {
+            character_delimited: CharacterDelimitedDecoderOptions {
+                delimiter,
+                max_length: Some(max_length),
+            },
+        }
+    }
+}
+
+#[typetag::serde(name = "character_delimited")]
+impl FramingConfig for CharacterDelimitedDecoderConfig {
+    fn build(&self) -> crate::Result<BoxedFramer> {
+        let CharacterDelimitedDecoderOptions {
+            delimiter,
+            max_length,
+        } = self.character_delimited;
+
+        if !delimiter.is_ascii() {
+            return Err(format!(
+                "Framing delimiter must be a single ASCII character, found {:?}.",
+                delimiter
+            )
+            .into());
+        }
+
+        Ok(Box::new(match max_length {
+            Some(max_length) => {
+                CharacterDelimitedDecoder::new_with_max_length(delimiter as u8, max_length)
+            }
+            None => CharacterDelimitedDecoder::new(delimiter as u8),
+        }))
+    }
+}
+
+/// A decoder for handling bytes that are delimited by (a) chosen character(s).
+///
+/// Frames that exceed the maximum length are discarded up to the next delimiter.
+#[derive(Debug, Clone)]
+pub struct CharacterDelimitedDecoder(BytesDelimitedCodec);
+
+impl CharacterDelimitedDecoder {
+    /// Creates a `CharacterDelimitedDecoder` with the specified delimiter.
+    pub fn new(delimiter: u8) -> Self {
+        Self(BytesDelimitedCodec::new(delimiter))
+    }
+
+    /// Creates a `CharacterDelimitedDecoder` with a maximum frame length limit.
+    ///
+    /// Any frames longer than `max_length` bytes will be discarded entirely.
+    pub fn new_with_max_length(delimiter: u8, max_length: usize) -> Self {
+        Self(BytesDelimitedCodec::new_with_max_length(
+            delimiter, max_length,
+        ))
+    }
+
+    /// Returns the maximum frame length when decoding.
+    pub fn max_length(&self) -> usize {
+        self.0.max_length()
+    }
+}
+
+impl Decoder for CharacterDelimitedDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.0.decode(src).map_err(Into::into)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.0.decode_eof(src).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let mut input = BytesMut::from("foo,bar,baz");
+        let mut decoder = CharacterDelimitedDecoder::new(b',');
+
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "foo");
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "bar");
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+        assert_eq!(decoder.decode_eof(&mut input).unwrap().unwrap(), "baz");
+    }
+
+    #[test]
+    fn decode_discards_frames_exceeding_max_length() {
+        let mut input = BytesMut::from("foo,barbaz,qux,");
+        let mut decoder = CharacterDelimitedDecoder::new_with_max_length(b',', 3);
+
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "foo");
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "qux");
+    }
+
+    #[test]
+    fn config_rejects_non_ascii_delimiter() {
+        assert!(CharacterDelimitedDecoderConfig::new('→').build().is_err());
+    }
+
+    #[test]
+    fn deserialize_config() {
+        let config: Box<dyn FramingConfig> = toml::from_str(
+            r#"
+            method = "character_delimited"
+            character_delimited.delimiter = "\t"
+            character_delimited.max_length = 10
+            "#,
+        )
+        .unwrap();
+        let mut decoder = config.build().unwrap();
+        let mut input = BytesMut::from("foo\tbar");
+
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "foo");
+    }
+}
@@ -0,0 +1,94 @@
+use crate::codecs::{BoxedFramer, BoxedFramingError, FramingConfig};
+use bytes::{Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Decoder;
+
+/// Config used to build a `LengthDelimitedDecoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LengthDelimitedDecoderConfig {}
+
+impl LengthDelimitedDecoderConfig {
+    /// Creates a new `LengthDelimitedDecoderConfig`.
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[typetag::serde(name = "length_delimited")]
+impl FramingConfig for LengthDelimitedDecoderConfig {
+    fn build(&self) -> crate::Result<BoxedFramer> {
+        Ok(Box::new(LengthDelimitedDecoder::new()))
+    }
+}
+
+/// A decoder for handling bytes that are delimited by a length header.
+///
+/// The header is a 4 byte unsigned integer in big-endian byte order that
+/// contains the length of the frame.
+#[derive(Debug)]
+pub struct LengthDelimitedDecoder(tokio_util::codec::LengthDelimitedCodec);
+
+impl LengthDelimitedDecoder {
+    /// Creates a new `LengthDelimitedDecoder`.
+    pub fn new() -> Self {
+        Self(tokio_util::codec::LengthDelimitedCodec::new())
+    }
+}
+
+impl Default for LengthDelimitedDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for LengthDelimitedDecoder {
+    fn clone(&self) -> Self {
+        // The codec keeps track of a frame that is partially read. Cloning
+        // resets this state, the clone starts decoding at a frame boundary.
+        Self::new()
+    }
+}
+
+impl Decoder for LengthDelimitedDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.0
+            .decode(src)
+            .map(|bytes| bytes.map(BytesMut::freeze))
+            .map_err(Into::into)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.0
+            .decode_eof(src)
+            .map(|bytes| bytes.map(BytesMut::freeze))
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let mut input = BytesMut::from(&b"\x00\x00\x00\x03foo\x00\x00\x00\x03ba"[..]);
+        let mut decoder = LengthDelimitedDecoder::new();
+
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "foo");
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+
+        input.extend_from_slice(b"r");
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "bar");
+    }
+
+    #[test]
+    fn decode_eof_with_incomplete_frame_is_an_error() {
+        let mut input = BytesMut::from(&b"\x00\x00\x00\x03fo"[..]);
+        let mut decoder = LengthDelimitedDecoder::new();
+
+        assert!(decoder.decode_eof(&mut input).is_err());
+    }
+}
@@ -0,0 +1,99 @@
+//! A collection of framing methods that can be used to convert from byte
+//! streams / byte messages to byte frames with defined boundaries.
+
+mod bytes;
+mod character_delimited;
+mod length_delimited;
+mod newline_delimited;
+mod octet_counting;
+
+pub use self::bytes::{BytesDecoder, BytesDecoderConfig};
+pub use self::character_delimited::{
+    CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions,
+};
+pub use self::length_delimited::{LengthDelimitedDecoder, LengthDelimitedDecoderConfig};
+pub use self::newline_delimited::{
+    NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig, NewlineDelimitedDecoderOptions,
+};
+pub use self::octet_counting::{
+    OctetCountingDecoder, OctetCountingDecoderConfig, OctetCountingDecoderOptions,
+};
+
+use ::bytes::Bytes;
+use dyn_clone::DynClone;
+use std::{any::Any, fmt::Debug};
+use tokio_util::codec::LinesCodecError;
+
+/// An error that occurred while producing byte frames from a byte stream /
+/// byte messages.
+pub trait FramingError: std::error::Error + Send + Sync + Any {
+    /// Coerces the error to `dyn Any`, which allows checking for the concrete
+    /// error type, e.g. whether the error originates from I/O.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl std::error::Error for BoxedFramingError {}
+
+impl FramingError for std::io::Error {
+    fn as_any(&self) -> &dyn Any {
+        self as &dyn Any
+    }
+}
+
+impl FramingError for LinesCodecError {
+    fn as_any(&self) -> &dyn Any {
+        self as &dyn Any
+    }
+}
+
+impl From<std::io::Error> for BoxedFramingError {
+    fn from(error: std::io::Error) -> Self {
+        Box::new(error)
+    }
+}
+
+impl From<LinesCodecError> for BoxedFramingError {
+    fn from(error: LinesCodecError) -> Self {
+        Box::new(error)
+    }
+}
+
+/// A `Box` containing a `FramingError`.
+pub type BoxedFramingError = Box<dyn FramingError>;
+
+/// Produce byte frames from a byte stream / byte message.
+pub trait Framer:
+    tokio_util::codec::Decoder<Item = Bytes, Error = BoxedFramingError> + DynClone + Debug + Send + Sync
+{
+}
+
+/// Default implementation for `Framer`s that implement
+/// `tokio_util::codec::Decoder`.
+impl<Decoder> Framer for Decoder where
+    Decoder: tokio_util::codec::Decoder<Item = Bytes, Error = BoxedFramingError>
+        + Clone
+        + Debug
+        + Send
+        + Sync
+{
+}
+
+dyn_clone::clone_trait_object!(Framer);
+
+/// A `Box` containing a `Framer`.
+pub type BoxedFramer = Box<dyn Framer>;
+
+/// Define options for a framer and build it from the config object.
+///
+/// Implementors must annotate the struct with `#[typetag::serde(name = "...")]`
+/// to define which value should be read from the `method` key to select their
+/// implementation.
+#[typetag::serde(tag = "method")]
+pub trait FramingConfig: Debug + DynClone + Send + Sync {
+    /// Builds a framer from this configuration.
+    ///
+    /// Fails if the configuration is invalid.
+    fn build(&self) -> crate::Result<BoxedFramer>;
+}
+
+dyn_clone::clone_trait_object!(FramingConfig);
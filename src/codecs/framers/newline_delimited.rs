@@ -0,0 +1,116 @@
+use crate::codecs::{BoxedFramer, BoxedFramingError, CharacterDelimitedDecoder, FramingConfig};
+use bytes::{Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Decoder;
+
+/// Config used to build a `NewlineDelimitedDecoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct NewlineDelimitedDecoderConfig {
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    newline_delimited: NewlineDelimitedDecoderOptions,
+}
+
+/// Options for building a `NewlineDelimitedDecoder`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct NewlineDelimitedDecoderOptions {
+    /// The maximum length of the byte buffer.
+    ///
+    /// This length does *not* include the trailing delimiter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_length: Option<usize>,
+}
+
+impl NewlineDelimitedDecoderConfig {
+    /// Creates a new `NewlineDelimitedDecoderConfig`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a `NewlineDelimitedDecoderConfig` with a maximum frame length
+    /// limit.
+    pub const fn new_with_max_length(max_length: usize) -> Self {
+        Self {
+            newline_delimited: NewlineDelimitedDecoderOptions {
+                max_length: Some(max_length),
+            },
+        }
+    }
+}
+
+#[typetag::serde(name = "newline_delimited")]
+impl FramingConfig for NewlineDelimitedDecoderConfig {
+    fn build(&self) -> crate::Result<BoxedFramer> {
+        Ok(Box::new(match self.newline_delimited.max_length {
+            Some(max_length) => NewlineDelimitedDecoder::new_with_max_length(max_length),
+            None => NewlineDelimitedDecoder::new(),
+        }))
+    }
+}
+
+/// A decoder for handling bytes that are delimited by (a) newline(s).
+#[derive(Debug, Clone)]
+pub struct NewlineDelimitedDecoder(CharacterDelimitedDecoder);
+
+impl NewlineDelimitedDecoder {
+    /// Creates a new `NewlineDelimitedDecoder`.
+    pub fn new() -> Self {
+        Self(CharacterDelimitedDecoder::new(b'\n'))
+    }
+
+    /// Creates a `NewlineDelimitedDecoder` with a maximum frame length limit.
+    ///
+    /// Any frames longer than `max_length` bytes will be discarded entirely.
+    pub fn new_with_max_length(max_length: usize) -> Self {
+        Self(CharacterDelimitedDecoder::new_with_max_length(
+            b'\n', max_length,
+        ))
+    }
+}
+
+impl Default for NewlineDelimitedDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for NewlineDelimitedDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.0.decode(src)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.0.decode_eof(src)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let mut input = BytesMut::from("foo\nbar\nbaz");
+        let mut decoder = NewlineDelimitedDecoder::new();
+
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "foo");
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "bar");
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+        assert_eq!(decoder.decode_eof(&mut input).unwrap().unwrap(), "baz");
+    }
+
+    #[test]
+    fn deserialize_config_without_options() {
+        let config: Box<dyn FramingConfig> =
+            toml::from_str(r#"method = "newline_delimited""#).unwrap();
+        let mut decoder = config.build().unwrap();
+        let mut input = BytesMut::from("foo\n");
+
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "foo");
+    }
+}
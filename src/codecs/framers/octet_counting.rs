@@ -0,0 +1,413 @@
+use crate::codecs::{BoxedFramer, BoxedFramingError, FramingConfig};
+use bytes::{Buf, Bytes, BytesMut};
+use codec::BytesDelimitedCodec;
+use serde::{Deserialize, Serialize};
+use std::io;
+use tokio_util::codec::{Decoder, LinesCodecError};
+
+/// Config used to build a `OctetCountingDecoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct OctetCountingDecoderConfig {
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    octet_counting: OctetCountingDecoderOptions,
+}
+
+/// Options for building a `OctetCountingDecoder`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct OctetCountingDecoderOptions {
+    /// The maximum length of the byte buffer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_length: Option<usize>,
+}
+
+impl OctetCountingDecoderConfig {
+    /// Creates a new `OctetCountingDecoderConfig`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a `OctetCountingDecoderConfig` with a maximum frame length
+    /// limit.
+    pub const fn new_with_max_length(max_length: usize) -> Self {
+        Self {
+            octet_counting: OctetCountingDecoderOptions {
+                max_length: Some(max_length),
+            },
+        }
+    }
+}
+
+#[typetag::serde(name = "octet_counting")]
+impl FramingConfig for OctetCountingDecoderConfig {
+    fn build(&self) -> crate::Result<BoxedFramer> {
+        Ok(Box::new(match self.octet_counting.max_length {
+            Some(max_length) => OctetCountingDecoder::new_with_max_length(max_length),
+            None => OctetCountingDecoder::new(),
+        }))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum State {
+    NotDiscarding,
+    Discarding(usize),
+    DiscardingToEol,
+}
+
+/// Decodes according to `Octet Counting` in https://tools.ietf.org/html/rfc6587
+///
+/// Frames that don't start with a non-zero digit are handled as newline
+/// delimited, as described by `Non-Transparent-Framing` in the same RFC.
+#[derive(Clone, Debug)]
+pub struct OctetCountingDecoder {
+    other: BytesDelimitedCodec,
+    octet_decoding: Option<State>,
+}
+
+impl OctetCountingDecoder {
+    /// Creates a new `OctetCountingDecoder`.
+    pub fn new() -> Self {
+        Self {
+            other: BytesDelimitedCodec::new(b'\n'),
+            octet_decoding: None,
+        }
+    }
+
+    /// Creates a `OctetCountingDecoder` with a maximum frame length limit.
+    pub fn new_with_max_length(max_length: usize) -> Self {
+        Self {
+            other: BytesDelimitedCodec::new_with_max_length(b'\n', max_length),
+            octet_decoding: None,
+        }
+    }
+
+    /// Decode a frame.
+    fn octet_decode(
+        &mut self,
+        state: State,
+        src: &mut BytesMut,
+    ) -> Result<Option<Bytes>, LinesCodecError> {
+        // Encoding scheme:
+        //
+        // len ' ' data
+        // |    |  | len number of bytes that contain syslog message
+        // |    |
+        // |    | Separating whitespace
+        // |
+        // | ASCII decimal number of unknown length
+
+        let space_pos = src.iter().position(|&b| b == b' ');
+
+        // If we are discarding, discard to the next newline.
+        let newline_pos = src.iter().position(|&b| b == b'\n');
+
+        match (state, newline_pos, space_pos) {
+            (State::Discarding(chars), _, _) if src.len() >= chars => {
+                // We have a certain number of chars to discard.
+                //
+                // There are enough chars in this frame to discard
+                src.advance(chars);
+                self.octet_decoding = None;
+                Err(LinesCodecError::MaxLineLengthExceeded)
+            }
+
+            (State::Discarding(chars), _, _) => {
+                // We have a certain number of chars to discard.
+                //
+                // There aren't enough in this frame so we need to discard the
+                // entire frame and adjust the amount to discard accordingly.
+                self.octet_decoding = Some(State::Discarding(chars - src.len()));
+                src.advance(src.len());
+                Ok(None)
+            }
+
+            (State::DiscardingToEol, Some(offset), _) => {
+                // When discarding we keep discarding to the next newline.
+                src.advance(offset + 1);
+                self.octet_decoding = None;
+                Err(LinesCodecError::MaxLineLengthExceeded)
+            }
+
+            (State::DiscardingToEol, None, _) => {
+                // There is no newline in this frame.
+                //
+                // Since we don't have a set number of chars we want to discard,
+                // we need to discard to the next newline. Advance as far as we
+                // can to discard the entire frame.
+                src.advance(src.len());
+                Ok(None)
+            }
+
+            (State::NotDiscarding, _, Some(space_pos)) if space_pos < self.other.max_length() => {
+                // Everything looks good.
+                //
+                // We aren't discarding, we have a space that is not beyond our
+                // maximum length. Attempt to parse the bytes as a number which
+                // will hopefully give us a sensible length for our message.
+                let len: usize = match std::str::from_utf8(&src[..space_pos])
+                    .map_err(|_| ())
+                    .and_then(|num| num.parse().map_err(|_| ()))
+                {
+                    Ok(len) => len,
+                    Err(_) => {
+                        // It was not a sensible number.
+                        //
+                        // Advance the buffer past the erroneous bytes to
+                        // prevent us getting stuck in an infinite loop.
+                        src.advance(space_pos + 1);
+                        self.octet_decoding = None;
+                        return Err(LinesCodecError::Io(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "Unable to decode message len as number",
+                        )));
+                    }
+                };
+
+                let from = space_pos + 1;
+                let to = from + len;
+
+                if len > self.other.max_length() {
+                    // The length is greater than we want.
+                    //
+                    // We need to discard the entire message.
+                    self.octet_decoding = Some(State::Discarding(len));
+                    src.advance(space_pos + 1);
+
+                    Ok(None)
+                } else if to <= src.len() {
+                    // We have managed to read the entire message.
+                    let frame = src.split_to(to).split_off(from).freeze();
+                    self.octet_decoding = None;
+                    Ok(Some(frame))
+                } else {
+                    // We have an acceptable number of bytes in this message,
+                    // but not all the data was in the frame, return `None` to
+                    // indicate we want more data before we do anything else.
+                    Ok(None)
+                }
+            }
+
+            (State::NotDiscarding, Some(newline_pos), _) => {
+                // Beyond maximum length, advance to the newline.
+                src.advance(newline_pos + 1);
+                Err(LinesCodecError::MaxLineLengthExceeded)
+            }
+
+            (State::NotDiscarding, None, _) if src.len() < self.other.max_length() => {
+                // We aren't discarding, but there is no useful character to
+                // tell us what to do next.
+                //
+                // We are still not beyond the max length, so just return `None`
+                // to indicate we need to wait for more data.
+                Ok(None)
+            }
+
+            (State::NotDiscarding, None, _) => {
+                // There is no newline in this frame and we have more data than
+                // we want to handle.
+                //
+                // Advance as far as we can to discard the entire frame.
+                self.octet_decoding = Some(State::DiscardingToEol);
+                src.advance(src.len());
+                Ok(None)
+            }
+        }
+    }
+
+    /// `None` if this is not octet counting encoded.
+    fn checked_decode(
+        &mut self,
+        src: &mut BytesMut,
+    ) -> Option<Result<Option<Bytes>, LinesCodecError>> {
+        if let Some(&first_byte) = src.get(0) {
+            if (49..=57).contains(&first_byte) {
+                // First character is non zero number so we can assume that
+                // octet count framing is used.
+                trace!("Octet counting encoded event detected.");
+                self.octet_decoding = Some(State::NotDiscarding);
+            }
+        }
+
+        self.octet_decoding
+            .map(|state| self.octet_decode(state, src))
+    }
+}
+
+impl Default for OctetCountingDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for OctetCountingDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if let Some(ret) = self.checked_decode(src) {
+            ret.map_err(Into::into)
+        } else {
+            // Octet counting isn't used so fallback to newline codec.
+            self.other.decode(src).map_err(Into::into)
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if let Some(ret) = self.checked_decode(src) {
+            ret.map_err(Into::into)
+        } else {
+            // Octet counting isn't used so fallback to newline codec.
+            self.other.decode_eof(src).map_err(Into::into)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BufMut;
+
+    #[test]
+    fn non_octet_decode_works_with_multiple_frames() {
+        let mut decoder = OctetCountingDecoder::new_with_max_length(128);
+        let mut buffer = BytesMut::with_capacity(16);
+
+        buffer.put(&b"<57>Mar 25 21:47:46 gleichner6005 quaerat[2444]: There were "[..]);
+        let result = decoder.decode(&mut buffer);
+        assert_eq!(Ok(None), result.map_err(|_| true));
+
+        buffer.put(&b"8 penguins in the shop.\n"[..]);
+        let result = decoder.decode(&mut buffer);
+        assert_eq!(
+            Ok(Some(
+                "<57>Mar 25 21:47:46 gleichner6005 quaerat[2444]: There were 8 penguins in the shop."
+                    .into()
+            )),
+            result.map_err(|_| true)
+        );
+    }
+
+    #[test]
+    fn octet_decode_works_with_multiple_frames() {
+        let mut decoder = OctetCountingDecoder::new_with_max_length(30);
+        let mut buffer = BytesMut::with_capacity(16);
+
+        buffer.put(&b"28 abcdefghijklm"[..]);
+        let result = decoder.decode(&mut buffer);
+        assert_eq!(Ok(None), result.map_err(|_| false));
+
+        // Sending another frame starting with a number should not cause it to
+        // try to decode a new message.
+        buffer.put(&b"3 nopqrstuvwxyz"[..]);
+        let result = decoder.decode(&mut buffer);
+        assert_eq!(
+            Ok(Some("abcdefghijklm3 nopqrstuvwxyz".into())),
+            result.map_err(|_| false)
+        );
+    }
+
+    #[test]
+    fn octet_decode_moves_past_invalid_length() {
+        let mut decoder = OctetCountingDecoder::new_with_max_length(16);
+        let mut buffer = BytesMut::with_capacity(16);
+
+        // An invalid syslog message that starts with a digit so we think it is
+        // starting with the len.
+        buffer.put(&b"232>1 zork"[..]);
+        let result = decoder.decode(&mut buffer);
+
+        assert!(result.is_err());
+        assert_eq!(b"zork"[..], buffer);
+    }
+
+    #[test]
+    fn octet_decode_moves_past_invalid_utf8() {
+        let mut decoder = OctetCountingDecoder::new_with_max_length(16);
+        let mut buffer = BytesMut::with_capacity(16);
+
+        // An invalid syslog message containing invalid utf8 bytes. Frames are
+        // passed through as bytes, validating the contents is up to the parser.
+        buffer.put(&[b'4', b' ', 0xf0, 0x28, 0x8c, 0xbc][..]);
+        let result = decoder.decode(&mut buffer);
+
+        assert_eq!(
+            Ok(Some(Bytes::from(&[0xf0, 0x28, 0x8c, 0xbc][..]))),
+            result.map_err(|_| false)
+        );
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn octet_decode_discards_frames_exceeding_max_length() {
+        let mut decoder = OctetCountingDecoder::new_with_max_length(4);
+        let mut buffer = BytesMut::with_capacity(16);
+
+        buffer.put(&b"8 abcdefgh4 abcd"[..]);
+        assert_eq!(Ok(None), decoder.decode(&mut buffer).map_err(|_| false));
+        assert!(decoder.decode(&mut buffer).is_err());
+        assert_eq!(
+            Ok(Some("abcd".into())),
+            decoder.decode(&mut buffer).map_err(|_| false)
+        );
+    }
+
+    #[test]
+    fn octet_decode_moves_past_exceeded_frame_length() {
+        let mut decoder = OctetCountingDecoder::new_with_max_length(16);
+        let mut buffer = BytesMut::with_capacity(32);
+
+        buffer.put(&b"32thisshouldbelongerthanthmaxframeasizewhichmeansthesyslogparserwillnotbeabletodecodeit\n"[..]);
+        let result = decoder.decode(&mut buffer);
+
+        assert!(result.is_err());
+        assert_eq!(b""[..], buffer);
+    }
+
+    #[test]
+    fn octet_decode_rejects_exceeded_frame_length() {
+        let mut decoder = OctetCountingDecoder::new_with_max_length(16);
+        let mut buffer = BytesMut::with_capacity(32);
+
+        buffer.put(&b"26 abcdefghijklmnopqrstuvwxyzand here we are"[..]);
+        let result = decoder.decode(&mut buffer);
+        assert_eq!(Ok(None), result.map_err(|_| false));
+        let result = decoder.decode(&mut buffer);
+
+        assert!(result.is_err());
+        assert_eq!(b"and here we are"[..], buffer);
+    }
+
+    #[test]
+    fn octet_decode_rejects_exceeded_frame_length_multiple_frames() {
+        let mut decoder = OctetCountingDecoder::new_with_max_length(16);
+        let mut buffer = BytesMut::with_capacity(32);
+
+        buffer.put(&b"26 abc"[..]);
+        let _result = decoder.decode(&mut buffer);
+
+        buffer.put(&b"defghijklmnopqrstuvwxyzand here we are"[..]);
+        let result = decoder.decode(&mut buffer);
+
+        assert!(result.is_err());
+        assert_eq!(b"and here we are"[..], buffer);
+    }
+
+    #[test]
+    fn octet_decode_moves_past_exceeded_frame_length_multiple_frames() {
+        let mut decoder = OctetCountingDecoder::new_with_max_length(16);
+        let mut buffer = BytesMut::with_capacity(32);
+
+        buffer.put(&b"32thisshouldbelongerthanthmaxframeasizewhichmeansthesyslogparserwillnotbeabletodecodeit"[..]);
+        let _ = decoder.decode(&mut buffer);
+
+        assert_eq!(decoder.octet_decoding, Some(State::DiscardingToEol));
+        buffer.put(&b"wemustcontinuetodiscard\n32 something valid"[..]);
+        let result = decoder.decode(&mut buffer);
+
+        assert!(result.is_err());
+        assert_eq!(b"32 something valid"[..], buffer);
+    }
+}
@@ -0,0 +1,226 @@
+//! A collection of codecs that can be used to transform between byte streams /
+//! byte messages, byte frames and structured events.
+
+pub mod framers;
+pub mod parsers;
+
+pub use framers::{
+    BoxedFramer, BoxedFramingError, BytesDecoder, BytesDecoderConfig, CharacterDelimitedDecoder,
+    CharacterDelimitedDecoderConfig, FramingConfig, FramingError, LengthDelimitedDecoder,
+    LengthDelimitedDecoderConfig, NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig,
+    OctetCountingDecoder, OctetCountingDecoderConfig,
+};
+pub use parsers::{
+    BoxedParser, BytesParser, BytesParserConfig, JsonParser, JsonParserConfig, NativeParser,
+    NativeParserConfig, Parser, ParserConfig,
+};
+#[cfg(feature = "codecs-syslog")]
+pub use parsers::{SyslogParser, SyslogParserConfig};
+
+use crate::{
+    event::Event,
+    internal_events::{DecoderFramingFailed, DecoderParseFailed},
+};
+use bytes::{Bytes, BytesMut};
+use std::fmt::Debug;
+
+/// An error that occurred while decoding structured events from a byte stream /
+/// byte messages.
+#[derive(Debug)]
+pub enum Error {
+    /// The error occurred while producing byte frames from the byte stream /
+    /// byte messages.
+    FramingError(BoxedFramingError),
+    /// The error occurred while parsing structured events from a byte frame.
+    ParsingError(crate::Error),
+}
+
+impl Error {
+    /// Whether it is reasonable to continue decoding after this error, e.g. the
+    /// framer discarded an oversized frame or a single frame could not be
+    /// parsed. I/O errors of the underlying stream are not recoverable.
+    pub fn can_continue(&self) -> bool {
+        match self {
+            Self::FramingError(error) => !error.as_any().is::<std::io::Error>(),
+            Self::ParsingError(_) => true,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FramingError(error) => write!(formatter, "FramingError({})", error),
+            Self::ParsingError(error) => write!(formatter, "ParsingError({})", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self::FramingError(Box::new(error))
+    }
+}
+
+#[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
+impl crate::sources::util::TcpIsErrorFatal for Error {
+    fn is_error_fatal(&self) -> bool {
+        !self.can_continue()
+    }
+}
+
+/// A decoder that can decode structured events from a byte stream / byte
+/// messages.
+///
+/// The byte stream is first split into frames by the framer, each frame is then
+/// parsed into events by the parser.
+#[derive(Debug, Clone)]
+pub struct Decoder {
+    framer: BoxedFramer,
+    parser: BoxedParser,
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Self {
+            framer: Box::new(NewlineDelimitedDecoder::new()),
+            parser: Box::new(BytesParser::new()),
+        }
+    }
+}
+
+impl Decoder {
+    /// Creates a new `Decoder` with the specified framer to produce byte frames
+    /// from the byte stream / byte messages and the parser to parse structured
+    /// events from these byte frames.
+    pub fn new(framer: BoxedFramer, parser: BoxedParser) -> Self {
+        Self { framer, parser }
+    }
+
+    /// Handles the framing result and parses it into a structured event, if
+    /// possible.
+    ///
+    /// Emits logs if either framing or parsing failed.
+    fn handle_framing_result(
+        &mut self,
+        frame: Result<Option<Bytes>, BoxedFramingError>,
+    ) -> Result<Option<(Vec<Event>, usize)>, Error> {
+        let frame = frame.map_err(|error| {
+            emit!(DecoderFramingFailed { error: &error });
+            Error::FramingError(error)
+        })?;
+
+        let frame = match frame {
+            Some(frame) => frame,
+            None => return Ok(None),
+        };
+
+        let byte_size = frame.len();
+
+        self.parser
+            .parse(frame)
+            .map(|events| Some((events, byte_size)))
+            .map_err(|error| {
+                emit!(DecoderParseFailed { error: &error });
+                Error::ParsingError(error)
+            })
+    }
+}
+
+impl tokio_util::codec::Decoder for Decoder {
+    type Item = (Vec<Event>, usize);
+    type Error = Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let frame = self.framer.decode(buf);
+        self.handle_framing_result(frame)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let frame = self.framer.decode_eof(buf);
+        self.handle_framing_result(frame)
+    }
+}
+
+/// Config used to build a `Decoder`.
+#[derive(Debug, Clone)]
+pub struct DecodingConfig {
+    /// The framing config.
+    framing: Box<dyn FramingConfig>,
+    /// The decoding config.
+    decoding: Box<dyn ParserConfig>,
+}
+
+impl DecodingConfig {
+    /// Creates a new `DecodingConfig` with the provided `FramingConfig` and
+    /// `ParserConfig`.
+    pub fn new(framing: Box<dyn FramingConfig>, decoding: Box<dyn ParserConfig>) -> Self {
+        Self { framing, decoding }
+    }
+
+    /// Builds a `Decoder` from the provided configuration.
+    pub fn build(&self) -> crate::Result<Decoder> {
+        // Build the framer.
+        let framer = self.framing.build()?;
+
+        // Build the parser.
+        let parser = self.decoding.build()?;
+
+        Ok(Decoder::new(framer, parser))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::log_schema;
+    use tokio_util::codec::Decoder as _;
+
+    #[test]
+    fn decode_frames_into_events() {
+        let config = DecodingConfig::new(
+            Box::new(CharacterDelimitedDecoderConfig::new(',')),
+            Box::new(JsonParserConfig::new()),
+        );
+        let mut decoder = config.build().unwrap();
+        let mut input = BytesMut::from(r#"{"foo":1},[{"bar":2},{"baz":3}],nope"#);
+
+        let (events, byte_size) = decoder.decode(&mut input).unwrap().unwrap();
+        assert_eq!(byte_size, 9);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()["foo"], 1.into());
+
+        let (events, _) = decoder.decode(&mut input).unwrap().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_log()["bar"], 2.into());
+        assert_eq!(events[1].as_log()["baz"], 3.into());
+
+        // The last frame isn't terminated by a delimiter.
+        assert!(decoder.decode(&mut input).unwrap().is_none());
+
+        let error = decoder.decode_eof(&mut input).unwrap_err();
+        assert!(matches!(error, Error::ParsingError(_)));
+        assert!(error.can_continue());
+        assert!(decoder.decode_eof(&mut input).unwrap().is_none());
+    }
+
+    #[test]
+    fn default_decoder_splits_lines_into_messages() {
+        let mut decoder = Decoder::default();
+        let mut input = BytesMut::from("foo\nbar");
+
+        let (events, _) = decoder.decode(&mut input).unwrap().unwrap();
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "foo".into());
+
+        let (events, _) = decoder.decode_eof(&mut input).unwrap().unwrap();
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "bar".into());
+    }
+
+    #[test]
+    fn io_errors_are_not_recoverable() {
+        let error = Error::from(std::io::Error::new(std::io::ErrorKind::Other, "broken"));
+        assert!(!error.can_continue());
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    codecs::{BoxedParser, Parser, ParserConfig},
+    event::{Event, LogEvent},
+};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+/// Config used to build a `BytesParser`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct BytesParserConfig {}
+
+impl BytesParserConfig {
+    /// Creates a new `BytesParserConfig`.
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[typetag::serde(name = "bytes")]
+impl ParserConfig for BytesParserConfig {
+    fn build(&self) -> crate::Result<BoxedParser> {
+        Ok(Box::new(BytesParser::new()))
+    }
+}
+
+/// Parser that converts bytes to an `Event`.
+///
+/// This parser can be considered as the no-op action for input where no further
+/// decoding has been specified: the bytes are stored as the message of a log
+/// event.
+#[derive(Debug, Clone, Default)]
+pub struct BytesParser;
+
+impl BytesParser {
+    /// Creates a new `BytesParser`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Parser for BytesParser {
+    fn parse(&self, bytes: Bytes) -> crate::Result<Vec<Event>> {
+        Ok(vec![LogEvent::from(bytes).into()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::log_schema;
+
+    #[test]
+    fn parse_bytes() {
+        let parser = BytesParser::new();
+        let events = parser.parse(Bytes::from("foo")).unwrap();
+
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "foo".into());
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+    }
+}
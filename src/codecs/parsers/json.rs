@@ -0,0 +1,148 @@
+use crate::{
+    codecs::{BoxedParser, Parser, ParserConfig},
+    config::log_schema,
+    event::{Event, LogEvent},
+};
+use bytes::Bytes;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+/// Config used to build a `JsonParser`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct JsonParserConfig {}
+
+impl JsonParserConfig {
+    /// Creates a new `JsonParserConfig`.
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[typetag::serde(name = "json")]
+impl ParserConfig for JsonParserConfig {
+    fn build(&self) -> crate::Result<BoxedParser> {
+        Ok(Box::new(JsonParser::new()))
+    }
+}
+
+/// Parser that builds `Event`s from a byte frame containing JSON.
+///
+/// A JSON object is turned into a single log event, a JSON array of objects is
+/// turned into one log event per element. The timestamp is set to the time of
+/// parsing unless the object already contains a timestamp field.
+#[derive(Debug, Clone, Default)]
+pub struct JsonParser;
+
+impl JsonParser {
+    /// Creates a new `JsonParser`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Parser for JsonParser {
+    fn parse(&self, bytes: Bytes) -> crate::Result<Vec<Event>> {
+        let json: JsonValue = serde_json::from_slice(&bytes)
+            .map_err(|error| format!("Error parsing JSON: {:?}", error))?;
+
+        match json {
+            JsonValue::Object(_) => Ok(vec![log_from_json(json)?.into()]),
+            JsonValue::Array(values) => values
+                .into_iter()
+                .map(|value| log_from_json(value).map(Into::into))
+                .collect(),
+            _ => Err(format!(
+                "JSON value must be an object or an array of objects, found {}.",
+                json_value_type(&json)
+            )
+            .into()),
+        }
+    }
+}
+
+fn log_from_json(value: JsonValue) -> crate::Result<LogEvent> {
+    match value {
+        JsonValue::Object(map) => {
+            let mut log = LogEvent::default();
+            for (key, value) in map {
+                log.insert_flat(key, value);
+            }
+
+            let timestamp_key = log_schema().timestamp_key();
+            if !log.contains(timestamp_key) {
+                log.insert(timestamp_key, Utc::now());
+            }
+
+            Ok(log)
+        }
+        value => Err(format!(
+            "JSON array elements must be objects, found {}.",
+            json_value_type(&value)
+        )
+        .into()),
+    }
+}
+
+fn json_value_type(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Object(_) => "object",
+        JsonValue::Array(_) => "array",
+        JsonValue::String(_) => "string",
+        JsonValue::Number(_) => "number",
+        JsonValue::Bool(_) => "boolean",
+        JsonValue::Null => "null",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_json_object() {
+        let parser = JsonParser::new();
+        let events = parser
+            .parse(Bytes::from(r#"{ "foo": 123, "bar.baz": "qux" }"#))
+            .unwrap();
+
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log["foo"], 123.into());
+        assert_eq!(log.get_flat("bar.baz").unwrap(), &"qux".into());
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+    }
+
+    #[test]
+    fn parse_json_array() {
+        let parser = JsonParser::new();
+        let events = parser
+            .parse(Bytes::from(r#"[{ "foo": 123 }, { "foo": 456 }]"#))
+            .unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_log()["foo"], 123.into());
+        assert_eq!(events[1].as_log()["foo"], 456.into());
+    }
+
+    #[test]
+    fn parse_json_keeps_existing_timestamp() {
+        let parser = JsonParser::new();
+        let input = format!(r#"{{ "{}": "yesterday" }}"#, log_schema().timestamp_key());
+        let events = parser.parse(Bytes::from(input)).unwrap();
+
+        assert_eq!(
+            events[0].as_log()[log_schema().timestamp_key()],
+            "yesterday".into()
+        );
+    }
+
+    #[test]
+    fn parse_invalid_json() {
+        let parser = JsonParser::new();
+
+        assert!(parser.parse(Bytes::from("{ foo")).is_err());
+        assert!(parser.parse(Bytes::from("123")).is_err());
+        assert!(parser.parse(Bytes::from("[123]")).is_err());
+    }
+}
@@ -0,0 +1,48 @@
+//! A collection of support structures that are used in the process of parsing
+//! bytes into structured events.
+
+mod bytes;
+mod json;
+mod native;
+#[cfg(feature = "codecs-syslog")]
+mod syslog;
+
+pub use self::bytes::{BytesParser, BytesParserConfig};
+pub use self::json::{JsonParser, JsonParserConfig};
+pub use self::native::{NativeParser, NativeParserConfig};
+#[cfg(feature = "codecs-syslog")]
+pub use self::syslog::{SyslogParser, SyslogParserConfig};
+
+use crate::event::Event;
+use ::bytes::Bytes;
+use dyn_clone::DynClone;
+use std::fmt::Debug;
+
+/// Parse structured events from bytes.
+pub trait Parser: DynClone + Debug + Send + Sync {
+    /// Parses structured events from bytes.
+    ///
+    /// It returns a `Vec` rather than an `Event` directly, since one byte frame
+    /// can potentially hold multiple events, e.g. when parsing a JSON array.
+    fn parse(&self, bytes: Bytes) -> crate::Result<Vec<Event>>;
+}
+
+dyn_clone::clone_trait_object!(Parser);
+
+/// A `Box` containing a `Parser`.
+pub type BoxedParser = Box<dyn Parser>;
+
+/// Define options for a parser and build it from the config object.
+///
+/// Implementors must annotate the struct with `#[typetag::serde(name = "...")]`
+/// to define which value should be read from the `codec` key to select their
+/// implementation.
+#[typetag::serde(tag = "codec")]
+pub trait ParserConfig: Debug + DynClone + Send + Sync {
+    /// Builds a parser from this configuration.
+    ///
+    /// Fails if the configuration is invalid.
+    fn build(&self) -> crate::Result<BoxedParser>;
+}
+
+dyn_clone::clone_trait_object!(ParserConfig);
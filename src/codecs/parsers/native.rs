@@ -0,0 +1,90 @@
+use crate::{
+    codecs::{BoxedParser, Parser, ParserConfig},
+    event::{proto, Event},
+};
+use bytes::Bytes;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+/// Config used to build a `NativeParser`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct NativeParserConfig {}
+
+impl NativeParserConfig {
+    /// Creates a new `NativeParserConfig`.
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[typetag::serde(name = "native")]
+impl ParserConfig for NativeParserConfig {
+    fn build(&self) -> crate::Result<BoxedParser> {
+        Ok(Box::new(NativeParser::new()))
+    }
+}
+
+/// Parser that builds `Event`s from a byte frame containing Vector's native
+/// protobuf encoding.
+///
+/// This is the same encoding that is used by the `vector` source and sink, so
+/// both log and metric events can be received without any loss.
+#[derive(Debug, Clone, Default)]
+pub struct NativeParser;
+
+impl NativeParser {
+    /// Creates a new `NativeParser`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Parser for NativeParser {
+    fn parse(&self, bytes: Bytes) -> crate::Result<Vec<Event>> {
+        let event = proto::EventWrapper::decode(bytes)
+            .map(Event::from)
+            .map_err(|error| format!("Error parsing native protobuf: {}", error))?;
+
+        Ok(vec![event])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{
+        metric::{MetricKind, MetricValue},
+        Metric,
+    };
+
+    #[test]
+    fn parse_native_events() {
+        let parser = NativeParser::new();
+
+        let log = Event::from("foo");
+        let mut buffer = Vec::new();
+        proto::EventWrapper::from(log.clone())
+            .encode(&mut buffer)
+            .unwrap();
+        assert_eq!(parser.parse(Bytes::from(buffer)).unwrap(), vec![log]);
+
+        let metric = Event::Metric(Metric::new(
+            "foo",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+        let mut buffer = Vec::new();
+        proto::EventWrapper::from(metric.clone())
+            .encode(&mut buffer)
+            .unwrap();
+        assert_eq!(parser.parse(Bytes::from(buffer)).unwrap(), vec![metric]);
+    }
+
+    #[test]
+    fn parse_invalid_native_event() {
+        let parser = NativeParser::new();
+
+        // The length prefix of the first field exceeds the remaining bytes.
+        assert!(parser.parse(Bytes::from_static(b"\x0a\x05ab")).is_err());
+    }
+}
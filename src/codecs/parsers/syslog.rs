@@ -0,0 +1,151 @@
+use crate::{
+    codecs::{BoxedParser, Parser, ParserConfig},
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+};
+use bytes::Bytes;
+use chrono::{Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use syslog_loose::{IncompleteDate, Message, ProcId, Protocol};
+
+/// Config used to build a `SyslogParser`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SyslogParserConfig {}
+
+impl SyslogParserConfig {
+    /// Creates a new `SyslogParserConfig`.
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[typetag::serde(name = "syslog")]
+impl ParserConfig for SyslogParserConfig {
+    fn build(&self) -> crate::Result<BoxedParser> {
+        Ok(Box::new(SyslogParser::new()))
+    }
+}
+
+/// Parser using `syslog_loose` to parse RFC 3164 and RFC 5424 messages into
+/// log events.
+#[derive(Debug, Clone, Default)]
+pub struct SyslogParser;
+
+impl SyslogParser {
+    /// Creates a new `SyslogParser`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Parser for SyslogParser {
+    fn parse(&self, bytes: Bytes) -> crate::Result<Vec<Event>> {
+        let line = std::str::from_utf8(&bytes)
+            .map_err(|error| format!("Error parsing syslog message as UTF-8: {}", error))?;
+        let line = line.trim();
+        let parsed = syslog_loose::parse_message_with_year(line, resolve_year);
+
+        let mut log = LogEvent::from(parsed.msg);
+
+        let timestamp = parsed
+            .timestamp
+            .map(|timestamp| timestamp.into())
+            .unwrap_or_else(Utc::now);
+        log.insert(log_schema().timestamp_key(), timestamp);
+
+        insert_fields_from_syslog(&mut log, parsed);
+
+        Ok(vec![log.into()])
+    }
+}
+
+/// Function used to resolve the year for syslog messages that don't include the year.
+/// If the current month is January, and the syslog message is for December, it will take the previous year.
+/// Otherwise, take the current year.
+fn resolve_year((month, _date, _hour, _min, _sec): IncompleteDate) -> i32 {
+    let now = Utc::now();
+    if now.month() == 1 && month == 12 {
+        now.year() - 1
+    } else {
+        now.year()
+    }
+}
+
+/// Inserts the fields of a parsed syslog message, apart from the message and
+/// the timestamp, into the log event.
+fn insert_fields_from_syslog(log: &mut LogEvent, parsed: Message<&str>) {
+    if let Some(host) = parsed.hostname {
+        log.insert("hostname", host.to_string());
+    }
+    if let Some(severity) = parsed.severity {
+        log.insert("severity", severity.as_str().to_owned());
+    }
+    if let Some(facility) = parsed.facility {
+        log.insert("facility", facility.as_str().to_owned());
+    }
+    if let Protocol::RFC5424(version) = parsed.protocol {
+        log.insert("version", version as i64);
+    }
+    if let Some(app_name) = parsed.appname {
+        log.insert("appname", app_name.to_owned());
+    }
+    if let Some(msg_id) = parsed.msgid {
+        log.insert("msgid", msg_id.to_owned());
+    }
+    if let Some(procid) = parsed.procid {
+        let value: Value = match procid {
+            ProcId::PID(pid) => pid.into(),
+            ProcId::Name(name) => name.to_string().into(),
+        };
+        log.insert("procid", value);
+    }
+
+    for element in parsed.structured_data.into_iter() {
+        for (name, value) in element.params.into_iter() {
+            let key = format!("{}.{}", element.id, name);
+            log.insert(key, value.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::prelude::*;
+
+    #[test]
+    fn parse_syslog_5424() {
+        let parser = SyslogParser::new();
+        let input = Bytes::from(
+            r#"<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 [exampleSDID@32473 iut="3"] 'su root' failed for lonvick on /dev/pts/8"#,
+        );
+        let events = parser.parse(input).unwrap();
+
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(
+            log[log_schema().message_key()],
+            "'su root' failed for lonvick on /dev/pts/8".into()
+        );
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.ymd(2003, 10, 11).and_hms_milli(22, 14, 15, 3).into()
+        );
+        assert_eq!(log["hostname"], "mymachine.example.com".into());
+        assert_eq!(log["severity"], "crit".into());
+        assert_eq!(log["facility"], "auth".into());
+        assert_eq!(log["version"], 1.into());
+        assert_eq!(log["appname"], "su".into());
+        assert_eq!(log["msgid"], "ID47".into());
+        assert_eq!(log["exampleSDID@32473.iut"], "3".into());
+    }
+
+    #[test]
+    fn parse_syslog_invalid_utf8() {
+        let parser = SyslogParser::new();
+
+        assert!(parser
+            .parse(Bytes::from_static(&[b'<', b'3', b'4', b'>', 0xf0, 0x28]))
+            .is_err());
+    }
+}
@@ -29,6 +29,16 @@ pub trait CheckFieldsPredicate: std::fmt::Debug + Send + Sync + dyn_clone::DynCl
     fn check(&self, e: &Event) -> bool;
 }
 
+/// Looks up a field of a log or trace event, as traces share their
+/// representation with logs. Metrics have no fields to look up.
+fn log_field<'a>(event: &'a Event, target: &str) -> Option<&'a Value> {
+    match event {
+        Event::Log(log) => log.get(target),
+        Event::Trace(trace) => trace.get(target),
+        Event::Metric(_) => None,
+    }
+}
+
 dyn_clone::clone_trait_object!(CheckFieldsPredicate);
 
 //------------------------------------------------------------------------------
@@ -54,26 +64,28 @@ impl EqualsPredicate {
 impl CheckFieldsPredicate for EqualsPredicate {
     fn check(&self, event: &Event) -> bool {
         match event {
-            Event::Log(l) => l.get(&self.target).map_or(false, |v| match &self.arg {
-                CheckFieldsPredicateArg::String(s) => s.as_bytes() == v.as_bytes(),
-                CheckFieldsPredicateArg::VecString(ss) => {
-                    ss.iter().any(|s| s.as_bytes() == v.as_bytes())
-                }
-                CheckFieldsPredicateArg::Integer(i) => match v {
-                    Value::Integer(vi) => *i == *vi,
-                    Value::Float(vf) => *i == *vf as i64,
-                    _ => false,
-                },
-                CheckFieldsPredicateArg::Float(f) => match v {
-                    Value::Float(vf) => *f == *vf,
-                    Value::Integer(vi) => *f == *vi as f64,
-                    _ => false,
-                },
-                CheckFieldsPredicateArg::Boolean(b) => match v {
-                    Value::Boolean(vb) => *b == *vb,
-                    _ => false,
-                },
-            }),
+            Event::Log(_) | Event::Trace(_) => {
+                log_field(event, &self.target).map_or(false, |v| match &self.arg {
+                    CheckFieldsPredicateArg::String(s) => s.as_bytes() == v.as_bytes(),
+                    CheckFieldsPredicateArg::VecString(ss) => {
+                        ss.iter().any(|s| s.as_bytes() == v.as_bytes())
+                    }
+                    CheckFieldsPredicateArg::Integer(i) => match v {
+                        Value::Integer(vi) => *i == *vi,
+                        Value::Float(vf) => *i == *vf as i64,
+                        _ => false,
+                    },
+                    CheckFieldsPredicateArg::Float(f) => match v {
+                        Value::Float(vf) => *f == *vf,
+                        Value::Integer(vi) => *f == *vi as f64,
+                        _ => false,
+                    },
+                    CheckFieldsPredicateArg::Boolean(b) => match v {
+                        Value::Boolean(vb) => *b == *vb,
+                        _ => false,
+                    },
+                })
+            }
             Event::Metric(m) => m
                 .tags()
                 .and_then(|t| t.get(&self.target))
@@ -115,7 +127,7 @@ impl ContainsPredicate {
 impl CheckFieldsPredicate for ContainsPredicate {
     fn check(&self, event: &Event) -> bool {
         match event {
-            Event::Log(l) => l.get(&self.target).map_or(false, |v| {
+            Event::Log(_) | Event::Trace(_) => log_field(event, &self.target).map_or(false, |v| {
                 let v = v.to_string_lossy();
                 self.arg.iter().any(|s| v.contains(s))
             }),
@@ -156,7 +168,7 @@ impl StartsWithPredicate {
 impl CheckFieldsPredicate for StartsWithPredicate {
     fn check(&self, event: &Event) -> bool {
         match event {
-            Event::Log(l) => l.get(&self.target).map_or(false, |v| {
+            Event::Log(_) | Event::Trace(_) => log_field(event, &self.target).map_or(false, |v| {
                 let v = v.to_string_lossy();
                 self.arg.iter().any(|s| v.starts_with(s))
             }),
@@ -195,7 +207,7 @@ impl EndsWithPredicate {
 impl CheckFieldsPredicate for EndsWithPredicate {
     fn check(&self, event: &Event) -> bool {
         match event {
-            Event::Log(l) => l.get(&self.target).map_or(false, |v| {
+            Event::Log(_) | Event::Trace(_) => log_field(event, &self.target).map_or(false, |v| {
                 let v = v.to_string_lossy();
                 self.arg.iter().any(|s| v.ends_with(s))
             }),
@@ -233,8 +245,7 @@ impl NotEqualsPredicate {
 impl CheckFieldsPredicate for NotEqualsPredicate {
     fn check(&self, event: &Event) -> bool {
         match event {
-            Event::Log(l) => l
-                .get(&self.target)
+            Event::Log(_) | Event::Trace(_) => log_field(event, &self.target)
                 .map(|f| f.as_bytes())
                 .map_or(false, |b| {
                     //false if any match, else true
@@ -276,8 +287,7 @@ impl RegexPredicate {
 impl CheckFieldsPredicate for RegexPredicate {
     fn check(&self, event: &Event) -> bool {
         match event {
-            Event::Log(log) => log
-                .get(&self.target)
+            Event::Log(_) | Event::Trace(_) => log_field(event, &self.target)
                 .map(|field| field.to_string_lossy())
                 .map_or(false, |field| self.regex.is_match(&field)),
             Event::Metric(metric) => metric
@@ -311,7 +321,7 @@ impl ExistsPredicate {
 impl CheckFieldsPredicate for ExistsPredicate {
     fn check(&self, event: &Event) -> bool {
         (match event {
-            Event::Log(l) => l.get(&self.target).is_some(),
+            Event::Log(_) | Event::Trace(_) => log_field(event, &self.target).is_some(),
             Event::Metric(m) => m.tags().map_or(false, |t| t.contains_key(&self.target)),
         }) == self.arg
     }
@@ -351,7 +361,7 @@ impl IpCidrPredicate {
 impl CheckFieldsPredicate for IpCidrPredicate {
     fn check(&self, event: &Event) -> bool {
         match event {
-            Event::Log(l) => l.get(&self.target).map_or(false, |v| {
+            Event::Log(_) | Event::Trace(_) => log_field(event, &self.target).map_or(false, |v| {
                 let v = v.to_string_lossy();
                 IpAddr::from_str(&v).map_or(false, |ip_addr| {
                     self.cidrs.iter().any(|cidr| cidr.contains(ip_addr))
@@ -415,7 +425,7 @@ impl LengthEqualsPredicate {
 impl CheckFieldsPredicate for LengthEqualsPredicate {
     fn check(&self, event: &Event) -> bool {
         match event {
-            Event::Log(l) => l.get(&self.target).map_or(false, |v| {
+            Event::Log(_) | Event::Trace(_) => log_field(event, &self.target).map_or(false, |v| {
                 let len = match v {
                     Value::Bytes(value) => value.len(),
                     Value::Array(value) => value.len(),
@@ -582,7 +592,7 @@ impl Condition for CheckFields {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::event::Event;
+    use crate::event::{Event, TraceEvent};
 
     #[test]
     fn generate_config() {
@@ -1100,6 +1110,44 @@ mod test {
         );
     }
 
+    #[test]
+    fn check_trace_fields() {
+        let mut preds: IndexMap<String, CheckFieldsPredicateArg> = IndexMap::new();
+        preds.insert(
+            "name.eq".into(),
+            CheckFieldsPredicateArg::String("checkout".into()),
+        );
+        preds.insert(
+            "status.neq".into(),
+            CheckFieldsPredicateArg::String("error".into()),
+        );
+        preds.insert(
+            "service.regex".into(),
+            CheckFieldsPredicateArg::String("^web-".into()),
+        );
+        preds.insert(
+            "parent_id.exists".into(),
+            CheckFieldsPredicateArg::Boolean(false),
+        );
+
+        let cond = CheckFieldsConfig { predicates: preds }.build().unwrap();
+
+        let mut trace = TraceEvent::default();
+        trace.insert("name", "checkout");
+        trace.insert("status", "ok");
+        trace.insert("service", "web-frontend");
+        let mut event = Event::Trace(trace);
+        assert!(cond.check(&event));
+        assert_eq!(cond.check_with_context(&event), Ok(()));
+
+        event.as_mut_trace().insert("parent_id", "1");
+        assert!(!cond.check(&event));
+        assert_eq!(
+            cond.check_with_context(&event),
+            Err("predicates failed: [ parent_id.exists: false ]".to_owned())
+        );
+    }
+
     #[test]
     fn check_field_length_eq() {
         let mut preds: IndexMap<String, CheckFieldsPredicateArg> = IndexMap::new();
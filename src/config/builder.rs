@@ -1,8 +1,9 @@
 #[cfg(feature = "api")]
 use super::api;
 use super::{
-    compiler, default_data_dir, provider, Config, GlobalOptions, HealthcheckOptions, SinkConfig,
-    SinkOuter, SourceConfig, SourceOuter, TestDefinition, TransformConfig, TransformOuter,
+    compiler, default_data_dir, provider, Config, EnrichmentTableConfig, EnrichmentTableOuter,
+    GlobalOptions, HealthcheckOptions, PipelineDefinition, SinkConfig, SinkOuter, SourceConfig,
+    SourceOuter, TestDefinition, TransformConfig, TransformOuter,
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
@@ -18,12 +19,16 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub healthchecks: HealthcheckOptions,
     #[serde(default)]
+    pub enrichment_tables: IndexMap<String, EnrichmentTableOuter>,
+    #[serde(default)]
     pub sources: IndexMap<String, SourceOuter>,
     #[serde(default)]
     pub sinks: IndexMap<String, SinkOuter>,
     #[serde(default)]
     pub transforms: IndexMap<String, TransformOuter>,
     #[serde(default)]
+    pub pipelines: IndexMap<String, PipelineDefinition>,
+    #[serde(default)]
     pub tests: Vec<TestDefinition>,
     pub provider: Option<Box<dyn provider::ProviderConfig>>,
 }
@@ -47,9 +52,11 @@ impl From<Config> for ConfigBuilder {
             #[cfg(feature = "api")]
             api: c.api,
             healthchecks: c.healthchecks,
+            enrichment_tables: c.enrichment_tables,
             sources: c.sources,
             sinks: c.sinks,
             transforms: c.transforms,
+            pipelines: IndexMap::new(),
             provider: None,
             tests: c.tests,
         }
@@ -71,6 +78,17 @@ impl ConfigBuilder {
         compiler::compile(self)
     }
 
+    pub fn add_enrichment_table<E: EnrichmentTableConfig + 'static, T: Into<String>>(
+        &mut self,
+        name: T,
+        enrichment_table: E,
+    ) {
+        self.enrichment_tables.insert(
+            name.into(),
+            EnrichmentTableOuter::new(Box::new(enrichment_table)),
+        );
+    }
+
     pub fn add_source<S: SourceConfig + 'static, T: Into<String>>(&mut self, name: T, source: S) {
         self.sources.insert(name.into(), SourceOuter::new(source));
     }
@@ -128,8 +146,17 @@ impl ConfigBuilder {
             errors.extend(merge_errors);
         }
 
+        // Acknowledgements are enabled globally if any of the configs
+        // enables them.
+        self.global.acknowledgements |= with.global.acknowledgements;
+
         self.healthchecks.merge(with.healthchecks);
 
+        with.enrichment_tables.keys().for_each(|k| {
+            if self.enrichment_tables.contains_key(k) {
+                errors.push(format!("duplicate enrichment_table name found: {}", k));
+            }
+        });
         with.sources.keys().for_each(|k| {
             if self.sources.contains_key(k) {
                 errors.push(format!("duplicate source name found: {}", k));
@@ -145,6 +172,11 @@ impl ConfigBuilder {
                 errors.push(format!("duplicate transform name found: {}", k));
             }
         });
+        with.pipelines.keys().for_each(|k| {
+            if self.pipelines.contains_key(k) {
+                errors.push(format!("duplicate pipeline name found: {}", k));
+            }
+        });
         with.tests.iter().for_each(|wt| {
             if self.tests.iter().any(|t| t.name == wt.name) {
                 errors.push(format!("duplicate test name found: {}", wt.name));
@@ -154,9 +186,11 @@ impl ConfigBuilder {
             return Err(errors);
         }
 
+        self.enrichment_tables.extend(with.enrichment_tables);
         self.sources.extend(with.sources);
         self.sinks.extend(with.sinks);
         self.transforms.extend(with.transforms);
+        self.pipelines.extend(with.pipelines);
         self.tests.extend(with.tests);
 
         Ok(())
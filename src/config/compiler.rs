@@ -1,12 +1,17 @@
-use super::{builder::ConfigBuilder, validation, Config, TransformOuter};
+use super::{
+    builder::ConfigBuilder, pipeline::PipelineTransformConfig, validation, Config,
+    ExpandedPipeline, TransformOuter, DROPPED_OUTPUT,
+};
 use indexmap::IndexMap;
+use std::collections::HashSet;
 
 pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<String>> {
     let mut errors = Vec::new();
 
-    let expansions = expand_macros(&mut builder)?;
+    let (pipelines, mut expansions) = expand_pipelines(&mut builder)?;
+    expansions.extend(expand_macros(&mut builder)?);
 
-    expand_globs(&mut builder);
+    expand_globs(&mut builder, &pipelines);
 
     let warnings = validation::warnings(&builder);
 
@@ -29,9 +34,11 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
                 #[cfg(feature = "api")]
                 api: builder.api,
                 healthchecks: builder.healthchecks,
+                enrichment_tables: builder.enrichment_tables,
                 sources: builder.sources,
                 sinks: builder.sinks,
                 transforms: builder.transforms,
+                pipelines,
                 tests: builder.tests,
                 expansions,
             },
@@ -42,6 +49,123 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
     }
 }
 
+/// Replaces each transform of type `pipeline` with the chain of transforms of
+/// its pipeline, named `<name>.<transform>`. The first transform of the chain
+/// takes the inputs of the replaced transform, and the last one takes its
+/// place as an input of other components.
+///
+/// Expanded names must not collide with the name of any other component, nor
+/// with a named output, e.g. `<component>.dropped`.
+pub(super) fn expand_pipelines(
+    config: &mut ConfigBuilder,
+) -> Result<
+    (
+        IndexMap<String, ExpandedPipeline>,
+        IndexMap<String, Vec<String>>,
+    ),
+    Vec<String>,
+> {
+    let mut expanded_transforms = IndexMap::new();
+    let mut pipelines = IndexMap::new();
+    let mut expansions = IndexMap::new();
+    let mut errors = Vec::new();
+
+    let mut taken = config
+        .sources
+        .keys()
+        .chain(config.sinks.keys())
+        .chain(config.transforms.iter().filter_map(|(name, transform)| {
+            PipelineTransformConfig::from_transform(transform.inner.as_ref())
+                .is_none()
+                .then(|| name)
+        }))
+        .cloned()
+        .chain(validation::named_outputs(config).map(|(output, _, _)| output))
+        .collect::<HashSet<_>>();
+
+    for (name, transform) in std::mem::take(&mut config.transforms) {
+        let instance = match PipelineTransformConfig::from_transform(transform.inner.as_ref()) {
+            Some(instance) => instance,
+            None => {
+                expanded_transforms.insert(name, transform);
+                continue;
+            }
+        };
+
+        let chain = match config.pipelines.get(&instance.pipeline) {
+            Some(definition) => definition.instantiate(&instance.params),
+            None => Err(vec!["Pipeline doesn't exist.".to_owned()]),
+        };
+        let chain = match chain {
+            Ok(chain) => chain,
+            Err(chain_errors) => {
+                errors.extend(chain_errors.into_iter().map(|error| {
+                    format!(
+                        "failed to expand pipeline {:?} for transform {:?}: {}",
+                        instance.pipeline, name, error
+                    )
+                }));
+                continue;
+            }
+        };
+
+        let collisions = chain
+            .iter()
+            .map(|(child, _)| format!("{}.{}", name, child))
+            .filter(|full_name| !taken.insert(full_name.clone()))
+            .collect::<Vec<_>>();
+        if !collisions.is_empty() {
+            errors.extend(collisions.into_iter().map(|full_name| {
+                format!(
+                    "failed to expand pipeline {:?} for transform {:?}: {:?} is already the name of a component or output.",
+                    instance.pipeline, name, full_name
+                )
+            }));
+            continue;
+        }
+
+        let mut inputs = transform.inputs;
+        let mut children = Vec::new();
+        for (child, inner) in chain {
+            let full_name = format!("{}.{}", name, child);
+            expanded_transforms.insert(full_name.clone(), TransformOuter { inputs, inner });
+            inputs = vec![full_name.clone()];
+            children.push(full_name);
+        }
+        expansions.insert(name.clone(), vec![children[0].clone()]);
+        pipelines.insert(
+            name,
+            ExpandedPipeline {
+                pipeline: instance.pipeline,
+                transforms: children,
+            },
+        );
+    }
+    config.transforms = expanded_transforms;
+
+    // Components reading from an expanded transform now read from the end of
+    // its chain.
+    let inputs = config
+        .transforms
+        .values_mut()
+        .map(|transform| &mut transform.inputs)
+        .chain(config.sinks.values_mut().map(|sink| &mut sink.inputs));
+    for input in inputs.flatten() {
+        if let Some(last) = pipelines
+            .get(input.as_str())
+            .and_then(|pipeline: &ExpandedPipeline| pipeline.transforms.last())
+        {
+            *input = last.clone();
+        }
+    }
+
+    if !errors.is_empty() {
+        Err(errors)
+    } else {
+        Ok((pipelines, expansions))
+    }
+}
+
 /// Some component configs can act like macros and expand themselves into multiple replacement
 /// configs. Performs those expansions and records the relevant metadata.
 pub(super) fn expand_macros(
@@ -85,21 +209,45 @@ pub(super) fn expand_macros(
     }
 }
 
-/// Expand globs in input lists
-fn expand_globs(config: &mut ConfigBuilder) {
+/// Expand globs in input lists. Only the last transform of an expanded
+/// pipeline can be matched, the others are internal to its chain.
+fn expand_globs(config: &mut ConfigBuilder, pipelines: &IndexMap<String, ExpandedPipeline>) {
+    let internal = pipelines
+        .values()
+        .flat_map(|pipeline| pipeline.transforms.split_last())
+        .flat_map(|(_, internal)| internal)
+        .collect::<Vec<_>>();
+    let (dropped_outputs, named_outputs) = validation::named_outputs(config)
+        .map(|(output, component, name)| (output, component.clone(), name))
+        .partition::<Vec<_>, _>(|(_, _, name)| name == DROPPED_OUTPUT);
+    // Each candidate along with the component it belongs to.
     let candidates = config
         .sources
         .keys()
-        .chain(config.transforms.keys())
-        .cloned()
-        .collect::<Vec<String>>();
+        .chain(
+            config
+                .transforms
+                .keys()
+                .filter(|name| !internal.contains(name)),
+        )
+        .map(|name| (name.clone(), name.clone()))
+        .chain(
+            named_outputs
+                .into_iter()
+                .map(|(output, component, _)| (output, component)),
+        )
+        .collect::<Vec<_>>();
+    let dropped_outputs = dropped_outputs
+        .into_iter()
+        .map(|(output, _, _)| output)
+        .collect::<Vec<_>>();
 
     for (name, transform) in config.transforms.iter_mut() {
-        expand_globs_inner(&mut transform.inputs, name, &candidates);
+        expand_globs_inner(&mut transform.inputs, name, &candidates, &dropped_outputs);
     }
 
     for (name, sink) in config.sinks.iter_mut() {
-        expand_globs_inner(&mut sink.inputs, name, &candidates);
+        expand_globs_inner(&mut sink.inputs, name, &candidates, &dropped_outputs);
     }
 }
 
@@ -119,17 +267,29 @@ impl InputMatcher {
     }
 }
 
-fn expand_globs_inner(inputs: &mut Vec<String>, name: &str, candidates: &[String]) {
+/// Dropped outputs are only matched by name, so that wildcards don't pull
+/// failed events into components that didn't ask for them. Wildcards never
+/// match a component's own outputs.
+fn expand_globs_inner(
+    inputs: &mut Vec<String>,
+    name: &str,
+    candidates: &[(String, String)],
+    dropped_outputs: &[String],
+) {
     let raw_inputs = std::mem::take(inputs);
     for raw_input in raw_inputs {
+        if dropped_outputs.contains(&raw_input) {
+            inputs.push(raw_input);
+            continue;
+        }
         let matcher = glob::Pattern::new(&raw_input)
             .map(InputMatcher::Pattern)
             .unwrap_or_else(|error| {
                 warn!(message = "Invalid glob pattern for input.", component_name = name, %error);
                 InputMatcher::String(raw_input)
             });
-        for input in candidates {
-            if matcher.matches(input) && input != name {
+        for (input, component) in candidates {
+            if matcher.matches(input) && component != name {
                 inputs.push(input.clone())
             }
         }
@@ -141,8 +301,8 @@ mod test {
     use super::*;
     use crate::{
         config::{
-            DataType, GlobalOptions, SinkConfig, SinkContext, SourceConfig, SourceContext,
-            TransformConfig,
+            DataType, SinkConfig, SinkContext, SourceConfig, SourceContext, TransformConfig,
+            TransformContext,
         },
         sinks::{Healthcheck, VectorSink},
         sources::Source,
@@ -157,6 +317,9 @@ mod test {
     #[derive(Debug, Clone, Serialize, Deserialize)]
     struct MockTransformConfig;
 
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct MockOutputsTransformConfig;
+
     #[derive(Debug, Serialize, Deserialize)]
     struct MockSinkConfig;
 
@@ -179,7 +342,7 @@ mod test {
     #[async_trait]
     #[typetag::serde(name = "mock")]
     impl TransformConfig for MockTransformConfig {
-        async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+        async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
             unimplemented!()
         }
 
@@ -196,6 +359,30 @@ mod test {
         }
     }
 
+    #[async_trait]
+    #[typetag::serde(name = "mock_outputs")]
+    impl TransformConfig for MockOutputsTransformConfig {
+        async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+            unimplemented!()
+        }
+
+        fn transform_type(&self) -> &'static str {
+            "mock_outputs"
+        }
+
+        fn input_type(&self) -> DataType {
+            DataType::Any
+        }
+
+        fn output_type(&self) -> DataType {
+            DataType::Any
+        }
+
+        fn named_outputs(&self) -> Vec<String> {
+            vec!["a".to_owned(), "b".to_owned()]
+        }
+    }
+
     #[async_trait]
     #[typetag::serde(name = "mock")]
     impl SinkConfig for MockSinkConfig {
@@ -233,4 +420,36 @@ mod test {
         );
         assert_eq!(config.sinks["quix"].inputs, vec!["foo1", "foo2", "foos"]);
     }
+
+    #[test]
+    fn glob_expansion_skips_dropped_outputs() {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("foo", MockSourceConfig);
+        builder.add_sink("bar", &["*"], MockSinkConfig);
+        builder.add_sink("dead_letters", &["bar.dropped"], MockSinkConfig);
+        builder.sinks["bar"].reroute_dropped = true;
+
+        let config = builder.build().expect("build should succeed");
+
+        assert_eq!(config.sinks["bar"].inputs, vec!["foo"]);
+        assert_eq!(config.sinks["dead_letters"].inputs, vec!["bar.dropped"]);
+    }
+
+    #[test]
+    fn glob_expansion_matches_named_outputs() {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("foo", MockSourceConfig);
+        builder.add_transform("split", &["*"], MockOutputsTransformConfig);
+        builder.add_sink("bar", &["split.*"], MockSinkConfig);
+        builder.add_sink("baz", &["*"], MockSinkConfig);
+
+        let config = builder.build().expect("build should succeed");
+
+        assert_eq!(config.transforms["split"].inputs, vec!["foo"]);
+        assert_eq!(config.sinks["bar"].inputs, vec!["split.a", "split.b"]);
+        assert_eq!(
+            config.sinks["baz"].inputs,
+            vec!["foo", "split", "split.a", "split.b"]
+        );
+    }
 }
@@ -26,6 +26,7 @@ pub mod component;
 mod diff;
 pub mod format;
 mod loading;
+mod pipeline;
 pub mod provider;
 mod unit_test;
 mod validation;
@@ -39,6 +40,7 @@ pub use loading::{
     load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider, load_from_str,
     merge_path_lists, process_paths, CONFIG_PATHS,
 };
+pub use pipeline::{ExpandedPipeline, PipelineDefinition};
 pub use unit_test::build_unit_tests_main as build_unit_tests;
 pub use validation::warnings;
 pub use vector_core::config::{log_schema, LogSchema};
@@ -78,9 +80,13 @@ pub struct Config {
     #[cfg(feature = "api")]
     pub api: api::Options,
     pub healthchecks: HealthcheckOptions,
+    pub enrichment_tables: IndexMap<String, EnrichmentTableOuter>,
     pub sources: IndexMap<String, SourceOuter>,
     pub sinks: IndexMap<String, SinkOuter>,
     pub transforms: IndexMap<String, TransformOuter>,
+    /// The pipelines used in the config, keyed by the name of the component
+    /// that was expanded into each of them.
+    pub pipelines: IndexMap<String, ExpandedPipeline>,
     tests: Vec<TestDefinition>,
     expansions: IndexMap<String, Vec<String>>,
 }
@@ -94,6 +100,26 @@ pub struct GlobalOptions {
     pub log_schema: LogSchema,
     #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
     pub timezone: TimeZone,
+    /// Default for the `acknowledgements` option of every source that
+    /// doesn't set it explicitly.
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub acknowledgements: bool,
+}
+
+/// The name of the output that components configured to reroute dropped
+/// events send them to.
+pub const DROPPED_OUTPUT: &str = "dropped";
+
+/// The name under which a named output of a component can be used as an
+/// input, e.g. `my_route.errors`.
+pub fn named_output(component: &str, output: &str) -> String {
+    format!("{}.{}", component, output)
+}
+
+/// The name under which a component's `dropped` output can be used as an
+/// input, e.g. `my_remap.dropped`.
+pub fn dropped_output(component: &str) -> String {
+    named_output(component, DROPPED_OUTPUT)
 }
 
 pub fn default_data_dir() -> Option<PathBuf> {
@@ -200,6 +226,7 @@ pub enum DataType {
     Any,
     Log,
     Metric,
+    Trace,
 }
 
 pub trait GenerateConfig {
@@ -219,23 +246,25 @@ macro_rules! impl_generate_config_from_default {
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SourceOuter {
-    #[serde(default = "default_acknowledgements")]
-    pub acknowledgements: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acknowledgements: Option<bool>,
     #[serde(flatten)]
     pub(super) inner: Box<dyn SourceConfig>,
 }
 
-fn default_acknowledgements() -> bool {
-    false
-}
-
 impl SourceOuter {
     pub(crate) fn new(source: impl SourceConfig + 'static) -> Self {
         Self {
-            acknowledgements: default_acknowledgements(),
+            acknowledgements: None,
             inner: Box::new(source),
         }
     }
+
+    /// Resolve whether this source waits for delivery before
+    /// acknowledging, falling back to the global option.
+    pub(crate) fn acknowledgements(&self, globals: &GlobalOptions) -> bool {
+        self.acknowledgements.unwrap_or(globals.acknowledgements)
+    }
 }
 
 #[async_trait]
@@ -275,7 +304,7 @@ impl SourceContext {
                 globals: GlobalOptions::default(),
                 shutdown: shutdown_signal,
                 out,
-                acknowledgements: default_acknowledgements(),
+                acknowledgements: false,
             },
             shutdown,
         )
@@ -288,7 +317,7 @@ impl SourceContext {
             globals: GlobalOptions::default(),
             shutdown: ShutdownSignal::noop(),
             out,
-            acknowledgements: default_acknowledgements(),
+            acknowledgements: false,
         }
     }
 }
@@ -312,6 +341,14 @@ pub struct SinkOuter {
     #[serde(default)]
     pub buffer: crate::buffers::BufferConfig,
 
+    /// Send the events the sink rejects as unrecoverable to its `dropped`
+    /// output instead of discarding them.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub reroute_dropped: bool,
+
     #[serde(flatten)]
     pub inner: Box<dyn SinkConfig>,
 }
@@ -322,6 +359,7 @@ impl SinkOuter {
             buffer: Default::default(),
             healthcheck: SinkHealthcheckOptions::default(),
             healthcheck_uri: None,
+            reroute_dropped: false,
             inner,
             inputs,
         }
@@ -436,10 +474,25 @@ pub struct TransformOuter {
     pub inner: Box<dyn TransformConfig>,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct TransformContext {
+    pub globals: GlobalOptions,
+    pub enrichment_tables: enrichment::TableRegistry,
+}
+
+impl TransformContext {
+    pub fn new_with_globals(globals: GlobalOptions) -> Self {
+        Self {
+            globals,
+            enrichment_tables: Default::default(),
+        }
+    }
+}
+
 #[async_trait]
 #[typetag::serde(tag = "type")]
 pub trait TransformConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone {
-    async fn build(&self, globals: &GlobalOptions) -> crate::Result<transforms::Transform>;
+    async fn build(&self, context: &TransformContext) -> crate::Result<transforms::Transform>;
 
     fn input_type(&self) -> DataType;
 
@@ -447,6 +500,17 @@ pub trait TransformConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone
 
     fn transform_type(&self) -> &'static str;
 
+    /// Resources that the transform is using.
+    fn resources(&self) -> Vec<Resource> {
+        Vec::new()
+    }
+
+    /// The named outputs the transform writes to besides its default one.
+    /// Each of them can be used as an input as `<name>.<output>`.
+    fn named_outputs(&self) -> Vec<String> {
+        Vec::new()
+    }
+
     /// Allows a transform configuration to expand itself into multiple "child"
     /// transformations to replace it. This allows a transform to act as a macro
     /// for various patterns.
@@ -461,6 +525,30 @@ pub type TransformDescription = ComponentDescription<Box<dyn TransformConfig>>;
 
 inventory::collect!(TransformDescription);
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EnrichmentTableOuter {
+    #[serde(flatten)]
+    pub inner: Box<dyn EnrichmentTableConfig>,
+}
+
+impl EnrichmentTableOuter {
+    pub fn new(inner: Box<dyn EnrichmentTableConfig>) -> Self {
+        EnrichmentTableOuter { inner }
+    }
+}
+
+#[async_trait]
+#[typetag::serde(tag = "type")]
+pub trait EnrichmentTableConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone {
+    async fn build(&self, globals: &GlobalOptions) -> crate::Result<Box<dyn enrichment::Table>>;
+}
+
+dyn_clone::clone_trait_object!(EnrichmentTableConfig);
+
+pub type EnrichmentTableDescription = ComponentDescription<Box<dyn EnrichmentTableConfig>>;
+
+inventory::collect!(EnrichmentTableDescription);
+
 /// Unique thing, like port, of which only one owner can be.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum Resource {
@@ -468,6 +556,9 @@ pub enum Resource {
     SystemFdOffset(usize),
     Stdin,
     DiskBuffer(String),
+    /// A subdirectory of the `data_dir` of a component, or of the global one
+    /// when it's `None`.
+    DataSubdir(Option<PathBuf>, String),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Copy)]
@@ -543,6 +634,12 @@ impl Display for Resource {
             Resource::SystemFdOffset(offset) => write!(fmt, "systemd {}th socket", offset + 1),
             Resource::Stdin => write!(fmt, "stdin"),
             Resource::DiskBuffer(name) => write!(fmt, "disk buffer {:?}", name),
+            Resource::DataSubdir(Some(data_dir), subdir) => {
+                write!(fmt, "data directory {:?}", data_dir.join(subdir))
+            }
+            Resource::DataSubdir(None, subdir) => {
+                write!(fmt, "data directory {:?} of the global data_dir", subdir)
+            }
         }
     }
 }
@@ -605,6 +702,16 @@ impl Config {
             .cloned()
             .unwrap_or_else(|| vec![String::from(identifier)])
     }
+
+    /// Resolve a logical component name into the name of the component its events come out of.
+    /// An expanded pipeline outputs the events of the last transform of its chain.
+    pub fn get_output(&self, identifier: &str) -> String {
+        self.pipelines
+            .get(identifier)
+            .and_then(|pipeline| pipeline.transforms.last())
+            .cloned()
+            .unwrap_or_else(|| String::from(identifier))
+    }
 }
 
 #[cfg(all(
@@ -641,6 +748,35 @@ mod test {
         )
     }
 
+    #[test]
+    fn acknowledgements_fall_back_to_global() {
+        let config = load_from_str(
+            indoc! {r#"
+                acknowledgements = true
+
+                [sources.in]
+                  type = "file"
+                  include = ["/var/log/messages"]
+
+                [sources.in_no_acks]
+                  type = "file"
+                  include = ["/var/log/syslog"]
+                  acknowledgements = false
+
+                [sinks.out]
+                  type = "console"
+                  inputs = ["in", "in_no_acks"]
+                  encoding = "json"
+            "#},
+            Some(Format::Toml),
+        )
+        .unwrap();
+
+        assert!(config.global.acknowledgements);
+        assert!(config.sources["in"].acknowledgements(&config.global));
+        assert!(!config.sources["in_no_acks"].acknowledgements(&config.global));
+    }
+
     #[test]
     fn default_schema() {
         let config = load_from_str(
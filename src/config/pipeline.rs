@@ -0,0 +1,203 @@
+use super::{DataType, TransformConfig, TransformContext};
+use crate::transforms::Transform;
+use indexmap::IndexMap;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A reusable chain of transforms. Each component of type `pipeline` using it
+/// is replaced by a copy of the chain when the config is compiled.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineDefinition {
+    /// Default values of the parameters.
+    #[serde(default)]
+    pub params: IndexMap<String, String>,
+    /// The transforms of the chain, in order. They are kept unparsed until
+    /// their parameters are substituted.
+    pub transforms: IndexMap<String, Value>,
+}
+
+/// A transform standing in for a pipeline, until it's expanded.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineTransformConfig {
+    pub pipeline: String,
+    #[serde(default)]
+    pub params: IndexMap<String, String>,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "pipeline")]
+impl TransformConfig for PipelineTransformConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Err("this transform must be expanded".into())
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "pipeline"
+    }
+}
+
+impl PipelineTransformConfig {
+    /// Recovers the pipeline options of a transform of type `pipeline`.
+    pub(super) fn from_transform(transform: &dyn TransformConfig) -> Option<Self> {
+        if transform.transform_type() != "pipeline" {
+            return None;
+        }
+        // Trait objects can't be downcast, so go through their serialized
+        // form, without the tag.
+        let mut value = serde_json::to_value(transform).ok()?;
+        value.as_object_mut()?.remove("type");
+        serde_json::from_value(value).ok()
+    }
+}
+
+/// The transforms a pipeline was expanded into.
+#[derive(Debug, Clone, Default)]
+pub struct ExpandedPipeline {
+    pub pipeline: String,
+    pub transforms: Vec<String>,
+}
+
+impl PipelineDefinition {
+    /// Builds the transforms of the chain, replacing every `${params.<name>}`
+    /// in their fields with the value of the parameter.
+    pub(super) fn instantiate(
+        &self,
+        params: &IndexMap<String, String>,
+    ) -> Result<Vec<(String, Box<dyn TransformConfig>)>, Vec<String>> {
+        let mut errors = Vec::new();
+        let mut transforms = Vec::new();
+
+        for (name, transform) in &self.transforms {
+            if transform.get("inputs").is_some() {
+                errors.push(format!(
+                    "Transform {:?} can't set `inputs`, the transforms of a pipeline are chained in order.",
+                    name
+                ));
+                continue;
+            }
+
+            let mut transform = transform.clone();
+            let param = |name: &str| {
+                params
+                    .get(name)
+                    .or_else(|| self.params.get(name))
+                    .cloned()
+                    .ok_or_else(|| format!("Parameter {:?} is not set.", name))
+            };
+            if let Err(error) = substitute(&mut transform, &param) {
+                errors.push(format!("Transform {:?}: {}", name, error));
+                continue;
+            }
+
+            match serde_json::from_value::<Box<dyn TransformConfig>>(transform) {
+                Ok(transform) if transform.transform_type() == "pipeline" => {
+                    errors.push(format!("Transform {:?}: pipelines can't be nested.", name))
+                }
+                Ok(transform) => transforms.push((name.clone(), transform)),
+                Err(error) => errors.push(format!("Transform {:?}: {}", name, error)),
+            }
+        }
+
+        if transforms.is_empty() && errors.is_empty() {
+            errors.push("Pipeline has no transforms.".to_owned());
+        }
+
+        if errors.is_empty() {
+            Ok(transforms)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn substitute(
+    value: &mut Value,
+    params: &dyn Fn(&str) -> Result<String, String>,
+) -> Result<(), String> {
+    lazy_static::lazy_static! {
+        static ref PARAM: Regex = Regex::new(r"\$\{params\.(\w+)\}").unwrap();
+    }
+
+    match value {
+        Value::String(string) => {
+            let mut error = None;
+            let substituted = PARAM.replace_all(string.as_str(), |caps: &Captures<'_>| {
+                params(&caps[1]).unwrap_or_else(|message| {
+                    error.get_or_insert(message);
+                    String::new()
+                })
+            });
+            if let Some(error) = error {
+                return Err(error);
+            }
+            *string = substituted.into_owned();
+        }
+        Value::Array(values) => {
+            for value in values {
+                substitute(value, params)?;
+            }
+        }
+        Value::Object(map) => {
+            for value in map.values_mut() {
+                substitute(value, params)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn substitutes_params() {
+        let mut value = json!({
+            "source": ".service = \"${params.service}\"",
+            "fields": ["${params.env}-${params.service}", 5],
+            "escaped": "${service}",
+        });
+        let params = vec![("service", "nginx"), ("env", "prod")]
+            .into_iter()
+            .map(|(k, v)| (k.to_owned(), v.to_owned()))
+            .collect::<IndexMap<_, _>>();
+
+        substitute(&mut value, &|name: &str| {
+            params.get(name).cloned().ok_or_else(|| name.to_owned())
+        })
+        .unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "source": ".service = \"nginx\"",
+                "fields": ["prod-nginx", 5],
+                "escaped": "${service}",
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unset_params() {
+        let mut value = json!({ "source": "${params.missing}" });
+        let error = substitute(&mut value, &|name: &str| {
+            Err(format!("Parameter {:?} is not set.", name))
+        })
+        .unwrap_err();
+
+        assert_eq!(error, "Parameter \"missing\" is not set.");
+    }
+}
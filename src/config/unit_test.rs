@@ -1,9 +1,9 @@
 use super::{Config, ConfigBuilder, TestDefinition, TestInput, TestInputValue};
-use crate::config::{self, ConfigPath, GlobalOptions, TransformConfig};
+use crate::config::{self, named_output, ConfigPath, TransformConfig, TransformContext};
 use crate::{
     conditions::Condition,
     event::{Event, Value},
-    transforms::Transform,
+    transforms::{Transform, TransformOutputs},
 };
 use indexmap::IndexMap;
 use std::collections::HashMap;
@@ -20,7 +20,8 @@ async fn build_unit_tests(mut builder: ConfigBuilder) -> Result<Vec<UnitTest>, V
     let mut tests = vec![];
     let mut errors = vec![];
 
-    let expansions = super::compiler::expand_macros(&mut builder)?;
+    let (pipelines, mut expansions) = super::compiler::expand_pipelines(&mut builder)?;
+    expansions.extend(super::compiler::expand_macros(&mut builder)?);
 
     // Don't let this escape since it's not validated
     let config = Config {
@@ -28,9 +29,11 @@ async fn build_unit_tests(mut builder: ConfigBuilder) -> Result<Vec<UnitTest>, V
         #[cfg(feature = "api")]
         api: builder.api,
         healthchecks: builder.healthchecks,
+        enrichment_tables: builder.enrichment_tables,
         sources: builder.sources,
         sinks: builder.sinks,
         transforms: builder.transforms,
+        pipelines,
         tests: builder.tests,
         expansions,
     };
@@ -61,13 +64,15 @@ pub struct UnitTest {
     transforms: IndexMap<String, UnitTestTransform>,
     checks: Vec<UnitTestCheck>,
     no_outputs_from: Vec<String>,
-    globals: GlobalOptions,
+    context: TransformContext,
 }
 
 struct UnitTestTransform {
     transform: Transform,
     config: Box<dyn TransformConfig>,
     next: Vec<String>,
+    /// The transforms consuming each of the named outputs of the transform.
+    named_next: IndexMap<String, Vec<String>>,
 }
 
 struct UnitTestCheck {
@@ -79,6 +84,7 @@ fn event_to_string(event: &Event) -> String {
     match event {
         Event::Log(log) => serde_json::to_string(&log).unwrap_or_else(|_| "{}".into()),
         Event::Metric(metric) => serde_json::to_string(&metric).unwrap_or_else(|_| "{}".into()),
+        Event::Trace(trace) => serde_json::to_string(&trace).unwrap_or_else(|_| "{}".into()),
     }
 }
 
@@ -106,17 +112,31 @@ fn walk(
     mut inputs: Vec<Event>,
     transforms: &mut IndexMap<String, UnitTestTransform>,
     aggregated_results: &mut HashMap<String, (Vec<Event>, Vec<Event>)>,
-    globals: &GlobalOptions,
+    context: &TransformContext,
 ) {
     let mut results = Vec::new();
     let mut targets = Vec::new();
+    let mut named_results = Vec::new();
 
     // Use `remove` to take ownership.
     if let Some((key, mut target)) = transforms.remove_entry(node) {
         match target.transform {
             Transform::Function(ref mut t) => {
-                for input in inputs.clone() {
-                    t.transform(&mut results, input)
+                let named_outputs = target.config.named_outputs();
+                if named_outputs.is_empty() {
+                    for input in inputs.clone() {
+                        t.transform(&mut results, input)
+                    }
+                } else {
+                    let mut outputs = TransformOutputs::new(named_outputs);
+                    for input in inputs.clone() {
+                        t.transform_outputs(&mut outputs, input)
+                    }
+                    results.extend(outputs.drain());
+                    for (output, next) in target.named_next.iter() {
+                        let events = outputs.drain_named(output).collect::<Vec<_>>();
+                        named_results.push((named_output(&key, output), events, next.clone()));
+                    }
                 }
                 targets = target.next.clone();
                 transforms.insert(key, target);
@@ -132,10 +152,11 @@ fn walk(
                 // TODO: This is a hack.
                 // Our tasktransforms must consume the transform to attach it to an input stream, so we rebuild it between input streams.
                 transforms.insert(key, UnitTestTransform {
-                    transform:  futures::executor::block_on(target.config.clone().build(globals))
+                    transform:  futures::executor::block_on(target.config.clone().build(context))
                         .expect("Failed to build a known valid transform config. Things may have changed during runtime."),
                     config: target.config,
-                    next: target.next
+                    next: target.next,
+                    named_next: target.named_next,
                 });
             }
         }
@@ -147,10 +168,34 @@ fn walk(
             results.clone(),
             transforms,
             aggregated_results,
-            globals,
+            context,
         );
     }
 
+    // Named outputs are checked like transforms, with the inputs of the
+    // transform they belong to.
+    for (output, events, next) in named_results {
+        for child in next {
+            walk(
+                &child,
+                events.clone(),
+                transforms,
+                aggregated_results,
+                context,
+            );
+        }
+        aggregate(&output, inputs.clone(), events, aggregated_results);
+    }
+
+    aggregate(node, inputs, results, aggregated_results);
+}
+
+fn aggregate(
+    node: &str,
+    mut inputs: Vec<Event>,
+    mut results: Vec<Event>,
+    aggregated_results: &mut HashMap<String, (Vec<Event>, Vec<Event>)>,
+) {
     if let Some((mut e_inputs, mut e_results)) = aggregated_results.remove(node) {
         inputs.append(&mut e_inputs);
         results.append(&mut e_results);
@@ -181,7 +226,7 @@ impl UnitTest {
                 inputs,
                 &mut self.transforms,
                 &mut results,
-                &self.globals,
+                &self.context,
             );
         }
 
@@ -401,12 +446,20 @@ async fn build_unit_test(
     };
 
     // Maps transform names with their output targets (transforms that use it as
-    // an input).
+    // an input). The named outputs of transforms are nodes of their own,
+    // targeted by the transform they belong to.
     let mut transform_outputs: IndexMap<String, IndexMap<String, ()>> = config
         .transforms
         .iter()
         .map(|(k, _)| (k.clone(), IndexMap::new()))
         .collect();
+    for (k, t) in config.transforms.iter() {
+        for output in t.inner.named_outputs() {
+            let output = named_output(k, &output);
+            transform_outputs[k].insert(output.clone(), ());
+            transform_outputs.insert(output, IndexMap::new());
+        }
+    }
 
     config.transforms.iter().for_each(|(k, t)| {
         t.inputs.iter().for_each(|i| {
@@ -418,7 +471,7 @@ async fn build_unit_test(
 
     for (i, (input_target, _)) in inputs.iter().enumerate() {
         for target in input_target {
-            if !transform_outputs.contains_key(target) {
+            if !config.transforms.contains_key(target) {
                 errors.push(format!(
                     "inputs[{}]: unable to locate target transform '{}'",
                     i, target
@@ -430,11 +483,23 @@ async fn build_unit_test(
         return Err(errors);
     }
 
+    // The outputs of a pipeline are read from the end of its chain.
+    let extract_from = definition
+        .outputs
+        .iter()
+        .map(|o| config.get_output(&o.extract_from))
+        .collect::<Vec<_>>();
+    let no_outputs_from = definition
+        .no_outputs_from
+        .iter()
+        .map(|o| config.get_output(o))
+        .collect::<Vec<_>>();
+
     let mut leaves: IndexMap<String, ()> = IndexMap::new();
-    definition.outputs.iter().for_each(|o| {
-        leaves.insert(o.extract_from.clone(), ());
+    extract_from.iter().for_each(|o| {
+        leaves.insert(o.clone(), ());
     });
-    definition.no_outputs_from.iter().for_each(|o| {
+    no_outputs_from.iter().for_each(|o| {
         leaves.insert(o.clone(), ());
     });
 
@@ -451,18 +516,59 @@ async fn build_unit_test(
         &mut transform_outputs,
     );
 
+    // Load enrichment tables for the transforms to search.
+    let context = TransformContext::new_with_globals(config.global.clone());
+    let mut enrichment_tables = enrichment::tables::TableMap::new();
+    for (name, table) in &config.enrichment_tables {
+        match table.inner.build(&config.global).await {
+            Ok(table) => {
+                enrichment_tables.insert(name.clone(), table);
+            }
+            Err(err) => {
+                errors.push(format!(
+                    "failed to load enrichment table '{}': {:#}",
+                    name,
+                    anyhow::anyhow!(err)
+                ));
+            }
+        }
+    }
+    context.enrichment_tables.load(enrichment_tables);
+
     // Build reduced transforms.
     let mut transforms: IndexMap<String, UnitTestTransform> = IndexMap::new();
+    let mut linked_outputs = Vec::new();
     for (name, transform_config) in &config.transforms {
         if let Some(outputs) = transform_outputs.remove(name) {
-            match transform_config.inner.build(&config.global).await {
+            let mut next = Vec::new();
+            let mut named_next = IndexMap::new();
+            let named_outputs = transform_config.inner.named_outputs();
+            for (child, _) in outputs {
+                match named_outputs
+                    .iter()
+                    .find(|output| named_output(name, output) == child)
+                {
+                    Some(output) => {
+                        let consumers: Vec<String> = transform_outputs
+                            .remove(&child)
+                            .map(|consumers| consumers.into_iter().map(|(k, _)| k).collect())
+                            .unwrap_or_default();
+                        named_next.insert(output.clone(), consumers);
+                        linked_outputs.push(child);
+                    }
+                    None => next.push(child),
+                }
+            }
+
+            match transform_config.inner.build(&context).await {
                 Ok(transform) => {
                     transforms.insert(
                         name.clone(),
                         UnitTestTransform {
                             transform,
                             config: transform_config.inner.clone(),
-                            next: outputs.into_iter().map(|(k, _)| k).collect(),
+                            next,
+                            named_next,
                         },
                     );
                 }
@@ -481,18 +587,20 @@ async fn build_unit_test(
         return Err(errors);
     }
 
-    definition.outputs.iter().for_each(|o| {
-        if !transforms.contains_key(&o.extract_from) {
+    context.enrichment_tables.finish_load();
+
+    extract_from.iter().for_each(|o| {
+        if !transforms.contains_key(o) && !linked_outputs.contains(o) {
             let targets = inputs.iter().map(|(i, _)| i).flatten().collect::<Vec<_>>();
             if targets.len() == 1 {
                 errors.push(format!(
                     "unable to complete topology between target transform '{}' and output target '{}'",
-                    targets.first().unwrap(), o.extract_from
+                    targets.first().unwrap(), o
                 ));
             } else {
                 errors.push(format!(
                     "unable to complete topology between target transforms {:?} and output target '{}'",
-                    targets, o.extract_from
+                    targets, o
                 ));
             }
         }
@@ -502,7 +610,8 @@ async fn build_unit_test(
     let checks = definition
         .outputs
         .iter()
-        .map(|o| {
+        .zip(extract_from)
+        .map(|(o, extract_from)| {
             let mut conditions: Vec<Box<dyn Condition>> = Vec::new();
             for (index, cond_conf) in o
                 .conditions
@@ -521,7 +630,7 @@ async fn build_unit_test(
             }
 
             UnitTestCheck {
-                extract_from: o.extract_from.clone(),
+                extract_from,
                 conditions,
             }
         })
@@ -541,8 +650,8 @@ async fn build_unit_test(
             inputs,
             transforms,
             checks,
-            no_outputs_from: definition.no_outputs_from.clone(),
-            globals: config.global.clone(),
+            no_outputs_from,
+            context,
         })
     }
 }
@@ -1016,6 +1125,51 @@ mod tests {
         assert_eq!(tests[0].run().1, Vec::<String>::new());
     }
 
+    #[tokio::test]
+    async fn test_pipeline() {
+        let config: ConfigBuilder = toml::from_str(indoc! {r#"
+            [pipelines.tagging.transforms.first]
+              type = "add_fields"
+              [pipelines.tagging.transforms.first.fields]
+                first_field = "first"
+
+            [pipelines.tagging.transforms.second]
+              type = "add_fields"
+              [pipelines.tagging.transforms.second.fields]
+                second_field = "second"
+
+            [transforms.foo]
+              inputs = ["ignored"]
+              type = "pipeline"
+              pipeline = "tagging"
+
+            [[tests]]
+              name = "pipeline test"
+
+              [tests.input]
+                insert_at = "foo"
+                value = "nah this doesnt matter"
+
+              [[tests.outputs]]
+                extract_from = "foo"
+                [[tests.outputs.conditions]]
+                  type = "check_fields"
+                  "first_field.equals" = "first"
+                  "second_field.equals" = "second"
+
+              [[tests.outputs]]
+                extract_from = "foo.first"
+                [[tests.outputs.conditions]]
+                  type = "check_fields"
+                  "first_field.equals" = "first"
+                  "second_field.exists" = false
+        "#})
+        .unwrap();
+
+        let mut tests = build_unit_tests(config).await.unwrap();
+        assert_eq!(tests[0].run().1, Vec::<String>::new());
+    }
+
     #[tokio::test]
     async fn test_fail_no_outputs() {
         let config: ConfigBuilder = toml::from_str(indoc! {r#"
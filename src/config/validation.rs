@@ -1,4 +1,6 @@
-use super::{builder::ConfigBuilder, DataType, Resource};
+use super::{
+    builder::ConfigBuilder, dropped_output, named_output, DataType, Resource, DROPPED_OUTPUT,
+};
 use std::collections::HashMap;
 
 /// Check that provide + topology config aren't present in the same builder, which is an error.
@@ -33,11 +35,17 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         iter.map(move |x| (tag, x))
     }
 
-    // Check for non-unique names across sources, sinks, and transforms
+    let named_outputs = named_outputs(config)
+        .map(|(output, _, _)| output)
+        .collect::<Vec<_>>();
+
+    // Check for non-unique names across sources, sinks, transforms, and
+    // named outputs
     let mut name_uses = HashMap::<&str, Vec<&'static str>>::new();
     for (ctype, name) in tagged("source", config.sources.keys())
         .chain(tagged("transform", config.transforms.keys()))
         .chain(tagged("sink", config.sinks.keys()))
+        .chain(tagged("output", named_outputs.iter()))
     {
         let uses = name_uses.entry(name).or_default();
         uses.push(ctype);
@@ -70,7 +78,10 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         }
 
         for input in inputs {
-            if !config.sources.contains_key(&input) && !config.transforms.contains_key(&input) {
+            if !config.sources.contains_key(&input)
+                && !config.transforms.contains_key(&input)
+                && !named_outputs.contains(&input)
+            {
                 errors.push(format!(
                     "Input {:?} for {} {:?} doesn't exist.",
                     input, output_type, name
@@ -91,12 +102,20 @@ pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         .sources
         .iter()
         .map(|(name, config)| (name, config.inner.resources()));
+    let transform_resources = config
+        .transforms
+        .iter()
+        .map(|(name, config)| (name, config.inner.resources()));
     let sink_resources = config
         .sinks
         .iter()
         .map(|(name, config)| (name, config.resources(name)));
 
-    let conflicting_components = Resource::conflicts(source_resources.chain(sink_resources));
+    let conflicting_components = Resource::conflicts(
+        source_resources
+            .chain(transform_resources)
+            .chain(sink_resources),
+    );
 
     if conflicting_components.is_empty() {
         Ok(())
@@ -116,21 +135,27 @@ pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
 pub fn warnings(config: &ConfigBuilder) -> Vec<String> {
     let mut warnings = vec![];
 
-    let source_names = config.sources.keys().map(|name| ("source", name.clone()));
-    let transform_names = config
-        .transforms
-        .keys()
-        .map(|name| ("transform", name.clone()));
-    for (input_type, name) in transform_names.chain(source_names) {
-        if !config
+    let is_consumed = |name: &String| {
+        config
             .transforms
             .iter()
-            .any(|(_, transform)| transform.inputs.contains(&name))
-            && !config
+            .any(|(_, transform)| transform.inputs.contains(name))
+            || config
                 .sinks
                 .iter()
-                .any(|(_, sink)| sink.inputs.contains(&name))
-        {
+                .any(|(_, sink)| sink.inputs.contains(name))
+    };
+    let named_outputs = named_outputs(config).collect::<Vec<_>>();
+
+    let source_names = config.sources.keys().map(|name| ("source", name));
+    let transform_names = config.transforms.keys().map(|name| ("transform", name));
+    for (input_type, name) in transform_names.chain(source_names) {
+        // A transform whose named outputs are consumed may well leave its
+        // default output unused, as `route` does.
+        let outputs_consumed = named_outputs
+            .iter()
+            .any(|(output, component, _)| *component == name && is_consumed(output));
+        if !is_consumed(name) && !outputs_consumed {
             warnings.push(format!(
                 "{} {:?} has no consumers",
                 capitalize(input_type),
@@ -139,9 +164,38 @@ pub fn warnings(config: &ConfigBuilder) -> Vec<String> {
         }
     }
 
+    for (output, _, _) in named_outputs.iter() {
+        if !is_consumed(output) {
+            warnings.push(format!("Output {:?} has no consumers", output));
+        }
+    }
+
     warnings
 }
 
+/// The named outputs of all components, along with the name of the
+/// component each belongs to and the name of the output itself. These are the
+/// outputs declared by transforms and the `dropped` outputs of the sinks
+/// configured to reroute dropped events.
+pub(super) fn named_outputs(
+    config: &ConfigBuilder,
+) -> impl Iterator<Item = (String, &String, String)> {
+    let transforms = config.transforms.iter().flat_map(|(name, transform)| {
+        transform
+            .inner
+            .named_outputs()
+            .into_iter()
+            .map(move |output| (named_output(name, &output), name, output))
+    });
+    let sinks = config
+        .sinks
+        .iter()
+        .filter(|(_, sink)| sink.reroute_dropped)
+        .map(|(name, _)| (dropped_output(name), name, DROPPED_OUTPUT.to_owned()));
+
+    transforms.chain(sinks)
+}
+
 pub fn typecheck(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     Graph::from(config).typecheck()
 }
@@ -160,6 +214,11 @@ enum Node {
         ty: DataType,
         inputs: Vec<String>,
     },
+    /// A named output of a component.
+    Output {
+        ty: DataType,
+        component: String,
+    },
 }
 
 #[derive(Default)]
@@ -196,6 +255,16 @@ impl Graph {
             .insert(name.to_string(), Node::Sink { ty, inputs });
     }
 
+    fn add_output(&mut self, name: &str, ty: DataType, component: impl Into<String>) {
+        self.nodes.insert(
+            name.to_string(),
+            Node::Output {
+                ty,
+                component: component.into(),
+            },
+        );
+    }
+
     fn paths(&self) -> Result<Vec<Vec<String>>, Vec<String>> {
         let mut errors = Vec::new();
 
@@ -237,10 +306,14 @@ impl Graph {
                     continue;
                 }
                 match (self.nodes[x].clone(), self.nodes[y].clone()) {
+                    // The type of a named output is set by its component.
+                    (_, Node::Output { .. }) => {}
                     (Node::Source { ty: ty1 }, Node::Sink { ty: ty2, .. })
                     | (Node::Source { ty: ty1 }, Node::Transform { in_ty: ty2, .. })
                     | (Node::Transform { out_ty: ty1, .. }, Node::Transform { in_ty: ty2, .. })
-                    | (Node::Transform { out_ty: ty1, .. }, Node::Sink { ty: ty2, .. }) => {
+                    | (Node::Transform { out_ty: ty1, .. }, Node::Sink { ty: ty2, .. })
+                    | (Node::Output { ty: ty1, .. }, Node::Transform { in_ty: ty2, .. })
+                    | (Node::Output { ty: ty1, .. }, Node::Sink { ty: ty2, .. }) => {
                         if ty1 != ty2 && ty1 != DataType::Any && ty2 != DataType::Any {
                             errors.push(format!(
                                 "Data type mismatch between {} ({:?}) and {} ({:?})",
@@ -285,6 +358,17 @@ impl From<&ConfigBuilder> for Graph {
             graph.add_sink(name, config.inner.input_type(), config.inputs.clone());
         }
 
+        // Transforms write their own events to their named outputs, while
+        // `dropped` outputs carry the events the component received.
+        for (output, component, name) in named_outputs(config) {
+            let ty = match config.transforms.get(component) {
+                Some(transform) if name != DROPPED_OUTPUT => transform.inner.output_type(),
+                Some(transform) => transform.inner.input_type(),
+                None => config.sinks[component].inner.input_type(),
+            };
+            graph.add_output(&output, ty, component);
+        }
+
         graph
     }
 }
@@ -311,6 +395,7 @@ fn paths_rec(
             path.reverse();
             Ok(vec![path])
         }
+        Some(Node::Output { component, .. }) => paths_rec(nodes, component, path),
         Some(Node::Transform { inputs, .. }) | Some(Node::Sink { inputs, .. }) => {
             let mut paths = Vec::new();
             for input in inputs {
@@ -337,7 +422,89 @@ fn capitalize(s: &str) -> String {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{
+        config::{
+            SinkConfig, SinkContext, SourceConfig, SourceContext, TransformConfig, TransformContext,
+        },
+        sinks::{Healthcheck, VectorSink},
+        sources::Source,
+        transforms::Transform,
+    };
+    use async_trait::async_trait;
     use pretty_assertions::assert_eq;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct MockLogSourceConfig;
+
+    /// Turns logs into metrics, rerouting the logs it fails to convert.
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct MockLogToMetricConfig;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct MockSinkConfig {
+        metrics: bool,
+    }
+
+    #[async_trait]
+    #[typetag::serde(name = "mock_log_source")]
+    impl SourceConfig for MockLogSourceConfig {
+        async fn build(&self, _cx: SourceContext) -> crate::Result<Source> {
+            unimplemented!()
+        }
+
+        fn source_type(&self) -> &'static str {
+            "mock_log_source"
+        }
+
+        fn output_type(&self) -> DataType {
+            DataType::Log
+        }
+    }
+
+    #[async_trait]
+    #[typetag::serde(name = "mock_log_to_metric")]
+    impl TransformConfig for MockLogToMetricConfig {
+        async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+            unimplemented!()
+        }
+
+        fn transform_type(&self) -> &'static str {
+            "mock_log_to_metric"
+        }
+
+        fn input_type(&self) -> DataType {
+            DataType::Log
+        }
+
+        fn output_type(&self) -> DataType {
+            DataType::Metric
+        }
+
+        fn named_outputs(&self) -> Vec<String> {
+            vec![DROPPED_OUTPUT.to_owned()]
+        }
+    }
+
+    #[async_trait]
+    #[typetag::serde(name = "mock_typed_sink")]
+    impl SinkConfig for MockSinkConfig {
+        async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+            unimplemented!()
+        }
+
+        fn sink_type(&self) -> &'static str {
+            "mock_typed_sink"
+        }
+
+        fn input_type(&self) -> DataType {
+            if self.metrics {
+                DataType::Metric
+            } else {
+                DataType::Log
+            }
+        }
+    }
 
     #[test]
     fn paths_detects_cycles() {
@@ -401,6 +568,71 @@ mod test {
         graph.paths().unwrap();
     }
 
+    #[test]
+    fn paths_detects_cycles_through_named_outputs() {
+        let mut graph = Graph::default();
+        graph.add_source("in", DataType::Log);
+        graph.add_transform(
+            "one",
+            DataType::Log,
+            DataType::Log,
+            vec!["in", "out.dropped"],
+        );
+        graph.add_sink("out", DataType::Log, vec!["one"]);
+        graph.add_output("out.dropped", DataType::Log, "out");
+
+        assert_eq!(
+            Err(vec![
+                "Cyclic dependency detected in the chain [ out -> out.dropped -> one -> out ]"
+                    .into()
+            ]),
+            graph.paths()
+        );
+    }
+
+    #[test]
+    fn typechecks_named_outputs() {
+        let mut graph = Graph::default();
+        graph.add_source("in", DataType::Any);
+        graph.add_transform("parse", DataType::Log, DataType::Metric, vec!["in"]);
+        graph.add_output("parse.dropped", DataType::Log, "parse");
+        graph.add_sink("metrics", DataType::Metric, vec!["parse"]);
+        graph.add_sink("failures", DataType::Log, vec!["parse.dropped"]);
+        graph.add_sink("wrong", DataType::Metric, vec!["parse.dropped"]);
+
+        assert_eq!(
+            Err(vec![
+                "Data type mismatch between parse.dropped (Log) and wrong (Metric)".into()
+            ]),
+            graph.typecheck()
+        );
+    }
+
+    #[test]
+    fn typechecks_transform_dropped_outputs_as_inputs() {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("in", MockLogSourceConfig);
+        builder.add_transform("parse", &["in"], MockLogToMetricConfig);
+        builder.add_sink("metrics", &["parse"], MockSinkConfig { metrics: true });
+        builder.add_sink(
+            "failures",
+            &["parse.dropped"],
+            MockSinkConfig { metrics: false },
+        );
+        builder.add_sink(
+            "wrong",
+            &["parse.dropped"],
+            MockSinkConfig { metrics: true },
+        );
+
+        assert_eq!(
+            Err(vec![
+                "Data type mismatch between parse.dropped (Log) and wrong (Metric)".into()
+            ]),
+            typecheck(&builder)
+        );
+    }
+
     #[test]
     fn detects_type_mismatches() {
         let mut graph = Graph::default();
@@ -415,6 +647,21 @@ mod test {
         );
     }
 
+    #[test]
+    fn detects_trace_type_mismatches() {
+        let mut graph = Graph::default();
+        graph.add_source("in", DataType::Trace);
+        graph.add_transform("log_to_log", DataType::Log, DataType::Log, vec!["in"]);
+        graph.add_sink("out", DataType::Any, vec!["log_to_log"]);
+
+        assert_eq!(
+            Err(vec![
+                "Data type mismatch between in (Trace) and log_to_log (Log)".into()
+            ]),
+            graph.typecheck()
+        );
+    }
+
     #[test]
     fn allows_log_or_metric_into_any() {
         let mut graph = Graph::default();
@@ -47,6 +47,41 @@ impl Resolver {
     }
 }
 
+/// A service location, as found in an SRV record.
+#[cfg(feature = "trust-dns-resolver")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SrvRecord {
+    pub target: String,
+    pub port: u16,
+}
+
+/// Looks up SRV records, as the standard library can only resolve addresses.
+///
+/// The resolver caches answers, so it should be built once and reused.
+#[cfg(feature = "trust-dns-resolver")]
+pub struct SrvResolver(trust_dns_resolver::TokioAsyncResolver);
+
+#[cfg(feature = "trust-dns-resolver")]
+impl SrvResolver {
+    /// Builds a resolver from the system's resolver configuration.
+    pub fn from_system_conf() -> Result<Self, DnsError> {
+        trust_dns_resolver::TokioAsyncResolver::tokio_from_system_conf()
+            .map(Self)
+            .context(Resolve)
+    }
+
+    pub async fn lookup_srv(&self, name: &str) -> Result<Vec<SrvRecord>, DnsError> {
+        let lookup = self.0.srv_lookup(name).await.context(Resolve)?;
+        Ok(lookup
+            .iter()
+            .map(|srv| SrvRecord {
+                target: srv.target().to_utf8().trim_end_matches('.').to_owned(),
+                port: srv.port(),
+            })
+            .collect())
+    }
+}
+
 impl Iterator for LookupIp {
     type Item = IpAddr;
 
@@ -75,6 +110,11 @@ pub enum DnsError {
     UnableLookup { source: tokio::io::Error },
     #[snafu(display("Failed to join with resolving future: {}", source))]
     JoinError { source: tokio::task::JoinError },
+    #[cfg(feature = "trust-dns-resolver")]
+    #[snafu(display("Unable to resolve name: {}", source))]
+    Resolve {
+        source: trust_dns_resolver::error::ResolveError,
+    },
 }
 
 #[cfg(test)]
@@ -105,4 +145,64 @@ mod tests {
     async fn resolve_ipv6() {
         assert!(resolve("::1").await);
     }
+
+    #[cfg(feature = "trust-dns-resolver")]
+    #[tokio::test]
+    async fn resolve_srv() {
+        use super::{SrvRecord, SrvResolver};
+        use tokio::net::UdpSocket;
+        use trust_dns_resolver::{
+            config::{NameServerConfigGroup, ResolverConfig, ResolverOpts},
+            proto::{
+                op::{Message, MessageType},
+                rr::{rdata::SRV, Name, RData, Record},
+            },
+            TokioAsyncResolver,
+        };
+
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let address = server.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buffer = vec![0; 4096];
+            let (len, peer) = server.recv_from(&mut buffer).await.unwrap();
+            let mut message = Message::from_vec(&buffer[..len]).unwrap();
+            let name = message.queries()[0].name().clone();
+            message.set_message_type(MessageType::Response);
+            for (target, port) in &[("node-1.example.com.", 9100), ("node-2.example.com.", 9101)] {
+                let srv = SRV::new(0, 0, *port, Name::from_ascii(target).unwrap());
+                message.add_answer(Record::from_rdata(name.clone(), 60, RData::SRV(srv)));
+            }
+            server
+                .send_to(&message.to_vec().unwrap(), peer)
+                .await
+                .unwrap();
+        });
+
+        let name_servers =
+            NameServerConfigGroup::from_ips_clear(&[address.ip()], address.port(), true);
+        let resolver = SrvResolver(
+            TokioAsyncResolver::tokio(
+                ResolverConfig::from_parts(None, vec![], name_servers),
+                ResolverOpts::default(),
+            )
+            .unwrap(),
+        );
+        let records = resolver
+            .lookup_srv("_metrics._tcp.example.com.")
+            .await
+            .unwrap();
+        assert_eq!(
+            records,
+            vec![
+                SrvRecord {
+                    target: "node-1.example.com".into(),
+                    port: 9100
+                },
+                SrvRecord {
+                    target: "node-2.example.com".into(),
+                    port: 9101
+                },
+            ]
+        );
+    }
 }
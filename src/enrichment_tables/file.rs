@@ -0,0 +1,397 @@
+use crate::config::{EnrichmentTableConfig, EnrichmentTableDescription, GlobalOptions};
+use enrichment::{Case, Condition, IndexHandle, Table};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use vrl::Value;
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Encoding {
+    Csv {
+        #[serde(default = "crate::serde::default_true")]
+        include_headers: bool,
+        #[serde(default = "default_delimiter")]
+        delimiter: char,
+    },
+}
+
+impl Default for Encoding {
+    fn default() -> Self {
+        Self::Csv {
+            include_headers: true,
+            delimiter: default_delimiter(),
+        }
+    }
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("The CSV delimiter must be an ASCII character, got {:?}", delimiter))]
+    NonAsciiDelimiter { delimiter: char },
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct FileSource {
+    path: PathBuf,
+    #[serde(default)]
+    encoding: Encoding,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FileConfig {
+    file: FileSource,
+}
+
+inventory::submit! {
+    EnrichmentTableDescription::new::<FileConfig>("file")
+}
+
+impl_generate_config_from_default!(FileConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "file")]
+impl EnrichmentTableConfig for FileConfig {
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Box<dyn Table>> {
+        let Encoding::Csv {
+            include_headers,
+            delimiter,
+        } = self.file.encoding;
+        if !delimiter.is_ascii() {
+            return Err(BuildError::NonAsciiDelimiter { delimiter }.into());
+        }
+
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(include_headers)
+            .delimiter(delimiter as u8)
+            .from_path(&self.file.path)?;
+
+        let data = reader
+            .records()
+            .map(|row| Ok(row?.iter().map(ToString::to_string).collect::<Vec<_>>()))
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let headers = if include_headers {
+            reader
+                .headers()?
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        } else {
+            // Without headers, columns are named by their position.
+            let columns = data.first().map_or(0, Vec::len);
+            (0..columns).map(|column| column.to_string()).collect()
+        };
+
+        trace!(
+            message = "Loaded enrichment file.",
+            path = ?self.file.path,
+            rows = data.len(),
+        );
+
+        Ok(Box::new(File::new(data, headers)))
+    }
+}
+
+/// An enrichment table loaded from a CSV file, with all values kept as
+/// strings.
+#[derive(Debug)]
+pub struct File {
+    data: Vec<Vec<String>>,
+    headers: Vec<String>,
+    indexes: HashMap<IndexHandle, HashMap<Vec<String>, Vec<usize>>>,
+}
+
+impl File {
+    pub fn new(data: Vec<Vec<String>>, headers: Vec<String>) -> Self {
+        Self {
+            data,
+            headers,
+            indexes: HashMap::new(),
+        }
+    }
+
+    fn column(&self, field: &str) -> Option<usize> {
+        self.headers.iter().position(|header| header == field)
+    }
+
+    fn row_matches(&self, case: Case, condition: &[Condition], row: &[String]) -> bool {
+        condition.iter().all(|Condition::Equals { field, value }| {
+            match self.column(field).and_then(|column| row.get(column)) {
+                Some(cell) => normalize(case, cell) == normalize(case, &value_to_string(value)),
+                None => false,
+            }
+        })
+    }
+
+    /// The rows meeting the conditions, looked up through the index if it can
+    /// be used for the conditions, going through every row otherwise.
+    fn matching_rows(
+        &self,
+        case: Case,
+        condition: &[Condition],
+        index: Option<&IndexHandle>,
+    ) -> Vec<&Vec<String>> {
+        let indexed = index.filter(|index| index.case == case).and_then(|index| {
+            let key = index
+                .fields
+                .iter()
+                .map(|field| {
+                    condition
+                        .iter()
+                        .find(|Condition::Equals { field: name, .. }| *name == field.as_str())
+                        .map(|Condition::Equals { value, .. }| {
+                            normalize(case, &value_to_string(value))
+                        })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            let rows = self.indexes.get(index)?;
+            Some(rows.get(&key).map(Vec::as_slice).unwrap_or_default())
+        });
+
+        match indexed {
+            Some(rows) => rows
+                .iter()
+                .map(|&row| &self.data[row])
+                .filter(|row| self.row_matches(case, condition, row))
+                .collect(),
+            None => self
+                .data
+                .iter()
+                .filter(|row| self.row_matches(case, condition, row))
+                .collect(),
+        }
+    }
+
+    fn row_to_object(&self, row: &[String]) -> BTreeMap<String, Value> {
+        self.headers
+            .iter()
+            .zip(row)
+            .map(|(header, cell)| (header.clone(), Value::from(cell.as_str())))
+            .collect()
+    }
+}
+
+impl Table for File {
+    fn find_table_row(
+        &self,
+        case: Case,
+        condition: &[Condition],
+        index: Option<&IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String> {
+        match self.matching_rows(case, condition, index).as_slice() {
+            [row] => Ok(self.row_to_object(row)),
+            [] => Err("no rows found".to_string()),
+            _ => Err("more than one row found".to_string()),
+        }
+    }
+
+    fn find_table_rows(
+        &self,
+        case: Case,
+        condition: &[Condition],
+        index: Option<&IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        Ok(self
+            .matching_rows(case, condition, index)
+            .into_iter()
+            .map(|row| self.row_to_object(row))
+            .collect())
+    }
+
+    fn add_index(&mut self, case: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+        let columns = fields
+            .iter()
+            .map(|field| {
+                self.column(field)
+                    .ok_or_else(|| format!("field \"{}\" is not a column of the table", field))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let handle = IndexHandle {
+            case,
+            fields: fields.iter().map(ToString::to_string).collect(),
+        };
+
+        if !self.indexes.contains_key(&handle) {
+            let mut index = HashMap::<_, Vec<_>>::new();
+            for (position, row) in self.data.iter().enumerate() {
+                let key = columns
+                    .iter()
+                    .map(|&column| normalize(case, row.get(column).map_or("", String::as_str)))
+                    .collect::<Vec<_>>();
+                index.entry(key).or_default().push(position);
+            }
+            self.indexes.insert(handle.clone(), index);
+        }
+
+        Ok(handle)
+    }
+
+    fn indexes(&self) -> Vec<IndexHandle> {
+        self.indexes.keys().cloned().collect()
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        value => value.to_string(),
+    }
+}
+
+fn normalize(case: Case, value: &str) -> String {
+    match case {
+        Case::Sensitive => value.to_string(),
+        Case::Insensitive => value.to_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<FileConfig>();
+    }
+
+    fn table() -> File {
+        File::new(
+            vec![
+                vec!["1".to_string(), "Bob".to_string(), "London".to_string()],
+                vec!["2".to_string(), "bob".to_string(), "Paris".to_string()],
+                vec!["3".to_string(), "Alice".to_string(), "London".to_string()],
+            ],
+            vec!["id".to_string(), "name".to_string(), "city".to_string()],
+        )
+    }
+
+    fn equals(field: &str, value: impl Into<Value>) -> Condition<'_> {
+        Condition::Equals {
+            field,
+            value: value.into(),
+        }
+    }
+
+    #[test]
+    fn finds_row() {
+        let table = table();
+
+        let row = table
+            .find_table_row(Case::Sensitive, &[equals("id", 3)], None)
+            .unwrap();
+        assert_eq!(row.get("name"), Some(&Value::from("Alice")));
+
+        assert_eq!(
+            table.find_table_row(Case::Sensitive, &[equals("city", "London")], None),
+            Err("more than one row found".to_string())
+        );
+        assert_eq!(
+            table.find_table_row(Case::Sensitive, &[equals("city", "Berlin")], None),
+            Err("no rows found".to_string())
+        );
+    }
+
+    #[test]
+    fn finds_rows_case_insensitive() {
+        let table = table();
+
+        let rows = table
+            .find_table_rows(Case::Insensitive, &[equals("name", "BOB")], None)
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+
+        let rows = table
+            .find_table_rows(Case::Sensitive, &[equals("name", "BOB")], None)
+            .unwrap();
+        assert!(rows.is_empty());
+    }
+
+    #[test]
+    fn finds_rows_through_index() {
+        let mut table = table();
+        let index = table
+            .add_index(Case::Insensitive, &["city", "name"])
+            .unwrap();
+        assert_eq!(table.indexes(), vec![index.clone()]);
+
+        let condition = [equals("name", "bob"), equals("city", "paris")];
+        let row = table
+            .find_table_row(Case::Insensitive, &condition, Some(&index))
+            .unwrap();
+        assert_eq!(row.get("id"), Some(&Value::from("2")));
+
+        let rows = table
+            .find_table_rows(Case::Insensitive, &[equals("city", "london")], Some(&index))
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+
+        assert_eq!(
+            table.add_index(Case::Sensitive, &["country"]),
+            Err("field \"country\" is not a column of the table".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn loads_csv_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.csv");
+        let mut file = std::fs::File::create(&path).unwrap();
+        writeln!(file, "1;Bob").unwrap();
+        writeln!(file, "2;Alice").unwrap();
+
+        let config: FileConfig = toml::from_str(&format!(
+            r#"
+            file.path = {:?}
+            file.encoding.type = "csv"
+            file.encoding.include_headers = false
+            file.encoding.delimiter = ";"
+            "#,
+            path
+        ))
+        .unwrap();
+        let table = config.build(&GlobalOptions::default()).await.unwrap();
+
+        let row = table
+            .find_table_row(Case::Sensitive, &[equals("1", "Alice")], None)
+            .unwrap();
+        assert_eq!(row.get("0"), Some(&Value::from("2")));
+    }
+
+    #[tokio::test]
+    async fn rejects_non_ascii_delimiter() {
+        let config: FileConfig = toml::from_str(
+            r#"
+            file.path = "data.csv"
+            file.encoding.type = "csv"
+            file.encoding.delimiter = "§"
+            "#,
+        )
+        .unwrap();
+        let error = config.build(&GlobalOptions::default()).await.unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The CSV delimiter must be an ASCII character, got '§'"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_file_fields() {
+        let config = toml::from_str::<FileConfig>(
+            r#"
+            file.path = "data.csv"
+            file.delimiter = ";"
+            "#,
+        );
+        assert!(config.is_err());
+    }
+}
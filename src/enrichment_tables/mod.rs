@@ -0,0 +1,4 @@
+pub use enrichment::{Case, Condition, IndexHandle, Table};
+
+#[cfg(feature = "enrichment-tables-file")]
+pub mod file;
@@ -85,6 +85,17 @@ pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
         dot += &format!("  \"{}\" [shape=trapezium]\n", name);
     }
 
+    // Group the transforms expanded from each pipeline, so it's clear where
+    // they came from.
+    for (name, pipeline) in &config.pipelines {
+        dot += &format!("  subgraph \"cluster_{}\" {{\n", name);
+        dot += &format!("    label=\"{} (pipeline {})\"\n", name, pipeline.pipeline);
+        for transform in &pipeline.transforms {
+            dot += &format!("    \"{}\"\n", transform);
+        }
+        dot += "  }\n";
+    }
+
     for (name, transform) in &config.transforms {
         dot += &format!("  \"{}\" [shape=diamond]\n", name);
 
@@ -0,0 +1,24 @@
+use super::InternalEvent;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct DatadogMetricsAbsoluteSketchRejected<'a> {
+    pub name: &'a str,
+}
+
+impl<'a> InternalEvent for DatadogMetricsAbsoluteSketchRejected<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Absolute sketches can't be sent to Datadog; rejecting event.",
+            name = %self.name,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "processing_errors_total", 1,
+            "error_type" => "invalid_metric",
+        );
+    }
+}
@@ -15,3 +15,59 @@ impl InternalEvent for DedupeEventDiscarded {
         counter!("events_discarded_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub(crate) struct DedupeCacheLoaded<'a> {
+    pub path: &'a std::path::Path,
+    pub count: usize,
+}
+
+impl<'a> InternalEvent for DedupeCacheLoaded<'a> {
+    fn emit_logs(&self) {
+        info!(
+            message = "Loaded persisted dedupe cache.",
+            path = ?self.path,
+            count = %self.count,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct DedupeCacheLoadFailed<'a> {
+    pub path: &'a std::path::Path,
+    pub error: std::io::Error,
+}
+
+impl<'a> InternalEvent for DedupeCacheLoadFailed<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed to load persisted dedupe cache; starting with an empty cache.",
+            path = ?self.path,
+            error = %self.error,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "cache_load_failed");
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct DedupeCachePersistFailed<'a> {
+    pub path: &'a std::path::Path,
+    pub error: std::io::Error,
+}
+
+impl<'a> InternalEvent for DedupeCachePersistFailed<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to persist dedupe cache.",
+            path = ?self.path,
+            error = %self.error,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "cache_persist_failed");
+    }
+}
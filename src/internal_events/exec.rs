@@ -76,6 +76,31 @@ impl InternalEvent for ExecTimeout<'_> {
     }
 }
 
+#[derive(Debug)]
+pub struct ExecParseError<'a> {
+    pub command: &'a str,
+    pub error: crate::Error,
+}
+
+impl InternalEvent for ExecParseError<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to parse command output.",
+            command = %self.command,
+            error = %self.error,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "processing_errors_total", 1,
+            "command" => self.command.to_owned(),
+            "error_type" => "parse_failed",
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct ExecCommandExecuted<'a> {
     pub command: &'a str,
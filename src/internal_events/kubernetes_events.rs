@@ -0,0 +1,15 @@
+use super::InternalEvent;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct KubernetesEventsEventReceived;
+
+impl InternalEvent for KubernetesEventsEventReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received one event.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_in_total", 1);
+    }
+}
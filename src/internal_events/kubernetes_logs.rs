@@ -87,3 +87,21 @@ impl InternalEvent for KubernetesLogsDockerFormatParseFailed<'_> {
         counter!("k8s_docker_format_parse_failures_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct KubernetesLogsWatchForbidden {
+    pub resource: &'static str,
+}
+
+impl InternalEvent for KubernetesLogsWatchForbidden {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Not allowed to watch Kubernetes resources, events won't be annotated with their metadata.",
+            resource = %self.resource,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("k8s_watch_forbidden_total", 1, "resource" => self.resource);
+    }
+}
@@ -26,6 +26,7 @@ mod aws_sqs;
 #[cfg(feature = "sinks-azure_blob")]
 pub(crate) mod azure_blob;
 mod blackhole;
+mod codecs;
 #[cfg(feature = "transforms-coercer")]
 mod coercer;
 #[cfg(feature = "transforms-concat")]
@@ -37,6 +38,8 @@ mod console;
 mod datadog_events;
 #[cfg(feature = "sinks-datadog")]
 mod datadog_logs;
+#[cfg(feature = "sinks-datadog")]
+mod datadog_metrics;
 #[cfg(feature = "transforms-dedupe")]
 mod dedupe;
 #[cfg(feature = "sources-dnstap")]
@@ -72,6 +75,8 @@ mod json_parser;
 mod kafka;
 #[cfg(feature = "transforms-key_value_parser")]
 mod key_value_parser;
+#[cfg(feature = "sources-kubernetes-events")]
+mod kubernetes_events;
 #[cfg(feature = "sources-kubernetes-logs")]
 mod kubernetes_logs;
 #[cfg(feature = "transforms-log_to_metric")]
@@ -90,6 +95,8 @@ mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
 mod open;
+#[cfg(any(feature = "sources-opentelemetry", feature = "sinks-opentelemetry"))]
+mod opentelemetry;
 #[cfg(feature = "sources-postgresql_metrics")]
 mod postgresql_metrics;
 mod process;
@@ -126,6 +133,8 @@ mod syslog;
 mod tag_cardinality_limit;
 mod tcp;
 mod template;
+#[cfg(feature = "transforms-throttle")]
+mod throttle;
 #[cfg(feature = "transforms-tokenizer")]
 mod tokenizer;
 mod topology;
@@ -159,6 +168,7 @@ pub use self::aws_kinesis_streams::*;
 #[cfg(feature = "sinks-aws_sqs")]
 pub use self::aws_sqs::*;
 pub use self::blackhole::*;
+pub use self::codecs::*;
 #[cfg(feature = "transforms-coercer")]
 pub(crate) use self::coercer::*;
 #[cfg(feature = "transforms-concat")]
@@ -170,6 +180,8 @@ pub use self::console::*;
 pub use self::datadog_events::*;
 #[cfg(feature = "sinks-datadog")]
 pub use self::datadog_logs::*;
+#[cfg(feature = "sinks-datadog")]
+pub use self::datadog_metrics::*;
 #[cfg(feature = "transforms-dedupe")]
 pub(crate) use self::dedupe::*;
 #[cfg(feature = "sources-dnstap")]
@@ -211,6 +223,8 @@ pub(crate) use self::json_parser::*;
 pub use self::kafka::*;
 #[cfg(feature = "transforms-key_value_parser")]
 pub(crate) use self::key_value_parser::*;
+#[cfg(feature = "sources-kubernetes-events")]
+pub use self::kubernetes_events::*;
 #[cfg(feature = "sources-kubernetes-logs")]
 pub use self::kubernetes_logs::*;
 #[cfg(feature = "transforms-log_to_metric")]
@@ -227,6 +241,8 @@ pub use self::nats::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
 pub use self::open::*;
+#[cfg(any(feature = "sources-opentelemetry", feature = "sinks-opentelemetry"))]
+pub(crate) use self::opentelemetry::*;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub(crate) use self::postgresql_metrics::*;
 pub use self::process::*;
@@ -263,6 +279,8 @@ pub use self::syslog::*;
 pub(crate) use self::tag_cardinality_limit::*;
 pub use self::tcp::*;
 pub use self::template::*;
+#[cfg(feature = "transforms-throttle")]
+pub(crate) use self::throttle::*;
 #[cfg(feature = "transforms-tokenizer")]
 pub(crate) use self::tokenizer::*;
 pub use self::topology::*;
@@ -0,0 +1,88 @@
+use super::InternalEvent;
+#[cfg(feature = "sinks-opentelemetry")]
+use crate::event::metric::Metric;
+use metrics::counter;
+#[cfg(feature = "sources-opentelemetry")]
+use prost::DecodeError;
+
+#[cfg(feature = "sources-opentelemetry")]
+#[derive(Debug)]
+pub struct OpentelemetryEventsReceived {
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+#[cfg(feature = "sources-opentelemetry")]
+impl InternalEvent for OpentelemetryEventsReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received events.", count = %self.count);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_in_total", self.count as u64);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[cfg(feature = "sources-opentelemetry")]
+#[derive(Debug)]
+pub struct OpentelemetryProtoDecodeError {
+    pub error: DecodeError,
+}
+
+#[cfg(feature = "sources-opentelemetry")]
+impl InternalEvent for OpentelemetryProtoDecodeError {
+    fn emit_logs(&self) {
+        error!(message = "Failed to decode OTLP request.", error = ?self.error, internal_log_rate_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("protobuf_decode_errors_total", 1);
+    }
+}
+
+#[cfg(feature = "sinks-opentelemetry")]
+#[derive(Debug)]
+pub struct OpentelemetryInvalidMetricReceived<'a> {
+    pub metric: &'a Metric,
+}
+
+#[cfg(feature = "sinks-opentelemetry")]
+impl<'a> InternalEvent for OpentelemetryInvalidMetricReceived<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Metric type is not supported by OTLP; rejecting event.",
+            value = ?self.metric.value(),
+            kind = ?self.metric.kind(),
+            internal_log_rate_secs = 30,
+        )
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "processing_errors_total", 1,
+            "error_type" => "invalid_metric",
+        );
+    }
+}
+
+#[cfg(feature = "sinks-opentelemetry")]
+#[derive(Debug)]
+pub struct OpentelemetryTraceRejected;
+
+#[cfg(feature = "sinks-opentelemetry")]
+impl InternalEvent for OpentelemetryTraceRejected {
+    fn emit_logs(&self) {
+        error!(
+            message = "Traces are not supported by the OTLP sink; rejecting event.",
+            internal_log_rate_secs = 30,
+        )
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "processing_errors_total", 1,
+            "error_type" => "unsupported_event",
+        );
+    }
+}
@@ -104,6 +104,25 @@ impl InternalEvent for PrometheusHttpError {
     }
 }
 
+#[derive(Debug)]
+pub struct PrometheusServiceDiscoveryError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for PrometheusServiceDiscoveryError {
+    fn emit_logs(&self) {
+        error!(
+            message = "Service discovery failed, keeping the previous targets.",
+            error = ?self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("service_discovery_errors_total", 1);
+    }
+}
+
 #[derive(Debug)]
 pub struct PrometheusRemoteWriteParseError {
     pub error: prost::DecodeError,
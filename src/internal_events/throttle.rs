@@ -0,0 +1,21 @@
+use super::InternalEvent;
+use metrics::counter;
+
+#[derive(Debug)]
+pub(crate) struct ThrottleEventDiscarded<'a> {
+    pub key: Option<&'a str>,
+}
+
+impl<'a> InternalEvent for ThrottleEventDiscarded<'a> {
+    fn emit_logs(&self) {
+        debug!(
+            message = "Rate limit exceeded; discarding event.",
+            key = ?self.key,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_discarded_total", 1);
+    }
+}
@@ -16,6 +16,7 @@ where
 {
     inner: WriteHandle<String, Value<T>>,
     debounced_flush: Option<Debounce>,
+    key: KeyFn<T>,
 }
 
 impl<T> Writer<T>
@@ -24,9 +25,20 @@ where
 {
     /// Take a [`WriteHandle`], initialize it and return it wrapped with
     /// [`Writer`].
+    /// Objects are keyed by their `uid`, see [`HashValue::uid`].
     pub fn new(
+        inner: WriteHandle<String, Value<T>>,
+        flush_debounce_timeout: Option<Duration>,
+    ) -> Self {
+        Self::new_with_key(inner, flush_debounce_timeout, HashValue::uid)
+    }
+
+    /// Same as [`Writer::new`], but objects are keyed with `key`, for
+    /// instance [`name`] for the resources that are looked up by name.
+    pub fn new_with_key(
         mut inner: WriteHandle<String, Value<T>>,
         flush_debounce_timeout: Option<Duration>,
+        key: KeyFn<T>,
     ) -> Self {
         // Prepare inner.
         inner.purge();
@@ -38,6 +50,7 @@ where
         Self {
             inner,
             debounced_flush,
+            key,
         }
     }
 
@@ -65,22 +78,24 @@ where
     type Item = T;
 
     async fn add(&mut self, item: Self::Item) {
-        if let Some((key, value)) = kv(item) {
+        if let Some((key, value)) = kv(item, self.key) {
             self.inner.insert(key, value);
             self.debounced_flush();
         }
     }
 
     async fn update(&mut self, item: Self::Item) {
-        if let Some((key, value)) = kv(item) {
+        if let Some((key, value)) = kv(item, self.key) {
             self.inner.update(key, value);
             self.debounced_flush();
         }
     }
 
     async fn delete(&mut self, item: Self::Item) {
-        if let Some((key, _value)) = kv(item) {
-            self.inner.empty(key);
+        if let Some((key, value)) = kv(item, self.key) {
+            // Values compare by `uid`, so an object that was recreated under
+            // the same key before a delayed delete fires is left in place.
+            self.inner.remove_value(key, value);
             self.debounced_flush();
         }
     }
@@ -118,10 +133,18 @@ where
 /// An alias to the value used at [`evmap`].
 pub type Value<T> = Box<HashValue<T>>;
 
+/// A function that extracts the [`evmap`] key from an object.
+pub type KeyFn<T> = fn(&HashValue<T>) -> Option<&str>;
+
+/// Key objects by their `name`.
+pub fn name<T: Metadata<Ty = ObjectMeta>>(value: &HashValue<T>) -> Option<&str> {
+    value.metadata().name.as_deref()
+}
+
 /// Build a key value pair for using in [`evmap`].
-fn kv<T: Metadata<Ty = ObjectMeta>>(object: T) -> Option<(String, Value<T>)> {
+fn kv<T: Metadata<Ty = ObjectMeta>>(object: T, key: KeyFn<T>) -> Option<(String, Value<T>)> {
     let value = Box::new(HashValue::new(object));
-    let key = value.uid()?.to_owned();
+    let key = key(&value)?.to_owned();
     Some((key, value))
 }
 
@@ -144,7 +167,7 @@ mod tests {
     #[test]
     fn test_kv() {
         let pod = make_pod("uid");
-        let (key, val) = kv(pod.clone()).unwrap();
+        let (key, val) = kv(pod.clone(), HashValue::uid).unwrap();
         assert_eq!(key, "uid");
         assert_eq!(val, Box::new(HashValue::new(pod)));
     }
@@ -164,11 +187,57 @@ mod tests {
             },
             ..Pod::default()
         };
-        let (key, val) = kv(pod.clone()).unwrap();
+        let (key, val) = kv(pod.clone(), HashValue::uid).unwrap();
         assert_eq!(key, "config-hashsum");
         assert_eq!(val, Box::new(HashValue::new(pod)));
     }
 
+    #[test]
+    fn test_kv_by_name() {
+        let pod = Pod {
+            metadata: ObjectMeta {
+                name: Some("name".to_owned()),
+                uid: Some("uid".to_owned()),
+                ..ObjectMeta::default()
+            },
+            ..Pod::default()
+        };
+        let (key, val) = kv(pod.clone(), name).unwrap();
+        assert_eq!(key, "name");
+        assert_eq!(val, Box::new(HashValue::new(pod)));
+
+        assert!(kv(make_pod("uid"), name).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_by_name_checks_uid() {
+        let make_pod = |uid: &str| Pod {
+            metadata: ObjectMeta {
+                name: Some("name".to_owned()),
+                uid: Some(uid.to_owned()),
+                ..ObjectMeta::default()
+            },
+            ..Pod::default()
+        };
+
+        let (state_reader, state_writer) = evmap::new();
+        let mut state_writer = Writer::new_with_key(state_writer, None, name);
+
+        state_writer.add(make_pod("uid0")).await;
+        state_writer.add(make_pod("uid1")).await;
+        state_writer.delete(make_pod("uid0")).await;
+
+        let guard = state_reader.get("name").unwrap();
+        assert_eq!(guard.len(), 1);
+        assert_eq!(guard.get_one().unwrap().uid(), Some("uid1"));
+        drop(guard);
+
+        state_writer.delete(make_pod("uid1")).await;
+        assert!(state_reader
+            .get("name")
+            .map_or(true, |values| values.is_empty()));
+    }
+
     #[tokio::test]
     async fn test_without_debounce() {
         let (state_reader, state_writer) = evmap::new();
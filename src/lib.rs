@@ -21,6 +21,7 @@ extern crate vrl_cli;
 #[macro_use]
 pub mod config;
 pub mod cli;
+pub mod codecs;
 pub mod conditions;
 pub mod dns;
 #[cfg(feature = "docker")]
@@ -37,6 +38,8 @@ pub mod app;
 pub mod async_read;
 pub mod buffers;
 pub mod encoding_transcode;
+#[cfg(feature = "enrichment-tables")]
+pub mod enrichment_tables;
 pub mod graph;
 pub mod heartbeat;
 pub mod http;
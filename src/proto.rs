@@ -3,3 +3,6 @@ use crate::event::proto as event;
 
 #[cfg(any(feature = "sources-vector", feature = "sinks-vector"))]
 pub(crate) mod vector;
+
+#[cfg(any(feature = "sources-opentelemetry", feature = "sinks-opentelemetry"))]
+pub(crate) mod opentelemetry;
@@ -0,0 +1,42 @@
+//! The OpenTelemetry protocol (OTLP) definitions. The generated code refers to
+//! the other packages by their relative paths, so the module tree mirrors the
+//! `opentelemetry.proto` package hierarchy.
+#![allow(clippy::clone_on_ref_ptr)]
+
+pub mod common {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.common.v1");
+    }
+}
+
+pub mod resource {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.resource.v1");
+    }
+}
+
+pub mod logs {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.logs.v1");
+    }
+}
+
+pub mod metrics {
+    pub mod v1 {
+        tonic::include_proto!("opentelemetry.proto.metrics.v1");
+    }
+}
+
+pub mod collector {
+    pub mod logs {
+        pub mod v1 {
+            tonic::include_proto!("opentelemetry.proto.collector.logs.v1");
+        }
+    }
+
+    pub mod metrics {
+        pub mod v1 {
+            tonic::include_proto!("opentelemetry.proto.collector.metrics.v1");
+        }
+    }
+}
@@ -166,7 +166,8 @@ impl CloudwatchLogsSinkConfig {
         let client = rusoto::client()?;
         let creds = self.auth.build(&region, self.assume_role.clone())?;
 
-        let client = rusoto_core::Client::new_with_encoding(creds, client, self.compression.into());
+        let client =
+            rusoto_core::Client::new_with_encoding(creds, client, self.compression.try_into()?);
         Ok(CloudWatchLogsClient::new_with_client(client, region))
     }
 }
@@ -469,13 +470,17 @@ fn partition_encode(
     let key = CloudwatchKey { group, stream };
 
     encoding.apply_rules(&mut event);
+    let metadata = event.metadata().clone();
     let event = encode_log(event.into_log(), encoding)
         .map_err(
             |error| error!(message = "Could not encode event.", %error, internal_log_rate_secs = 5),
         )
         .ok()?;
 
-    Some(EncodedEvent::new(PartitionInnerBuffer::new(event, key)))
+    Some(EncodedEvent {
+        item: PartitionInnerBuffer::new(event, key),
+        metadata: Some(metadata),
+    })
 }
 
 #[derive(Debug, Snafu)]
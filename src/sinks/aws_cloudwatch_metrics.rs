@@ -122,7 +122,8 @@ impl CloudWatchMetricsSinkConfig {
         let client = rusoto::client()?;
         let creds = self.auth.build(&region, self.assume_role.clone())?;
 
-        let client = rusoto_core::Client::new_with_encoding(creds, client, self.compression.into());
+        let client =
+            rusoto_core::Client::new_with_encoding(creds, client, self.compression.try_into()?);
         Ok(CloudWatchClient::new_with_client(client, region))
     }
 }
@@ -155,9 +156,11 @@ impl CloudWatchMetricsSvc {
                         .take_namespace()
                         .take()
                         .unwrap_or_else(|| default_namespace.clone());
-                    Ok(EncodedEvent::new(PartitionInnerBuffer::new(
-                        metric, namespace,
-                    )))
+                    let metadata = std::mem::take(metric.metadata_mut());
+                    Ok(EncodedEvent {
+                        item: PartitionInnerBuffer::new(metric, namespace),
+                        metadata: Some(metadata),
+                    })
                 }))
             });
 
@@ -133,7 +133,8 @@ impl KinesisFirehoseSinkConfig {
         let client = rusoto::client()?;
         let creds = self.auth.build(&region, self.assume_role.clone())?;
 
-        let client = rusoto_core::Client::new_with_encoding(creds, client, self.compression.into());
+        let client =
+            rusoto_core::Client::new_with_encoding(creds, client, self.compression.try_into()?);
         Ok(KinesisFirehoseClient::new_with_client(client, region))
     }
 }
@@ -256,7 +257,7 @@ fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> Encode
 
     let data = Bytes::from(data);
 
-    EncodedEvent::new(Record { data })
+    EncodedEvent::new(Record { data }).with_metadata(log)
 }
 
 #[cfg(test)]
@@ -136,7 +136,8 @@ impl KinesisSinkConfig {
         let client = rusoto::client()?;
         let creds = self.auth.build(&region, self.assume_role.clone())?;
 
-        let client = rusoto_core::Client::new_with_encoding(creds, client, self.compression.into());
+        let client =
+            rusoto_core::Client::new_with_encoding(creds, client, self.compression.try_into()?);
         Ok(KinesisClient::new_with_client(client, region))
     }
 }
@@ -304,11 +305,14 @@ fn encode_event(
             .unwrap_or_default(),
     };
 
-    Some(EncodedEvent::new(PutRecordsRequestEntry {
-        data: Bytes::from(data),
-        partition_key,
-        ..Default::default()
-    }))
+    Some(
+        EncodedEvent::new(PutRecordsRequestEntry {
+            data: Bytes::from(data),
+            partition_key,
+            ..Default::default()
+        })
+        .with_metadata(log),
+    )
 }
 
 fn gen_partition_key() -> String {
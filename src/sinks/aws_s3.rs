@@ -1,16 +1,18 @@
 use crate::{
-    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     internal_events::TemplateRenderingFailed,
     rusoto::{self, AwsAuthentication, RegionOrEndpoint},
     serde::to_string,
     sinks::util::{
         batch::{BatchConfig, BatchSettings},
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{
+            Encoder, EncodingConfig, ParquetEncoder, ParquetRow, StandardEncoder, StandardEncodings,
+        },
         retries::RetryLogic,
         sink::Response,
-        Buffer, Compression, Concurrency, EncodedEvent, PartitionBatchSink, PartitionBuffer,
-        PartitionInnerBuffer, ServiceBuilderExt, TowerRequestConfig,
+        Buffer, Compression, Concurrency, EncodedEvent, ParquetBuffer, PartitionBatchSink,
+        PartitionBuffer, PartitionInnerBuffer, ServiceBuilderExt, TowerRequestConfig,
     },
     template::Template,
 };
@@ -52,7 +54,7 @@ pub struct S3SinkConfig {
     options: S3Options,
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<StandardEncodings>,
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
     #[serde(default)]
@@ -125,13 +127,6 @@ lazy_static! {
     };
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Derivative)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Ndjson,
-}
-
 inventory::submit! {
     SinkDescription::new::<S3SinkConfig>("aws_s3")
 }
@@ -146,7 +141,7 @@ impl GenerateConfig for S3SinkConfig {
             filename_extension: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::default(),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
@@ -192,9 +187,14 @@ enum HealthcheckError {
 impl S3SinkConfig {
     pub fn new(&self, client: S3Client, cx: SinkContext) -> crate::Result<super::VectorSink> {
         let request = self.request.unwrap_with(&REQUEST_DEFAULTS);
-        let encoding = self.encoding.clone();
+        let parquet = self.encoding.codec == StandardEncodings::Parquet;
 
-        let compression = self.compression;
+        // Parquet pages are compressed within the file, so the object itself is not.
+        let compression = if parquet {
+            Compression::None
+        } else {
+            self.compression
+        };
         let filename_time_format = self
             .filename_time_format
             .clone()
@@ -210,7 +210,10 @@ impl S3SinkConfig {
 
         let s3 = S3Sink { client };
 
-        let filename_extension = self.filename_extension.clone();
+        let filename_extension = self
+            .filename_extension
+            .clone()
+            .or_else(|| parquet.then(|| "parquet".into()));
         let bucket = self.bucket.clone();
         let options = self.options.clone();
 
@@ -226,16 +229,34 @@ impl S3SinkConfig {
                     options.clone(),
                 )
             })
-            .settings(request, S3RetryLogic)
-            .service(s3);
-
-        let buffer = PartitionBuffer::new(Buffer::new(batch.size, self.compression));
+            .settings(request, S3RetryLogic);
+
+        if parquet {
+            let encoder = ParquetEncoder::new(self.encoding.clone(), self.compression)?;
+            let buffer = PartitionBuffer::new(ParquetBuffer::new(
+                batch.into::<ParquetBuffer>().size,
+                encoder.clone(),
+            ));
+
+            let sink = PartitionBatchSink::new(svc.service(s3), buffer, batch.timeout, cx.acker())
+                .with_flat_map(move |e| {
+                    stream::iter(encode_parquet_row(e, &key_prefix, &encoder)).map(Ok)
+                })
+                .sink_map_err(|error| error!(message = "Sink failed to flush.", %error));
+
+            Ok(super::VectorSink::Sink(Box::new(sink)))
+        } else {
+            let encoder = StandardEncoder::new(self.encoding.clone())?;
+            let buffer = PartitionBuffer::new(Buffer::new(batch.size, self.compression));
 
-        let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
-            .with_flat_map(move |e| stream::iter(encode_event(e, &key_prefix, &encoding)).map(Ok))
-            .sink_map_err(|error| error!(message = "Sink failed to flush.", %error));
+            let sink = PartitionBatchSink::new(svc.service(s3), buffer, batch.timeout, cx.acker())
+                .with_flat_map(move |e| {
+                    stream::iter(encode_event(e, &key_prefix, &encoder)).map(Ok)
+                })
+                .sink_map_err(|error| error!(message = "Sink failed to flush.", %error));
 
-        Ok(super::VectorSink::Sink(Box::new(sink)))
+            Ok(super::VectorSink::Sink(Box::new(sink)))
+        }
     }
 
     pub async fn healthcheck(self, client: S3Client) -> crate::Result<()> {
@@ -396,9 +417,9 @@ impl RetryLogic for S3RetryLogic {
 }
 
 fn encode_event(
-    mut event: Event,
+    event: Event,
     key_prefix: &Template,
-    encoding: &EncodingConfig<Encoding>,
+    encoder: &StandardEncoder,
 ) -> Option<EncodedEvent<PartitionInnerBuffer<Vec<u8>, Bytes>>> {
     let key = key_prefix
         .render_string(&event)
@@ -411,36 +432,47 @@ fn encode_event(
         })
         .ok()?;
 
-    encoding.apply_rules(&mut event);
-
-    let log = event.into_log();
-    let bytes = match encoding.codec() {
-        Encoding::Ndjson => serde_json::to_vec(&log)
-            .map(|mut b| {
-                b.push(b'\n');
-                b
-            })
-            .expect("Failed to encode event as json, this is a bug!"),
-        Encoding::Text => {
-            let mut bytes = log
-                .get(log_schema().message_key())
-                .map(|v| v.as_bytes().to_vec())
-                .unwrap_or_default();
-            bytes.push(b'\n');
-            bytes
-        }
-    };
+    let metadata = event.metadata().clone();
+    let bytes = encoder
+        .encode_input_to_vec(event)
+        .map_err(|error| error!(message = "Unable to encode.", %error))
+        .ok()?;
 
-    let (_fields, metadata) = log.into_parts();
     Some(EncodedEvent {
         item: PartitionInnerBuffer::new(bytes, key.into()),
         metadata: Some(metadata),
     })
 }
 
+fn encode_parquet_row(
+    event: Event,
+    key_prefix: &Template,
+    encoder: &ParquetEncoder,
+) -> Option<EncodedEvent<PartitionInnerBuffer<ParquetRow, Bytes>>> {
+    let key = key_prefix
+        .render_string(&event)
+        .map_err(|error| {
+            emit!(TemplateRenderingFailed {
+                error,
+                field: Some("key_prefix"),
+                drop_event: true,
+            });
+        })
+        .ok()?;
+
+    let metadata = event.metadata().clone();
+    let row = encoder.encode_row(event);
+
+    Some(EncodedEvent {
+        item: PartitionInnerBuffer::new(row, key.into()),
+        metadata: Some(metadata),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::log_schema;
 
     #[test]
     fn generate_config() {
@@ -451,12 +483,8 @@ mod tests {
     fn s3_encode_event_text() {
         let message = "hello world".to_string();
         let batch_time_format = Template::try_from("date=%F").unwrap();
-        let encoded = encode_event(
-            message.clone().into(),
-            &batch_time_format,
-            &Encoding::Text.into(),
-        )
-        .unwrap();
+        let encoder = StandardEncoder::new(StandardEncodings::Text.into()).unwrap();
+        let encoded = encode_event(message.clone().into(), &batch_time_format, &encoder).unwrap();
 
         let encoded_message = message + "\n";
         let (bytes, _) = encoded.item.into_parts();
@@ -470,7 +498,8 @@ mod tests {
         event.as_mut_log().insert("key", "value");
 
         let batch_time_format = Template::try_from("date=%F").unwrap();
-        let encoded = encode_event(event, &batch_time_format, &Encoding::Ndjson.into()).unwrap();
+        let encoder = StandardEncoder::new(StandardEncodings::Ndjson.into()).unwrap();
+        let encoded = encode_event(event, &batch_time_format, &encoder).unwrap();
 
         let (bytes, _) = encoded.item.into_parts();
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
@@ -488,14 +517,17 @@ mod tests {
         let key_prefix = Template::try_from("{{ key }}").unwrap();
 
         let encoding_config = EncodingConfig {
-            codec: Encoding::Ndjson,
+            codec: StandardEncodings::Ndjson,
             schema: None,
             only_fields: None,
             except_fields: Some(vec!["key".into()]),
             timestamp_format: None,
+            framing: None,
+            parquet: None,
         };
+        let encoder = StandardEncoder::new(encoding_config).unwrap();
 
-        let encoded = encode_event(event, &key_prefix, &encoding_config).unwrap();
+        let encoded = encode_event(event, &key_prefix, &encoder).unwrap();
 
         let (bytes, _) = encoded.item.into_parts();
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
@@ -829,7 +861,7 @@ mod integration_tests {
             filename_extension: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::with_endpoint("http://localhost:4566".to_owned()),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             compression: Compression::None,
             batch: BatchConfig {
                 max_bytes: Some(batch_size),
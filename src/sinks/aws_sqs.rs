@@ -280,10 +280,13 @@ fn encode_event(
         Encoding::Json => serde_json::to_string(&log).expect("Error encoding event as json."),
     };
 
-    Some(EncodedEvent::new(SendMessageEntry {
-        message_body,
-        message_group_id,
-    }))
+    Some(
+        EncodedEvent::new(SendMessageEntry {
+            message_body,
+            message_group_id,
+        })
+        .with_metadata(log),
+    )
 }
 
 #[cfg(test)]
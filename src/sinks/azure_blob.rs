@@ -1,5 +1,5 @@
 use crate::{
-    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext},
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext},
     event::Event,
     internal_events::{
         azure_blob::{AzureBlobErrorResponse, AzureBlobEventSent, AzureBlobHttpError},
@@ -7,12 +7,15 @@ use crate::{
     },
     sinks::{
         util::{
-            encoding::{EncodingConfig, EncodingConfiguration},
+            encoding::{
+                Encoder, EncodingConfig, ParquetEncoder, ParquetRow, StandardEncoder,
+                StandardEncodings,
+            },
             retries::RetryLogic,
             sink::Response,
             BatchConfig, BatchSettings, Buffer, Compression, Concurrency, EncodedEvent,
-            PartitionBatchSink, PartitionBuffer, PartitionInnerBuffer, ServiceBuilderExt,
-            TowerRequestConfig,
+            ParquetBuffer, PartitionBatchSink, PartitionBuffer, PartitionInnerBuffer,
+            ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
@@ -54,7 +57,7 @@ pub struct AzureBlobSinkConfig {
     pub blob_prefix: Option<String>,
     pub blob_time_format: Option<String>,
     pub blob_append_uuid: Option<bool>,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<StandardEncodings>,
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
     #[serde(default)]
@@ -75,13 +78,6 @@ struct AzureBlobSinkRequest {
     content_type: &'static str,
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Ndjson,
-    Text,
-}
-
 #[derive(Debug, Snafu)]
 enum HealthcheckError {
     #[snafu(display("Invalid connection string specified"))]
@@ -108,7 +104,7 @@ impl GenerateConfig for AzureBlobSinkConfig {
             blob_prefix: Some(String::from("blob")),
             blob_time_format: Some(String::from("%s")),
             blob_append_uuid: Some(true),
-            encoding: Encoding::Ndjson.into(),
+            encoding: StandardEncodings::Ndjson.into(),
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
@@ -144,6 +140,7 @@ impl AzureBlobSinkConfig {
             .timeout(300)
             .parse_config(self.batch)?;
         let compression = self.compression;
+        let codec = self.encoding.codec;
         let container_name = self.container_name.clone();
         let blob_time_format = self.blob_time_format.clone().unwrap_or_else(|| "%s".into());
         let blob_append_uuid = self.blob_append_uuid.unwrap_or(true);
@@ -153,6 +150,7 @@ impl AzureBlobSinkConfig {
                 build_request(
                     partition,
                     compression,
+                    codec,
                     container_name.clone(),
                     blob_time_format.clone(),
                     blob_append_uuid,
@@ -161,17 +159,33 @@ impl AzureBlobSinkConfig {
             .settings(request, AzureBlobRetryLogic)
             .service(blob);
 
-        let encoding = self.encoding.clone();
         let blob_prefix = self.blob_prefix.as_deref().unwrap_or("blob/%F/");
         let blob_prefix = Template::try_from(blob_prefix)?;
-        let buffer = PartitionBuffer::new(Buffer::new(batch.size, compression));
-        let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
-            .with_flat_map(move |event| {
-                stream::iter(encode_event(event, &blob_prefix, &encoding)).map(Ok)
-            })
-            .sink_map_err(|error| error!(message = "Sink failed to flush.", %error));
 
-        Ok(super::VectorSink::Sink(Box::new(sink)))
+        if codec == StandardEncodings::Parquet {
+            let encoder = ParquetEncoder::new(self.encoding.clone(), compression)?;
+            let buffer = PartitionBuffer::new(ParquetBuffer::new(
+                batch.into::<ParquetBuffer>().size,
+                encoder.clone(),
+            ));
+            let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
+                .with_flat_map(move |event| {
+                    stream::iter(encode_parquet_row(event, &blob_prefix, &encoder)).map(Ok)
+                })
+                .sink_map_err(|error| error!(message = "Sink failed to flush.", %error));
+
+            Ok(super::VectorSink::Sink(Box::new(sink)))
+        } else {
+            let encoder = StandardEncoder::new(self.encoding.clone())?;
+            let buffer = PartitionBuffer::new(Buffer::new(batch.size, compression));
+            let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
+                .with_flat_map(move |event| {
+                    stream::iter(encode_event(event, &blob_prefix, &encoder)).map(Ok)
+                })
+                .sink_map_err(|error| error!(message = "Sink failed to flush.", %error));
+
+            Ok(super::VectorSink::Sink(Box::new(sink)))
+        }
     }
 
     pub async fn healthcheck(self, client: Arc<ContainerClient>) -> Result<()> {
@@ -256,6 +270,9 @@ impl Compression {
         match self {
             Self::None => "text/plain",
             Self::Gzip(_) => "application/gzip",
+            Self::Zlib(_) => "application/zlib",
+            Self::Zstd(_) => "application/zstd",
+            Self::Snappy => "application/x-snappy-framed",
         }
     }
 }
@@ -277,9 +294,9 @@ impl RetryLogic for AzureBlobRetryLogic {
 }
 
 fn encode_event(
-    mut event: Event,
+    event: Event,
     blob_prefix: &Template,
-    encoding: &EncodingConfig<Encoding>,
+    encoder: &StandardEncoder,
 ) -> Option<EncodedEvent<PartitionInnerBuffer<Vec<u8>, Bytes>>> {
     let key = blob_prefix
         .render_string(&event)
@@ -292,35 +309,47 @@ fn encode_event(
         })
         .ok()?;
 
-    encoding.apply_rules(&mut event);
+    let metadata = event.metadata().clone();
+    let bytes = encoder
+        .encode_input_to_vec(event)
+        .map_err(|error| error!(message = "Unable to encode.", %error))
+        .ok()?;
 
-    let log = event.into_log();
-    let bytes = match encoding.codec() {
-        Encoding::Ndjson => serde_json::to_vec(&log)
-            .map(|mut b| {
-                b.push(b'\n');
-                b
-            })
-            .expect("Failed to encode event as json, this is a bug!"),
-        Encoding::Text => {
-            let mut bytes = log
-                .get(log_schema().message_key())
-                .map(|v| v.as_bytes().to_vec())
-                .expect("Failed to encode event as text");
-            bytes.push(b'\n');
-            bytes
-        }
-    };
+    Some(EncodedEvent {
+        item: PartitionInnerBuffer::new(bytes, key.into()),
+        metadata: Some(metadata),
+    })
+}
 
-    Some(EncodedEvent::new(PartitionInnerBuffer::new(
-        bytes,
-        key.into(),
-    )))
+fn encode_parquet_row(
+    event: Event,
+    blob_prefix: &Template,
+    encoder: &ParquetEncoder,
+) -> Option<EncodedEvent<PartitionInnerBuffer<ParquetRow, Bytes>>> {
+    let key = blob_prefix
+        .render_string(&event)
+        .map_err(|error| {
+            emit!(TemplateRenderingFailed {
+                error,
+                field: Some("blob_prefix"),
+                drop_event: true,
+            });
+        })
+        .ok()?;
+
+    let metadata = event.metadata().clone();
+    let row = encoder.encode_row(event);
+
+    Some(EncodedEvent {
+        item: PartitionInnerBuffer::new(row, key.into()),
+        metadata: Some(metadata),
+    })
 }
 
 fn build_request(
     partition: PartitionInnerBuffer<Vec<u8>, Bytes>,
     compression: Compression,
+    codec: StandardEncodings,
     container_name: String,
     blob_time_format: String,
     blob_append_uuid: bool,
@@ -336,8 +365,17 @@ fn build_request(
             time_format.to_string()
         }
     };
+    let (extension, content_encoding, content_type) = match codec {
+        // Parquet pages are compressed within the file, so the blob itself is not.
+        StandardEncodings::Parquet => ("parquet", None, codec.content_type()),
+        _ => (
+            compression.extension(),
+            compression.content_encoding(),
+            compression.content_type(),
+        ),
+    };
     let blob = String::from_utf8_lossy(&key[..]).into_owned();
-    let blob = format!("{}{}.{}", blob, filename, compression.extension());
+    let blob = format!("{}{}.{}", blob, filename, extension);
 
     debug!(
         message = "Sending events.",
@@ -350,15 +388,15 @@ fn build_request(
         container_name,
         blob_data: inner,
         blob_name: blob,
-        content_encoding: compression.content_encoding(),
-        content_type: compression.content_type(),
+        content_encoding,
+        content_type,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::event::LogEvent;
+    use crate::{config::log_schema, event::LogEvent};
     use std::collections::BTreeMap;
 
     #[test]
@@ -372,14 +410,17 @@ mod tests {
         let log = LogEvent::from(message.clone());
         let blob_prefix = Template::try_from("logs/blob/%F").unwrap();
         let encoding = EncodingConfig {
-            codec: Encoding::Text,
+            codec: StandardEncodings::Text,
             schema: None,
             only_fields: None,
             except_fields: None,
             timestamp_format: None,
+            framing: None,
+            parquet: None,
         };
+        let encoder = StandardEncoder::new(encoding).unwrap();
 
-        let bytes = encode_event(log.into(), &blob_prefix, &encoding).unwrap();
+        let bytes = encode_event(log.into(), &blob_prefix, &encoder).unwrap();
 
         let encoded_message = message + "\n";
         let (bytes, _) = bytes.item.into_parts();
@@ -393,14 +434,17 @@ mod tests {
         log.insert("key", "value");
         let blob_prefix = Template::try_from("logs/blob/%F").unwrap();
         let encoding = EncodingConfig {
-            codec: Encoding::Ndjson,
+            codec: StandardEncodings::Ndjson,
             schema: None,
             only_fields: None,
             except_fields: None,
             timestamp_format: None,
+            framing: None,
+            parquet: None,
         };
+        let encoder = StandardEncoder::new(encoding).unwrap();
 
-        let bytes = encode_event(log.into(), &blob_prefix, &encoding).unwrap();
+        let bytes = encode_event(log.into(), &blob_prefix, &encoder).unwrap();
 
         let (bytes, _) = bytes.item.into_parts();
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
@@ -415,14 +459,17 @@ mod tests {
         log.insert("key", "value");
         let blob_prefix = Template::try_from("logs/blob/%F").unwrap();
         let encoding = EncodingConfig {
-            codec: Encoding::Ndjson,
+            codec: StandardEncodings::Ndjson,
             schema: None,
             only_fields: None,
             except_fields: Some(vec!["key".into()]),
             timestamp_format: None,
+            framing: None,
+            parquet: None,
         };
+        let encoder = StandardEncoder::new(encoding).unwrap();
 
-        let bytes = encode_event(log.into(), &blob_prefix, &encoding).unwrap();
+        let bytes = encode_event(log.into(), &blob_prefix, &encoder).unwrap();
 
         let (bytes, _) = bytes.item.into_parts();
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
@@ -441,6 +488,7 @@ mod tests {
         let request = build_request(
             partition,
             compression,
+            StandardEncodings::Ndjson,
             container_name,
             blob_time_format,
             blob_append_uuid,
@@ -463,6 +511,7 @@ mod tests {
         let request = build_request(
             partition,
             compression,
+            StandardEncodings::Ndjson,
             container_name,
             blob_time_format,
             blob_append_uuid,
@@ -485,6 +534,7 @@ mod tests {
         let request = build_request(
             partition,
             compression,
+            StandardEncodings::Ndjson,
             container_name,
             blob_time_format,
             blob_append_uuid,
@@ -510,6 +560,7 @@ mod tests {
         let request = build_request(
             partition,
             compression,
+            StandardEncodings::Ndjson,
             container_name,
             blob_time_format,
             blob_append_uuid,
@@ -520,6 +571,28 @@ mod tests {
         assert_eq!(request.content_encoding, None);
         assert_eq!(request.content_type, "text/plain");
     }
+
+    #[test]
+    fn azure_blob_build_request_parquet() {
+        let partition = PartitionInnerBuffer::new(vec![0u8; 10], Bytes::from("blob"));
+        let compression = Compression::gzip_default();
+        let container_name = String::from("logs");
+        let blob_time_format = String::from("");
+        let blob_append_uuid = false;
+
+        let request = build_request(
+            partition,
+            compression,
+            StandardEncodings::Parquet,
+            container_name,
+            blob_time_format,
+            blob_append_uuid,
+        );
+
+        assert_eq!(request.blob_name, "blob.parquet".to_string());
+        assert_eq!(request.content_encoding, None);
+        assert_eq!(request.content_type, "application/octet-stream");
+    }
 }
 
 #[cfg(feature = "azure-blob-integration-tests")]
@@ -590,7 +663,7 @@ mod integration_tests {
         let config = AzureBlobSinkConfig::new_emulator().await;
         let config = AzureBlobSinkConfig {
             blob_prefix: Some(blob_prefix.clone()),
-            encoding: Encoding::Ndjson.into(),
+            encoding: StandardEncodings::Ndjson.into(),
             ..config
         };
         let sink = config.to_sink();
@@ -647,7 +720,7 @@ mod integration_tests {
         let config = AzureBlobSinkConfig::new_emulator().await;
         let config = AzureBlobSinkConfig {
             blob_prefix: Some(blob_prefix.clone()),
-            encoding: Encoding::Ndjson.into(),
+            encoding: StandardEncodings::Ndjson.into(),
             compression: Compression::gzip_default(),
             ..config
         };
@@ -713,7 +786,7 @@ mod integration_tests {
                 blob_prefix: None,
                 blob_time_format: None,
                 blob_append_uuid: None,
-                encoding: Encoding::Text.into(),
+                encoding: StandardEncodings::Text.into(),
                 compression: Compression::None,
                 batch: Default::default(),
                 request: TowerRequestConfig::default(),
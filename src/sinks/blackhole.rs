@@ -2,7 +2,7 @@ use crate::{
     buffers::Acker,
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     emit,
-    event::Event,
+    event::{Event, EventStatus},
     internal_events::BlackholeEventReceived,
     sinks::util::StreamSink,
 };
@@ -89,9 +89,11 @@ impl StreamSink for BlackholeSink {
                 self.last = Some(until);
             }
 
+            event.metadata().update_status(EventStatus::Delivered);
             let message_len = match event {
                 Event::Log(log) => serde_json::to_string(&log),
                 Event::Metric(metric) => serde_json::to_string(&metric),
+                Event::Trace(trace) => serde_json::to_string(&trace),
             }
             .map(|v| v.len())
             .unwrap_or(0);
@@ -1,7 +1,7 @@
 use crate::{
     buffers::Acker,
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
-    event::Event,
+    event::{Event, EventStatus},
     internal_events::{ConsoleEventProcessed, ConsoleFieldNotFound},
     sinks::util::{
         encoding::{EncodingConfig, EncodingConfiguration},
@@ -121,6 +121,13 @@ fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> Option
                 .ok(),
             Encoding::Text => Some(format!("{}", metric)),
         },
+        // Spans have no single message field to print as text, so they are
+        // always written as JSON.
+        Event::Trace(trace) => serde_json::to_string(&trace)
+            .map_err(|error| {
+                error!(message = "Error encoding json.", %error);
+            })
+            .ok(),
     }
 }
 
@@ -135,14 +142,17 @@ impl StreamSink for WriterSink {
     async fn run(&mut self, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
         while let Some(event) = input.next().await {
             self.acker.ack(1);
+            let metadata = event.metadata().clone();
             if let Some(mut buf) = encode_event(event, &self.encoding) {
                 buf.push('\n');
                 if let Err(error) = self.output.write_all(buf.as_bytes()).await {
                     // Error when writing to stdout/stderr is likely irrecoverable,
                     // so stop the sink.
+                    metadata.update_status(EventStatus::Errored);
                     error!(message = "Error writing to output. Stopping sink.", %error);
                     return Err(());
                 }
+                metadata.update_status(EventStatus::Delivered);
 
                 emit!(ConsoleEventProcessed {
                     byte_size: buf.len(),
@@ -157,7 +167,7 @@ impl StreamSink for WriterSink {
 mod test {
     use super::{encode_event, ConsoleSinkConfig, Encoding, EncodingConfig};
     use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
-    use crate::event::{Event, Value};
+    use crate::event::{Event, TraceEvent, Value};
     use chrono::{offset::TimeZone, Utc};
     use pretty_assertions::assert_eq;
 
@@ -188,6 +198,24 @@ mod test {
         assert_eq!(encoded.unwrap(), expected);
     }
 
+    #[test]
+    fn encodes_trace_events() {
+        let mut trace = TraceEvent::default();
+        trace.insert("span_id", "abc123");
+        trace.insert("duration_ns", 42);
+        let event = Event::Trace(trace);
+
+        let expected = r#"{"duration_ns":42,"span_id":"abc123"}"#;
+        assert_eq!(
+            encode_event(event.clone(), &EncodingConfig::from(Encoding::Json)).unwrap(),
+            expected
+        );
+        assert_eq!(
+            encode_event(event, &EncodingConfig::from(Encoding::Text)).unwrap(),
+            expected
+        );
+    }
+
     #[test]
     fn encodes_counter() {
         let event = Event::Metric(
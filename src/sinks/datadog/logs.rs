@@ -8,7 +8,7 @@ use crate::{
         util::{
             batch::{Batch, BatchError},
             encode_event,
-            encoding::{EncodingConfig, EncodingConfiguration},
+            encoding::{EncodingConfig, EncodingConfiguration, StandardEncoder},
             http::{HttpSink, PartitionHttpSink},
             BatchConfig, BatchSettings, BoxedRawValue, Compression, EncodedEvent, Encoding,
             JsonArrayBuffer, PartitionBuffer, PartitionInnerBuffer, TowerRequestConfig, VecBuffer,
@@ -18,14 +18,13 @@ use crate::{
     tls::{MaybeTlsSettings, TlsConfig},
 };
 use bytes::Bytes;
-use flate2::write::GzEncoder;
 use futures::{FutureExt, SinkExt};
 use http::{Request, StatusCode};
 use hyper::body::Body;
 use indoc::indoc;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::{io::Write, sync::Arc, time::Duration};
+use std::{sync::Arc, time::Duration};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
@@ -61,6 +60,7 @@ struct DatadogLogsJsonService {
 #[derive(Clone)]
 struct DatadogLogsTextService {
     config: DatadogLogsConfig,
+    encoder: StandardEncoder,
     // Used to store the complete URI and avoid calling `get_uri` for each request
     uri: String,
     default_api_key: ApiKey,
@@ -154,7 +154,7 @@ impl DatadogLogsConfig {
         Ok((VectorSink::Sink(Box::new(sink)), healthcheck))
     }
 
-    /// Build the request, GZipping the contents if the config specifies.
+    /// Build the request, compressing the contents if the config specifies.
     fn build_request(
         &self,
         uri: &str,
@@ -168,21 +168,14 @@ impl DatadogLogsConfig {
 
         let compression = self.compression.unwrap_or(Compression::Gzip(None));
 
-        let (request, body) = match compression {
-            Compression::None => (request, body),
-            Compression::Gzip(level) => {
-                // Default the compression level to 6, which is similar to datadog agent.
-                // https://docs.datadoghq.com/agent/logs/log_transport/?tab=https#log-compression
-                let level = level.unwrap_or(6);
-                let mut encoder =
-                    GzEncoder::new(Vec::new(), flate2::Compression::new(level as u32));
-
-                encoder.write_all(&body)?;
-                (
-                    request.header("Content-Encoding", "gzip"),
-                    encoder.finish()?,
-                )
-            }
+        let (request, body) = match compression.content_encoding() {
+            None => (request, body),
+            // Gzip defaults to level 6, which is similar to datadog agent.
+            // https://docs.datadoghq.com/agent/logs/log_transport/?tab=https#log-compression
+            Some(content_encoding) => (
+                request.header("Content-Encoding", content_encoding),
+                compression.compress(body)?,
+            ),
         };
 
         request
@@ -219,6 +212,7 @@ impl SinkConfig for DatadogLogsConfig {
                     cx,
                     DatadogLogsTextService {
                         config: self.clone(),
+                        encoder: StandardEncoder::new(self.encoding.clone().into_encoding())?,
                         uri: self.get_uri(),
                         default_api_key: Arc::from(self.default_api_key.clone()),
                     },
@@ -303,7 +297,7 @@ impl HttpSink for DatadogLogsTextService {
                 .unwrap_or(&self.default_api_key),
         );
 
-        encode_event(event, &self.config.encoding).map(|e| {
+        encode_event(event, &self.encoder).map(|e| {
             emit!(DatadogLogEventProcessed {
                 byte_size: e.item.len(),
                 count: 1,
@@ -2,8 +2,9 @@ use super::healthcheck;
 use crate::{
     config::{DataType, SinkConfig, SinkContext, SinkDescription},
     event::metric::{Metric, MetricKind, MetricValue, Sample, StatisticKind},
-    event::Event,
+    event::{Event, EventStatus},
     http::HttpClient,
+    internal_events::DatadogMetricsAbsoluteSketchRejected,
     sinks::{
         util::{
             batch::{BatchConfig, BatchSettings},
@@ -20,6 +21,7 @@ use chrono::{DateTime, Utc};
 use futures::{stream, FutureExt, SinkExt};
 use http::{uri::InvalidUri, Request, Uri};
 use lazy_static::lazy_static;
+use prost::Message;
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use std::{
@@ -29,6 +31,15 @@ use std::{
     sync::atomic::{AtomicI64, Ordering::SeqCst},
 };
 
+mod ddsketch_proto {
+    include!(concat!(env!("OUT_DIR"), "/datadog.agentpayload.rs"));
+}
+
+use ddsketch_proto::{
+    sketch_payload::{sketch::Dogsketch, Sketch},
+    SketchPayload,
+};
+
 #[derive(Debug, Snafu)]
 enum BuildError {
     #[snafu(display("Invalid host {:?}: {:?}", host, source))]
@@ -53,6 +64,8 @@ pub struct DatadogConfig {
     pub site: Option<String>,
     pub api_key: String,
     #[serde(default)]
+    pub send_distributions_as_sketches: bool,
+    #[serde(default)]
     pub batch: BatchConfig,
     #[serde(default)]
     pub request: TowerRequestConfig,
@@ -148,6 +161,7 @@ struct DatadogStats {
 enum DatadogEndpoint {
     Series,
     Distribution,
+    Sketches,
 }
 
 impl DatadogEndpoint {
@@ -158,6 +172,10 @@ impl DatadogEndpoint {
                 DatadogEndpoint::Distribution,
                 build_uri(host, "/api/v1/distribution_points")?,
             ),
+            (
+                DatadogEndpoint::Sketches,
+                build_uri(host, "/api/beta/sketches")?,
+            ),
         ])
     }
 
@@ -167,6 +185,7 @@ impl DatadogEndpoint {
                 statistic: StatisticKind::Summary,
                 ..
             } => Self::Distribution,
+            MetricValue::Sketch { .. } => Self::Sketches,
             _ => Self::Series,
         }
     }
@@ -214,11 +233,16 @@ impl SinkConfig for DatadogConfig {
 
         let buffer = PartitionBuffer::new(MetricsBuffer::new(batch.size));
         let mut normalizer = MetricNormalizer::<DatadogMetricNormalize>::default();
+        let send_distributions_as_sketches = self.send_distributions_as_sketches;
 
         let svc_sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
             .sink_map_err(|error| error!(message = "Fatal datadog metric sink error.", %error))
             .with_flat_map(move |event: Event| {
-                stream::iter(normalizer.apply(event).map(encode_metric))
+                stream::iter(
+                    reject_absolute_sketch(event.into_metric())
+                        .and_then(|metric| normalizer.apply(metric.into()))
+                        .map(|metric| encode_metric(metric, send_distributions_as_sketches)),
+                )
             });
 
         Ok((VectorSink::Sink(Box::new(svc_sink)), healthcheck))
@@ -233,9 +257,37 @@ impl SinkConfig for DatadogConfig {
     }
 }
 
+/// Datadog expects the sketch of each interval, and sketches can't be turned
+/// back into increments, so absolute sketches are rejected.
+fn reject_absolute_sketch(metric: Metric) -> Option<Metric> {
+    match (metric.kind(), metric.value()) {
+        (MetricKind::Absolute, MetricValue::Sketch { .. }) => {
+            emit!(DatadogMetricsAbsoluteSketchRejected {
+                name: metric.name()
+            });
+            metric.metadata().update_status(EventStatus::Failed);
+            None
+        }
+        _ => Some(metric),
+    }
+}
+
 fn encode_metric(
     metric: Metric,
+    send_distributions_as_sketches: bool,
 ) -> Result<EncodedEvent<PartitionInnerBuffer<Metric, DatadogEndpoint>>, ()> {
+    // Sketches keep the samples of summary distributions in a bounded number
+    // of bins instead of sending every sample.
+    let metric = match metric.value() {
+        MetricValue::Distribution {
+            statistic: StatisticKind::Summary,
+            ..
+        } if send_distributions_as_sketches => {
+            let value = metric.value().clone().distribution_to_sketch();
+            metric.with_value(value)
+        }
+        _ => metric,
+    };
     let endpoint = DatadogEndpoint::from_metric(&metric);
     // TODO: Avoiding this clone requires rewriting MetricsBuffer to
     // accept separated MetricSeries and MetricData values, which in
@@ -262,11 +314,11 @@ impl DatadogSink {
         let interval = now - endpoint_data.1.load(SeqCst);
         endpoint_data.1.store(now, SeqCst);
 
-        let body = match endpoint {
+        let (body, content_type) = match endpoint {
             DatadogEndpoint::Series => {
                 let input =
                     encode_events(events, self.config.default_namespace.as_deref(), interval);
-                serde_json::to_vec(&input).unwrap()
+                (serde_json::to_vec(&input).unwrap(), "application/json")
             }
             DatadogEndpoint::Distribution => {
                 let input = encode_distribution_events(
@@ -274,12 +326,18 @@ impl DatadogSink {
                     self.config.default_namespace.as_deref(),
                     interval,
                 );
-                serde_json::to_vec(&input).unwrap()
+                (serde_json::to_vec(&input).unwrap(), "application/json")
+            }
+            DatadogEndpoint::Sketches => {
+                let input = encode_sketch_events(events, self.config.default_namespace.as_deref());
+                let mut body = Vec::with_capacity(input.encoded_len());
+                input.encode(&mut body).expect("Out of memory");
+                (body, "application/x-protobuf")
             }
         };
 
         Request::post(endpoint_data.0.clone())
-            .header("Content-Type", "application/json")
+            .header("Content-Type", content_type)
             .header("DD-API-KEY", self.config.api_key.clone())
             .body(body)
             .map_err(Into::into)
@@ -518,10 +576,59 @@ fn encode_distribution_events(
     DatadogRequest { series }
 }
 
+fn encode_sketch_events(events: Vec<Metric>, default_namespace: Option<&str>) -> SketchPayload {
+    debug!(message = "Sketches.", count = events.len());
+    let sketches = events
+        .into_iter()
+        .filter_map(|event| {
+            let fullname =
+                encode_namespace(event.namespace().or(default_namespace), '.', event.name());
+            let ts = encode_timestamp(event.timestamp());
+            let tags = event.tags().map(encode_tags).unwrap_or_default();
+            match (event.kind(), event.value()) {
+                // Absolute sketches are rejected before they're batched.
+                (MetricKind::Incremental, MetricValue::Sketch { sketch }) if !sketch.is_empty() => {
+                    let (k, n) = sketch
+                        .bins()
+                        .iter()
+                        .map(|bin| (i32::from(bin.k), bin.n))
+                        .unzip();
+                    Some(Sketch {
+                        metric: fullname,
+                        host: String::new(),
+                        distributions: Vec::new(),
+                        tags,
+                        dogsketches: vec![Dogsketch {
+                            ts,
+                            cnt: i64::from(sketch.count()),
+                            min: sketch.min().unwrap_or_default(),
+                            max: sketch.max().unwrap_or_default(),
+                            avg: sketch.avg(),
+                            sum: sketch.sum(),
+                            k,
+                            n,
+                        }],
+                    })
+                }
+                _ => None,
+            }
+        })
+        .collect();
+
+    SketchPayload {
+        sketches,
+        metadata: None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{event::metric::Sample, sinks::util::test::load_sink};
+    use crate::{
+        event::{metric::Sample, BatchNotifier, BatchStatus},
+        metrics::DDSketch,
+        sinks::util::test::load_sink,
+    };
     use chrono::offset::TimeZone;
     use http::Method;
     use pretty_assertions::assert_eq;
@@ -804,4 +911,141 @@ mod tests {
             r#"{"series":[{"metric":"requests","interval":60,"points":[[1542182950,[1.0,1.0,1.0,2.0,2.0,2.0,3.0,3.0]]],"tags":null}]}"#
         );
     }
+
+    #[test]
+    fn sends_summary_distribution_as_distribution_by_default() {
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vector_core::samples![1.0 => 3, 2.0 => 3, 3.0 => 2],
+                statistic: StatisticKind::Summary,
+            },
+        );
+        let (encoded, endpoint) = encode_metric(metric.clone(), false)
+            .unwrap()
+            .item
+            .into_parts();
+
+        assert_eq!(endpoint, DatadogEndpoint::Distribution);
+        assert_eq!(encoded, metric);
+    }
+
+    #[test]
+    fn encodes_summary_distribution_as_sketch() {
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vector_core::samples![1.0 => 3, 2.0 => 3, 3.0 => 2],
+                statistic: StatisticKind::Summary,
+            },
+        );
+        let (metric, endpoint) = encode_metric(metric, true).unwrap().item.into_parts();
+
+        assert_eq!(endpoint, DatadogEndpoint::Sketches);
+        match metric.value() {
+            MetricValue::Sketch { sketch } => {
+                assert_eq!(sketch.count(), 8);
+                assert_eq!(sketch.sum(), 15.0);
+            }
+            value => panic!("Expected a sketch, got {:?}", value),
+        }
+    }
+
+    #[test]
+    fn rejects_absolute_sketch() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Absolute,
+            MetricValue::Sketch {
+                sketch: DDSketch::from_samples(&vector_core::samples![1.0 => 1]),
+            },
+        )
+        .with_batch_notifier(&batch);
+        drop(batch);
+
+        assert!(reject_absolute_sketch(metric).is_none());
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Failed));
+    }
+
+    #[test]
+    fn encode_sketch() {
+        let sketch = DDSketch::from_samples(&vector_core::samples![1.0 => 3, 2.0 => 3, 3.0 => 2]);
+        let events = vec![
+            Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Sketch {
+                    sketch: sketch.clone(),
+                },
+            )
+            .with_namespace(Some("ns"))
+            .with_tags(Some(tags()))
+            .with_timestamp(Some(ts())),
+            Metric::new(
+                "empty",
+                MetricKind::Incremental,
+                MetricValue::Sketch {
+                    sketch: DDSketch::default(),
+                },
+            ),
+        ];
+        let payload = encode_sketch_events(events, None);
+
+        assert_eq!(payload.sketches.len(), 1);
+        let encoded = &payload.sketches[0];
+        assert_eq!(encoded.metric, "ns.requests");
+        assert_eq!(
+            encoded.tags,
+            vec!["empty_tag:", "normal_tag:value", "true_tag:true"]
+        );
+
+        let dogsketch = &encoded.dogsketches[0];
+        assert_eq!(dogsketch.ts, 1542182950);
+        assert_eq!(dogsketch.cnt, 8);
+        assert_eq!(dogsketch.min, 1.0);
+        assert_eq!(dogsketch.max, 3.0);
+        assert_eq!(dogsketch.sum, 15.0);
+        assert_eq!(dogsketch.avg, 1.875);
+        assert_eq!(
+            dogsketch.k,
+            sketch
+                .bins()
+                .iter()
+                .map(|bin| i32::from(bin.k))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(dogsketch.n, vec![3, 3, 2]);
+    }
+
+    #[tokio::test]
+    async fn sketch_request_is_protobuf() {
+        let (sink, _cx) = load_sink::<DatadogConfig>(r#"api_key = "test""#).unwrap();
+        let uri = DatadogEndpoint::build_uri(&sink.get_endpoint()).unwrap();
+        let sink = DatadogSink {
+            config: sink,
+            endpoint_data: uri
+                .into_iter()
+                .map(|(endpoint, uri)| (endpoint, (uri, AtomicI64::new(0))))
+                .collect(),
+        };
+
+        let events = vec![Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Sketch {
+                sketch: DDSketch::from_samples(&vector_core::samples![1.0 => 1]),
+            },
+        )];
+        let req = sink
+            .build_request(PartitionInnerBuffer::new(events, DatadogEndpoint::Sketches))
+            .unwrap();
+
+        assert!(req.uri().to_string().ends_with("/api/beta/sketches"));
+        assert_eq!(req.headers()["Content-Type"], "application/x-protobuf");
+        let payload = SketchPayload::decode(&req.body()[..]).unwrap();
+        assert_eq!(payload.sketches[0].dogsketches[0].cnt, 1);
+    }
 }
@@ -549,6 +549,7 @@ impl HttpSink for ElasticSearchCommon {
         let log = match event {
             Event::Log(log) => Some(log),
             Event::Metric(metric) => self.metric_to_log.transform_one(metric),
+            Event::Trace(trace) => Some(trace.into_log()),
         };
         log.and_then(|log| self.encode_log(log.into()))
     }
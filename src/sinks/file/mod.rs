@@ -1,17 +1,20 @@
 use crate::expiring_hash_map::ExpiringHashMap;
 use crate::{
     buffers::Acker,
-    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
-    event::Event,
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    event::{Event, EventFinalizers, EventStatus},
     internal_events::FileOpen,
     internal_events::TemplateRenderingFailed,
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
-        StreamSink,
+        encoding::{Encoder, EncodingConfig, StandardEncoder, StandardEncodings},
+        Compression, StreamSink,
     },
     template::Template,
 };
-use async_compression::tokio::write::GzipEncoder;
+use async_compression::{
+    tokio::write::{GzipEncoder, ZlibEncoder, ZstdEncoder},
+    Level,
+};
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::{
@@ -20,7 +23,11 @@ use futures::{
     FutureExt,
 };
 use serde::{Deserialize, Serialize};
-use std::time::{Duration, Instant};
+use std::{
+    collections::HashMap,
+    io::Read,
+    time::{Duration, Instant},
+};
 
 use tokio::{
     fs::{self, File},
@@ -35,7 +42,7 @@ use std::convert::TryFrom;
 pub struct FileSinkConfig {
     pub path: Template,
     pub idle_timeout_secs: Option<u64>,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<StandardEncodings>,
     #[serde(
         default,
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
@@ -52,43 +59,46 @@ impl GenerateConfig for FileSinkConfig {
         toml::Value::try_from(Self {
             path: Template::try_from("/tmp/vector-%Y-%m-%d.log").unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             compression: Default::default(),
         })
         .unwrap()
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Ndjson,
-}
+/// Snappy input is collected up to the maximum size of a frame chunk before
+/// it is compressed and written out.
+const SNAPPY_CHUNK_SIZE: usize = 1 << 16;
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy)]
-#[serde(rename_all = "snake_case")]
-pub enum Compression {
-    Gzip,
-    None,
-}
-
-impl Default for Compression {
-    fn default() -> Self {
-        Compression::None
-    }
-}
+/// The maximum number of events written before the files are flushed and the
+/// events acknowledged.
+const MAX_PENDING_EVENTS: usize = 1024;
 
 enum OutFile {
     Regular(File),
     Gzip(GzipEncoder<File>),
+    Zlib(ZlibEncoder<File>),
+    Zstd(ZstdEncoder<File>),
+    Snappy { file: File, buffer: Vec<u8> },
 }
 
 impl OutFile {
     fn new(file: File, compression: Compression) -> Self {
         match compression {
             Compression::None => OutFile::Regular(file),
-            Compression::Gzip => OutFile::Gzip(GzipEncoder::new(file)),
+            Compression::Gzip(level) => {
+                OutFile::Gzip(GzipEncoder::with_quality(file, quality(level)))
+            }
+            Compression::Zlib(level) => {
+                OutFile::Zlib(ZlibEncoder::with_quality(file, quality(level)))
+            }
+            Compression::Zstd(level) => {
+                OutFile::Zstd(ZstdEncoder::with_quality(file, quality(level)))
+            }
+            Compression::Snappy => OutFile::Snappy {
+                file,
+                buffer: Vec::with_capacity(SNAPPY_CHUNK_SIZE),
+            },
         }
     }
 
@@ -96,6 +106,9 @@ impl OutFile {
         match self {
             OutFile::Regular(file) => file.sync_all().await,
             OutFile::Gzip(gzip) => gzip.get_mut().sync_all().await,
+            OutFile::Zlib(zlib) => zlib.get_mut().sync_all().await,
+            OutFile::Zstd(zstd) => zstd.get_mut().sync_all().await,
+            OutFile::Snappy { file, .. } => file.sync_all().await,
         }
     }
 
@@ -103,6 +116,26 @@ impl OutFile {
         match self {
             OutFile::Regular(file) => file.shutdown().await,
             OutFile::Gzip(gzip) => gzip.shutdown().await,
+            OutFile::Zlib(zlib) => zlib.shutdown().await,
+            OutFile::Zstd(zstd) => zstd.shutdown().await,
+            OutFile::Snappy { file, buffer } => {
+                write_snappy_frame(file, buffer).await?;
+                file.shutdown().await
+            }
+        }
+    }
+
+    /// Flushes the data buffered by the encoder, if any, to the file.
+    async fn flush(&mut self) -> Result<(), std::io::Error> {
+        match self {
+            OutFile::Regular(file) => file.flush().await,
+            OutFile::Gzip(gzip) => gzip.flush().await,
+            OutFile::Zlib(zlib) => zlib.flush().await,
+            OutFile::Zstd(zstd) => zstd.flush().await,
+            OutFile::Snappy { file, buffer } => {
+                write_snappy_frame(file, buffer).await?;
+                file.flush().await
+            }
         }
     }
 
@@ -110,6 +143,15 @@ impl OutFile {
         match self {
             OutFile::Regular(file) => file.write_all(src).await,
             OutFile::Gzip(gzip) => gzip.write_all(src).await,
+            OutFile::Zlib(zlib) => zlib.write_all(src).await,
+            OutFile::Zstd(zstd) => zstd.write_all(src).await,
+            OutFile::Snappy { file, buffer } => {
+                buffer.extend_from_slice(src);
+                if buffer.len() >= SNAPPY_CHUNK_SIZE {
+                    write_snappy_frame(file, buffer).await?;
+                }
+                Ok(())
+            }
         }
     }
 
@@ -121,6 +163,26 @@ impl OutFile {
     }
 }
 
+/// Levels are validated when the config is loaded, the encoder's default is
+/// used when none is set.
+fn quality(level: Option<usize>) -> Level {
+    level.map_or(Level::Default, |level| Level::Precise(level as u32))
+}
+
+/// Compresses `buffer` into a snappy framed stream and appends it to `file`.
+///
+/// Each call writes a complete stream, the framing format allows streams to
+/// be concatenated.
+async fn write_snappy_frame(file: &mut File, buffer: &mut Vec<u8>) -> std::io::Result<()> {
+    if buffer.is_empty() {
+        return Ok(());
+    }
+    let mut frame = Vec::new();
+    snap::read::FrameEncoder::new(&buffer[..]).read_to_end(&mut frame)?;
+    buffer.clear();
+    file.write_all(&frame).await
+}
+
 #[async_trait::async_trait]
 #[typetag::serde(name = "file")]
 impl SinkConfig for FileSinkConfig {
@@ -128,7 +190,7 @@ impl SinkConfig for FileSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let sink = FileSink::new(self, cx.acker());
+        let sink = FileSink::new(self, cx.acker())?;
         Ok((
             super::VectorSink::Stream(Box::new(sink)),
             future::ok(()).boxed(),
@@ -148,22 +210,26 @@ impl SinkConfig for FileSinkConfig {
 pub struct FileSink {
     acker: Acker,
     path: Template,
-    encoding: EncodingConfig<Encoding>,
+    encoder: StandardEncoder,
     idle_timeout: Duration,
     files: ExpiringHashMap<Bytes, OutFile>,
     compression: Compression,
+    /// The finalizers of the events written to each file since it was last
+    /// flushed.
+    pending: HashMap<Bytes, EventFinalizers>,
 }
 
 impl FileSink {
-    pub fn new(config: &FileSinkConfig, acker: Acker) -> Self {
-        Self {
+    pub fn new(config: &FileSinkConfig, acker: Acker) -> crate::Result<Self> {
+        Ok(Self {
             acker,
             path: config.path.clone(),
-            encoding: config.encoding.clone(),
+            encoder: StandardEncoder::new(config.encoding.clone())?,
             idle_timeout: Duration::from_secs(config.idle_timeout_secs.unwrap_or(30)),
             files: ExpiringHashMap::default(),
             compression: config.compression,
-        }
+            pending: HashMap::new(),
+        })
     }
 
     /// Uses pass the `event` to `self.path` template to obtain the file path
@@ -190,14 +256,21 @@ impl FileSink {
             .expect("unable to compute next deadline")
     }
 
-    async fn run(&mut self, mut input: BoxStream<'_, Event>) -> crate::Result<()> {
+    async fn run(&mut self, input: BoxStream<'_, Event>) -> crate::Result<()> {
+        // The events are only delivered once the files they were written to
+        // are flushed, which is done after writing all the ready events.
+        let mut input = input.ready_chunks(MAX_PENDING_EVENTS);
         loop {
             tokio::select! {
-                event = input.next() => {
-                    match event {
-                        Some(event) => {
-                            self.process_event(event).await;
-                            self.acker.ack(1);
+                events = input.next() => {
+                    match events {
+                        Some(events) => {
+                            let count = events.len();
+                            for event in events {
+                                self.process_event(event).await;
+                            }
+                            self.flush_pending().await;
+                            self.acker.ack(count);
                         },
                         None => {
                             // If we got `None` - terminate the processing.
@@ -249,7 +322,8 @@ impl FileSink {
         Ok(())
     }
 
-    async fn process_event(&mut self, event: Event) {
+    async fn process_event(&mut self, mut event: Event) {
+        let finalizers = event.metadata_mut().take_finalizers();
         let path = match self.partition_event(&event) {
             Some(path) => path,
             None => {
@@ -257,6 +331,7 @@ impl FileSink {
                 // file.
                 // This is already logged at `partition_event`, so
                 // here we just skip the event.
+                finalizers.update_status(EventStatus::Errored);
                 return;
             }
         };
@@ -276,6 +351,7 @@ impl FileSink {
                     // Maybe other events will work though! Just log
                     // the error and skip this event.
                     error!(message = "Unable to open the file.", path = ?path, %error);
+                    finalizers.update_status(EventStatus::Errored);
                     return;
                 }
             };
@@ -290,8 +366,30 @@ impl FileSink {
         };
 
         trace!(message = "Writing an event to file.", path = ?path);
-        if let Err(error) = write_event_to_file(file, event, &self.encoding).await {
-            error!(message = "Failed to write file.", path = ?path, %error);
+        match write_event_to_file(file, event, &self.encoder).await {
+            Ok(()) => self.pending.entry(path).or_default().merge(finalizers),
+            Err(error) => {
+                error!(message = "Failed to write file.", path = ?path, %error);
+                finalizers.update_status(EventStatus::Errored);
+            }
+        }
+    }
+
+    /// Flushes the files written to since the last flush, and updates the
+    /// status of their events.
+    async fn flush_pending(&mut self) {
+        for (path, finalizers) in std::mem::take(&mut self.pending) {
+            let status = match self.files.get_mut(&path) {
+                Some(file) => match file.flush().await {
+                    Ok(()) => EventStatus::Delivered,
+                    Err(error) => {
+                        error!(message = "Failed to flush file.", path = ?path, %error);
+                        EventStatus::Errored
+                    }
+                },
+                None => EventStatus::Errored,
+            };
+            finalizers.update_status(status);
         }
     }
 }
@@ -312,25 +410,12 @@ async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
         .await
 }
 
-pub fn encode_event(encoding: &EncodingConfig<Encoding>, mut event: Event) -> Vec<u8> {
-    encoding.apply_rules(&mut event);
-    let log = event.into_log();
-    match encoding.codec() {
-        Encoding::Ndjson => serde_json::to_vec(&log).expect("Unable to encode event as JSON."),
-        Encoding::Text => log
-            .get(log_schema().message_key())
-            .map(|v| v.to_string_lossy().into_bytes())
-            .unwrap_or_default(),
-    }
-}
-
 async fn write_event_to_file(
     file: &mut OutFile,
     event: Event,
-    encoding: &EncodingConfig<Encoding>,
+    encoder: &StandardEncoder,
 ) -> Result<(), std::io::Error> {
-    let mut buf = encode_event(encoding, event);
-    buf.push(b'\n');
+    let buf = encoder.encode_input_to_vec(event)?;
     file.write_all(&buf[..]).await
 }
 
@@ -345,9 +430,13 @@ impl StreamSink for FileSink {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_util::{
-        lines_from_file, lines_from_gzip_file, random_events_with_stream, random_lines_with_stream,
-        temp_dir, temp_file, trace_init,
+    use crate::{
+        config::log_schema,
+        event::{BatchNotifier, BatchStatus},
+        test_util::{
+            lines_from_file, lines_from_gzip_file, lines_from_zst_file, random_events_with_stream,
+            random_lines_with_stream, temp_dir, temp_file, trace_init,
+        },
     };
     use futures::{stream, SinkExt};
     use std::convert::TryInto;
@@ -366,11 +455,11 @@ mod tests {
         let config = FileSinkConfig {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             compression: Compression::None,
         };
 
-        let mut sink = FileSink::new(&config, Acker::Null);
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
         let (input, _events) = random_lines_with_stream(100, 64, None);
 
         let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
@@ -391,11 +480,11 @@ mod tests {
         let config = FileSinkConfig {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
-            compression: Compression::Gzip,
+            encoding: StandardEncodings::Text.into(),
+            compression: Compression::gzip_default(),
         };
 
-        let mut sink = FileSink::new(&config, Acker::Null);
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
         let (input, _) = random_lines_with_stream(100, 64, None);
 
         let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
@@ -407,6 +496,57 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn single_partition_zstd() {
+        trace_init();
+
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding: StandardEncodings::Text.into(),
+            compression: Compression::Zstd(Some(10)),
+        };
+
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
+        let (input, _) = random_lines_with_stream(100, 64, None);
+
+        let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
+        sink.run(events).await.unwrap();
+
+        let output = lines_from_zst_file(template);
+        assert_eq!(input, output);
+    }
+
+    #[tokio::test]
+    async fn single_partition_snappy() {
+        trace_init();
+
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding: StandardEncodings::Text.into(),
+            compression: Compression::Snappy,
+        };
+
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
+        // Enough input to span several snappy streams.
+        let (input, _) = random_lines_with_stream(100, 2048, None);
+
+        let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
+        sink.run(events).await.unwrap();
+
+        let mut output = String::new();
+        snap::read::FrameDecoder::new(std::fs::File::open(template).unwrap())
+            .read_to_string(&mut output)
+            .unwrap();
+        let output = output.lines().map(|s| s.to_owned()).collect::<Vec<_>>();
+        assert_eq!(input, output);
+    }
+
     #[tokio::test]
     async fn many_partitions() {
         trace_init();
@@ -421,11 +561,11 @@ mod tests {
         let config = FileSinkConfig {
             path: template.try_into().unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             compression: Compression::None,
         };
 
-        let mut sink = FileSink::new(&config, Acker::Null);
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
 
         let (mut input, _events) = random_events_with_stream(32, 8, None);
         input[0].as_mut_log().insert("date", "2019-26-07");
@@ -491,6 +631,43 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn acknowledges_written_events() {
+        trace_init();
+
+        let directory = temp_dir();
+        let mut template = directory.to_string_lossy().to_string();
+        template.push_str("/{{level}}.log.gz");
+
+        let config = FileSinkConfig {
+            path: template.try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding: StandardEncodings::Text.into(),
+            compression: Compression::gzip_default(),
+        };
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
+
+        let (written, written_receiver) = BatchNotifier::new_with_receiver();
+        let (unrendered, unrendered_receiver) = BatchNotifier::new_with_receiver();
+        let mut event = Event::from("written");
+        event.as_mut_log().insert("level", "info");
+        let events = vec![
+            event.with_batch_notifier(&written),
+            Event::from("no level").with_batch_notifier(&unrendered),
+        ];
+        drop(written);
+        drop(unrendered);
+
+        sink.run(Box::pin(stream::iter(events))).await.unwrap();
+
+        assert_eq!(written_receiver.await, BatchStatus::Delivered);
+        assert_eq!(unrendered_receiver.await, BatchStatus::Errored);
+        assert_eq!(
+            lines_from_gzip_file(directory.join("info.log.gz")),
+            vec!["written".to_owned()]
+        );
+    }
+
     #[tokio::test]
     async fn reopening() {
         use pretty_assertions::assert_eq;
@@ -502,11 +679,11 @@ mod tests {
         let config = FileSinkConfig {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: Some(1),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             compression: Compression::None,
         };
 
-        let mut sink = FileSink::new(&config, Acker::Null);
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
         let (mut input, _events) = random_lines_with_stream(10, 64, None);
 
         let (mut tx, rx) = futures::channel::mpsc::channel(0);
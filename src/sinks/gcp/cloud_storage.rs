@@ -8,10 +8,13 @@ use crate::{
     sinks::{
         util::{
             batch::{BatchConfig, BatchSettings},
-            encoding::{EncodingConfig, EncodingConfiguration},
+            encoding::{
+                Encoder, EncodingConfig, EncodingConfiguration, ParquetEncoder, ParquetRow,
+                StandardEncoder, StandardEncodings,
+            },
             retries::{RetryAction, RetryLogic},
-            Buffer, Compression, Concurrency, EncodedEvent, PartitionBatchSink, PartitionBuffer,
-            PartitionInnerBuffer, ServiceBuilderExt, TowerRequestConfig,
+            Buffer, Compression, Concurrency, EncodedEvent, ParquetBuffer, PartitionBatchSink,
+            PartitionBuffer, PartitionInnerBuffer, ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
@@ -63,7 +66,7 @@ pub struct GcsSinkConfig {
     filename_time_format: Option<String>,
     filename_append_uuid: Option<bool>,
     filename_extension: Option<String>,
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
     #[serde(default)]
     compression: Compression,
     #[serde(default)]
@@ -76,7 +79,7 @@ pub struct GcsSinkConfig {
 }
 
 #[cfg(test)]
-fn default_config(e: Encoding) -> GcsSinkConfig {
+fn default_config(e: StandardEncodings) -> GcsSinkConfig {
     GcsSinkConfig {
         bucket: Default::default(),
         acl: Default::default(),
@@ -127,22 +130,6 @@ lazy_static! {
     };
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy)]
-#[serde(rename_all = "snake_case")]
-enum Encoding {
-    Text,
-    Ndjson,
-}
-
-impl Encoding {
-    fn content_type(self) -> &'static str {
-        match self {
-            Self::Text => "text/plain",
-            Self::Ndjson => "application/x-ndjson",
-        }
-    }
-}
-
 inventory::submit! {
     SinkDescription::new::<GcsSinkConfig>(NAME)
 }
@@ -210,7 +197,6 @@ impl GcsSink {
 
     fn service(self, config: &GcsSinkConfig, cx: &SinkContext) -> crate::Result<VectorSink> {
         let request = config.request.unwrap_with(&REQUEST_DEFAULTS);
-        let encoding = config.encoding.clone();
 
         let batch = BatchSettings::default()
             .bytes(bytesize::mib(10u64))
@@ -227,15 +213,32 @@ impl GcsSink {
             .settings(request, GcsRetryLogic)
             .service(self);
 
-        let buffer = PartitionBuffer::new(Buffer::new(batch.size, config.compression));
-
-        let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
-            .sink_map_err(|error| error!(message = "Fatal gcp_cloud_storage error.", %error))
-            .with_flat_map(move |event| {
-                stream::iter(encode_event(event, &key_prefix, &encoding)).map(Ok)
-            });
-
-        Ok(VectorSink::Sink(Box::new(sink)))
+        if config.encoding.codec == StandardEncodings::Parquet {
+            let encoder = ParquetEncoder::new(config.encoding.clone(), config.compression)?;
+            let buffer = PartitionBuffer::new(ParquetBuffer::new(
+                batch.into::<ParquetBuffer>().size,
+                encoder.clone(),
+            ));
+
+            let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
+                .sink_map_err(|error| error!(message = "Fatal gcp_cloud_storage error.", %error))
+                .with_flat_map(move |event| {
+                    stream::iter(encode_parquet_row(event, &key_prefix, &encoder)).map(Ok)
+                });
+
+            Ok(VectorSink::Sink(Box::new(sink)))
+        } else {
+            let encoder = StandardEncoder::new(config.encoding.clone())?;
+            let buffer = PartitionBuffer::new(Buffer::new(batch.size, config.compression));
+
+            let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
+                .sink_map_err(|error| error!(message = "Fatal gcp_cloud_storage error.", %error))
+                .with_flat_map(move |event| {
+                    stream::iter(encode_event(event, &key_prefix, &encoder)).map(Ok)
+                });
+
+            Ok(VectorSink::Sink(Box::new(sink)))
+        }
     }
 
     async fn healthcheck(self) -> crate::Result<()> {
@@ -355,9 +358,12 @@ impl RequestSettings {
             .acl
             .map(|acl| HeaderValue::from_str(&to_string(acl)).unwrap());
         let content_type = HeaderValue::from_str(config.encoding.codec().content_type()).unwrap();
+        let parquet = config.encoding.codec == StandardEncodings::Parquet;
+        // Parquet pages are compressed within the file, so the object itself is not.
         let content_encoding = config
             .compression
             .content_encoding()
+            .filter(|_| !parquet)
             .map(|ce| HeaderValue::from_str(&to_string(ce)).unwrap());
         let storage_class = config.storage_class.unwrap_or_default();
         let storage_class = HeaderValue::from_str(&to_string(storage_class)).unwrap();
@@ -371,10 +377,13 @@ impl RequestSettings {
                     .collect::<Result<Vec<_>, _>>()
             })
             .unwrap_or_else(|| Ok(vec![]))?;
-        let extension = config
-            .filename_extension
-            .clone()
-            .unwrap_or_else(|| config.compression.extension().into());
+        let extension = config.filename_extension.clone().unwrap_or_else(|| {
+            if parquet {
+                "parquet".into()
+            } else {
+                config.compression.extension().into()
+            }
+        });
         let time_format = config
             .filename_time_format
             .clone()
@@ -402,9 +411,9 @@ fn make_header((name, value): (&String, &String)) -> crate::Result<(HeaderName,
 }
 
 fn encode_event(
-    mut event: Event,
+    event: Event,
     key_prefix: &Template,
-    encoding: &EncodingConfig<Encoding>,
+    encoder: &StandardEncoder,
 ) -> Option<EncodedEvent<PartitionInnerBuffer<Vec<u8>, Bytes>>> {
     let key = key_prefix
         .render_string(&event)
@@ -416,29 +425,40 @@ fn encode_event(
             });
         })
         .ok()?;
-    encoding.apply_rules(&mut event);
-    let log = event.into_log();
-    let bytes = match encoding.codec() {
-        Encoding::Ndjson => serde_json::to_vec(&log)
-            .map(|mut b| {
-                b.push(b'\n');
-                b
-            })
-            .expect("Failed to encode event as json, this is a bug!"),
-        Encoding::Text => {
-            let mut bytes = log
-                .get(crate::config::log_schema().message_key())
-                .map(|v| v.as_bytes().to_vec())
-                .unwrap_or_default();
-            bytes.push(b'\n');
-            bytes
-        }
-    };
+    let metadata = event.metadata().clone();
+    let bytes = encoder
+        .encode_input_to_vec(event)
+        .map_err(|error| error!(message = "Unable to encode.", %error))
+        .ok()?;
 
-    Some(EncodedEvent::new(PartitionInnerBuffer::new(
-        bytes,
-        key.into(),
-    )))
+    Some(EncodedEvent {
+        item: PartitionInnerBuffer::new(bytes, key.into()),
+        metadata: Some(metadata),
+    })
+}
+
+fn encode_parquet_row(
+    event: Event,
+    key_prefix: &Template,
+    encoder: &ParquetEncoder,
+) -> Option<EncodedEvent<PartitionInnerBuffer<ParquetRow, Bytes>>> {
+    let key = key_prefix
+        .render_string(&event)
+        .map_err(|error| {
+            emit!(TemplateRenderingFailed {
+                error,
+                field: Some("key_prefix"),
+                drop_event: true,
+            });
+        })
+        .ok()?;
+    let metadata = event.metadata().clone();
+    let row = encoder.encode_row(event);
+
+    Some(EncodedEvent {
+        item: PartitionInnerBuffer::new(row, key.into()),
+        metadata: Some(metadata),
+    })
 }
 
 #[derive(Clone)]
@@ -481,12 +501,8 @@ mod tests {
     fn gcs_encode_event_text() {
         let message = "hello world".to_string();
         let batch_time_format = Template::try_from("date=%F").unwrap();
-        let encoded = encode_event(
-            message.clone().into(),
-            &batch_time_format,
-            &Encoding::Text.into(),
-        )
-        .unwrap();
+        let encoder = StandardEncoder::new(StandardEncodings::Text.into()).unwrap();
+        let encoded = encode_event(message.clone().into(), &batch_time_format, &encoder).unwrap();
 
         let encoded_message = message + "\n";
         let (bytes, _) = encoded.item.into_parts();
@@ -500,7 +516,8 @@ mod tests {
         event.as_mut_log().insert("key", "value");
 
         let batch_time_format = Template::try_from("date=%F").unwrap();
-        let encoded = encode_event(event, &batch_time_format, &Encoding::Ndjson.into()).unwrap();
+        let encoder = StandardEncoder::new(StandardEncodings::Ndjson.into()).unwrap();
+        let encoded = encode_event(event, &batch_time_format, &encoder).unwrap();
 
         let (bytes, _) = encoded.item.into_parts();
         let map: HashMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
@@ -521,7 +538,8 @@ mod tests {
         event.as_mut_log().insert("key", "value");
 
         let key_format = Template::try_from("key: {{ key }}").unwrap();
-        let encoded = encode_event(event, &key_format, &Encoding::Text.into()).unwrap();
+        let encoder = StandardEncoder::new(StandardEncodings::Text.into()).unwrap();
+        let encoded = encode_event(event, &key_format, &encoder).unwrap();
 
         let (_, key) = encoded.item.into_parts();
         assert_eq!(key, "key: value");
@@ -538,7 +556,7 @@ mod tests {
             filename_extension: extension.map(Into::into),
             filename_append_uuid: Some(uuid),
             compression,
-            ..default_config(Encoding::Ndjson)
+            ..default_config(StandardEncodings::Ndjson)
         })
         .expect("Could not create request settings")
     }
@@ -566,9 +584,34 @@ mod tests {
         assert_eq!(req.key, "key/date.log.gz".to_string());
 
         let req = RequestWrapper::new(
-            buf,
+            buf.clone(),
             request_settings(None, true, Compression::gzip_default()),
         );
         assert_ne!(req.key, "key/date.log.gz".to_string());
+
+        let settings = request_settings(None, false, Compression::Zstd(None));
+        assert_eq!(
+            settings.content_encoding,
+            Some(HeaderValue::from_static("zstd"))
+        );
+        let req = RequestWrapper::new(buf, settings);
+        assert_eq!(req.key, "key/date.log.zst".to_string());
+    }
+
+    #[test]
+    fn gcs_build_request_parquet() {
+        let settings = RequestSettings::new(&GcsSinkConfig {
+            key_prefix: Some("key/".into()),
+            filename_time_format: Some("date".into()),
+            filename_append_uuid: Some(false),
+            compression: Compression::gzip_default(),
+            ..default_config(StandardEncodings::Parquet)
+        })
+        .expect("Could not create request settings");
+        assert!(settings.content_encoding.is_none());
+
+        let buf = PartitionInnerBuffer::new(vec![0u8; 10], Bytes::from("key/"));
+        let req = RequestWrapper::new(buf, settings);
+        assert_eq!(req.key, "key/date.parquet".to_string());
     }
 }
@@ -105,7 +105,8 @@ impl HttpSink for HttpEventSink {
     type Output = Vec<Metric>;
 
     fn encode_event(&self, event: Event) -> Option<EncodedEvent<Self::Input>> {
-        let metric = event.into_metric();
+        let mut metric = event.into_metric();
+        let metadata = std::mem::take(metric.metadata_mut());
 
         match metric.value() {
             &MetricValue::Counter { .. } => Some(metric),
@@ -115,7 +116,10 @@ impl HttpSink for HttpEventSink {
                 None
             }
         }
-        .map(EncodedEvent::new)
+        .map(|item| EncodedEvent {
+            item,
+            metadata: Some(metadata),
+        })
     }
 
     async fn build_request(
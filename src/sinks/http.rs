@@ -4,15 +4,13 @@ use crate::{
     http::{Auth, HttpClient, MaybeAuth},
     internal_events::{HttpEventEncoded, HttpEventMissingMessage},
     sinks::util::{
-        buffer::compression::GZIP_DEFAULT,
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{Encoder, EncodingConfig, StandardEncoder, StandardEncodings},
         http::{BatchedHttpSink, HttpSink, RequestConfig},
         BatchConfig, BatchSettings, Buffer, Compression, Concurrency, EncodedEvent,
         TowerRequestConfig, UriSerde,
     },
     tls::{TlsOptions, TlsSettings},
 };
-use flate2::write::GzEncoder;
 use futures::{future, FutureExt, SinkExt};
 use http::{
     header::{self, HeaderName, HeaderValue},
@@ -23,7 +21,6 @@ use indexmap::IndexMap;
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
-use std::io::Write;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -49,7 +46,7 @@ pub struct HttpSinkConfig {
     pub headers: Option<IndexMap<String, String>>,
     #[serde(default)]
     pub compression: Compression,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<StandardEncodings>,
     #[serde(default)]
     pub batch: BatchConfig,
     #[serde(default)]
@@ -58,7 +55,7 @@ pub struct HttpSinkConfig {
 }
 
 #[cfg(test)]
-fn default_config(e: Encoding) -> HttpSinkConfig {
+fn default_config(e: StandardEncodings) -> HttpSinkConfig {
     HttpSinkConfig {
         uri: Default::default(),
         method: Default::default(),
@@ -96,14 +93,6 @@ pub enum HttpMethod {
     Patch,
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Ndjson,
-    Json,
-}
-
 inventory::submit! {
     SinkDescription::new::<HttpSinkConfig>("http")
 }
@@ -151,7 +140,7 @@ impl SinkConfig for HttpSinkConfig {
         let request = config.request.tower.unwrap_with(&REQUEST_DEFAULTS);
 
         let sink = BatchedHttpSink::new(
-            config,
+            HttpEventSink::new(config)?,
             Buffer::new(batch.size, Compression::None),
             request,
             batch.timeout,
@@ -174,53 +163,57 @@ impl SinkConfig for HttpSinkConfig {
     }
 }
 
+#[derive(Clone, Debug)]
+struct HttpEventSink {
+    config: HttpSinkConfig,
+    encoder: StandardEncoder,
+}
+
+impl HttpEventSink {
+    fn new(config: HttpSinkConfig) -> crate::Result<Self> {
+        let encoder = StandardEncoder::new(config.encoding.clone())?;
+        Ok(Self { config, encoder })
+    }
+}
+
 #[async_trait::async_trait]
-impl HttpSink for HttpSinkConfig {
+impl HttpSink for HttpEventSink {
     type Input = Vec<u8>;
     type Output = Vec<u8>;
 
-    fn encode_event(&self, mut event: Event) -> Option<EncodedEvent<Self::Input>> {
-        self.encoding.apply_rules(&mut event);
-        let event = event.into_log();
-
-        let body = match &self.encoding.codec() {
-            Encoding::Text => {
-                if let Some(v) = event.get(crate::config::log_schema().message_key()) {
-                    let mut b = v.to_string_lossy().into_bytes();
-                    b.push(b'\n');
-                    b
-                } else {
-                    emit!(HttpEventMissingMessage);
-                    return None;
-                }
-            }
-
-            Encoding::Ndjson => {
-                let mut b = serde_json::to_vec(&event)
-                    .map_err(|error| panic!("Unable to encode into JSON: {}", error))
-                    .ok()?;
-                b.push(b'\n');
-                b
-            }
+    fn encode_event(&self, event: Event) -> Option<EncodedEvent<Self::Input>> {
+        let log = event.as_log();
+        if self.encoder.codec() == StandardEncodings::Text
+            && log.get(crate::config::log_schema().message_key()).is_none()
+        {
+            emit!(HttpEventMissingMessage);
+            return None;
+        }
 
-            Encoding::Json => {
-                let mut b = serde_json::to_vec(&event)
-                    .map_err(|error| panic!("Unable to encode into JSON: {}", error))
-                    .ok()?;
+        let metadata = event.metadata().clone();
+        let body = match self.encoder.codec() {
+            // The events are joined into a JSON array when the request is built.
+            StandardEncodings::Json => self.encoder.encode_event(event).map(|mut b| {
                 b.push(b',');
                 b
-            }
-        };
+            }),
+            _ => self.encoder.encode_input_to_vec(event),
+        }
+        .map_err(|error| error!(message = "Unable to encode.", %error))
+        .ok()?;
 
         emit!(HttpEventEncoded {
             byte_size: body.len(),
         });
 
-        Some(EncodedEvent::new(body).with_metadata(event))
+        Some(EncodedEvent {
+            item: body,
+            metadata: Some(metadata),
+        })
     }
 
     async fn build_request(&self, mut body: Self::Output) -> crate::Result<http::Request<Vec<u8>>> {
-        let method = match &self.method.clone().unwrap_or(HttpMethod::Post) {
+        let method = match &self.config.method.clone().unwrap_or(HttpMethod::Post) {
             HttpMethod::Get => Method::GET,
             HttpMethod::Head => Method::HEAD,
             HttpMethod::Post => Method::POST,
@@ -230,43 +223,32 @@ impl HttpSink for HttpSinkConfig {
             HttpMethod::Trace => Method::TRACE,
             HttpMethod::Patch => Method::PATCH,
         };
-        let uri: Uri = self.uri.uri.clone();
-
-        let ct = match self.encoding.codec() {
-            Encoding::Text => "text/plain",
-            Encoding::Ndjson => "application/x-ndjson",
-            Encoding::Json => {
-                body.insert(0, b'[');
-                body.pop(); // remove trailing comma from last record
-                body.push(b']');
-                "application/json"
-            }
-        };
+        let uri: Uri = self.config.uri.uri.clone();
+
+        if self.encoder.codec() == StandardEncodings::Json {
+            body.insert(0, b'[');
+            body.pop(); // remove trailing comma from last record
+            body.push(b']');
+        }
+        let ct = self.encoder.codec().content_type();
 
         let mut builder = Request::builder()
             .method(method)
             .uri(uri)
             .header("Content-Type", ct);
 
-        match self.compression {
-            Compression::Gzip(level) => {
-                builder = builder.header("Content-Encoding", "gzip");
-
-                let level = level.unwrap_or(GZIP_DEFAULT) as u32;
-                let mut w = GzEncoder::new(Vec::new(), flate2::Compression::new(level));
-                w.write_all(&body).expect("Writing to Vec can't fail");
-                body = w.finish().expect("Writing to Vec can't fail");
-            }
-            Compression::None => {}
+        if let Some(content_encoding) = self.config.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", content_encoding);
+            body = self.config.compression.compress(body)?;
         }
 
-        for (header, value) in self.request.headers.iter() {
+        for (header, value) in self.config.request.headers.iter() {
             builder = builder.header(header.as_str(), value.as_str());
         }
 
         let mut request = builder.body(body).unwrap();
 
-        if let Some(auth) = &self.auth {
+        if let Some(auth) = &self.config.auth {
             auth.apply(&mut request);
         }
 
@@ -337,24 +319,26 @@ mod tests {
 
     #[test]
     fn http_encode_event_text() {
-        let encoding = EncodingConfig::from(Encoding::Text);
+        let encoding = EncodingConfig::from(StandardEncodings::Text);
         let event = Event::from("hello world");
 
-        let mut config = default_config(Encoding::Text);
+        let mut config = default_config(StandardEncodings::Text);
         config.encoding = encoding;
-        let bytes = config.encode_event(event).unwrap().item;
+        let sink = HttpEventSink::new(config).unwrap();
+        let bytes = sink.encode_event(event).unwrap().item;
 
         assert_eq!(bytes, Vec::from("hello world\n"));
     }
 
     #[test]
     fn http_encode_event_json() {
-        let encoding = EncodingConfig::from(Encoding::Ndjson);
+        let encoding = EncodingConfig::from(StandardEncodings::Ndjson);
         let event = Event::from("hello world");
 
-        let mut config = default_config(Encoding::Json);
+        let mut config = default_config(StandardEncodings::Json);
         config.encoding = encoding;
-        let bytes = config.encode_event(event).unwrap().item;
+        let sink = HttpEventSink::new(config).unwrap();
+        let bytes = sink.encode_event(event).unwrap().item;
 
         #[derive(Deserialize, Debug)]
         #[serde(deny_unknown_fields)]
@@ -646,6 +630,61 @@ mod tests {
         assert_eq!(input_lines, output_lines);
     }
 
+    #[tokio::test]
+    async fn zstd_compression() {
+        let num_lines = 1000;
+
+        let in_addr = next_addr();
+
+        let config = r#"
+        uri = "http://$IN_ADDR/frames"
+        compression = "zstd"
+        encoding = "ndjson"
+    "#
+        .replace("$IN_ADDR", &format!("{}", in_addr));
+        let config: HttpSinkConfig = toml::from_str(&config).unwrap();
+
+        let cx = SinkContext::new_test();
+
+        let (sink, _) = config.build(cx).await.unwrap();
+        let (rx, trigger, server) = build_test_server(in_addr);
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input_lines, events) = random_lines_with_stream(100, num_lines, Some(batch));
+        let pump = sink.run(events);
+
+        tokio::spawn(server);
+
+        pump.await.unwrap();
+        drop(trigger);
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let output_lines = rx
+            .flat_map(|(parts, body)| {
+                assert_eq!(
+                    Some("zstd"),
+                    parts
+                        .headers
+                        .get("Content-Encoding")
+                        .map(|value| value.to_str().unwrap())
+                );
+
+                let decoder = zstd::stream::read::Decoder::new(body.reader()).unwrap();
+                stream::iter(BufReader::new(decoder).lines())
+            })
+            .map(Result::unwrap)
+            .map(|line| {
+                let val: serde_json::Value = serde_json::from_str(&line).unwrap();
+                val.get("message").unwrap().as_str().unwrap().to_owned()
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(num_lines, output_lines.len());
+        assert_eq!(input_lines, output_lines);
+    }
+
     async fn get_received(
         rx: mpsc::Receiver<(Parts, Bytes)>,
         assert_parts: impl Fn(Parts),
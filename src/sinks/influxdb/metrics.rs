@@ -5,6 +5,7 @@ use crate::{
         Event,
     },
     http::HttpClient,
+    metrics::DDSketch,
     sinks::{
         influxdb::{
             encode_timestamp, healthcheck, influx_line_protocol, influxdb_settings, Field,
@@ -151,11 +152,13 @@ impl InfluxDbSvc {
                 sink::StdServiceLogic::default(),
             )
             .with_flat_map(move |event: Event| {
-                stream::iter(
-                    normalizer
-                        .apply(event)
-                        .map(|metric| Ok(EncodedEvent::new(metric))),
-                )
+                stream::iter(normalizer.apply(event).map(|mut metric| {
+                    let metadata = std::mem::take(metric.metadata_mut());
+                    Ok(EncodedEvent {
+                        item: metric,
+                        metadata: Some(metadata),
+                    })
+                }))
             })
             .sink_map_err(|error| error!(message = "Fatal influxdb sink error.", %error));
 
@@ -325,9 +328,30 @@ fn get_type_and_fields(
             let fields = encode_distribution(samples, quantiles);
             ("distribution", fields)
         }
+        MetricValue::Sketch { sketch } => ("sketch", encode_sketch(sketch, quantiles)),
     }
 }
 
+fn encode_sketch(sketch: &DDSketch, quantiles: &[f64]) -> Option<HashMap<String, Field>> {
+    let fields: HashMap<String, Field> = vec![
+        ("min".to_owned(), Field::Float(sketch.min()?)),
+        ("max".to_owned(), Field::Float(sketch.max()?)),
+        ("median".to_owned(), Field::Float(sketch.quantile(0.5)?)),
+        ("avg".to_owned(), Field::Float(sketch.avg())),
+        ("sum".to_owned(), Field::Float(sketch.sum())),
+        ("count".to_owned(), Field::Float(sketch.count() as f64)),
+    ]
+    .into_iter()
+    .chain(quantiles.iter().filter_map(|&p| {
+        sketch
+            .quantile(p)
+            .map(|val| (format!("quantile_{:.2}", p), Field::Float(val)))
+    }))
+    .collect();
+
+    Some(fields)
+}
+
 fn encode_distribution(samples: &[Sample], quantiles: &[f64]) -> Option<HashMap<String, Field>> {
     let statistic = DistributionStatistic::from_samples(samples, quantiles)?;
 
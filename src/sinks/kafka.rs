@@ -5,7 +5,7 @@ use crate::{
     kafka::{KafkaAuthConfig, KafkaCompression, KafkaStatisticsContext},
     serde::to_string,
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{EncodingConfig, StandardEncoder, StandardEncodings},
         BatchConfig,
     },
     template::{Template, TemplateParseError},
@@ -48,7 +48,7 @@ pub struct KafkaSinkConfig {
     bootstrap_servers: String,
     topic: String,
     key_field: Option<String>,
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
     /// These batching options will **not** override librdkafka_options values.
     #[serde(default)]
     batch: BatchConfig,
@@ -72,18 +72,11 @@ fn default_message_timeout_ms() -> u64 {
     300000 // default in librdkafka
 }
 
-#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
-}
-
 pub struct KafkaSink {
     producer: Arc<FutureProducer<KafkaStatisticsContext>>,
     topic: Template,
     key_field: Option<String>,
-    encoding: EncodingConfig<Encoding>,
+    encoder: StandardEncoder,
     delivery_fut: FuturesUnordered<
         BoxFuture<'static, (usize, Result<DeliveryFuture, KafkaError>, EventMetadata)>,
     >,
@@ -243,7 +236,7 @@ impl KafkaSink {
             producer: Arc::new(producer),
             topic: Template::try_from(config.topic).context(TopicTemplate)?,
             key_field: config.key_field,
-            encoding: config.encoding,
+            encoder: StandardEncoder::new(config.encoding)?,
             delivery_fut: FuturesUnordered::new(),
             in_flight: FuturesUnordered::new(),
             acker,
@@ -305,9 +298,16 @@ impl Sink<Event> for KafkaSink {
                 .and_then(|v| v.as_timestamp())
                 .copied(),
             Event::Metric(metric) => metric.timestamp(),
+            Event::Trace(trace) => trace
+                .get(log_schema().timestamp_key())
+                .and_then(|v| v.as_timestamp())
+                .copied(),
         }
         .map(|ts| ts.timestamp_millis());
-        let (key, body, metadata) = encode_event(item, &self.key_field, &self.encoding);
+        let (key, body, metadata) =
+            encode_event(item, &self.key_field, &self.encoder).map_err(|error| {
+                error!(message = "Unable to encode event.", %error);
+            })?;
 
         let seqno = self.seq_head;
         self.seq_head += 1;
@@ -423,10 +423,10 @@ async fn healthcheck(config: KafkaSinkConfig) -> crate::Result<()> {
 }
 
 fn encode_event(
-    mut event: Event,
+    event: Event,
     key_field: &Option<String>,
-    encoding: &EncodingConfig<Encoding>,
-) -> (Vec<u8>, Vec<u8>, EventMetadata) {
+    encoder: &StandardEncoder,
+) -> std::io::Result<(Vec<u8>, Vec<u8>, EventMetadata)> {
     let key = key_field
         .as_ref()
         .and_then(|f| match &event {
@@ -435,27 +435,14 @@ fn encode_event(
                 .tags()
                 .and_then(|tags| tags.get(f))
                 .map(|value| value.clone().into_bytes()),
+            Event::Trace(trace) => trace.get(f).map(|value| value.as_bytes().to_vec()),
         })
         .unwrap_or_default();
 
-    encoding.apply_rules(&mut event);
-
-    let body = match &event {
-        Event::Log(log) => match encoding.codec() {
-            Encoding::Json => serde_json::to_vec(&log).unwrap(),
-            Encoding::Text => log
-                .get(log_schema().message_key())
-                .map(|v| v.as_bytes().to_vec())
-                .unwrap_or_default(),
-        },
-        Event::Metric(metric) => match encoding.codec() {
-            Encoding::Json => serde_json::to_vec(&metric).unwrap(),
-            Encoding::Text => metric.to_string().into_bytes(),
-        },
-    };
+    let metadata = event.metadata().clone();
+    let body = encoder.encode_event(event)?;
 
-    let metadata = event.into_metadata();
-    (key, body, metadata)
+    Ok((key, body, metadata))
 }
 
 #[cfg(test)]
@@ -477,8 +464,9 @@ mod tests {
         let (key_bytes, bytes, _metadata) = encode_event(
             message.clone().into(),
             &None,
-            &EncodingConfig::from(Encoding::Text),
-        );
+            &StandardEncoder::new(StandardEncodings::Text.into()).unwrap(),
+        )
+        .unwrap();
 
         assert_eq!(&key_bytes[..], key.as_bytes());
         assert_eq!(&bytes[..], message.as_bytes());
@@ -495,8 +483,9 @@ mod tests {
         let (key, bytes, _metadata) = encode_event(
             event,
             &Some("key".into()),
-            &EncodingConfig::from(Encoding::Json),
-        );
+            &StandardEncoder::new(StandardEncodings::Json.into()).unwrap(),
+        )
+        .unwrap();
 
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
 
@@ -516,8 +505,9 @@ mod tests {
         let (key_bytes, bytes, _metadata) = encode_event(
             metric.clone().into(),
             &None,
-            &EncodingConfig::from(Encoding::Text),
-        );
+            &StandardEncoder::new(StandardEncodings::Text.into()).unwrap(),
+        )
+        .unwrap();
 
         assert_eq!("", String::from_utf8_lossy(&key_bytes));
         assert_eq!(metric.to_string(), String::from_utf8_lossy(&bytes));
@@ -533,8 +523,9 @@ mod tests {
         let (key_bytes, bytes, _metadata) = encode_event(
             metric.clone().into(),
             &None,
-            &EncodingConfig::from(Encoding::Json),
-        );
+            &StandardEncoder::new(StandardEncodings::Json.into()).unwrap(),
+        )
+        .unwrap();
 
         assert_eq!("", String::from_utf8_lossy(&key_bytes));
         assert_eq!(
@@ -552,14 +543,18 @@ mod tests {
         let (key, bytes, _metadata) = encode_event(
             event,
             &Some("key".into()),
-            &EncodingConfig {
-                codec: Encoding::Json,
+            &StandardEncoder::new(EncodingConfig {
+                codec: StandardEncodings::Json,
                 schema: None,
                 only_fields: None,
                 except_fields: Some(vec!["key".into()]),
                 timestamp_format: None,
-            },
-        );
+                framing: None,
+                parquet: None,
+            })
+            .unwrap(),
+        )
+        .unwrap();
 
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
 
@@ -595,7 +590,7 @@ mod integration_test {
             bootstrap_servers: "localhost:9091".into(),
             topic: topic.clone(),
             key_field: None,
-            encoding: EncodingConfig::from(Encoding::Text),
+            encoding: EncodingConfig::from(StandardEncodings::Text),
             batch: BatchConfig::default(),
             compression: KafkaCompression::None,
             auth: KafkaAuthConfig::default(),
@@ -646,7 +641,7 @@ mod integration_test {
             bootstrap_servers: "localhost:9091".to_string(),
             topic: format!("{}-%Y%m%d", topic),
             compression: KafkaCompression::None,
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             key_field: None,
             auth: KafkaAuthConfig {
                 sasl: None,
@@ -800,7 +795,7 @@ mod integration_test {
             bootstrap_servers: server.to_string(),
             topic: format!("{}-%Y%m%d", topic),
             key_field: None,
-            encoding: EncodingConfig::from(Encoding::Text),
+            encoding: EncodingConfig::from(StandardEncodings::Text),
             batch: BatchConfig::default(),
             compression,
             auth: kafka_auth.clone(),
@@ -2,10 +2,10 @@ use crate::{
     buffers::Acker,
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     emit,
-    event::Event,
+    event::{Event, EventStatus},
     internal_events::{NatsEventSendFail, NatsEventSendSuccess, TemplateRenderingFailed},
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{EncodingConfig, StandardEncoder, StandardEncodings},
         StreamSink,
     },
     template::{Template, TemplateParseError},
@@ -28,7 +28,7 @@ enum BuildError {
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct NatsSinkConfig {
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
     #[serde(default = "default_name")]
     name: String,
     subject: String,
@@ -39,13 +39,6 @@ fn default_name() -> String {
     String::from("vector")
 }
 
-#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
-}
-
 inventory::submit! {
     SinkDescription::new::<NatsSinkConfig>("nats")
 }
@@ -115,7 +108,7 @@ struct NatsOptions {
 }
 
 pub struct NatsSink {
-    encoding: EncodingConfig<Encoding>,
+    encoder: StandardEncoder,
     options: NatsOptions,
     subject: Template,
     url: String,
@@ -126,7 +119,7 @@ impl NatsSink {
     fn new(config: NatsSinkConfig, acker: Acker) -> crate::Result<Self> {
         Ok(NatsSink {
             options: (&config).into(),
-            encoding: config.encoding,
+            encoder: StandardEncoder::new(config.encoding)?,
             subject: Template::try_from(config.subject).context(SubjectTemplate)?,
             url: config.url,
             acker,
@@ -171,16 +164,26 @@ impl StreamSink for NatsSink {
                 }
             };
 
-            let log = encode_event(event, &self.encoding);
-            let message_len = log.len();
+            let metadata = event.metadata().clone();
+            let message = match self.encoder.encode_event(event) {
+                Ok(message) => message,
+                Err(error) => {
+                    error!(message = "Unable to encode event.", %error);
+                    self.acker.ack(1);
+                    continue;
+                }
+            };
+            let message_len = message.len();
 
-            match nc.publish(&subject, log).await {
+            match nc.publish(&subject, message).await {
                 Ok(_) => {
+                    metadata.update_status(EventStatus::Delivered);
                     emit!(NatsEventSendSuccess {
                         byte_size: message_len,
                     });
                 }
                 Err(error) => {
+                    metadata.update_status(EventStatus::Errored);
                     emit!(NatsEventSendFail { error });
                 }
             }
@@ -192,23 +195,9 @@ impl StreamSink for NatsSink {
     }
 }
 
-fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> String {
-    encoding.apply_rules(&mut event);
-
-    match encoding.codec() {
-        Encoding::Json => serde_json::to_string(event.as_log()).unwrap(),
-        Encoding::Text => event
-            .as_log()
-            .get(crate::config::log_schema().message_key())
-            .map(|v| v.to_string_lossy())
-            .unwrap_or_default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use super::{encode_event, Encoding, EncodingConfig};
     use crate::event::{Event, Value};
 
     #[test]
@@ -219,10 +208,8 @@ mod tests {
     #[test]
     fn encodes_raw_logs() {
         let event = Event::from("foo");
-        assert_eq!(
-            "foo",
-            encode_event(event, &EncodingConfig::from(Encoding::Text))
-        );
+        let encoder = StandardEncoder::new(StandardEncodings::Text.into()).unwrap();
+        assert_eq!(b"foo", &encoder.encode_event(event).unwrap()[..]);
     }
 
     #[test]
@@ -233,9 +220,10 @@ mod tests {
         log.insert("z", Value::from(25));
         log.insert("a", Value::from("0"));
 
-        let encoded = encode_event(event, &EncodingConfig::from(Encoding::Json));
+        let encoder = StandardEncoder::new(StandardEncodings::Json.into()).unwrap();
+        let encoded = encoder.encode_event(event).unwrap();
         let expected = r#"{"a":"0","x":"23","z":25}"#;
-        assert_eq!(encoded, expected);
+        assert_eq!(String::from_utf8(encoded).unwrap(), expected);
     }
 }
 
@@ -258,7 +246,7 @@ mod integration_tests {
         let subject = format!("test-{}", random_string(10));
 
         let cnf = NatsSinkConfig {
-            encoding: EncodingConfig::from(Encoding::Text),
+            encoding: EncodingConfig::from(StandardEncodings::Text),
             name: "".to_owned(),
             subject: subject.clone(),
             url: "nats://127.0.0.1:4222".to_owned(),
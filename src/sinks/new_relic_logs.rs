@@ -3,7 +3,7 @@ use crate::{
     sinks::{
         http::{HttpMethod, HttpSinkConfig},
         util::{
-            encoding::{EncodingConfig, EncodingConfigWithDefault},
+            encoding::{EncodingConfig, EncodingConfigWithDefault, StandardEncodings},
             http::RequestConfig,
             BatchConfig, Compression, Concurrency, TowerRequestConfig,
         },
@@ -77,10 +77,10 @@ pub enum Encoding {
     Json,
 }
 
-impl From<Encoding> for crate::sinks::http::Encoding {
-    fn from(v: Encoding) -> crate::sinks::http::Encoding {
+impl From<Encoding> for StandardEncodings {
+    fn from(v: Encoding) -> StandardEncodings {
         match v {
-            Encoding::Json => crate::sinks::http::Encoding::Json,
+            Encoding::Json => StandardEncodings::Json,
         }
     }
 }
@@ -0,0 +1,590 @@
+//! Conversions from Vector events into OTLP export requests.
+
+use crate::{
+    config::log_schema,
+    event::{
+        metric::{Metric, MetricKind, MetricValue},
+        EventStatus, LogEvent, Value,
+    },
+    internal_events::OpentelemetryInvalidMetricReceived,
+    proto::opentelemetry::{
+        collector::{logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest},
+        common::v1::{any_value, AnyValue, ArrayValue, KeyValue, KeyValueList},
+        logs::v1::{InstrumentationLibraryLogs, LogRecord, ResourceLogs},
+        metrics::v1::{
+            metric::Data, number_data_point, summary_data_point::ValueAtQuantile,
+            AggregationTemporality, Gauge, Histogram, HistogramDataPoint,
+            InstrumentationLibraryMetrics, Metric as OtlpMetric, NumberDataPoint, ResourceMetrics,
+            Sum, Summary, SummaryDataPoint,
+        },
+        resource::v1::Resource,
+    },
+    sinks::util::{encode_namespace, EncodedEvent, EncodedLength, Partition},
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use prost::Message;
+use std::{
+    collections::BTreeMap,
+    convert::{TryFrom, TryInto},
+};
+
+/// A single log record or metric, along with the attributes of the resource
+/// which produced it. Items sharing a resource are grouped back together when
+/// the batch is turned into export requests.
+#[derive(Clone, Debug)]
+pub(super) enum OtlpItem {
+    Log {
+        resource: Option<Resource>,
+        record: LogRecord,
+    },
+    Metric {
+        resource: Option<Resource>,
+        metric: OtlpMetric,
+    },
+}
+
+/// The kind of export request an item belongs to. Batches are partitioned
+/// by kind so that each request only exports logs or metrics, and a retry
+/// never sends the other kind a second time.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(super) enum OtlpItemKind {
+    Logs,
+    Metrics,
+}
+
+impl Partition<OtlpItemKind> for OtlpItem {
+    fn partition(&self) -> OtlpItemKind {
+        match self {
+            OtlpItem::Log { .. } => OtlpItemKind::Logs,
+            OtlpItem::Metric { .. } => OtlpItemKind::Metrics,
+        }
+    }
+}
+
+impl EncodedLength for OtlpItem {
+    fn encoded_length(&self) -> usize {
+        match self {
+            OtlpItem::Log { resource, record } => {
+                resource.as_ref().map_or(0, Message::encoded_len) + record.encoded_len()
+            }
+            OtlpItem::Metric { resource, metric } => {
+                resource.as_ref().map_or(0, Message::encoded_len) + metric.encoded_len()
+            }
+        }
+    }
+}
+
+/// Fields which are mapped onto the fields of the log record itself, rather
+/// than into its attributes.
+const RECORD_FIELDS: &[&str] = &[
+    "observed_timestamp",
+    "resources",
+    "attributes",
+    "name",
+    "severity_text",
+    "severity_number",
+    "trace_id",
+    "span_id",
+    "flags",
+    "dropped_attributes_count",
+];
+
+pub(super) fn encode_log(mut log: LogEvent) -> EncodedEvent<OtlpItem> {
+    let body = log
+        .remove(log_schema().message_key())
+        .map(value_to_any_value);
+    let time_unix_nano = log
+        .remove(log_schema().timestamp_key())
+        .map_or(0, timestamp_nanos);
+    log.remove(log_schema().source_type_key());
+
+    let mut fields = RECORD_FIELDS
+        .iter()
+        .filter_map(|&field| log.remove(field).map(|value| (field, value)))
+        .collect::<BTreeMap<_, _>>();
+    let mut take = |field: &str| fields.remove(field);
+
+    let resource = match take("resources") {
+        Some(Value::Map(map)) => Some(Resource {
+            attributes: map_to_key_values(map),
+            dropped_attributes_count: 0,
+        }),
+        _ => None,
+    };
+    let observed_time_unix_nano = take("observed_timestamp").map_or(0, timestamp_nanos);
+    let name = take("name").map(|value| value.to_string_lossy());
+    let severity_text = take("severity_text").map(|value| value.to_string_lossy());
+    let severity_number = take("severity_number").and_then(integer).unwrap_or(0);
+    let trace_id = take("trace_id").and_then(from_hex);
+    let span_id = take("span_id").and_then(from_hex);
+    let flags = take("flags").and_then(integer).unwrap_or(0);
+    let dropped_attributes_count = take("dropped_attributes_count")
+        .and_then(integer)
+        .unwrap_or(0);
+
+    // Any fields without a place of their own in the log record are kept as
+    // attributes.
+    let mut attributes = match take("attributes") {
+        Some(Value::Map(map)) => map,
+        Some(value) => vec![("attributes".to_owned(), value)].into_iter().collect(),
+        None => BTreeMap::new(),
+    };
+    let (remaining, metadata) = log.into_parts();
+    attributes.extend(remaining);
+
+    let record = LogRecord {
+        time_unix_nano,
+        observed_time_unix_nano,
+        severity_number,
+        severity_text: severity_text.unwrap_or_default(),
+        name: name.unwrap_or_default(),
+        body,
+        attributes: map_to_key_values(attributes),
+        dropped_attributes_count,
+        flags,
+        trace_id: trace_id.unwrap_or_default(),
+        span_id: span_id.unwrap_or_default(),
+    };
+
+    EncodedEvent {
+        item: OtlpItem::Log { resource, record },
+        metadata: Some(metadata),
+    }
+}
+
+/// Distributions, sets and sketches have no OTLP equivalent and are rejected.
+pub(super) fn encode_metric(metric: Metric) -> Option<EncodedEvent<OtlpItem>> {
+    let attributes = metric
+        .tags()
+        .map(|tags| {
+            tags.iter()
+                .map(|(key, value)| KeyValue {
+                    key: key.clone(),
+                    value: Some(value_to_any_value(Value::from(value.as_str()))),
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let time_unix_nano = metric.timestamp().map_or(0, nanos);
+    let aggregation_temporality = match metric.kind() {
+        MetricKind::Incremental => AggregationTemporality::Delta,
+        MetricKind::Absolute => AggregationTemporality::Cumulative,
+    } as i32;
+    let number_point = |value: f64| NumberDataPoint {
+        attributes: attributes.clone(),
+        start_time_unix_nano: 0,
+        time_unix_nano,
+        value: Some(number_data_point::Value::AsDouble(value)),
+        exemplars: Vec::new(),
+        flags: 0,
+    };
+
+    let data = match metric.value() {
+        MetricValue::Counter { value } => Data::Sum(Sum {
+            data_points: vec![number_point(*value)],
+            aggregation_temporality,
+            is_monotonic: true,
+        }),
+        MetricValue::Gauge { value } => match metric.kind() {
+            MetricKind::Absolute => Data::Gauge(Gauge {
+                data_points: vec![number_point(*value)],
+            }),
+            // An incremental gauge is a change which may go either way.
+            MetricKind::Incremental => Data::Sum(Sum {
+                data_points: vec![number_point(*value)],
+                aggregation_temporality,
+                is_monotonic: false,
+            }),
+        },
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => {
+            // OTLP carries an explicit count for the values above the last
+            // bound, which Vector leaves implied by the total count.
+            let mut bucket_counts = buckets
+                .iter()
+                .map(|bucket| u64::from(bucket.count))
+                .collect::<Vec<_>>();
+            let bucketed = bucket_counts.iter().sum::<u64>();
+            bucket_counts.push(u64::from(*count).saturating_sub(bucketed));
+
+            Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    attributes: attributes.clone(),
+                    start_time_unix_nano: 0,
+                    time_unix_nano,
+                    count: u64::from(*count),
+                    sum: *sum,
+                    bucket_counts,
+                    explicit_bounds: buckets.iter().map(|bucket| bucket.upper_limit).collect(),
+                    exemplars: Vec::new(),
+                    flags: 0,
+                }],
+                aggregation_temporality,
+            })
+        }
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => Data::Summary(Summary {
+            data_points: vec![SummaryDataPoint {
+                attributes: attributes.clone(),
+                start_time_unix_nano: 0,
+                time_unix_nano,
+                count: u64::from(*count),
+                sum: *sum,
+                quantile_values: quantiles
+                    .iter()
+                    .map(|quantile| ValueAtQuantile {
+                        quantile: quantile.upper_limit,
+                        value: quantile.value,
+                    })
+                    .collect(),
+                flags: 0,
+            }],
+        }),
+        _ => {
+            emit!(OpentelemetryInvalidMetricReceived { metric: &metric });
+            let (_series, _data, mut metadata) = metric.into_parts();
+            metadata
+                .take_finalizers()
+                .update_status(EventStatus::Failed);
+            return None;
+        }
+    };
+
+    let otlp_metric = OtlpMetric {
+        name: encode_namespace(metric.namespace(), '.', metric.name()),
+        description: String::new(),
+        unit: String::new(),
+        data: Some(data),
+    };
+    let (_series, _data, metadata) = metric.into_parts();
+
+    Some(EncodedEvent {
+        item: OtlpItem::Metric {
+            resource: None,
+            metric: otlp_metric,
+        },
+        metadata: Some(metadata),
+    })
+}
+
+/// Groups a batch of items by their resource into at most one logs and one
+/// metrics export request.
+pub(super) fn build_requests(
+    items: Vec<OtlpItem>,
+) -> (
+    Option<ExportLogsServiceRequest>,
+    Option<ExportMetricsServiceRequest>,
+) {
+    let mut resource_logs: Vec<ResourceLogs> = Vec::new();
+    let mut resource_metrics: Vec<ResourceMetrics> = Vec::new();
+
+    for item in items {
+        match item {
+            OtlpItem::Log { resource, record } => {
+                match resource_logs
+                    .iter_mut()
+                    .find(|logs| logs.resource == resource)
+                {
+                    Some(logs) => logs.instrumentation_library_logs[0].logs.push(record),
+                    None => resource_logs.push(ResourceLogs {
+                        resource,
+                        instrumentation_library_logs: vec![InstrumentationLibraryLogs {
+                            instrumentation_library: None,
+                            logs: vec![record],
+                            schema_url: String::new(),
+                        }],
+                        schema_url: String::new(),
+                    }),
+                }
+            }
+            OtlpItem::Metric { resource, metric } => {
+                match resource_metrics
+                    .iter_mut()
+                    .find(|metrics| metrics.resource == resource)
+                {
+                    Some(metrics) => metrics.instrumentation_library_metrics[0]
+                        .metrics
+                        .push(metric),
+                    None => resource_metrics.push(ResourceMetrics {
+                        resource,
+                        instrumentation_library_metrics: vec![InstrumentationLibraryMetrics {
+                            instrumentation_library: None,
+                            metrics: vec![metric],
+                            schema_url: String::new(),
+                        }],
+                        schema_url: String::new(),
+                    }),
+                }
+            }
+        }
+    }
+
+    let logs = (!resource_logs.is_empty()).then(|| ExportLogsServiceRequest { resource_logs });
+    let metrics =
+        (!resource_metrics.is_empty()).then(|| ExportMetricsServiceRequest { resource_metrics });
+    (logs, metrics)
+}
+
+fn map_to_key_values(map: BTreeMap<String, Value>) -> Vec<KeyValue> {
+    map.into_iter()
+        .map(|(key, value)| KeyValue {
+            key,
+            value: Some(value_to_any_value(value)),
+        })
+        .collect()
+}
+
+fn value_to_any_value(value: Value) -> AnyValue {
+    let value = match value {
+        Value::Bytes(bytes) => Some(match String::from_utf8(bytes.to_vec()) {
+            Ok(string) => any_value::Value::StringValue(string),
+            Err(error) => any_value::Value::BytesValue(error.into_bytes()),
+        }),
+        Value::Integer(value) => Some(any_value::Value::IntValue(value)),
+        Value::Float(value) => Some(any_value::Value::DoubleValue(value)),
+        Value::Boolean(value) => Some(any_value::Value::BoolValue(value)),
+        Value::Timestamp(timestamp) => Some(any_value::Value::StringValue(
+            timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        )),
+        Value::Map(map) => Some(any_value::Value::KvlistValue(KeyValueList {
+            values: map_to_key_values(map),
+        })),
+        Value::Array(values) => Some(any_value::Value::ArrayValue(ArrayValue {
+            values: values.into_iter().map(value_to_any_value).collect(),
+        })),
+        Value::Null => None,
+    };
+    AnyValue { value }
+}
+
+fn timestamp_nanos(value: Value) -> u64 {
+    match value {
+        Value::Timestamp(timestamp) => nanos(timestamp),
+        _ => 0,
+    }
+}
+
+fn nanos(timestamp: DateTime<Utc>) -> u64 {
+    u64::try_from(timestamp.timestamp_nanos()).unwrap_or(0)
+}
+
+fn integer<T: TryFrom<i64>>(value: Value) -> Option<T> {
+    match value {
+        Value::Integer(value) => value.try_into().ok(),
+        _ => None,
+    }
+}
+
+fn from_hex(value: Value) -> Option<Vec<u8>> {
+    let hex = value.to_string_lossy();
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::metric::{Bucket, StatisticKind};
+    use chrono::TimeZone;
+
+    fn string_value(value: &str) -> Option<AnyValue> {
+        Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.to_owned())),
+        })
+    }
+
+    #[test]
+    fn encodes_logs() {
+        let mut log = LogEvent::from("order placed");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.timestamp(1_600_000_000, 1),
+        );
+        log.insert("resources.service\\.name", "checkout");
+        log.insert("severity_text", "INFO");
+        log.insert("severity_number", 9);
+        log.insert("trace_id", "ab".repeat(16));
+        log.insert("span_id", "not hex");
+        log.insert("attributes.order", 3);
+        log.insert("user", "alice");
+
+        let encoded = encode_log(log);
+        assert!(encoded.metadata.is_some());
+        let (resource, record) = match encoded.item {
+            OtlpItem::Log { resource, record } => (resource, record),
+            item => panic!("unexpected item {:?}", item),
+        };
+
+        assert_eq!(
+            resource.unwrap().attributes,
+            vec![KeyValue {
+                key: "service.name".to_owned(),
+                value: string_value("checkout"),
+            }]
+        );
+        assert_eq!(record.body, string_value("order placed"));
+        assert_eq!(record.time_unix_nano, 1_600_000_000_000_000_001);
+        assert_eq!(record.observed_time_unix_nano, 0);
+        assert_eq!(record.severity_text, "INFO");
+        assert_eq!(record.severity_number, 9);
+        assert_eq!(record.trace_id, vec![0xab; 16]);
+        assert!(record.span_id.is_empty());
+        assert_eq!(
+            record.attributes,
+            vec![
+                KeyValue {
+                    key: "order".to_owned(),
+                    value: Some(AnyValue {
+                        value: Some(any_value::Value::IntValue(3)),
+                    }),
+                },
+                KeyValue {
+                    key: "user".to_owned(),
+                    value: string_value("alice"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn encodes_counters_and_gauges() {
+        let counter = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 10.0 },
+        )
+        .with_namespace(Some("app"))
+        .with_tags(Some(
+            vec![("host".to_owned(), "a".to_owned())]
+                .into_iter()
+                .collect(),
+        ));
+        let gauge = Metric::new(
+            "memory",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.5 },
+        );
+
+        let counter = match encode_metric(counter).unwrap().item {
+            OtlpItem::Metric { metric, .. } => metric,
+            item => panic!("unexpected item {:?}", item),
+        };
+        assert_eq!(counter.name, "app.requests");
+        match counter.data {
+            Some(Data::Sum(sum)) => {
+                assert!(sum.is_monotonic);
+                assert_eq!(
+                    sum.aggregation_temporality,
+                    AggregationTemporality::Delta as i32
+                );
+                assert_eq!(
+                    sum.data_points[0].value,
+                    Some(number_data_point::Value::AsDouble(10.0))
+                );
+                assert_eq!(sum.data_points[0].attributes[0].key, "host");
+            }
+            data => panic!("unexpected data {:?}", data),
+        }
+
+        let gauge = match encode_metric(gauge).unwrap().item {
+            OtlpItem::Metric { metric, .. } => metric,
+            item => panic!("unexpected item {:?}", item),
+        };
+        assert!(matches!(gauge.data, Some(Data::Gauge(_))));
+    }
+
+    #[test]
+    fn encodes_histograms_with_overflow_bucket() {
+        let histogram = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 1,
+                    },
+                    Bucket {
+                        upper_limit: 2.0,
+                        count: 2,
+                    },
+                ],
+                count: 6,
+                sum: 12.0,
+            },
+        );
+
+        let metric = match encode_metric(histogram).unwrap().item {
+            OtlpItem::Metric { metric, .. } => metric,
+            item => panic!("unexpected item {:?}", item),
+        };
+        match metric.data {
+            Some(Data::Histogram(histogram)) => {
+                let point = &histogram.data_points[0];
+                assert_eq!(point.explicit_bounds, vec![1.0, 2.0]);
+                assert_eq!(point.bucket_counts, vec![1, 2, 3]);
+                assert_eq!(point.count, 6);
+            }
+            data => panic!("unexpected data {:?}", data),
+        }
+    }
+
+    #[test]
+    fn drops_unsupported_metrics() {
+        let distribution = Metric::new(
+            "latency",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vector_core::samples![1.0 => 1],
+                statistic: StatisticKind::Histogram,
+            },
+        );
+        assert!(encode_metric(distribution).is_none());
+    }
+
+    #[test]
+    fn groups_requests_by_resource() {
+        let log = |service: &str| {
+            let mut log = LogEvent::from("message");
+            log.insert("resources.service", service.to_owned());
+            encode_log(log).item
+        };
+        let gauge = Metric::new(
+            "memory",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.0 },
+        );
+
+        let (logs, metrics) = build_requests(vec![
+            log("api"),
+            log("web"),
+            log("api"),
+            encode_metric(gauge).unwrap().item,
+        ]);
+
+        let logs = logs.unwrap();
+        assert_eq!(logs.resource_logs.len(), 2);
+        assert_eq!(
+            logs.resource_logs[0].instrumentation_library_logs[0]
+                .logs
+                .len(),
+            2
+        );
+        assert_eq!(metrics.unwrap().resource_metrics.len(), 1);
+
+        let (logs, metrics) = build_requests(vec![log("api")]);
+        assert!(logs.is_some());
+        assert!(metrics.is_none());
+    }
+}
@@ -0,0 +1,293 @@
+mod encode;
+mod service;
+
+use self::{
+    encode::{encode_log, encode_metric, OtlpItem},
+    service::{OtlpRetryLogic, OtlpService},
+};
+use crate::{
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    event::{Event, EventStatus},
+    http::HttpClient,
+    internal_events::OpentelemetryTraceRejected,
+    proto::opentelemetry::collector::{
+        logs::v1::logs_service_client::LogsServiceClient,
+        metrics::v1::metrics_service_client::MetricsServiceClient,
+    },
+    sinks::{
+        util::{
+            BatchConfig, BatchSettings, Concurrency, EncodedEvent, PartitionBatchSink,
+            ServiceBuilderExt, TowerRequestConfig, VecBuffer,
+        },
+        Healthcheck, UriParseError, VectorSink,
+    },
+    tls::{TlsOptions, TlsSettings},
+};
+use futures::{future, stream, FutureExt, SinkExt, StreamExt};
+use http::Uri;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
+use tower::ServiceBuilder;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    endpoint: String,
+    #[serde(default)]
+    protocol: Protocol,
+    #[serde(default)]
+    batch: BatchConfig,
+    #[serde(default)]
+    request: TowerRequestConfig,
+    tls: Option<TlsOptions>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Derivative, Clone, Copy)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+enum Protocol {
+    #[derivative(Default)]
+    Grpc,
+    Http,
+}
+
+inventory::submit! {
+    SinkDescription::new::<OpentelemetryConfig>("opentelemetry")
+}
+
+impl GenerateConfig for OpentelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            endpoint: "http://127.0.0.1:4317".to_owned(),
+            protocol: Protocol::Grpc,
+            batch: BatchConfig::default(),
+            request: TowerRequestConfig::default(),
+            tls: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SinkConfig for OpentelemetryConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let service = match self.protocol {
+            Protocol::Grpc => self.grpc_service().await?,
+            Protocol::Http => self.http_service()?,
+        };
+
+        let mut request = self.request;
+        request.concurrency = request.concurrency.if_none(Concurrency::Adaptive);
+        let request = request.unwrap_with(&TowerRequestConfig::default());
+        let batch = BatchSettings::default()
+            .events(1000)
+            .timeout(1)
+            .parse_config(self.batch)?;
+
+        let svc = ServiceBuilder::new()
+            .settings(request, OtlpRetryLogic)
+            .service(service);
+
+        let buffer = VecBuffer::new(batch.size);
+        let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
+            .sink_map_err(|error| error!(message = "Fatal opentelemetry sink error.", %error))
+            .with_flat_map(|event| stream::iter(encode_event(event)).map(Ok));
+
+        // OTLP doesn't define a health check for either transport.
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((VectorSink::Sink(Box::new(sink)), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "opentelemetry"
+    }
+}
+
+impl OpentelemetryConfig {
+    async fn grpc_service(&self) -> crate::Result<OtlpService> {
+        let uri = self.endpoint.parse::<Uri>().context(UriParseError)?;
+        let mut endpoint = Endpoint::from(uri.clone());
+
+        if let Some(tls) = &self.tls {
+            let mut tls_config = ClientTlsConfig::new();
+            if let Some(ca_file) = &tls.ca_file {
+                let ca = Certificate::from_pem(tokio::fs::read(ca_file).await?);
+                tls_config = tls_config.ca_certificate(ca);
+            }
+            if let (Some(crt_file), Some(key_file)) = (&tls.crt_file, &tls.key_file) {
+                let crt = tokio::fs::read(crt_file).await?;
+                let key = tokio::fs::read(key_file).await?;
+                tls_config = tls_config.identity(Identity::from_pem(crt, key));
+            }
+            if let Some(host) = uri.host() {
+                tls_config = tls_config.domain_name(host);
+            }
+            endpoint = endpoint.tls_config(tls_config)?;
+        }
+
+        let channel = endpoint.connect_lazy()?;
+        Ok(OtlpService::Grpc {
+            logs: LogsServiceClient::new(channel.clone()),
+            metrics: MetricsServiceClient::new(channel),
+        })
+    }
+
+    fn http_service(&self) -> crate::Result<OtlpService> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls)?;
+        let uri = |path| {
+            format!("{}/{}", self.endpoint.trim_end_matches('/'), path)
+                .parse::<Uri>()
+                .context(UriParseError)
+        };
+
+        Ok(OtlpService::Http {
+            client,
+            logs_uri: uri("v1/logs")?,
+            metrics_uri: uri("v1/metrics")?,
+        })
+    }
+}
+
+fn encode_event(event: Event) -> Option<EncodedEvent<OtlpItem>> {
+    match event {
+        Event::Log(log) => Some(encode_log(log)),
+        Event::Metric(metric) => encode_metric(metric),
+        Event::Trace(mut trace) => {
+            // Spans aren't exported yet, so traces are rejected rather than
+            // silently acknowledged.
+            emit!(OpentelemetryTraceRejected);
+            trace
+                .metadata_mut()
+                .take_finalizers()
+                .update_status(EventStatus::Failed);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::{
+            metric::{Metric, MetricKind, MetricValue},
+            BatchNotifier, BatchStatus, TraceEvent,
+        },
+        proto::opentelemetry::collector::{
+            logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+        },
+        sinks::util::test::build_test_server,
+        test_util::next_addr,
+    };
+    use prost::Message;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetryConfig>();
+    }
+
+    #[tokio::test]
+    async fn sends_logs_and_metrics_over_http() {
+        let address = next_addr();
+        let config = OpentelemetryConfig {
+            endpoint: format!("http://{}", address),
+            protocol: Protocol::Http,
+            batch: BatchConfig {
+                max_events: Some(2),
+                ..Default::default()
+            },
+            request: TowerRequestConfig::default(),
+            tls: None,
+        };
+        let (sink, _healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (rx, trigger, server) = build_test_server(address);
+        tokio::spawn(server);
+
+        let events = vec![
+            Event::from("order placed"),
+            Event::from(Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )),
+        ];
+        sink.run(stream::iter(events)).await.unwrap();
+        drop(trigger);
+
+        let mut requests = rx.collect::<Vec<_>>().await;
+        requests.sort_by_key(|(parts, _)| parts.uri.path().to_owned());
+        assert_eq!(requests.len(), 2);
+
+        let (parts, body) = &requests[0];
+        assert_eq!(parts.uri.path(), "/v1/logs");
+        assert_eq!(parts.headers["Content-Type"], "application/x-protobuf");
+        let logs = ExportLogsServiceRequest::decode(body.clone()).unwrap();
+        let record = &logs.resource_logs[0].instrumentation_library_logs[0].logs[0];
+        assert!(record.time_unix_nano > 0);
+        assert!(record.body.is_some());
+
+        let (parts, body) = &requests[1];
+        assert_eq!(parts.uri.path(), "/v1/metrics");
+        let metrics = ExportMetricsServiceRequest::decode(body.clone()).unwrap();
+        assert_eq!(
+            metrics.resource_metrics[0].instrumentation_library_metrics[0].metrics[0].name,
+            "requests"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_traces() {
+        let config = OpentelemetryConfig {
+            endpoint: format!("http://{}", next_addr()),
+            protocol: Protocol::Http,
+            batch: BatchConfig::default(),
+            request: TowerRequestConfig::default(),
+            tls: None,
+        };
+        let (sink, _healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let event = Event::Trace(TraceEvent::default().with_batch_notifier(&batch));
+        drop(batch);
+        sink.run(stream::once(future::ready(event))).await.unwrap();
+
+        assert_eq!(receiver.await, BatchStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn rejects_unsupported_metrics() {
+        let config = OpentelemetryConfig {
+            endpoint: format!("http://{}", next_addr()),
+            protocol: Protocol::Http,
+            batch: BatchConfig::default(),
+            request: TowerRequestConfig::default(),
+            tls: None,
+        };
+        let (sink, _healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let event = Event::from(
+            Metric::new(
+                "users",
+                MetricKind::Incremental,
+                MetricValue::Set {
+                    values: vec!["alice".to_owned()].into_iter().collect(),
+                },
+            )
+            .with_batch_notifier(&batch),
+        );
+        drop(batch);
+        sink.run(stream::once(future::ready(event))).await.unwrap();
+
+        assert_eq!(receiver.await, BatchStatus::Failed);
+    }
+}
@@ -0,0 +1,152 @@
+use super::encode::{build_requests, OtlpItem};
+use crate::{
+    http::{HttpClient, HttpError},
+    proto::opentelemetry::collector::{
+        logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+        metrics::v1::{metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest},
+    },
+    sinks::util::retries::RetryLogic,
+};
+use futures::future::BoxFuture;
+use http::{StatusCode, Uri};
+use prost::Message;
+use snafu::{ResultExt, Snafu};
+use std::task::{Context, Poll};
+use tonic::{transport::Channel, Code};
+
+#[derive(Debug, Snafu)]
+pub(super) enum Error {
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: tonic::Status },
+
+    #[snafu(display("HTTP request failed: {}", source))]
+    Http { source: HttpError },
+
+    #[snafu(display("Unexpected status: {}", status))]
+    UnexpectedStatus { status: StatusCode },
+}
+
+/// Sends batches to an OTLP receiver, splitting each batch into a logs and a
+/// metrics export request as needed.
+#[derive(Clone)]
+pub(super) enum OtlpService {
+    Grpc {
+        logs: LogsServiceClient<Channel>,
+        metrics: MetricsServiceClient<Channel>,
+    },
+    Http {
+        client: HttpClient,
+        logs_uri: Uri,
+        metrics_uri: Uri,
+    },
+}
+
+impl OtlpService {
+    async fn export_logs(&self, request: ExportLogsServiceRequest) -> Result<(), Error> {
+        match self {
+            Self::Grpc { logs, .. } => logs
+                .clone()
+                .export(request)
+                .await
+                .map(drop)
+                .context(Request),
+            Self::Http {
+                client, logs_uri, ..
+            } => send_http(client, logs_uri.clone(), request).await,
+        }
+    }
+
+    async fn export_metrics(&self, request: ExportMetricsServiceRequest) -> Result<(), Error> {
+        match self {
+            Self::Grpc { metrics, .. } => metrics
+                .clone()
+                .export(request)
+                .await
+                .map(drop)
+                .context(Request),
+            Self::Http {
+                client,
+                metrics_uri,
+                ..
+            } => send_http(client, metrics_uri.clone(), request).await,
+        }
+    }
+}
+
+async fn send_http(client: &HttpClient, uri: Uri, request: impl Message) -> Result<(), Error> {
+    let mut body = Vec::with_capacity(request.encoded_len());
+    request.encode(&mut body).expect("Out of memory");
+
+    let request = http::Request::post(uri)
+        .header("Content-Type", "application/x-protobuf")
+        .body(body.into())
+        .expect("Request should be valid");
+    let response = client.send(request).await.context(Http)?;
+
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(Error::UnexpectedStatus { status })
+    }
+}
+
+impl tower::Service<Vec<OtlpItem>> for OtlpService {
+    type Response = ();
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Both clients wait for readiness when a request is made.
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, items: Vec<OtlpItem>) -> Self::Future {
+        let service = self.clone();
+        let (logs, metrics) = build_requests(items);
+
+        // Batches are partitioned by `OtlpItemKind`, so only one of these is
+        // ever set and each export is retried on its own.
+        Box::pin(async move {
+            if let Some(request) = logs {
+                service.export_logs(request).await?;
+            }
+            if let Some(request) = metrics {
+                service.export_metrics(request).await?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Retries the failures which the OTLP specification marks as retryable.
+#[derive(Debug, Clone)]
+pub(super) struct OtlpRetryLogic;
+
+impl RetryLogic for OtlpRetryLogic {
+    type Error = Error;
+    type Response = ();
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            Error::Request { source } => matches!(
+                source.code(),
+                Code::Cancelled
+                    | Code::DeadlineExceeded
+                    | Code::ResourceExhausted
+                    | Code::Aborted
+                    | Code::OutOfRange
+                    | Code::Unavailable
+                    | Code::DataLoss
+            ),
+            Error::Http { .. } => true,
+            Error::UnexpectedStatus { status } => matches!(
+                *status,
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+        }
+    }
+}
@@ -113,7 +113,7 @@ fn encode_event(
 
     s.push(b'\n');
 
-    EncodedEvent::new(Bytes::from(s))
+    EncodedEvent::new(Bytes::from(s)).with_metadata(log)
 }
 
 #[cfg(test)]
@@ -139,6 +139,8 @@ mod tests {
                 only_fields: None,
                 except_fields: Some(vec!["magic".into()]),
                 timestamp_format: None,
+                framing: None,
+                parquet: None,
             },
         )
         .item;
@@ -178,6 +178,23 @@ pub(super) trait MetricCollector {
                     self.emit_value(timestamp, name, "_sum", *sum, tags, None);
                     self.emit_value(timestamp, name, "_count", *count as f64, tags, None);
                 }
+                MetricValue::Sketch { sketch } => {
+                    // sketches are exposed as summaries with the configured quantiles
+                    for q in quantiles {
+                        if let Some(value) = sketch.quantile(*q) {
+                            self.emit_value(
+                                timestamp,
+                                name,
+                                "",
+                                value,
+                                tags,
+                                Some(("quantile", q.to_string())),
+                            );
+                        }
+                    }
+                    self.emit_value(timestamp, name, "_sum", sketch.sum(), tags, None);
+                    self.emit_value(timestamp, name, "_count", sketch.count() as f64, tags, None);
+                }
             }
         }
     }
@@ -380,7 +397,7 @@ fn prometheus_metric_type(metric_value: &MetricValue) -> proto::MetricType {
             ..
         } => MetricType::Summary,
         MetricValue::AggregatedHistogram { .. } => MetricType::Histogram,
-        MetricValue::AggregatedSummary { .. } => MetricType::Summary,
+        MetricValue::AggregatedSummary { .. } | MetricValue::Sketch { .. } => MetricType::Summary,
     }
 }
 
@@ -2,7 +2,7 @@ use crate::{
     buffers::Acker,
     config::{DataType, GenerateConfig, Resource, SinkConfig, SinkContext, SinkDescription},
     event::metric::{Metric, MetricData, MetricKind, MetricValue},
-    event::Event,
+    event::{Event, EventStatus},
     internal_events::PrometheusServerRequestComplete,
     sinks::{
         util::{statistic::validate_quantiles, StreamSink},
@@ -291,7 +291,10 @@ impl StreamSink for PrometheusExporter {
     async fn run(&mut self, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
         self.start_server_if_needed().await;
         while let Some(event) = input.next().await {
-            let item = event.into_metric();
+            let mut item = event.into_metric();
+            // The metric is kept around until it is scraped or expired, so
+            // its metadata is detached to not hold back its finalization.
+            let metadata = std::mem::take(item.metadata_mut());
             let mut metrics = self.metrics.write().unwrap();
 
             // sets need to be expired from time to time
@@ -333,6 +336,7 @@ impl StreamSink for PrometheusExporter {
                 }
             };
 
+            metadata.update_status(EventStatus::Delivered);
             self.acker.ack(1);
         }
         Ok(())
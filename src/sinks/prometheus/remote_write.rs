@@ -106,7 +106,7 @@ impl SinkConfig for RemoteWriteConfig {
 
             PartitionBatchSink::new(service, buffer, batch.timeout, cx.acker())
                 .with_flat_map(move |event: Event| {
-                    stream::iter(normalizer.apply(event).map(|event| {
+                    stream::iter(normalizer.apply(event).map(|mut event| {
                         let tenant_id = tenant_id.as_ref().and_then(|template| {
                             template
                                 .render_string(&event)
@@ -120,7 +120,11 @@ impl SinkConfig for RemoteWriteConfig {
                                 .ok()
                         });
                         let key = PartitionKey { tenant_id };
-                        Ok(EncodedEvent::new(PartitionInnerBuffer::new(event, key)))
+                        let metadata = std::mem::take(event.metadata_mut());
+                        Ok(EncodedEvent {
+                            item: PartitionInnerBuffer::new(event, key),
+                            metadata: Some(metadata),
+                        })
                     }))
                 })
                 .sink_map_err(
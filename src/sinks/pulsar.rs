@@ -1,7 +1,7 @@
 use crate::{
     buffers::Acker,
     config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
-    event::Event,
+    event::{Event, EventMetadata, EventStatus},
     internal_events::PulsarEncodeEventFailed,
     sinks::util::encoding::{EncodingConfig, EncodingConfiguration},
 };
@@ -54,15 +54,32 @@ type BoxedPulsarProducer = Box<PulsarProducer>;
 enum PulsarSinkState {
     None,
     Ready(BoxedPulsarProducer),
-    Sending(BoxFuture<'static, (BoxedPulsarProducer, Result<SendFuture, PulsarError>)>),
+    Sending(
+        BoxFuture<
+            'static,
+            (
+                BoxedPulsarProducer,
+                Result<SendFuture, PulsarError>,
+                EventMetadata,
+            ),
+        >,
+    ),
 }
 
 struct PulsarSink {
     encoding: EncodingConfig<Encoding>,
     avro_schema: Option<avro_rs::Schema>,
     state: PulsarSinkState,
-    in_flight:
-        FuturesUnordered<BoxFuture<'static, (usize, Result<CommandSendReceipt, PulsarError>)>>,
+    in_flight: FuturesUnordered<
+        BoxFuture<
+            'static,
+            (
+                usize,
+                Result<CommandSendReceipt, PulsarError>,
+                EventMetadata,
+            ),
+        >,
+    >,
 
     acker: Acker,
     seq_head: usize,
@@ -187,7 +204,7 @@ impl PulsarSink {
 
     fn poll_in_flight_prepare(&mut self, cx: &mut Context<'_>) -> Poll<()> {
         if let PulsarSinkState::Sending(fut) = &mut self.state {
-            let (producer, result) = ready!(fut.as_mut().poll(cx));
+            let (producer, result, metadata) = ready!(fut.as_mut().poll(cx));
 
             let seqno = self.seq_head;
             self.seq_head += 1;
@@ -198,7 +215,7 @@ impl PulsarSink {
                     Ok(fut) => fut.await,
                     Err(error) => Err(error),
                 };
-                (seqno, result)
+                (seqno, result, metadata)
             }));
         }
 
@@ -220,6 +237,7 @@ impl Sink<Event> for PulsarSink {
             "Expected `poll_ready` to be called first."
         );
 
+        let metadata = item.metadata().clone();
         let message = encode_event(item, &self.encoding, &self.avro_schema).map_err(|e| {
             emit!(PulsarEncodeEventFailed {
                 error: &*e.to_string()
@@ -235,7 +253,7 @@ impl Sink<Event> for PulsarSink {
             &mut self.state,
             PulsarSinkState::Sending(Box::pin(async move {
                 let result = producer.send(message).await;
-                (producer, result)
+                (producer, result, metadata)
             })),
         );
 
@@ -248,7 +266,8 @@ impl Sink<Event> for PulsarSink {
         let this = Pin::into_inner(self);
         while !this.in_flight.is_empty() {
             match ready!(Pin::new(&mut this.in_flight).poll_next(cx)) {
-                Some((seqno, Ok(result))) => {
+                Some((seqno, Ok(result), metadata)) => {
+                    metadata.update_status(EventStatus::Delivered);
                     trace!(
                         message = "Pulsar sink produced message.",
                         message_id = ?result.message_id,
@@ -265,7 +284,8 @@ impl Sink<Event> for PulsarSink {
                     }
                     this.acker.ack(num_to_ack);
                 }
-                Some((_, Err(error))) => {
+                Some((_, Err(error), metadata)) => {
+                    metadata.update_status(EventStatus::Errored);
                     error!(message = "Pulsar sink generated an error.", %error);
                     return Poll::Ready(Err(()));
                 }
@@ -380,6 +400,8 @@ mod tests {
                 only_fields: None,
                 except_fields: Some(vec!["key".into()]),
                 timestamp_format: None,
+                framing: None,
+                parquet: None,
             },
             &None,
         )
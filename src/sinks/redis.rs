@@ -1,10 +1,10 @@
 use crate::{
-    config::{self, log_schema, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    config::{self, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     internal_events::{RedisEventSent, RedisSendEventFailed, TemplateRenderingFailed},
     sinks::util::{
         batch::{BatchConfig, BatchSettings},
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{EncodingConfig, StandardEncoder, StandardEncodings},
         retries::{RetryAction, RetryLogic},
         sink::Response,
         BatchSink, Concurrency, EncodedEvent, EncodedLength, ServiceBuilderExt, TowerRequestConfig,
@@ -68,13 +68,6 @@ pub enum Method {
     LPush,
 }
 
-#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
-}
-
 lazy_static! {
     static ref REQUEST_DEFAULTS: TowerRequestConfig = TowerRequestConfig {
         concurrency: Concurrency::Fixed(1),
@@ -86,7 +79,7 @@ lazy_static! {
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RedisSinkConfig {
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
     #[serde(default)]
     data_type: DataTypeConfig,
     #[serde(alias = "list")]
@@ -149,7 +142,7 @@ impl RedisSinkConfig {
         let request = self.request.unwrap_with(&REQUEST_DEFAULTS);
 
         let key = Template::try_from(self.key.clone()).context(KeyTemplate)?;
-        let encoding = self.encoding.clone();
+        let encoder = StandardEncoder::new(self.encoding.clone())?;
 
         let method = self.list_option.map(|option| option.method);
 
@@ -172,7 +165,7 @@ impl RedisSinkConfig {
             .service(redis);
 
         let sink = BatchSink::new(svc, buffer, batch.timeout, cx.acker())
-            .with_flat_map(move |e| stream::iter(encode_event(e, &key, &encoding)).map(Ok))
+            .with_flat_map(move |e| stream::iter(encode_event(e, &key, &encoder)).map(Ok))
             .sink_map_err(|error| error!(message = "Sink failed to flush.", %error));
 
         Ok(super::VectorSink::Sink(Box::new(sink)))
@@ -208,9 +201,9 @@ impl EncodedLength for RedisKvEntry {
 }
 
 fn encode_event(
-    mut event: Event,
+    event: Event,
     key: &Template,
-    encoding: &EncodingConfig<Encoding>,
+    encoder: &StandardEncoder,
 ) -> Option<EncodedEvent<RedisKvEntry>> {
     let key = key
         .render_string(&event)
@@ -223,21 +216,16 @@ fn encode_event(
         })
         .ok()?;
 
-    encoding.apply_rules(&mut event);
-
-    let value = match encoding.codec() {
-        Encoding::Json => serde_json::to_vec(event.as_log())
-            .map_err(|error| panic!("Unable to encode into JSON: {}", error))
-            .unwrap_or_default(),
-        Encoding::Text => event
-            .as_log()
-            .get(log_schema().message_key())
-            .map(|v| v.as_bytes().to_vec())
-            .unwrap_or_default(),
-    };
+    let metadata = event.metadata().clone();
+    let value = encoder
+        .encode_event(event)
+        .map_err(|error| error!(message = "Unable to encode event.", %error))
+        .ok()?;
 
-    let event = EncodedEvent::new(RedisKvEntry { key, value });
-    Some(event)
+    Some(EncodedEvent {
+        item: RedisKvEntry { key, value },
+        metadata: Some(metadata),
+    })
 }
 
 type RedisPipeResult = RedisResult<Vec<bool>>;
@@ -340,6 +328,7 @@ impl Service<Vec<RedisKvEntry>> for RedisSink {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::log_schema;
     use std::collections::HashMap;
     use std::convert::TryFrom;
 
@@ -356,7 +345,7 @@ mod tests {
         let result = encode_event(
             evt,
             &Template::try_from("key").unwrap(),
-            &EncodingConfig::from(Encoding::Json),
+            &StandardEncoder::new(StandardEncodings::Json.into()).unwrap(),
         )
         .unwrap()
         .item
@@ -372,7 +361,7 @@ mod tests {
         let event = encode_event(
             evt,
             &Template::try_from("key").unwrap(),
-            &EncodingConfig::from(Encoding::Text),
+            &StandardEncoder::new(StandardEncodings::Text.into()).unwrap(),
         )
         .unwrap()
         .item
@@ -389,13 +378,16 @@ mod tests {
         let result = encode_event(
             evt,
             &Template::try_from("key").unwrap(),
-            &EncodingConfig {
-                codec: Encoding::Json,
+            &StandardEncoder::new(EncodingConfig {
+                codec: StandardEncodings::Json,
                 schema: None,
                 only_fields: None,
                 except_fields: Some(vec!["key".into()]),
                 timestamp_format: None,
-            },
+                framing: None,
+                parquet: None,
+            })
+            .unwrap(),
         )
         .unwrap()
         .item
@@ -429,7 +421,7 @@ mod integration_tests {
         let cnf = RedisSinkConfig {
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
-            encoding: Encoding::Json.into(),
+            encoding: StandardEncodings::Json.into(),
             data_type: DataTypeConfig::List,
             list_option: Some(ListOption {
                 method: Method::LPush,
@@ -488,7 +480,7 @@ mod integration_tests {
         let cnf = RedisSinkConfig {
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
-            encoding: Encoding::Json.into(),
+            encoding: StandardEncodings::Json.into(),
             data_type: DataTypeConfig::List,
             list_option: Some(ListOption {
                 method: Method::RPush,
@@ -562,7 +554,7 @@ mod integration_tests {
         let cnf = RedisSinkConfig {
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
-            encoding: Encoding::Json.into(),
+            encoding: StandardEncodings::Json.into(),
             data_type: DataTypeConfig::Channel,
             list_option: None,
             batch: BatchConfig::default(),
@@ -158,11 +158,13 @@ impl SematextMetricsService {
                 sink::StdServiceLogic::default(),
             )
             .with_flat_map(move |event: Event| {
-                stream::iter(
-                    normalizer
-                        .apply(event)
-                        .map(|item| Ok(EncodedEvent::new(item))),
-                )
+                stream::iter(normalizer.apply(event).map(|mut item| {
+                    let metadata = std::mem::take(item.metadata_mut());
+                    Ok(EncodedEvent {
+                        item,
+                        metadata: Some(metadata),
+                    })
+                }))
             })
             .sink_map_err(|error| error!(message = "Fatal sematext metrics sink error.", %error));
 
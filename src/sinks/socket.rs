@@ -3,7 +3,10 @@ use crate::sinks::util::unix::UnixSinkConfig;
 use crate::{
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     sinks::util::{
-        encode_event, encoding::EncodingConfig, tcp::TcpSinkConfig, udp::UdpSinkConfig, Encoding,
+        encode_event,
+        encoding::{EncodingConfig, StandardEncoder, StandardEncodings},
+        tcp::TcpSinkConfig,
+        udp::UdpSinkConfig,
     },
 };
 use serde::{Deserialize, Serialize};
@@ -14,7 +17,7 @@ use serde::{Deserialize, Serialize};
 pub struct SocketSinkConfig {
     #[serde(flatten)]
     pub mode: Mode,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<StandardEncodings>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -42,14 +45,14 @@ impl GenerateConfig for SocketSinkConfig {
 }
 
 impl SocketSinkConfig {
-    pub fn new(mode: Mode, encoding: EncodingConfig<Encoding>) -> Self {
+    pub fn new(mode: Mode, encoding: EncodingConfig<StandardEncodings>) -> Self {
         SocketSinkConfig { mode, encoding }
     }
 
     pub fn make_basic_tcp_config(address: String) -> Self {
         Self::new(
             Mode::Tcp(TcpSinkConfig::from_address(address)),
-            EncodingConfig::from(Encoding::Text),
+            EncodingConfig::from(StandardEncodings::Text),
         )
     }
 }
@@ -61,8 +64,8 @@ impl SinkConfig for SocketSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let encoding = self.encoding.clone();
-        let encode_event = move |event| encode_event(event, &encoding);
+        let encoder = StandardEncoder::new(self.encoding.clone())?;
+        let encode_event = move |event| encode_event(event, &encoder);
         match &self.mode {
             Mode::Tcp(config) => config.build(cx, encode_event),
             Mode::Udp(config) => config.build(cx, encode_event),
@@ -111,7 +114,7 @@ mod test {
 
         let config = SocketSinkConfig {
             mode: Mode::Udp(UdpSinkConfig::from_address(addr.to_string())),
-            encoding: Encoding::Json.into(),
+            encoding: StandardEncodings::Json.into(),
         };
         let context = SinkContext::new_test();
         let (sink, _healthcheck) = config.build(context).await.unwrap();
@@ -153,7 +156,7 @@ mod test {
         let addr = next_addr();
         let config = SocketSinkConfig {
             mode: Mode::Tcp(TcpSinkConfig::from_address(addr.to_string())),
-            encoding: Encoding::Json.into(),
+            encoding: StandardEncodings::Json.into(),
         };
 
         let context = SinkContext::new_test();
@@ -224,7 +227,7 @@ mod test {
                 }),
                 None,
             )),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
         };
         let context = SinkContext::new_test();
         let (sink, _healthcheck) = config.build(context).await.unwrap();
@@ -340,7 +343,7 @@ mod test {
         let addr = next_addr();
         let config = SocketSinkConfig {
             mode: Mode::Tcp(TcpSinkConfig::from_address(addr.to_string())),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
         };
 
         let context = SinkContext::new_test();
@@ -220,7 +220,10 @@ fn encode_event(event: Event, default_namespace: Option<&str>) -> Option<Encoded
     let mut body: Vec<u8> = message.into_bytes();
     body.push(b'\n');
 
-    Some(EncodedEvent::new(body))
+    Some(EncodedEvent {
+        item: body,
+        metadata: Some(event.into_metadata()),
+    })
 }
 
 impl Service<Vec<u8>> for StatsdSvc {
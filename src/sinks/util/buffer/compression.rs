@@ -1,18 +1,37 @@
+use flate2::write::{GzEncoder, ZlibEncoder};
 use serde::{de, ser};
 use serde_json::Value;
-use std::fmt;
+use std::{fmt, io, io::Write};
 
 pub const GZIP_NONE: usize = 0;
 pub const GZIP_FAST: usize = 1;
 pub const GZIP_DEFAULT: usize = 6;
 pub const GZIP_BEST: usize = 9;
 
+pub const ZSTD_FAST: usize = 1;
+pub const ZSTD_DEFAULT: usize = 3;
+pub const ZSTD_BEST: usize = 21;
+
+#[cfg(feature = "sinks-utils-snappy")]
+const ALGORITHMS: &[&str] = &["none", "gzip", "zlib", "zstd", "snappy"];
+#[cfg(not(feature = "sinks-utils-snappy"))]
+const ALGORITHMS: &[&str] = &["none", "gzip", "zlib", "zstd"];
+
+#[cfg(feature = "sinks-utils-snappy")]
+const EXPECTED: &str = r#""none", "gzip", "zlib", "zstd" or "snappy""#;
+#[cfg(not(feature = "sinks-utils-snappy"))]
+const EXPECTED: &str = r#""none", "gzip", "zlib" or "zstd""#;
+
 #[derive(Debug, Derivative, Copy, Clone, Eq, PartialEq)]
 #[derivative(Default)]
 pub enum Compression {
     #[derivative(Default)]
     None,
     Gzip(Option<usize>),
+    Zlib(Option<usize>),
+    Zstd(Option<usize>),
+    #[cfg(feature = "sinks-utils-snappy")]
+    Snappy,
 }
 
 impl Compression {
@@ -24,6 +43,11 @@ impl Compression {
         match self {
             Self::None => None,
             Self::Gzip(_) => Some("gzip"),
+            // The HTTP `deflate` coding is the zlib format.
+            Self::Zlib(_) => Some("deflate"),
+            Self::Zstd(_) => Some("zstd"),
+            #[cfg(feature = "sinks-utils-snappy")]
+            Self::Snappy => Some("snappy"),
         }
     }
 
@@ -31,31 +55,145 @@ impl Compression {
         match self {
             Self::None => "log",
             Self::Gzip(_) => "log.gz",
+            Self::Zlib(_) => "log.zz",
+            Self::Zstd(_) => "log.zst",
+            #[cfg(feature = "sinks-utils-snappy")]
+            Self::Snappy => "log.snappy",
+        }
+    }
+
+    pub const fn algorithm(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Gzip(_) => "gzip",
+            Self::Zlib(_) => "zlib",
+            Self::Zstd(_) => "zstd",
+            #[cfg(feature = "sinks-utils-snappy")]
+            Self::Snappy => "snappy",
+        }
+    }
+
+    /// Compresses a complete body at once.
+    ///
+    /// Snappy uses the framing format, like every other snappy output written by the sinks.
+    pub fn compress(&self, body: Vec<u8>) -> io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(body),
+            Self::Gzip(level) => {
+                let level = level.unwrap_or(GZIP_DEFAULT) as u32;
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::new(level));
+                encoder.write_all(&body)?;
+                encoder.finish()
+            }
+            Self::Zlib(level) => {
+                let level = level.unwrap_or(GZIP_DEFAULT) as u32;
+                let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::new(level));
+                encoder.write_all(&body)?;
+                encoder.finish()
+            }
+            Self::Zstd(level) => {
+                zstd::stream::encode_all(&body[..], level.unwrap_or(ZSTD_DEFAULT) as i32)
+            }
+            #[cfg(feature = "sinks-utils-snappy")]
+            Self::Snappy => {
+                let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+                encoder.write_all(&body)?;
+                encoder
+                    .into_inner()
+                    .map_err(|error| io::Error::new(io::ErrorKind::Other, error.to_string()))
+            }
         }
     }
 }
+
 impl fmt::Display for Compression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Compression::None => write!(f, "none"),
             Compression::Gzip(ref level) => write!(f, "gzip({})", level.unwrap_or(GZIP_DEFAULT)),
+            Compression::Zlib(ref level) => write!(f, "zlib({})", level.unwrap_or(GZIP_DEFAULT)),
+            Compression::Zstd(ref level) => write!(f, "zstd({})", level.unwrap_or(ZSTD_DEFAULT)),
+            #[cfg(feature = "sinks-utils-snappy")]
+            Compression::Snappy => write!(f, "snappy"),
         }
     }
 }
 
 #[cfg(feature = "rusoto_core")]
-impl From<Compression> for rusoto_core::encoding::ContentEncoding {
-    fn from(compression: Compression) -> Self {
+impl std::convert::TryFrom<Compression> for rusoto_core::encoding::ContentEncoding {
+    type Error = crate::Error;
+
+    fn try_from(compression: Compression) -> crate::Result<Self> {
         match compression {
-            Compression::None => rusoto_core::encoding::ContentEncoding::Identity,
+            Compression::None => Ok(rusoto_core::encoding::ContentEncoding::Identity),
             Compression::Gzip(level) => {
                 let level = level.unwrap_or(GZIP_DEFAULT);
-                rusoto_core::encoding::ContentEncoding::Gzip(None, level as u32)
+                Ok(rusoto_core::encoding::ContentEncoding::Gzip(
+                    None,
+                    level as u32,
+                ))
             }
+            compression => Err(format!(
+                "The `{}` compression isn't supported by AWS clients, use `none` or `gzip`.",
+                compression.algorithm()
+            )
+            .into()),
         }
     }
 }
 
+/// The levels of the deflate based algorithms, `gzip` and `zlib`.
+fn deflate_level<E: de::Error>(level: Value) -> Result<usize, E> {
+    match level {
+        Value::Number(level) => match level.as_u64() {
+            Some(value) if value <= 9 => Ok(value as usize),
+            Some(_) | None => Err(de::Error::invalid_value(
+                de::Unexpected::Other(&level.to_string()),
+                &"0, 1, 2, 3, 4, 5, 6, 7, 8 or 9",
+            )),
+        },
+        Value::String(level) => match level.as_str() {
+            "none" => Ok(GZIP_NONE),
+            "fast" => Ok(GZIP_FAST),
+            "default" => Ok(GZIP_DEFAULT),
+            "best" => Ok(GZIP_BEST),
+            level => Err(de::Error::invalid_value(
+                de::Unexpected::Str(level),
+                &r#""none", "fast", "best" or "default""#,
+            )),
+        },
+        value => Err(de::Error::invalid_type(
+            de::Unexpected::Other(&value.to_string()),
+            &"integer or string",
+        )),
+    }
+}
+
+fn zstd_level<E: de::Error>(level: Value) -> Result<usize, E> {
+    match level {
+        Value::Number(level) => match level.as_u64() {
+            Some(value) if (1..=21).contains(&value) => Ok(value as usize),
+            Some(_) | None => Err(de::Error::invalid_value(
+                de::Unexpected::Other(&level.to_string()),
+                &"an integer between 1 and 21",
+            )),
+        },
+        Value::String(level) => match level.as_str() {
+            "fast" => Ok(ZSTD_FAST),
+            "default" => Ok(ZSTD_DEFAULT),
+            "best" => Ok(ZSTD_BEST),
+            level => Err(de::Error::invalid_value(
+                de::Unexpected::Str(level),
+                &r#""fast", "best" or "default""#,
+            )),
+        },
+        value => Err(de::Error::invalid_type(
+            de::Unexpected::Other(&value.to_string()),
+            &"integer or string",
+        )),
+    }
+}
+
 impl<'de> de::Deserialize<'de> for Compression {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -77,10 +215,11 @@ impl<'de> de::Deserialize<'de> for Compression {
                 match s {
                     "none" => Ok(Compression::None),
                     "gzip" => Ok(Compression::gzip_default()),
-                    _ => Err(de::Error::invalid_value(
-                        de::Unexpected::Str(s),
-                        &r#""none" or "gzip""#,
-                    )),
+                    "zlib" => Ok(Compression::Zlib(None)),
+                    "zstd" => Ok(Compression::Zstd(None)),
+                    #[cfg(feature = "sinks-utils-snappy")]
+                    "snappy" => Ok(Compression::Snappy),
+                    _ => Err(de::Error::invalid_value(de::Unexpected::Str(s), &EXPECTED)),
                 }
             }
 
@@ -103,35 +242,7 @@ impl<'de> de::Deserialize<'de> for Compression {
                             if level.is_some() {
                                 return Err(de::Error::duplicate_field("level"));
                             }
-                            level = Some(match map.next_value::<Value>()? {
-                                Value::Number(level) => match level.as_u64() {
-                                    Some(value) if value <= 9 => value as usize,
-                                    Some(_) | None => {
-                                        return Err(de::Error::invalid_value(
-                                            de::Unexpected::Other(&level.to_string()),
-                                            &"0, 1, 2, 3, 4, 5, 6, 7, 8 or 9",
-                                        ))
-                                    }
-                                },
-                                Value::String(level) => match level.as_str() {
-                                    "none" => GZIP_NONE,
-                                    "fast" => GZIP_FAST,
-                                    "default" => GZIP_DEFAULT,
-                                    "best" => GZIP_BEST,
-                                    level => {
-                                        return Err(de::Error::invalid_value(
-                                            de::Unexpected::Str(level),
-                                            &r#""none", "fast", "best" or "default""#,
-                                        ))
-                                    }
-                                },
-                                value => {
-                                    return Err(de::Error::invalid_type(
-                                        de::Unexpected::Other(&value.to_string()),
-                                        &"integer or string",
-                                    ));
-                                }
-                            });
+                            level = Some(map.next_value::<Value>()?);
                         }
                         _ => return Err(de::Error::unknown_field(key, &["algorithm", "level"])),
                     };
@@ -142,8 +253,15 @@ impl<'de> de::Deserialize<'de> for Compression {
                         Some(_) => Err(de::Error::unknown_field("level", &[])),
                         None => Ok(Compression::None),
                     },
-                    "gzip" => Ok(Compression::Gzip(level)),
-                    algorithm => Err(de::Error::unknown_variant(algorithm, &["none", "gzip"])),
+                    #[cfg(feature = "sinks-utils-snappy")]
+                    "snappy" => match level {
+                        Some(_) => Err(de::Error::unknown_field("level", &[])),
+                        None => Ok(Compression::Snappy),
+                    },
+                    "gzip" => Ok(Compression::Gzip(level.map(deflate_level).transpose()?)),
+                    "zlib" => Ok(Compression::Zlib(level.map(deflate_level).transpose()?)),
+                    "zstd" => Ok(Compression::Zstd(level.map(zstd_level).transpose()?)),
+                    algorithm => Err(de::Error::unknown_variant(algorithm, ALGORITHMS)),
                 }
             }
         }
@@ -162,8 +280,12 @@ impl ser::Serialize for Compression {
         let mut map = serializer.serialize_map(None)?;
         match self {
             Compression::None => map.serialize_entry("algorithm", "none")?,
-            Compression::Gzip(level) => {
-                map.serialize_entry("algorithm", "gzip")?;
+            Compression::Gzip(level) | Compression::Zlib(level) => {
+                let algorithm = match self {
+                    Compression::Gzip(_) => "gzip",
+                    _ => "zlib",
+                };
+                map.serialize_entry("algorithm", algorithm)?;
                 match level.unwrap_or(GZIP_DEFAULT) {
                     GZIP_NONE => map.serialize_entry("level", "none")?,
                     GZIP_FAST => map.serialize_entry("level", "fast")?,
@@ -172,12 +294,24 @@ impl ser::Serialize for Compression {
                     level => map.serialize_entry("level", &level)?,
                 };
             }
+            Compression::Zstd(level) => {
+                map.serialize_entry("algorithm", "zstd")?;
+                match level.unwrap_or(ZSTD_DEFAULT) {
+                    ZSTD_FAST => map.serialize_entry("level", "fast")?,
+                    ZSTD_DEFAULT => map.serialize_entry("level", "default")?,
+                    ZSTD_BEST => map.serialize_entry("level", "best")?,
+                    level => map.serialize_entry("level", &level)?,
+                };
+            }
+            #[cfg(feature = "sinks-utils-snappy")]
+            Compression::Snappy => map.serialize_entry("algorithm", "snappy")?,
         };
         map.end()
     }
 }
 
-#[cfg(test)]
+// The error messages checked below list `snappy`.
+#[cfg(all(test, feature = "sinks-utils-snappy"))]
 mod test {
     use super::Compression;
 
@@ -195,6 +329,22 @@ mod test {
                 r#"{"algorithm": "gzip", "level": 8}"#,
                 Compression::Gzip(Some(8)),
             ),
+            (r#""zlib""#, Compression::Zlib(None)),
+            (
+                r#"{"algorithm": "zlib", "level": "fast"}"#,
+                Compression::Zlib(Some(1)),
+            ),
+            (r#""zstd""#, Compression::Zstd(None)),
+            (
+                r#"{"algorithm": "zstd", "level": "best"}"#,
+                Compression::Zstd(Some(21)),
+            ),
+            (
+                r#"{"algorithm": "zstd", "level": 19}"#,
+                Compression::Zstd(Some(19)),
+            ),
+            (r#""snappy""#, Compression::Snappy),
+            (r#"{"algorithm": "snappy"}"#, Compression::Snappy),
         ];
         for (sources, result) in fixtures_valid.iter() {
             let deserialized: Result<Compression, _> = serde_json::from_str(sources);
@@ -208,11 +358,11 @@ mod test {
             ),
             (
                 r#""b42""#,
-                r#"invalid value: string "b42", expected "none" or "gzip" at line 1 column 5"#,
+                r#"invalid value: string "b42", expected "none", "gzip", "zlib", "zstd" or "snappy" at line 1 column 5"#,
             ),
             (
                 r#"{"algorithm": "b42"}"#,
-                r#"unknown variant `b42`, expected `none` or `gzip` at line 1 column 20"#,
+                r#"unknown variant `b42`, expected one of `none`, `gzip`, `zlib`, `zstd`, `snappy` at line 1 column 20"#,
             ),
             (
                 r#"{"algorithm": "none", "level": "default"}"#,
@@ -230,6 +380,14 @@ mod test {
                 r#"{"algorithm": "gzip", "level": {}}"#,
                 r#"invalid type: {}, expected integer or string at line 1 column 34"#,
             ),
+            (
+                r#"{"algorithm": "zstd", "level": 22}"#,
+                r#"invalid value: 22, expected an integer between 1 and 21 at line 1 column 34"#,
+            ),
+            (
+                r#"{"algorithm": "snappy", "level": "fast"}"#,
+                r#"unknown field `level`, there are no fields at line 1 column 40"#,
+            ),
             (
                 r#"{"algorithm": "gzip", "level": "default", "key": 42}"#,
                 r#"unknown field `key`, expected `algorithm` or `level` at line 1 column 47"#,
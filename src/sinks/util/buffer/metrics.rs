@@ -200,13 +200,13 @@ impl MetricSet {
                     Some(metric.into_incremental())
                 } else {
                     // Metric changed type, store this and emit nothing
-                    self.insert(metric);
+                    self.insert_reference(metric);
                     None
                 }
             }
             None => {
                 // No reference so store this and emit nothing
-                self.insert(metric);
+                self.insert_reference(metric);
                 None
             }
         }
@@ -217,6 +217,14 @@ impl MetricSet {
         self.0.insert(series, (data, metadata));
     }
 
+    /// Store the metric as a reference value only. Its metadata is
+    /// dropped so that it doesn't hold back the finalization of the
+    /// event it came from.
+    fn insert_reference(&mut self, metric: Metric) {
+        let (series, data, _metadata) = metric.into_parts();
+        self.0.insert(series, (data, EventMetadata::default()));
+    }
+
     fn insert_update(&mut self, metric: Metric) {
         let update = match metric.kind() {
             MetricKind::Absolute => Some(metric),
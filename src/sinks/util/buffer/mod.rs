@@ -1,7 +1,7 @@
 use super::batch::{
     err_event_too_large, Batch, BatchConfig, BatchError, BatchSettings, BatchSize, PushResult,
 };
-use flate2::write::GzEncoder;
+use flate2::write::{GzEncoder, ZlibEncoder};
 use std::io::Write;
 
 pub mod compression;
@@ -9,10 +9,14 @@ pub mod json;
 #[cfg(feature = "sinks-loki")]
 pub mod loki;
 pub mod metrics;
+#[cfg(feature = "serializers-parquet")]
+pub mod parquet;
 pub mod partition;
 pub mod vec;
 
-pub use compression::{Compression, GZIP_FAST};
+#[cfg(feature = "serializers-parquet")]
+pub use self::parquet::ParquetBuffer;
+pub use compression::{Compression, GZIP_FAST, ZSTD_DEFAULT};
 pub use partition::{Partition, PartitionBuffer, PartitionInnerBuffer};
 
 #[derive(Debug)]
@@ -28,6 +32,10 @@ pub struct Buffer {
 pub enum InnerBuffer {
     Plain(Vec<u8>),
     Gzip(GzEncoder<Vec<u8>>),
+    Zlib(ZlibEncoder<Vec<u8>>),
+    Zstd(zstd::stream::write::Encoder<Vec<u8>>),
+    #[cfg(feature = "sinks-utils-snappy")]
+    Snappy(snap::write::FrameEncoder<Vec<u8>>),
 }
 
 impl Buffer {
@@ -55,6 +63,22 @@ impl Buffer {
                         flate2::Compression::new(level as u32),
                     ))
                 }
+                Compression::Zlib(level) => {
+                    let level = level.unwrap_or(GZIP_FAST);
+                    InnerBuffer::Zlib(ZlibEncoder::new(
+                        buffer,
+                        flate2::Compression::new(level as u32),
+                    ))
+                }
+                Compression::Zstd(level) => {
+                    let level = level.unwrap_or(ZSTD_DEFAULT);
+                    InnerBuffer::Zstd(
+                        zstd::stream::write::Encoder::new(buffer, level as i32)
+                            .expect("Zstd levels are validated when the config is loaded"),
+                    )
+                }
+                #[cfg(feature = "sinks-utils-snappy")]
+                Compression::Snappy => InnerBuffer::Snappy(snap::write::FrameEncoder::new(buffer)),
             }
         })
     }
@@ -68,6 +92,16 @@ impl Buffer {
             InnerBuffer::Gzip(inner) => {
                 inner.write_all(input).unwrap();
             }
+            InnerBuffer::Zlib(inner) => {
+                inner.write_all(input).unwrap();
+            }
+            InnerBuffer::Zstd(inner) => {
+                inner.write_all(input).unwrap();
+            }
+            #[cfg(feature = "sinks-utils-snappy")]
+            InnerBuffer::Snappy(inner) => {
+                inner.write_all(input).unwrap();
+            }
         }
     }
 
@@ -77,6 +111,12 @@ impl Buffer {
             .map(|inner| match inner {
                 InnerBuffer::Plain(inner) => inner.is_empty(),
                 InnerBuffer::Gzip(inner) => inner.get_ref().is_empty(),
+                InnerBuffer::Zlib(inner) => inner.get_ref().is_empty(),
+                // These encoders hold their output back until a block is complete, so the inner
+                // buffer says nothing about what has been pushed.
+                InnerBuffer::Zstd(_) => self.num_items == 0,
+                #[cfg(feature = "sinks-utils-snappy")]
+                InnerBuffer::Snappy(_) => self.num_items == 0,
             })
             .unwrap_or(true)
     }
@@ -127,6 +167,16 @@ impl Batch for Buffer {
             Some(InnerBuffer::Gzip(inner)) => inner
                 .finish()
                 .expect("This can't fail because the inner writer is a Vec"),
+            Some(InnerBuffer::Zlib(inner)) => inner
+                .finish()
+                .expect("This can't fail because the inner writer is a Vec"),
+            Some(InnerBuffer::Zstd(inner)) => inner
+                .finish()
+                .expect("This can't fail because the inner writer is a Vec"),
+            #[cfg(feature = "sinks-utils-snappy")]
+            Some(InnerBuffer::Snappy(inner)) => inner
+                .into_inner()
+                .unwrap_or_else(|_| unreachable!("The inner writer is a Vec")),
             None => Vec::new(),
         }
     }
@@ -141,7 +191,7 @@ mod test {
     use super::{Buffer, Compression};
     use crate::{
         buffers::Acker,
-        sinks::util::{BatchSettings, BatchSink, EncodedEvent},
+        sinks::util::{Batch, BatchSettings, BatchSink, EncodedEvent},
     };
     use futures::{future, stream, SinkExt, StreamExt};
     use std::{
@@ -207,4 +257,52 @@ mod test {
         .take(100_000)
         .flatten()));
     }
+
+    fn compress_batch(compression: Compression) -> Vec<u8> {
+        let batch_size = BatchSettings::default().bytes(100_000).events(1_000).size;
+        let mut buffer = Buffer::new(batch_size, compression);
+        for _ in 0..100 {
+            buffer.push(b"It's going down, I'm yelling timber\n");
+        }
+        Batch::finish(buffer)
+    }
+
+    fn expected_batch() -> Vec<u8> {
+        b"It's going down, I'm yelling timber\n".repeat(100)
+    }
+
+    #[test]
+    fn zlib() {
+        let mut decompressed = vec![];
+        flate2::read::ZlibDecoder::new(compress_batch(Compression::Zlib(None)).as_slice())
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, expected_batch());
+    }
+
+    #[test]
+    fn zstd() {
+        let output = compress_batch(Compression::Zstd(Some(5)));
+        assert!(output.len() < expected_batch().len());
+        assert_eq!(
+            zstd::stream::decode_all(output.as_slice()).unwrap(),
+            expected_batch()
+        );
+    }
+
+    #[cfg(feature = "sinks-utils-snappy")]
+    #[test]
+    fn snappy() {
+        let output = compress_batch(Compression::Snappy);
+        assert_eq!(
+            {
+                let mut decompressed = vec![];
+                snap::read::FrameDecoder::new(output.as_slice())
+                    .read_to_end(&mut decompressed)
+                    .unwrap();
+                decompressed
+            },
+            expected_batch()
+        );
+    }
 }
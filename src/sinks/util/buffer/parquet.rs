@@ -0,0 +1,123 @@
+use super::{
+    err_event_too_large, Batch, BatchConfig, BatchError, BatchSettings, BatchSize, PushResult,
+};
+use crate::sinks::util::{
+    encoding::{ParquetEncoder, ParquetRow},
+    EncodedLength,
+};
+
+/// Collects rows and writes each batch as a single Parquet file.
+///
+/// Batches are limited by the estimated size of the uncompressed values. Row groups hold at most
+/// `encoding.parquet.row_group_size` rows, or the whole batch when it isn't set.
+#[derive(Debug)]
+pub struct ParquetBuffer {
+    rows: Vec<ParquetRow>,
+    num_bytes: usize,
+    settings: BatchSize<Self>,
+    encoder: ParquetEncoder,
+}
+
+impl ParquetBuffer {
+    pub const fn new(settings: BatchSize<Self>, encoder: ParquetEncoder) -> Self {
+        Self {
+            rows: Vec::new(),
+            num_bytes: 0,
+            settings,
+            encoder,
+        }
+    }
+}
+
+impl Batch for ParquetBuffer {
+    type Input = ParquetRow;
+    type Output = Vec<u8>;
+
+    fn get_settings_defaults(
+        config: BatchConfig,
+        defaults: BatchSettings<Self>,
+    ) -> Result<BatchSettings<Self>, BatchError> {
+        Ok(config
+            .use_size_as_bytes()?
+            .get_settings_or_default(defaults))
+    }
+
+    fn push(&mut self, item: Self::Input) -> PushResult<Self::Input> {
+        let len = item.encoded_length();
+        let new_bytes = self.num_bytes + len;
+        if self.is_empty() && len > self.settings.bytes {
+            err_event_too_large(len)
+        } else if self.rows.len() >= self.settings.events || new_bytes > self.settings.bytes {
+            PushResult::Overflow(item)
+        } else {
+            self.rows.push(item);
+            self.num_bytes = new_bytes;
+            PushResult::Ok(
+                self.rows.len() >= self.settings.events || new_bytes >= self.settings.bytes,
+            )
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    fn fresh(&self) -> Self {
+        Self::new(self.settings, self.encoder.clone())
+    }
+
+    fn finish(self) -> Self::Output {
+        let row_group_size = self
+            .encoder
+            .row_group_size()
+            .unwrap_or(self.settings.events);
+        self.encoder.encode_rows(&self.rows, row_group_size)
+    }
+
+    fn num_items(&self) -> usize {
+        self.rows.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::Event,
+        sinks::util::{
+            encoding::{EncodingConfig, ParquetConfig, ParquetType, StandardEncodings},
+            Compression,
+        },
+    };
+
+    #[test]
+    fn parquet_buffer_is_limited_by_events() {
+        let mut config = EncodingConfig::from(StandardEncodings::Parquet);
+        config.parquet = Some(ParquetConfig {
+            schema: vec![("message".to_string(), ParquetType::String)]
+                .into_iter()
+                .collect(),
+            row_group_size: None,
+        });
+        let encoder = ParquetEncoder::new(config, Compression::None).unwrap();
+        let settings = BatchSettings::default().bytes(1000).events(2).size;
+        let mut buffer = ParquetBuffer::new(settings, encoder.clone());
+
+        assert_eq!(
+            buffer.push(encoder.encode_row(Event::from("one"))),
+            PushResult::Ok(false)
+        );
+        assert_eq!(
+            buffer.push(encoder.encode_row(Event::from("two"))),
+            PushResult::Ok(true)
+        );
+        assert!(matches!(
+            buffer.push(encoder.encode_row(Event::from("three"))),
+            PushResult::Overflow(_)
+        ));
+
+        let file = buffer.finish();
+        assert_eq!(&file[..4], b"PAR1");
+        assert_eq!(&file[file.len() - 4..], b"PAR1");
+    }
+}
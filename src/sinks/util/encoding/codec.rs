@@ -0,0 +1,589 @@
+//! Standard serializers shared by byte-oriented sinks.
+//!
+//! Sinks that write raw bytes (files, sockets, object stores, message queues) should use
+//! `EncodingConfig<StandardEncodings>` in their configuration and build a `StandardEncoder` from
+//! it, rather than defining their own `Encoding` enum.
+
+#[cfg(feature = "serializers-csv")]
+use crate::event::PathComponent;
+use crate::{
+    config::log_schema,
+    event::{proto, Event, LogEvent, Value},
+    sinks::util::encoding::{EncodingConfig, EncodingConfiguration},
+};
+use indexmap::IndexMap;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use std::{
+    convert::TryFrom,
+    fmt::Write as _,
+    io::{self, Write},
+};
+
+/// Encodes an input into a writer.
+pub trait Encoder<T> {
+    /// Encodes the input into the provided writer.
+    ///
+    /// # Errors
+    ///
+    /// If an I/O error is encountered while encoding the input, an error variant will be returned.
+    fn encode_input(&self, input: T, writer: &mut dyn io::Write) -> io::Result<usize>;
+
+    /// Encodes the input into a `Vec<u8>`.
+    fn encode_input_to_vec(&self, input: T) -> io::Result<Vec<u8>> {
+        let mut writer = Vec::new();
+        self.encode_input(input, &mut writer)?;
+        Ok(writer)
+    }
+}
+
+/// The serialization formats shared by byte-oriented sinks.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StandardEncodings {
+    /// The `message` field of log events, the textual representation of metrics.
+    Text,
+    /// A JSON object per event, batches of events are written as a JSON array.
+    Json,
+    /// A JSON object per event, delimited by newlines.
+    Ndjson,
+    /// Flattened `key=value` pairs of log events.
+    Logfmt,
+    /// The fields of log events as a CSV row, ordered by `only_fields` when given.
+    #[cfg(feature = "serializers-csv")]
+    Csv,
+    /// An Avro datum per log event, using the schema given in `schema`.
+    #[cfg(feature = "serializers-avro")]
+    Avro,
+    /// Vector's native protobuf representation of events.
+    Native,
+    /// A Parquet file per batch, using the columns declared in `parquet.schema`.
+    ///
+    /// This is a whole-batch format, only object storage sinks support it through
+    /// `ParquetEncoder`.
+    #[cfg(feature = "serializers-parquet")]
+    Parquet,
+}
+
+impl StandardEncodings {
+    /// The framing used between events when none is configured.
+    pub const fn default_framing(&self) -> Framing {
+        match self {
+            Self::Native => Framing::LengthDelimited,
+            #[cfg(feature = "serializers-avro")]
+            Self::Avro => Framing::LengthDelimited,
+            #[cfg(feature = "serializers-parquet")]
+            Self::Parquet => Framing::Bytes,
+            Self::Text | Self::Json | Self::Ndjson | Self::Logfmt => Framing::NewlineDelimited,
+            #[cfg(feature = "serializers-csv")]
+            Self::Csv => Framing::NewlineDelimited,
+        }
+    }
+
+    /// The MIME type of a batch of events encoded with this codec.
+    pub const fn content_type(&self) -> &'static str {
+        match self {
+            Self::Text | Self::Logfmt => "text/plain",
+            Self::Json => "application/json",
+            Self::Ndjson => "application/x-ndjson",
+            #[cfg(feature = "serializers-csv")]
+            Self::Csv => "text/csv",
+            #[cfg(feature = "serializers-avro")]
+            Self::Avro => "avro/binary",
+            Self::Native => "application/octet-stream",
+            #[cfg(feature = "serializers-parquet")]
+            Self::Parquet => "application/octet-stream",
+        }
+    }
+}
+
+/// The `encoding.parquet` options.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ParquetConfig {
+    /// The columns of the file, in order, mapping field paths to column types.
+    pub schema: IndexMap<String, ParquetType>,
+    /// The maximum number of rows in a row group.
+    ///
+    /// Defaults to the batch's `max_events`, so each batch is a single row group.
+    #[serde(default)]
+    pub row_group_size: Option<usize>,
+}
+
+/// The type of a Parquet column.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetType {
+    Boolean,
+    Int64,
+    Double,
+    String,
+    /// Milliseconds since the Unix epoch.
+    ///
+    /// Integer values, such as those produced by `timestamp_format = "unix"`, are taken as
+    /// seconds since the Unix epoch and converted to milliseconds.
+    Timestamp,
+}
+
+/// How consecutive encoded events are delimited in a byte stream.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum Framing {
+    /// Events are written back to back without any delimiter.
+    Bytes,
+    /// Each event is followed by the given character.
+    CharacterDelimited { delimiter: char },
+    /// Each event is prefixed by its length as a 32-bit big-endian integer.
+    LengthDelimited,
+    /// Each event is followed by a newline.
+    NewlineDelimited,
+}
+
+impl Framing {
+    fn write_frame(&self, frame: &[u8], writer: &mut dyn io::Write) -> io::Result<usize> {
+        match self {
+            Self::Bytes => {
+                writer.write_all(frame)?;
+                Ok(frame.len())
+            }
+            Self::CharacterDelimited { delimiter } => {
+                let mut buf = [0; 4];
+                let delimiter = delimiter.encode_utf8(&mut buf).as_bytes();
+                writer.write_all(frame)?;
+                writer.write_all(delimiter)?;
+                Ok(frame.len() + delimiter.len())
+            }
+            Self::LengthDelimited => {
+                let len = u32::try_from(frame.len()).map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "Frame is too large.")
+                })?;
+                writer.write_all(&len.to_be_bytes())?;
+                writer.write_all(frame)?;
+                Ok(frame.len() + 4)
+            }
+            Self::NewlineDelimited => {
+                writer.write_all(frame)?;
+                writer.write_all(b"\n")?;
+                Ok(frame.len() + 1)
+            }
+        }
+    }
+}
+
+/// Serializes events according to an `EncodingConfig<StandardEncodings>`.
+///
+/// The encoding rules (`only_fields`, `except_fields`, `timestamp_format`) are applied to each
+/// event before it is serialized.
+#[derive(Debug, Clone)]
+pub struct StandardEncoder {
+    config: EncodingConfig<StandardEncodings>,
+    framing: Framing,
+    #[cfg(feature = "serializers-avro")]
+    avro_schema: Option<avro_rs::Schema>,
+}
+
+impl StandardEncoder {
+    /// Creates a new `StandardEncoder`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `avro` codec is selected without a valid `schema`, or if the
+    /// `parquet` codec is selected, as it can only encode whole batches.
+    pub fn new(config: EncodingConfig<StandardEncodings>) -> crate::Result<Self> {
+        #[cfg(feature = "serializers-parquet")]
+        if *config.codec() == StandardEncodings::Parquet {
+            return Err("The `parquet` codec is only supported by object storage sinks.".into());
+        }
+        #[cfg(feature = "serializers-avro")]
+        let avro_schema = match config.codec() {
+            StandardEncodings::Avro => match config.schema() {
+                Some(schema) => Some(avro_rs::Schema::parse_str(schema)?),
+                None => {
+                    return Err(
+                        "Avro requires a schema, specify a schema with `encoding.schema`.".into(),
+                    )
+                }
+            },
+            _ => None,
+        };
+        let framing = config
+            .framing()
+            .unwrap_or_else(|| config.codec().default_framing());
+
+        Ok(Self {
+            config,
+            framing,
+            #[cfg(feature = "serializers-avro")]
+            avro_schema,
+        })
+    }
+
+    /// The codec used by this encoder.
+    pub const fn codec(&self) -> StandardEncodings {
+        self.config.codec
+    }
+
+    /// The encoding configuration of this encoder.
+    pub const fn config(&self) -> &EncodingConfig<StandardEncodings> {
+        &self.config
+    }
+
+    /// Applies the encoding rules to the event and serializes it without any framing.
+    ///
+    /// This is suitable for sinks that send each event as a separate message.
+    pub fn encode_event(&self, mut event: Event) -> io::Result<Vec<u8>> {
+        self.config.apply_rules(&mut event);
+
+        // Spans share their representation with logs, so every codec but
+        // `native` encodes them as logs.
+        let event = match event {
+            Event::Trace(trace) if self.codec() != StandardEncodings::Native => {
+                Event::Log(trace.into_log())
+            }
+            event => event,
+        };
+
+        match (self.codec(), event) {
+            (StandardEncodings::Native, event) => {
+                let event = proto::EventWrapper::from(event);
+                let mut buf = Vec::with_capacity(event.encoded_len());
+                event.encode(&mut buf).map_err(invalid_data)?;
+                Ok(buf)
+            }
+            (StandardEncodings::Text, Event::Log(log)) => Ok(log
+                .get(log_schema().message_key())
+                .map(|value| value.as_bytes().to_vec())
+                .unwrap_or_default()),
+            (StandardEncodings::Text, Event::Metric(metric)) => Ok(metric.to_string().into_bytes()),
+            (StandardEncodings::Json, Event::Log(log))
+            | (StandardEncodings::Ndjson, Event::Log(log)) => {
+                serde_json::to_vec(&log).map_err(invalid_data)
+            }
+            (StandardEncodings::Json, Event::Metric(metric))
+            | (StandardEncodings::Ndjson, Event::Metric(metric)) => {
+                serde_json::to_vec(&metric).map_err(invalid_data)
+            }
+            (StandardEncodings::Logfmt, Event::Log(log)) => Ok(encode_logfmt(&log).into_bytes()),
+            #[cfg(feature = "serializers-csv")]
+            (StandardEncodings::Csv, Event::Log(log)) => {
+                encode_csv(&log, self.config.only_fields().as_deref())
+            }
+            #[cfg(feature = "serializers-avro")]
+            (StandardEncodings::Avro, Event::Log(log)) => {
+                let schema = self
+                    .avro_schema
+                    .as_ref()
+                    .expect("Avro encoding selected but no schema found. Please report this.");
+                avro_rs::to_value(log)
+                    .and_then(|value| value.resolve(schema))
+                    .and_then(|value| avro_rs::to_avro_datum(schema, value))
+                    .map_err(invalid_data)
+            }
+            (codec, event) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "The `{:?}` codec can't encode {} events.",
+                    codec,
+                    event_type(&event)
+                ),
+            )),
+        }
+    }
+}
+
+impl Encoder<Event> for StandardEncoder {
+    /// Encodes a single event followed by the configured framing.
+    fn encode_input(&self, event: Event, writer: &mut dyn io::Write) -> io::Result<usize> {
+        let frame = self.encode_event(event)?;
+        self.framing.write_frame(&frame, writer)
+    }
+}
+
+impl Encoder<Vec<Event>> for StandardEncoder {
+    /// Encodes a batch of events.
+    ///
+    /// The `json` codec writes the batch as a JSON array, all other codecs write each event
+    /// followed by the configured framing.
+    fn encode_input(&self, events: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        match self.codec() {
+            StandardEncodings::Json => {
+                let mut written = 0;
+                writer.write_all(b"[")?;
+                written += 1;
+                for (i, event) in events.into_iter().enumerate() {
+                    if i > 0 {
+                        writer.write_all(b",")?;
+                        written += 1;
+                    }
+                    let frame = self.encode_event(event)?;
+                    writer.write_all(&frame)?;
+                    written += frame.len();
+                }
+                writer.write_all(b"]")?;
+                Ok(written + 1)
+            }
+            _ => events.into_iter().try_fold(0, |written, event| {
+                self.encode_input(event, writer)
+                    .map(|event_written| written + event_written)
+            }),
+        }
+    }
+}
+
+fn invalid_data<E>(error: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+const fn event_type(event: &Event) -> &'static str {
+    match event {
+        Event::Log(_) => "log",
+        Event::Metric(_) => "metric",
+        Event::Trace(_) => "trace",
+    }
+}
+
+fn encode_logfmt(log: &LogEvent) -> String {
+    let mut output = String::new();
+
+    for (key, value) in log.all_fields() {
+        if !output.is_empty() {
+            output.push(' ');
+        }
+        encode_logfmt_string(&mut output, &key);
+        output.push('=');
+        match value {
+            Value::Bytes(bytes) => {
+                encode_logfmt_string(&mut output, &String::from_utf8_lossy(bytes))
+            }
+            Value::Null => (),
+            value => encode_logfmt_string(&mut output, &value.to_string_lossy()),
+        }
+    }
+
+    output
+}
+
+fn encode_logfmt_string(output: &mut String, string: &str) {
+    let needs_quoting = string.is_empty()
+        || string
+            .chars()
+            .any(|c| c.is_whitespace() || c == '=' || c == '"');
+
+    if needs_quoting {
+        output.push('"');
+    }
+
+    for c in string.chars() {
+        match c {
+            '\\' => output.push_str(r#"\\"#),
+            '"' => output.push_str(r#"\""#),
+            '\n' => output.push_str(r#"\n"#),
+            _ => output
+                .write_char(c)
+                .expect("writing to a String can't fail"),
+        }
+    }
+
+    if needs_quoting {
+        output.push('"');
+    }
+}
+
+#[cfg(feature = "serializers-csv")]
+fn encode_csv(log: &LogEvent, columns: Option<&[Vec<PathComponent>]>) -> io::Result<Vec<u8>> {
+    let record: Vec<String> = match columns {
+        Some(columns) => columns
+            .iter()
+            .map(|path| {
+                let mut path = path.iter().cloned();
+                match path.next() {
+                    Some(PathComponent::Key(key)) => log
+                        .as_map()
+                        .get(&key)
+                        .and_then(|value| crate::event::util::log::get_value(value, path))
+                        .map(csv_field)
+                        .unwrap_or_default(),
+                    _ => String::new(),
+                }
+            })
+            .collect(),
+        None => log
+            .all_fields()
+            .map(|(_, value)| csv_field(value))
+            .collect(),
+    };
+
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .terminator(csv::Terminator::Any(b'\n'))
+        .from_writer(Vec::new());
+    writer.write_record(&record)?;
+    let mut buf = writer
+        .into_inner()
+        .map_err(|error| io::Error::new(io::ErrorKind::Other, error.to_string()))?;
+    // The framing takes care of delimiting records.
+    buf.pop();
+
+    Ok(buf)
+}
+
+#[cfg(feature = "serializers-csv")]
+fn csv_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        value => value.to_string_lossy(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Metric, MetricKind, MetricValue, TraceEvent};
+    use indoc::indoc;
+
+    #[derive(Deserialize, Serialize, Debug)]
+    #[serde(deny_unknown_fields)]
+    struct TestConfig {
+        encoding: EncodingConfig<StandardEncodings>,
+    }
+
+    fn encoder(toml: &str) -> StandardEncoder {
+        let config: TestConfig = toml::from_str(toml).unwrap();
+        StandardEncoder::new(config.encoding).unwrap()
+    }
+
+    fn log_event() -> Event {
+        let mut event = Event::from("hello world");
+        let log = event.as_mut_log();
+        log.remove(log_schema().timestamp_key());
+        log.insert("foo", "bar");
+        log.insert("nested.count", 3);
+        event
+    }
+
+    #[test]
+    fn encodes_text_with_newline_framing() {
+        let encoder = encoder(r#"encoding = "text""#);
+        let bytes = encoder.encode_input_to_vec(log_event()).unwrap();
+        assert_eq!(bytes, b"hello world\n");
+    }
+
+    #[test]
+    fn encodes_ndjson_batches() {
+        let encoder = encoder(r#"encoding = "ndjson""#);
+        let bytes = encoder
+            .encode_input_to_vec(vec![log_event(), log_event()])
+            .unwrap();
+        let line = r#"{"foo":"bar","message":"hello world","nested":{"count":3}}"#;
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            format!("{}\n{}\n", line, line)
+        );
+    }
+
+    #[test]
+    fn encodes_json_batches_as_array() {
+        let encoder = encoder(indoc! {r#"
+            encoding.codec = "json"
+            encoding.only_fields = ["foo"]
+        "#});
+        let bytes = encoder
+            .encode_input_to_vec(vec![log_event(), log_event()])
+            .unwrap();
+        assert_eq!(bytes, br#"[{"foo":"bar"},{"foo":"bar"}]"#);
+    }
+
+    #[test]
+    fn encodes_logfmt() {
+        let encoder = encoder(r#"encoding = "logfmt""#);
+        let bytes = encoder.encode_event(log_event()).unwrap();
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            r#"foo=bar message="hello world" nested.count=3"#
+        );
+    }
+
+    #[cfg(feature = "serializers-csv")]
+    #[test]
+    fn encodes_csv_in_only_fields_order() {
+        let encoder = encoder(indoc! {r#"
+            encoding.codec = "csv"
+            encoding.only_fields = ["nested.count", "message", "foo"]
+        "#});
+        let bytes = encoder.encode_input_to_vec(log_event()).unwrap();
+        assert_eq!(bytes, b"3,hello world,bar\n");
+    }
+
+    #[test]
+    fn encodes_with_character_delimited_framing() {
+        let encoder = encoder(indoc! {r#"
+            encoding.codec = "text"
+            encoding.framing.method = "character_delimited"
+            encoding.framing.delimiter = ";"
+        "#});
+        let bytes = encoder
+            .encode_input_to_vec(vec![log_event(), log_event()])
+            .unwrap();
+        assert_eq!(bytes, b"hello world;hello world;");
+    }
+
+    #[test]
+    fn encodes_native_with_length_delimited_framing() {
+        let encoder = encoder(r#"encoding = "native""#);
+        let event = log_event();
+        let bytes = encoder.encode_input_to_vec(event.clone()).unwrap();
+
+        let len = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+        assert_eq!(len, bytes.len() - 4);
+        let decoded = proto::EventWrapper::decode(&bytes[4..]).unwrap();
+        assert_eq!(
+            Event::from(decoded).as_log().as_map(),
+            event.as_log().as_map()
+        );
+    }
+
+    #[cfg(feature = "serializers-avro")]
+    #[test]
+    fn encodes_avro() {
+        let encoder = encoder(indoc! {r#"
+            encoding.codec = "avro"
+            encoding.only_fields = ["foo"]
+            encoding.schema = '{"type": "record", "name": "log", "fields": [{"name": "foo", "type": "string"}]}'
+        "#});
+        let bytes = encoder.encode_event(log_event()).unwrap();
+        // A zig-zag encoded string length followed by the string.
+        assert_eq!(bytes, b"\x06bar");
+    }
+
+    #[test]
+    fn encodes_traces_with_configured_codec() {
+        let encoder = encoder(indoc! {r#"
+            encoding.codec = "logfmt"
+            encoding.except_fields = ["message"]
+        "#});
+        let trace = TraceEvent::from(log_event().into_log());
+        let bytes = encoder.encode_event(trace.into()).unwrap();
+        assert_eq!(String::from_utf8(bytes).unwrap(), "foo=bar nested.count=3");
+    }
+
+    #[cfg(feature = "serializers-avro")]
+    #[test]
+    fn avro_requires_schema() {
+        let config: TestConfig = toml::from_str(r#"encoding = "avro""#).unwrap();
+        assert!(StandardEncoder::new(config.encoding).is_err());
+    }
+
+    #[test]
+    fn rejects_metrics_for_log_only_codecs() {
+        let encoder = encoder(r#"encoding = "logfmt""#);
+        let metric = Metric::new(
+            "counter",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        );
+        assert!(encoder.encode_event(metric.into()).is_err());
+    }
+}
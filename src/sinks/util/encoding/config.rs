@@ -2,7 +2,8 @@ use crate::{
     event::{PathComponent, PathIter},
     serde::skip_serializing_if_default,
     sinks::util::encoding::{
-        with_default::EncodingConfigWithDefault, EncodingConfiguration, TimestampFormat,
+        validate_framing, with_default::EncodingConfigWithDefault, EncodingConfiguration, Framing,
+        ParquetConfig, TimestampFormat,
     },
 };
 use serde::{
@@ -31,6 +32,10 @@ pub struct EncodingConfig<E> {
     pub(crate) except_fields: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
     pub(crate) timestamp_format: Option<TimestampFormat>,
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) framing: Option<Framing>,
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) parquet: Option<ParquetConfig>,
 }
 
 impl<E> EncodingConfiguration<E> for EncodingConfig<E> {
@@ -50,6 +55,12 @@ impl<E> EncodingConfiguration<E> for EncodingConfig<E> {
     fn timestamp_format(&self) -> &Option<TimestampFormat> {
         &self.timestamp_format
     }
+    fn framing(&self) -> &Option<Framing> {
+        &self.framing
+    }
+    fn parquet(&self) -> &Option<ParquetConfig> {
+        &self.parquet
+    }
 }
 
 impl<E> From<EncodingConfigWithDefault<E>> for EncodingConfig<E>
@@ -63,6 +74,8 @@ where
             only_fields: encoding.only_fields,
             except_fields: encoding.except_fields,
             timestamp_format: encoding.timestamp_format,
+            framing: encoding.framing,
+            parquet: encoding.parquet,
         }
     }
 }
@@ -79,6 +92,8 @@ impl<E> EncodingConfig<E> {
             only_fields: self.only_fields,
             except_fields: self.except_fields,
             timestamp_format: self.timestamp_format,
+            framing: self.framing,
+            parquet: self.parquet,
         }
     }
 }
@@ -91,13 +106,15 @@ impl<E> From<E> for EncodingConfig<E> {
             only_fields: Default::default(),
             except_fields: Default::default(),
             timestamp_format: Default::default(),
+            framing: Default::default(),
+            parquet: Default::default(),
         }
     }
 }
 
 impl<'de, E> Deserialize<'de> for EncodingConfig<E>
 where
-    E: DeserializeOwned + Serialize + Debug + Clone + PartialEq + Eq,
+    E: DeserializeOwned + Serialize + Debug + Clone + PartialEq + Eq + 'static,
 {
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
@@ -132,6 +149,8 @@ where
                     only_fields: Default::default(),
                     except_fields: Default::default(),
                     timestamp_format: Default::default(),
+                    framing: Default::default(),
+                    parquet: Default::default(),
                 })
             }
 
@@ -161,9 +180,14 @@ where
             }),
             except_fields: inner.except_fields,
             timestamp_format: inner.timestamp_format,
+            framing: inner.framing,
+            parquet: inner.parquet,
         };
 
-        concrete.validate().map_err(serde::de::Error::custom)?;
+        concrete
+            .validate()
+            .and_then(|_| validate_framing::<E>(&concrete.framing))
+            .map_err(serde::de::Error::custom)?;
         Ok(concrete)
     }
 }
@@ -179,4 +203,8 @@ pub struct Inner<E> {
     except_fields: Option<Vec<String>>,
     #[serde(default)]
     timestamp_format: Option<TimestampFormat>,
+    #[serde(default)]
+    framing: Option<Framing>,
+    #[serde(default)]
+    parquet: Option<ParquetConfig>,
 }
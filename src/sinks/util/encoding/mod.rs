@@ -4,7 +4,9 @@
 //!   * `EncodingConfig<E>`: For sinks without a default `Encoding`.
 //!   * `EncodingConfigWithDefault<E: Default>`: For sinks that have a default `Encoding`.
 //!
-//! Your sink should define some `Encoding` enum that is used as the `E` parameter.
+//! Byte-oriented sinks should use `StandardEncodings` as the `E` parameter and serialize events with
+//! a `StandardEncoder` built from the configuration. Other sinks can define their own `Encoding`
+//! enum.
 //!
 //! You can use either of these for a sink! They both implement `EncodingConfiguration`, which you
 //! will need to import as well.
@@ -27,19 +29,26 @@
 //!
 // TODO: To avoid users forgetting to apply the rules, the `E` param should require a trait
 //       `Encoder` that defines some `encode` function which this config then calls internally as
-//       part of it's own (yet to be written) `encode() -> Vec<u8>` function.
+//       part of it's own (yet to be written) `encode() -> Vec<u8>` function. `StandardEncoder`
+//       already does this for `StandardEncodings`.
 
+mod codec;
+pub use codec::{Encoder, Framing, ParquetConfig, ParquetType, StandardEncoder, StandardEncodings};
 mod config;
 pub use config::EncodingConfig;
+#[cfg(feature = "serializers-parquet")]
+mod parquet;
+#[cfg(feature = "serializers-parquet")]
+pub use self::parquet::{ParquetEncoder, ParquetRow};
 mod with_default;
 pub use with_default::EncodingConfigWithDefault;
 
 use crate::{
-    event::{Event, PathComponent, PathIter, Value},
+    event::{Event, LogEvent, PathComponent, PathIter, Value},
     Result,
 };
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use std::{any::TypeId, fmt::Debug};
 
 /// The behavior of a encoding configuration.
 pub trait EncodingConfiguration<E> {
@@ -51,71 +60,64 @@ pub trait EncodingConfiguration<E> {
     fn only_fields(&self) -> &Option<Vec<Vec<PathComponent>>>;
     fn except_fields(&self) -> &Option<Vec<String>>;
     fn timestamp_format(&self) -> &Option<TimestampFormat>;
+    fn framing(&self) -> &Option<Framing>;
+    fn parquet(&self) -> &Option<ParquetConfig>;
 
     fn apply_only_fields(&self, event: &mut Event) {
         if let Some(only_fields) = &self.only_fields() {
-            match event {
-                Event::Log(log_event) => {
-                    let mut to_remove = log_event
-                        .keys()
-                        .filter(|field| {
-                            let field_path = PathIter::new(field).collect::<Vec<_>>();
-                            !only_fields.iter().any(|only| {
-                                // TODO(2410): Using PathComponents here is a hack for #2407, #2410 should fix this fully.
-                                field_path.starts_with(&only[..])
-                            })
+            // Metrics don't get affected by this one!
+            if let Some(log_event) = log_fields_mut(event) {
+                let mut to_remove = log_event
+                    .keys()
+                    .filter(|field| {
+                        let field_path = PathIter::new(field).collect::<Vec<_>>();
+                        !only_fields.iter().any(|only| {
+                            // TODO(2410): Using PathComponents here is a hack for #2407, #2410 should fix this fully.
+                            field_path.starts_with(&only[..])
                         })
-                        .collect::<Vec<_>>();
+                    })
+                    .collect::<Vec<_>>();
 
-                    // reverse sort so that we delete array elements at the end first rather than
-                    // the start so that any `nulls` at the end are dropped and empty arrays are
-                    // pruned
-                    to_remove.sort_by(|a, b| b.cmp(a));
+                // reverse sort so that we delete array elements at the end first rather than
+                // the start so that any `nulls` at the end are dropped and empty arrays are
+                // pruned
+                to_remove.sort_by(|a, b| b.cmp(a));
 
-                    for removal in to_remove {
-                        log_event.remove_prune(removal, true);
-                    }
-                }
-                Event::Metric(_) => {
-                    // Metrics don't get affected by this one!
+                for removal in to_remove {
+                    log_event.remove_prune(removal, true);
                 }
             }
         }
     }
     fn apply_except_fields(&self, event: &mut Event) {
         if let Some(except_fields) = &self.except_fields() {
-            match event {
-                Event::Log(log_event) => {
-                    for field in except_fields {
-                        log_event.remove(field);
-                    }
+            // Metrics don't get affected by this one!
+            if let Some(log_event) = log_fields_mut(event) {
+                for field in except_fields {
+                    log_event.remove(field);
                 }
-                Event::Metric(_) => (), // Metrics don't get affected by this one!
             }
         }
     }
     fn apply_timestamp_format(&self, event: &mut Event) {
         if let Some(timestamp_format) = &self.timestamp_format() {
-            match event {
-                Event::Log(log_event) => {
-                    match timestamp_format {
-                        TimestampFormat::Unix => {
-                            let mut unix_timestamps = Vec::new();
-                            for (k, v) in log_event.all_fields() {
-                                if let Value::Timestamp(ts) = v {
-                                    unix_timestamps
-                                        .push((k.clone(), Value::Integer(ts.timestamp())));
-                                }
-                            }
-                            for (k, v) in unix_timestamps {
-                                log_event.insert(k, v);
+            // Metrics don't get affected by this one!
+            if let Some(log_event) = log_fields_mut(event) {
+                match timestamp_format {
+                    TimestampFormat::Unix => {
+                        let mut unix_timestamps = Vec::new();
+                        for (k, v) in log_event.all_fields() {
+                            if let Value::Timestamp(ts) = v {
+                                unix_timestamps.push((k.clone(), Value::Integer(ts.timestamp())));
                             }
                         }
-                        // RFC3339 is the default serialization of a timestamp.
-                        TimestampFormat::Rfc3339 => (),
+                        for (k, v) in unix_timestamps {
+                            log_event.insert(k, v);
+                        }
                     }
+                    // RFC3339 is the default serialization of a timestamp.
+                    TimestampFormat::Rfc3339 => (),
                 }
-                Event::Metric(_) => (), // Metrics don't get affected by this one!
             }
         }
     }
@@ -152,6 +154,25 @@ pub trait EncodingConfiguration<E> {
     }
 }
 
+/// Check that `framing` is only configured for sinks serializing with `StandardEncodings`, as
+/// other sinks have no byte stream to delimit and would silently ignore it.
+fn validate_framing<E: 'static>(framing: &Option<Framing>) -> Result<()> {
+    if framing.is_some() && TypeId::of::<E>() != TypeId::of::<StandardEncodings>() {
+        return Err("`framing` is not supported by this sink.".into());
+    }
+    Ok(())
+}
+
+/// The fields the encoding rules apply to. Spans share their representation
+/// with logs, so they're treated alike.
+fn log_fields_mut(event: &mut Event) -> Option<&mut LogEvent> {
+    match event {
+        Event::Log(log) => Some(log),
+        Event::Trace(trace) => Some(trace.as_mut_log()),
+        Event::Metric(_) => None,
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum TimestampFormat {
@@ -217,6 +238,16 @@ mod tests {
         assert!(config.is_err())
     }
 
+    const TOML_FRAMING: &str = indoc! {r#"
+        encoding.codec = "Snoot"
+        encoding.framing.method = "newline_delimited"
+    "#};
+    #[test]
+    fn framing_rejected_without_standard_encodings() {
+        let config: std::result::Result<TestConfig, _> = toml::from_str(TOML_FRAMING);
+        assert!(config.is_err())
+    }
+
     const TOML_EXCEPT_FIELD: &str = indoc! {r#"
         encoding.codec = "Snoot"
         encoding.except_fields = ["a.b.c", "b", "c[0].y", "d\\.z", "e"]
@@ -0,0 +1,538 @@
+//! Parquet serialization for object storage sinks.
+//!
+//! Parquet is a columnar format, so unlike the `StandardEncoder` codecs it can't encode events one
+//! at a time. `ParquetEncoder` turns each event into a `ParquetRow` holding the configured
+//! columns, and a batch of rows is then written out as a single Parquet file by
+//! `ParquetBuffer`.
+//!
+//! Every column is optional. Pages are compressed with the sink's `compression`, using the
+//! default level of each codec. Parquet has no zlib codec, so `zlib` is rejected.
+
+use crate::{
+    event::{Event, Value},
+    sinks::util::{
+        encoding::{EncodingConfig, EncodingConfiguration, ParquetType, StandardEncodings},
+        Compression, EncodedLength,
+    },
+};
+use ::parquet::{
+    basic::{Compression as ParquetCompression, ConvertedType, Repetition, Type as PhysicalType},
+    column::writer::ColumnWriter,
+    data_type::ByteArray,
+    errors::Result as ParquetResult,
+    file::{
+        properties::{WriterProperties, WriterPropertiesPtr},
+        writer::{FileWriter, InMemoryWriteableCursor, RowGroupWriter, SerializedFileWriter},
+    },
+    schema::types::{Type, TypePtr},
+};
+use bytes::Bytes;
+use std::sync::Arc;
+
+impl ParquetType {
+    const fn physical_type(self) -> PhysicalType {
+        match self {
+            Self::Boolean => PhysicalType::BOOLEAN,
+            Self::Int64 | Self::Timestamp => PhysicalType::INT64,
+            Self::Double => PhysicalType::DOUBLE,
+            Self::String => PhysicalType::BYTE_ARRAY,
+        }
+    }
+
+    const fn converted_type(self) -> ConvertedType {
+        match self {
+            Self::String => ConvertedType::UTF8,
+            Self::Timestamp => ConvertedType::TIMESTAMP_MILLIS,
+            Self::Boolean | Self::Int64 | Self::Double => ConvertedType::NONE,
+        }
+    }
+
+    /// Converts a field value to this column type. Values that don't fit the column are written
+    /// as nulls.
+    fn coerce(self, value: &Value) -> Option<ParquetValue> {
+        match (self, value) {
+            (_, Value::Null) => None,
+            (Self::Boolean, Value::Boolean(value)) => Some(ParquetValue::Boolean(*value)),
+            (Self::Int64, Value::Integer(value)) => Some(ParquetValue::Int64(*value)),
+            (Self::Double, Value::Float(value)) => Some(ParquetValue::Double(*value)),
+            (Self::Double, Value::Integer(value)) => Some(ParquetValue::Double(*value as f64)),
+            (Self::Timestamp, Value::Timestamp(value)) => {
+                Some(ParquetValue::Int64(value.timestamp_millis()))
+            }
+            // `timestamp_format = "unix"` turns timestamps into seconds, so integers are always
+            // read as seconds.
+            (Self::Timestamp, Value::Integer(value)) => {
+                Some(ParquetValue::Int64(value.saturating_mul(1000)))
+            }
+            (Self::String, Value::Bytes(value)) => Some(ParquetValue::Bytes(value.clone())),
+            (Self::String, value) => Some(ParquetValue::Bytes(value.to_string_lossy().into())),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ParquetValue {
+    Boolean(bool),
+    Int64(i64),
+    Double(f64),
+    Bytes(Bytes),
+}
+
+impl ParquetValue {
+    fn encoded_length(&self) -> usize {
+        match self {
+            Self::Boolean(_) => 1,
+            Self::Int64(_) | Self::Double(_) => 8,
+            Self::Bytes(value) => value.len() + 4,
+        }
+    }
+}
+
+/// The configured columns of a single event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParquetRow {
+    values: Vec<Option<ParquetValue>>,
+}
+
+impl EncodedLength for ParquetRow {
+    fn encoded_length(&self) -> usize {
+        self.values
+            .iter()
+            .map(|value| value.as_ref().map_or(0, ParquetValue::encoded_length) + 1)
+            .sum()
+    }
+}
+
+/// Converts events into `ParquetRow`s and writes batches of rows as Parquet files.
+#[derive(Debug, Clone)]
+pub struct ParquetEncoder {
+    config: EncodingConfig<StandardEncodings>,
+    columns: Arc<Vec<(String, ParquetType)>>,
+    row_group_size: Option<usize>,
+    schema: TypePtr,
+    properties: WriterPropertiesPtr,
+}
+
+impl ParquetEncoder {
+    /// Creates a new `ParquetEncoder`, compressing pages with `compression`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the codec isn't `parquet`, if no columns are declared in
+    /// `encoding.parquet.schema` or if `compression` is `zlib`.
+    pub fn new(
+        config: EncodingConfig<StandardEncodings>,
+        compression: Compression,
+    ) -> crate::Result<Self> {
+        if config.codec != StandardEncodings::Parquet {
+            return Err(
+                format!("Expected the `parquet` codec, found `{:?}`.", config.codec).into(),
+            );
+        }
+        let parquet = match config.parquet() {
+            Some(parquet) if !parquet.schema.is_empty() => parquet.clone(),
+            _ => return Err(
+                "Parquet requires a schema, declare the columns with `encoding.parquet.schema`."
+                    .into(),
+            ),
+        };
+        if parquet.row_group_size == Some(0) {
+            return Err("`encoding.parquet.row_group_size` must be greater than zero.".into());
+        }
+
+        let compression = match compression {
+            Compression::None => ParquetCompression::UNCOMPRESSED,
+            Compression::Gzip(_) => ParquetCompression::GZIP,
+            Compression::Zlib(_) => {
+                return Err("The `zlib` compression isn't supported by the `parquet` codec.".into())
+            }
+            #[cfg(feature = "sinks-utils-snappy")]
+            Compression::Snappy => ParquetCompression::SNAPPY,
+            Compression::Zstd(_) => ParquetCompression::ZSTD,
+        };
+        let properties = WriterProperties::builder()
+            .set_compression(compression)
+            .set_created_by(format!("vector version {}", crate::get_version()))
+            .build();
+
+        let mut fields = parquet
+            .schema
+            .iter()
+            .map(|(name, column_type)| {
+                Type::primitive_type_builder(name, column_type.physical_type())
+                    .with_repetition(Repetition::OPTIONAL)
+                    .with_converted_type(column_type.converted_type())
+                    .build()
+                    .map(Arc::new)
+            })
+            .collect::<ParquetResult<Vec<_>>>()?;
+        let schema = Type::group_type_builder("schema")
+            .with_fields(&mut fields)
+            .build()?;
+
+        Ok(Self {
+            columns: Arc::new(parquet.schema.into_iter().collect()),
+            row_group_size: parquet.row_group_size,
+            config,
+            schema: Arc::new(schema),
+            properties: Arc::new(properties),
+        })
+    }
+
+    /// The configured maximum number of rows in a row group.
+    pub const fn row_group_size(&self) -> Option<usize> {
+        self.row_group_size
+    }
+
+    /// Applies the encoding rules to the event and extracts the configured columns.
+    pub fn encode_row(&self, mut event: Event) -> ParquetRow {
+        self.config.apply_rules(&mut event);
+        let log = event.into_log();
+
+        let values = self
+            .columns
+            .iter()
+            .map(|(field, column_type)| log.get(field).and_then(|value| column_type.coerce(value)))
+            .collect();
+
+        ParquetRow { values }
+    }
+
+    /// Writes the rows as a Parquet file, splitting them into row groups of at most
+    /// `row_group_size` rows.
+    pub fn encode_rows(&self, rows: &[ParquetRow], row_group_size: usize) -> Vec<u8> {
+        self.write_file(rows, row_group_size).expect(
+            "This can't fail because the rows match the schema and the file is held in memory",
+        )
+    }
+
+    fn write_file(&self, rows: &[ParquetRow], row_group_size: usize) -> ParquetResult<Vec<u8>> {
+        let cursor = InMemoryWriteableCursor::default();
+        let mut writer = SerializedFileWriter::new(
+            cursor.clone(),
+            Arc::clone(&self.schema),
+            Arc::clone(&self.properties),
+        )?;
+
+        for rows in rows.chunks(row_group_size.max(1)) {
+            let mut row_group = writer.next_row_group()?;
+            write_row_group(&mut *row_group, rows)?;
+            writer.close_row_group(row_group)?;
+        }
+        writer.close()?;
+
+        Ok(cursor.data())
+    }
+}
+
+fn write_row_group(row_group: &mut dyn RowGroupWriter, rows: &[ParquetRow]) -> ParquetResult<()> {
+    let mut index = 0;
+    while let Some(mut column) = row_group.next_column()? {
+        write_column(
+            &mut column,
+            rows.iter().map(|row| row.values[index].as_ref()),
+        )?;
+        row_group.close_column(column)?;
+        index += 1;
+    }
+    Ok(())
+}
+
+/// Writes the non-null values of a column, along with the definition levels marking which rows
+/// hold a value.
+fn write_column<'a>(
+    column: &mut ColumnWriter,
+    values: impl Iterator<Item = Option<&'a ParquetValue>>,
+) -> ParquetResult<()> {
+    let mut levels = Vec::new();
+    let mut present = Vec::new();
+    for value in values {
+        levels.push(i16::from(value.is_some()));
+        present.extend(value);
+    }
+    let levels = Some(levels.as_slice());
+
+    // The values always match the column, as `ParquetType::coerce` only produces values of the
+    // column's type.
+    match column {
+        ColumnWriter::BoolColumnWriter(writer) => {
+            let values = present
+                .into_iter()
+                .filter_map(|value| match value {
+                    ParquetValue::Boolean(value) => Some(*value),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            writer.write_batch(&values, levels, None)?;
+        }
+        ColumnWriter::Int64ColumnWriter(writer) => {
+            let values = present
+                .into_iter()
+                .filter_map(|value| match value {
+                    ParquetValue::Int64(value) => Some(*value),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            writer.write_batch(&values, levels, None)?;
+        }
+        ColumnWriter::DoubleColumnWriter(writer) => {
+            let values = present
+                .into_iter()
+                .filter_map(|value| match value {
+                    ParquetValue::Double(value) => Some(*value),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            writer.write_batch(&values, levels, None)?;
+        }
+        ColumnWriter::ByteArrayColumnWriter(writer) => {
+            let values = present
+                .into_iter()
+                .filter_map(|value| match value {
+                    ParquetValue::Bytes(value) => Some(ByteArray::from(value.to_vec())),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            writer.write_batch(&values, levels, None)?;
+        }
+        _ => unreachable!("Only the physical types of `ParquetType` are declared"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sinks::util::encoding::ParquetConfig;
+    use ::parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::Field,
+        util::cursor::SliceableCursor,
+    };
+    use chrono::{TimeZone, Utc};
+
+    fn encoder(schema: &[(&str, ParquetType)], compression: Compression) -> ParquetEncoder {
+        let mut config = EncodingConfig::from(StandardEncodings::Parquet);
+        config.parquet = Some(ParquetConfig {
+            schema: schema
+                .iter()
+                .map(|(name, column_type)| (name.to_string(), *column_type))
+                .collect(),
+            row_group_size: None,
+        });
+        ParquetEncoder::new(config, compression).unwrap()
+    }
+
+    fn reader(file: Vec<u8>) -> SerializedFileReader<SliceableCursor> {
+        SerializedFileReader::new(SliceableCursor::new(file)).unwrap()
+    }
+
+    /// Reads the file back column by column.
+    fn read_parquet(file: Vec<u8>) -> Vec<(String, Vec<Option<ParquetValue>>)> {
+        let reader = reader(file);
+        let mut columns = reader
+            .metadata()
+            .file_metadata()
+            .schema_descr()
+            .columns()
+            .iter()
+            .map(|column| (column.name().to_owned(), Vec::new()))
+            .collect::<Vec<_>>();
+
+        for row in reader.get_row_iter(None).unwrap() {
+            for (index, (_, field)) in row.get_column_iter().enumerate() {
+                let value = match field {
+                    Field::Null => None,
+                    Field::Bool(value) => Some(ParquetValue::Boolean(*value)),
+                    Field::Long(value) => Some(ParquetValue::Int64(*value)),
+                    Field::Double(value) => Some(ParquetValue::Double(*value)),
+                    Field::Str(value) => Some(ParquetValue::Bytes(value.clone().into())),
+                    Field::TimestampMillis(value) => Some(ParquetValue::Int64(*value as i64)),
+                    field => panic!("Unexpected field {:?}", field),
+                };
+                columns[index].1.push(value);
+            }
+        }
+
+        columns
+    }
+
+    #[test]
+    fn parquet_requires_schema() {
+        let config = EncodingConfig::from(StandardEncodings::Parquet);
+        assert!(ParquetEncoder::new(config, Compression::None).is_err());
+    }
+
+    #[test]
+    fn parquet_rejects_zlib() {
+        let mut config = EncodingConfig::from(StandardEncodings::Parquet);
+        config.parquet = Some(ParquetConfig {
+            schema: vec![("message".to_string(), ParquetType::String)]
+                .into_iter()
+                .collect(),
+            row_group_size: None,
+        });
+        let error = ParquetEncoder::new(config, Compression::Zlib(None)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The `zlib` compression isn't supported by the `parquet` codec."
+        );
+    }
+
+    #[test]
+    fn parquet_encodes_row_with_configured_columns() {
+        let encoder = encoder(
+            &[
+                ("message", ParquetType::String),
+                ("status", ParquetType::Int64),
+                ("ok", ParquetType::Boolean),
+                ("missing", ParquetType::Double),
+                ("timestamp", ParquetType::Timestamp),
+            ],
+            Compression::None,
+        );
+
+        let mut event = Event::from("hello");
+        event.as_mut_log().insert("status", 200);
+        event.as_mut_log().insert("ok", true);
+        event.as_mut_log().insert("extra", "dropped");
+        event
+            .as_mut_log()
+            .insert("timestamp", Utc.timestamp_millis(1_600_000_000_123));
+
+        assert_eq!(
+            encoder.encode_row(event).values,
+            vec![
+                Some(ParquetValue::Bytes("hello".into())),
+                Some(ParquetValue::Int64(200)),
+                Some(ParquetValue::Boolean(true)),
+                None,
+                Some(ParquetValue::Int64(1_600_000_000_123)),
+            ]
+        );
+    }
+
+    #[test]
+    fn parquet_round_trips() {
+        let mut compressions = vec![
+            Compression::None,
+            Compression::gzip_default(),
+            Compression::Zstd(None),
+        ];
+        #[cfg(feature = "sinks-utils-snappy")]
+        compressions.push(Compression::Snappy);
+
+        for compression in compressions {
+            let encoder = encoder(
+                &[
+                    ("message", ParquetType::String),
+                    ("status", ParquetType::Int64),
+                    ("ok", ParquetType::Boolean),
+                    ("duration", ParquetType::Double),
+                    ("timestamp", ParquetType::Timestamp),
+                ],
+                compression,
+            );
+
+            let rows = (0..3)
+                .map(|i| {
+                    let mut event = Event::from(format!("line {}", i));
+                    let log = event.as_mut_log();
+                    log.insert("status", 200 + i);
+                    if i != 1 {
+                        log.insert("ok", i == 0);
+                        log.insert("duration", i as f64 / 2.0);
+                    }
+                    log.insert("timestamp", Utc.timestamp_millis(1_600_000_000_000 + i));
+                    encoder.encode_row(event)
+                })
+                .collect::<Vec<_>>();
+
+            let file = encoder.encode_rows(&rows, 2);
+            assert_eq!(reader(file.clone()).num_row_groups(), 2);
+
+            assert_eq!(
+                read_parquet(file),
+                vec![
+                    (
+                        "message".to_owned(),
+                        vec![
+                            Some(ParquetValue::Bytes("line 0".into())),
+                            Some(ParquetValue::Bytes("line 1".into())),
+                            Some(ParquetValue::Bytes("line 2".into())),
+                        ]
+                    ),
+                    (
+                        "status".to_owned(),
+                        vec![
+                            Some(ParquetValue::Int64(200)),
+                            Some(ParquetValue::Int64(201)),
+                            Some(ParquetValue::Int64(202)),
+                        ]
+                    ),
+                    (
+                        "ok".to_owned(),
+                        vec![
+                            Some(ParquetValue::Boolean(true)),
+                            None,
+                            Some(ParquetValue::Boolean(false)),
+                        ]
+                    ),
+                    (
+                        "duration".to_owned(),
+                        vec![
+                            Some(ParquetValue::Double(0.0)),
+                            None,
+                            Some(ParquetValue::Double(1.0)),
+                        ]
+                    ),
+                    (
+                        "timestamp".to_owned(),
+                        vec![
+                            Some(ParquetValue::Int64(1_600_000_000_000)),
+                            Some(ParquetValue::Int64(1_600_000_000_001)),
+                            Some(ParquetValue::Int64(1_600_000_000_002)),
+                        ]
+                    ),
+                ],
+                "{} didn't round trip",
+                compression
+            );
+        }
+    }
+
+    #[test]
+    fn parquet_reads_integer_timestamps_as_seconds() {
+        let encoder = encoder(&[("timestamp", ParquetType::Timestamp)], Compression::None);
+        let mut event = Event::from("hello");
+        event.as_mut_log().insert("timestamp", 1_600_000_000);
+
+        assert_eq!(
+            encoder.encode_row(event).values,
+            vec![Some(ParquetValue::Int64(1_600_000_000_000))]
+        );
+    }
+
+    #[test]
+    fn parquet_compresses_pages() {
+        let mut compressions = vec![
+            (Compression::gzip_default(), ParquetCompression::GZIP),
+            (Compression::Zstd(None), ParquetCompression::ZSTD),
+        ];
+        #[cfg(feature = "sinks-utils-snappy")]
+        compressions.push((Compression::Snappy, ParquetCompression::SNAPPY));
+        for (compression, codec) in compressions {
+            let encoder = encoder(&[("message", ParquetType::String)], compression);
+            let rows = vec![encoder.encode_row(Event::from("a".repeat(1000)))];
+
+            let file = encoder.encode_rows(&rows, 10);
+
+            assert!(file.len() < 1000, "{} wasn't applied", compression);
+            assert_eq!(
+                reader(file).metadata().row_group(0).column(0).compression(),
+                codec
+            );
+        }
+    }
+}
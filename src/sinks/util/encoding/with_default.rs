@@ -1,7 +1,9 @@
 use crate::{
     event::{PathComponent, PathIter},
     serde::skip_serializing_if_default,
-    sinks::util::encoding::{EncodingConfiguration, TimestampFormat},
+    sinks::util::encoding::{
+        validate_framing, EncodingConfiguration, Framing, ParquetConfig, TimestampFormat,
+    },
 };
 use serde::{
     de::{self, DeserializeOwned, Deserializer, IntoDeserializer, MapAccess, Visitor},
@@ -34,6 +36,10 @@ pub struct EncodingConfigWithDefault<E: Default + PartialEq> {
     /// Format for outgoing timestamps.
     #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
     pub(crate) timestamp_format: Option<TimestampFormat>,
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) framing: Option<Framing>,
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) parquet: Option<ParquetConfig>,
 }
 
 impl<E: Default + PartialEq> EncodingConfiguration<E> for EncodingConfigWithDefault<E> {
@@ -53,6 +59,12 @@ impl<E: Default + PartialEq> EncodingConfiguration<E> for EncodingConfigWithDefa
     fn timestamp_format(&self) -> &Option<TimestampFormat> {
         &self.timestamp_format
     }
+    fn framing(&self) -> &Option<Framing> {
+        &self.framing
+    }
+    fn parquet(&self) -> &Option<ParquetConfig> {
+        &self.parquet
+    }
 }
 
 impl<E> From<E> for EncodingConfigWithDefault<E>
@@ -66,13 +78,15 @@ where
             only_fields: Default::default(),
             except_fields: Default::default(),
             timestamp_format: Default::default(),
+            framing: Default::default(),
+            parquet: Default::default(),
         }
     }
 }
 
 impl<'de, E> Deserialize<'de> for EncodingConfigWithDefault<E>
 where
-    E: DeserializeOwned + Serialize + Debug + Clone + PartialEq + Eq + Default,
+    E: DeserializeOwned + Serialize + Debug + Clone + PartialEq + Eq + Default + 'static,
 {
     // Derived from https://serde.rs/string-or-struct.html
     #[allow(dead_code)] // For supporting `--no-default-features`
@@ -109,6 +123,8 @@ where
                     only_fields: Default::default(),
                     except_fields: Default::default(),
                     timestamp_format: Default::default(),
+                    framing: Default::default(),
+                    parquet: Default::default(),
                 })
             }
 
@@ -138,9 +154,14 @@ where
             }),
             except_fields: inner.except_fields,
             timestamp_format: inner.timestamp_format,
+            framing: inner.framing,
+            parquet: inner.parquet,
         };
 
-        concrete.validate().map_err(de::Error::custom)?;
+        concrete
+            .validate()
+            .and_then(|_| validate_framing::<E>(&concrete.framing))
+            .map_err(de::Error::custom)?;
         Ok(concrete)
     }
 }
@@ -157,4 +178,8 @@ pub struct InnerWithDefault<E: Default> {
     except_fields: Option<Vec<String>>,
     #[serde(default)]
     timestamp_format: Option<TimestampFormat>,
+    #[serde(default)]
+    framing: Option<Framing>,
+    #[serde(default)]
+    parquet: Option<ParquetConfig>,
 }
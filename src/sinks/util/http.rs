@@ -1,4 +1,5 @@
 use super::{
+    encode_or_reject,
     retries::{RetryAction, RetryLogic},
     sink::{self, ServiceLogic},
     Batch, EncodedEvent, Partition, TowerBatchedSink, TowerPartitionSink, TowerRequestConfig,
@@ -174,7 +175,7 @@ where
     }
 
     fn start_send(self: Pin<&mut Self>, item: Event) -> Result<(), Self::Error> {
-        if let Some(item) = self.sink.encode_event(item) {
+        if let Some(item) = encode_or_reject(item, |event| self.sink.encode_event(event)) {
             *self.project().slot = Some(item);
         }
 
@@ -320,7 +321,7 @@ where
     }
 
     fn start_send(self: Pin<&mut Self>, item: Event) -> Result<(), Self::Error> {
-        if let Some(item) = self.sink.encode_event(item) {
+        if let Some(item) = encode_or_reject(item, |event| self.sink.encode_event(event)) {
             *self.project().slot = Some(item);
         }
 
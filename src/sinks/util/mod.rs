@@ -16,9 +16,9 @@ pub mod udp;
 pub mod unix;
 pub mod uri;
 
-use crate::event::{Event, EventMetadata, LogEvent};
+use crate::event::{Event, EventMetadata, EventStatus, LogEvent};
 use bytes::Bytes;
-use encoding::{EncodingConfig, EncodingConfiguration};
+use encoding::{Encoder, StandardEncoder, StandardEncodings};
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
 use std::borrow::Cow;
@@ -27,6 +27,8 @@ pub use batch::{Batch, BatchConfig, BatchSettings, BatchSize, PushResult};
 pub use buffer::json::{BoxedRawValue, JsonArrayBuffer};
 pub use buffer::partition::Partition;
 pub use buffer::vec::{EncodedLength, VecBuffer};
+#[cfg(feature = "serializers-parquet")]
+pub use buffer::ParquetBuffer;
 pub use buffer::{Buffer, Compression, PartitionBuffer, PartitionInnerBuffer};
 pub use service::{
     Concurrency, ServiceBuilderExt, TowerBatchedSink, TowerPartitionSink, TowerRequestConfig,
@@ -94,39 +96,49 @@ pub enum Encoding {
     Json,
 }
 
+impl From<Encoding> for StandardEncodings {
+    fn from(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Text => StandardEncodings::Text,
+            Encoding::Json => StandardEncodings::Json,
+        }
+    }
+}
+
 /**
 * Encodes the given event into raw bytes that can be sent into a Sink, according to
-* the given encoding. If there are any errors encoding the event, logs a warning
+* the given encoder. If there are any errors encoding the event, logs a warning
 * and returns None.
 **/
-pub fn encode_event(
-    mut event: Event,
-    encoding: &EncodingConfig<Encoding>,
-) -> Option<EncodedEvent<Bytes>> {
-    encoding.apply_rules(&mut event);
-    let log = event.into_log();
-
-    let b = match encoding.codec() {
-        Encoding::Json => serde_json::to_vec(&log),
-        Encoding::Text => {
-            let bytes = log
-                .get(crate::config::log_schema().message_key())
-                .map(|v| v.as_bytes().to_vec())
-                .unwrap_or_default();
-            Ok(bytes)
-        }
-    };
-    let (_fields, metadata) = log.into_parts();
+pub fn encode_event(event: Event, encoder: &StandardEncoder) -> Option<EncodedEvent<Bytes>> {
+    let metadata = event.metadata().clone();
 
-    b.map(|mut b| {
-        b.push(b'\n');
-        EncodedEvent {
-            item: Bytes::from(b),
+    match encoder.encode_input_to_vec(event) {
+        Ok(bytes) => Some(EncodedEvent {
+            item: Bytes::from(bytes),
             metadata: Some(metadata),
+        }),
+        Err(error) => {
+            error!(message = "Unable to encode.", %error);
+            metadata.update_status(EventStatus::Failed);
+            None
         }
-    })
-    .map_err(|error| error!(message = "Unable to encode.", %error))
-    .ok()
+    }
+}
+
+/// Encodes an event with the given function, marking the event as failed when
+/// it can't be encoded so that its source, and the sink's `dropped` output,
+/// know the sink rejected it.
+pub fn encode_or_reject<I>(
+    event: Event,
+    encode: impl FnOnce(Event) -> Option<EncodedEvent<I>>,
+) -> Option<EncodedEvent<I>> {
+    let metadata = event.metadata().clone();
+    let encoded = encode(event);
+    if encoded.is_none() {
+        metadata.update_status(EventStatus::Failed);
+    }
+    encoded
 }
 
 /// Joins namespace with name via delimiter if namespace is present.
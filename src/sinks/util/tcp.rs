@@ -10,6 +10,7 @@ use crate::{
     sink::VecSinkExt,
     sinks::{
         util::{
+            encode_or_reject,
             retries::ExponentialBackoff,
             socket_bytes_sink::{BytesSink, ShutdownCheck},
             EncodedEvent, SinkBuildError, StreamSink,
@@ -254,17 +255,17 @@ impl StreamSink for TcpSink {
         // connection only when we have something to send.
         let encode_event = Arc::clone(&self.encode_event);
         let mut input = input
-            .map(|event| encode_event(event).unwrap_or_else(|| EncodedEvent::new(Bytes::new())))
+            .map(|event| {
+                encode_or_reject(event, &*encode_event)
+                    .unwrap_or_else(|| EncodedEvent::new(Bytes::new()))
+            })
             .peekable();
 
         while Pin::new(&mut input).peek().await.is_some() {
             let mut sink = self.connect().await;
             let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
 
-            let result = match sink
-                .send_all_peekable(&mut (&mut input).map(|item| item.item).peekable())
-                .await
-            {
+            let result = match sink.send_all_peekable(&mut (&mut input).peekable()).await {
                 Ok(()) => sink.close().await,
                 Err(error) => Err(error),
             };
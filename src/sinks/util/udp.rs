@@ -4,13 +4,13 @@ use crate::{
     buffers::Acker,
     config::SinkContext,
     dns,
-    event::Event,
+    event::{Event, EventStatus},
     internal_events::{
         SocketEventsSent, SocketMode, UdpSendIncomplete, UdpSocketConnectionEstablished,
         UdpSocketConnectionFailed, UdpSocketError,
     },
     sinks::{
-        util::{retries::ExponentialBackoff, EncodedEvent, StreamSink},
+        util::{encode_or_reject, retries::ExponentialBackoff, EncodedEvent, StreamSink},
         Healthcheck, VectorSink,
     },
 };
@@ -255,12 +255,20 @@ impl StreamSink for UdpSink {
             while let Some(event) = input.next().await {
                 self.acker.ack(1);
 
-                let input = match (self.encode_event)(event) {
+                let input = match encode_or_reject(event, &self.encode_event) {
                     Some(input) => input,
                     None => continue,
                 };
 
-                match udp_send(&mut socket, &input.item).await {
+                let result = udp_send(&mut socket, &input.item).await;
+                if let Some(metadata) = &input.metadata {
+                    metadata.update_status(match result {
+                        Ok(()) => EventStatus::Delivered,
+                        Err(_) => EventStatus::Errored,
+                    });
+                }
+
+                match result {
                     Ok(()) => emit!(SocketEventsSent {
                         mode: SocketMode::Udp,
                         count: 1,
@@ -9,6 +9,7 @@ use crate::{
     sink::VecSinkExt,
     sinks::{
         util::{
+            encode_or_reject,
             retries::ExponentialBackoff,
             socket_bytes_sink::{BytesSink, ShutdownCheck},
             EncodedEvent, StreamSink,
@@ -136,17 +137,17 @@ impl StreamSink for UnixSink {
     async fn run(&mut self, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let encode_event = Arc::clone(&self.encode_event);
         let mut input = input
-            .map(|event| encode_event(event).unwrap_or_else(|| EncodedEvent::new(Bytes::new())))
+            .map(|event| {
+                encode_or_reject(event, &*encode_event)
+                    .unwrap_or_else(|| EncodedEvent::new(Bytes::new()))
+            })
             .peekable();
 
         while Pin::new(&mut input).peek().await.is_some() {
             let mut sink = self.connect().await;
             let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
 
-            let result = match sink
-                .send_all_peekable(&mut (&mut input).map(|item| item.item).peekable())
-                .await
-            {
+            let result = match sink.send_all_peekable(&mut (&mut input).peekable()).await {
                 Ok(()) => sink.close().await,
                 Err(error) => Err(error),
             };
@@ -166,7 +167,10 @@ impl StreamSink for UnixSink {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::sinks::util::{encode_event, Encoding};
+    use crate::sinks::util::{
+        encode_event,
+        encoding::{StandardEncoder, StandardEncodings},
+    };
     use crate::test_util::{random_lines_with_stream, CountReceiver};
     use tokio::net::UnixListener;
 
@@ -205,9 +209,9 @@ mod tests {
         // Set up Sink
         let config = UnixSinkConfig::new(out_path);
         let cx = SinkContext::new_test();
-        let encoding = Encoding::Text.into();
+        let encoder = StandardEncoder::new(StandardEncodings::Text.into()).unwrap();
         let (sink, _healthcheck) = config
-            .build(cx, move |event| encode_event(event, &encoding))
+            .build(cx, move |event| encode_event(event, &encoder))
             .unwrap();
 
         // Send the test data
@@ -267,17 +267,31 @@ mod tests {
         sinks::util::test::build_test_server_status,
         test_util::{next_addr, random_lines_with_stream},
     };
-    use bytes::Bytes;
+    use bytes::{Bytes, BytesMut};
     use futures::{channel::mpsc, StreamExt};
     use http::{request::Parts, StatusCode};
     use hyper::Method;
-    use vector_core::event::{BatchNotifier, BatchStatus};
+    use vector_core::event::{BatchNotifier, BatchStatus, TraceEvent};
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<VectorConfig>();
     }
 
+    #[test]
+    fn encodes_trace_events() {
+        let mut trace = TraceEvent::default();
+        trace.insert("span_id", "abc123");
+        trace.insert("duration_ns", 42);
+
+        let encoded = encode_event(Event::Trace(trace.clone()));
+        let mut buffer = BytesMut::with_capacity(encoded.item.encoded_len());
+        encoded.item.encode(&mut buffer).unwrap();
+
+        let decoded: Event = EventWrapper::decode(buffer).unwrap().into();
+        assert_eq!(decoded, Event::Trace(trace));
+    }
+
     #[tokio::test]
     async fn deliver_message() {
         let num_lines = 10;
@@ -1,4 +1,5 @@
 use crate::{
+    codecs::{BytesDecoderConfig, JsonParserConfig},
     config::{
         log_schema, DataType, GenerateConfig, Resource, SourceConfig, SourceContext,
         SourceDescription,
@@ -6,7 +7,7 @@ use crate::{
     event::Event,
     sources::{
         self,
-        util::{decode_body, Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig},
+        util::{BodyDecoder, ErrorMessage, HttpSource, HttpSourceAuthConfig},
     },
     tls::TlsConfig,
 };
@@ -54,6 +55,7 @@ impl SourceConfig for DatadogLogsConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
         let source = DatadogLogsSource {
             store_api_key: self.store_api_key,
+            decoder: BodyDecoder::new(&BytesDecoderConfig::new(), &JsonParserConfig::new())?,
         };
         // We accept /v1/input & /v1/input/<API_KEY>
         source.run(self.address, "/v1/input", false, &self.tls, &self.auth, cx)
@@ -72,9 +74,10 @@ impl SourceConfig for DatadogLogsConfig {
     }
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone)]
 struct DatadogLogsSource {
     store_api_key: bool,
+    decoder: BodyDecoder,
 }
 
 impl HttpSource for DatadogLogsSource {
@@ -100,7 +103,7 @@ impl HttpSource for DatadogLogsSource {
         }
         .map(Arc::from);
 
-        decode_body(body, Encoding::Json).map(|mut events| {
+        self.decoder.decode(body).map(|mut events| {
             // Datadog API key in metadata & source type field
             let key = log_schema().source_type_key();
             for event in &mut events {
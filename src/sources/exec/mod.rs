@@ -1,28 +1,31 @@
 use crate::async_read::VecAsyncReadExt;
 use crate::config::{DataType, SourceContext};
-use crate::event::LogEvent;
-use crate::internal_events::{ExecCommandExecuted, ExecTimeout};
+use crate::event::{LogEvent, Metric};
+use crate::internal_events::{ExecCommandExecuted, ExecParseError, ExecTimeout};
 use crate::{
     config::{log_schema, SourceConfig, SourceDescription},
     event::Event,
     internal_events::{ExecEventReceived, ExecFailed},
     shutdown::ShutdownSignal,
+    sources::prometheus::parser,
     Pipeline,
 };
 use bytes::Bytes;
 use chrono::Utc;
 use futures::{FutureExt, SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 use snafu::Snafu;
 use std::io::{Error, ErrorKind};
 use std::path::PathBuf;
 use std::process::ExitStatus;
 use tokio::io::{AsyncRead, BufReader};
-use tokio::process::Command;
+use tokio::process::{Child, Command};
 use tokio::sync::mpsc::{channel, Sender};
 use tokio::time::{self, sleep, Duration, Instant};
 use tokio_stream::wrappers::IntervalStream;
 use tokio_util::codec::{FramedRead, LinesCodec};
+use uuid::Uuid;
 
 pub mod sized_bytes_codec;
 
@@ -40,6 +43,8 @@ pub struct ExecConfig {
     pub event_per_line: bool,
     #[serde(default = "default_maximum_buffer_size")]
     pub maximum_buffer_size_bytes: usize,
+    pub decoding: Decoding,
+    pub run_metadata: bool,
 }
 
 // TODO: Would be nice to combine the scheduled and streaming config with the mode enum once
@@ -51,6 +56,27 @@ pub enum Mode {
     Streaming,
 }
 
+/// How the standard output of the command is turned into events.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Decoding {
+    /// Each line, or the whole output without `event_per_line`, is the
+    /// message of a log event.
+    Lines,
+    /// Each line, or the whole output without `event_per_line`, is a JSON
+    /// object whose fields are added to a log event.
+    Json,
+    /// The whole output is in the Prometheus text exposition format, and
+    /// each of its samples is a metric event.
+    Prometheus,
+}
+
+impl Default for Decoding {
+    fn default() -> Self {
+        Self::Lines
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct ScheduledConfig {
@@ -88,6 +114,8 @@ impl Default for ExecConfig {
             include_stderr: default_include_stderr(),
             event_per_line: default_events_per_line(),
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
+            decoding: Decoding::default(),
+            run_metadata: false,
         }
     }
 }
@@ -127,6 +155,12 @@ const STDERR: &str = "stderr";
 const STREAM_KEY: &str = "stream";
 const PID_KEY: &str = "pid";
 const COMMAND_KEY: &str = "command";
+const RUN_ID_KEY: &str = "run_id";
+const EXIT_CODE_KEY: &str = "exit_code";
+const RUN_DURATION_KEY: &str = "run_duration_seconds";
+const RUN_SUMMARY_KEY: &str = "run_summary";
+const EVENT_COUNT_KEY: &str = "event_count";
+const TIMED_OUT_KEY: &str = "timed_out";
 
 inventory::submit! {
     SourceDescription::new::<ExecConfig>("exec")
@@ -204,7 +238,11 @@ impl SourceConfig for ExecConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        match self.decoding {
+            Decoding::Lines | Decoding::Json => DataType::Log,
+            // Errors and run summaries are still logs.
+            Decoding::Prometheus => DataType::Any,
+        }
     }
 
     fn source_type(&self) -> &'static str {
@@ -225,34 +263,22 @@ async fn run_scheduled(
     let mut interval = IntervalStream::new(time::interval(schedule)).take_until(shutdown.clone());
 
     while interval.next().await.is_some() {
-        // Wait for our task to finish, wrapping it in a timeout
-        let timeout = tokio::time::timeout(
-            schedule,
-            run_command(
-                config.clone(),
-                hostname.clone(),
-                shutdown.clone(),
-                out.clone(),
-            ),
-        );
-
-        let timeout_result = timeout.await;
+        // Wait for our task to finish, killing the command if it's still
+        // running at the next tick
+        let output = run_command(
+            config.clone(),
+            hostname.clone(),
+            Some(schedule),
+            shutdown.clone(),
+            out.clone(),
+        )
+        .await;
 
-        match timeout_result {
-            Ok(output) => {
-                if let Err(command_error) = output {
-                    emit!(ExecFailed {
-                        command: config.command_line().as_str(),
-                        error: command_error,
-                    });
-                }
-            }
-            Err(_) => {
-                emit!(ExecTimeout {
-                    command: config.command_line().as_str(),
-                    elapsed_seconds: schedule.as_secs(),
-                });
-            }
+        if let Err(command_error) = output {
+            emit!(ExecFailed {
+                command: config.command_line().as_str(),
+                error: command_error,
+            });
         }
     }
 
@@ -275,7 +301,7 @@ async fn run_streaming(
         loop {
             tokio::select! {
                 _ = shutdown.clone() => break, // will break early if a shutdown is started
-                output = run_command(config.clone(), hostname.clone(), shutdown.clone(), out.clone()) => {
+                output = run_command(config.clone(), hostname.clone(), None, shutdown.clone(), out.clone()) => {
                     // handle command finished
                     if let Err(command_error) = output {
                         emit!(ExecFailed {
@@ -297,7 +323,7 @@ async fn run_streaming(
             }
         }
     } else {
-        let output = run_command(config.clone(), hostname, shutdown, out).await;
+        let output = run_command(config.clone(), hostname, None, shutdown, out).await;
 
         if let Err(command_error) = output {
             emit!(ExecFailed {
@@ -313,6 +339,7 @@ async fn run_streaming(
 async fn run_command(
     config: ExecConfig,
     hostname: Option<String>,
+    timeout: Option<Duration>,
     shutdown: ShutdownSignal,
     mut out: Pipeline,
 ) -> Result<Option<ExitStatus>, Error> {
@@ -358,48 +385,145 @@ async fn run_command(
 
     let pid = child.id();
 
+    // The Prometheus text format is parsed as a whole once the command
+    // exits, so its lines are read one by one and joined back.
+    let event_per_line = config.event_per_line || config.decoding == Decoding::Prometheus;
     spawn_reader_thread(
         stdout_reader,
-        config.event_per_line,
+        event_per_line,
         config.maximum_buffer_size_bytes,
         STDOUT,
         sender,
     );
 
-    while let Some((line, stream)) = receiver.recv().await {
-        let event = create_event(&config, &hostname, line, &Some(stream.to_string()), pid);
+    let run_id = if config.run_metadata {
+        Some(Uuid::new_v4().to_string())
+    } else {
+        None
+    };
+    // The exit code and duration of a run are only known once the command
+    // exits, so the events of scheduled runs are held until then.
+    let hold_events = run_id.is_some() && matches!(config.mode, Mode::Scheduled);
+    let mut held_events = Vec::new();
+    let mut metrics_text = String::new();
+    let mut event_count = 0_usize;
+
+    let run = async {
+        while let Some((line, stream)) = receiver.recv().await {
+            if stream == STDOUT && config.decoding == Decoding::Prometheus {
+                metrics_text.push_str(&String::from_utf8_lossy(&line));
+                metrics_text.push('\n');
+                continue;
+            }
+
+            let mut event = create_event(&config, &hostname, line, &Some(stream.to_string()), pid);
+            if stream == STDOUT && config.decoding == Decoding::Json {
+                decode_json(&config, event.as_mut_log());
+            }
+            if let Some(run_id) = &run_id {
+                event.as_mut_log().insert(RUN_ID_KEY, run_id.clone());
+            }
+            event_count += 1;
 
-        let _ = out
-            .send(event)
-            .await
-            .map_err(|_: crate::pipeline::ClosedError| {
-                error!(message = "Failed to forward events; downstream is closed.");
-            });
-    }
+            if hold_events {
+                held_events.push(event);
+            } else {
+                send_event(&mut out, event).await;
+            }
+        }
+
+        wait_for_exit(&mut child, shutdown).await
+    };
+    let result = match timeout {
+        Some(timeout) => time::timeout(timeout, run).await.ok(),
+        None => Some(run.await),
+    };
 
     let elapsed = start.elapsed();
 
-    let result = match child.try_wait() {
-        Ok(Some(exit_status)) => {
-            handle_exit_status(&config, exit_status.code(), elapsed);
-            Ok(Some(exit_status))
-        }
-        Ok(None) => {
-            handle_exit_status(&config, None, elapsed);
-            Ok(None)
+    let timed_out = result.is_none();
+    let exit_status = match result {
+        Some(exit_status) => exit_status,
+        None => {
+            emit!(ExecTimeout {
+                command: config.command_line().as_str(),
+                elapsed_seconds: elapsed.as_secs(),
+            });
+            let _ = child.kill().await;
+            None
         }
-        Err(error) => {
-            error!(message = "Unable to obtain exit status.", %error);
+    };
+    let exit_code = exit_status.and_then(|exit_status| exit_status.code());
+    handle_exit_status(&config, exit_code, elapsed);
 
-            handle_exit_status(&config, None, elapsed);
-            Ok(None)
+    for mut event in held_events {
+        insert_exit_metadata(event.as_mut_log(), exit_code, elapsed);
+        send_event(&mut out, event).await;
+    }
+
+    if !metrics_text.is_empty() {
+        match parser::parse_text(&metrics_text) {
+            Ok(metrics) => {
+                for mut metric in metrics {
+                    emit!(ExecEventReceived {
+                        command: config.command_line().as_str(),
+                        byte_size: 0,
+                    });
+                    if let Some(run_id) = &run_id {
+                        insert_run_tags(metric.as_mut_metric(), run_id, exit_code, elapsed);
+                    }
+                    event_count += 1;
+                    send_event(&mut out, metric).await;
+                }
+            }
+            Err(error) => emit!(ExecParseError {
+                command: config.command_line().as_str(),
+                error: error.into(),
+            }),
         }
-    };
+    }
+
+    if let Some(run_id) = run_id {
+        let message = Bytes::from("Finished command run.");
+        let mut event = create_event(&config, &hostname, message, &None, pid);
+        let log = event.as_mut_log();
+        log.insert(RUN_SUMMARY_KEY, true);
+        log.insert(RUN_ID_KEY, run_id);
+        insert_exit_metadata(log, exit_code, elapsed);
+        log.insert(TIMED_OUT_KEY, timed_out);
+        log.insert(EVENT_COUNT_KEY, event_count as i64);
+        send_event(&mut out, event).await;
+    }
 
     debug!("Finished command run.");
     let _ = out.flush().await;
 
-    result
+    Ok(exit_status)
+}
+
+/// Waits for the command to exit, unless Vector is shutting down.
+async fn wait_for_exit(child: &mut Child, shutdown: ShutdownSignal) -> Option<ExitStatus> {
+    let exit_status = tokio::select! {
+        exit_status = child.wait() => exit_status.map(Some),
+        _ = shutdown => child.try_wait(),
+    };
+
+    match exit_status {
+        Ok(exit_status) => exit_status,
+        Err(error) => {
+            error!(message = "Unable to obtain exit status.", %error);
+            None
+        }
+    }
+}
+
+async fn send_event(out: &mut Pipeline, event: Event) {
+    let _ = out
+        .send(event)
+        .await
+        .map_err(|_: crate::pipeline::ClosedError| {
+            error!(message = "Failed to forward events; downstream is closed.");
+        });
 }
 
 fn handle_exit_status(config: &ExecConfig, exit_status: Option<i32>, exec_duration: Duration) {
@@ -485,6 +609,52 @@ fn create_event(
     Event::Log(log_event)
 }
 
+/// Replaces the message of the event with the fields of the JSON object it
+/// holds, leaving it as is if it isn't one.
+fn decode_json(config: &ExecConfig, log: &mut LogEvent) {
+    let message = match log.get(log_schema().message_key()) {
+        Some(message) => message.as_bytes(),
+        None => return,
+    };
+
+    match serde_json::from_slice::<JsonValue>(&message) {
+        Ok(JsonValue::Object(object)) => {
+            log.remove(log_schema().message_key());
+            for (key, value) in object {
+                log.insert_flat(key, value);
+            }
+        }
+        Ok(_) => emit!(ExecParseError {
+            command: config.command_line().as_str(),
+            error: "The output is not a JSON object.".into(),
+        }),
+        Err(error) => emit!(ExecParseError {
+            command: config.command_line().as_str(),
+            error: error.into(),
+        }),
+    }
+}
+
+fn insert_exit_metadata(log: &mut LogEvent, exit_code: Option<i32>, duration: Duration) {
+    if let Some(exit_code) = exit_code {
+        log.insert(EXIT_CODE_KEY, exit_code as i64);
+    }
+    log.insert(RUN_DURATION_KEY, duration.as_secs_f64());
+}
+
+/// Metrics are parsed once the command exits, so they always get the exit
+/// code and duration of the run, as tags.
+fn insert_run_tags(metric: &mut Metric, run_id: &str, exit_code: Option<i32>, duration: Duration) {
+    metric.insert_tag(RUN_ID_KEY.to_owned(), run_id.to_owned());
+    if let Some(exit_code) = exit_code {
+        metric.insert_tag(EXIT_CODE_KEY.to_owned(), exit_code.to_string());
+    }
+    metric.insert_tag(
+        RUN_DURATION_KEY.to_owned(),
+        duration.as_secs_f64().to_string(),
+    );
+}
+
 fn spawn_reader_thread<R: 'static + AsyncRead + Unpin + std::marker::Send>(
     reader: BufReader<R>,
     event_per_line: bool,
@@ -545,6 +715,7 @@ fn spawn_reader_thread<R: 'static + AsyncRead + Unpin + std::marker::Send>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::event::metric::MetricValue;
     use crate::test_util::trace_init;
     use std::io::Cursor;
 
@@ -607,6 +778,8 @@ mod tests {
             include_stderr: default_include_stderr(),
             event_per_line: default_events_per_line(),
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
+            decoding: Decoding::default(),
+            run_metadata: false,
         };
 
         let command = build_command(&config);
@@ -741,7 +914,7 @@ mod tests {
         // Wait for our task to finish, wrapping it in a timeout
         let timeout = tokio::time::timeout(
             time::Duration::from_secs(5),
-            run_command(config.clone(), hostname, shutdown, tx),
+            run_command(config.clone(), hostname, None, shutdown, tx),
         );
 
         let timeout_result = timeout.await;
@@ -767,6 +940,156 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    #[cfg(not(target_os = "windows"))]
+    async fn test_run_command_json() {
+        trace_init();
+        let config = ExecConfig {
+            command: vec![
+                "echo".to_owned(),
+                r#"{"status":"ok","checks":3}"#.to_owned(),
+            ],
+            decoding: Decoding::Json,
+            ..Default::default()
+        };
+
+        let (_, events) = run_test_command(config, None).await;
+
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log["status"], "ok".into());
+        assert_eq!(log["checks"], 3.into());
+        assert_eq!(log[STREAM_KEY], STDOUT.into());
+        assert!(log.get(log_schema().message_key()).is_none());
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_os = "windows"))]
+    async fn test_run_command_prometheus() {
+        trace_init();
+        let config = ExecConfig {
+            command: vec![
+                "sh".to_owned(),
+                "-c".to_owned(),
+                "echo '# TYPE checks_passed gauge'; echo 'checks_passed{check=\"disk\"} 1'"
+                    .to_owned(),
+            ],
+            decoding: Decoding::Prometheus,
+            ..Default::default()
+        };
+
+        let (_, events) = run_test_command(config, None).await;
+
+        assert_eq!(events.len(), 1);
+        let metric = events[0].as_metric();
+        assert_eq!(metric.name(), "checks_passed");
+        assert_eq!(metric.value(), &MetricValue::Gauge { value: 1.0 });
+        assert_eq!(metric.tags().unwrap()["check"], "disk");
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_os = "windows"))]
+    async fn test_run_command_run_metadata() {
+        trace_init();
+        let config = ExecConfig {
+            command: vec![
+                "sh".to_owned(),
+                "-c".to_owned(),
+                "echo first; echo second; exit 3".to_owned(),
+            ],
+            run_metadata: true,
+            ..Default::default()
+        };
+
+        let (exit_status, events) = run_test_command(config, Some(Duration::from_secs(5))).await;
+
+        assert_eq!(exit_status.unwrap().code(), Some(3));
+        assert_eq!(events.len(), 3);
+        let run_id = events[0].as_log()[RUN_ID_KEY].clone();
+        for event in &events {
+            let log = event.as_log();
+            assert_eq!(log[RUN_ID_KEY], run_id);
+            assert_eq!(log[EXIT_CODE_KEY], 3.into());
+            assert!(log.contains(RUN_DURATION_KEY));
+        }
+
+        let summary = events[2].as_log();
+        assert_eq!(summary[RUN_SUMMARY_KEY], true.into());
+        assert_eq!(summary[EVENT_COUNT_KEY], 2.into());
+        assert_eq!(summary[TIMED_OUT_KEY], false.into());
+        assert!(summary.get(STREAM_KEY).is_none());
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_os = "windows"))]
+    async fn test_run_command_prometheus_run_metadata() {
+        trace_init();
+        let config = ExecConfig {
+            command: vec![
+                "sh".to_owned(),
+                "-c".to_owned(),
+                "echo 'checks_passed 1'; exit 2".to_owned(),
+            ],
+            decoding: Decoding::Prometheus,
+            run_metadata: true,
+            ..Default::default()
+        };
+
+        let (_, events) = run_test_command(config, Some(Duration::from_secs(5))).await;
+
+        assert_eq!(events.len(), 2);
+        let tags = events[0].as_metric().tags().unwrap();
+        let summary = events[1].as_log();
+        assert_eq!(summary[RUN_ID_KEY], tags[RUN_ID_KEY].as_str().into());
+        assert_eq!(tags[EXIT_CODE_KEY], "2");
+        assert!(tags[RUN_DURATION_KEY].parse::<f64>().is_ok());
+        assert_eq!(summary[EVENT_COUNT_KEY], 1.into());
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_os = "windows"))]
+    async fn test_run_command_timeout() {
+        trace_init();
+        let config = ExecConfig {
+            command: vec![
+                "sh".to_owned(),
+                "-c".to_owned(),
+                "echo started; sleep 10".to_owned(),
+            ],
+            run_metadata: true,
+            ..Default::default()
+        };
+
+        let (exit_status, events) =
+            run_test_command(config, Some(Duration::from_millis(500))).await;
+
+        assert!(exit_status.is_none());
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "started".into()
+        );
+        let summary = events[1].as_log();
+        assert_eq!(summary[TIMED_OUT_KEY], true.into());
+        assert!(summary.get(EXIT_CODE_KEY).is_none());
+    }
+
+    async fn run_test_command(
+        config: ExecConfig,
+        timeout: Option<Duration>,
+    ) -> (Option<ExitStatus>, Vec<Event>) {
+        let (tx, rx) = Pipeline::new_test();
+        let exit_status = tokio::time::timeout(
+            Duration::from_secs(5),
+            run_command(config, None, timeout, ShutdownSignal::noop(), tx),
+        )
+        .await
+        .expect("command timed out")
+        .expect("command error");
+
+        (exit_status, rx.collect().await)
+    }
+
     fn standard_scheduled_test_config() -> ExecConfig {
         Default::default()
     }
@@ -784,6 +1107,8 @@ mod tests {
             include_stderr: default_include_stderr(),
             event_per_line: default_events_per_line(),
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
+            decoding: Decoding::default(),
+            run_metadata: false,
         }
     }
 }
@@ -1,10 +1,13 @@
 use super::util::finalizer::OrderedFinalizer;
 use super::util::{EncodingConfig, MultilineConfig};
 use crate::{
+    codecs::{BoxedParser, ParserConfig},
     config::{log_schema, DataType, SourceConfig, SourceContext, SourceDescription},
     encoding_transcode::{Decoder, Encoder},
     event::{BatchNotifier, Event, LogEvent},
-    internal_events::{FileEventReceived, FileOpen, FileSourceInternalEventsEmitter},
+    internal_events::{
+        DecoderParseFailed, FileEventReceived, FileOpen, FileSourceInternalEventsEmitter,
+    },
     line_agg::{self, LineAgg},
     shutdown::ShutdownSignal,
     trace::{current_span, Instrument},
@@ -58,7 +61,7 @@ enum BuildError {
     },
 }
 
-#[derive(Deserialize, Serialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(deny_unknown_fields, default)]
 pub struct FileConfig {
     pub include: Vec<PathBuf>,
@@ -89,6 +92,9 @@ pub struct FileConfig {
     pub remove_after_secs: Option<u64>,
     pub line_delimiter: String,
     pub encoding: Option<EncodingConfig>,
+    // Framing is always line based, since checkpointing relies on the
+    // `line_delimiter`, so only the parsing of each line is configurable.
+    pub decoding: Option<Box<dyn ParserConfig>>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
@@ -190,6 +196,7 @@ impl Default for FileConfig {
             remove_after_secs: None,
             line_delimiter: "\n".to_string(),
             encoding: None,
+            decoding: None,
         }
     }
 }
@@ -223,6 +230,10 @@ impl SourceConfig for FileConfig {
                 Regex::new(indicator)
                     .with_context(|| InvalidMessageStartIndicator { indicator })?;
             }
+
+            if let Some(ref decoding) = self.decoding {
+                decoding.build()?;
+            }
         }
 
         Ok(file_source(
@@ -305,6 +316,10 @@ pub fn file_source(
         .clone()
         .unwrap_or_else(|| log_schema().host_key().to_string());
     let hostname = crate::get_hostname().ok();
+    let parser = config
+        .decoding
+        .as_ref()
+        .map(|decoding| decoding.build().unwrap()); // validated in build
 
     let include = config.include.clone();
     let exclude = config.exclude.clone();
@@ -362,13 +377,24 @@ pub fn file_source(
         let span = current_span();
         let span2 = span.clone();
         let mut messages = messages
-            .map(move |line| {
+            .flat_map(move |line| {
                 let _enter = span2.enter();
-                let mut event =
-                    create_event(line.text, line.filename, &host_key, &hostname, &file_key);
+                let mut events = create_events(
+                    line.text,
+                    line.filename,
+                    &parser,
+                    &host_key,
+                    &hostname,
+                    &file_key,
+                );
                 if let Some(finalizer) = &finalizer {
+                    // All events of a line share a batch, so that its offset is
+                    // only checkpointed once every one of them is delivered.
                     let (batch, receiver) = BatchNotifier::new_with_receiver();
-                    event = event.with_batch_notifier(&batch);
+                    events = events
+                        .into_iter()
+                        .map(|event| event.with_batch_notifier(&batch))
+                        .collect();
                     let entry = FinalizerEntry {
                         file_id: line.file_id,
                         offset: line.offset,
@@ -377,7 +403,7 @@ pub fn file_source(
                 } else {
                     checkpoints.update(line.file_id, line.offset);
                 }
-                event
+                futures::stream::iter(events)
             })
             .map(Ok);
         tokio::spawn(async move { out.send_all(&mut messages).instrument(span).await });
@@ -439,38 +465,50 @@ fn wrap_with_line_agg(
     )
 }
 
-fn create_event(
+fn create_events(
     line: Bytes,
     file: String,
+    parser: &Option<BoxedParser>,
     host_key: &str,
     hostname: &Option<String>,
     file_key: &Option<String>,
-) -> Event {
+) -> Vec<Event> {
     emit!(FileEventReceived {
         file: &file,
         byte_size: line.len(),
     });
 
-    let mut event = LogEvent::from(line);
+    let mut events = match parser {
+        Some(parser) => parser.parse(line).unwrap_or_else(|error| {
+            emit!(DecoderParseFailed { error: &error });
+            Vec::new()
+        }),
+        None => vec![LogEvent::from(line).into()],
+    };
 
-    // Add source type
-    event.insert(log_schema().source_type_key(), Bytes::from("file"));
+    for event in &mut events {
+        if let Event::Log(event) = event {
+            // Add source type
+            event.insert(log_schema().source_type_key(), Bytes::from("file"));
 
-    if let Some(file_key) = &file_key {
-        event.insert(file_key.clone(), file);
-    }
+            if let Some(file_key) = &file_key {
+                event.insert(file_key.clone(), file.clone());
+            }
 
-    if let Some(hostname) = &hostname {
-        event.insert(host_key, hostname.clone());
+            if let Some(hostname) = &hostname {
+                event.insert(host_key, hostname.clone());
+            }
+        }
     }
 
-    event.into()
+    events
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
+        codecs::JsonParserConfig,
         config::Config,
         event::{EventStatus, Value},
         shutdown::ShutdownSignal,
@@ -528,7 +566,10 @@ mod tests {
         "#,
         )
         .unwrap();
-        assert_eq!(config, FileConfig::default());
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            serde_json::to_value(FileConfig::default()).unwrap()
+        );
         assert_eq!(
             config.fingerprint,
             FingerprintConfig::Checksum {
@@ -619,8 +660,9 @@ mod tests {
         let hostname = Some("Some.Machine".to_string());
         let file_key = Some("file".to_string());
 
-        let event = create_event(line, file, &host_key, &hostname, &file_key);
-        let log = event.into_log();
+        let mut events = create_events(line, file, &None, &host_key, &hostname, &file_key);
+        assert_eq!(events.len(), 1);
+        let log = events.remove(0).into_log();
 
         assert_eq!(log["file"], "some_file.rs".into());
         assert_eq!(log["host"], "Some.Machine".into());
@@ -628,6 +670,30 @@ mod tests {
         assert_eq!(log[log_schema().source_type_key()], "file".into());
     }
 
+    #[test]
+    fn file_create_events_with_decoding() {
+        let file = "some_file.rs".to_string();
+        let host_key = "host".to_string();
+        let hostname = Some("Some.Machine".to_string());
+        let file_key = Some("file".to_string());
+        let parser = Some(JsonParserConfig::new().build().unwrap());
+
+        let line = Bytes::from(r#"[{"foo":1},{"foo":2}]"#);
+        let events = create_events(line, file.clone(), &parser, &host_key, &hostname, &file_key);
+        assert_eq!(events.len(), 2);
+        for (i, event) in events.into_iter().enumerate() {
+            let log = event.into_log();
+            assert_eq!(log["foo"], (i as i64 + 1).into());
+            assert_eq!(log["file"], "some_file.rs".into());
+            assert_eq!(log["host"], "Some.Machine".into());
+            assert_eq!(log[log_schema().source_type_key()], "file".into());
+        }
+
+        let line = Bytes::from("not json");
+        let events = create_events(line, file, &parser, &host_key, &hostname, &file_key);
+        assert!(events.is_empty());
+    }
+
     #[tokio::test]
     async fn file_happy_path() {
         let n = 5;
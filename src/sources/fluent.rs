@@ -60,6 +60,7 @@ impl SourceConfig for FluentConfig {
             self.receive_buffer_bytes,
             cx.shutdown,
             cx.out,
+            false,
         )
     }
 
@@ -87,14 +88,14 @@ impl TcpSource for FluentSource {
         FluentDecoder::new()
     }
 
-    fn build_event(&self, frame: FluentFrame, host: Bytes) -> Option<Event> {
+    fn build_events(&self, frame: FluentFrame, host: Bytes) -> Vec<Event> {
         let mut log = LogEvent::from(frame);
 
         if !log.contains(log_schema().host_key()) {
             log.insert(log_schema().host_key(), host);
         }
 
-        Some(Event::from(log))
+        vec![Event::from(log)]
     }
 }
 
@@ -0,0 +1,353 @@
+use super::{FilterList, HostMetricsConfig};
+use crate::event::metric::Metric;
+use serde::{Deserialize, Serialize};
+#[cfg(target_os = "linux")]
+use {
+    chrono::{DateTime, Utc},
+    shared::btreemap,
+    std::{
+        collections::HashMap,
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(super) struct CgroupsConfig {
+    #[serde(default)]
+    groups: FilterList,
+}
+
+impl HostMetricsConfig {
+    pub async fn cgroups_metrics(&self) -> Vec<Metric> {
+        #[cfg(target_os = "linux")]
+        let result = {
+            let config = self.clone();
+            let root = super::sysfs_root().join("fs/cgroup");
+            match tokio::task::spawn_blocking(move || config.read_cgroups_metrics(&root)).await {
+                Ok(metrics) => metrics,
+                Err(error) => {
+                    error!(message = "Failed to load cgroups info.", %error, internal_log_rate_secs = 60);
+                    vec![]
+                }
+            }
+        };
+        #[cfg(not(target_os = "linux"))]
+        let result = vec![];
+
+        result
+    }
+
+    #[cfg(target_os = "linux")]
+    fn read_cgroups_metrics(&self, root: &Path) -> Vec<Metric> {
+        let timestamp = Utc::now();
+        let mut metrics = Vec::new();
+        // The unified hierarchy of cgroup v2 has the list of the available
+        // controllers at its root.
+        if root.join("cgroup.controllers").is_file() {
+            for (dir, cgroup) in self.list_cgroups(root) {
+                self.read_v2(&dir, &cgroup, timestamp, &mut metrics);
+            }
+        } else {
+            for (dir, cgroup) in self.list_cgroups(&root.join("cpuacct")) {
+                self.read_v1_cpuacct(&dir, &cgroup, timestamp, &mut metrics);
+            }
+            for (dir, cgroup) in self.list_cgroups(&root.join("memory")) {
+                self.read_v1_memory(&dir, &cgroup, timestamp, &mut metrics);
+            }
+        }
+        metrics
+    }
+
+    /// Lists the cgroups of the hierarchy mounted at `root` which aren't
+    /// filtered out, along with their path relative to it.
+    #[cfg(target_os = "linux")]
+    fn list_cgroups(&self, root: &Path) -> Vec<(PathBuf, String)> {
+        let mut cgroups = Vec::new();
+        let mut pending = vec![(root.to_path_buf(), String::from("/"))];
+        while let Some((dir, cgroup)) = pending.pop() {
+            if let Ok(entries) = fs::read_dir(&dir) {
+                for entry in entries.filter_map(Result::ok) {
+                    let is_dir = entry
+                        .file_type()
+                        .map(|file_type| file_type.is_dir())
+                        .unwrap_or(false);
+                    if let (true, Some(name)) = (is_dir, entry.file_name().to_str()) {
+                        let child = format!("{}/{}", cgroup.trim_end_matches('/'), name);
+                        pending.push((entry.path(), child));
+                    }
+                }
+            }
+            // Children are still visited when their parent is filtered out,
+            // so that patterns can select them.
+            if self.cgroups.groups.contains_path(Some(Path::new(&cgroup))) {
+                cgroups.push((dir, cgroup));
+            }
+        }
+        cgroups.sort_by(|(_, a), (_, b)| a.cmp(b));
+        cgroups
+    }
+
+    #[cfg(target_os = "linux")]
+    fn read_v2(
+        &self,
+        dir: &Path,
+        cgroup: &str,
+        timestamp: DateTime<Utc>,
+        metrics: &mut Vec<Metric>,
+    ) {
+        let tags = || btreemap! { "cgroup" => cgroup };
+        let cpu = read_keyed(&dir.join("cpu.stat"));
+        for (key, name) in &[
+            ("usage_usec", "cgroup_cpu_usage_seconds_total"),
+            ("user_usec", "cgroup_cpu_user_seconds_total"),
+            ("system_usec", "cgroup_cpu_system_seconds_total"),
+        ] {
+            if let Some(usec) = cpu.get(*key) {
+                metrics.push(self.counter(name, timestamp, *usec as f64 / 1_000_000.0, tags()));
+            }
+        }
+        // The root cgroup doesn't have any memory accounting files.
+        if let Some(bytes) = read_value(&dir.join("memory.current")) {
+            metrics.push(self.gauge(
+                "cgroup_memory_current_bytes",
+                timestamp,
+                bytes as f64,
+                tags(),
+            ));
+        }
+        let memory = read_keyed(&dir.join("memory.stat"));
+        for (key, name) in &[
+            ("anon", "cgroup_memory_anon_bytes"),
+            ("file", "cgroup_memory_file_bytes"),
+        ] {
+            if let Some(bytes) = memory.get(*key) {
+                metrics.push(self.gauge(name, timestamp, *bytes as f64, tags()));
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn read_v1_cpuacct(
+        &self,
+        dir: &Path,
+        cgroup: &str,
+        timestamp: DateTime<Utc>,
+        metrics: &mut Vec<Metric>,
+    ) {
+        let tags = || btreemap! { "cgroup" => cgroup };
+        if let Some(nsec) = read_value(&dir.join("cpuacct.usage")) {
+            metrics.push(self.counter(
+                "cgroup_cpu_usage_seconds_total",
+                timestamp,
+                nsec as f64 / 1_000_000_000.0,
+                tags(),
+            ));
+        }
+        // Unlike the total usage, these are in clock ticks.
+        let cpu = read_keyed(&dir.join("cpuacct.stat"));
+        for (key, name) in &[
+            ("user", "cgroup_cpu_user_seconds_total"),
+            ("system", "cgroup_cpu_system_seconds_total"),
+        ] {
+            if let Some(ticks) = cpu.get(*key) {
+                metrics.push(self.counter(
+                    name,
+                    timestamp,
+                    *ticks as f64 / super::process::ticks_per_second(),
+                    tags(),
+                ));
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn read_v1_memory(
+        &self,
+        dir: &Path,
+        cgroup: &str,
+        timestamp: DateTime<Utc>,
+        metrics: &mut Vec<Metric>,
+    ) {
+        let tags = || btreemap! { "cgroup" => cgroup };
+        if let Some(bytes) = read_value(&dir.join("memory.usage_in_bytes")) {
+            metrics.push(self.gauge(
+                "cgroup_memory_current_bytes",
+                timestamp,
+                bytes as f64,
+                tags(),
+            ));
+        }
+        let memory = read_keyed(&dir.join("memory.stat"));
+        for (key, name) in &[
+            ("rss", "cgroup_memory_anon_bytes"),
+            ("cache", "cgroup_memory_file_bytes"),
+        ] {
+            if let Some(bytes) = memory.get(*key) {
+                metrics.push(self.gauge(name, timestamp, *bytes as f64, tags()));
+            }
+        }
+    }
+}
+
+/// Reads a file holding a single number.
+#[cfg(target_os = "linux")]
+fn read_value(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Reads a file made of `<key> <number>` lines, such as `cpu.stat`.
+#[cfg(target_os = "linux")]
+fn read_keyed(path: &Path) -> HashMap<String, u64> {
+    fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| {
+                    let mut parts = line.split_whitespace();
+                    let key = parts.next()?;
+                    let value = parts.next()?.parse().ok()?;
+                    Some((key.to_owned(), value))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::super::PatternWrapper;
+    use super::*;
+    use crate::event::metric::MetricValue;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn value(metrics: &[Metric], name: &str, cgroup: &str) -> Option<f64> {
+        metrics
+            .iter()
+            .find(|metric| {
+                metric.name() == name && metric.tags().unwrap().get("cgroup").unwrap() == cgroup
+            })
+            .map(|metric| match metric.value() {
+                MetricValue::Counter { value } | MetricValue::Gauge { value } => *value,
+                _ => panic!("unexpected metric value"),
+            })
+    }
+
+    fn cgroup_v2() -> TempDir {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path();
+        write(path, "cgroup.controllers", "cpu io memory pids\n");
+        write(
+            path,
+            "cpu.stat",
+            "usage_usec 9000000\nuser_usec 6000000\nsystem_usec 3000000\n",
+        );
+        write(
+            path,
+            "system.slice/vector.service/cpu.stat",
+            "usage_usec 1500000\nuser_usec 1000000\nsystem_usec 500000\nnr_periods 0\n",
+        );
+        write(path, "system.slice/vector.service/memory.current", "4096\n");
+        write(
+            path,
+            "system.slice/vector.service/memory.stat",
+            "anon 3072\nfile 1024\nkernel_stack 0\n",
+        );
+        write(path, "user.slice/cpu.stat", "usage_usec 100\n");
+        root
+    }
+
+    #[test]
+    fn generates_cgroup_v2_metrics() {
+        let root = cgroup_v2();
+        let metrics = HostMetricsConfig::default().read_cgroups_metrics(root.path());
+
+        assert_eq!(
+            value(&metrics, "cgroup_cpu_usage_seconds_total", "/"),
+            Some(9.0)
+        );
+        assert_eq!(value(&metrics, "cgroup_memory_current_bytes", "/"), None);
+        let service = "/system.slice/vector.service";
+        assert_eq!(
+            value(&metrics, "cgroup_cpu_system_seconds_total", service),
+            Some(0.5)
+        );
+        assert_eq!(
+            value(&metrics, "cgroup_memory_current_bytes", service),
+            Some(4096.0)
+        );
+        assert_eq!(
+            value(&metrics, "cgroup_memory_anon_bytes", service),
+            Some(3072.0)
+        );
+        assert_eq!(
+            value(&metrics, "cgroup_memory_file_bytes", service),
+            Some(1024.0)
+        );
+        assert_eq!(
+            value(&metrics, "cgroup_cpu_usage_seconds_total", "/user.slice"),
+            Some(0.0001)
+        );
+    }
+
+    #[test]
+    fn generates_cgroup_v1_metrics() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path();
+        write(path, "cpuacct/docker/abc/cpuacct.usage", "2500000000\n");
+        write(path, "memory/docker/abc/memory.usage_in_bytes", "8192\n");
+        write(
+            path,
+            "memory/docker/abc/memory.stat",
+            "cache 2048\nrss 6144\nmapped_file 0\n",
+        );
+        let metrics = HostMetricsConfig::default().read_cgroups_metrics(path);
+
+        assert_eq!(metrics.len(), 4);
+        assert_eq!(
+            value(&metrics, "cgroup_cpu_usage_seconds_total", "/docker/abc"),
+            Some(2.5)
+        );
+        assert_eq!(
+            value(&metrics, "cgroup_memory_current_bytes", "/docker/abc"),
+            Some(8192.0)
+        );
+        assert_eq!(
+            value(&metrics, "cgroup_memory_anon_bytes", "/docker/abc"),
+            Some(6144.0)
+        );
+        assert_eq!(
+            value(&metrics, "cgroup_memory_file_bytes", "/docker/abc"),
+            Some(2048.0)
+        );
+    }
+
+    #[test]
+    fn cgroups_metrics_filters_on_group() {
+        let root = cgroup_v2();
+        let config = HostMetricsConfig {
+            cgroups: CgroupsConfig {
+                groups: FilterList {
+                    includes: Some(vec![PatternWrapper::new("/system.slice/*").unwrap()]),
+                    excludes: None,
+                },
+            },
+            ..Default::default()
+        };
+        let metrics = config.read_cgroups_metrics(root.path());
+
+        assert_eq!(metrics.len(), 6);
+        assert!(metrics.iter().all(|metric| metric
+            .tags()
+            .unwrap()
+            .get("cgroup")
+            .unwrap()
+            .starts_with("/system.slice/")));
+    }
+}
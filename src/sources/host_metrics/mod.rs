@@ -35,9 +35,17 @@ use shared::btreemap;
 use std::collections::BTreeMap;
 use std::fmt;
 use std::path::Path;
+#[cfg(target_os = "linux")]
+use std::path::PathBuf;
 use tokio::time;
 use tokio_stream::wrappers::IntervalStream;
 
+mod cgroups;
+mod process;
+
+use cgroups::CgroupsConfig;
+use process::ProcessConfig;
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
 enum Collector {
@@ -48,6 +56,8 @@ enum Collector {
     Host,
     Memory,
     Network,
+    Process,
+    Cgroups,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -103,6 +113,10 @@ pub struct HostMetricsConfig {
     filesystem: FilesystemConfig,
     #[serde(default)]
     network: NetworkConfig,
+    #[serde(default)]
+    process: ProcessConfig,
+    #[serde(default)]
+    cgroups: CgroupsConfig,
 }
 
 const fn default_scrape_interval() -> u64 {
@@ -153,7 +167,9 @@ impl HostMetricsConfig {
 
     fn has_collector(&self, collector: Collector) -> bool {
         match &self.collectors {
-            None => true,
+            // The per-process and per-cgroup collectors can generate a lot of
+            // metrics, so they have to be enabled explicitly.
+            None => !matches!(collector, Collector::Process | Collector::Cgroups),
             Some(collectors) => collectors.iter().any(|&c| c == collector),
         }
     }
@@ -183,6 +199,12 @@ impl HostMetricsConfig {
         if self.has_collector(Collector::Network) {
             metrics.extend(add_collector("network", self.network_metrics().await));
         }
+        if self.has_collector(Collector::Process) {
+            metrics.extend(add_collector("process", self.process_metrics().await));
+        }
+        if self.has_collector(Collector::Cgroups) {
+            metrics.extend(add_collector("cgroups", self.cgroups_metrics().await));
+        }
         if let Ok(hostname) = &hostname {
             for metric in &mut metrics {
                 metric.insert_tag("host".into(), hostname.into());
@@ -749,6 +771,20 @@ fn init_roots() {
     };
 }
 
+#[cfg(target_os = "linux")]
+fn procfs_root() -> PathBuf {
+    std::env::var_os("PROCFS_ROOT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("/proc"))
+}
+
+#[cfg(target_os = "linux")]
+fn sysfs_root() -> PathBuf {
+    std::env::var_os("SYSFS_ROOT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("/sys"))
+}
+
 impl FilterList {
     fn contains<T, M>(&self, value: &Option<T>, matches: M) -> bool
     where
@@ -0,0 +1,292 @@
+use super::{FilterList, HostMetricsConfig};
+use crate::event::metric::Metric;
+use serde::{Deserialize, Serialize};
+#[cfg(target_os = "linux")]
+use {
+    chrono::Utc,
+    shared::btreemap,
+    std::{fs, path::Path},
+};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(super) struct ProcessConfig {
+    #[serde(default)]
+    names: FilterList,
+}
+
+impl HostMetricsConfig {
+    pub async fn process_metrics(&self) -> Vec<Metric> {
+        #[cfg(target_os = "linux")]
+        let result = {
+            let config = self.clone();
+            match tokio::task::spawn_blocking(move || {
+                config.read_process_metrics(&super::procfs_root())
+            })
+            .await
+            {
+                Ok(metrics) => metrics,
+                Err(error) => {
+                    error!(message = "Failed to load process info.", %error, internal_log_rate_secs = 60);
+                    vec![]
+                }
+            }
+        };
+        #[cfg(not(target_os = "linux"))]
+        let result = vec![];
+
+        result
+    }
+
+    #[cfg(target_os = "linux")]
+    fn read_process_metrics(&self, procfs_root: &Path) -> Vec<Metric> {
+        let entries = match fs::read_dir(procfs_root) {
+            Ok(entries) => entries,
+            Err(error) => {
+                error!(message = "Failed to list processes.", %error, internal_log_rate_secs = 60);
+                return vec![];
+            }
+        };
+
+        let ticks_per_second = ticks_per_second();
+        let timestamp = Utc::now();
+        let mut metrics = Vec::new();
+        for entry in entries.filter_map(Result::ok) {
+            let pid = match entry.file_name().to_str().map(str::parse::<u32>) {
+                Some(Ok(pid)) => pid,
+                _ => continue,
+            };
+            // Processes can exit at any time, so those which can't be read
+            // are skipped without complaint.
+            let process = match ProcessStats::read(&entry.path(), &self.process.names) {
+                Some(process) => process,
+                None => continue,
+            };
+
+            let tags = btreemap! { "pid" => pid.to_string(), "name" => process.name };
+            let with_mode = |mode: &str| {
+                let mut tags = tags.clone();
+                tags.insert("mode".into(), mode.into());
+                tags
+            };
+            metrics.push(self.counter(
+                "process_cpu_seconds_total",
+                timestamp,
+                process.user_ticks as f64 / ticks_per_second,
+                with_mode("user"),
+            ));
+            metrics.push(self.counter(
+                "process_cpu_seconds_total",
+                timestamp,
+                process.system_ticks as f64 / ticks_per_second,
+                with_mode("system"),
+            ));
+            metrics.push(self.gauge(
+                "process_memory_rss_bytes",
+                timestamp,
+                process.rss_bytes as f64,
+                tags.clone(),
+            ));
+            metrics.push(self.gauge(
+                "process_threads",
+                timestamp,
+                process.threads as f64,
+                tags.clone(),
+            ));
+            // The file descriptors of processes owned by other users are
+            // only visible with enough privileges.
+            if let Some(open_fds) = process.open_fds {
+                metrics.push(self.gauge("process_open_fds", timestamp, open_fds as f64, tags));
+            }
+        }
+        metrics
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(super) fn ticks_per_second() -> f64 {
+    use nix::unistd::{sysconf, SysconfVar};
+
+    match sysconf(SysconfVar::CLK_TCK) {
+        Ok(Some(ticks)) if ticks > 0 => ticks as f64,
+        // USER_HZ is 100 on all the architectures Linux supports.
+        _ => 100.0,
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Debug, PartialEq)]
+struct ProcessStats {
+    name: String,
+    user_ticks: u64,
+    system_ticks: u64,
+    rss_bytes: u64,
+    threads: u64,
+    open_fds: Option<u64>,
+}
+
+#[cfg(target_os = "linux")]
+impl ProcessStats {
+    /// Reads the statistics of the process in the given `/proc/<pid>`
+    /// directory, unless its name isn't in `names`.
+    fn read(dir: &Path, names: &FilterList) -> Option<Self> {
+        let status = fs::read_to_string(dir.join("status")).ok()?;
+        let mut name = None;
+        let mut rss_bytes = 0;
+        let mut threads = 0;
+        for line in status.lines() {
+            let mut parts = line.splitn(2, ':');
+            let (key, value) = match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) => (key, value.trim()),
+                _ => continue,
+            };
+            match key {
+                "Name" => name = Some(value.to_owned()),
+                // Kernel threads don't have a `VmRSS` line.
+                "VmRSS" => rss_bytes = parse_kilobytes(value)?,
+                "Threads" => threads = value.parse().ok()?,
+                _ => {}
+            }
+        }
+        let name = name?;
+        if !names.contains_str(Some(&name)) {
+            return None;
+        }
+
+        // The command name is between parentheses and can contain spaces, so
+        // the fields are counted from the last parenthesis, starting with the
+        // third one, `state`.
+        let stat = fs::read_to_string(dir.join("stat")).ok()?;
+        let mut fields = stat[stat.rfind(')')? + 1..].split_whitespace().skip(11);
+        let user_ticks = fields.next()?.parse().ok()?;
+        let system_ticks = fields.next()?.parse().ok()?;
+
+        let open_fds = fs::read_dir(dir.join("fd"))
+            .ok()
+            .map(|entries| entries.count() as u64);
+
+        Some(Self {
+            name,
+            user_ticks,
+            system_ticks,
+            rss_bytes,
+            threads,
+            open_fds,
+        })
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn parse_kilobytes(value: &str) -> Option<u64> {
+    let kilobytes = value.strip_suffix("kB")?.trim().parse::<u64>().ok()?;
+    Some(kilobytes * 1024)
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::super::PatternWrapper;
+    use super::*;
+    use crate::event::metric::MetricValue;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn write_process(root: &Path, pid: u32, name: &str, fds: usize) {
+        let dir = root.join(pid.to_string());
+        fs::create_dir_all(dir.join("fd")).unwrap();
+        fs::write(
+            dir.join("status"),
+            format!(
+                "Name:\t{}\nUmask:\t0022\nState:\tS (sleeping)\nVmRSS:\t    2048 kB\nThreads:\t3\n",
+                name
+            ),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("stat"),
+            format!(
+                "{} ({} x) S 1 1 1 0 -1 4194560 100 0 0 0 250 50 0 0 20 0 3 0 100 1000 512\n",
+                pid, name
+            ),
+        )
+        .unwrap();
+        for fd in 0..fds {
+            fs::write(dir.join("fd").join(fd.to_string()), "").unwrap();
+        }
+    }
+
+    fn procfs() -> TempDir {
+        let root = tempfile::tempdir().unwrap();
+        write_process(root.path(), 1, "init", 4);
+        write_process(root.path(), 42, "vector", 2);
+        fs::write(root.path().join("uptime"), "1000.00 500.00\n").unwrap();
+        fs::create_dir(root.path().join("self")).unwrap();
+        root
+    }
+
+    fn value(metrics: &[Metric], name: &str, pid: &str) -> f64 {
+        let metric = metrics
+            .iter()
+            .find(|metric| {
+                metric.name() == name && metric.tags().unwrap().get("pid").unwrap() == pid
+            })
+            .unwrap();
+        match metric.value() {
+            MetricValue::Counter { value } | MetricValue::Gauge { value } => *value,
+            _ => panic!("unexpected metric value"),
+        }
+    }
+
+    #[test]
+    fn reads_process_stats() {
+        let root = procfs();
+        let stats = ProcessStats::read(&root.path().join("42"), &FilterList::default());
+
+        assert_eq!(
+            stats,
+            Some(ProcessStats {
+                name: "vector".into(),
+                user_ticks: 250,
+                system_ticks: 50,
+                rss_bytes: 2048 * 1024,
+                threads: 3,
+                open_fds: Some(2),
+            })
+        );
+    }
+
+    #[test]
+    fn generates_process_metrics() {
+        let root = procfs();
+        let metrics = HostMetricsConfig::default().read_process_metrics(root.path());
+
+        assert_eq!(metrics.len(), 10);
+        assert_eq!(value(&metrics, "process_open_fds", "1"), 4.0);
+        assert_eq!(value(&metrics, "process_threads", "42"), 3.0);
+        assert_eq!(
+            value(&metrics, "process_memory_rss_bytes", "42"),
+            2048.0 * 1024.0
+        );
+        assert!(metrics
+            .iter()
+            .all(|metric| metric.tags().unwrap().contains_key("name")));
+    }
+
+    #[test]
+    fn process_metrics_filters_on_name() {
+        let root = procfs();
+        let config = HostMetricsConfig {
+            process: ProcessConfig {
+                names: FilterList {
+                    includes: Some(vec![PatternWrapper::new("vec*").unwrap()]),
+                    excludes: None,
+                },
+            },
+            ..Default::default()
+        };
+        let metrics = config.read_process_metrics(root.path());
+
+        assert_eq!(metrics.len(), 5);
+        assert!(metrics
+            .iter()
+            .all(|metric| metric.tags().unwrap().get("name").unwrap() == "vector"));
+    }
+}
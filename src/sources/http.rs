@@ -1,11 +1,12 @@
 use crate::{
+    codecs::{FramingConfig, ParserConfig},
     config::{
         log_schema, DataType, GenerateConfig, Resource, SourceConfig, SourceContext,
         SourceDescription,
     },
     event::{Event, Value},
     sources::util::{
-        add_query_parameters, decode_body, Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
+        add_query_parameters, BodyDecoder, Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
     },
     tls::TlsConfig,
 };
@@ -20,6 +21,8 @@ pub struct SimpleHttpConfig {
     address: SocketAddr,
     #[serde(default)]
     encoding: Encoding,
+    framing: Option<Box<dyn FramingConfig>>,
+    decoding: Option<Box<dyn ParserConfig>>,
     #[serde(default)]
     headers: Vec<String>,
     #[serde(default)]
@@ -43,6 +46,8 @@ impl GenerateConfig for SimpleHttpConfig {
         toml::Value::try_from(Self {
             address: "0.0.0.0:8080".parse().unwrap(),
             encoding: Default::default(),
+            framing: None,
+            decoding: None,
             headers: Vec::new(),
             query_parameters: Vec::new(),
             tls: None,
@@ -65,7 +70,7 @@ fn default_path_key() -> String {
 
 #[derive(Clone)]
 struct SimpleHttpSource {
-    encoding: Encoding,
+    decoder: BodyDecoder,
     headers: Vec<String>,
     query_parameters: Vec<String>,
     path_key: String,
@@ -79,7 +84,8 @@ impl HttpSource for SimpleHttpSource {
         query_parameters: HashMap<String, String>,
         request_path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
-        decode_body(body, self.encoding)
+        self.decoder
+            .decode(body)
             .map(|events| add_headers(events, &self.headers, header_map))
             .map(|events| add_query_parameters(events, &self.query_parameters, query_parameters))
             .map(|events| add_path(events, self.path_key.as_str(), request_path))
@@ -98,8 +104,19 @@ impl HttpSource for SimpleHttpSource {
 #[typetag::serde(name = "http")]
 impl SourceConfig for SimpleHttpConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        // The `framing` and `decoding` options take precedence over the ones
+        // implied by the `encoding`.
+        let framing = self
+            .framing
+            .clone()
+            .unwrap_or_else(|| self.encoding.framing());
+        let parser = match &self.decoding {
+            Some(decoding) => decoding.build()?,
+            None => self.encoding.parser(),
+        };
+
         let source = SimpleHttpSource {
-            encoding: self.encoding,
+            decoder: BodyDecoder::with_parser(&*framing, parser)?,
             headers: self.headers.clone(),
             query_parameters: self.query_parameters.clone(),
             path_key: self.path_key.clone(),
@@ -201,6 +218,8 @@ mod tests {
             SimpleHttpConfig {
                 address,
                 encoding,
+                framing: None,
+                decoding: None,
                 headers,
                 query_parameters,
                 tls: None,
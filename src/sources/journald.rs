@@ -1,6 +1,6 @@
 use crate::{
     config::{log_schema, DataType, SourceConfig, SourceContext, SourceDescription},
-    event::{Event, LogEvent, Value},
+    event::{BatchNotifier, BatchStatus, Event, LogEvent, Value},
     internal_events::{JournaldEventReceived, JournaldInvalidRecord},
     shutdown::ShutdownSignal,
     Pipeline,
@@ -17,13 +17,15 @@ use nix::{
 use serde::{Deserialize, Serialize};
 use serde_json::{Error as JsonError, Value as JsonValue};
 use snafu::{ResultExt, Snafu};
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use std::{
+    cmp::Ordering,
     collections::{HashMap, HashSet},
     io::SeekFrom,
     iter::FromIterator,
     process::Stdio,
     str::FromStr,
+    sync::Arc,
     time::Duration,
 };
 use tokio_util::codec::FramedRead;
@@ -62,15 +64,34 @@ enum BuildError {
         unit
     ))]
     DuplicatedUnit { unit: String },
+    #[snafu(display("Cannot use both `current_boot_only` and `boot`"))]
+    BothCurrentBootOnlyAndBoot,
+    #[snafu(display("Cannot use both `since_now` and `from_cursor`"))]
+    BothSinceNowAndFromCursor,
+    #[snafu(display(
+        "Invalid match expression {:?}, expected a field name, an operator among `=`, `!=`, `<`, `<=`, `>` and `>=`, and a value",
+        expression
+    ))]
+    InvalidMatch { expression: String },
+    #[snafu(display(
+        "Invalid match expression {:?}, `<`, `<=`, `>` and `>=` require a numeric value",
+        expression
+    ))]
+    NonNumericMatch { expression: String },
 }
 
 #[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(deny_unknown_fields, default)]
 pub struct JournaldConfig {
     pub current_boot_only: Option<bool>,
+    pub boot: Option<String>,
     pub units: Vec<String>,
     pub include_units: Vec<String>,
     pub exclude_units: Vec<String>,
+    pub matches: Vec<String>,
+    pub since_now: bool,
+    pub from_cursor: Option<String>,
+    pub journal_directory: Option<PathBuf>,
     pub data_dir: Option<PathBuf>,
     pub batch_size: Option<usize>,
     pub journalctl_path: Option<PathBuf>,
@@ -119,28 +140,43 @@ impl SourceConfig for JournaldConfig {
             return Err(BuildError::DuplicatedUnit { unit }.into());
         }
 
+        let matches = Matches::parse(&self.matches)?;
+
+        if self.current_boot_only.is_some() && self.boot.is_some() {
+            return Err(BuildError::BothCurrentBootOnlyAndBoot.into());
+        }
+        if self.since_now && self.from_cursor.is_some() {
+            return Err(BuildError::BothSinceNowAndFromCursor.into());
+        }
+
         let mut checkpoint_path = data_dir;
         checkpoint_path.push(CHECKPOINT_FILENAME);
 
-        let journalctl_path = self
-            .journalctl_path
-            .clone()
-            .unwrap_or_else(|| JOURNALCTL.clone());
-
         let batch_size = self.batch_size.unwrap_or(DEFAULT_BATCH_SIZE);
-        let current_boot_only = self.current_boot_only.unwrap_or(true);
 
-        let start: StartJournalctlFn =
-            Box::new(move |cursor| start_journalctl(&journalctl_path, current_boot_only, cursor));
+        let journalctl = Journalctl {
+            path: self
+                .journalctl_path
+                .clone()
+                .unwrap_or_else(|| JOURNALCTL.clone()),
+            journal_directory: self.journal_directory.clone(),
+            current_boot_only: self.current_boot_only.unwrap_or(true),
+            boot: self.boot.clone(),
+            since_now: self.since_now,
+        };
+        let start: StartJournalctlFn = Box::new(move |cursor| journalctl.start(cursor));
 
         Ok(Box::pin(
             JournaldSource {
                 include_units,
                 exclude_units,
+                matches,
+                from_cursor: self.from_cursor.clone(),
                 checkpoint_path,
                 batch_size,
                 remap_priority: self.remap_priority,
                 out: cx.out,
+                acknowledgements: cx.acknowledgements,
             }
             .run_shutdown(cx.shutdown, start),
         ))
@@ -158,10 +194,13 @@ impl SourceConfig for JournaldConfig {
 struct JournaldSource {
     include_units: HashSet<String>,
     exclude_units: HashSet<String>,
+    matches: Matches,
+    from_cursor: Option<String>,
     checkpoint_path: PathBuf,
     batch_size: usize,
     remap_priority: bool,
     out: Pipeline,
+    acknowledgements: bool,
 }
 
 impl JournaldSource {
@@ -190,6 +229,11 @@ impl JournaldSource {
                 None
             }
         };
+        // The checkpoint takes precedence, so that a restarted source resumes
+        // where it stopped rather than replaying the journal again.
+        if cursor.is_none() {
+            cursor = self.from_cursor.clone();
+        }
 
         let mut on_stop = None;
         let run = Box::pin(self.run(
@@ -242,6 +286,10 @@ impl JournaldSource {
 
     /// Process `journalctl` output until some error occurs.
     /// Return `true` if should restart `journalctl`.
+    ///
+    /// With acknowledgements enabled, the `cursor` is only advanced once
+    /// all the events of a batch have been delivered by the sinks. When they
+    /// aren't, `journalctl` is restarted to read the batch again.
     async fn run_stream<'a>(
         &'a mut self,
         mut stream: BoxStream<'static, io::Result<Bytes>>,
@@ -250,12 +298,21 @@ impl JournaldSource {
     ) -> bool {
         loop {
             let mut saw_record = false;
+            let mut should_restart = false;
+            let mut batch_cursor = None;
+            let (batch, receiver) = if self.acknowledgements {
+                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                (Some(batch), Some(receiver))
+            } else {
+                (None, None)
+            };
 
             for _ in 0..self.batch_size {
                 let bytes = match stream.next().await {
                     None => {
                         warn!("Journalctl process stopped.");
-                        return true;
+                        should_restart = true;
+                        break;
                     }
                     Some(Ok(text)) => text,
                     Some(Err(error)) => {
@@ -267,7 +324,7 @@ impl JournaldSource {
                     }
                 };
 
-                let mut record = match decode_record(&bytes, self.remap_priority) {
+                let mut record = match decode_record(&bytes) {
                     Ok(record) => record,
                     Err(error) => {
                         emit!(JournaldInvalidRecord {
@@ -278,21 +335,37 @@ impl JournaldSource {
                     }
                 };
                 if let Some(tmp) = record.remove(&*CURSOR) {
-                    *cursor = Some(tmp);
+                    if self.acknowledgements {
+                        batch_cursor = Some(tmp);
+                    } else {
+                        *cursor = Some(tmp);
+                    }
                 }
 
                 saw_record = true;
 
                 let unit = record.get(&*SYSTEMD_UNIT);
-                if filter_unit(unit, &self.include_units, &self.exclude_units) {
+                if filter_unit(unit, &self.include_units, &self.exclude_units)
+                    || !self.matches.matches(&record)
+                {
                     continue;
                 }
+                if self.remap_priority {
+                    if let Some(priority) = record.get_mut("PRIORITY") {
+                        remap_priority(priority);
+                    }
+                }
 
                 emit!(JournaldEventReceived {
                     byte_size: bytes.len()
                 });
 
-                match self.out.send(create_event(record)).await {
+                let mut event = create_event(record);
+                if let Some(batch) = &batch {
+                    event.add_batch_notifier(Arc::clone(batch));
+                }
+
+                match self.out.send(event).await {
                     Ok(_) => {}
                     Err(error) => {
                         error!(message = "Could not send journald log.", %error);
@@ -302,9 +375,32 @@ impl JournaldSource {
                 }
             }
 
+            drop(batch);
+            if let Some(receiver) = receiver {
+                match receiver.await {
+                    BatchStatus::Delivered => {}
+                    BatchStatus::Errored => {
+                        warn!(message = "Journald batch was not delivered, reading it again.");
+                        return true;
+                    }
+                    // Reading a rejected batch again would only get it
+                    // rejected again, so it is skipped.
+                    BatchStatus::Failed => {
+                        warn!(message = "Journald batch was rejected, skipping it.");
+                    }
+                }
+                if batch_cursor.is_some() {
+                    *cursor = batch_cursor;
+                }
+            }
+
             if saw_record {
                 Self::save_checkpoint(checkpointer, &*cursor).await;
             }
+
+            if should_restart {
+                return true;
+            }
         }
     }
 
@@ -336,43 +432,69 @@ type StartJournalctlFn = Box<
 
 type StopJournalctlFn = Box<dyn FnOnce() + Send>;
 
-fn start_journalctl(
-    path: &Path,
+/// The options of the `journalctl` process.
+struct Journalctl {
+    path: PathBuf,
+    journal_directory: Option<PathBuf>,
     current_boot_only: bool,
-    cursor: &Option<String>,
-) -> crate::Result<(BoxStream<'static, io::Result<Bytes>>, StopJournalctlFn)> {
-    let mut command = Command::new(path);
-    command.stdout(Stdio::piped());
-    command.arg("--follow");
-    command.arg("--all");
-    command.arg("--show-cursor");
-    command.arg("--output=json");
+    boot: Option<String>,
+    since_now: bool,
+}
 
-    if current_boot_only {
-        command.arg("--boot");
-    }
+impl Journalctl {
+    fn args(&self, cursor: &Option<String>) -> Vec<String> {
+        let mut args = vec![
+            "--follow".to_owned(),
+            "--all".to_owned(),
+            "--show-cursor".to_owned(),
+            "--output=json".to_owned(),
+        ];
+
+        if let Some(directory) = &self.journal_directory {
+            args.push(format!("--directory={}", directory.display()));
+        }
 
-    if let Some(cursor) = cursor {
-        command.arg(format!("--after-cursor={}", cursor));
-    } else {
-        // journalctl --follow only outputs a few lines without a starting point
-        command.arg("--since=2000-01-01");
+        if let Some(boot) = &self.boot {
+            args.push(format!("--boot={}", boot));
+        } else if self.current_boot_only {
+            args.push("--boot".to_owned());
+        }
+
+        if let Some(cursor) = cursor {
+            args.push(format!("--after-cursor={}", cursor));
+        } else if self.since_now {
+            args.push("--since=now".to_owned());
+        } else {
+            // journalctl --follow only outputs a few lines without a starting point
+            args.push("--since=2000-01-01".to_owned());
+        }
+
+        args
     }
 
-    let mut child = command.spawn().context(JournalctlSpawn)?;
+    fn start(
+        &self,
+        cursor: &Option<String>,
+    ) -> crate::Result<(BoxStream<'static, io::Result<Bytes>>, StopJournalctlFn)> {
+        let mut command = Command::new(&self.path);
+        command.stdout(Stdio::piped());
+        command.args(self.args(cursor));
 
-    let stream = FramedRead::new(
-        child.stdout.take().unwrap(),
-        BytesDelimitedCodec::new(b'\n'),
-    )
-    .boxed();
+        let mut child = command.spawn().context(JournalctlSpawn)?;
 
-    let pid = Pid::from_raw(child.id().unwrap() as _);
-    let stop = Box::new(move || {
-        let _ = kill(pid, Signal::SIGTERM);
-    });
+        let stream = FramedRead::new(
+            child.stdout.take().unwrap(),
+            BytesDelimitedCodec::new(b'\n'),
+        )
+        .boxed();
 
-    Ok((stream, stop))
+        let pid = Pid::from_raw(child.id().unwrap() as _);
+        let stop = Box::new(move || {
+            let _ = kill(pid, Signal::SIGTERM);
+        });
+
+        Ok((stream, stop))
+    }
 }
 
 fn create_event(record: Record) -> Event {
@@ -413,7 +535,7 @@ fn fixup_unit(unit: &str) -> String {
     }
 }
 
-fn decode_record(line: &[u8], remap: bool) -> Result<Record, JsonError> {
+fn decode_record(line: &[u8]) -> Result<Record, JsonError> {
     let mut record = serde_json::from_str::<JsonValue>(&String::from_utf8_lossy(line))?;
     // journalctl will output non-ASCII values using an array
     // of integers. Look for those values and re-parse them.
@@ -422,9 +544,6 @@ fn decode_record(line: &[u8], remap: bool) -> Result<Record, JsonError> {
             *value = decode_array(value.as_array().expect("already validated"));
         }
     }
-    if remap {
-        record.get_mut("PRIORITY").map(remap_priority);
-    }
     serde_json::from_value(record)
 }
 
@@ -451,8 +570,8 @@ fn decode_array_as_bytes(array: &[JsonValue]) -> Option<JsonValue> {
         .map(|array| String::from_utf8_lossy(&array).into())
 }
 
-fn remap_priority(priority: &mut JsonValue) {
-    if let Some(num) = priority.as_str().and_then(|s| usize::from_str(s).ok()) {
+fn remap_priority(priority: &mut String) {
+    if let Ok(num) = usize::from_str(priority) {
         let text = match num {
             0 => "EMERG",
             1 => "ALERT",
@@ -464,7 +583,137 @@ fn remap_priority(priority: &mut JsonValue) {
             7 => "DEBUG",
             _ => "UNKNOWN",
         };
-        *priority = JsonValue::String(text.into());
+        *priority = text.into();
+    }
+}
+
+/// The operators of the match expressions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum MatchOperator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl MatchOperator {
+    /// Whether the operator orders values, which is only done for numbers.
+    const fn is_ordering(self) -> bool {
+        matches!(
+            self,
+            Self::Less | Self::LessOrEqual | Self::Greater | Self::GreaterOrEqual
+        )
+    }
+}
+
+/// A condition on a field of the journal records, such as `PRIORITY<=3`.
+#[derive(Clone, Debug, PartialEq)]
+struct Match {
+    field: String,
+    operator: MatchOperator,
+    value: String,
+}
+
+impl FromStr for Match {
+    type Err = BuildError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let invalid = || BuildError::InvalidMatch {
+            expression: expression.into(),
+        };
+
+        let start = expression
+            .find(|c| matches!(c, '=' | '!' | '<' | '>'))
+            .ok_or_else(invalid)?;
+        let (field, rest) = expression.split_at(start);
+        // The operators are sorted so that `<=` is tried before `<`.
+        let (operator, value) = [
+            ("!=", MatchOperator::NotEqual),
+            ("<=", MatchOperator::LessOrEqual),
+            (">=", MatchOperator::GreaterOrEqual),
+            ("=", MatchOperator::Equal),
+            ("<", MatchOperator::Less),
+            (">", MatchOperator::Greater),
+        ]
+        .iter()
+        .find_map(|(symbol, operator)| rest.strip_prefix(symbol).map(|value| (*operator, value)))
+        .ok_or_else(invalid)?;
+
+        // Journal field names are made of uppercase letters, digits and
+        // underscores.
+        let valid_field = !field.is_empty()
+            && field
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+        if !valid_field {
+            return Err(invalid());
+        }
+        if operator.is_ordering() && value.parse::<f64>().is_err() {
+            return Err(BuildError::NonNumericMatch {
+                expression: expression.into(),
+            });
+        }
+
+        Ok(Self {
+            field: field.into(),
+            operator,
+            value: value.into(),
+        })
+    }
+}
+
+impl Match {
+    fn matches(&self, record: &Record) -> bool {
+        let value = match record.get(&self.field) {
+            Some(value) => value,
+            None => return self.operator == MatchOperator::NotEqual,
+        };
+        // Numbers, such as priorities, are compared by value. Other values
+        // are only compared for equality, so ordering them never matches.
+        let ordering = match (value.parse::<f64>(), self.value.parse::<f64>()) {
+            (Ok(value), Ok(expected)) => value.partial_cmp(&expected),
+            _ if self.operator.is_ordering() => None,
+            _ => Some(value.as_str().cmp(self.value.as_str())),
+        };
+        match self.operator {
+            MatchOperator::Equal => ordering == Some(Ordering::Equal),
+            MatchOperator::NotEqual => ordering != Some(Ordering::Equal),
+            MatchOperator::Less => ordering == Some(Ordering::Less),
+            MatchOperator::LessOrEqual => {
+                matches!(ordering, Some(Ordering::Less) | Some(Ordering::Equal))
+            }
+            MatchOperator::Greater => ordering == Some(Ordering::Greater),
+            MatchOperator::GreaterOrEqual => {
+                matches!(ordering, Some(Ordering::Greater) | Some(Ordering::Equal))
+            }
+        }
+    }
+}
+
+/// Match expressions grouped by field. Like the matches of `journalctl`, a
+/// record must match at least one expression of every field.
+#[derive(Debug, Default)]
+struct Matches(HashMap<String, Vec<Match>>);
+
+impl Matches {
+    fn parse(expressions: &[String]) -> Result<Self, BuildError> {
+        let mut matches = HashMap::<String, Vec<Match>>::new();
+        for expression in expressions {
+            let condition = expression.parse::<Match>()?;
+            matches
+                .entry(condition.field.clone())
+                .or_default()
+                .push(condition);
+        }
+        Ok(Self(matches))
+    }
+
+    fn matches(&self, record: &Record) -> bool {
+        self.0
+            .values()
+            .all(|conditions| conditions.iter().any(|condition| condition.matches(record)))
     }
 }
 
@@ -571,6 +820,7 @@ mod checkpointer_tests {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::event::EventStatus;
     use futures::Stream;
     use std::pin::Pin;
     use std::{
@@ -639,7 +889,47 @@ mod tests {
     }
 
     async fn run_journal(iunits: &[&str], xunits: &[&str], cursor: Option<&str>) -> Vec<Event> {
-        let (tx, rx) = Pipeline::new_test();
+        run_journal_with(iunits, xunits, cursor, false).await.0
+    }
+
+    /// Runs the source over the fake journal, returning the received
+    /// events and the saved checkpoint.
+    async fn run_journal_with(
+        iunits: &[&str],
+        xunits: &[&str],
+        cursor: Option<&str>,
+        acknowledgements: bool,
+    ) -> (Vec<Event>, Option<String>) {
+        run_source(cursor, |source| {
+            source.include_units = iunits.iter().map(|&s| s.into()).collect();
+            source.exclude_units = xunits.iter().map(|&s| s.into()).collect();
+            source.acknowledgements = acknowledgements;
+        })
+        .await
+    }
+
+    async fn run_journal_matching(matches: &[&str]) -> Vec<Event> {
+        let matches = matches.iter().map(|&s| s.into()).collect::<Vec<_>>();
+        run_source(None, |source| {
+            source.matches = Matches::parse(&matches).unwrap();
+        })
+        .await
+        .0
+    }
+
+    async fn run_source(
+        cursor: Option<&str>,
+        configure: impl FnOnce(&mut JournaldSource),
+    ) -> (Vec<Event>, Option<String>) {
+        run_source_with_status(cursor, EventStatus::Delivered, configure).await
+    }
+
+    async fn run_source_with_status(
+        cursor: Option<&str>,
+        status: EventStatus,
+        configure: impl FnOnce(&mut JournaldSource),
+    ) -> (Vec<Event>, Option<String>) {
+        let (tx, rx) = Pipeline::new_test_finalize(status);
         let (trigger, shutdown, _) = ShutdownSignal::new_wired();
 
         let tempdir = tempdir().unwrap();
@@ -657,27 +947,39 @@ mod tests {
                 .expect("Could not set checkpoint");
         }
 
-        let include_units: HashSet<String> = iunits.iter().map(|&s| s.into()).collect();
-        let exclude_units: HashSet<String> = xunits.iter().map(|&s| s.into()).collect();
-
-        let source = JournaldSource {
-            include_units,
-            exclude_units,
+        let mut source = JournaldSource {
+            include_units: HashSet::new(),
+            exclude_units: HashSet::new(),
+            matches: Matches::default(),
+            from_cursor: None,
             checkpoint_path,
             batch_size: DEFAULT_BATCH_SIZE,
             remap_priority: true,
             out: tx,
-        }
-        .run_shutdown(
+            acknowledgements: false,
+        };
+        configure(&mut source);
+        let source = source.run_shutdown(
             shutdown,
             Box::new(|checkpoint| Ok(FakeJournal::new(checkpoint))),
         );
-        tokio::spawn(source);
+        let source = tokio::spawn(source);
+        let events = tokio::spawn(rx.collect::<Vec<_>>());
 
         sleep(Duration::from_millis(100)).await;
         drop(trigger);
 
-        timeout(Duration::from_secs(1), rx.collect()).await.unwrap()
+        timeout(Duration::from_secs(1), source)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let events = timeout(Duration::from_secs(1), events)
+            .await
+            .unwrap()
+            .unwrap();
+        let checkpoint = checkpointer.get().await.unwrap();
+        (events, checkpoint)
     }
 
     #[tokio::test]
@@ -733,6 +1035,52 @@ mod tests {
         assert_eq!(timestamp(&received[0]), value_ts(1578529839, 140002000));
     }
 
+    #[tokio::test]
+    async fn starts_from_cursor() {
+        let (received, _) = run_source(None, |source| source.from_cursor = Some("3".into())).await;
+        assert_eq!(received.len(), 4);
+        assert_eq!(
+            message(&received[0]),
+            Value::Bytes("Missing timestamp".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn checkpoint_overrides_from_cursor() {
+        let (received, _) =
+            run_source(Some("5"), |source| source.from_cursor = Some("1".into())).await;
+        assert_eq!(received.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn checkpoints_after_acknowledgement() {
+        let (received, checkpoint) = run_journal_with(&[], &[], None, true).await;
+        assert_eq!(received.len(), 7);
+        assert_eq!(checkpoint.as_deref(), Some("6"));
+    }
+
+    #[tokio::test]
+    async fn keeps_checkpoint_when_not_delivered() {
+        let (received, checkpoint) =
+            run_source_with_status(Some("1"), EventStatus::Errored, |source| {
+                source.acknowledgements = true
+            })
+            .await;
+        assert_eq!(received.len(), 6);
+        assert_eq!(checkpoint.as_deref(), Some("1"));
+    }
+
+    #[tokio::test]
+    async fn checkpoints_after_rejection() {
+        let (received, checkpoint) =
+            run_source_with_status(Some("1"), EventStatus::Failed, |source| {
+                source.acknowledgements = true
+            })
+            .await;
+        assert_eq!(received.len(), 6);
+        assert_eq!(checkpoint.as_deref(), Some("6"));
+    }
+
     #[tokio::test]
     async fn parses_array_messages() {
         let received = run_journal(&["badunit.service"], &[], None).await;
@@ -768,6 +1116,123 @@ mod tests {
         assert_eq!(timestamp(&received[1]), value_ts(1578529839, 140005000));
     }
 
+    #[tokio::test]
+    async fn filters_on_matches() {
+        let received = run_journal_matching(&["PRIORITY<=3"]).await;
+        assert_eq!(received.len(), 3);
+        // Priorities are remapped after matching.
+        assert_eq!(priority(&received[0]), Value::Bytes("CRIT".into()));
+
+        let received = run_journal_matching(&["_SYSTEMD_UNIT=stdout", "PRIORITY=3"]).await;
+        assert_eq!(received.len(), 1);
+        assert_eq!(
+            message(&received[0]),
+            Value::Bytes("Different timestamps".into())
+        );
+
+        let received =
+            run_journal_matching(&["_SYSTEMD_UNIT=unit.service", "_SYSTEMD_UNIT=stdout"]).await;
+        assert_eq!(received.len(), 3);
+
+        let received = run_journal_matching(&["_SYSTEMD_UNIT!=stdout", "MISSING_FIELD!=x"]).await;
+        assert_eq!(received.len(), 5);
+    }
+
+    #[test]
+    fn parses_matches() {
+        assert_eq!(
+            "PRIORITY<=3".parse::<Match>().unwrap(),
+            Match {
+                field: "PRIORITY".into(),
+                operator: MatchOperator::LessOrEqual,
+                value: "3".into(),
+            }
+        );
+        assert_eq!(
+            "_TRANSPORT!=kernel".parse::<Match>().unwrap(),
+            Match {
+                field: "_TRANSPORT".into(),
+                operator: MatchOperator::NotEqual,
+                value: "kernel".into(),
+            }
+        );
+        assert_eq!(
+            "MESSAGE=a=b".parse::<Match>().unwrap().value,
+            String::from("a=b")
+        );
+        for invalid in &["PRIORITY", "=3", "priority=3", "PRIORITY!3", "PRIORITY 3"] {
+            assert!(invalid.parse::<Match>().is_err(), "{}", invalid);
+        }
+        for non_numeric in &["_COMM<sshd", "_COMM<=sshd", "_COMM>sshd", "_COMM>=sshd"] {
+            assert!(
+                matches!(
+                    non_numeric.parse::<Match>(),
+                    Err(BuildError::NonNumericMatch { .. })
+                ),
+                "{}",
+                non_numeric
+            );
+        }
+    }
+
+    #[test]
+    fn orders_only_numeric_values() {
+        let condition = "PRIORITY<=3".parse::<Match>().unwrap();
+        let record = |priority: &str| {
+            vec![("PRIORITY".to_owned(), priority.to_owned())]
+                .into_iter()
+                .collect::<Record>()
+        };
+        assert!(condition.matches(&record("2")));
+        assert!(!condition.matches(&record("10")));
+        assert!(!condition.matches(&record("debug")));
+    }
+
+    #[test]
+    fn builds_journalctl_args() {
+        let mut journalctl = Journalctl {
+            path: JOURNALCTL.clone(),
+            journal_directory: None,
+            current_boot_only: true,
+            boot: None,
+            since_now: false,
+        };
+        assert_eq!(
+            journalctl.args(&None),
+            vec![
+                "--follow",
+                "--all",
+                "--show-cursor",
+                "--output=json",
+                "--boot",
+                "--since=2000-01-01"
+            ]
+        );
+
+        journalctl.journal_directory = Some("/var/lib/containers/journal".into());
+        journalctl.boot = Some("-1".into());
+        journalctl.since_now = true;
+        assert_eq!(
+            journalctl.args(&None)[4..],
+            [
+                "--directory=/var/lib/containers/journal",
+                "--boot=-1",
+                "--since=now"
+            ]
+        );
+        assert_eq!(
+            journalctl.args(&Some("s=abc".into()))[6..],
+            ["--after-cursor=s=abc"]
+        );
+
+        journalctl.boot = None;
+        journalctl.current_boot_only = false;
+        assert_eq!(
+            journalctl.args(&None)[4..],
+            ["--directory=/var/lib/containers/journal", "--since=now"]
+        );
+    }
+
     #[test]
     fn filter_unit_works_correctly() {
         let empty: HashSet<String> = vec![].into_iter().collect();
@@ -0,0 +1,521 @@
+//! This mod implements `kubernetes_events` source.
+//! The source watches the cluster `Event` resources via the Kubernetes API
+//! and emits each of them as a log event. It's intended to run as a single
+//! instance per cluster, rather than as a `DaemonSet`.
+
+#![deny(missing_docs)]
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, SourceConfig, SourceContext, SourceDescription,
+    },
+    event::{Event, LogEvent},
+    internal_events::KubernetesEventsEventReceived,
+    kubernetes as k8s,
+    shutdown::ShutdownSignal,
+    sources, Pipeline,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{future::BoxFuture, SinkExt};
+use k8s_openapi::api::core::v1::Event as KubeEvent;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+const COMPONENT_NAME: &str = "kubernetes_events";
+
+/// Configuration for the `kubernetes_events` source.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct Config {
+    /// The namespace to watch the `Event`s at. All the namespaces are watched
+    /// if unset.
+    namespace: Option<String>,
+
+    /// Specifies the field selector to filter `Event`s with.
+    field_selector: Option<String>,
+
+    /// Specifies the label selector to filter `Event`s with.
+    label_selector: Option<String>,
+
+    /// Optional path to a kubeconfig file readable by Vector. If not set,
+    /// Vector will try to connect to Kubernetes using in-cluster configuration.
+    kube_config_file: Option<PathBuf>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<Config>(COMPONENT_NAME)
+}
+
+impl GenerateConfig for Config {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(&Self::default()).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "kubernetes_events")]
+impl SourceConfig for Config {
+    async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
+        let k8s_config = match &self.kube_config_file {
+            Some(kc) => k8s::client::config::Config::kubeconfig(kc)?,
+            None => k8s::client::config::Config::in_cluster()?,
+        };
+        let client = k8s::client::Client::new(k8s_config)?;
+
+        let field_selector = self.field_selector.clone();
+        let label_selector = self.label_selector.clone();
+        Ok(match &self.namespace {
+            Some(namespace) => {
+                let watcher = k8s::api_watcher::ApiWatcher::new(
+                    client,
+                    k8s::watch_request_builder::Namespaced(
+                        namespace.clone(),
+                        KubeEvent::watch_namespaced_event,
+                    ),
+                );
+                let watcher = k8s::instrumenting_watcher::InstrumentingWatcher::new(watcher);
+                Box::pin(run(
+                    watcher,
+                    field_selector,
+                    label_selector,
+                    cx.out,
+                    cx.shutdown,
+                ))
+            }
+            None => {
+                let watcher = k8s::api_watcher::ApiWatcher::new(
+                    client,
+                    KubeEvent::watch_event_for_all_namespaces,
+                );
+                let watcher = k8s::instrumenting_watcher::InstrumentingWatcher::new(watcher);
+                Box::pin(run(
+                    watcher,
+                    field_selector,
+                    label_selector,
+                    cx.out,
+                    cx.shutdown,
+                ))
+            }
+        })
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        COMPONENT_NAME
+    }
+}
+
+/// Run the reflector until shutdown, emitting the watched `Event`s to `out`.
+async fn run<W>(
+    watcher: W,
+    field_selector: Option<String>,
+    label_selector: Option<String>,
+    out: Pipeline,
+    shutdown: ShutdownSignal,
+) -> Result<(), ()>
+where
+    W: k8s::watcher::Watcher<Object = KubeEvent>,
+    <W as k8s::watcher::Watcher>::InvocationError: Unpin,
+    <W as k8s::watcher::Watcher>::StreamError: Unpin,
+{
+    let mut reflector = k8s::reflector::Reflector::new(
+        watcher,
+        EventsWriter::new(out, Utc::now()),
+        field_selector,
+        label_selector,
+        Duration::from_secs(1),
+    );
+
+    tokio::select! {
+        result = reflector.run() => {
+            if let Err(error) = result {
+                error!(message = "Reflector process exited with an error.", %error);
+            }
+            Err(())
+        }
+        _ = shutdown => {
+            info!(message = "Reflector process completed gracefully.");
+            Ok(())
+        }
+    }
+}
+
+/// A [`k8s::state::Write`] implementation that, rather than caching the
+/// state, emits every new `Event` to the pipeline.
+///
+/// The initial list contains the `Event`s retained by the cluster, so the ones
+/// that last occurred before the source started are skipped rather than
+/// emitted again on every restart.
+///
+/// When the reflector desyncs it lists all the `Event`s again from scratch,
+/// so the resource versions already emitted are tracked per `uid` and the
+/// repeated ones are skipped.
+struct EventsWriter {
+    out: Pipeline,
+    start_time: DateTime<Utc>,
+    emitted: HashMap<String, String>,
+    /// The resource versions emitted before the last resync. The ones that
+    /// aren't listed again are dropped at the next resync.
+    emitted_before_resync: HashMap<String, String>,
+}
+
+impl EventsWriter {
+    fn new(out: Pipeline, start_time: DateTime<Utc>) -> Self {
+        Self {
+            out,
+            start_time,
+            emitted: HashMap::new(),
+            emitted_before_resync: HashMap::new(),
+        }
+    }
+
+    async fn emit(&mut self, item: KubeEvent) {
+        // `Event` timestamps have a second precision.
+        if let Some(timestamp) = last_occurrence(&item) {
+            if timestamp.timestamp() < self.start_time.timestamp() {
+                trace!(message = "Skipping event that occurred before the source started.", %timestamp);
+                return;
+            }
+        }
+
+        let metadata = &item.metadata;
+        if let (Some(uid), Some(resource_version)) = (&metadata.uid, &metadata.resource_version) {
+            let emitted_before_resync = self.emitted_before_resync.remove(uid);
+            let emitted = self
+                .emitted
+                .insert(uid.clone(), resource_version.clone())
+                .or(emitted_before_resync);
+            if emitted.as_ref() == Some(resource_version) {
+                trace!(message = "Skipping already emitted event.", %uid, %resource_version);
+                return;
+            }
+        }
+
+        let event = create_event(item);
+        emit!(KubernetesEventsEventReceived);
+        if let Err(error) = self.out.send(event).await {
+            error!(message = "Error sending event.", %error);
+        }
+    }
+}
+
+#[async_trait]
+impl k8s::state::Write for EventsWriter {
+    type Item = KubeEvent;
+
+    async fn add(&mut self, item: Self::Item) {
+        self.emit(item).await;
+    }
+
+    async fn update(&mut self, item: Self::Item) {
+        // Repeated occurrences update the `count` and `last_timestamp` of the
+        // existing `Event`, each of them is emitted.
+        self.emit(item).await;
+    }
+
+    async fn delete(&mut self, item: Self::Item) {
+        // `Event`s are deleted when they expire, there's nothing to emit.
+        if let Some(uid) = item.metadata.uid {
+            self.emitted.remove(&uid);
+            self.emitted_before_resync.remove(&uid);
+        }
+    }
+
+    async fn resync(&mut self) {
+        // Keep the emitted resource versions until the next resync, the
+        // relisted `Event`s are deduplicated against them and the expired
+        // ones, which aren't listed anymore, are pruned.
+        self.emitted_before_resync = std::mem::take(&mut self.emitted);
+    }
+}
+
+#[async_trait]
+impl k8s::state::MaintainedWrite for EventsWriter {
+    fn maintenance_request(&mut self) -> Option<BoxFuture<'_, ()>> {
+        None
+    }
+
+    async fn perform_maintenance(&mut self) {}
+}
+
+fn create_event(item: KubeEvent) -> Event {
+    let mut log = LogEvent::default();
+
+    log.insert(log_schema().message_key(), item.message.unwrap_or_default());
+    log.insert(log_schema().source_type_key(), COMPONENT_NAME.to_owned());
+
+    let timestamp = last_occurrence(&item).unwrap_or_else(Utc::now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+
+    insert_optional(&mut log, "kubernetes.event_name", item.metadata.name);
+    insert_optional(
+        &mut log,
+        "kubernetes.event_namespace",
+        item.metadata.namespace,
+    );
+    insert_optional(&mut log, "kubernetes.event_uid", item.metadata.uid);
+
+    let involved_object = item.involved_object;
+    for (key, val) in vec![
+        ("api_version", involved_object.api_version),
+        ("kind", involved_object.kind),
+        ("name", involved_object.name),
+        ("namespace", involved_object.namespace),
+        ("uid", involved_object.uid),
+        ("field_path", involved_object.field_path),
+    ] {
+        insert_optional(
+            &mut log,
+            &format!("kubernetes.involved_object.{}", key),
+            val,
+        );
+    }
+
+    insert_optional(&mut log, "reason", item.reason);
+    insert_optional(&mut log, "type", item.type_);
+    insert_optional(&mut log, "action", item.action);
+    if let Some(count) = item.count {
+        log.insert("count", count as i64);
+    }
+    if let Some(first_timestamp) = item.first_timestamp {
+        log.insert("first_timestamp", first_timestamp.0);
+    }
+    if let Some(last_timestamp) = item.last_timestamp {
+        log.insert("last_timestamp", last_timestamp.0);
+    }
+    if let Some(source) = item.source {
+        insert_optional(&mut log, "source.component", source.component);
+        insert_optional(&mut log, "source.host", source.host);
+    }
+    insert_optional(&mut log, "reporting_component", item.reporting_component);
+    insert_optional(&mut log, "reporting_instance", item.reporting_instance);
+
+    log.into()
+}
+
+/// The time the `Event` last occurred at, if set.
+fn last_occurrence(item: &KubeEvent) -> Option<DateTime<Utc>> {
+    item.last_timestamp
+        .as_ref()
+        .map(|time| time.0)
+        .or_else(|| item.event_time.as_ref().map(|time| time.0))
+        .or_else(|| item.first_timestamp.as_ref().map(|time| time.0))
+}
+
+fn insert_optional(log: &mut LogEvent, key: &str, val: Option<String>) {
+    if let Some(val) = val {
+        log.insert(key, val);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        kubernetes::{
+            mock_watcher::{self, MockWatcher},
+            state::Write,
+        },
+        test_util::trace_init,
+    };
+    use futures::{channel::mpsc, StreamExt};
+    use k8s_openapi::{
+        api::core::v1::ObjectReference,
+        apimachinery::pkg::apis::meta::v1::{ObjectMeta, Time, WatchEvent},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<Config>();
+    }
+
+    fn make_event(uid: &str, resource_version: &str, message: &str) -> KubeEvent {
+        KubeEvent {
+            metadata: ObjectMeta {
+                name: Some(format!("{}-name", uid)),
+                namespace: Some("sandbox0-ns".to_owned()),
+                uid: Some(uid.to_owned()),
+                resource_version: Some(resource_version.to_owned()),
+                ..ObjectMeta::default()
+            },
+            involved_object: ObjectReference {
+                kind: Some("Pod".to_owned()),
+                name: Some("sandbox0-pod".to_owned()),
+                namespace: Some("sandbox0-ns".to_owned()),
+                uid: Some("sandbox0-pod-uid".to_owned()),
+                ..ObjectReference::default()
+            },
+            message: Some(message.to_owned()),
+            reason: Some("Started".to_owned()),
+            type_: Some("Normal".to_owned()),
+            count: Some(1),
+            ..KubeEvent::default()
+        }
+    }
+
+    #[test]
+    fn create_event_with_involved_object() {
+        let timestamp = Utc::now();
+        let mut item = make_event("uid0", "10", "Started container");
+        item.last_timestamp = Some(Time(timestamp));
+
+        let event = create_event(item);
+        let log = event.as_log();
+
+        assert_eq!(log[log_schema().message_key()], "Started container".into());
+        assert_eq!(log[log_schema().source_type_key()], COMPONENT_NAME.into());
+        assert_eq!(log[log_schema().timestamp_key()], timestamp.into());
+        assert_eq!(log["kubernetes.event_name"], "uid0-name".into());
+        assert_eq!(log["kubernetes.event_uid"], "uid0".into());
+        assert_eq!(log["kubernetes.involved_object.kind"], "Pod".into());
+        assert_eq!(
+            log["kubernetes.involved_object.name"],
+            "sandbox0-pod".into()
+        );
+        assert_eq!(log["reason"], "Started".into());
+        assert_eq!(log["type"], "Normal".into());
+        assert_eq!(log["count"], 1.into());
+        assert!(!log.contains("source.component"));
+    }
+
+    #[tokio::test]
+    async fn skips_events_before_start() {
+        let (out, rx) = Pipeline::new_test();
+        let start_time = Utc::now();
+        let mut writer = EventsWriter::new(out, start_time);
+
+        let mut item = make_event("uid0", "10", "before start");
+        item.last_timestamp = Some(Time(start_time - chrono::Duration::minutes(5)));
+        writer.add(item).await;
+
+        // Repeated occurrences after the start are emitted.
+        let mut item = make_event("uid0", "11", "after start");
+        item.first_timestamp = Some(Time(start_time - chrono::Duration::minutes(5)));
+        item.last_timestamp = Some(Time(start_time + chrono::Duration::minutes(1)));
+        writer.update(item).await;
+        drop(writer);
+
+        let messages = rx
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(messages, vec!["after start"]);
+    }
+
+    #[tokio::test]
+    async fn prunes_emitted_on_resync() {
+        let (out, _rx) = Pipeline::new_test();
+        let mut writer = EventsWriter::new(out, Utc::now());
+
+        writer.add(make_event("uid0", "10", "first")).await;
+        writer.add(make_event("uid1", "15", "second")).await;
+
+        // Only `uid1` is listed again, `uid0` has expired meanwhile.
+        writer.resync().await;
+        writer.add(make_event("uid1", "15", "second")).await;
+        writer.resync().await;
+
+        assert!(writer.emitted.is_empty());
+        assert_eq!(
+            writer.emitted_before_resync.keys().collect::<Vec<_>>(),
+            vec!["uid1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn emits_events_and_deduplicates_on_resume() {
+        trace_init();
+
+        let (out, rx) = Pipeline::new_test();
+        let (_trigger, shutdown, _) = ShutdownSignal::new_wired();
+
+        let (watcher_events_tx, mut watcher_events_rx) = mpsc::channel(0);
+        let (mut watcher_invocations_tx, watcher_invocations_rx) = mpsc::channel(0);
+        let watcher = MockWatcher::<KubeEvent>::new(watcher_events_tx, watcher_invocations_rx);
+
+        let source = tokio::spawn(run(watcher, None, None, out, shutdown));
+
+        // Drives a single watch invocation, streaming `items` and then
+        // desyncing to make the reflector list all the `Event`s again.
+        async fn invocation(
+            watcher_events_rx: &mut mpsc::Receiver<mock_watcher::ScenarioEvent>,
+            watcher_invocations_tx: &mut mpsc::Sender<
+                mock_watcher::ScenarioActionInvocation<KubeEvent>,
+            >,
+            items: Vec<WatchEvent<KubeEvent>>,
+        ) {
+            assert!(matches!(
+                watcher_events_rx.next().await.unwrap(),
+                mock_watcher::ScenarioEvent::Invocation(_)
+            ));
+            let (mut stream_tx, stream_rx) = mpsc::channel(0);
+            watcher_invocations_tx
+                .send(mock_watcher::ScenarioActionInvocation::Ok(stream_rx))
+                .await
+                .unwrap();
+            for item in items {
+                assert_eq!(
+                    watcher_events_rx.next().await.unwrap(),
+                    mock_watcher::ScenarioEvent::Stream
+                );
+                stream_tx
+                    .send(mock_watcher::ScenarioActionStream::Ok(item))
+                    .await
+                    .unwrap();
+            }
+            assert_eq!(
+                watcher_events_rx.next().await.unwrap(),
+                mock_watcher::ScenarioEvent::Stream
+            );
+            stream_tx
+                .send(mock_watcher::ScenarioActionStream::ErrDesync)
+                .await
+                .unwrap();
+        }
+
+        invocation(
+            &mut watcher_events_rx,
+            &mut watcher_invocations_tx,
+            vec![
+                WatchEvent::Added(make_event("uid0", "10", "first")),
+                WatchEvent::Added(make_event("uid1", "15", "second")),
+            ],
+        )
+        .await;
+
+        // After the desync the same `Event`s are listed again, along with an
+        // update of an existing one and a new one.
+        invocation(
+            &mut watcher_events_rx,
+            &mut watcher_invocations_tx,
+            vec![
+                WatchEvent::Added(make_event("uid0", "10", "first")),
+                WatchEvent::Added(make_event("uid1", "20", "second again")),
+                WatchEvent::Added(make_event("uid2", "25", "third")),
+            ],
+        )
+        .await;
+
+        // Wait for the next invocation to make sure the stream was processed.
+        assert!(matches!(
+            watcher_events_rx.next().await.unwrap(),
+            mock_watcher::ScenarioEvent::Invocation(_)
+        ));
+        watcher_invocations_tx
+            .send(mock_watcher::ScenarioActionInvocation::ErrOther)
+            .await
+            .unwrap();
+        assert_eq!(source.await.unwrap(), Err(()));
+
+        let messages = rx
+            .take(4)
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(messages, vec!["first", "second", "second again", "third"]);
+    }
+}
@@ -8,7 +8,7 @@
 use crate::event::{Event, LogEvent};
 use crate::internal_events::{
     FileSourceInternalEventsEmitter, KubernetesLogsEventAnnotationFailed,
-    KubernetesLogsEventReceived,
+    KubernetesLogsEventReceived, KubernetesLogsWatchForbidden,
 };
 use crate::kubernetes as k8s;
 use crate::{
@@ -24,15 +24,18 @@ use file_source::{
     Checkpointer, FileServer, FileServerShutdown, FingerprintStrategy, Fingerprinter, Line,
     ReadFrom,
 };
-use k8s_openapi::api::core::v1::Pod;
+use http::StatusCode;
+use k8s_openapi::api::core::v1::{Namespace, Node, Pod};
 use serde::{Deserialize, Serialize};
 use shared::TimeZone;
-use std::convert::TryInto;
+use std::convert::{Infallible, TryInto};
 use std::path::PathBuf;
 use std::time::Duration;
 
 mod k8s_paths_provider;
 mod lifecycle;
+mod namespace_metadata_annotator;
+mod node_metadata_annotator;
 mod parser;
 mod partial_events_merger;
 mod path_helpers;
@@ -43,6 +46,8 @@ mod util;
 use futures::{future::FutureExt, sink::Sink, stream::StreamExt};
 use k8s_paths_provider::K8sPathsProvider;
 use lifecycle::Lifecycle;
+use namespace_metadata_annotator::NamespaceMetadataAnnotator;
+use node_metadata_annotator::NodeMetadataAnnotator;
 use pod_metadata_annotator::PodMetadataAnnotator;
 
 /// The key we use for `file` field.
@@ -77,6 +82,16 @@ pub struct Config {
     /// Specifies the field names for metadata annotation.
     annotation_fields: pod_metadata_annotator::FieldsSpec,
 
+    /// Specifies the field names for `Namespace` metadata annotation.
+    /// `Namespace`s are only watched, and events annotated with their
+    /// metadata, when this is set.
+    namespace_annotation_fields: Option<namespace_metadata_annotator::FieldsSpec>,
+
+    /// Specifies the field names for `Node` metadata annotation.
+    /// The `Node` is only watched, and events annotated with its metadata,
+    /// when this is set.
+    node_annotation_fields: Option<node_metadata_annotator::FieldsSpec>,
+
     /// A list of glob patterns to exclude from reading the files.
     exclude_paths_glob_patterns: Vec<PathBuf>,
 
@@ -139,6 +154,8 @@ impl Default for Config {
             auto_partial_merge: true,
             data_dir: None,
             annotation_fields: pod_metadata_annotator::FieldsSpec::default(),
+            namespace_annotation_fields: None,
+            node_annotation_fields: None,
             exclude_paths_glob_patterns: default_path_exclusion(),
             max_read_bytes: default_max_read_bytes(),
             max_line_bytes: default_max_line_bytes(),
@@ -180,6 +197,9 @@ struct Source {
     data_dir: PathBuf,
     auto_partial_merge: bool,
     fields_spec: pod_metadata_annotator::FieldsSpec,
+    namespace_fields_spec: Option<namespace_metadata_annotator::FieldsSpec>,
+    node_fields_spec: Option<node_metadata_annotator::FieldsSpec>,
+    self_node_name: String,
     field_selector: String,
     label_selector: String,
     exclude_paths: Vec<glob::Pattern>,
@@ -193,7 +213,8 @@ struct Source {
 
 impl Source {
     fn new(config: &Config, globals: &GlobalOptions, name: &str) -> crate::Result<Self> {
-        let field_selector = prepare_field_selector(config)?;
+        let self_node_name = prepare_self_node_name(config)?;
+        let field_selector = prepare_field_selector(config, &self_node_name);
         let label_selector = prepare_label_selector(config);
 
         let k8s_config = match &config.kube_config_file {
@@ -217,6 +238,9 @@ impl Source {
             data_dir,
             auto_partial_merge: config.auto_partial_merge,
             fields_spec: config.annotation_fields.clone(),
+            namespace_fields_spec: config.namespace_annotation_fields.clone(),
+            node_fields_spec: config.node_annotation_fields.clone(),
+            self_node_name,
             field_selector,
             label_selector,
             exclude_paths,
@@ -239,6 +263,9 @@ impl Source {
             data_dir,
             auto_partial_merge,
             fields_spec,
+            namespace_fields_spec,
+            node_fields_spec,
+            self_node_name,
             field_selector,
             label_selector,
             exclude_paths,
@@ -250,7 +277,8 @@ impl Source {
             timezone,
         } = self;
 
-        let watcher = k8s::api_watcher::ApiWatcher::new(client, Pod::watch_pod_for_all_namespaces);
+        let watcher =
+            k8s::api_watcher::ApiWatcher::new(client.clone(), Pod::watch_pod_for_all_namespaces);
         let watcher = k8s::instrumenting_watcher::InstrumentingWatcher::new(watcher);
         let (state_reader, state_writer) = evmap::new();
         let state_writer =
@@ -268,6 +296,85 @@ impl Source {
         );
         let reflector_process = reflector.run();
 
+        // Namespaces and the `Node` are looked up by name rather than by uid.
+        let (ns_annotator, ns_reflector_process) = match namespace_fields_spec {
+            Some(namespace_fields_spec) => {
+                let ns_watcher =
+                    k8s::api_watcher::ApiWatcher::new(client.clone(), Namespace::watch_namespace);
+                let ns_watcher = k8s::instrumenting_watcher::InstrumentingWatcher::new(ns_watcher);
+                let (ns_state_reader, ns_state_writer) = evmap::new();
+                let ns_state_writer = k8s::state::evmap::Writer::new_with_key(
+                    ns_state_writer,
+                    Some(Duration::from_millis(10)),
+                    k8s::state::evmap::name,
+                );
+                let ns_state_writer = k8s::state::instrumenting::Writer::new(ns_state_writer);
+                let ns_state_writer = k8s::state::delayed_delete::Writer::new(
+                    ns_state_writer,
+                    Duration::from_secs(60),
+                );
+
+                let mut ns_reflector = k8s::reflector::Reflector::new(
+                    ns_watcher,
+                    ns_state_writer,
+                    None,
+                    None,
+                    Duration::from_secs(1),
+                );
+                let ns_reflector_process = async move {
+                    degrade_on_forbidden(ns_reflector.run().await, "namespaces").await
+                };
+
+                (
+                    Some(NamespaceMetadataAnnotator::new(
+                        ns_state_reader,
+                        namespace_fields_spec,
+                    )),
+                    Some(ns_reflector_process),
+                )
+            }
+            None => (None, None),
+        };
+
+        let (node_annotator, node_reflector_process) = match node_fields_spec {
+            Some(node_fields_spec) => {
+                let node_watcher = k8s::api_watcher::ApiWatcher::new(client, Node::watch_node);
+                let node_watcher =
+                    k8s::instrumenting_watcher::InstrumentingWatcher::new(node_watcher);
+                let (node_state_reader, node_state_writer) = evmap::new();
+                let node_state_writer = k8s::state::evmap::Writer::new_with_key(
+                    node_state_writer,
+                    Some(Duration::from_millis(10)),
+                    k8s::state::evmap::name,
+                );
+                let node_state_writer = k8s::state::instrumenting::Writer::new(node_state_writer);
+                let node_state_writer = k8s::state::delayed_delete::Writer::new(
+                    node_state_writer,
+                    Duration::from_secs(60),
+                );
+
+                let mut node_reflector = k8s::reflector::Reflector::new(
+                    node_watcher,
+                    node_state_writer,
+                    Some(format!("metadata.name={}", self_node_name)),
+                    None,
+                    Duration::from_secs(1),
+                );
+                let node_reflector_process =
+                    async move { degrade_on_forbidden(node_reflector.run().await, "nodes").await };
+
+                (
+                    Some(NodeMetadataAnnotator::new(
+                        node_state_reader,
+                        self_node_name,
+                        node_fields_spec,
+                    )),
+                    Some(node_reflector_process),
+                )
+            }
+            None => (None, None),
+        };
+
         let paths_provider = K8sPathsProvider::new(state_reader.clone(), exclude_paths);
         let annotator = PodMetadataAnnotator::new(state_reader, fields_spec);
 
@@ -347,6 +454,12 @@ impl Source {
                 ingestion_timestamp_field.as_deref(),
             );
             let file_info = annotator.annotate(&mut event, &line.filename);
+            if let (Some(ns_annotator), Some(file_info)) = (&ns_annotator, &file_info) {
+                ns_annotator.annotate(&mut event, file_info.pod_namespace);
+            }
+            if let Some(node_annotator) = &node_annotator {
+                node_annotator.annotate(&mut event);
+            }
 
             emit!(KubernetesLogsEventReceived {
                 file: &line.filename,
@@ -383,6 +496,31 @@ impl Source {
                 });
             slot.bind(Box::pin(fut));
         }
+        if let Some(ns_reflector_process) = ns_reflector_process {
+            let (slot, shutdown) = lifecycle.add();
+            let fut = util::cancel_on_signal(ns_reflector_process, shutdown).map(|result| {
+                match result {
+                    Ok(()) => info!(message = "Namespace reflector process completed gracefully."),
+                    Err(error) => {
+                        error!(message = "Namespace reflector process exited with an error.", %error)
+                    }
+                }
+            });
+            slot.bind(Box::pin(fut));
+        }
+        if let Some(node_reflector_process) = node_reflector_process {
+            let (slot, shutdown) = lifecycle.add();
+            let fut =
+                util::cancel_on_signal(node_reflector_process, shutdown).map(
+                    |result| match result {
+                        Ok(()) => info!(message = "Node reflector process completed gracefully."),
+                        Err(error) => {
+                            error!(message = "Node reflector process exited with an error.", %error)
+                        }
+                    },
+                );
+            slot.bind(Box::pin(fut));
+        }
         {
             let (slot, shutdown) = lifecycle.add();
             let fut = util::run_file_server(file_server, file_source_tx, shutdown, checkpointer)
@@ -421,6 +559,26 @@ impl Source {
     }
 }
 
+/// Keeps the source running without the annotations of a metadata reflector
+/// that isn't allowed to watch its `resource`, rather than shutting it down.
+async fn degrade_on_forbidden<S>(
+    result: Result<Infallible, k8s::reflector::Error<k8s::api_watcher::invocation::Error, S>>,
+    resource: &'static str,
+) -> Result<Infallible, k8s::reflector::Error<k8s::api_watcher::invocation::Error, S>>
+where
+    S: std::error::Error + 'static,
+{
+    match result {
+        Err(k8s::reflector::Error::Invocation {
+            source: k8s::api_watcher::invocation::Error::BadStatus { status },
+        }) if status == StatusCode::FORBIDDEN => {
+            emit!(KubernetesLogsWatchForbidden { resource });
+            futures::future::pending().await
+        }
+        result => result,
+    }
+}
+
 fn create_event(line: Bytes, file: &str, ingestion_timestamp_field: Option<&str>) -> Event {
     let mut event = LogEvent::from(line);
 
@@ -501,9 +659,9 @@ fn prepare_exclude_paths(config: &Config) -> crate::Result<Vec<glob::Pattern>> {
     Ok(exclude_paths)
 }
 
-// This function constructs the effective field selector to use, based on
-// the specified configuration.
-fn prepare_field_selector(config: &Config) -> crate::Result<String> {
+// This function resolves the name of the `Node` Vector runs at, from the
+// config or from the env var.
+fn prepare_self_node_name(config: &Config) -> crate::Result<String> {
     let self_node_name = if config.self_node_name.is_empty()
         || config.self_node_name == default_self_node_name_env_template()
     {
@@ -520,17 +678,19 @@ fn prepare_field_selector(config: &Config) -> crate::Result<String> {
         message = "Obtained Kubernetes Node name to collect logs for (self).",
         ?self_node_name
     );
+    Ok(self_node_name)
+}
 
+// This function constructs the effective field selector to use, based on
+// the specified configuration.
+fn prepare_field_selector(config: &Config, self_node_name: &str) -> String {
     let field_selector = format!("spec.nodeName={}", self_node_name);
 
     if config.extra_field_selector.is_empty() {
-        return Ok(field_selector);
+        return field_selector;
     }
 
-    Ok(format!(
-        "{},{}",
-        field_selector, config.extra_field_selector
-    ))
+    format!("{},{}", field_selector, config.extra_field_selector)
 }
 
 // This function constructs the effective label selector to use, based on
@@ -627,7 +787,8 @@ mod tests {
         ];
 
         for (input, expected) in cases {
-            let output = super::prepare_field_selector(&input).unwrap();
+            let self_node_name = super::prepare_self_node_name(&input).unwrap();
+            let output = super::prepare_field_selector(&input, &self_node_name);
             assert_eq!(expected, output, "expected left, actual right");
         }
     }
@@ -657,4 +818,50 @@ mod tests {
             assert_eq!(expected, output, "expected left, actual right");
         }
     }
+
+    #[test]
+    fn metadata_annotations_are_opt_in() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(config.namespace_annotation_fields.is_none());
+        assert!(config.node_annotation_fields.is_none());
+
+        let config: Config = toml::from_str(
+            r#"
+            namespace_annotation_fields = {}
+            node_annotation_fields.node_labels = "node"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.namespace_annotation_fields.unwrap().namespace_labels,
+            "kubernetes.namespace_labels"
+        );
+        assert_eq!(config.node_annotation_fields.unwrap().node_labels, "node");
+    }
+
+    #[tokio::test]
+    async fn degrade_on_forbidden() {
+        use crate::kubernetes::{api_watcher, reflector};
+        use futures::FutureExt;
+        use http::StatusCode;
+
+        let error = |status| {
+            Err::<std::convert::Infallible, _>(
+                reflector::Error::<_, api_watcher::stream::Error>::Invocation {
+                    source: api_watcher::invocation::Error::BadStatus { status },
+                },
+            )
+        };
+
+        // The source keeps running without the metadata.
+        assert!(
+            super::degrade_on_forbidden(error(StatusCode::FORBIDDEN), "nodes")
+                .now_or_never()
+                .is_none()
+        );
+        assert!(matches!(
+            super::degrade_on_forbidden(error(StatusCode::UNAUTHORIZED), "nodes").now_or_never(),
+            Some(Err(_))
+        ));
+    }
 }
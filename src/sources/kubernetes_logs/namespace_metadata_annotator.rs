@@ -0,0 +1,162 @@
+//! Annotates events with namespace metadata.
+
+#![deny(missing_docs)]
+
+use crate::{
+    event::{Event, LogEvent, PathComponent, PathIter},
+    kubernetes as k8s,
+};
+use evmap::ReadHandle;
+use k8s_openapi::{api::core::v1::Namespace, apimachinery::pkg::apis::meta::v1::ObjectMeta};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields, default)]
+pub struct FieldsSpec {
+    pub namespace_labels: String,
+}
+
+impl Default for FieldsSpec {
+    fn default() -> Self {
+        Self {
+            namespace_labels: "kubernetes.namespace_labels".to_owned(),
+        }
+    }
+}
+
+/// Annotate the event with namespace metadata.
+pub struct NamespaceMetadataAnnotator {
+    namespace_state_reader: ReadHandle<String, k8s::state::evmap::Value<Namespace>>,
+    fields_spec: FieldsSpec,
+}
+
+impl NamespaceMetadataAnnotator {
+    /// Create a new [`NamespaceMetadataAnnotator`].
+    pub fn new(
+        namespace_state_reader: ReadHandle<String, k8s::state::evmap::Value<Namespace>>,
+        fields_spec: FieldsSpec,
+    ) -> Self {
+        Self {
+            namespace_state_reader,
+            fields_spec,
+        }
+    }
+}
+
+impl NamespaceMetadataAnnotator {
+    /// Annotates an event with the information from the
+    /// [`Namespace::metadata`] of the namespace the pod belongs to.
+    /// The namespace state is keyed by the namespace name.
+    pub fn annotate(&self, event: &mut Event, pod_namespace: &str) -> Option<()> {
+        let log = event.as_mut_log();
+        let guard = self.namespace_state_reader.get(pod_namespace)?;
+        let entry = guard.get_one()?;
+        let namespace: &Namespace = entry.as_ref();
+
+        annotate_from_metadata(log, &self.fields_spec, &namespace.metadata);
+        Some(())
+    }
+}
+
+fn annotate_from_metadata(log: &mut LogEvent, fields_spec: &FieldsSpec, metadata: &ObjectMeta) {
+    // Calculate and cache the prefix path.
+    let prefix_path = PathIter::new(fields_spec.namespace_labels.as_ref()).collect::<Vec<_>>();
+    for (key, val) in metadata.labels.iter() {
+        let mut path = prefix_path.clone();
+        path.push(PathComponent::Key(key.clone()));
+        log.insert_path(path, val.to_owned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::assert_event_data_eq;
+
+    #[test]
+    fn test_annotate_from_metadata() {
+        let cases = vec![
+            (
+                FieldsSpec::default(),
+                ObjectMeta::default(),
+                LogEvent::default(),
+            ),
+            (
+                FieldsSpec::default(),
+                ObjectMeta {
+                    name: Some("sandbox0-ns".to_owned()),
+                    labels: vec![
+                        ("team".to_owned(), "observability".to_owned()),
+                        ("example.com/owner".to_owned(), "sre".to_owned()),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    ..ObjectMeta::default()
+                },
+                {
+                    let mut log = LogEvent::default();
+                    log.insert("kubernetes.namespace_labels.team", "observability");
+                    log.insert("kubernetes.namespace_labels.example\\.com/owner", "sre");
+                    log
+                },
+            ),
+            (
+                FieldsSpec {
+                    namespace_labels: "ns_labels".to_owned(),
+                },
+                ObjectMeta {
+                    name: Some("sandbox0-ns".to_owned()),
+                    labels: vec![("team".to_owned(), "observability".to_owned())]
+                        .into_iter()
+                        .collect(),
+                    ..ObjectMeta::default()
+                },
+                {
+                    let mut log = LogEvent::default();
+                    log.insert("ns_labels.team", "observability");
+                    log
+                },
+            ),
+        ];
+
+        for (fields_spec, metadata, expected) in cases.into_iter() {
+            let mut log = LogEvent::default();
+            annotate_from_metadata(&mut log, &fields_spec, &metadata);
+            assert_event_data_eq!(log, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_annotate_by_namespace_name() {
+        use k8s::state::Write;
+
+        let (state_reader, state_writer) = evmap::new();
+        let mut state_writer =
+            k8s::state::evmap::Writer::new_with_key(state_writer, None, k8s::state::evmap::name);
+        state_writer
+            .add(Namespace {
+                metadata: ObjectMeta {
+                    name: Some("sandbox0-ns".to_owned()),
+                    uid: Some("sandbox0-ns-uid".to_owned()),
+                    labels: vec![("team".to_owned(), "observability".to_owned())]
+                        .into_iter()
+                        .collect(),
+                    ..ObjectMeta::default()
+                },
+                ..Namespace::default()
+            })
+            .await;
+
+        let annotator = NamespaceMetadataAnnotator::new(state_reader, FieldsSpec::default());
+
+        let mut event = Event::from("hello");
+        assert!(annotator.annotate(&mut event, "sandbox0-ns").is_some());
+        assert_eq!(
+            event.as_log()["kubernetes.namespace_labels.team"],
+            "observability".into()
+        );
+
+        let mut event = Event::from("hello");
+        assert!(annotator.annotate(&mut event, "unknown-ns").is_none());
+    }
+}
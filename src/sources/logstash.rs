@@ -61,6 +61,7 @@ impl SourceConfig for LogstashConfig {
             self.receive_buffer_bytes,
             cx.shutdown,
             cx.out,
+            cx.acknowledgements,
         )
     }
 
@@ -99,7 +100,7 @@ impl TcpSource for LogstashSource {
         Bytes::from(bytes)
     }
 
-    fn build_event(&self, frame: LogstashEventFrame, host: Bytes) -> Option<Event> {
+    fn build_events(&self, frame: LogstashEventFrame, host: Bytes) -> Vec<Event> {
         let mut log = LogEvent::from(
             frame
                 .fields
@@ -122,7 +123,7 @@ impl TcpSource for LogstashSource {
                 .unwrap_or_else(|| Value::from(chrono::Utc::now()));
             log.insert(log_schema().timestamp_key(), timestamp);
         }
-        Some(Event::from(log))
+        vec![Event::from(log)]
     }
 }
 
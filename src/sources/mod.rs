@@ -38,6 +38,8 @@ pub mod internal_metrics;
 pub mod journald;
 #[cfg(all(feature = "sources-kafka", feature = "rdkafka"))]
 pub mod kafka;
+#[cfg(feature = "sources-kubernetes-events")]
+pub mod kubernetes_events;
 #[cfg(feature = "sources-kubernetes-logs")]
 pub mod kubernetes_logs;
 #[cfg(all(feature = "sources-logstash"))]
@@ -46,9 +48,11 @@ pub mod logstash;
 pub mod mongodb_metrics;
 #[cfg(feature = "sources-nginx_metrics")]
 pub mod nginx_metrics;
+#[cfg(feature = "sources-opentelemetry")]
+pub mod opentelemetry;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub mod postgresql_metrics;
-#[cfg(feature = "sources-prometheus")]
+#[cfg(any(feature = "sources-prometheus", feature = "sources-exec"))]
 pub mod prometheus;
 #[cfg(feature = "sources-socket")]
 pub mod socket;
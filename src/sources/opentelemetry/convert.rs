@@ -0,0 +1,510 @@
+//! Conversions from OTLP export requests into Vector events.
+
+use crate::{
+    config::log_schema,
+    event::{
+        metric::{Bucket, Metric, MetricKind, MetricTags, MetricValue, Quantile},
+        Event, LogEvent, Value,
+    },
+    proto::opentelemetry::{
+        collector::{logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest},
+        common::v1::{any_value, AnyValue, KeyValue},
+        logs::v1::LogRecord,
+        metrics::v1::{
+            metric::Data, number_data_point, AggregationTemporality, Metric as OtlpMetric,
+            NumberDataPoint,
+        },
+        resource::v1::Resource,
+    },
+};
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use std::{collections::BTreeMap, convert::TryFrom};
+
+pub(super) const SOURCE_NAME: &str = "opentelemetry";
+
+pub(super) fn decode_logs(request: ExportLogsServiceRequest) -> Vec<Event> {
+    let now = Utc::now();
+    let mut events = Vec::new();
+
+    for resource_logs in request.resource_logs {
+        let resources = resource_attributes(resource_logs.resource);
+        for library_logs in resource_logs.instrumentation_library_logs {
+            for record in library_logs.logs {
+                events.push(decode_log_record(record, &resources, now).into());
+            }
+        }
+    }
+
+    events
+}
+
+fn decode_log_record(
+    record: LogRecord,
+    resources: &BTreeMap<String, Value>,
+    now: DateTime<Utc>,
+) -> LogEvent {
+    let mut log = LogEvent::default();
+
+    if let Some(body) = record.body {
+        log.insert(log_schema().message_key(), any_value_to_value(body));
+    }
+
+    let observed_timestamp = nanos_to_timestamp(record.observed_time_unix_nano);
+    let timestamp = nanos_to_timestamp(record.time_unix_nano)
+        .or(observed_timestamp)
+        .unwrap_or(now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+    if let Some(observed_timestamp) = observed_timestamp {
+        log.insert("observed_timestamp", observed_timestamp);
+    }
+
+    if !record.attributes.is_empty() {
+        log.insert("attributes", key_values_to_map(record.attributes));
+    }
+    if !resources.is_empty() {
+        log.insert("resources", resources.clone());
+    }
+    if !record.name.is_empty() {
+        log.insert("name", record.name);
+    }
+    if !record.severity_text.is_empty() {
+        log.insert("severity_text", record.severity_text);
+    }
+    if record.severity_number != 0 {
+        log.insert("severity_number", record.severity_number);
+    }
+    if !record.trace_id.is_empty() {
+        log.insert("trace_id", to_hex(&record.trace_id));
+    }
+    if !record.span_id.is_empty() {
+        log.insert("span_id", to_hex(&record.span_id));
+    }
+    if record.flags != 0 {
+        log.insert("flags", i64::from(record.flags));
+    }
+    if record.dropped_attributes_count != 0 {
+        log.insert(
+            "dropped_attributes_count",
+            i64::from(record.dropped_attributes_count),
+        );
+    }
+    log.insert(log_schema().source_type_key(), Bytes::from(SOURCE_NAME));
+
+    log
+}
+
+pub(super) fn decode_metrics(request: ExportMetricsServiceRequest) -> Vec<Event> {
+    let mut events = Vec::new();
+
+    for resource_metrics in request.resource_metrics {
+        let resource_tags = resource_attributes(resource_metrics.resource)
+            .into_iter()
+            .map(|(key, value)| (key, value.to_string_lossy()))
+            .collect::<MetricTags>();
+        for library_metrics in resource_metrics.instrumentation_library_metrics {
+            for metric in library_metrics.metrics {
+                decode_metric(metric, &resource_tags, &mut events);
+            }
+        }
+    }
+
+    events
+}
+
+/// Each OTLP data point becomes its own metric, tagged with the attributes
+/// of both the resource and the point itself.
+fn decode_metric(metric: OtlpMetric, resource_tags: &MetricTags, events: &mut Vec<Event>) {
+    let name = metric.name;
+    let new_metric = |kind, value, attributes, time_unix_nano| {
+        point_metric(
+            &name,
+            kind,
+            value,
+            resource_tags,
+            attributes,
+            time_unix_nano,
+        )
+    };
+
+    match metric.data {
+        Some(Data::Gauge(gauge)) => {
+            for point in gauge.data_points {
+                let value = MetricValue::Gauge {
+                    value: number_value(&point),
+                };
+                events.push(new_metric(
+                    MetricKind::Absolute,
+                    value,
+                    point.attributes,
+                    point.time_unix_nano,
+                ));
+            }
+        }
+        Some(Data::Sum(sum)) => {
+            let kind = metric_kind(sum.aggregation_temporality);
+            for point in sum.data_points {
+                let value = number_value(&point);
+                // Sums which may go down can't be represented as counters.
+                let value = if sum.is_monotonic {
+                    MetricValue::Counter { value }
+                } else {
+                    MetricValue::Gauge { value }
+                };
+                events.push(new_metric(
+                    kind,
+                    value,
+                    point.attributes,
+                    point.time_unix_nano,
+                ));
+            }
+        }
+        Some(Data::Histogram(histogram)) => {
+            let kind = metric_kind(histogram.aggregation_temporality);
+            for point in histogram.data_points {
+                // The final bucket count covers the values above the last
+                // bound, which is implied by the total count.
+                let buckets = point
+                    .explicit_bounds
+                    .iter()
+                    .zip(point.bucket_counts.iter())
+                    .map(|(&upper_limit, &count)| Bucket {
+                        upper_limit,
+                        count: saturating_u32(count),
+                    })
+                    .collect();
+                let value = MetricValue::AggregatedHistogram {
+                    buckets,
+                    count: saturating_u32(point.count),
+                    sum: point.sum,
+                };
+                events.push(new_metric(
+                    kind,
+                    value,
+                    point.attributes,
+                    point.time_unix_nano,
+                ));
+            }
+        }
+        Some(Data::Summary(summary)) => {
+            for point in summary.data_points {
+                let quantiles = point
+                    .quantile_values
+                    .iter()
+                    .map(|quantile| Quantile {
+                        upper_limit: quantile.quantile,
+                        value: quantile.value,
+                    })
+                    .collect();
+                let value = MetricValue::AggregatedSummary {
+                    quantiles,
+                    count: saturating_u32(point.count),
+                    sum: point.sum,
+                };
+                events.push(new_metric(
+                    MetricKind::Absolute,
+                    value,
+                    point.attributes,
+                    point.time_unix_nano,
+                ));
+            }
+        }
+        None => {}
+    }
+}
+
+fn point_metric(
+    name: &str,
+    kind: MetricKind,
+    value: MetricValue,
+    resource_tags: &MetricTags,
+    attributes: Vec<KeyValue>,
+    time_unix_nano: u64,
+) -> Event {
+    let mut tags = resource_tags.clone();
+    tags.extend(
+        key_values_to_map(attributes)
+            .into_iter()
+            .map(|(key, value)| (key, value.to_string_lossy())),
+    );
+    Metric::new(name, kind, value)
+        .with_tags((!tags.is_empty()).then(|| tags))
+        .with_timestamp(nanos_to_timestamp(time_unix_nano))
+        .into()
+}
+
+fn metric_kind(aggregation_temporality: i32) -> MetricKind {
+    if aggregation_temporality == AggregationTemporality::Delta as i32 {
+        MetricKind::Incremental
+    } else {
+        MetricKind::Absolute
+    }
+}
+
+fn number_value(point: &NumberDataPoint) -> f64 {
+    match point.value {
+        Some(number_data_point::Value::AsDouble(value)) => value,
+        Some(number_data_point::Value::AsInt(value)) => value as f64,
+        None => 0.0,
+    }
+}
+
+fn saturating_u32(value: u64) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
+
+fn resource_attributes(resource: Option<Resource>) -> BTreeMap<String, Value> {
+    resource
+        .map(|resource| key_values_to_map(resource.attributes))
+        .unwrap_or_default()
+}
+
+fn key_values_to_map(key_values: Vec<KeyValue>) -> BTreeMap<String, Value> {
+    key_values
+        .into_iter()
+        .map(|kv| (kv.key, kv.value.map_or(Value::Null, any_value_to_value)))
+        .collect()
+}
+
+fn any_value_to_value(value: AnyValue) -> Value {
+    match value.value {
+        Some(any_value::Value::StringValue(value)) => Value::from(value),
+        Some(any_value::Value::BoolValue(value)) => Value::Boolean(value),
+        Some(any_value::Value::IntValue(value)) => Value::Integer(value),
+        Some(any_value::Value::DoubleValue(value)) => Value::Float(value),
+        Some(any_value::Value::BytesValue(value)) => Value::Bytes(value.into()),
+        Some(any_value::Value::ArrayValue(array)) => {
+            Value::Array(array.values.into_iter().map(any_value_to_value).collect())
+        }
+        Some(any_value::Value::KvlistValue(list)) => Value::Map(key_values_to_map(list.values)),
+        None => Value::Null,
+    }
+}
+
+/// OTLP uses zero to mark a timestamp as unknown.
+fn nanos_to_timestamp(nanos: u64) -> Option<DateTime<Utc>> {
+    (nanos != 0).then(|| Utc.timestamp_nanos(nanos as i64))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::opentelemetry::{
+        common::v1::KeyValueList,
+        logs::v1::{InstrumentationLibraryLogs, ResourceLogs},
+        metrics::v1::{
+            Gauge, Histogram, HistogramDataPoint, InstrumentationLibraryMetrics, ResourceMetrics,
+            Sum,
+        },
+    };
+
+    fn string_value(value: &str) -> Option<AnyValue> {
+        Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.to_owned())),
+        })
+    }
+
+    fn key_value(key: &str, value: Option<AnyValue>) -> KeyValue {
+        KeyValue {
+            key: key.to_owned(),
+            value,
+        }
+    }
+
+    fn resource() -> Option<Resource> {
+        Some(Resource {
+            attributes: vec![key_value("service.name", string_value("checkout"))],
+            dropped_attributes_count: 0,
+        })
+    }
+
+    fn metrics_request(metrics: Vec<OtlpMetric>) -> ExportMetricsServiceRequest {
+        ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: resource(),
+                instrumentation_library_metrics: vec![InstrumentationLibraryMetrics {
+                    instrumentation_library: None,
+                    metrics,
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }
+    }
+
+    fn number_point(value: number_data_point::Value, time_unix_nano: u64) -> NumberDataPoint {
+        NumberDataPoint {
+            attributes: vec![key_value("host", string_value("a"))],
+            start_time_unix_nano: 0,
+            time_unix_nano,
+            value: Some(value),
+            exemplars: vec![],
+            flags: 0,
+        }
+    }
+
+    #[test]
+    fn decodes_log_records() {
+        let request = ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: resource(),
+                instrumentation_library_logs: vec![InstrumentationLibraryLogs {
+                    instrumentation_library: None,
+                    logs: vec![LogRecord {
+                        time_unix_nano: 1_600_000_000_000_000_001,
+                        observed_time_unix_nano: 0,
+                        severity_number: 9,
+                        severity_text: "INFO".to_owned(),
+                        name: String::new(),
+                        body: string_value("order placed"),
+                        attributes: vec![key_value(
+                            "order",
+                            Some(AnyValue {
+                                value: Some(any_value::Value::KvlistValue(KeyValueList {
+                                    values: vec![key_value(
+                                        "items",
+                                        Some(AnyValue {
+                                            value: Some(any_value::Value::IntValue(3)),
+                                        }),
+                                    )],
+                                })),
+                            }),
+                        )],
+                        dropped_attributes_count: 0,
+                        flags: 0,
+                        trace_id: vec![0xab; 16],
+                        span_id: vec![0x01, 0x02],
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        };
+
+        let events = decode_logs(request);
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "order placed".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1_600_000_000, 1).into()
+        );
+        assert_eq!(log["resources.service\\.name"], "checkout".into());
+        assert_eq!(log["attributes.order.items"], 3.into());
+        assert_eq!(log["severity_text"], "INFO".into());
+        assert_eq!(log["severity_number"], 9.into());
+        assert_eq!(log["trace_id"], "ab".repeat(16).into());
+        assert_eq!(log["span_id"], "0102".into());
+        assert_eq!(log[log_schema().source_type_key()], SOURCE_NAME.into());
+        assert!(!log.contains("observed_timestamp"));
+        assert!(!log.contains("flags"));
+    }
+
+    #[test]
+    fn decodes_gauges_and_sums() {
+        let request = metrics_request(vec![
+            OtlpMetric {
+                name: "memory".to_owned(),
+                description: String::new(),
+                unit: String::new(),
+                data: Some(Data::Gauge(Gauge {
+                    data_points: vec![number_point(
+                        number_data_point::Value::AsDouble(1.5),
+                        1_600_000_000_000_000_000,
+                    )],
+                })),
+            },
+            OtlpMetric {
+                name: "requests".to_owned(),
+                description: String::new(),
+                unit: String::new(),
+                data: Some(Data::Sum(Sum {
+                    data_points: vec![number_point(number_data_point::Value::AsInt(10), 0)],
+                    aggregation_temporality: AggregationTemporality::Delta as i32,
+                    is_monotonic: true,
+                })),
+            },
+            OtlpMetric {
+                name: "queue_depth".to_owned(),
+                description: String::new(),
+                unit: String::new(),
+                data: Some(Data::Sum(Sum {
+                    data_points: vec![number_point(number_data_point::Value::AsInt(4), 0)],
+                    aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                    is_monotonic: false,
+                })),
+            },
+        ]);
+
+        let events = decode_metrics(request);
+        let metrics = events.iter().map(Event::as_metric).collect::<Vec<_>>();
+        assert_eq!(metrics.len(), 3);
+
+        let tags = metrics[0].tags().unwrap();
+        assert_eq!(tags["service.name"], "checkout");
+        assert_eq!(tags["host"], "a");
+        assert_eq!(metrics[0].kind(), MetricKind::Absolute);
+        assert_eq!(metrics[0].value(), &MetricValue::Gauge { value: 1.5 });
+        assert_eq!(
+            metrics[0].timestamp(),
+            Some(Utc.timestamp(1_600_000_000, 0))
+        );
+
+        assert_eq!(metrics[1].name(), "requests");
+        assert_eq!(metrics[1].kind(), MetricKind::Incremental);
+        assert_eq!(metrics[1].value(), &MetricValue::Counter { value: 10.0 });
+        assert_eq!(metrics[1].timestamp(), None);
+
+        assert_eq!(metrics[2].kind(), MetricKind::Absolute);
+        assert_eq!(metrics[2].value(), &MetricValue::Gauge { value: 4.0 });
+    }
+
+    #[test]
+    fn decodes_histograms() {
+        let request = metrics_request(vec![OtlpMetric {
+            name: "latency".to_owned(),
+            description: String::new(),
+            unit: String::new(),
+            data: Some(Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    attributes: vec![],
+                    start_time_unix_nano: 0,
+                    time_unix_nano: 0,
+                    count: 6,
+                    sum: 12.0,
+                    bucket_counts: vec![1, 2, 3],
+                    explicit_bounds: vec![1.0, 2.0],
+                    exemplars: vec![],
+                    flags: 0,
+                }],
+                aggregation_temporality: AggregationTemporality::Cumulative as i32,
+            })),
+        }]);
+
+        let events = decode_metrics(request);
+        assert_eq!(events.len(), 1);
+        let metric = events[0].as_metric();
+        assert_eq!(metric.kind(), MetricKind::Absolute);
+        assert_eq!(
+            metric.value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 1
+                    },
+                    Bucket {
+                        upper_limit: 2.0,
+                        count: 2
+                    },
+                ],
+                count: 6,
+                sum: 12.0,
+            }
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use super::convert;
+use crate::{
+    config::SourceContext,
+    internal_events::OpentelemetryEventsReceived,
+    proto::opentelemetry::collector::{
+        logs::v1::{
+            logs_service_server::{LogsService, LogsServiceServer},
+            ExportLogsServiceRequest, ExportLogsServiceResponse,
+        },
+        metrics::v1::{
+            metrics_service_server::{MetricsService, MetricsServiceServer},
+            ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+        },
+    },
+    shutdown::ShutdownSignalToken,
+    sources::util::{build_grpc_server, send_grpc_events, GrpcTlsConfig},
+    Pipeline,
+};
+use futures::FutureExt;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use tonic::{Request, Response, Status};
+use vector_core::event::Event;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcConfig {
+    pub address: SocketAddr,
+    #[serde(default)]
+    pub tls: Option<GrpcTlsConfig>,
+}
+
+#[derive(Debug, Clone)]
+struct Service {
+    pipeline: Pipeline,
+    acknowledgements: bool,
+}
+
+impl Service {
+    async fn send(&self, events: Vec<Event>, byte_size: usize) -> Result<(), Status> {
+        emit!(OpentelemetryEventsReceived {
+            count: events.len(),
+            byte_size,
+        });
+
+        send_grpc_events(&self.pipeline, self.acknowledgements, events).await
+    }
+}
+
+#[tonic::async_trait]
+impl LogsService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportLogsServiceRequest>,
+    ) -> Result<Response<ExportLogsServiceResponse>, Status> {
+        let request = request.into_inner();
+        let byte_size = request.encoded_len();
+        self.send(convert::decode_logs(request), byte_size).await?;
+
+        Ok(Response::new(ExportLogsServiceResponse {}))
+    }
+}
+
+#[tonic::async_trait]
+impl MetricsService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportMetricsServiceRequest>,
+    ) -> Result<Response<ExportMetricsServiceResponse>, Status> {
+        let request = request.into_inner();
+        let byte_size = request.encoded_len();
+        self.send(convert::decode_metrics(request), byte_size)
+            .await?;
+
+        Ok(Response::new(ExportMetricsServiceResponse {}))
+    }
+}
+
+pub(super) async fn run(config: GrpcConfig, cx: SourceContext) -> crate::Result<()> {
+    let _span = crate::trace::current_span();
+
+    let service = Service {
+        pipeline: cx.out,
+        acknowledgements: cx.acknowledgements,
+    };
+    let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
+
+    build_grpc_server(config.tls.as_ref())
+        .await?
+        .add_service(LogsServiceServer::new(service.clone()))
+        .add_service(MetricsServiceServer::new(service))
+        .serve_with_shutdown(
+            config.address,
+            cx.shutdown.map(|token| tx.send(token).unwrap()),
+        )
+        .await?;
+
+    drop(rx.await);
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use super::convert;
+use crate::{
+    event::Event,
+    internal_events::OpentelemetryProtoDecodeError,
+    proto::opentelemetry::collector::{
+        logs::v1::{ExportLogsServiceRequest, ExportLogsServiceResponse},
+        metrics::v1::{ExportMetricsServiceRequest, ExportMetricsServiceResponse},
+    },
+    sources::util::{ErrorMessage, HttpSource, HttpSourceAuthConfig},
+    tls::TlsConfig,
+};
+use bytes::Bytes;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, net::SocketAddr};
+use warp::{
+    http::{header::CONTENT_TYPE, HeaderMap, StatusCode},
+    reply::Response,
+    Reply,
+};
+
+pub(super) const PATH_PREFIX: &str = "v1";
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct HttpConfig {
+    pub address: SocketAddr,
+    pub tls: Option<TlsConfig>,
+    pub auth: Option<HttpSourceAuthConfig>,
+}
+
+/// Accepts OTLP/HTTP requests, which carry the same protobuf payloads as the
+/// gRPC services, on `/v1/logs` and `/v1/metrics`.
+#[derive(Clone)]
+pub(super) struct OpentelemetryHttpSource;
+
+impl HttpSource for OpentelemetryHttpSource {
+    fn build_events(
+        &self,
+        body: Bytes,
+        header_map: HeaderMap,
+        _query_parameters: HashMap<String, String>,
+        path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        // OTLP/HTTP also allows JSON encoded payloads, which aren't supported yet.
+        if header_map
+            .get("Content-Type")
+            .and_then(|content_type| content_type.to_str().ok())
+            .map_or(false, |content_type| {
+                content_type.starts_with("application/json")
+            })
+        {
+            return Err(ErrorMessage::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "Only protobuf encoded requests are supported".to_owned(),
+            ));
+        }
+
+        match path.trim_end_matches('/') {
+            "/v1/logs" => ExportLogsServiceRequest::decode(body)
+                .map(convert::decode_logs)
+                .map_err(decode_error),
+            "/v1/metrics" => ExportMetricsServiceRequest::decode(body)
+                .map(convert::decode_metrics)
+                .map_err(decode_error),
+            _ => Err(ErrorMessage::new(
+                StatusCode::NOT_FOUND,
+                "Not found".to_owned(),
+            )),
+        }
+    }
+
+    fn build_response(&self, path: &str) -> Response {
+        // Successful OTLP/HTTP requests are answered with the protobuf encoded
+        // response of the matching gRPC service.
+        let body = match path.trim_end_matches('/') {
+            "/v1/logs" => encode(ExportLogsServiceResponse {}),
+            "/v1/metrics" => encode(ExportMetricsServiceResponse {}),
+            _ => return warp::reply().into_response(),
+        };
+        warp::reply::with_header(body, CONTENT_TYPE, "application/x-protobuf").into_response()
+    }
+}
+
+fn encode(message: impl Message) -> Vec<u8> {
+    let mut body = Vec::with_capacity(message.encoded_len());
+    message.encode(&mut body).expect("Out of memory");
+    body
+}
+
+fn decode_error(error: prost::DecodeError) -> ErrorMessage {
+    emit!(OpentelemetryProtoDecodeError {
+        error: error.clone()
+    });
+    ErrorMessage::new(
+        StatusCode::BAD_REQUEST,
+        format!("Could not decode request: {}", error),
+    )
+}
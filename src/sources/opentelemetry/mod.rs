@@ -0,0 +1,327 @@
+mod convert;
+mod grpc;
+mod http;
+
+use self::{
+    grpc::GrpcConfig,
+    http::{HttpConfig, OpentelemetryHttpSource},
+};
+use crate::{
+    config::{DataType, GenerateConfig, Resource, SourceConfig, SourceContext, SourceDescription},
+    sources::{util::HttpSource, Source},
+};
+use futures::TryFutureExt;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one of `grpc` or `http` must be configured"))]
+    NoServers,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    #[serde(default)]
+    grpc: Option<GrpcConfig>,
+    #[serde(default)]
+    http: Option<HttpConfig>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<OpentelemetryConfig>(convert::SOURCE_NAME)
+}
+
+impl GenerateConfig for OpentelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            grpc: Some(GrpcConfig {
+                address: "0.0.0.0:4317".parse().unwrap(),
+                tls: None,
+            }),
+            http: Some(HttpConfig {
+                address: "0.0.0.0:4318".parse().unwrap(),
+                tls: None,
+                auth: None,
+            }),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SourceConfig for OpentelemetryConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
+        if self.grpc.is_none() && self.http.is_none() {
+            return Err(BuildError::NoServers.into());
+        }
+
+        // The servers feed the same pipeline and stop on the same shutdown
+        // signal.
+        let mut servers: Vec<Source> = Vec::new();
+        if let Some(http) = &self.http {
+            let http_cx = SourceContext {
+                name: cx.name.clone(),
+                globals: cx.globals.clone(),
+                shutdown: cx.shutdown.clone(),
+                out: cx.out.clone(),
+                acknowledgements: cx.acknowledgements,
+            };
+            servers.push(OpentelemetryHttpSource.run(
+                http.address,
+                http::PATH_PREFIX,
+                false,
+                &http.tls,
+                &http.auth,
+                http_cx,
+            )?);
+        }
+        if let Some(grpc) = &self.grpc {
+            servers.push(Box::pin(grpc::run(grpc.clone(), cx).map_err(|error| {
+                error!(message = "Source future failed.", %error);
+            })));
+        }
+
+        Ok(Box::pin(
+            futures::future::try_join_all(servers).map_ok(|_| ()),
+        ))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn source_type(&self) -> &'static str {
+        convert::SOURCE_NAME
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        let grpc = self.grpc.iter().map(|grpc| grpc.address);
+        let http = self.http.iter().map(|http| http.address);
+        grpc.chain(http).map(Resource::tcp).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::log_schema,
+        event::{
+            metric::{MetricKind, MetricValue},
+            Event, EventStatus,
+        },
+        proto::opentelemetry::{
+            collector::{
+                logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+                metrics::v1::{ExportMetricsServiceRequest, ExportMetricsServiceResponse},
+            },
+            common::v1::{any_value, AnyValue, KeyValue},
+            logs::v1::{InstrumentationLibraryLogs, LogRecord, ResourceLogs},
+            metrics::v1::{
+                metric::Data, number_data_point, Gauge, InstrumentationLibraryMetrics, Metric,
+                NumberDataPoint, ResourceMetrics,
+            },
+            resource::v1::Resource as OtlpResource,
+        },
+        test_util::{collect_n, next_addr, wait_for_tcp},
+        Pipeline,
+    };
+    use futures::Stream;
+    use prost::Message;
+    use std::net::SocketAddr;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetryConfig>();
+    }
+
+    async fn source() -> (impl Stream<Item = Event> + Unpin, SocketAddr, SocketAddr) {
+        let (sender, recv) = Pipeline::new_test_finalize(EventStatus::Delivered);
+        let grpc_address = next_addr();
+        let http_address = next_addr();
+        let config = OpentelemetryConfig {
+            grpc: Some(GrpcConfig {
+                address: grpc_address,
+                tls: None,
+            }),
+            http: Some(HttpConfig {
+                address: http_address,
+                tls: None,
+                auth: None,
+            }),
+        };
+        let source = config.build(SourceContext::new_test(sender)).await.unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(grpc_address).await;
+        wait_for_tcp(http_address).await;
+        (recv, grpc_address, http_address)
+    }
+
+    fn resource() -> Option<OtlpResource> {
+        Some(OtlpResource {
+            attributes: vec![KeyValue {
+                key: "service.name".to_owned(),
+                value: Some(AnyValue {
+                    value: Some(any_value::Value::StringValue("checkout".to_owned())),
+                }),
+            }],
+            dropped_attributes_count: 0,
+        })
+    }
+
+    #[tokio::test]
+    async fn receives_logs_over_grpc() {
+        let (rx, grpc_address, _) = source().await;
+
+        let mut client = LogsServiceClient::connect(format!("http://{}", grpc_address))
+            .await
+            .unwrap();
+        client
+            .export(ExportLogsServiceRequest {
+                resource_logs: vec![ResourceLogs {
+                    resource: resource(),
+                    instrumentation_library_logs: vec![InstrumentationLibraryLogs {
+                        instrumentation_library: None,
+                        logs: vec![LogRecord {
+                            body: Some(AnyValue {
+                                value: Some(any_value::Value::StringValue(
+                                    "order placed".to_owned(),
+                                )),
+                            }),
+                            ..Default::default()
+                        }],
+                        schema_url: String::new(),
+                    }],
+                    schema_url: String::new(),
+                }],
+            })
+            .await
+            .unwrap();
+
+        let events = collect_n(rx, 1).await;
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "order placed".into());
+        assert_eq!(log["resources.service\\.name"], "checkout".into());
+    }
+
+    #[tokio::test]
+    async fn receives_metrics_over_http() {
+        let (rx, _, http_address) = source().await;
+
+        let request = ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: resource(),
+                instrumentation_library_metrics: vec![InstrumentationLibraryMetrics {
+                    instrumentation_library: None,
+                    metrics: vec![Metric {
+                        name: "memory".to_owned(),
+                        description: String::new(),
+                        unit: String::new(),
+                        data: Some(Data::Gauge(Gauge {
+                            data_points: vec![NumberDataPoint {
+                                value: Some(number_data_point::Value::AsInt(42)),
+                                ..Default::default()
+                            }],
+                        })),
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        };
+        let mut body = Vec::with_capacity(request.encoded_len());
+        request.encode(&mut body).unwrap();
+
+        let response = reqwest::Client::new()
+            .post(&format!("http://{}/v1/metrics", http_address))
+            .header("Content-Type", "application/x-protobuf")
+            .body(body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["Content-Type"], "application/x-protobuf");
+        let body = response.bytes().await.unwrap();
+        assert_eq!(
+            ExportMetricsServiceResponse::decode(body).unwrap(),
+            ExportMetricsServiceResponse {}
+        );
+
+        let events = collect_n(rx, 1).await;
+        let metric = events[0].as_metric();
+        assert_eq!(metric.name(), "memory");
+        assert_eq!(metric.kind(), MetricKind::Absolute);
+        assert_eq!(metric.value(), &MetricValue::Gauge { value: 42.0 });
+        assert_eq!(metric.tags().unwrap()["service.name"], "checkout");
+    }
+
+    #[tokio::test]
+    async fn requires_a_server() {
+        let config: OpentelemetryConfig = toml::from_str("").unwrap();
+        let (sender, _recv) = Pipeline::new_test();
+        assert!(config.build(SourceContext::new_test(sender)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn receives_logs_over_http_only() {
+        let (sender, rx) = Pipeline::new_test_finalize(EventStatus::Delivered);
+        let http_address = next_addr();
+        let config: OpentelemetryConfig =
+            toml::from_str(&format!("http.address = \"{}\"", http_address)).unwrap();
+        assert_eq!(config.resources(), vec![Resource::tcp(http_address)]);
+        tokio::spawn(config.build(SourceContext::new_test(sender)).await.unwrap());
+        wait_for_tcp(http_address).await;
+
+        let request = ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: resource(),
+                instrumentation_library_logs: vec![InstrumentationLibraryLogs {
+                    instrumentation_library: None,
+                    logs: vec![LogRecord {
+                        body: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue("order placed".to_owned())),
+                        }),
+                        ..Default::default()
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        };
+        let mut body = Vec::with_capacity(request.encoded_len());
+        request.encode(&mut body).unwrap();
+
+        let status = reqwest::Client::new()
+            .post(&format!("http://{}/v1/logs", http_address))
+            .header("Content-Type", "application/x-protobuf")
+            .body(body)
+            .send()
+            .await
+            .unwrap()
+            .status();
+        assert_eq!(status, 200);
+
+        let events = collect_n(rx, 1).await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "order placed".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_http_paths() {
+        let (_rx, _, http_address) = source().await;
+
+        let status = reqwest::Client::new()
+            .post(&format!("http://{}/v1/traces", http_address))
+            .body(Vec::new())
+            .send()
+            .await
+            .unwrap()
+            .status();
+        assert_eq!(status, 404);
+    }
+}
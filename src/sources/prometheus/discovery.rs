@@ -0,0 +1,491 @@
+use crate::{dns, internal_events::PrometheusServiceDiscoveryError, shutdown::ShutdownSignal};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
+use tokio::sync::watch;
+
+/// Label overriding the scheme of a target found in a file.
+const SCHEME_LABEL: &str = "__scheme__";
+/// Label overriding the path of a target found in a file.
+const METRICS_PATH_LABEL: &str = "__metrics_path__";
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(super) struct FileSdConfig {
+    files: Vec<String>,
+    #[serde(default = "default_refresh_interval_secs")]
+    refresh_interval_secs: u64,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub(super) struct DnsSdConfig {
+    names: Vec<String>,
+    #[serde(default, rename = "type")]
+    record_type: DnsRecordType,
+    port: Option<u16>,
+    #[serde(default = "default_scheme")]
+    scheme: String,
+    #[serde(default = "default_metrics_path")]
+    path: String,
+    #[serde(default = "default_refresh_interval_secs")]
+    refresh_interval_secs: u64,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum DnsRecordType {
+    Srv,
+    A,
+}
+
+impl Default for DnsRecordType {
+    fn default() -> Self {
+        Self::Srv
+    }
+}
+
+const fn default_refresh_interval_secs() -> u64 {
+    30
+}
+
+fn default_scheme() -> String {
+    "http".into()
+}
+
+fn default_metrics_path() -> String {
+    "/metrics".into()
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum DiscoveryError {
+    #[snafu(display("Invalid target {:?}: {}", target, source))]
+    InvalidTarget {
+        target: String,
+        source: http::uri::InvalidUri,
+    },
+    #[snafu(display("Invalid file pattern {:?}: {}", pattern, source))]
+    InvalidPattern {
+        pattern: String,
+        source: glob::PatternError,
+    },
+    #[snafu(display("Could not read {:?}: {}", path, source))]
+    ReadFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Could not parse {:?}: {}", path, message))]
+    ParseFile { path: PathBuf, message: String },
+    #[snafu(display("Could not look up {:?}: {}", name, source))]
+    Lookup { name: String, source: dns::DnsError },
+    #[snafu(display("A `port` is required to discover targets with A records"))]
+    MissingPort,
+    #[snafu(display("`refresh_interval_secs` must be greater than zero"))]
+    ZeroRefreshInterval,
+}
+
+/// A group of targets sharing the same labels, in the format of the
+/// `file_sd` discovery of Prometheus.
+#[derive(Deserialize, Debug)]
+struct TargetGroup {
+    targets: Vec<String>,
+    #[serde(default)]
+    labels: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) struct Target {
+    pub url: http::Uri,
+    pub labels: BTreeMap<String, String>,
+}
+
+impl Target {
+    pub fn new(url: http::Uri) -> Self {
+        Self {
+            url,
+            labels: BTreeMap::new(),
+        }
+    }
+
+    fn from_address(
+        address: &str,
+        scheme: &str,
+        path: &str,
+        labels: BTreeMap<String, String>,
+    ) -> Result<Self, DiscoveryError> {
+        let url = format!("{}://{}{}", scheme, address, path);
+        let url = url.parse().context(InvalidTarget { target: url })?;
+        Ok(Self { url, labels })
+    }
+}
+
+enum Provider {
+    File(FileSdConfig),
+    /// The SRV resolver is built by the first refresh and reused afterwards.
+    Dns(DnsSdConfig, Option<dns::SrvResolver>),
+}
+
+impl Provider {
+    fn refresh_interval(&self) -> Duration {
+        Duration::from_secs(match self {
+            Self::File(config) => config.refresh_interval_secs,
+            Self::Dns(config, _) => config.refresh_interval_secs,
+        })
+    }
+
+    async fn discover(&mut self) -> Result<Vec<Target>, DiscoveryError> {
+        match self {
+            Self::File(config) => discover_files(config).await,
+            Self::Dns(config, resolver) => discover_dns(config, resolver).await,
+        }
+    }
+}
+
+/// Keeps track of the targets to scrape. The service discovery providers
+/// are refreshed in the background once their refresh interval is elapsed,
+/// so that scraping only reads the targets they found last.
+pub(super) struct Discovery {
+    static_targets: Vec<Target>,
+    providers: Vec<Provider>,
+    discovered: Vec<watch::Receiver<Vec<Target>>>,
+    current: Vec<Target>,
+}
+
+impl Discovery {
+    pub fn new(
+        static_targets: Vec<Target>,
+        file_sd: Option<FileSdConfig>,
+        dns_sd: Option<DnsSdConfig>,
+    ) -> Result<Self, DiscoveryError> {
+        if let Some(DnsSdConfig {
+            record_type: DnsRecordType::A,
+            port: None,
+            ..
+        }) = &dns_sd
+        {
+            return Err(DiscoveryError::MissingPort);
+        }
+        let refresh_intervals = file_sd
+            .iter()
+            .map(|config| config.refresh_interval_secs)
+            .chain(dns_sd.iter().map(|config| config.refresh_interval_secs));
+        for refresh_interval_secs in refresh_intervals {
+            if refresh_interval_secs == 0 {
+                return Err(DiscoveryError::ZeroRefreshInterval);
+            }
+        }
+        if let Some(config) = &file_sd {
+            for pattern in &config.files {
+                glob::Pattern::new(pattern).context(InvalidPattern { pattern })?;
+            }
+        }
+
+        let providers = file_sd
+            .map(Provider::File)
+            .into_iter()
+            .chain(dns_sd.map(|config| Provider::Dns(config, None)))
+            .collect();
+
+        Ok(Self {
+            static_targets,
+            providers,
+            discovered: Vec::new(),
+            current: Vec::new(),
+        })
+    }
+
+    /// Spawns a task refreshing each provider until Vector shuts down.
+    pub fn start(&mut self, shutdown: ShutdownSignal) {
+        for provider in self.providers.drain(..) {
+            let (tx, rx) = watch::channel(Vec::new());
+            tokio::spawn(refresh(provider, tx, shutdown.clone()));
+            self.discovered.push(rx);
+        }
+    }
+
+    /// Returns the targets to scrape now. When a provider fails, the targets
+    /// it found last are kept.
+    pub fn targets(&mut self) -> Vec<Target> {
+        let mut targets = self.static_targets.clone();
+        for discovered in &self.discovered {
+            for target in discovered.borrow().iter() {
+                if !targets.contains(target) {
+                    targets.push(target.clone());
+                }
+            }
+        }
+
+        for target in targets.iter().filter(|t| !self.current.contains(t)) {
+            info!(message = "Scrape target added.", url = %target.url, labels = ?target.labels);
+        }
+        for target in self.current.iter().filter(|t| !targets.contains(t)) {
+            info!(message = "Scrape target removed.", url = %target.url, labels = ?target.labels);
+        }
+        self.current = targets.clone();
+
+        targets
+    }
+}
+
+async fn refresh(mut provider: Provider, tx: watch::Sender<Vec<Target>>, shutdown: ShutdownSignal) {
+    loop {
+        match provider.discover().await {
+            Ok(targets) => {
+                if tx.send(targets).is_err() {
+                    // The source is gone.
+                    break;
+                }
+            }
+            Err(error) => emit!(PrometheusServiceDiscoveryError {
+                error: error.into()
+            }),
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(provider.refresh_interval()) => {}
+            _ = shutdown.clone() => break,
+        }
+    }
+}
+
+async fn discover_files(config: &FileSdConfig) -> Result<Vec<Target>, DiscoveryError> {
+    let mut targets = Vec::new();
+    for pattern in &config.files {
+        let paths = glob::glob(pattern).context(InvalidPattern { pattern })?;
+        for path in paths.filter_map(Result::ok) {
+            let content = tokio::fs::read_to_string(&path)
+                .await
+                .context(ReadFile { path: &path })?;
+            for group in parse_target_groups(&path, &content)? {
+                targets.extend(group_targets(group)?);
+            }
+        }
+    }
+    Ok(targets)
+}
+
+fn parse_target_groups(
+    path: &std::path::Path,
+    content: &str,
+) -> Result<Vec<TargetGroup>, DiscoveryError> {
+    let parsed = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => serde_json::from_str(content).map_err(|error| error.to_string()),
+        Some("yml") | Some("yaml") => {
+            serde_yaml::from_str(content).map_err(|error| error.to_string())
+        }
+        _ => Err("unknown file format, expected `.json`, `.yml` or `.yaml`".to_owned()),
+    };
+    parsed.map_err(|message| DiscoveryError::ParseFile {
+        path: path.to_owned(),
+        message,
+    })
+}
+
+fn group_targets(group: TargetGroup) -> Result<Vec<Target>, DiscoveryError> {
+    let mut labels = group.labels;
+    let scheme = labels.remove(SCHEME_LABEL).unwrap_or_else(default_scheme);
+    let path = labels
+        .remove(METRICS_PATH_LABEL)
+        .unwrap_or_else(default_metrics_path);
+    // The other reserved labels are only meaningful to Prometheus.
+    labels.retain(|name, _| !name.starts_with("__"));
+
+    group
+        .targets
+        .iter()
+        .map(|address| Target::from_address(address, &scheme, &path, labels.clone()))
+        .collect()
+}
+
+async fn discover_dns(
+    config: &DnsSdConfig,
+    resolver: &mut Option<dns::SrvResolver>,
+) -> Result<Vec<Target>, DiscoveryError> {
+    let mut targets = Vec::new();
+    for name in &config.names {
+        let addresses = match config.record_type {
+            DnsRecordType::Srv => {
+                let resolver = match resolver {
+                    Some(resolver) => resolver,
+                    None => resolver
+                        .insert(dns::SrvResolver::from_system_conf().context(Lookup { name })?),
+                };
+                resolver
+                    .lookup_srv(name)
+                    .await
+                    .context(Lookup { name })?
+                    .into_iter()
+                    .map(|record| format!("{}:{}", record.target, record.port))
+                    .collect::<Vec<_>>()
+            }
+            DnsRecordType::A => {
+                let port = config.port.ok_or(DiscoveryError::MissingPort)?;
+                dns::Resolver
+                    .lookup_ip(name.clone())
+                    .await
+                    .context(Lookup { name })?
+                    .map(|ip| std::net::SocketAddr::new(ip, port).to_string())
+                    .collect()
+            }
+        };
+        for address in addresses {
+            targets.push(Target::from_address(
+                &address,
+                &config.scheme,
+                &config.path,
+                BTreeMap::new(),
+            )?);
+        }
+    }
+    Ok(targets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::btreemap;
+    use std::time::Instant;
+
+    fn file_sd(dir: &tempfile::TempDir) -> Discovery {
+        let config = FileSdConfig {
+            files: vec![format!("{}/*.json", dir.path().display())],
+            refresh_interval_secs: 1,
+        };
+        let mut discovery = Discovery::new(vec![], Some(config), None).unwrap();
+        discovery.start(ShutdownSignal::noop());
+        discovery
+    }
+
+    async fn wait_for_targets(discovery: &mut Discovery, expected: Vec<Target>) {
+        let started = Instant::now();
+        while discovery.targets() != expected {
+            assert!(
+                started.elapsed() < Duration::from_secs(5),
+                "Timed out while waiting for targets"
+            );
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[test]
+    fn parses_target_groups() {
+        let content = r#"
+        - targets: ["node-1:9100", "node-2:9100"]
+          labels:
+            env: prod
+            __scheme__: https
+            __metrics_path__: /probe
+            __meta_custom: ignored
+        - targets: ["node-3:9100"]
+        "#;
+        let groups = parse_target_groups("targets.yml".as_ref(), content).unwrap();
+        let targets = groups
+            .into_iter()
+            .map(group_targets)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+            .concat();
+
+        assert_eq!(
+            targets,
+            vec![
+                Target {
+                    url: "https://node-1:9100/probe".parse().unwrap(),
+                    labels: btreemap! { "env" => "prod" },
+                },
+                Target {
+                    url: "https://node-2:9100/probe".parse().unwrap(),
+                    labels: btreemap! { "env" => "prod" },
+                },
+                Target::new("http://node-3:9100/metrics".parse().unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_file_format() {
+        assert!(parse_target_groups("targets.txt".as_ref(), "[]").is_err());
+    }
+
+    #[test]
+    fn requires_port_for_a_records() {
+        let config = DnsSdConfig {
+            names: vec!["example.com".into()],
+            record_type: DnsRecordType::A,
+            port: None,
+            scheme: default_scheme(),
+            path: default_metrics_path(),
+            refresh_interval_secs: 30,
+        };
+        assert!(matches!(
+            Discovery::new(vec![], None, Some(config)),
+            Err(DiscoveryError::MissingPort)
+        ));
+    }
+
+    #[test]
+    fn rejects_zero_refresh_interval() {
+        let config = FileSdConfig {
+            files: vec!["targets/*.json".into()],
+            refresh_interval_secs: 0,
+        };
+        assert!(matches!(
+            Discovery::new(vec![], Some(config), None),
+            Err(DiscoveryError::ZeroRefreshInterval)
+        ));
+    }
+
+    #[tokio::test]
+    async fn follows_target_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("targets.json");
+        let mut discovery = file_sd(&dir);
+        assert_eq!(discovery.targets(), vec![]);
+
+        std::fs::write(
+            &path,
+            r#"[{"targets": ["node-1:9100"], "labels": {"env": "prod"}}]"#,
+        )
+        .unwrap();
+        wait_for_targets(
+            &mut discovery,
+            vec![Target {
+                url: "http://node-1:9100/metrics".parse().unwrap(),
+                labels: btreemap! { "env" => "prod" },
+            }],
+        )
+        .await;
+
+        std::fs::write(&path, r#"[{"targets": ["node-2:9100"]}]"#).unwrap();
+        wait_for_targets(
+            &mut discovery,
+            vec![Target::new("http://node-2:9100/metrics".parse().unwrap())],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn keeps_targets_on_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("targets.json");
+        let mut discovery = file_sd(&dir);
+
+        std::fs::write(&path, r#"[{"targets": ["node-1:9100"]}]"#).unwrap();
+        let targets = vec![Target::new("http://node-1:9100/metrics".parse().unwrap())];
+        wait_for_targets(&mut discovery, targets.clone()).await;
+
+        std::fs::write(&path, "not json").unwrap();
+        // Wait for at least one refresh of the invalid file.
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert_eq!(discovery.targets(), targets);
+    }
+
+    #[tokio::test]
+    async fn includes_static_targets() {
+        let static_target = Target::new("http://localhost:9090/metrics".parse().unwrap());
+        let mut discovery = Discovery::new(vec![static_target.clone()], None, None).unwrap();
+        discovery.start(ShutdownSignal::noop());
+
+        assert_eq!(discovery.targets(), vec![static_target]);
+    }
+}
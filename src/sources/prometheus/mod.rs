@@ -1,3 +1,7 @@
+#[cfg(feature = "sources-prometheus")]
+mod discovery;
 pub(crate) mod parser;
+#[cfg(feature = "sources-prometheus")]
 mod remote_write;
+#[cfg(feature = "sources-prometheus")]
 mod scrape;
@@ -6,7 +6,7 @@ use chrono::{DateTime, TimeZone, Utc};
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 
-use prometheus_parser::{proto, GroupKind, MetricGroup, ParserError};
+use prometheus_parser::{GroupKind, MetricGroup, ParserError};
 
 fn has_values_or_none(tags: BTreeMap<String, String>) -> Option<BTreeMap<String, String>> {
     if tags.is_empty() {
@@ -25,11 +25,14 @@ fn utc_timestamp(timestamp: Option<i64>, default: DateTime<Utc>) -> DateTime<Utc
         .unwrap_or(default)
 }
 
-pub(super) fn parse_text(packet: &str) -> Result<Vec<Event>, ParserError> {
+pub(crate) fn parse_text(packet: &str) -> Result<Vec<Event>, ParserError> {
     prometheus_parser::parse_text(packet).map(reparse_groups)
 }
 
-pub(super) fn parse_request(request: proto::WriteRequest) -> Result<Vec<Event>, ParserError> {
+#[cfg(feature = "sources-prometheus")]
+pub(super) fn parse_request(
+    request: prometheus_parser::proto::WriteRequest,
+) -> Result<Vec<Event>, ParserError> {
     prometheus_parser::parse_request(request).map(reparse_groups)
 }
 
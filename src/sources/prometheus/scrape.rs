@@ -1,6 +1,10 @@
-use super::parser;
+use super::{
+    discovery::{Discovery, DnsSdConfig, FileSdConfig, Target},
+    parser,
+};
 use crate::{
     config::{self, GenerateConfig, SourceConfig, SourceContext, SourceDescription},
+    event::{Event, Metric, MetricKind, MetricValue},
     http::Auth,
     http::HttpClient,
     internal_events::{
@@ -12,12 +16,15 @@ use crate::{
     tls::{TlsOptions, TlsSettings},
     Pipeline,
 };
+use chrono::Utc;
 use futures::{stream, FutureExt, SinkExt, StreamExt, TryFutureExt};
 use hyper::{Body, Request};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use std::{
-    future::ready,
+    collections::BTreeMap,
+    sync::Arc,
     time::{Duration, Instant},
 };
 use tokio_stream::wrappers::IntervalStream;
@@ -26,16 +33,27 @@ use tokio_stream::wrappers::IntervalStream;
 enum ConfigError {
     #[snafu(display("Cannot set both `endpoints` and `hosts`"))]
     BothEndpointsAndHosts,
+    #[snafu(display("At least one of `endpoints`, `file_sd` or `dns_sd` must be set"))]
+    NoTargets,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 struct PrometheusScrapeConfig {
     // Deprecated name
-    #[serde(alias = "hosts")]
+    #[serde(alias = "hosts", default)]
     endpoints: Vec<String>,
     #[serde(default = "default_scrape_interval_secs")]
     scrape_interval_secs: u64,
 
+    file_sd: Option<FileSdConfig>,
+    dns_sd: Option<DnsSdConfig>,
+
+    job: Option<String>,
+    #[serde(default)]
+    honor_labels: bool,
+    #[serde(default)]
+    query: IndexMap<String, Vec<String>>,
+
     tls: Option<TlsOptions>,
 
     auth: Option<Auth>,
@@ -58,6 +76,11 @@ impl GenerateConfig for PrometheusScrapeConfig {
         toml::Value::try_from(Self {
             endpoints: vec!["http://localhost:9090/metrics".to_string()],
             scrape_interval_secs: default_scrape_interval_secs(),
+            file_sd: None,
+            dns_sd: None,
+            job: None,
+            honor_labels: false,
+            query: IndexMap::new(),
             tls: None,
             auth: None,
         })
@@ -74,11 +97,24 @@ impl SourceConfig for PrometheusScrapeConfig {
             .iter()
             .map(|s| s.parse::<http::Uri>().context(sources::UriParseError))
             .collect::<Result<Vec<http::Uri>, sources::BuildError>>()?;
-        let tls = TlsSettings::from_options(&self.tls)?;
+        if urls.is_empty() && self.file_sd.is_none() && self.dns_sd.is_none() {
+            return Err(ConfigError::NoTargets.into());
+        }
+        let discovery = Discovery::new(
+            urls.into_iter().map(Target::new).collect(),
+            self.file_sd.clone(),
+            self.dns_sd.clone(),
+        )?;
+        let settings = ScrapeSettings {
+            client: HttpClient::new(TlsSettings::from_options(&self.tls)?)?,
+            auth: self.auth.clone(),
+            job: self.job.clone().unwrap_or(cx.name),
+            honor_labels: self.honor_labels,
+            query: self.query.clone(),
+        };
         Ok(prometheus(
-            urls,
-            tls,
-            self.auth.clone(),
+            discovery,
+            settings,
             self.scrape_interval_secs,
             cx.shutdown,
             cx.out,
@@ -100,11 +136,20 @@ impl SourceConfig for PrometheusScrapeConfig {
 struct PrometheusCompatConfig {
     // Clone of PrometheusScrapeConfig to work around serde bug
     // https://github.com/serde-rs/serde/issues/1504
-    #[serde(alias = "hosts")]
+    #[serde(alias = "hosts", default)]
     endpoints: Vec<String>,
     #[serde(default = "default_scrape_interval_secs")]
     scrape_interval_secs: u64,
 
+    file_sd: Option<FileSdConfig>,
+    dns_sd: Option<DnsSdConfig>,
+
+    job: Option<String>,
+    #[serde(default)]
+    honor_labels: bool,
+    #[serde(default)]
+    query: IndexMap<String, Vec<String>>,
+
     tls: Option<TlsOptions>,
 
     auth: Option<Auth>,
@@ -119,6 +164,11 @@ impl SourceConfig for PrometheusCompatConfig {
         let config = PrometheusScrapeConfig {
             endpoints: self.endpoints.clone(),
             scrape_interval_secs: self.scrape_interval_secs,
+            file_sd: self.file_sd.clone(),
+            dns_sd: self.dns_sd.clone(),
+            job: self.job.clone(),
+            honor_labels: self.honor_labels,
+            query: self.query.clone(),
             tls: self.tls.clone(),
             auth: self.auth.clone(),
         };
@@ -134,105 +184,195 @@ impl SourceConfig for PrometheusCompatConfig {
     }
 }
 
-fn prometheus(
-    urls: Vec<http::Uri>,
-    tls: TlsSettings,
+struct ScrapeSettings {
+    client: HttpClient,
     auth: Option<Auth>,
+    job: String,
+    honor_labels: bool,
+    query: IndexMap<String, Vec<String>>,
+}
+
+impl ScrapeSettings {
+    /// Adds the configured query parameters to the URL of the target.
+    fn url(&self, url: &http::Uri) -> http::Uri {
+        if self.query.is_empty() {
+            return url.clone();
+        }
+
+        let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+        if let Some(query) = url.query() {
+            serializer.extend_pairs(url::form_urlencoded::parse(query.as_bytes()));
+        }
+        for (name, values) in &self.query {
+            for value in values {
+                serializer.append_pair(name, value);
+            }
+        }
+
+        let mut parts = url.clone().into_parts();
+        let path = url.path();
+        parts.path_and_query = Some(
+            format!("{}?{}", path, serializer.finish())
+                .parse()
+                .expect("Query parameters are always valid"),
+        );
+        http::Uri::from_parts(parts).expect("Only the query was changed")
+    }
+
+    /// The labels attached to every metric scraped from the target. Those
+    /// found by the service discovery override the default ones.
+    fn target_labels(&self, target: &Target) -> BTreeMap<String, String> {
+        let mut labels = BTreeMap::new();
+        labels.insert("job".to_owned(), self.job.clone());
+        if let Some(authority) = target.url.authority() {
+            labels.insert("instance".to_owned(), authority.to_string());
+        }
+        labels.extend(target.labels.clone());
+        labels
+    }
+}
+
+fn prometheus(
+    mut discovery: Discovery,
+    settings: ScrapeSettings,
     interval: u64,
     shutdown: ShutdownSignal,
     out: Pipeline,
 ) -> sources::Source {
     let out = out.sink_map_err(|error| error!(message = "Error sending metric.", %error));
+    let settings = Arc::new(settings);
+
+    Box::pin(async move {
+        discovery.start(shutdown.clone());
+
+        IntervalStream::new(tokio::time::interval(Duration::from_secs(interval)))
+            .take_until(shutdown)
+            .map(move |_| discovery.targets())
+            .map(stream::iter)
+            .flatten()
+            .then(move |target| scrape(Arc::clone(&settings), target))
+            .map(|events| stream::iter(events).map(Ok))
+            .flatten()
+            .forward(out)
+            .inspect(|_| info!("Finished sending."))
+            .await
+    })
+}
 
-    Box::pin(IntervalStream::new(tokio::time::interval(Duration::from_secs(interval)))
-        .take_until(shutdown)
-        .map(move |_| stream::iter(urls.clone()))
-        .flatten()
-        .map(move |url| {
-            let client = HttpClient::new(tls.clone()).expect("Building HTTP client failed");
-
-            let mut request = Request::get(&url)
-                .body(Body::empty())
-                .expect("error creating request");
-            if let Some(auth) = &auth {
-                auth.apply(&mut request);
-            }
+/// Scrapes the target, returning its metrics followed by the `up` and
+/// `scrape_duration_seconds` metrics describing the health of the scrape.
+async fn scrape(settings: Arc<ScrapeSettings>, target: Target) -> Vec<Event> {
+    let url = settings.url(&target.url);
+    let labels = settings.target_labels(&target);
+
+    let mut request = Request::get(&url)
+        .body(Body::empty())
+        .expect("error creating request");
+    if let Some(auth) = &settings.auth {
+        auth.apply(&mut request);
+    }
 
-            let start = Instant::now();
-            client
-                .send(request)
-                .map_err(crate::Error::from)
-                .and_then(|response| async move {
-                    let (header, body) = response.into_parts();
-                    let body = hyper::body::to_bytes(body).await?;
-                    Ok((header, body))
-                })
-                .into_stream()
-                .filter_map(move |response| {
-                    ready(match response {
-                        Ok((header, body)) if header.status == hyper::StatusCode::OK => {
-                            emit!(PrometheusRequestCompleted {
-                                start,
-                                end: Instant::now()
-                            });
-
-                            let byte_size = body.len();
-                            let body = String::from_utf8_lossy(&body);
-
-                            match parser::parse_text(&body) {
-                                Ok(metrics) => {
-                                    emit!(PrometheusEventReceived {
-                                        byte_size,
-                                        count: metrics.len(),
-                                        uri: url.clone()
-                                    });
-                                    Some(stream::iter(metrics).map(Ok))
-                                }
-                                Err(error) => {
-                                    if url.path() == "/" {
-                                        // https://github.com/timberio/vector/pull/3801#issuecomment-700723178
-                                        warn!(
-                                            message = "No path is set on the endpoint and we got a parse error, did you mean to use /metrics? This behavior changed in version 0.11.",
-                                            endpoint = %url
-                                        );
-                            }
-                                    emit!(PrometheusParseError {
-                                        error,
-                                        url: url.clone(),
-                                        body,
-                                    });
-                                    None
-                                }
-                            }
-                        }
-                        Ok((header, _)) => {
-                            if header.status == hyper::StatusCode::NOT_FOUND && url.path() == "/" {
-                                // https://github.com/timberio/vector/pull/3801#issuecomment-700723178
-                                warn!(
-                                    message = "No path is set on the endpoint and we got a 404, did you mean to use /metrics? This behavior changed in version 0.11.",
-                                    endpoint = %url
-                                );
-                            }
-                            emit!(PrometheusErrorResponse {
-                                code: header.status,
-                                url: url.clone(),
-                            });
-                            None
-                        }
-                        Err(error) => {
-                            emit!(PrometheusHttpError {
-                                error,
-                                url: url.clone(),
-                            });
-                            None
-                        }
-                    })
-                })
-                .flatten()
+    let start = Instant::now();
+    let response = settings
+        .client
+        .send(request)
+        .map_err(crate::Error::from)
+        .and_then(|response| async move {
+            let (header, body) = response.into_parts();
+            let body = hyper::body::to_bytes(body).await?;
+            Ok((header, body))
         })
-        .flatten()
-        .forward(out)
-        .inspect(|_| info!("Finished sending.")))
+        .await;
+    let end = Instant::now();
+
+    let metrics = match response {
+        Ok((header, body)) if header.status == hyper::StatusCode::OK => {
+            emit!(PrometheusRequestCompleted { start, end });
+
+            let byte_size = body.len();
+            let body = String::from_utf8_lossy(&body);
+
+            match parser::parse_text(&body) {
+                Ok(metrics) => {
+                    emit!(PrometheusEventReceived {
+                        byte_size,
+                        count: metrics.len(),
+                        uri: url.clone()
+                    });
+                    Some(metrics)
+                }
+                Err(error) => {
+                    if url.path() == "/" {
+                        // https://github.com/timberio/vector/pull/3801#issuecomment-700723178
+                        warn!(
+                            message = "No path is set on the endpoint and we got a parse error, did you mean to use /metrics? This behavior changed in version 0.11.",
+                            endpoint = %url
+                        );
+                    }
+                    emit!(PrometheusParseError {
+                        error,
+                        url: url.clone(),
+                        body,
+                    });
+                    None
+                }
+            }
+        }
+        Ok((header, _)) => {
+            if header.status == hyper::StatusCode::NOT_FOUND && url.path() == "/" {
+                // https://github.com/timberio/vector/pull/3801#issuecomment-700723178
+                warn!(
+                    message = "No path is set on the endpoint and we got a 404, did you mean to use /metrics? This behavior changed in version 0.11.",
+                    endpoint = %url
+                );
+            }
+            emit!(PrometheusErrorResponse {
+                code: header.status,
+                url: url.clone(),
+            });
+            None
+        }
+        Err(error) => {
+            emit!(PrometheusHttpError {
+                error,
+                url: url.clone(),
+            });
+            None
+        }
+    };
+
+    let up = metrics.is_some();
+    let mut events = metrics.unwrap_or_default();
+    for event in &mut events {
+        apply_labels(event.as_mut_metric(), &labels, settings.honor_labels);
+    }
+
+    let timestamp = Utc::now();
+    let health = |name: &str, value: f64| {
+        Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
+            .with_tags(Some(labels.clone()))
+            .with_timestamp(Some(timestamp))
+    };
+    events.push(health("up", if up { 1.0 } else { 0.0 }).into());
+    events.push(health("scrape_duration_seconds", (end - start).as_secs_f64()).into());
+    events
+}
+
+/// Attaches the labels of the target to a scraped metric. Like Prometheus,
+/// conflicting labels of the metric are kept when `honor_labels` is set, and
+/// renamed to `exported_<name>` otherwise.
+fn apply_labels(metric: &mut Metric, labels: &BTreeMap<String, String>, honor_labels: bool) {
+    for (name, value) in labels {
+        let existing = metric.tags().and_then(|tags| tags.get(name)).cloned();
+        match existing {
+            Some(_) if honor_labels => continue,
+            Some(existing) => {
+                metric.insert_tag(format!("exported_{}", name), existing);
+            }
+            None => {}
+        }
+        metric.insert_tag(name.clone(), value.clone());
+    }
 }
 
 #[cfg(all(test, feature = "sinks-prometheus"))]
@@ -308,6 +448,11 @@ mod test {
             PrometheusScrapeConfig {
                 endpoints: vec![format!("http://{}", in_addr)],
                 scrape_interval_secs: 1,
+                file_sd: None,
+                dns_sd: None,
+                job: None,
+                honor_labels: false,
+                query: IndexMap::new(),
                 tls: None,
                 auth: None,
             },
@@ -335,43 +480,236 @@ mod test {
         assert!(response.status().is_success());
 
         let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
-        let lines = std::str::from_utf8(&body)
+        let (health, lines): (Vec<_>, Vec<_>) = std::str::from_utf8(&body)
             .unwrap()
             .lines()
-            .collect::<Vec<_>>();
+            .map(String::from)
+            .partition(|line| {
+                line.contains("vector_up") || line.contains("vector_scrape_duration")
+            });
 
+        // Every scraped metric is tagged with the instance and the job.
+        let tags = format!("instance=\"{}\",job=\"in\"", in_addr);
         assert_eq!(lines, vec![
-            "# HELP vector_http_request_duration_seconds http_request_duration_seconds",
-            "# TYPE vector_http_request_duration_seconds histogram",
-            "vector_http_request_duration_seconds_bucket{le=\"0.05\"} 24054 1612411516789",
-            "vector_http_request_duration_seconds_bucket{le=\"0.1\"} 33444 1612411516789",
-            "vector_http_request_duration_seconds_bucket{le=\"0.2\"} 100392 1612411516789",
-            "vector_http_request_duration_seconds_bucket{le=\"0.5\"} 129389 1612411516789",
-            "vector_http_request_duration_seconds_bucket{le=\"1\"} 133988 1612411516789",
-            "vector_http_request_duration_seconds_bucket{le=\"+Inf\"} 144320 1612411516789",
-            "vector_http_request_duration_seconds_sum 53423 1612411516789",
-            "vector_http_request_duration_seconds_count 144320 1612411516789",
-            "# HELP vector_prometheus_remote_storage_samples_in_total prometheus_remote_storage_samples_in_total",
-            "# TYPE vector_prometheus_remote_storage_samples_in_total gauge",
-            "vector_prometheus_remote_storage_samples_in_total 57011636 1612411516789",
-            "# HELP vector_promhttp_metric_handler_requests_total promhttp_metric_handler_requests_total",
-            "# TYPE vector_promhttp_metric_handler_requests_total counter",
-            "vector_promhttp_metric_handler_requests_total{code=\"200\"} 100 1612411516789",
-            "vector_promhttp_metric_handler_requests_total{code=\"404\"} 7 1612411516789",
-            "# HELP vector_rpc_duration_seconds rpc_duration_seconds",
-            "# TYPE vector_rpc_duration_seconds summary",
-            "vector_rpc_duration_seconds{code=\"200\",quantile=\"0.01\"} 3102 1612411516789",
-            "vector_rpc_duration_seconds{code=\"200\",quantile=\"0.05\"} 3272 1612411516789",
-            "vector_rpc_duration_seconds{code=\"200\",quantile=\"0.5\"} 4773 1612411516789",
-            "vector_rpc_duration_seconds{code=\"200\",quantile=\"0.9\"} 9001 1612411516789",
-            "vector_rpc_duration_seconds{code=\"200\",quantile=\"0.99\"} 76656 1612411516789",
-            "vector_rpc_duration_seconds_sum{code=\"200\"} 17560473 1612411516789",
-            "vector_rpc_duration_seconds_count{code=\"200\"} 2693 1612411516789",
+            "# HELP vector_http_request_duration_seconds http_request_duration_seconds".to_owned(),
+            "# TYPE vector_http_request_duration_seconds histogram".to_owned(),
+            format!("vector_http_request_duration_seconds_bucket{{{},le=\"0.05\"}} 24054 1612411516789", tags),
+            format!("vector_http_request_duration_seconds_bucket{{{},le=\"0.1\"}} 33444 1612411516789", tags),
+            format!("vector_http_request_duration_seconds_bucket{{{},le=\"0.2\"}} 100392 1612411516789", tags),
+            format!("vector_http_request_duration_seconds_bucket{{{},le=\"0.5\"}} 129389 1612411516789", tags),
+            format!("vector_http_request_duration_seconds_bucket{{{},le=\"1\"}} 133988 1612411516789", tags),
+            format!("vector_http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} 144320 1612411516789", tags),
+            format!("vector_http_request_duration_seconds_sum{{{}}} 53423 1612411516789", tags),
+            format!("vector_http_request_duration_seconds_count{{{}}} 144320 1612411516789", tags),
+            "# HELP vector_prometheus_remote_storage_samples_in_total prometheus_remote_storage_samples_in_total".to_owned(),
+            "# TYPE vector_prometheus_remote_storage_samples_in_total gauge".to_owned(),
+            format!("vector_prometheus_remote_storage_samples_in_total{{{}}} 57011636 1612411516789", tags),
+            "# HELP vector_promhttp_metric_handler_requests_total promhttp_metric_handler_requests_total".to_owned(),
+            "# TYPE vector_promhttp_metric_handler_requests_total counter".to_owned(),
+            format!("vector_promhttp_metric_handler_requests_total{{code=\"200\",{}}} 100 1612411516789", tags),
+            format!("vector_promhttp_metric_handler_requests_total{{code=\"404\",{}}} 7 1612411516789", tags),
+            "# HELP vector_rpc_duration_seconds rpc_duration_seconds".to_owned(),
+            "# TYPE vector_rpc_duration_seconds summary".to_owned(),
+            format!("vector_rpc_duration_seconds{{code=\"200\",{},quantile=\"0.01\"}} 3102 1612411516789", tags),
+            format!("vector_rpc_duration_seconds{{code=\"200\",{},quantile=\"0.05\"}} 3272 1612411516789", tags),
+            format!("vector_rpc_duration_seconds{{code=\"200\",{},quantile=\"0.5\"}} 4773 1612411516789", tags),
+            format!("vector_rpc_duration_seconds{{code=\"200\",{},quantile=\"0.9\"}} 9001 1612411516789", tags),
+            format!("vector_rpc_duration_seconds{{code=\"200\",{},quantile=\"0.99\"}} 76656 1612411516789", tags),
+            format!("vector_rpc_duration_seconds_sum{{code=\"200\",{}}} 17560473 1612411516789", tags),
+            format!("vector_rpc_duration_seconds_count{{code=\"200\",{}}} 2693 1612411516789", tags),
             ],
         );
 
+        assert_eq!(health.len(), 6);
+        assert!(health
+            .iter()
+            .any(|line| line.starts_with(&format!("vector_up{{{}}} 1 ", tags))));
+
         topology.stop().await;
     }
+
+    #[tokio::test]
+    async fn test_prometheus_file_sd() {
+        let in_addr = next_addr();
+
+        let make_svc = make_service_fn(|_| async {
+            Ok::<_, Error>(service_fn(|request: hyper::Request<Body>| async move {
+                assert_eq!(request.uri().path(), "/custom");
+                Ok::<_, Error>(Response::new(Body::from(
+                    "# TYPE probe_success gauge\nprobe_success{env=\"scraped\"} 1\n",
+                )))
+            }))
+        });
+
+        tokio::spawn(async move {
+            if let Err(error) = Server::bind(&in_addr).serve(make_svc).await {
+                error!(message = "Server error.", %error);
+            }
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("targets.json"),
+            serde_json::json!([{
+                "targets": [in_addr.to_string()],
+                "labels": {"env": "prod", "team": "infra", "__metrics_path__": "/custom"},
+            }])
+            .to_string(),
+        )
+        .unwrap();
+
+        let config: PrometheusScrapeConfig = toml::from_str(&format!(
+            r#"
+            scrape_interval_secs = 1
+            file_sd.files = ["{}/*.json"]
+            "#,
+            dir.path().display()
+        ))
+        .unwrap();
+
+        let (tx, rx) = Pipeline::new_test();
+        let source = config
+            .build(config::SourceContext::new_test(tx))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        sleep(Duration::from_secs(1)).await;
+
+        let events = crate::test_util::collect_ready(rx).await;
+        assert!(!events.is_empty());
+        for event in events {
+            let metric = event.into_metric();
+            let tags = metric.tags().unwrap();
+            assert_eq!(tags.get("env").map(String::as_str), Some("prod"));
+            assert_eq!(tags.get("team").map(String::as_str), Some("infra"));
+            assert!(!tags.contains_key("__metrics_path__"));
+            if metric.name() == "probe_success" {
+                assert_eq!(
+                    tags.get("exported_env").map(String::as_str),
+                    Some("scraped")
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_unreachable_targets() {
+        let in_addr = next_addr();
+        let config: PrometheusScrapeConfig = toml::from_str(&format!(
+            r#"
+            endpoints = ["http://{}/metrics"]
+            scrape_interval_secs = 10
+            job = "node"
+            "#,
+            in_addr
+        ))
+        .unwrap();
+
+        let (tx, rx) = Pipeline::new_test();
+        let source = config
+            .build(config::SourceContext::new_test(tx))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        sleep(Duration::from_secs(1)).await;
+
+        let metrics = crate::test_util::collect_ready(rx)
+            .await
+            .into_iter()
+            .map(Event::into_metric)
+            .collect::<Vec<_>>();
+        let names = metrics.iter().map(Metric::name).collect::<Vec<_>>();
+        assert_eq!(names, vec!["up", "scrape_duration_seconds"]);
+        assert_eq!(metrics[0].value(), &MetricValue::Gauge { value: 0.0 });
+        let tags = metrics[0].tags().unwrap();
+        assert_eq!(tags["job"], "node");
+        assert_eq!(tags["instance"], in_addr.to_string());
+    }
+
+    fn settings(honor_labels: bool, query: IndexMap<String, Vec<String>>) -> ScrapeSettings {
+        ScrapeSettings {
+            client: HttpClient::new(TlsSettings::from_options(&None).unwrap()).unwrap(),
+            auth: None,
+            job: "node".into(),
+            honor_labels,
+            query,
+        }
+    }
+
+    #[test]
+    fn labels_conflicts() {
+        let target = Target {
+            url: "http://localhost:9100/metrics".parse().unwrap(),
+            labels: shared::btreemap! { "env" => "prod" },
+        };
+        let scraped = Metric::new(
+            "requests",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_tags(Some(
+            shared::btreemap! { "env" => "staging", "job" => "api" },
+        ));
+
+        let settings = settings(false, IndexMap::new());
+        let mut metric = scraped.clone();
+        apply_labels(&mut metric, &settings.target_labels(&target), false);
+        assert_eq!(
+            metric.tags().unwrap(),
+            &shared::btreemap! {
+                "env" => "prod",
+                "exported_env" => "staging",
+                "exported_job" => "api",
+                "instance" => "localhost:9100",
+                "job" => "node",
+            }
+        );
+
+        let mut metric = scraped;
+        apply_labels(&mut metric, &settings.target_labels(&target), true);
+        assert_eq!(
+            metric.tags().unwrap(),
+            &shared::btreemap! {
+                "env" => "staging",
+                "instance" => "localhost:9100",
+                "job" => "api",
+            }
+        );
+    }
+
+    #[test]
+    fn adds_query_parameters() {
+        let mut query = IndexMap::new();
+        query.insert("module".to_owned(), vec!["http_2xx".to_owned()]);
+        query.insert(
+            "target".to_owned(),
+            vec!["a.example.com".to_owned(), "b example".to_owned()],
+        );
+        let settings = settings(false, query);
+
+        assert_eq!(
+            settings.url(&"http://localhost:9115/probe?debug=true".parse().unwrap()),
+            "http://localhost:9115/probe?debug=true&module=http_2xx&target=a.example.com&target=b+example"
+                .parse::<http::Uri>()
+                .unwrap()
+        );
+        assert_eq!(
+            settings.url(&"http://localhost:9115".parse().unwrap()),
+            "http://localhost:9115/?module=http_2xx&target=a.example.com&target=b+example"
+                .parse::<http::Uri>()
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn requires_targets() {
+        let config: PrometheusScrapeConfig = toml::from_str("").unwrap();
+        let (tx, _rx) = Pipeline::new_test();
+        assert!(config
+            .build(config::SourceContext::new_test(tx))
+            .await
+            .is_err());
+    }
 }
 
 #[cfg(all(test, feature = "prometheus-integration-tests"))]
@@ -389,6 +727,11 @@ mod integration_tests {
         let config = PrometheusScrapeConfig {
             endpoints: vec!["http://localhost:9090/metrics".into()],
             scrape_interval_secs: 1,
+            file_sd: None,
+            dns_sd: None,
+            job: None,
+            honor_labels: false,
+            query: IndexMap::new(),
             auth: None,
             tls: None,
         };
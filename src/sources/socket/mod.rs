@@ -5,6 +5,7 @@ mod unix;
 
 use super::util::TcpSource;
 use crate::{
+    codecs::{BytesParserConfig, DecodingConfig, NewlineDelimitedDecoderConfig, ParserConfig},
     config::{
         log_schema, DataType, GenerateConfig, Resource, SourceConfig, SourceContext,
         SourceDescription,
@@ -80,9 +81,16 @@ impl SourceConfig for SocketConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         match self.mode.clone() {
             Mode::Tcp(config) => {
-                let tcp = tcp::RawTcpSource {
-                    config: config.clone(),
-                };
+                let decoding = config.decoding().clone();
+                let framing = config.framing().clone().unwrap_or_else(|| {
+                    Box::new(NewlineDelimitedDecoderConfig::new_with_max_length(
+                        config.max_length(),
+                    ))
+                });
+                let decoder =
+                    DecodingConfig::new(framing, decoding_or_default(decoding)).build()?;
+
+                let tcp = tcp::RawTcpSource::new(config.clone(), decoder);
                 let tls = MaybeTlsSettings::from_config(config.tls(), true)?;
                 tcp.run(
                     config.address(),
@@ -92,6 +100,7 @@ impl SourceConfig for SocketConfig {
                     config.receive_buffer_bytes(),
                     cx.shutdown,
                     cx.out,
+                    false,
                 )
             }
             Mode::Udp(config) => {
@@ -99,11 +108,19 @@ impl SourceConfig for SocketConfig {
                     .host_key()
                     .clone()
                     .unwrap_or_else(|| log_schema().host_key().to_string());
+                let decoding = config.decoding().clone();
+                let framing = config
+                    .framing()
+                    .clone()
+                    .unwrap_or_else(|| Box::new(NewlineDelimitedDecoderConfig::new()));
+                let decoder =
+                    DecodingConfig::new(framing, decoding_or_default(decoding)).build()?;
                 Ok(udp::udp(
                     config.address(),
                     config.max_length(),
                     host_key,
                     config.receive_buffer_bytes(),
+                    decoder,
                     cx.shutdown,
                     cx.out,
                 ))
@@ -113,10 +130,18 @@ impl SourceConfig for SocketConfig {
                 let host_key = config
                     .host_key
                     .unwrap_or_else(|| log_schema().host_key().to_string());
+                let framing = config.framing.unwrap_or_else(|| {
+                    Box::new(NewlineDelimitedDecoderConfig::new_with_max_length(
+                        config.max_length,
+                    ))
+                });
+                let decoder =
+                    DecodingConfig::new(framing, decoding_or_default(config.decoding)).build()?;
                 Ok(unix::unix_datagram(
                     config.path,
                     config.max_length,
                     host_key,
+                    decoder,
                     cx.shutdown,
                     cx.out,
                 ))
@@ -126,10 +151,17 @@ impl SourceConfig for SocketConfig {
                 let host_key = config
                     .host_key
                     .unwrap_or_else(|| log_schema().host_key().to_string());
+                let framing = config.framing.unwrap_or_else(|| {
+                    Box::new(NewlineDelimitedDecoderConfig::new_with_max_length(
+                        config.max_length,
+                    ))
+                });
+                let decoder =
+                    DecodingConfig::new(framing, decoding_or_default(config.decoding)).build()?;
                 Ok(unix::unix_stream(
                     config.path,
-                    config.max_length,
                     host_key,
+                    decoder,
                     cx.shutdown,
                     cx.out,
                 ))
@@ -157,10 +189,17 @@ impl SourceConfig for SocketConfig {
     }
 }
 
+/// Events are stored as raw bytes in the message field unless configured
+/// otherwise.
+fn decoding_or_default(decoding: Option<Box<dyn ParserConfig>>) -> Box<dyn ParserConfig> {
+    decoding.unwrap_or_else(|| Box::new(BytesParserConfig::new()))
+}
+
 #[cfg(test)]
 mod test {
     use super::{tcp::TcpConfig, udp::UdpConfig, SocketConfig};
     use crate::{
+        codecs::{CharacterDelimitedDecoderConfig, JsonParserConfig},
         config::{log_schema, GlobalOptions, SinkContext, SourceConfig, SourceContext},
         event::Event,
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
@@ -248,6 +287,39 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn tcp_decodes_with_configured_framing_and_decoding() {
+        let (tx, rx) = Pipeline::new_test();
+        let addr = next_addr();
+
+        let mut config = TcpConfig::from_address(addr.into());
+        config.set_framing(Some(Box::new(CharacterDelimitedDecoderConfig::new(';'))));
+        config.set_decoding(Some(Box::new(JsonParserConfig::new())));
+
+        let server = SocketConfig::from(config)
+            .build(SourceContext::new_test(tx))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        wait_for_tcp(addr).await;
+        send_lines(
+            addr,
+            vec![r#"{"foo":"bar"};[{"baz":1},{"baz":2}];"#.to_owned()].into_iter(),
+        )
+        .await
+        .unwrap();
+
+        let events = collect_n(rx, 3).await;
+        assert_eq!(events[0].as_log()["foo"], "bar".into());
+        assert_eq!(events[1].as_log()["baz"], 1.into());
+        assert_eq!(events[2].as_log()["baz"], 2.into());
+        assert_eq!(
+            events[2].as_log()[log_schema().source_type_key()],
+            "socket".into()
+        );
+    }
+
     #[tokio::test]
     async fn tcp_continue_after_long_line() {
         let (tx, mut rx) = Pipeline::new_test();
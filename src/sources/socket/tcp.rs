@@ -1,4 +1,5 @@
 use crate::{
+    codecs::{self, FramingConfig, ParserConfig},
     event::Event,
     internal_events::{SocketEventReceived, SocketMode},
     sources::util::{SocketListenAddr, TcpSource},
@@ -6,7 +7,6 @@ use crate::{
     tls::TlsConfig,
 };
 use bytes::Bytes;
-use codec::BytesDelimitedCodec;
 use getset::{CopyGetters, Getters, Setters};
 use serde::{Deserialize, Serialize};
 
@@ -28,6 +28,10 @@ pub struct TcpConfig {
     tls: Option<TlsConfig>,
     #[get_copy = "pub"]
     receive_buffer_bytes: Option<usize>,
+    #[getset(get = "pub", set = "pub")]
+    framing: Option<Box<dyn FramingConfig>>,
+    #[getset(get = "pub", set = "pub")]
+    decoding: Option<Box<dyn ParserConfig>>,
 }
 
 fn default_max_length() -> usize {
@@ -47,6 +51,8 @@ impl TcpConfig {
         host_key: Option<String>,
         tls: Option<TlsConfig>,
         receive_buffer_bytes: Option<usize>,
+        framing: Option<Box<dyn FramingConfig>>,
+        decoding: Option<Box<dyn ParserConfig>>,
     ) -> Self {
         Self {
             address,
@@ -56,6 +62,8 @@ impl TcpConfig {
             host_key,
             tls,
             receive_buffer_bytes,
+            framing,
+            decoding,
         }
     }
 
@@ -68,43 +76,56 @@ impl TcpConfig {
             host_key: None,
             tls: None,
             receive_buffer_bytes: None,
+            framing: None,
+            decoding: None,
         }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct RawTcpSource {
-    pub config: TcpConfig,
+    config: TcpConfig,
+    decoder: codecs::Decoder,
+}
+
+impl RawTcpSource {
+    pub fn new(config: TcpConfig, decoder: codecs::Decoder) -> Self {
+        Self { config, decoder }
+    }
 }
 
 impl TcpSource for RawTcpSource {
-    type Error = std::io::Error;
-    type Decoder = BytesDelimitedCodec;
+    type Error = codecs::Error;
+    type Decoder = codecs::Decoder;
 
     fn decoder(&self) -> Self::Decoder {
-        BytesDelimitedCodec::new_with_max_length(b'\n', self.config.max_length)
+        self.decoder.clone()
     }
 
-    fn build_event(&self, frame: Bytes, host: Bytes) -> Option<Event> {
-        let byte_size = frame.len();
-        let mut event = Event::from(frame);
-
-        event.as_mut_log().insert(
-            crate::config::log_schema().source_type_key(),
-            Bytes::from("socket"),
-        );
-
-        let host_key = (self.config.host_key.clone())
-            .unwrap_or_else(|| crate::config::log_schema().host_key().to_string());
-
-        event.as_mut_log().insert(host_key, host);
-
+    fn build_events(
+        &self,
+        (mut events, byte_size): (Vec<Event>, usize),
+        host: Bytes,
+    ) -> Vec<Event> {
         emit!(SocketEventReceived {
             byte_size,
             mode: SocketMode::Tcp
         });
 
-        Some(event)
+        let host_key = (self.config.host_key.as_deref())
+            .unwrap_or_else(|| crate::config::log_schema().host_key());
+
+        for event in &mut events {
+            if let Event::Log(ref mut log) = event {
+                log.insert(
+                    crate::config::log_schema().source_type_key(),
+                    Bytes::from("socket"),
+                );
+                log.insert(host_key, host.clone());
+            }
+        }
+
+        events
     }
 }
 
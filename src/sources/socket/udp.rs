@@ -1,5 +1,6 @@
 use crate::udp;
 use crate::{
+    codecs::{self, FramingConfig, ParserConfig},
     event::Event,
     internal_events::{SocketEventReceived, SocketMode, SocketReceiveError},
     shutdown::ShutdownSignal,
@@ -7,15 +8,15 @@ use crate::{
     Pipeline,
 };
 use bytes::{Bytes, BytesMut};
-use codec::BytesDelimitedCodec;
-use futures::SinkExt;
+use futures::{SinkExt, StreamExt};
 use getset::{CopyGetters, Getters};
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 use tokio::net::UdpSocket;
-use tokio_util::codec::Decoder;
+use tokio_util::codec::FramedRead;
 
-/// UDP processes messages per packet, where messages are separated by newline.
+/// UDP processes messages per packet, where messages are separated by newline
+/// unless configured otherwise.
 #[derive(Deserialize, Serialize, Debug, Clone, Getters, CopyGetters)]
 #[serde(deny_unknown_fields)]
 pub struct UdpConfig {
@@ -28,6 +29,10 @@ pub struct UdpConfig {
     host_key: Option<String>,
     #[get_copy = "pub"]
     receive_buffer_bytes: Option<usize>,
+    #[get = "pub"]
+    framing: Option<Box<dyn FramingConfig>>,
+    #[get = "pub"]
+    decoding: Option<Box<dyn ParserConfig>>,
 }
 
 fn default_max_length() -> usize {
@@ -41,6 +46,8 @@ impl UdpConfig {
             max_length: default_max_length(),
             host_key: None,
             receive_buffer_bytes: None,
+            framing: None,
+            decoding: None,
         }
     }
 }
@@ -50,6 +57,7 @@ pub fn udp(
     max_length: usize,
     host_key: String,
     receive_buffer_bytes: Option<usize>,
+    decoder: codecs::Decoder,
     mut shutdown: ShutdownSignal,
     out: Pipeline,
 ) -> Source {
@@ -86,29 +94,40 @@ pub fn udp(
                         });
                     })?;
 
-                    let mut payload = buf.split_to(byte_size);
+                    let payload = buf.split_to(byte_size);
 
-                    // UDP processes messages per payload, where messages are separated by newline
-                    // and stretch to end of payload.
-                    let mut decoder = BytesDelimitedCodec::new(b'\n');
-                    while let Ok(Some(line)) = decoder.decode_eof(&mut payload) {
-                        let mut event = Event::from(line);
+                    // UDP processes messages per payload, where messages are separated by the
+                    // configured framing and stretch to end of payload.
+                    let mut stream = FramedRead::new(payload.as_ref(), decoder.clone());
 
-                        event
-                            .as_mut_log()
-                            .insert(crate::config::log_schema().source_type_key(), Bytes::from("socket"));
-                        event
-                            .as_mut_log()
-                            .insert(host_key.clone(), address.to_string());
+                    loop {
+                        match stream.next().await {
+                            Some(Ok((events, byte_size))) => {
+                                emit!(SocketEventReceived { byte_size, mode: SocketMode::Udp });
 
-                        emit!(SocketEventReceived { byte_size,mode:SocketMode::Udp });
+                                for mut event in events {
+                                    if let Event::Log(ref mut log) = event {
+                                        log.insert(crate::config::log_schema().source_type_key(), Bytes::from("socket"));
+                                        log.insert(host_key.clone(), address.to_string());
+                                    }
 
-                        tokio::select!{
-                            result = out.send(event) => {match result {
-                                Ok(()) => { },
-                                Err(()) => return Ok(()),
-                            }}
-                            _ = &mut shutdown => return Ok(()),
+                                    tokio::select!{
+                                        result = out.send(event) => {match result {
+                                            Ok(()) => { },
+                                            Err(()) => return Ok(()),
+                                        }}
+                                        _ = &mut shutdown => return Ok(()),
+                                    }
+                                }
+                            }
+                            Some(Err(error)) => {
+                                // Error is logged by `crate::codecs::Decoder`, no further handling
+                                // is needed here.
+                                if !error.can_continue() {
+                                    break;
+                                }
+                            }
+                            None => break,
                         }
                     }
                 }
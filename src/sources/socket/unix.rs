@@ -1,4 +1,5 @@
 use crate::{
+    codecs::{self, FramingConfig, ParserConfig},
     event::Event,
     internal_events::{SocketEventReceived, SocketMode},
     shutdown::ShutdownSignal,
@@ -11,7 +12,6 @@ use crate::{
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use tokio_util::codec::LinesCodec;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
@@ -20,6 +20,8 @@ pub struct UnixConfig {
     #[serde(default = "default_max_length")]
     pub max_length: usize,
     pub host_key: Option<String>,
+    pub framing: Option<Box<dyn FramingConfig>>,
+    pub decoding: Option<Box<dyn ParserConfig>>,
 }
 
 fn default_max_length() -> usize {
@@ -32,35 +34,45 @@ impl UnixConfig {
             path,
             max_length: default_max_length(),
             host_key: None,
+            framing: None,
+            decoding: None,
         }
     }
 }
 
 /**
 * Function to pass to build_unix_*_source, specific to the basic unix source.
-* Takes a single line of a received message and builds an Event object.
+* Takes the events decoded from a received frame and enriches them with
+* source specific fields.
 **/
-fn build_event(host_key: &str, received_from: Option<Bytes>, line: &str) -> Event {
-    let byte_size = line.len();
-    let mut event = Event::from(line);
-    event.as_mut_log().insert(
-        crate::config::log_schema().source_type_key(),
-        Bytes::from("socket"),
-    );
-    if let Some(host) = received_from {
-        event.as_mut_log().insert(host_key, host);
+fn build_events(
+    host_key: &str,
+    received_from: Option<Bytes>,
+    (mut events, byte_size): (Vec<Event>, usize),
+) -> Vec<Event> {
+    for event in &mut events {
+        if let Event::Log(ref mut log) = event {
+            log.insert(
+                crate::config::log_schema().source_type_key(),
+                Bytes::from("socket"),
+            );
+            if let Some(ref host) = received_from {
+                log.insert(host_key, host.clone());
+            }
+        }
     }
     emit!(SocketEventReceived {
         byte_size,
         mode: SocketMode::Unix
     });
-    event
+    events
 }
 
 pub(super) fn unix_datagram(
     path: PathBuf,
     max_length: usize,
     host_key: String,
+    decoder: codecs::Decoder,
     shutdown: ShutdownSignal,
     out: Pipeline,
 ) -> Source {
@@ -68,26 +80,19 @@ pub(super) fn unix_datagram(
         path,
         max_length,
         host_key,
-        LinesCodec::new_with_max_length(max_length),
+        decoder,
         shutdown,
         out,
-        |host_key, received_from, line| Some(build_event(host_key, received_from, line)),
+        build_events,
     )
 }
 
 pub(super) fn unix_stream(
     path: PathBuf,
-    max_length: usize,
     host_key: String,
+    decoder: codecs::Decoder,
     shutdown: ShutdownSignal,
     out: Pipeline,
 ) -> Source {
-    build_unix_stream_source(
-        path,
-        LinesCodec::new_with_max_length(max_length),
-        host_key,
-        shutdown,
-        out,
-        |host_key, received_from, line| Some(build_event(host_key, received_from, line)),
-    )
+    build_unix_stream_source(path, decoder, host_key, shutdown, out, build_events)
 }
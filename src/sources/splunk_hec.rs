@@ -1,6 +1,6 @@
 use crate::{
     config::{log_schema, DataType, Resource, SourceConfig, SourceContext, SourceDescription},
-    event::{Event, LogEvent, Value},
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, LogEvent, Value},
     internal_events::{
         SplunkHecEventReceived, SplunkHecRequestBodyInvalid, SplunkHecRequestError,
         SplunkHecRequestReceived,
@@ -11,7 +11,7 @@ use crate::{
 use bytes::{Buf, Bytes};
 use chrono::{DateTime, TimeZone, Utc};
 use flate2::read::MultiGzDecoder;
-use futures::{stream, FutureExt, SinkExt, StreamExt, TryFutureExt};
+use futures::{stream, FutureExt, SinkExt, StreamExt, TryStreamExt};
 use http::StatusCode;
 use serde::{de, Deserialize, Serialize};
 use serde_json::{de::IoRead, json, Deserializer, Value as JsonValue};
@@ -21,6 +21,7 @@ use std::{
     future,
     io::Read,
     net::{Ipv4Addr, SocketAddr},
+    sync::Arc,
 };
 
 use warp::{filters::BoxedFilter, path, reject::Rejection, reply::Response, Filter, Reply};
@@ -77,7 +78,7 @@ fn default_socket_address() -> SocketAddr {
 #[typetag::serde(name = "splunk_hec")]
 impl SourceConfig for SplunkConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
-        let source = SplunkSource::new(self);
+        let source = SplunkSource::new(self, cx.acknowledgements);
 
         let event_service = source.event_service(cx.out.clone());
         let raw_service = source.raw_service(cx.out);
@@ -137,15 +138,17 @@ impl SourceConfig for SplunkConfig {
 /// Shared data for responding to requests.
 struct SplunkSource {
     credentials: Option<Bytes>,
+    acknowledgements: bool,
 }
 
 impl SplunkSource {
-    fn new(config: &SplunkConfig) -> Self {
+    fn new(config: &SplunkConfig, acknowledgements: bool) -> Self {
         SplunkSource {
             credentials: config
                 .token
                 .as_ref()
                 .map(|token| format!("Splunk {}", token).into()),
+            acknowledgements,
         }
     }
 
@@ -158,6 +161,7 @@ impl SplunkSource {
             .and(splunk_channel_query_param)
             .map(|header: Option<String>, query_param| header.or(query_param));
 
+        let acknowledgements = self.acknowledgements;
         warp::post()
             .and(path!("event").or(path!("event" / "1.0")))
             .and(self.authorization())
@@ -184,7 +188,9 @@ impl SplunkSource {
                             Box::new(body.reader())
                         };
 
-                        let events = stream::iter(EventIterator::new(reader, channel, remote, xff));
+                        let (batch, receiver) = new_batch(acknowledgements);
+                        let events = stream::iter(EventIterator::new(reader, channel, remote, xff))
+                            .map_ok(move |event| with_batch(event, &batch));
 
                         // `fn send_all` can be used once https://github.com/rust-lang/futures-rs/issues/2402
                         // is resolved.
@@ -192,7 +198,8 @@ impl SplunkSource {
 
                         out.flush().await?;
 
-                        res
+                        res?;
+                        handle_batch_status(receiver).await
                     }
                 },
             )
@@ -213,6 +220,7 @@ impl SplunkSource {
                     .ok_or_else(|| Rejection::from(ApiError::MissingChannel))
             });
 
+        let acknowledgements = self.acknowledgements;
         warp::post()
             .and(path!("raw" / "1.0").or(path!("raw")))
             .and(self.authorization())
@@ -231,13 +239,18 @@ impl SplunkSource {
                       body: Bytes| {
                     let out = out.clone();
                     async move {
-                        let event = future::ready(raw_event(body, gzip, channel, remote, xff));
+                        let (batch, receiver) = new_batch(acknowledgements);
+                        let event = future::ready(
+                            raw_event(body, gzip, channel, remote, xff)
+                                .map(|event| with_batch(event, &batch)),
+                        );
+                        drop(batch);
                         futures::stream::once(event)
                             .forward(
                                 out.sink_map_err(|_| Rejection::from(ApiError::ServerShutdown)),
                             )
-                            .map_ok(|_| ())
-                            .await
+                            .await?;
+                        handle_batch_status(receiver).await
                     }
                 },
             )
@@ -505,6 +518,37 @@ impl<R: Read> Iterator for EventIterator<R> {
     }
 }
 
+/// Creates the batch notifier shared by all the events of a request, if
+/// acknowledgements are enabled.
+fn new_batch(acknowledgements: bool) -> (Option<Arc<BatchNotifier>>, Option<BatchStatusReceiver>) {
+    if acknowledgements {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        (Some(batch), Some(receiver))
+    } else {
+        (None, None)
+    }
+}
+
+fn with_batch(event: Event, batch: &Option<Arc<BatchNotifier>>) -> Event {
+    match batch {
+        Some(batch) => event.with_batch_notifier(batch),
+        None => event,
+    }
+}
+
+/// Waits for the sinks to deliver the events of a request before
+/// responding to it.
+async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), Rejection> {
+    match receiver {
+        None => Ok(()),
+        Some(receiver) => match receiver.await {
+            BatchStatus::Delivered => Ok(()),
+            BatchStatus::Errored => Err(ApiError::DeliveryErrored.into()),
+            BatchStatus::Failed => Err(ApiError::DeliveryFailed.into()),
+        },
+    }
+}
+
 /// Parse a `i64` unix timestamp that can either be in seconds, milliseconds or
 /// nanoseconds.
 ///
@@ -654,6 +698,8 @@ pub(crate) enum ApiError {
     EmptyEventField { event: usize },
     MissingEventField { event: usize },
     BadRequest,
+    DeliveryErrored,
+    DeliveryFailed,
 }
 
 impl warp::reject::Reject for ApiError {}
@@ -683,6 +729,8 @@ mod splunk_response {
             json_to_bytes(json!({"text":"unsupported content encoding"}));
         pub static ref NO_CHANNEL: Bytes =
             json_to_bytes(json!({"text":"Data channel is missing","code":10}));
+        pub static ref DELIVERY_FAILED: Bytes =
+            json_to_bytes(json!({"text":"Events failed to deliver","code":8}));
     }
 }
 
@@ -725,6 +773,14 @@ async fn finish_err(rejection: Rejection) -> Result<(Response,), Rejection> {
                 event_error("Event field is required", 12, event)
             }
             ApiError::BadRequest => empty_response(StatusCode::BAD_REQUEST),
+            ApiError::DeliveryErrored => response_json(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                splunk_response::SERVER_ERROR.as_ref(),
+            ),
+            ApiError::DeliveryFailed => response_json(
+                StatusCode::BAD_REQUEST,
+                splunk_response::DELIVERY_FAILED.as_ref(),
+            ),
         },))
     } else {
         Err(rejection)
@@ -769,17 +825,17 @@ mod tests {
     use super::{parse_timestamp, SplunkConfig};
     use crate::{
         config::{log_schema, SinkConfig, SinkContext, SourceConfig, SourceContext},
-        event::Event,
+        event::{Event, EventStatus},
         sinks::{
             splunk_hec::{Encoding, HecSinkConfig},
             util::{encoding::EncodingConfig, BatchConfig, Compression, TowerRequestConfig},
             Healthcheck, VectorSink,
         },
-        test_util::{collect_n, next_addr, trace_init, wait_for_tcp},
+        test_util::{collect_n, next_addr, spawn_collect_n, trace_init, wait_for_tcp},
         Pipeline,
     };
     use chrono::{TimeZone, Utc};
-    use futures::{channel::mpsc, stream, StreamExt};
+    use futures::{channel::mpsc, stream, Stream, StreamExt};
     use std::{future::ready, net::SocketAddr};
 
     #[test]
@@ -813,6 +869,29 @@ mod tests {
         (recv, address)
     }
 
+    async fn source_with_acknowledgements(
+        status: EventStatus,
+    ) -> (impl Stream<Item = Event> + Unpin, SocketAddr) {
+        let (sender, recv) = Pipeline::new_test_finalize(status);
+        let address = next_addr();
+        let mut context = SourceContext::new_test(sender);
+        context.acknowledgements = true;
+        tokio::spawn(async move {
+            SplunkConfig {
+                address,
+                token: Some(TOKEN.to_owned()),
+                tls: None,
+            }
+            .build(context)
+            .await
+            .unwrap()
+            .await
+            .unwrap()
+        });
+        wait_for_tcp(address).await;
+        (recv, address)
+    }
+
     async fn sink(
         address: SocketAddr,
         encoding: impl Into<EncodingConfig<Encoding>>,
@@ -1317,4 +1396,63 @@ mod tests {
         assert_eq!(event.as_log()[log_schema().message_key()], message.into());
         assert!(event.as_log().get(log_schema().host_key()).is_none());
     }
+
+    #[tokio::test]
+    async fn event_delivered_with_acknowledgements() {
+        trace_init();
+
+        let (rx, address) = source_with_acknowledgements(EventStatus::Delivered).await;
+
+        let events = spawn_collect_n(
+            async move {
+                assert_eq!(
+                    200,
+                    post(address, "services/collector/event", r#"{"event":"first"}"#).await
+                );
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "first".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn event_delivery_failure() {
+        trace_init();
+
+        let (rx, address) = source_with_acknowledgements(EventStatus::Failed).await;
+
+        spawn_collect_n(
+            async move {
+                assert_eq!(
+                    400,
+                    post(address, "services/collector/event", r#"{"event":"first"}"#).await
+                );
+            },
+            rx,
+            1,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn raw_delivery_errored() {
+        trace_init();
+
+        let (rx, address) = source_with_acknowledgements(EventStatus::Errored).await;
+
+        spawn_collect_n(
+            async move {
+                assert_eq!(500, post(address, "services/collector/raw", "first").await);
+            },
+            rx,
+            1,
+        )
+        .await;
+    }
 }
@@ -108,6 +108,7 @@ impl SourceConfig for StatsdConfig {
                     config.receive_buffer_bytes,
                     cx.shutdown,
                     cx.out,
+                    false,
                 )
             }
             #[cfg(unix)]
@@ -204,9 +205,9 @@ impl TcpSource for StatsdTcpSource {
         BytesDelimitedCodec::new(b'\n')
     }
 
-    fn build_event(&self, line: Bytes, _host: Bytes) -> Option<Event> {
+    fn build_events(&self, line: Bytes, _host: Bytes) -> Vec<Event> {
         let line = String::from_utf8_lossy(line.as_ref());
-        parse_event(&line)
+        parse_event(&line).into_iter().collect()
     }
 }
 
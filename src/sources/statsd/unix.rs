@@ -12,8 +12,8 @@ pub struct UnixConfig {
     pub path: PathBuf,
 }
 
-fn build_event(_: &str, _: Option<Bytes>, line: &str) -> Option<Event> {
-    super::parse_event(line)
+fn build_event(_: &str, _: Option<Bytes>, line: String) -> Option<Event> {
+    super::parse_event(&line)
 }
 
 pub fn statsd_unix(config: UnixConfig, shutdown: ShutdownSignal, out: Pipeline) -> Source {
@@ -1,16 +1,19 @@
 use crate::{
-    config::log_schema,
-    event::{Event, LogEvent},
+    codecs::{
+        BoxedFramer, BoxedParser, BytesDecoderConfig, BytesParser, FramingConfig, JsonParser,
+        NewlineDelimitedDecoderConfig, Parser, ParserConfig,
+    },
+    event::Event,
+    internal_events::{DecoderFramingFailed, DecoderParseFailed},
     sources::util::http::ErrorMessage,
 };
 use bytes::{Bytes, BytesMut};
-use chrono::Utc;
-use codec::BytesDelimitedCodec;
 use serde::{Deserialize, Serialize};
-use serde_json::Value as JsonValue;
 use tokio_util::codec::Decoder;
 use warp::http::StatusCode;
 
+/// The `encoding` of the HTTP based sources. Each encoding is a shorthand for
+/// a combination of the framing and decoding codecs.
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Derivative, Copy)]
 #[serde(rename_all = "snake_case")]
 #[derivative(Default)]
@@ -22,100 +25,140 @@ pub enum Encoding {
     Binary,
 }
 
-#[cfg(any(feature = "sources-http", feature = "sources-datadog"))]
-fn body_to_lines(buf: Bytes) -> impl Iterator<Item = Result<Bytes, ErrorMessage>> {
-    let mut body = BytesMut::new();
-    body.extend_from_slice(&buf);
-
-    let mut decoder = BytesDelimitedCodec::new(b'\n');
-    std::iter::from_fn(move || {
-        match decoder.decode_eof(&mut body) {
-            Err(error) => Some(Err(ErrorMessage::new(
-                StatusCode::BAD_REQUEST,
-                format!("Bad request: {}", error),
-            ))),
-            Ok(Some(b)) => Some(Ok(b)),
-            Ok(None) => None, // actually done
+impl Encoding {
+    /// The framing used for this encoding.
+    pub fn framing(self) -> Box<dyn FramingConfig> {
+        match self {
+            Self::Text | Self::Ndjson => Box::new(NewlineDelimitedDecoderConfig::new()),
+            Self::Json | Self::Binary => Box::new(BytesDecoderConfig::new()),
         }
-    })
-    .filter(|s| match s {
-        // filter empty lines
-        Ok(b) => !b.is_empty(),
-        _ => true,
-    })
-}
+    }
 
-#[cfg(any(feature = "sources-http", feature = "sources-datadog"))]
-pub fn decode_body(body: Bytes, enc: Encoding) -> Result<Vec<Event>, ErrorMessage> {
-    match enc {
-        Encoding::Text => body_to_lines(body)
-            .map(|r| Ok(LogEvent::from(r?).into()))
-            .collect::<Result<_, _>>(),
-        Encoding::Ndjson => body_to_lines(body)
-            .map(|j| {
-                let parsed_json = serde_json::from_slice(&j?)
-                    .map_err(|error| json_error(format!("Error parsing Ndjson: {:?}", error)))?;
-                json_parse_object(parsed_json).map(Into::into)
-            })
-            .collect::<Result<_, _>>(),
-        Encoding::Json => {
-            let parsed_json = serde_json::from_slice(&body)
-                .map_err(|error| json_error(format!("Error parsing Json: {:?}", error)))?;
-            json_parse_array_of_object(parsed_json)
+    /// The parser used for this encoding.
+    pub fn parser(self) -> BoxedParser {
+        match self {
+            Self::Text | Self::Binary => Box::new(BytesParser::new()),
+            Self::Ndjson => Box::new(NdjsonParser),
+            Self::Json => Box::new(JsonParser::new()),
         }
-        Encoding::Binary => Ok(vec![LogEvent::from(body).into()]),
     }
 }
 
-#[cfg(any(feature = "sources-http", feature = "sources-datadog"))]
-fn json_parse_object(value: JsonValue) -> Result<LogEvent, ErrorMessage> {
-    match value {
-        JsonValue::Object(map) => {
-            let mut log = LogEvent::default();
-            log.insert(log_schema().timestamp_key(), Utc::now()); // Add timestamp
-            for (k, v) in map {
-                log.insert_flat(k, v);
-            }
-            Ok(log)
+/// Parses the lines of `ndjson` bodies, each of which must hold a single JSON
+/// object rather than an array of them.
+#[derive(Debug, Clone)]
+struct NdjsonParser;
+
+impl Parser for NdjsonParser {
+    fn parse(&self, bytes: Bytes) -> crate::Result<Vec<Event>> {
+        if bytes.iter().find(|byte| !byte.is_ascii_whitespace()) == Some(&b'[') {
+            return Err("Expected a JSON object on each line, found an array.".into());
         }
-        _ => Err(json_error(format!(
-            "Expected Object, got {}",
-            json_value_to_type_string(&value)
-        ))),
+        JsonParser::new().parse(bytes)
     }
 }
 
-#[cfg(any(feature = "sources-http", feature = "sources-datadog"))]
-fn json_parse_array_of_object(value: JsonValue) -> Result<Vec<Event>, ErrorMessage> {
-    match value {
-        JsonValue::Array(v) => v
-            .into_iter()
-            .map(|object| json_parse_object(object).map(Into::into))
-            .collect::<Result<_, _>>(),
-        JsonValue::Object(map) => {
-            //treat like an array of one object
-            Ok(vec![json_parse_object(JsonValue::Object(map))?.into()])
+/// Decodes request bodies into events, splitting them into frames with the
+/// framer and parsing each frame with the parser.
+#[derive(Debug, Clone)]
+pub struct BodyDecoder {
+    framer: BoxedFramer,
+    parser: BoxedParser,
+}
+
+impl BodyDecoder {
+    pub fn new(framing: &dyn FramingConfig, decoding: &dyn ParserConfig) -> crate::Result<Self> {
+        Self::with_parser(framing, decoding.build()?)
+    }
+
+    pub fn with_parser(framing: &dyn FramingConfig, parser: BoxedParser) -> crate::Result<Self> {
+        Ok(Self {
+            framer: framing.build()?,
+            parser,
+        })
+    }
+
+    /// Empty frames, e.g. blank lines, are skipped. The whole request is
+    /// rejected if any frame can't be framed or parsed.
+    pub fn decode(&self, body: Bytes) -> Result<Vec<Event>, ErrorMessage> {
+        let mut framer = self.framer.clone();
+        let mut body = BytesMut::from(&body[..]);
+        let mut events = Vec::new();
+
+        loop {
+            let frame = framer.decode_eof(&mut body).map_err(|error| {
+                emit!(DecoderFramingFailed { error: &error });
+                bad_request(error)
+            })?;
+
+            match frame {
+                Some(frame) if frame.is_empty() => continue,
+                Some(frame) => events.extend(self.parser.parse(frame).map_err(|error| {
+                    emit!(DecoderParseFailed { error: &error });
+                    bad_request(error)
+                })?),
+                None => break,
+            }
         }
-        _ => Err(json_error(format!(
-            "Expected Array or Object, got {}.",
-            json_value_to_type_string(&value)
-        ))),
+
+        Ok(events)
     }
 }
 
-#[cfg(any(feature = "sources-http", feature = "sources-datadog"))]
-fn json_error(s: String) -> ErrorMessage {
-    ErrorMessage::new(StatusCode::BAD_REQUEST, format!("Bad JSON: {}", s))
+fn bad_request(error: impl std::fmt::Display) -> ErrorMessage {
+    ErrorMessage::new(StatusCode::BAD_REQUEST, format!("Bad request: {}", error))
 }
 
-#[cfg(any(feature = "sources-http", feature = "sources-datadog"))]
-fn json_value_to_type_string(value: &JsonValue) -> &'static str {
-    match value {
-        JsonValue::Object(_) => "Object",
-        JsonValue::Array(_) => "Array",
-        JsonValue::String(_) => "String",
-        JsonValue::Number(_) => "Number",
-        JsonValue::Bool(_) => "Bool",
-        JsonValue::Null => "Null",
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        codecs::{CharacterDelimitedDecoderConfig, JsonParserConfig},
+        config::log_schema,
+    };
+
+    #[test]
+    fn decodes_with_configured_codecs() {
+        let decoder = BodyDecoder::new(
+            &CharacterDelimitedDecoderConfig::new(';'),
+            &JsonParserConfig::new(),
+        )
+        .unwrap();
+
+        let events = decoder
+            .decode(Bytes::from(r#"{"foo":1};;[{"bar":2},{"baz":3}]"#))
+            .unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].as_log()["foo"], 1.into());
+        assert_eq!(events[2].as_log()["baz"], 3.into());
+
+        let error = decoder
+            .decode(Bytes::from(r#"{"foo":1};nope"#))
+            .unwrap_err();
+        assert!(error.to_string().starts_with("400: Bad request: "));
+    }
+
+    #[test]
+    fn text_encoding_skips_empty_lines() {
+        let encoding = Encoding::Text;
+        let decoder = BodyDecoder::with_parser(&*encoding.framing(), encoding.parser()).unwrap();
+
+        let events = decoder.decode(Bytes::from("foo\n\nbar\n")).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "bar".into());
+    }
+
+    #[test]
+    fn ndjson_encoding_rejects_arrays() {
+        let encoding = Encoding::Ndjson;
+        let decoder = BodyDecoder::with_parser(&*encoding.framing(), encoding.parser()).unwrap();
+
+        assert_eq!(
+            decoder.decode(Bytes::from("{\"foo\":1}\n")).unwrap().len(),
+            1
+        );
+        assert!(decoder
+            .decode(Bytes::from("{\"foo\":1}\n [{\"bar\":2}]\n"))
+            .is_err());
     }
 }
@@ -0,0 +1,72 @@
+use crate::Pipeline;
+use futures::{SinkExt, StreamExt, TryFutureExt};
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, sync::Arc};
+use tonic::{
+    transport::{Certificate, Identity, Server, ServerTlsConfig},
+    Status,
+};
+use vector_core::event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcTlsConfig {
+    ca_file: PathBuf,
+    crt_file: PathBuf,
+    key_file: PathBuf,
+}
+
+/// Builds a gRPC server, requiring client certificates signed by the
+/// configured CA when TLS is enabled.
+pub(crate) async fn build_grpc_server(tls: Option<&GrpcTlsConfig>) -> crate::Result<Server> {
+    Ok(match tls {
+        Some(tls) => {
+            let ca = Certificate::from_pem(tokio::fs::read(&tls.ca_file).await?);
+            let crt = tokio::fs::read(&tls.crt_file).await?;
+            let key = tokio::fs::read(&tls.key_file).await?;
+            let identity = Identity::from_pem(crt, key);
+
+            let tls_config = ServerTlsConfig::new().identity(identity).client_ca_root(ca);
+
+            Server::builder().tls_config(tls_config)?
+        }
+        None => Server::builder(),
+    })
+}
+
+/// Sends the events of a gRPC request into the pipeline and, with
+/// acknowledgements enabled, waits until they have been delivered.
+pub(crate) async fn send_grpc_events(
+    pipeline: &Pipeline,
+    acknowledgements: bool,
+    mut events: Vec<Event>,
+) -> Result<(), Status> {
+    let receiver = acknowledgements.then(|| {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        for event in &mut events {
+            event.add_batch_notifier(Arc::clone(&batch));
+        }
+
+        receiver
+    });
+
+    pipeline
+        .clone()
+        .send_all(&mut futures::stream::iter(events).map(Ok))
+        .map_err(|err| Status::unavailable(err.to_string()))
+        .and_then(|_| handle_batch_status(receiver))
+        .await
+}
+
+async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), Status> {
+    let status = match receiver {
+        Some(receiver) => receiver.await,
+        None => BatchStatus::Delivered,
+    };
+
+    match status {
+        BatchStatus::Errored => Err(Status::internal("Delivery error")),
+        BatchStatus::Failed => Err(Status::data_loss("Delivery failed")),
+        BatchStatus::Delivered => Ok(()),
+    }
+}
@@ -20,7 +20,8 @@ use warp::{
     filters::{path::FullPath, path::Tail, BoxedFilter},
     http::{HeaderMap, StatusCode},
     reject::Rejection,
-    Filter,
+    reply::Response,
+    Filter, Reply,
 };
 
 #[cfg(any(feature = "sources-http", feature = "sources-heroku_logs"))]
@@ -185,6 +186,11 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         path: &str,
     ) -> Result<Vec<Event>, ErrorMessage>;
 
+    /// The response to a request whose events were accepted, empty by default.
+    fn build_response(&self, _path: &str) -> Response {
+        warp::reply().into_response()
+    }
+
     fn run(
         self,
         address: SocketAddr,
@@ -244,8 +250,9 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                                 self.build_events(body, headers, query_parameters, path.as_str())
                                     .map(|events| (events, body_len))
                             });
+                        let response = self.build_response(path.as_str());
 
-                        handle_request(events, acknowledgements, out.clone())
+                        handle_request(events, response, acknowledgements, out.clone())
                             .instrument(span.clone())
                     },
                 );
@@ -281,9 +288,10 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
 
 async fn handle_request(
     events: Result<(Vec<Event>, usize), ErrorMessage>,
+    response: Response,
     acknowledgements: bool,
     mut out: Pipeline,
-) -> Result<impl warp::Reply, Rejection> {
+) -> Result<Response, Rejection> {
     match events {
         Ok((mut events, body_size)) => {
             emit!(HttpEventsReceived {
@@ -307,7 +315,7 @@ async fn handle_request(
                     error!(message = "Tried to send the following event.", %error);
                     warp::reject::custom(RejectShuttingDown)
                 })
-                .and_then(|_| handle_batch_status(receiver))
+                .and_then(|_| handle_batch_status(receiver, response))
                 .await
         }
         Err(error) => {
@@ -322,11 +330,12 @@ async fn handle_request(
 
 async fn handle_batch_status(
     receiver: Option<BatchStatusReceiver>,
-) -> Result<impl warp::Reply, Rejection> {
+    response: Response,
+) -> Result<Response, Rejection> {
     match receiver {
-        None => Ok(warp::reply()),
+        None => Ok(response),
         Some(receiver) => match receiver.await {
-            BatchStatus::Delivered => Ok(warp::reply()),
+            BatchStatus::Delivered => Ok(response),
             BatchStatus::Errored => Err(warp::reject::custom(ErrorMessage::new(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Error delivering contents to sink".into(),
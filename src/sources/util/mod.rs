@@ -5,6 +5,8 @@ mod encoding_config;
 pub(crate) mod finalizer;
 #[cfg(all(unix, feature = "sources-dnstap"))]
 pub mod framestream;
+#[cfg(feature = "sources-utils-grpc")]
+mod grpc;
 #[cfg(feature = "sources-utils-http")]
 mod http;
 pub mod multiline_config;
@@ -16,7 +18,9 @@ mod unix_datagram;
 mod unix_stream;
 
 #[cfg(any(feature = "sources-http", feature = "sources-datadog"))]
-pub(crate) use self::body_decoding::{decode_body, Encoding};
+pub(crate) use self::body_decoding::{BodyDecoder, Encoding};
+#[cfg(feature = "sources-utils-grpc")]
+pub(crate) use self::grpc::{build_grpc_server, send_grpc_events, GrpcTlsConfig};
 #[cfg(any(feature = "sources-http", feature = "sources-heroku_logs"))]
 pub(crate) use self::http::add_query_parameters;
 #[cfg(feature = "sources-prometheus")]
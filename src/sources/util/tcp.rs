@@ -1,6 +1,6 @@
 use crate::{
     config::Resource,
-    event::Event,
+    event::{BatchNotifier, BatchStatus, Event},
     internal_events::{ConnectionOpen, OpenGauge, TcpSendAckError, TcpSocketConnectionError},
     shutdown::ShutdownSignal,
     tcp::TcpKeepaliveConfig,
@@ -8,11 +8,11 @@ use crate::{
     Pipeline,
 };
 use bytes::Bytes;
-use futures::{future::BoxFuture, FutureExt, Sink, SinkExt, StreamExt};
+use futures::{future::BoxFuture, stream, FutureExt, Sink, SinkExt, StreamExt};
 use listenfd::ListenFd;
 use serde::{de, Deserialize, Deserializer, Serialize};
 use socket2::SockRef;
-use std::{fmt, io, mem::drop, net::SocketAddr, time::Duration};
+use std::{fmt, io, mem::drop, net::SocketAddr, sync::Arc, time::Duration};
 use tokio::{
     io::AsyncWriteExt,
     net::{TcpListener, TcpStream},
@@ -80,7 +80,7 @@ where
 
     fn decoder(&self) -> Self::Decoder;
 
-    fn build_event(&self, frame: <Self::Decoder as Decoder>::Item, host: Bytes) -> Option<Event>;
+    fn build_events(&self, frame: <Self::Decoder as Decoder>::Item, host: Bytes) -> Vec<Event>;
 
     fn build_ack(&self, _frame: &<Self::Decoder as Decoder>::Item) -> Bytes {
         Bytes::new()
@@ -95,6 +95,7 @@ where
         receive_buffer_bytes: Option<usize>,
         shutdown_signal: ShutdownSignal,
         out: Pipeline,
+        acknowledgements: bool,
     ) -> crate::Result<crate::sources::Source> {
         let out = out.sink_map_err(|error| error!(message = "Error sending event.", %error));
 
@@ -175,6 +176,7 @@ where
                                 tripwire,
                                 host,
                                 out,
+                                acknowledgements,
                             );
 
                             tokio::spawn(
@@ -198,6 +200,7 @@ async fn handle_stream<T>(
     mut tripwire: BoxFuture<'static, ()>,
     host: Bytes,
     mut out: impl Sink<Event> + Send + 'static + Unpin,
+    acknowledgements: bool,
 ) where
     <<T as TcpSource>::Decoder as tokio_util::codec::Decoder>::Item: std::marker::Send,
     T: TcpSource,
@@ -253,20 +256,45 @@ async fn handle_stream<T>(
                         let host = host.clone();
                         let ack = source.build_ack(&frame);
 
-                        if let Some(event) = source.build_event(frame, host) {
-                            match out.send(event).await {
-                                Ok(_) => {
-                                    let stream = reader.get_mut();
-                                    if let Err(error) = stream.write_all(&ack).await {
-                                        emit!(TcpSendAckError{ error });
+                        let mut events = source.build_events(frame, host);
+                        if events.is_empty() {
+                            continue;
+                        }
+
+                        // The frame is only acknowledged to the client once
+                        // the sinks have delivered all of its events.
+                        let receiver = acknowledgements.then(|| {
+                            let (batch, receiver) = BatchNotifier::new_with_receiver();
+                            for event in &mut events {
+                                event.add_batch_notifier(Arc::clone(&batch));
+                            }
+                            receiver
+                        });
+
+                        let mut events = stream::iter(events.into_iter().map(Ok));
+                        match out.send_all(&mut events).await {
+                            Ok(_) => {
+                                if let Some(receiver) = receiver {
+                                    let status = tokio::select! {
+                                        status = receiver => status,
+                                        _ = &mut tripwire => break,
+                                    };
+                                    if status != BatchStatus::Delivered {
+                                        warn!(message = "Failed to deliver events, closing connection.", ?status);
                                         break;
                                     }
                                 }
-                                Err(_) => {
-                                    warn!("Failed to send event.");
+
+                                let stream = reader.get_mut();
+                                if let Err(error) = stream.write_all(&ack).await {
+                                    emit!(TcpSendAckError{ error });
                                     break;
                                 }
                             }
+                            Err(_) => {
+                                warn!("Failed to send event.");
+                                break;
+                            }
                         }
                     }
                     Some(Err(error)) => {
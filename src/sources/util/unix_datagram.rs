@@ -7,28 +7,31 @@ use crate::{
     Pipeline,
 };
 use bytes::{Bytes, BytesMut};
-use futures::SinkExt;
+use futures::{SinkExt, StreamExt};
 use std::{fs::remove_file, path::PathBuf};
 use tokio::net::UnixDatagram;
-use tokio_util::codec::Decoder;
+use tokio_util::codec::{Decoder, FramedRead};
 use tracing::field;
 
 /// Returns a Source object corresponding to a Unix domain datagram
-/// socket.  Passing in different functions for build_event can allow
+/// socket.  Passing in different functions for build_events can allow
 /// for different source-specific logic (such as decoding syslog
 /// messages in the syslog source).
-pub fn build_unix_datagram_source<D>(
+pub fn build_unix_datagram_source<D, E>(
     listen_path: PathBuf,
     max_length: usize,
     host_key: String,
     decoder: D,
     shutdown: ShutdownSignal,
     out: Pipeline,
-    build_event: impl Fn(&str, Option<Bytes>, &str) -> Option<Event> + Clone + Send + Sync + 'static,
+    build_events: impl Fn(&str, Option<Bytes>, D::Item) -> E + Clone + Send + Sync + 'static,
 ) -> Source
 where
-    D: Decoder<Item = String> + Clone + Send + 'static,
+    D: Decoder + Clone + Send + 'static,
+    D::Item: Send,
     D::Error: From<std::io::Error> + std::fmt::Debug + std::fmt::Display + Send,
+    E: IntoIterator<Item = Event>,
+    E::IntoIter: Send,
 {
     Box::pin(async move {
         let socket = UnixDatagram::bind(&listen_path).expect("Failed to bind to datagram socket");
@@ -41,7 +44,7 @@ where
             decoder,
             shutdown,
             out,
-            build_event,
+            build_events,
         )
         .await;
 
@@ -57,18 +60,21 @@ where
     })
 }
 
-async fn listen<D>(
+async fn listen<D, E>(
     socket: UnixDatagram,
     max_length: usize,
     host_key: String,
-    mut decoder: D,
+    decoder: D,
     mut shutdown: ShutdownSignal,
     out: Pipeline,
-    build_event: impl Fn(&str, Option<Bytes>, &str) -> Option<Event> + Clone + Send + Sync + 'static,
+    build_events: impl Fn(&str, Option<Bytes>, D::Item) -> E + Clone + Send + Sync + 'static,
 ) -> Result<(), ()>
 where
-    D: Decoder<Item = String> + Clone + Send + 'static,
+    D: Decoder + Clone + Send + 'static,
+    D::Item: Send,
     D::Error: From<std::io::Error> + std::fmt::Debug + std::fmt::Display + Send,
+    E: IntoIterator<Item = Event>,
+    E::IntoIter: Send,
 {
     let mut out = out.sink_map_err(|error| error!(message = "Error sending line.", %error));
     let mut buf = BytesMut::with_capacity(max_length);
@@ -80,7 +86,7 @@ where
                     emit!(SocketReceiveError { error, mode: SocketMode::Unix })
                 })?;
 
-                let mut payload = buf.split_to(byte_size);
+                let payload = buf.split_to(byte_size);
 
                 let span = info_span!("datagram");
                 let path = address.as_pathname().map(|e| e.to_owned()).map(|path| {
@@ -91,9 +97,19 @@ where
                 let received_from: Option<Bytes> =
                     path.map(|p| p.to_string_lossy().into_owned().into());
 
-                while let Ok(Some(line)) = decoder.decode_eof(&mut payload) {
-                    if let Some(event) = build_event(&host_key, received_from.clone(), &line) {
-                        out.send(event).await?;
+                let mut stream = FramedRead::new(payload.as_ref(), decoder.clone());
+
+                while let Some(frame) = stream.next().await {
+                    match frame {
+                        Ok(frame) => {
+                            for event in build_events(&host_key, received_from.clone(), frame) {
+                                out.send(event).await?;
+                            }
+                        }
+                        Err(error) => {
+                            debug!(message = "Failed decoding datagram.", %error);
+                            break;
+                        }
                     }
                 }
             }
@@ -8,8 +8,8 @@ use crate::{
     Pipeline,
 };
 use bytes::Bytes;
-use futures::{FutureExt, SinkExt, StreamExt};
-use std::{fs::remove_file, future::ready, path::PathBuf, time::Duration};
+use futures::{stream, FutureExt, SinkExt, StreamExt};
+use std::{fs::remove_file, path::PathBuf, time::Duration};
 use tokio::{
     io::AsyncWriteExt,
     net::{UnixListener, UnixStream},
@@ -21,20 +21,21 @@ use tracing::field;
 use tracing_futures::Instrument;
 
 /// Returns a Source object corresponding to a Unix domain stream
-/// socket.  Passing in different functions for build_event can allow
+/// socket.  Passing in different functions for build_events can allow
 /// for different source-specific logic (such as decoding syslog
 /// messages in the syslog source).
-pub fn build_unix_stream_source<D>(
+pub fn build_unix_stream_source<D, E>(
     listen_path: PathBuf,
     decoder: D,
     host_key: String,
     shutdown: ShutdownSignal,
     out: Pipeline,
-    build_event: impl Fn(&str, Option<Bytes>, &str) -> Option<Event> + Clone + Send + Sync + 'static,
+    build_events: impl Fn(&str, Option<Bytes>, D::Item) -> E + Clone + Send + Sync + 'static,
 ) -> Source
 where
-    D: Decoder<Item = String> + Clone + Send + 'static,
+    D: Decoder + Clone + Send + 'static,
     D::Error: From<std::io::Error> + std::fmt::Debug + std::fmt::Display,
+    E: IntoIterator<Item = Event>,
 {
     let out = out.sink_map_err(|error| error!(message = "Error sending line.", %error));
 
@@ -69,22 +70,26 @@ where
                 None
             };
 
-            let build_event = build_event.clone();
+            let build_events = build_events.clone();
             let received_from: Option<Bytes> =
                 path.map(|p| p.to_string_lossy().into_owned().into());
 
             let stream = socket.allow_read_until(shutdown.clone().map(|_| ()));
-            let mut stream = FramedRead::new(stream, decoder.clone()).filter_map(move |line| {
-                ready(match line {
-                    Ok(line) => build_event(&host_key, received_from.clone(), &line).map(Ok),
+            let mut stream = FramedRead::new(stream, decoder.clone()).flat_map(move |frame| {
+                let events: Vec<Result<Event, ()>> = match frame {
+                    Ok(frame) => build_events(&host_key, received_from.clone(), frame)
+                        .into_iter()
+                        .map(Ok)
+                        .collect(),
                     Err(error) => {
                         emit!(UnixSocketError {
                             error,
                             path: &listen_path
                         });
-                        None
+                        Vec::new()
                     }
-                })
+                };
+                stream::iter(events)
             });
 
             let connection_open = connection_open.clone();
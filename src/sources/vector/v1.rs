@@ -64,6 +64,7 @@ impl VectorConfig {
             self.receive_buffer_bytes,
             cx.shutdown,
             cx.out,
+            cx.acknowledgements,
         )
     }
 
@@ -91,16 +92,16 @@ impl TcpSource for VectorSource {
         LengthDelimitedCodec::new()
     }
 
-    fn build_event(&self, frame: BytesMut, _host: Bytes) -> Option<Event> {
+    fn build_events(&self, frame: BytesMut, _host: Bytes) -> Vec<Event> {
         let byte_size = frame.len();
         match proto::EventWrapper::decode(frame).map(Event::from) {
             Ok(event) => {
                 emit!(VectorEventReceived { byte_size });
-                Some(event)
+                vec![event]
             }
             Err(error) => {
                 emit!(VectorProtoDecodeError { error });
-                None
+                Vec::new()
             }
         }
     }
@@ -134,9 +135,9 @@ mod test {
 
     #[cfg(not(target_os = "windows"))]
     use {
-        crate::event::proto,
+        crate::event::{proto, EventStatus},
         bytes::BytesMut,
-        futures::SinkExt,
+        futures::{SinkExt, StreamExt},
         prost::Message,
         tokio_util::codec::{FramedWrite, LengthDelimitedCodec},
     };
@@ -256,7 +257,7 @@ mod test {
     #[cfg(not(target_os = "windows"))]
     async fn it_processes_stream_of_protobufs() {
         trace_init();
-        let (tx, rx) = Pipeline::new_test();
+        let (tx, rx) = Pipeline::new_test_finalize(EventStatus::Delivered);
         let addr = next_addr();
 
         let config = VectorConfig::from_address(addr.into());
@@ -274,6 +275,9 @@ mod test {
             .await
             .unwrap();
         tokio::spawn(server);
+        // The frame is only acknowledged once its events are delivered, so
+        // they have to be consumed while the connection is open.
+        let output = tokio::spawn(rx.take(1).collect::<Vec<_>>());
 
         let event = proto::EventWrapper::from(Event::from("short"));
         let event_len = event.encoded_len();
@@ -295,7 +299,7 @@ mod test {
         drop(trigger_shutdown);
         shutdown_down.await;
 
-        let output = collect_ready(rx).await;
+        let output = output.await.unwrap();
         assert_event_data_eq!([Event::from(event)][..], output.as_slice());
     }
 }
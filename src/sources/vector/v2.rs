@@ -3,20 +3,18 @@ use crate::{
     config::{DataType, GenerateConfig, Resource},
     proto::vector as proto,
     shutdown::ShutdownSignalToken,
-    sources::Source,
+    sources::{
+        util::{build_grpc_server, send_grpc_events, GrpcTlsConfig},
+        Source,
+    },
     Pipeline,
 };
 
-use futures::{FutureExt, SinkExt, StreamExt, TryFutureExt};
+use futures::{FutureExt, TryFutureExt};
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
-use std::path::PathBuf;
-use std::sync::Arc;
-use tonic::{
-    transport::{Certificate, Identity, Server, ServerTlsConfig},
-    Request, Response, Status,
-};
-use vector_core::event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event};
+use tonic::{Request, Response, Status};
+use vector_core::event::Event;
 
 #[derive(Debug, Clone)]
 pub struct Service {
@@ -30,28 +28,14 @@ impl proto::Service for Service {
         &self,
         request: Request<proto::PushEventsRequest>,
     ) -> Result<Response<proto::PushEventsResponse>, Status> {
-        let mut events: Vec<Event> = request
+        let events: Vec<Event> = request
             .into_inner()
             .events
             .into_iter()
             .map(Event::from)
             .collect();
 
-        let receiver = self.acknowledgements.then(|| {
-            let (batch, receiver) = BatchNotifier::new_with_receiver();
-            for event in &mut events {
-                event.add_batch_notifier(Arc::clone(&batch));
-            }
-
-            receiver
-        });
-
-        self.pipeline
-            .clone()
-            .send_all(&mut futures::stream::iter(events).map(Ok))
-            .map_err(|err| Status::unavailable(err.to_string()))
-            .and_then(|_| handle_batch_status(receiver))
-            .await?;
+        send_grpc_events(&self.pipeline, self.acknowledgements, events).await?;
 
         Ok(Response::new(proto::PushEventsResponse {}))
     }
@@ -69,19 +53,6 @@ impl proto::Service for Service {
     }
 }
 
-async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), Status> {
-    let status = match receiver {
-        Some(receiver) => receiver.await,
-        None => BatchStatus::Delivered,
-    };
-
-    match status {
-        BatchStatus::Errored => Err(Status::internal("Delivery error")),
-        BatchStatus::Failed => Err(Status::data_loss("Delivery failed")),
-        BatchStatus::Delivered => Ok(()),
-    }
-}
-
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct VectorConfig {
@@ -92,14 +63,6 @@ pub struct VectorConfig {
     pub tls: Option<GrpcTlsConfig>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
-#[serde(deny_unknown_fields)]
-pub struct GrpcTlsConfig {
-    ca_file: PathBuf,
-    crt_file: PathBuf,
-    key_file: PathBuf,
-}
-
 fn default_shutdown_timeout_secs() -> u64 {
     30
 }
@@ -150,21 +113,8 @@ async fn run(
     });
     let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
 
-    let mut server = match tls {
-        Some(tls) => {
-            let ca = Certificate::from_pem(tokio::fs::read(&tls.ca_file).await?);
-            let crt = tokio::fs::read(&tls.crt_file).await?;
-            let key = tokio::fs::read(&tls.key_file).await?;
-            let identity = Identity::from_pem(crt, key);
-
-            let tls_config = ServerTlsConfig::new().identity(identity).client_ca_root(ca);
-
-            Server::builder().tls_config(tls_config)?
-        }
-        None => Server::builder(),
-    };
-
-    server
+    build_grpc_server(tls.as_ref())
+        .await?
         .add_service(service)
         .serve_with_shutdown(address, cx.shutdown.map(|token| tx.send(token).unwrap()))
         .await?;
@@ -5,22 +5,32 @@ use super::{
 };
 use crate::{
     buffers,
-    config::{DataType, SinkContext, SourceContext},
-    event::Event,
+    config::{
+        dropped_output, named_output, DataType, SinkContext, SourceContext, TransformContext,
+    },
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, EventFinalizers, EventStatus},
     internal_events::{EventIn, EventOut, EventZeroIn},
     shutdown::SourceShutdownCoordinator,
-    transforms::Transform,
+    transforms::{annotate_dropped, FunctionTransform, Transform, TransformOutputs},
     Pipeline,
 };
-use futures::{future, stream, FutureExt, SinkExt, StreamExt, TryFutureExt};
+use futures::{future, stream, FutureExt, SinkExt, Stream, StreamExt, TryFutureExt};
 use std::pin::Pin;
 use std::{
     collections::HashMap,
-    future::ready,
+    future::{ready, Future},
     sync::{Arc, Mutex},
 };
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
-use tokio::time::{timeout, Duration};
+use tokio::{
+    sync::mpsc,
+    time::{timeout, Duration},
+};
+use tokio_stream::wrappers::ReceiverStream;
+
+/// The number of events a sink rerouting dropped events may hold without
+/// having reported their status.
+const MAX_TRACKED_EVENTS: usize = 10_000;
 
 pub struct Pieces {
     pub inputs: HashMap<String, (buffers::BufferInputCloner<Event>, Vec<String>)>,
@@ -30,13 +40,21 @@ pub struct Pieces {
     pub healthchecks: HashMap<String, Task>,
     pub shutdown_coordinator: SourceShutdownCoordinator,
     pub detach_triggers: HashMap<String, Trigger>,
+    /// The full names of the named outputs of each component that has any.
+    pub named_outputs: HashMap<String, Vec<String>>,
+    /// The enrichment tables the transforms were built against.
+    pub enrichment_tables: enrichment::TableRegistry,
 }
 
 /// Builds only the new pieces, and doesn't check their topology.
+///
+/// The enrichment tables are loaded into `enrichment_tables`, which is shared
+/// with the transforms that are already running when reloading a config.
 pub async fn build_pieces(
     config: &super::Config,
     diff: &ConfigDiff,
     mut buffers: HashMap<String, BuiltBuffer>,
+    enrichment_tables: &enrichment::TableRegistry,
 ) -> Result<Pieces, Vec<String>> {
     let mut inputs = HashMap::new();
     let mut outputs = HashMap::new();
@@ -45,9 +63,25 @@ pub async fn build_pieces(
     let mut healthchecks = HashMap::new();
     let mut shutdown_coordinator = SourceShutdownCoordinator::default();
     let mut detach_triggers = HashMap::new();
+    let mut named_outputs = HashMap::new();
 
     let mut errors = vec![];
 
+    // Load enrichment tables, which are reloaded along with every config so
+    // that changes to their files are picked up.
+    let mut tables = enrichment::tables::TableMap::new();
+    for (name, table) in config.enrichment_tables.iter() {
+        match table.inner.build(&config.global).await {
+            Err(error) => {
+                errors.push(format!("Enrichment Table \"{}\": {}", name, error));
+            }
+            Ok(table) => {
+                tables.insert(name.clone(), table);
+            }
+        }
+    }
+    enrichment_tables.load(tables);
+
     // Build sources
     for (name, source) in config
         .sources
@@ -66,7 +100,7 @@ pub async fn build_pieces(
             globals: config.global.clone(),
             shutdown: shutdown_signal,
             out: pipeline,
-            acknowledgements: source.acknowledgements,
+            acknowledgements: source.acknowledgements(&config.global),
         };
         let server = match source.inner.build(context).await {
             Err(error) => {
@@ -111,9 +145,15 @@ pub async fn build_pieces(
         let trans_inputs = &transform.inputs;
 
         let typetag = transform.inner.transform_type();
+        let transform_outputs = transform.inner.named_outputs();
 
         let input_type = transform.inner.input_type();
-        let transform = match transform.inner.build(&config.global).await {
+        let context = TransformContext {
+            globals: config.global.clone(),
+            enrichment_tables: enrichment_tables.clone(),
+        };
+
+        let transform = match transform.inner.build(&context).await {
             Err(error) => {
                 errors.push(format!("Transform \"{}\": {}", name, error));
                 continue;
@@ -121,6 +161,14 @@ pub async fn build_pieces(
             Ok(transform) => transform,
         };
 
+        if !transform_outputs.is_empty() && matches!(transform, Transform::Task(_)) {
+            errors.push(format!(
+                "Transform \"{}\": Named outputs are only supported by function transforms.",
+                name
+            ));
+            continue;
+        }
+
         let (input_tx, input_rx, _) =
             vector_core::buffers::build(vector_core::buffers::Variant::Memory {
                 max_events: 100,
@@ -130,8 +178,27 @@ pub async fn build_pieces(
         let input_rx = crate::utilization::wrap(Pin::new(input_rx));
 
         let (output, control) = Fanout::new();
+        let named = transform_outputs
+            .into_iter()
+            .map(|output| {
+                let (fanout, control) = Fanout::new();
+                let output_name = named_output(name, &output);
+                outputs.insert(output_name.clone(), control);
+                named_outputs
+                    .entry(name.clone())
+                    .or_insert_with(Vec::new)
+                    .push(output_name);
+                (output, fanout)
+            })
+            .collect::<Vec<_>>();
 
         let transform = match transform {
+            Transform::Function(t) if !named.is_empty() => {
+                let filtered = input_rx
+                    .filter(move |event| ready(filter_event_type(event, input_type)))
+                    .inspect(|_| emit!(EventIn));
+                run_with_outputs(t, filtered, output, named).boxed()
+            }
             Transform::Function(mut t) => input_rx
                 .filter(move |event| ready(filter_event_type(event, input_type)))
                 .inspect(|_| emit!(EventIn))
@@ -179,6 +246,14 @@ pub async fn build_pieces(
 
         let typetag = sink.inner.sink_type();
         let input_type = sink.inner.input_type();
+        let dropped = if sink.reroute_dropped {
+            let (dropped, control) = Fanout::new();
+            outputs.insert(dropped_output(name), control);
+            named_outputs.insert(name.clone(), vec![dropped_output(name)]);
+            Some(dropped)
+        } else {
+            None
+        };
 
         let (tx, rx, acker) = if let Some(buffer) = buffers.remove(name) {
             buffer
@@ -224,14 +299,20 @@ pub async fn build_pieces(
 
             let mut rx = Box::pin(crate::utilization::wrap(rx));
 
-            sink.run(
-                rx.by_ref()
-                    .filter(|event| ready(filter_event_type(event, input_type)))
-                    .inspect(|_| emit!(EventIn))
-                    .take_until_if(tripwire),
-            )
-            .await
-            .map(|_| {
+            let events = rx
+                .by_ref()
+                .filter(|event| ready(filter_event_type(event, input_type)))
+                .inspect(|_| emit!(EventIn))
+                .take_until_if(tripwire);
+            let result = match dropped {
+                Some(dropped) => {
+                    let (events, rejected) = track_dropped(events, dropped);
+                    let (result, _) = future::join(sink.run(events), rejected).await;
+                    result
+                }
+                None => sink.run(events).await,
+            };
+            result.map(|_| {
                 debug!("Finished.");
                 TaskOutput::Sink(rx, acker)
             })
@@ -283,6 +364,10 @@ pub async fn build_pieces(
     }
 
     if errors.is_empty() {
+        // The transforms have added the indexes they need, so the tables can
+        // now replace the ones searched by running transforms.
+        enrichment_tables.finish_load();
+
         let pieces = Pieces {
             inputs,
             outputs,
@@ -291,6 +376,8 @@ pub async fn build_pieces(
             healthchecks,
             shutdown_coordinator,
             detach_triggers,
+            named_outputs,
+            enrichment_tables: enrichment_tables.clone(),
         };
 
         Ok(pieces)
@@ -299,10 +386,113 @@ pub async fn build_pieces(
     }
 }
 
+/// Runs a function transform that declares named outputs, sending the events
+/// it writes to each of them to the matching fanout.
+async fn run_with_outputs(
+    mut transform: Box<dyn FunctionTransform>,
+    input: impl Stream<Item = Event>,
+    mut output: Fanout,
+    mut named: Vec<(String, Fanout)>,
+) -> Result<(), ()> {
+    let mut input = Box::pin(input);
+    let mut buf = TransformOutputs::new(named.iter().map(|(name, _)| name.clone()));
+    while let Some(event) = input.next().await {
+        transform.transform_outputs(&mut buf, event);
+        emit!(EventOut { count: buf.len() });
+        output
+            .send_all(&mut stream::iter(buf.drain()).map(Ok))
+            .await?;
+        for (name, fanout) in named.iter_mut() {
+            if !buf.named_mut(name).is_empty() {
+                fanout
+                    .send_all(&mut stream::iter(buf.drain_named(name)).map(Ok))
+                    .await?;
+            }
+        }
+    }
+    output.close().await?;
+    for (_, fanout) in named.iter_mut() {
+        fanout.close().await?;
+    }
+    Ok(())
+}
+
+/// Tracks the delivery of each event sent to a sink that reroutes dropped
+/// events, sending the events the sink rejects to `dropped`.
+///
+/// Every event gets its own batch notifier, and the finalizers it came with
+/// are held back until the sink reports its status. Rejected events take
+/// them along to the `dropped` output, so their sources are told whether
+/// delivering them there succeeded instead. This keeps a copy of every event
+/// the sink hasn't finalized yet, so at most `MAX_TRACKED_EVENTS` are waited
+/// on at once before the sink's input waits too.
+///
+/// The returned future forwards the rejected events and must be polled
+/// alongside the sink, finishing once the sink has finalized every event.
+fn track_dropped(
+    events: impl Stream<Item = Event>,
+    dropped: Fanout,
+) -> (
+    impl Stream<Item = Event>,
+    impl Future<Output = Result<(), ()>>,
+) {
+    let (tx, rx) =
+        mpsc::channel::<(BatchStatusReceiver, Event, EventFinalizers)>(MAX_TRACKED_EVENTS);
+
+    let rejected = ReceiverStream::new(rx)
+        .map(|(receiver, event, finalizers)| rejected_event(receiver, event, finalizers))
+        .buffer_unordered(MAX_TRACKED_EVENTS)
+        .filter_map(ready)
+        .map(Ok)
+        .forward(dropped);
+
+    let events = events.then(move |mut event| {
+        let tx = tx.clone();
+        async move {
+            let finalizers = event.metadata_mut().take_finalizers();
+            let rejected = event.clone();
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            event.add_batch_notifier(batch);
+            // This only fails once the sink's dropped output has shut down.
+            let _ = tx.send((receiver, rejected, finalizers)).await;
+            event
+        }
+    });
+
+    (events, rejected)
+}
+
+/// Waits for a sink to report the status of an event, returning its copy if
+/// the sink rejected it.
+async fn rejected_event(
+    receiver: BatchStatusReceiver,
+    mut event: Event,
+    finalizers: EventFinalizers,
+) -> Option<Event> {
+    match receiver.await {
+        BatchStatus::Delivered => {
+            finalizers.update_status(EventStatus::Delivered);
+            None
+        }
+        BatchStatus::Errored => {
+            finalizers.update_status(EventStatus::Errored);
+            None
+        }
+        BatchStatus::Failed => {
+            event.metadata_mut().merge_finalizers(finalizers);
+            annotate_dropped(&mut event, "rejected", "The sink rejected the event.");
+            Some(event)
+        }
+    }
+}
+
+/// Components only receive the events matching their input type, as typecheck
+/// allows `Any` outputs, which may carry traces, into `Log` or `Metric` inputs.
 fn filter_event_type(event: &Event, data_type: DataType) -> bool {
     match data_type {
         DataType::Any => true,
         DataType::Log => matches!(event, Event::Log(_)),
         DataType::Metric => matches!(event, Event::Metric(_)),
+        DataType::Trace => matches!(event, Event::Trace(_)),
     }
 }
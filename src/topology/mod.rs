@@ -54,6 +54,7 @@ pub type WatchRx = watch::Receiver<Outputs>;
 pub struct RunningTopology {
     inputs: HashMap<String, buffers::BufferInputCloner<Event>>,
     outputs: HashMap<String, fanout::ControlChannel>,
+    named_outputs: HashMap<String, Vec<String>>,
     source_tasks: HashMap<String, TaskHandle>,
     tasks: HashMap<String, TaskHandle>,
     shutdown_coordinator: SourceShutdownCoordinator,
@@ -61,6 +62,7 @@ pub struct RunningTopology {
     config: Config,
     abort_tx: mpsc::UnboundedSender<()>,
     watch: (WatchTx, WatchRx),
+    enrichment_tables: enrichment::TableRegistry,
 }
 
 pub async fn start_validated(
@@ -73,6 +75,7 @@ pub async fn start_validated(
     let mut running_topology = RunningTopology {
         inputs: HashMap::new(),
         outputs: HashMap::new(),
+        named_outputs: HashMap::new(),
         config,
         shutdown_coordinator: SourceShutdownCoordinator::default(),
         detach_triggers: HashMap::new(),
@@ -80,6 +83,7 @@ pub async fn start_validated(
         tasks: HashMap::new(),
         abort_tx,
         watch: watch::channel(HashMap::new()),
+        enrichment_tables: pieces.enrichment_tables.clone(),
     };
 
     if !running_topology
@@ -94,12 +98,22 @@ pub async fn start_validated(
     Some((running_topology, abort_rx))
 }
 
+/// Builds the pieces of a new topology, which gets its own enrichment tables.
 pub async fn build_or_log_errors(
     config: &Config,
     diff: &ConfigDiff,
     buffers: HashMap<String, BuiltBuffer>,
 ) -> Option<Pieces> {
-    match builder::build_pieces(config, diff, buffers).await {
+    build_with_tables_or_log_errors(config, diff, buffers, &Default::default()).await
+}
+
+async fn build_with_tables_or_log_errors(
+    config: &Config,
+    diff: &ConfigDiff,
+    buffers: HashMap<String, BuiltBuffer>,
+    enrichment_tables: &enrichment::TableRegistry,
+) -> Option<Pieces> {
+    match builder::build_pieces(config, diff, buffers, enrichment_tables).await {
         Err(errors) => {
             for error in errors {
                 error!(message = "Configuration error.", %error);
@@ -239,7 +253,13 @@ impl RunningTopology {
         }
 
         // Now let's actually build the new pieces.
-        if let Some(mut new_pieces) = build_or_log_errors(&new_config, &diff, buffers.clone()).await
+        if let Some(mut new_pieces) = build_with_tables_or_log_errors(
+            &new_config,
+            &diff,
+            buffers.clone(),
+            &self.enrichment_tables,
+        )
+        .await
         {
             if self
                 .run_healthchecks(&diff, &mut new_pieces, new_config.healthchecks)
@@ -256,7 +276,10 @@ impl RunningTopology {
         // We need to rebuild the removed.
         info!("Rebuilding old configuration.");
         let diff = diff.flip();
-        if let Some(mut new_pieces) = build_or_log_errors(&self.config, &diff, buffers).await {
+        if let Some(mut new_pieces) =
+            build_with_tables_or_log_errors(&self.config, &diff, buffers, &self.enrichment_tables)
+                .await
+        {
             if self
                 .run_healthchecks(&diff, &mut new_pieces, self.config.healthchecks)
                 .await
@@ -429,6 +452,7 @@ impl RunningTopology {
         for name in &diff.sinks.to_remove {
             info!(message = "Removing sink.", name = ?name);
             self.remove_inputs(name).await;
+            self.remove_outputs(name);
         }
 
         // Detach changed sinks
@@ -494,6 +518,24 @@ impl RunningTopology {
             self.setup_outputs(name, new_pieces).await;
         }
 
+        // Named outputs
+        // Like the transform outputs, these have to be set up before they can be used as inputs.
+        for name in diff
+            .transforms
+            .changed_and_added()
+            .chain(diff.sinks.changed_and_added())
+        {
+            for output in self.named_outputs.remove(name).unwrap_or_default() {
+                self.outputs.remove(&output);
+            }
+            if let Some(outputs) = new_pieces.named_outputs.remove(name) {
+                for output in &outputs {
+                    self.setup_outputs(output, new_pieces).await;
+                }
+                self.named_outputs.insert(name.clone(), outputs);
+            }
+        }
+
         for name in &diff.transforms.to_change {
             self.replace_inputs(name, new_pieces).await;
         }
@@ -611,6 +653,9 @@ impl RunningTopology {
 
     fn remove_outputs(&mut self, name: &str) {
         self.outputs.remove(name);
+        for output in self.named_outputs.remove(name).unwrap_or_default() {
+            self.outputs.remove(&output);
+        }
     }
 
     async fn remove_inputs(&mut self, name: &str) {
@@ -848,10 +893,10 @@ mod tests {
     feature = "sinks-prometheus",
     feature = "transforms-log_to_metric",
     feature = "sinks-socket",
-    feature = "leveldb"
+    feature = "disk-buffer"
 ))]
 mod reload_tests {
-    use crate::buffers::{BufferConfig, WhenFull};
+    use crate::buffers::{BufferConfig, BufferType, WhenFull};
     use crate::config::Config;
     use crate::sinks::console::{ConsoleSinkConfig, Encoding, Target};
     use crate::sinks::prometheus::exporter::PrometheusExporterConfig;
@@ -1094,9 +1139,11 @@ mod reload_tests {
                 ..PrometheusExporterConfig::default()
             },
         );
-        old_config.sinks["out"].buffer = BufferConfig::Disk {
-            max_size: 1024,
-            when_full: WhenFull::Block,
+        old_config.sinks["out"].buffer = BufferConfig {
+            stages: vec![BufferType::Disk {
+                max_size: 1024,
+                when_full: WhenFull::Block,
+            }],
         };
 
         let mut new_config = old_config.clone();
@@ -1105,9 +1152,11 @@ mod reload_tests {
             flush_period_secs: 1,
             ..PrometheusExporterConfig::default()
         });
-        new_config.sinks["out"].buffer = BufferConfig::Disk {
-            max_size: 2048,
-            when_full: WhenFull::Block,
+        new_config.sinks["out"].buffer = BufferConfig {
+            stages: vec![BufferType::Disk {
+                max_size: 2048,
+                when_full: WhenFull::Block,
+            }],
         };
 
         reload_sink_test(
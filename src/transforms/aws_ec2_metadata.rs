@@ -1,5 +1,5 @@
 use crate::{
-    config::{DataType, GlobalOptions, TransformConfig, TransformDescription},
+    config::{DataType, TransformConfig, TransformContext, TransformDescription},
     event::Event,
     http::HttpClient,
     internal_events::{AwsEc2MetadataRefreshFailed, AwsEc2MetadataRefreshSuccessful},
@@ -110,7 +110,7 @@ impl_generate_config_from_default!(Ec2Metadata);
 #[async_trait::async_trait]
 #[typetag::serde(name = "aws_ec2_metadata")]
 impl TransformConfig for Ec2Metadata {
-    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
         let (read, write) = evmap::new();
 
         // Check if the namespace is set to `""` which should mean that we do
@@ -203,6 +203,13 @@ impl Ec2MetadataTransform {
                         }
                     });
                 }
+                Event::Trace(ref mut trace) => {
+                    read_ref.into_iter().for_each(|(k, v)| {
+                        if let Some(value) = v.get_one() {
+                            trace.insert(k.clone(), value.clone());
+                        }
+                    });
+                }
             }
         }
 
@@ -522,7 +529,7 @@ enum Ec2MetadataError {
 mod integration_tests {
     use super::*;
     use crate::{
-        config::GlobalOptions, event::metric, event::LogEvent, event::Metric,
+        config::TransformContext, event::metric, event::LogEvent, event::Metric,
         test_util::trace_init, transforms::TaskTransform,
     };
     use futures::{SinkExt, StreamExt};
@@ -553,7 +560,7 @@ mod integration_tests {
 
     async fn make_transform(config: Ec2Metadata) -> Box<dyn TaskTransform> {
         config
-            .build(&GlobalOptions::default())
+            .build(&TransformContext::default())
             .await
             .unwrap()
             .into_task()
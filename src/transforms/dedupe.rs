@@ -1,16 +1,33 @@
 use crate::{
     config::{
-        log_schema, DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription,
+        log_schema, DataType, GenerateConfig, Resource, TransformConfig, TransformContext,
+        TransformDescription,
     },
     event::{Event, Value},
-    internal_events::DedupeEventDiscarded,
+    expiring_hash_map::ExpiringHashMap,
+    internal_events::{
+        DedupeCacheLoadFailed, DedupeCacheLoaded, DedupeCachePersistFailed, DedupeEventDiscarded,
+    },
     transforms::{TaskTransform, Transform},
 };
+use async_stream::stream;
 use bytes::Bytes;
-use futures::{Stream, StreamExt};
+use chrono::{DateTime, Utc};
+use futures::{FutureExt, Stream, StreamExt};
 use lru::LruCache;
 use serde::{Deserialize, Serialize};
-use std::{future::ready, pin::Pin};
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    path::{is_separator, Path, PathBuf},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+use tokio::{sync::mpsc, task::JoinHandle};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
@@ -24,7 +41,27 @@ pub enum FieldMatchConfig {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct CacheConfig {
+    #[serde(default = "default_num_events")]
     pub num_events: usize,
+    /// When set, events are only considered duplicates of events seen within
+    /// the last `max_age_secs` seconds, up to a year. `num_events` still
+    /// bounds the cache.
+    #[serde(default)]
+    pub max_age_secs: Option<u64>,
+    #[serde(default)]
+    pub persistence: Option<PersistenceConfig>,
+}
+
+/// Persists the cache contents to the data directory periodically and when
+/// the transform stops, and restores them when it starts.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PersistenceConfig {
+    /// Identifies this cache within the data directory. Must be unique among
+    /// all `dedupe` transforms sharing a data directory, and must not contain
+    /// path separators.
+    pub key: String,
+    pub data_dir: Option<PathBuf>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -36,8 +73,16 @@ pub struct DedupeConfig {
     pub cache: CacheConfig,
 }
 
+const fn default_num_events() -> usize {
+    5000
+}
+
 fn default_cache_config() -> CacheConfig {
-    CacheConfig { num_events: 5000 }
+    CacheConfig {
+        num_events: default_num_events(),
+        max_age_secs: None,
+        persistence: None,
+    }
 }
 
 impl DedupeConfig {
@@ -59,7 +104,14 @@ impl DedupeConfig {
 
 pub struct Dedupe {
     fields: FieldMatchConfig,
-    cache: LruCache<CacheEntry, bool>,
+    cache: Cache,
+    persist_path: Option<PathBuf>,
+    /// Whether the cache changed since it was last persisted.
+    dirty: bool,
+    /// The snapshot being written in the background, if any.
+    pending_persist: Option<JoinHandle<()>>,
+    /// Serializes reads and writes of the persisted cache.
+    io_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 inventory::submit! {
@@ -79,8 +131,40 @@ impl GenerateConfig for DedupeConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "dedupe")]
 impl TransformConfig for DedupeConfig {
-    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
-        Ok(Transform::task(Dedupe::new(self.clone())))
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        if let Some(max_age_secs) = self.cache.max_age_secs {
+            if Duration::from_secs(max_age_secs) > MAX_AGE {
+                return Err(format!(
+                    "`cache.max_age_secs` must be at most {} seconds.",
+                    MAX_AGE.as_secs()
+                )
+                .into());
+            }
+        }
+
+        let mut dedupe = Dedupe::new(self.clone());
+
+        if let Some(persistence) = &self.cache.persistence {
+            let key = &persistence.key;
+            if key.is_empty() || key == "." || key.contains("..") || key.contains(is_separator) {
+                return Err(format!(
+                    "`cache.persistence.key` must be a plain directory name, found {:?}.",
+                    key
+                )
+                .into());
+            }
+
+            // The cache is restored once the transform starts. On reload the
+            // transform it replaces hands its final snapshot over, see
+            // `PersistedCache`.
+            let path = context
+                .globals
+                .resolve_and_make_data_subdir(persistence.data_dir.as_ref(), &persistence.key)?
+                .join(PERSISTED_CACHE_FILE);
+            dedupe.persist_path = Some(path);
+        }
+
+        Ok(Transform::task(dedupe))
     }
 
     fn input_type(&self) -> DataType {
@@ -94,6 +178,81 @@ impl TransformConfig for DedupeConfig {
     fn transform_type(&self) -> &'static str {
         "dedupe"
     }
+
+    fn resources(&self) -> Vec<Resource> {
+        self.cache
+            .persistence
+            .iter()
+            .map(|persistence| {
+                Resource::DataSubdir(persistence.data_dir.clone(), persistence.key.clone())
+            })
+            .collect()
+    }
+}
+
+const PERSISTED_CACHE_FILE: &str = "dedupe_cache.json";
+
+/// How often a changed cache is written to the data directory.
+const PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The longest supported `max_age_secs`, well within what the timers
+/// expiring the entries can handle.
+const MAX_AGE: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+lazy_static::lazy_static! {
+    static ref PERSISTED_CACHES: Mutex<HashMap<PathBuf, PersistedCache>> =
+        Mutex::new(HashMap::new());
+}
+
+static NEXT_INSTANCE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The transforms persisting their cache to the same file, which only
+/// overlap on reload.
+#[derive(Default)]
+struct PersistedCache {
+    /// Held while the file is read or written, so a snapshot is never
+    /// restored while it's being replaced.
+    io_lock: Arc<tokio::sync::Mutex<()>>,
+    /// The last transform started, to which the transforms it replaces hand
+    /// their final snapshot over instead of writing it. That way it isn't
+    /// missing the entries they saw after it restored the file.
+    latest: Option<(u64, mpsc::UnboundedSender<Vec<PersistedEntry>>)>,
+}
+
+/// Registers a transform persisting its cache at `path` as the latest one,
+/// returning its id and the receiver of the snapshots handed over to it.
+fn register_persisted_cache(
+    path: &Path,
+) -> (
+    u64,
+    Arc<tokio::sync::Mutex<()>>,
+    mpsc::UnboundedReceiver<Vec<PersistedEntry>>,
+) {
+    let id = NEXT_INSTANCE_ID.fetch_add(1, Ordering::Relaxed);
+    let (tx, rx) = mpsc::unbounded_channel();
+    let mut caches = PERSISTED_CACHES.lock().unwrap();
+    let cache = caches.entry(path.to_path_buf()).or_default();
+    cache.latest = Some((id, tx));
+    (id, Arc::clone(&cache.io_lock), rx)
+}
+
+/// Hands the final `snapshot` of the transform `id` over to the transform
+/// that replaced it, if any. Otherwise it's given back to be written.
+fn hand_over(
+    path: &Path,
+    id: u64,
+    snapshot: Vec<PersistedEntry>,
+) -> Result<(), Vec<PersistedEntry>> {
+    let mut caches = PERSISTED_CACHES.lock().unwrap();
+    let cache = caches.entry(path.to_path_buf()).or_default();
+    match cache.latest.take() {
+        Some((latest_id, tx)) if latest_id != id => {
+            let result = tx.send(snapshot).map_err(|error| error.0);
+            cache.latest = Some((latest_id, tx));
+            result
+        }
+        _ => Err(snapshot),
+    }
 }
 
 type TypeId = u8;
@@ -121,7 +280,7 @@ type TypeId = u8;
 /// iterating over the fields of the incoming Events, we know that the
 /// CacheEntries for 2 equivalent events will always contain the fields in the
 /// same order.
-#[derive(PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 enum CacheEntry {
     Match(Vec<Option<(TypeId, Bytes)>>),
     Ignore(Vec<(String, TypeId, Bytes)>),
@@ -141,25 +300,274 @@ fn type_id_for_value(val: &Value) -> TypeId {
     }
 }
 
+/// The set of recently seen entries, bounded either by count alone or by
+/// both count and age.
+enum Cache {
+    Lru(LruCache<CacheEntry, bool>),
+    Expiring {
+        entries: ExpiringHashMap<CacheEntry, DateTime<Utc>>,
+        /// The cached entries from the first to expire to the last one.
+        order: VecDeque<CacheEntry>,
+        max_age: Duration,
+        capacity: usize,
+    },
+}
+
+impl Cache {
+    /// Records `entry` in the cache, returning `true` if it was already
+    /// present.
+    fn insert(&mut self, entry: CacheEntry) -> bool {
+        match self {
+            Cache::Lru(cache) => cache.put(entry, true).is_some(),
+            Cache::Expiring {
+                entries,
+                order,
+                max_age,
+                capacity,
+            } => {
+                // The original deadline is kept for duplicates, so an entry
+                // expires `max_age` after it was first seen.
+                if entries.get(&entry).is_some() {
+                    return true;
+                }
+                // Once full, the entry closest to expiring makes room.
+                if entries.len() >= *capacity {
+                    if let Some(oldest) = order.pop_front() {
+                        entries.remove(&oldest);
+                    }
+                }
+                let expires_at = Utc::now() + max_age_duration(*max_age);
+                order.push_back(entry.clone());
+                entries.insert(entry, expires_at, *max_age);
+                false
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Cache::Lru(cache) => cache.len(),
+            Cache::Expiring { entries, .. } => entries.len(),
+        }
+    }
+
+    fn has_expiring_entries(&self) -> bool {
+        match self {
+            Cache::Lru(_) => false,
+            Cache::Expiring { entries, .. } => !entries.is_empty(),
+        }
+    }
+
+    /// Waits for the next entry to expire and removes it. Must only be polled
+    /// when `has_expiring_entries` returns `true`.
+    async fn next_expired(&mut self) {
+        if let Cache::Expiring { entries, order, .. } = self {
+            match entries.next_expired().await {
+                Some(Ok((_, expired))) => {
+                    let expired = expired.get_ref();
+                    if order.front() == Some(expired) {
+                        order.pop_front();
+                    } else {
+                        order.retain(|entry| entry != expired);
+                    }
+                }
+                Some(Err(error)) => {
+                    error!(message = "An error occurred while expiring a cache entry.", %error);
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Returns the cached entries ordered from least to most recently seen.
+    fn snapshot(&mut self) -> Vec<PersistedEntry> {
+        match self {
+            Cache::Lru(cache) => cache
+                .iter()
+                .rev()
+                .map(|(entry, _)| PersistedEntry {
+                    entry: entry.clone(),
+                    expires_at: None,
+                })
+                .collect(),
+            Cache::Expiring { entries, .. } => {
+                let mut snapshot = entries
+                    .iter_mut()
+                    .map(|(entry, expires_at)| PersistedEntry {
+                        entry: entry.clone(),
+                        expires_at: Some(*expires_at),
+                    })
+                    .collect::<Vec<_>>();
+                snapshot.sort_by_key(|persisted| persisted.expires_at);
+                snapshot
+            }
+        }
+    }
+
+    fn restore(&mut self, persisted: Vec<PersistedEntry>) {
+        let now = Utc::now();
+        for PersistedEntry { entry, expires_at } in persisted {
+            match self {
+                Cache::Lru(cache) => {
+                    if expires_at.map_or(true, |expires_at| expires_at > now)
+                        && !cache.contains(&entry)
+                    {
+                        cache.put(entry, true);
+                    }
+                }
+                Cache::Expiring {
+                    entries,
+                    order,
+                    max_age,
+                    capacity,
+                } => {
+                    if entries.get(&entry).is_some() {
+                        continue;
+                    }
+                    // The persisted entries are ordered from the first to
+                    // expire, so the latest ones are kept if they don't fit.
+                    if entries.len() >= *capacity {
+                        if let Some(oldest) = order.pop_front() {
+                            entries.remove(&oldest);
+                        }
+                    }
+                    let latest = now + max_age_duration(*max_age);
+                    let expires_at = expires_at.unwrap_or(latest).min(latest);
+                    if let Ok(ttl) = (expires_at - now).to_std() {
+                        order.push_back(entry.clone());
+                        entries.insert(entry, expires_at, ttl);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Converts a `max_age`, which is at most `MAX_AGE`, for date arithmetic.
+fn max_age_duration(max_age: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(max_age).expect("max_age is at most MAX_AGE")
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct PersistedEntry {
+    entry: CacheEntry,
+    expires_at: Option<DateTime<Utc>>,
+}
+
 impl Dedupe {
     pub fn new(config: DedupeConfig) -> Self {
         let num_entries = config.cache.num_events;
         let fields = config.fill_default_fields_match();
+        let cache = match config.cache.max_age_secs {
+            Some(max_age_secs) => Cache::Expiring {
+                entries: ExpiringHashMap::default(),
+                order: VecDeque::new(),
+                max_age: Duration::from_secs(max_age_secs).min(MAX_AGE),
+                capacity: num_entries,
+            },
+            None => Cache::Lru(LruCache::new(num_entries)),
+        };
         Self {
             fields,
-            cache: LruCache::new(num_entries),
+            cache,
+            persist_path: None,
+            dirty: false,
+            pending_persist: None,
+            io_lock: Arc::default(),
         }
     }
 
     fn transform_one(&mut self, event: Event) -> Option<Event> {
         let cache_entry = build_cache_entry(&event, &self.fields);
-        if self.cache.put(cache_entry, true).is_some() {
+        self.dirty = true;
+        if self.cache.insert(cache_entry) {
             emit!(DedupeEventDiscarded { event });
             None
         } else {
             Some(event)
         }
     }
+
+    /// Loads previously persisted entries from `path`, if any. A missing or
+    /// unreadable file leaves the cache empty rather than failing the
+    /// transform.
+    async fn restore(&mut self, path: &Path) {
+        let read = {
+            let _io_guard = self.io_lock.lock().await;
+            tokio::fs::read(path).await
+        };
+        let persisted = match read {
+            Ok(data) => serde_json::from_slice::<Vec<PersistedEntry>>(&data)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return,
+            Err(error) => Err(error),
+        };
+
+        match persisted {
+            Ok(persisted) => {
+                self.cache.restore(persisted);
+                emit!(DedupeCacheLoaded {
+                    path,
+                    count: self.cache.len(),
+                });
+            }
+            Err(error) => emit!(DedupeCacheLoadFailed { path, error }),
+        }
+    }
+
+    /// Writes a snapshot of the cache in the background, so events aren't held
+    /// up by the disk. Nothing is written while the previous snapshot still is,
+    /// the cache stays dirty until the next attempt instead.
+    fn persist(&mut self) {
+        let path = match &self.persist_path {
+            Some(path) => path.clone(),
+            None => return,
+        };
+        if let Some(pending) = &mut self.pending_persist {
+            if pending.now_or_never().is_none() {
+                return;
+            }
+        }
+        let snapshot = self.cache.snapshot();
+        let io_lock = Arc::clone(&self.io_lock);
+        self.pending_persist = Some(tokio::spawn(persist_snapshot(io_lock, path, snapshot)));
+        self.dirty = false;
+    }
+
+    /// Hands the final snapshot of the cache over to the transform replacing
+    /// this one, or writes it once the previous snapshot is done.
+    async fn persist_final(&mut self, id: u64) {
+        if let Some(pending) = self.pending_persist.take() {
+            let _ = pending.await;
+        }
+        if let Some(path) = self.persist_path.clone() {
+            if let Err(snapshot) = hand_over(&path, id, self.cache.snapshot()) {
+                persist_snapshot(Arc::clone(&self.io_lock), path, snapshot).await;
+            }
+            self.dirty = false;
+        }
+    }
+}
+
+async fn persist_snapshot(
+    io_lock: Arc<tokio::sync::Mutex<()>>,
+    path: PathBuf,
+    snapshot: Vec<PersistedEntry>,
+) {
+    let _io_guard = io_lock.lock().await;
+    // Write to a temporary file first so an interrupted write never leaves a
+    // truncated cache behind.
+    let tmp_path = path.with_extension("json.tmp");
+    let result = match serde_json::to_vec(&snapshot) {
+        Ok(data) => match tokio::fs::write(&tmp_path, data).await {
+            Ok(()) => tokio::fs::rename(&tmp_path, &path).await,
+            Err(error) => Err(error),
+        },
+        Err(error) => Err(io::Error::new(io::ErrorKind::InvalidData, error)),
+    };
+    if let Err(error) = result {
+        emit!(DedupeCachePersistFailed { path: &path, error });
+    }
 }
 
 /// Takes in an Event and returns a CacheEntry to place into the LRU cache
@@ -194,14 +602,76 @@ fn build_cache_entry(event: &Event, fields: &FieldMatchConfig) -> CacheEntry {
 
 impl TaskTransform for Dedupe {
     fn transform(
-        self: Box<Self>,
-        task: Pin<Box<dyn Stream<Item = Event> + Send>>,
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
     ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
     where
         Self: 'static,
     {
-        let mut inner = self;
-        Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
+        Box::pin(stream! {
+            // Registering before restoring the file makes sure the final
+            // snapshot of a replaced transform is either in the file or handed
+            // over.
+            let (id, mut handed_over) = match self.persist_path.clone() {
+                Some(path) => {
+                    let (id, io_lock, handed_over) = register_persisted_cache(&path);
+                    self.io_lock = io_lock;
+                    self.restore(&path).await;
+                    (id, Some(handed_over))
+                }
+                None => (0, None),
+            };
+
+            let persists = self.persist_path.is_some();
+            let mut persist_interval = tokio::time::interval(PERSIST_INTERVAL);
+            let mut done = false;
+            while !done {
+                let mut output = None;
+                tokio::select! {
+                    // Expire entries before looking at new events so that a
+                    // late duplicate isn't matched against a stale entry.
+                    biased;
+
+                    _ = self.cache.next_expired(), if self.cache.has_expiring_entries() => {}
+                    snapshot = next_handed_over(&mut handed_over), if handed_over.is_some() => {
+                        match snapshot {
+                            Some(snapshot) => {
+                                self.cache.restore(snapshot);
+                                self.dirty = true;
+                            }
+                            // A later transform took over the file.
+                            None => handed_over = None,
+                        }
+                    }
+                    _ = persist_interval.tick(), if persists => {
+                        if self.dirty {
+                            self.persist();
+                        }
+                    }
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            Some(event) => output = self.transform_one(event),
+                            None => {
+                                self.persist_final(id).await;
+                                done = true;
+                            }
+                        }
+                    }
+                };
+                if let Some(event) = output {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+async fn next_handed_over(
+    handed_over: &mut Option<mpsc::UnboundedReceiver<Vec<PersistedEntry>>>,
+) -> Option<Vec<PersistedEntry>> {
+    match handed_over {
+        Some(handed_over) => handed_over.recv().await,
+        None => None,
     }
 }
 
@@ -219,7 +689,11 @@ mod tests {
 
     fn make_match_transform(num_events: usize, fields: Vec<String>) -> Dedupe {
         Dedupe::new(DedupeConfig {
-            cache: CacheConfig { num_events },
+            cache: CacheConfig {
+                num_events,
+                max_age_secs: None,
+                persistence: None,
+            },
             fields: Some(FieldMatchConfig::MatchFields(fields)),
         })
     }
@@ -230,7 +704,11 @@ mod tests {
         fields.extend(given_fields);
 
         Dedupe::new(DedupeConfig {
-            cache: CacheConfig { num_events },
+            cache: CacheConfig {
+                num_events,
+                max_age_secs: None,
+                persistence: None,
+            },
             fields: Some(FieldMatchConfig::IgnoreFields(fields)),
         })
     }
@@ -471,4 +949,201 @@ mod tests {
         let new_event = transform.transform_one(event2.clone()).unwrap();
         assert_eq!(new_event, event2);
     }
+
+    fn make_expiring_transform(num_events: usize, max_age_secs: u64) -> Dedupe {
+        Dedupe::new(DedupeConfig {
+            cache: CacheConfig {
+                num_events,
+                max_age_secs: Some(max_age_secs),
+                persistence: None,
+            },
+            fields: Some(FieldMatchConfig::MatchFields(vec!["matched".into()])),
+        })
+    }
+
+    fn matched_event(value: &str) -> Event {
+        let mut event = Event::from("message");
+        event.as_mut_log().insert("matched", value);
+        event
+    }
+
+    #[tokio::test]
+    async fn dedupe_max_age_expires_entries() {
+        tokio::time::pause();
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+        let transform: Box<dyn TaskTransform> = Box::new(make_expiring_transform(5, 10));
+        let mut out = transform.transform(Box::pin(rx));
+
+        let event = matched_event("some value");
+        tx.try_send(event.clone()).unwrap();
+        assert_eq!(out.next().await, Some(event.clone()));
+
+        // A duplicate within `max_age_secs` is dropped.
+        tokio::time::advance(Duration::from_secs(5)).await;
+        tx.try_send(event.clone()).unwrap();
+        assert!(tokio::time::timeout(Duration::from_secs(1), out.next())
+            .await
+            .is_err());
+
+        // Once the first sighting has expired the event passes again.
+        tokio::time::advance(Duration::from_secs(5)).await;
+        tx.try_send(event.clone()).unwrap();
+        assert_eq!(out.next().await, Some(event));
+
+        drop(tx);
+        assert_eq!(out.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn dedupe_max_age_evicts_oldest_when_full() {
+        let mut transform = make_expiring_transform(2, 60);
+
+        let event1 = matched_event("some value");
+        let event2 = matched_event("some value2");
+        let event3 = matched_event("some value3");
+
+        assert!(transform.transform_one(event1.clone()).is_some());
+        assert!(transform.transform_one(event2.clone()).is_some());
+        // The cache is full, so the oldest entry makes room for this one.
+        assert!(transform.transform_one(event3.clone()).is_some());
+        assert!(transform.transform_one(event2).is_none());
+        assert!(transform.transform_one(event3).is_none());
+        assert!(transform.transform_one(event1).is_some());
+    }
+
+    fn persistent_config(data_dir: &tempfile::TempDir) -> DedupeConfig {
+        DedupeConfig {
+            cache: CacheConfig {
+                num_events: 5,
+                max_age_secs: Some(60),
+                persistence: Some(PersistenceConfig {
+                    key: "dedupe_test".into(),
+                    data_dir: Some(data_dir.path().to_path_buf()),
+                }),
+            },
+            fields: Some(FieldMatchConfig::MatchFields(vec!["matched".into()])),
+        }
+    }
+
+    #[tokio::test]
+    async fn dedupe_persists_cache_across_restarts() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = persistent_config(&data_dir);
+        let event = matched_event("some value");
+
+        let transform = config.build(&TransformContext::default()).await.unwrap();
+        let out = transform
+            .into_task()
+            .transform(Box::pin(futures::stream::iter(vec![event.clone()])))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(out, vec![event.clone()]);
+        assert!(data_dir
+            .path()
+            .join("dedupe_test")
+            .join(PERSISTED_CACHE_FILE)
+            .exists());
+
+        // A rebuilt transform still remembers the event from the first run.
+        let transform = config.build(&TransformContext::default()).await.unwrap();
+        let out = transform
+            .into_task()
+            .transform(Box::pin(futures::stream::iter(vec![event])))
+            .collect::<Vec<_>>()
+            .await;
+        assert!(out.is_empty());
+    }
+
+    #[tokio::test]
+    async fn dedupe_restores_cache_on_reload() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = persistent_config(&data_dir);
+        let event = matched_event("some value");
+
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+        let transform = config.build(&TransformContext::default()).await.unwrap();
+        let mut out = transform.into_task().transform(Box::pin(rx));
+        tx.try_send(event.clone()).unwrap();
+        assert_eq!(out.next().await, Some(event.clone()));
+
+        // The replacement is built while the first transform still runs, and
+        // restores the cache the first one persisted when it stopped.
+        let replacement = config.build(&TransformContext::default()).await.unwrap();
+        drop(tx);
+        assert_eq!(out.next().await, None);
+
+        let out = replacement
+            .into_task()
+            .transform(Box::pin(futures::stream::iter(vec![event])))
+            .collect::<Vec<_>>()
+            .await;
+        assert!(out.is_empty());
+    }
+
+    #[tokio::test]
+    async fn dedupe_replacement_receives_final_snapshot() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = persistent_config(&data_dir);
+        let first = matched_event("first");
+        let second = matched_event("second");
+
+        let (mut tx, rx) = futures::channel::mpsc::channel(10);
+        let transform = config.build(&TransformContext::default()).await.unwrap();
+        let mut out = transform.into_task().transform(Box::pin(rx));
+        tx.try_send(first.clone()).unwrap();
+        assert_eq!(out.next().await, Some(first.clone()));
+
+        // On reload the replacement starts running, and receives events,
+        // before the transform it replaces stopped.
+        let (mut replacement_tx, replacement_rx) = futures::channel::mpsc::channel(10);
+        let replacement = config.build(&TransformContext::default()).await.unwrap();
+        let mut replacement_out = replacement.into_task().transform(Box::pin(replacement_rx));
+        replacement_tx.try_send(second.clone()).unwrap();
+        assert_eq!(replacement_out.next().await, Some(second.clone()));
+
+        // The events seen by the replaced transform until it stopped are
+        // handed over to the replacement.
+        tx.try_send(second.clone()).unwrap();
+        assert_eq!(out.next().await, Some(second));
+        drop(tx);
+        assert_eq!(out.next().await, None);
+        replacement_tx.try_send(first).unwrap();
+        drop(replacement_tx);
+        assert_eq!(replacement_out.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn dedupe_rejects_path_like_keys() {
+        for key in &["", "..", "../outside", "nested/key"] {
+            let data_dir = tempfile::tempdir().unwrap();
+            let mut config = persistent_config(&data_dir);
+            config.cache.persistence.as_mut().unwrap().key = key.to_string();
+            assert!(config.build(&TransformContext::default()).await.is_err());
+        }
+    }
+
+    #[test]
+    fn dedupe_claims_its_persistence_directory() {
+        let data_dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            persistent_config(&data_dir).resources(),
+            vec![Resource::DataSubdir(
+                Some(data_dir.path().to_path_buf()),
+                "dedupe_test".into()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn dedupe_rejects_too_large_max_age() {
+        let config = DedupeConfig {
+            cache: CacheConfig {
+                num_events: 5,
+                max_age_secs: Some(u64::MAX),
+                persistence: None,
+            },
+            fields: None,
+        };
+        assert!(config.build(&TransformContext::default()).await.is_err());
+    }
 }
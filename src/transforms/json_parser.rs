@@ -1,8 +1,11 @@
 use crate::{
-    config::{log_schema, DataType, GlobalOptions, TransformConfig, TransformDescription},
+    config::{
+        log_schema, DataType, TransformConfig, TransformContext, TransformDescription,
+        DROPPED_OUTPUT,
+    },
     event::Event,
     internal_events::{JsonParserFailedParse, JsonParserTargetExists},
-    transforms::{FunctionTransform, Transform},
+    transforms::{annotate_dropped, FunctionTransform, Transform, TransformOutputs},
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -17,6 +20,9 @@ pub struct JsonParserConfig {
     pub drop_field: bool,
     pub target_field: Option<String>,
     pub overwrite_target: Option<bool>,
+    /// Send the events dropped by `drop_invalid` to the `dropped` output
+    /// instead of discarding them.
+    pub reroute_dropped: bool,
 }
 
 inventory::submit! {
@@ -28,7 +34,7 @@ impl_generate_config_from_default!(JsonParserConfig);
 #[async_trait::async_trait]
 #[typetag::serde(name = "json_parser")]
 impl TransformConfig for JsonParserConfig {
-    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
         Ok(Transform::function(JsonParser::from(self.clone())))
     }
 
@@ -43,6 +49,14 @@ impl TransformConfig for JsonParserConfig {
     fn transform_type(&self) -> &'static str {
         "json_parser"
     }
+
+    fn named_outputs(&self) -> Vec<String> {
+        if self.reroute_dropped {
+            vec![DROPPED_OUTPUT.to_owned()]
+        } else {
+            Vec::new()
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -70,16 +84,25 @@ impl From<JsonParserConfig> for JsonParser {
     }
 }
 
-impl FunctionTransform for JsonParser {
-    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+impl JsonParser {
+    /// Parses `event`. If `reroute_dropped` is set, the event that
+    /// `drop_invalid` would discard is returned instead.
+    fn run(
+        &mut self,
+        output: &mut Vec<Event>,
+        reroute_dropped: bool,
+        mut event: Event,
+    ) -> Option<Event> {
         let log = event.as_mut_log();
         let value = log.get(&self.field);
 
+        let mut parse_error = None;
         let parsed = value
             .and_then(|value| {
                 let to_parse = value.as_bytes();
                 serde_json::from_slice::<Value>(to_parse.as_ref())
                     .map_err(|error| {
+                        parse_error = Some(error.to_string());
                         emit!(JsonParserFailedParse {
                             field: &self.field,
                             value: value.to_string_lossy().as_str(),
@@ -123,10 +146,30 @@ impl FunctionTransform for JsonParser {
                 }
             }
         } else if self.drop_invalid {
-            return;
+            if reroute_dropped {
+                let message = parse_error.unwrap_or_else(|| {
+                    format!("Field {:?} is missing or not a JSON object.", self.field)
+                });
+                annotate_dropped(&mut event, "invalid_json", message);
+                return Some(event);
+            }
+            return None;
         }
 
         output.push(event);
+        None
+    }
+}
+
+impl FunctionTransform for JsonParser {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        self.run(output, false, event);
+    }
+
+    fn transform_outputs(&mut self, outputs: &mut TransformOutputs, event: Event) {
+        if let Some(event) = self.run(outputs.primary_mut(), true, event) {
+            outputs.push_named(DROPPED_OUTPUT, event);
+        }
     }
 }
 
@@ -385,6 +428,37 @@ mod test {
         assert!(transform_one(&mut parser, event).is_none());
     }
 
+    #[test]
+    fn json_parser_reroute_dropped() {
+        let invalid = r#"{"greeting": "hello","#;
+
+        let config = JsonParserConfig {
+            drop_invalid: true,
+            reroute_dropped: true,
+            ..Default::default()
+        };
+        let mut outputs = TransformOutputs::new(config.named_outputs());
+        let mut parser = JsonParser::from(config);
+
+        parser.transform_outputs(&mut outputs, Event::from(invalid));
+        parser.transform_outputs(&mut outputs, Event::from(r#""hello""#));
+
+        assert_eq!(outputs.drain().len(), 0);
+        let dropped = outputs.drain_named(DROPPED_OUTPUT).collect::<Vec<_>>();
+        assert_eq!(dropped.len(), 2);
+
+        let log = dropped[0].as_log();
+        assert_eq!(log[log_schema().message_key()], invalid.into());
+        assert_eq!(log["metadata.dropped.reason"], "invalid_json".into());
+        assert!(log.get("metadata.dropped.message").is_some());
+
+        let log = dropped[1].as_log();
+        assert_eq!(
+            log["metadata.dropped.message"],
+            "Field \"message\" is missing or not a JSON object.".into()
+        );
+    }
+
     #[test]
     fn json_parser_chained() {
         let mut parser1 = JsonParser::from(JsonParserConfig {
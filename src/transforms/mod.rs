@@ -60,12 +60,36 @@ pub mod sample;
 pub mod split;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
+#[cfg(feature = "transforms-throttle")]
+pub mod throttle;
 #[cfg(feature = "transforms-tokenizer")]
 pub mod tokenizer;
 #[cfg(feature = "wasm")]
 pub mod wasm;
 
-pub use vector_core::transform::{FunctionTransform, TaskTransform, Transform};
+pub use vector_core::transform::{FunctionTransform, TaskTransform, Transform, TransformOutputs};
+
+use crate::event::Event;
+
+/// Records on an event sent to a `dropped` output why it failed processing.
+/// Logs get a `metadata.dropped` object, metrics get `dropped_*` tags.
+pub fn annotate_dropped(event: &mut Event, reason: &str, message: impl Into<String>) {
+    let message = message.into();
+    match event {
+        Event::Log(log) => {
+            log.insert("metadata.dropped.reason", reason);
+            log.insert("metadata.dropped.message", message);
+        }
+        Event::Metric(metric) => {
+            metric.insert_tag("dropped_reason".into(), reason.into());
+            metric.insert_tag("dropped_message".into(), message);
+        }
+        Event::Trace(trace) => {
+            trace.insert("metadata.dropped.reason", reason);
+            trace.insert("metadata.dropped.message", message);
+        }
+    }
+}
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -1,8 +1,8 @@
 use crate::{
-    config::{DataType, GlobalOptions, TransformConfig, TransformDescription},
+    config::{DataType, TransformConfig, TransformContext, TransformDescription, DROPPED_OUTPUT},
     event::{Event, VrlTarget},
     internal_events::{RemapMappingAbort, RemapMappingError},
-    transforms::{FunctionTransform, Transform},
+    transforms::{annotate_dropped, FunctionTransform, Transform, TransformOutputs},
     Result,
 };
 use serde::{Deserialize, Serialize};
@@ -20,6 +20,9 @@ pub struct RemapConfig {
     pub drop_on_error: bool,
     #[serde(default = "crate::serde::default_true")]
     pub drop_on_abort: bool,
+    /// Send the events dropped by `drop_on_error` or `drop_on_abort` to the
+    /// `dropped` output instead of discarding them.
+    pub reroute_dropped: bool,
 }
 
 inventory::submit! {
@@ -31,8 +34,8 @@ impl_generate_config_from_default!(RemapConfig);
 #[async_trait::async_trait]
 #[typetag::serde(name = "remap")]
 impl TransformConfig for RemapConfig {
-    async fn build(&self, _globals: &GlobalOptions) -> Result<Transform> {
-        Remap::new(self.clone()).map(Transform::function)
+    async fn build(&self, context: &TransformContext) -> Result<Transform> {
+        Remap::new(self.clone(), context).map(Transform::function)
     }
 
     fn input_type(&self) -> DataType {
@@ -46,6 +49,14 @@ impl TransformConfig for RemapConfig {
     fn transform_type(&self) -> &'static str {
         "remap"
     }
+
+    fn named_outputs(&self) -> Vec<String> {
+        if self.reroute_dropped {
+            vec![DROPPED_OUTPUT.to_owned()]
+        } else {
+            Vec::new()
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -57,12 +68,16 @@ pub struct Remap {
 }
 
 impl Remap {
-    pub fn new(config: RemapConfig) -> crate::Result<Self> {
-        let program = vrl::compile(&config.source, &vrl_stdlib::all()).map_err(|diagnostics| {
-            Formatter::new(&config.source, diagnostics)
-                .colored()
-                .to_string()
-        })?;
+    pub fn new(config: RemapConfig, context: &TransformContext) -> crate::Result<Self> {
+        let mut state = vrl::state::Compiler::default();
+        state.set_external_context(context.enrichment_tables.clone());
+
+        let program = vrl::compile_with_state(&config.source, &vrl_stdlib::all(), &mut state)
+            .map_err(|diagnostics| {
+                Formatter::new(&config.source, diagnostics)
+                    .colored()
+                    .to_string()
+            })?;
 
         Ok(Remap {
             program,
@@ -73,8 +88,18 @@ impl Remap {
     }
 }
 
-impl FunctionTransform for Remap {
-    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+impl Remap {
+    /// Runs the program on `event`. If `reroute_dropped` is set, the event
+    /// that would otherwise be discarded after a failure is returned instead.
+    fn run(
+        &mut self,
+        output: &mut Vec<Event>,
+        reroute_dropped: bool,
+        event: Event,
+    ) -> Option<Event> {
+        let keep_on_error = !self.drop_on_error || reroute_dropped;
+        let keep_on_abort = !self.drop_on_abort || reroute_dropped;
+
         // If a program can fail or abort at runtime, we need to clone the
         // original event and keep it around, to allow us to discard any
         // mutations made to the event while the VRL program runs, before it
@@ -83,10 +108,12 @@ impl FunctionTransform for Remap {
         // The `drop_on_{error, abort}` transform config allows operators to
         // ignore events if their failed/aborted, in which case we can skip the
         // cloning, since any mutations made by VRL will be ignored regardless.
+        // Events rerouted to the `dropped` output are sent unmodified, so
+        // they need the clone too.
         #[allow(clippy::if_same_then_else)]
-        let original_event = if !self.drop_on_error && self.program.can_fail() {
+        let original_event = if keep_on_error && self.program.can_fail() {
             Some(event.clone())
-        } else if !self.drop_on_abort && self.program.can_abort() {
+        } else if keep_on_abort && self.program.can_abort() {
             Some(event.clone())
         } else {
             None
@@ -103,30 +130,57 @@ impl FunctionTransform for Remap {
                 for event in target.into_events() {
                     output.push(event)
                 }
+                None
             }
             Err(Terminate::Abort) => {
                 emit!(RemapMappingAbort {
-                    event_dropped: self.drop_on_abort,
+                    event_dropped: !keep_on_abort,
                 });
 
                 if !self.drop_on_abort {
-                    output.push(original_event.expect("event will be set"))
+                    output.push(original_event.expect("event will be set"));
+                    None
+                } else if reroute_dropped {
+                    let mut event = original_event.expect("event will be set");
+                    annotate_dropped(&mut event, "abort", "Event mapping aborted.");
+                    Some(event)
+                } else {
+                    None
                 }
             }
             Err(Terminate::Error(error)) => {
                 emit!(RemapMappingError {
-                    error,
-                    event_dropped: self.drop_on_error,
+                    error: error.clone(),
+                    event_dropped: !keep_on_error,
                 });
 
                 if !self.drop_on_error {
-                    output.push(original_event.expect("event will be set"))
+                    output.push(original_event.expect("event will be set"));
+                    None
+                } else if reroute_dropped {
+                    let mut event = original_event.expect("event will be set");
+                    annotate_dropped(&mut event, "error", error);
+                    Some(event)
+                } else {
+                    None
                 }
             }
         }
     }
 }
 
+impl FunctionTransform for Remap {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        self.run(output, false, event);
+    }
+
+    fn transform_outputs(&mut self, outputs: &mut TransformOutputs, event: Event) {
+        if let Some(event) = self.run(outputs.primary_mut(), true, event) {
+            outputs.push_named(DROPPED_OUTPUT, event);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,8 +222,9 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: true,
             drop_on_abort: false,
+            reroute_dropped: false,
         };
-        let mut tform = Remap::new(conf).unwrap();
+        let mut tform = Remap::new(conf, &TransformContext::default()).unwrap();
 
         let result = transform_one(&mut tform, event).unwrap();
         assert_eq!(get_field_string(&result, "message"), "augment me");
@@ -200,8 +255,9 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: true,
             drop_on_abort: false,
+            reroute_dropped: false,
         };
-        let mut tform = Remap::new(conf).unwrap();
+        let mut tform = Remap::new(conf, &TransformContext::default()).unwrap();
 
         let mut result = vec![];
         tform.transform(&mut result, event);
@@ -229,8 +285,9 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: false,
             drop_on_abort: false,
+            reroute_dropped: false,
         };
-        let mut tform = Remap::new(conf).unwrap();
+        let mut tform = Remap::new(conf, &TransformContext::default()).unwrap();
 
         let event = transform_one(&mut tform, event).unwrap();
 
@@ -256,8 +313,9 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: true,
             drop_on_abort: false,
+            reroute_dropped: false,
         };
-        let mut tform = Remap::new(conf).unwrap();
+        let mut tform = Remap::new(conf, &TransformContext::default()).unwrap();
 
         assert!(transform_one(&mut tform, event).is_none())
     }
@@ -278,8 +336,9 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: false,
             drop_on_abort: false,
+            reroute_dropped: false,
         };
-        let mut tform = Remap::new(conf).unwrap();
+        let mut tform = Remap::new(conf, &TransformContext::default()).unwrap();
 
         let event = transform_one(&mut tform, event).unwrap();
 
@@ -305,8 +364,9 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: false,
             drop_on_abort: false,
+            reroute_dropped: false,
         };
-        let mut tform = Remap::new(conf).unwrap();
+        let mut tform = Remap::new(conf, &TransformContext::default()).unwrap();
 
         let event = transform_one(&mut tform, event).unwrap();
 
@@ -332,12 +392,82 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: false,
             drop_on_abort: true,
+            reroute_dropped: false,
         };
-        let mut tform = Remap::new(conf).unwrap();
+        let mut tform = Remap::new(conf, &TransformContext::default()).unwrap();
 
         assert!(transform_one(&mut tform, event).is_none())
     }
 
+    #[test]
+    fn check_remap_error_reroute() {
+        let event = {
+            let mut event = Event::from("augment me");
+            event.as_mut_log().insert("bar", "is a string");
+            event
+        };
+
+        let conf = RemapConfig {
+            source: formatdoc! {r#"
+                .foo = "foo"
+                .not_an_int = int!(.bar)
+                .baz = 12
+            "#},
+            timezone: TimeZone::default(),
+            drop_on_error: true,
+            drop_on_abort: false,
+            reroute_dropped: true,
+        };
+        let conf_outputs = conf.named_outputs();
+        let mut tform = Remap::new(conf, &TransformContext::default()).unwrap();
+
+        let mut outputs = TransformOutputs::new(conf_outputs);
+        tform.transform_outputs(&mut outputs, event);
+
+        assert_eq!(outputs.drain().len(), 0);
+        let dropped = outputs.drain_named(DROPPED_OUTPUT).collect::<Vec<_>>();
+        assert_eq!(dropped.len(), 1);
+        let log = dropped[0].as_log();
+        assert_eq!(log.get("bar"), Some(&Value::from("is a string")));
+        assert!(log.get("foo").is_none());
+        assert_eq!(
+            log.get("metadata.dropped.reason"),
+            Some(&Value::from("error"))
+        );
+        assert!(log.get("metadata.dropped.message").is_some());
+    }
+
+    #[test]
+    fn check_remap_abort_reroute() {
+        let event = Event::from("augment me");
+
+        let conf = RemapConfig {
+            source: formatdoc! {r#"
+                .foo = "foo"
+                abort
+            "#},
+            timezone: TimeZone::default(),
+            drop_on_error: false,
+            drop_on_abort: true,
+            reroute_dropped: true,
+        };
+        let conf_outputs = conf.named_outputs();
+        let mut tform = Remap::new(conf, &TransformContext::default()).unwrap();
+
+        let mut outputs = TransformOutputs::new(conf_outputs);
+        tform.transform_outputs(&mut outputs, event);
+
+        assert_eq!(outputs.drain().len(), 0);
+        let dropped = outputs.drain_named(DROPPED_OUTPUT).collect::<Vec<_>>();
+        assert_eq!(dropped.len(), 1);
+        let log = dropped[0].as_log();
+        assert!(log.get("foo").is_none());
+        assert_eq!(
+            log.get("metadata.dropped.reason"),
+            Some(&Value::from("abort"))
+        );
+    }
+
     #[test]
     fn check_remap_metric() {
         let metric = Event::Metric(Metric::new(
@@ -356,8 +486,9 @@ mod tests {
             timezone: TimeZone::default(),
             drop_on_error: true,
             drop_on_abort: false,
+            reroute_dropped: false,
         };
-        let mut tform = Remap::new(conf).unwrap();
+        let mut tform = Remap::new(conf, &TransformContext::default()).unwrap();
 
         let result = transform_one(&mut tform, metric).unwrap();
         assert_eq!(
@@ -1,60 +1,42 @@
 use crate::{
     conditions::{AnyCondition, Condition},
-    config::{DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription},
+    config::{DataType, GenerateConfig, TransformConfig, TransformContext, TransformDescription},
     event::Event,
     internal_events::RouteEventDiscarded,
-    transforms::{FunctionTransform, Transform},
+    transforms::{FunctionTransform, Transform, TransformOutputs},
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 //------------------------------------------------------------------------------
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(deny_unknown_fields)]
-pub struct LaneConfig {
-    condition: AnyCondition,
-}
-
-#[async_trait::async_trait]
-#[typetag::serde(name = "lane")]
-impl TransformConfig for LaneConfig {
-    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
-        Ok(Transform::function(Lane::new(self.condition.build()?)))
-    }
-
-    fn input_type(&self) -> DataType {
-        DataType::Any
-    }
-
-    fn output_type(&self) -> DataType {
-        DataType::Any
-    }
-
-    fn transform_type(&self) -> &'static str {
-        "lane"
-    }
-}
-
 #[derive(Clone, Derivative)]
 #[derivative(Debug)]
-pub struct Lane {
+pub struct Route {
     #[derivative(Debug = "ignore")]
-    condition: Box<dyn Condition>,
+    conditions: Vec<(String, Box<dyn Condition>)>,
 }
 
-impl Lane {
-    pub fn new(condition: Box<dyn Condition>) -> Self {
-        Self { condition }
+impl Route {
+    pub fn new(conditions: Vec<(String, Box<dyn Condition>)>) -> Self {
+        Self { conditions }
     }
 }
 
-impl FunctionTransform for Lane {
-    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
-        if self.condition.check(&event) {
-            output.push(event);
-        } else {
-            emit!(RouteEventDiscarded);
+impl FunctionTransform for Route {
+    fn transform(&mut self, _output: &mut Vec<Event>, _event: Event) {
+        // Nothing is ever written to the default output, events only go to
+        // the outputs of the routes they match.
+        emit!(RouteEventDiscarded);
+    }
+
+    fn transform_outputs(&mut self, outputs: &mut TransformOutputs, event: Event) {
+        for (output, condition) in self.conditions.iter() {
+            if condition.check(&event) {
+                outputs.push_named(output, event.clone());
+            } else {
+                emit!(RouteEventDiscarded);
+            }
         }
     }
 }
@@ -89,22 +71,21 @@ impl GenerateConfig for RouteConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "route")]
 impl TransformConfig for RouteConfig {
-    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
-        Err("this transform must be expanded".into())
-    }
-
-    fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
-        let mut map: IndexMap<String, Box<dyn TransformConfig>> = IndexMap::new();
-
-        while let Some((k, v)) = self.route.pop() {
-            map.insert(k.clone(), Box::new(LaneConfig { condition: v }));
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if self.route.is_empty() {
+            return Err("must specify at least one route".into());
         }
 
-        if !map.is_empty() {
-            Ok(Some(map))
-        } else {
-            Err("must specify at least one lane".into())
-        }
+        let conditions = self
+            .route
+            .iter()
+            .map(|(output, condition)| Ok((output.clone(), condition.build()?)))
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(Transform::function(Route::new(conditions)))
+    }
+
+    fn named_outputs(&self) -> Vec<String> {
+        self.route.keys().cloned().collect()
     }
 
     fn input_type(&self) -> DataType {
@@ -127,12 +108,12 @@ struct RouteCompatConfig(RouteConfig);
 #[async_trait::async_trait]
 #[typetag::serde(name = "swimlanes")]
 impl TransformConfig for RouteCompatConfig {
-    async fn build(&self, globals: &GlobalOptions) -> crate::Result<Transform> {
-        self.0.build(globals).await
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        self.0.build(context).await
     }
 
-    fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
-        self.0.expand()
+    fn named_outputs(&self) -> Vec<String> {
+        self.0.named_outputs()
     }
 
     fn input_type(&self) -> DataType {
@@ -170,20 +151,6 @@ mod test {
         .unwrap();
     }
 
-    #[test]
-    fn can_serialize_remap() {
-        // We need to serialize the config to check if a config has
-        // changed when reloading.
-        let config = LaneConfig {
-            condition: AnyCondition::String("foo".to_string()),
-        };
-
-        assert_eq!(
-            serde_json::to_string(&config).unwrap(),
-            r#"{"condition":"foo"}"#
-        );
-    }
-
     #[test]
     fn can_serialize_check_fields() {
         // We need to serialize the config to check if a config has
@@ -194,14 +161,50 @@ mod test {
             lanes.first."message.eq" = "foo"
         "#,
         )
-        .unwrap()
-        .expand()
-        .unwrap()
         .unwrap();
 
         assert_eq!(
             serde_json::to_string(&config).unwrap(),
-            r#"{"first":{"type":"lane","condition":{"type":"check_fields","message.eq":"foo"}}}"#
+            r#"{"route":{"first":{"type":"check_fields","message.eq":"foo"}}}"#
         );
     }
+
+    #[tokio::test]
+    async fn route_to_matching_outputs() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.first.type = "check_fields"
+            route.first."message.eq" = "foo"
+
+            route.second.type = "check_fields"
+            route.second."message.contains" = "o"
+
+            route.third.type = "check_fields"
+            route.third."message.eq" = "bar"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.named_outputs(), vec!["first", "second", "third"]);
+
+        let mut outputs = TransformOutputs::new(config.named_outputs());
+        let mut transform = config
+            .build(&TransformContext::default())
+            .await
+            .unwrap()
+            .into_function();
+        transform.transform_outputs(&mut outputs, Event::from("foo"));
+
+        assert_eq!(outputs.drain().len(), 0);
+        assert_eq!(outputs.drain_named("first").len(), 1);
+        assert_eq!(outputs.drain_named("second").len(), 1);
+        assert_eq!(outputs.drain_named("third").len(), 0);
+    }
+
+    #[tokio::test]
+    async fn requires_a_route() {
+        let config = RouteConfig {
+            route: IndexMap::new(),
+        };
+        assert!(config.build(&TransformContext::default()).await.is_err());
+    }
 }
@@ -0,0 +1,299 @@
+use crate::{
+    conditions::{AnyCondition, Condition},
+    config::{DataType, GenerateConfig, TransformConfig, TransformContext, TransformDescription},
+    event::Event,
+    internal_events::{TemplateRenderingFailed, ThrottleEventDiscarded},
+    template::Template,
+    transforms::{FunctionTransform, Transform},
+};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::{collections::HashMap, time::Duration};
+use tokio::time::Instant;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ThrottleConfig {
+    pub threshold: u32,
+    pub window_secs: f64,
+    pub key_field: Option<Template>,
+    pub exclude: Option<AnyCondition>,
+}
+
+inventory::submit! {
+    TransformDescription::new::<ThrottleConfig>("throttle")
+}
+
+impl GenerateConfig for ThrottleConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"threshold = 100
+            window_secs = 1.0"#,
+        )
+        .unwrap()
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum ConfigError {
+    #[snafu(display("`threshold` must be greater than 0"))]
+    ZeroThreshold,
+    #[snafu(display("`window_secs` must be a positive, finite number of seconds"))]
+    InvalidWindow,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "throttle")]
+impl TransformConfig for ThrottleConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if self.threshold == 0 {
+            return Err(Box::new(ConfigError::ZeroThreshold));
+        }
+        if !(self.window_secs.is_finite() && self.window_secs > 0.0) {
+            return Err(Box::new(ConfigError::InvalidWindow));
+        }
+
+        Ok(Transform::function(Throttle::new(
+            self.threshold,
+            Duration::from_secs_f64(self.window_secs),
+            self.key_field.clone(),
+            self.exclude
+                .as_ref()
+                .map(|condition| condition.build())
+                .transpose()?,
+        )))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "throttle"
+    }
+}
+
+/// A token bucket holding up to `threshold` tokens, refilled continuously so
+/// that a full bucket is regained over the configured window.
+#[derive(Clone, Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+#[derive(Clone)]
+pub struct Throttle {
+    threshold: f64,
+    window: Duration,
+    key_field: Option<Template>,
+    exclude: Option<Box<dyn Condition>>,
+    buckets: HashMap<Option<String>, Bucket>,
+    last_prune: Instant,
+}
+
+impl Throttle {
+    pub fn new(
+        threshold: u32,
+        window: Duration,
+        key_field: Option<Template>,
+        exclude: Option<Box<dyn Condition>>,
+    ) -> Self {
+        Self {
+            threshold: threshold as f64,
+            window,
+            key_field,
+            exclude,
+            buckets: HashMap::new(),
+            last_prune: Instant::now(),
+        }
+    }
+
+    fn refill(&self, bucket: &mut Bucket, now: Instant) {
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        let refilled = elapsed.as_secs_f64() / self.window.as_secs_f64() * self.threshold;
+        bucket.tokens = (bucket.tokens + refilled).min(self.threshold);
+        bucket.last_refill = now;
+    }
+
+    /// Takes a token from the bucket for `key`, returning `false` when the
+    /// bucket is empty and the event should be dropped.
+    fn try_acquire(&mut self, key: Option<String>, now: Instant) -> bool {
+        let threshold = self.threshold;
+        let mut bucket = self.buckets.remove(&key).unwrap_or(Bucket {
+            tokens: threshold,
+            last_refill: now,
+        });
+        self.refill(&mut bucket, now);
+
+        let acquired = bucket.tokens >= 1.0;
+        if acquired {
+            bucket.tokens -= 1.0;
+        }
+        self.buckets.insert(key, bucket);
+        acquired
+    }
+
+    /// Buckets that have refilled completely are indistinguishable from new
+    /// ones, so drop them once per window to keep high cardinality keys from
+    /// growing the map without bound.
+    fn prune(&mut self, now: Instant) {
+        if now.saturating_duration_since(self.last_prune) < self.window {
+            return;
+        }
+        self.last_prune = now;
+
+        let threshold = self.threshold;
+        let window = self.window.as_secs_f64();
+        self.buckets.retain(|_, bucket| {
+            let elapsed = now.saturating_duration_since(bucket.last_refill);
+            bucket.tokens + elapsed.as_secs_f64() / window * threshold < threshold
+        });
+    }
+}
+
+impl FunctionTransform for Throttle {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        if let Some(condition) = self.exclude.as_ref() {
+            if condition.check(&event) {
+                output.push(event);
+                return;
+            }
+        }
+
+        let key = match self.key_field.as_ref() {
+            Some(template) => match template.render_string(&event) {
+                Ok(key) => Some(key),
+                Err(error) => {
+                    emit!(TemplateRenderingFailed {
+                        error,
+                        field: Some("key_field"),
+                        drop_event: false,
+                    });
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let now = Instant::now();
+        self.prune(now);
+
+        if self.try_acquire(key.clone(), now) {
+            output.push(event);
+        } else {
+            emit!(ThrottleEventDiscarded {
+                key: key.as_deref()
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        conditions::{ConditionConfig, VrlConfig},
+        event::Event,
+        transforms::test::transform_one,
+    };
+    use std::convert::TryFrom;
+
+    fn throttle(threshold: u32, window_secs: u64, key_field: Option<&str>) -> Throttle {
+        Throttle::new(
+            threshold,
+            Duration::from_secs(window_secs),
+            key_field.map(|key| Template::try_from(key).unwrap()),
+            None,
+        )
+    }
+
+    fn passed(throttle: &mut Throttle, events: impl IntoIterator<Item = Event>) -> usize {
+        events
+            .into_iter()
+            .filter_map(|event| transform_one(throttle, event))
+            .count()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ThrottleConfig>();
+    }
+
+    #[tokio::test]
+    async fn throttles_above_threshold() {
+        tokio::time::pause();
+        let mut throttle = throttle(2, 5, None);
+
+        let events = (0..5).map(|i| Event::from(format!("message {}", i)));
+        assert_eq!(passed(&mut throttle, events), 2);
+    }
+
+    #[tokio::test]
+    async fn refills_over_window() {
+        tokio::time::pause();
+        let mut throttle = throttle(2, 10, None);
+
+        assert_eq!(passed(&mut throttle, vec![Event::from("a"); 3]), 2);
+
+        // Half a window restores half of the threshold.
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert_eq!(passed(&mut throttle, vec![Event::from("b"); 3]), 1);
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert_eq!(passed(&mut throttle, vec![Event::from("c"); 3]), 2);
+    }
+
+    #[tokio::test]
+    async fn throttles_per_key() {
+        tokio::time::pause();
+        let mut throttle = throttle(1, 5, Some("{{ service }}"));
+
+        let events = ["api", "api", "web", "web", "db"].iter().map(|service| {
+            let mut event = Event::from("message");
+            event.as_mut_log().insert("service", *service);
+            event
+        });
+        assert_eq!(passed(&mut throttle, events), 3);
+    }
+
+    #[tokio::test]
+    async fn excluded_events_bypass_throttle() {
+        tokio::time::pause();
+        let mut throttle = Throttle::new(
+            1,
+            Duration::from_secs(5),
+            None,
+            Some(
+                VrlConfig {
+                    source: r#"contains!(.message, "important")"#.to_owned(),
+                }
+                .build()
+                .unwrap(),
+            ),
+        );
+
+        assert_eq!(passed(&mut throttle, vec![Event::from("noise"); 3]), 1);
+        assert_eq!(passed(&mut throttle, vec![Event::from("important"); 3]), 3);
+    }
+
+    #[tokio::test]
+    async fn prunes_refilled_buckets() {
+        tokio::time::pause();
+        let mut throttle = throttle(1, 1, Some("{{ service }}"));
+
+        for service in &["api", "web", "db"] {
+            let mut event = Event::from("message");
+            event.as_mut_log().insert("service", *service);
+            transform_one(&mut throttle, event);
+        }
+        assert_eq!(throttle.buckets.len(), 3);
+
+        tokio::time::advance(Duration::from_secs(2)).await;
+        transform_one(&mut throttle, Event::from("message"));
+        assert_eq!(throttle.buckets.len(), 1);
+    }
+}
@@ -168,7 +168,7 @@ async fn validate_components(
     diff: &ConfigDiff,
     fmt: &mut Formatter,
 ) -> Option<Pieces> {
-    match topology::builder::build_pieces(config, diff, HashMap::new()).await {
+    match topology::builder::build_pieces(config, diff, HashMap::new(), &Default::default()).await {
         Ok(pieces) => {
             fmt.success("Component configuration");
             Some(pieces)
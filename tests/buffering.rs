@@ -1,4 +1,4 @@
-#![cfg(feature = "leveldb")]
+#![cfg(feature = "disk-buffer")]
 
 use futures::{SinkExt, StreamExt};
 use shared::assert_event_data_eq;
@@ -6,7 +6,7 @@ use tempfile::tempdir;
 use tokio::runtime::Runtime;
 use tracing::trace;
 use vector::{
-    buffers::BufferConfig,
+    buffers::{BufferConfig, BufferType},
     config,
     test_util::{
         random_events_with_stream, runtime, start_topology, trace_init, wait_for_atomic_usize,
@@ -49,9 +49,11 @@ fn test_buffering() {
         let mut config = config::Config::builder();
         config.add_source("in", source_config);
         config.add_sink("out", &["in"], sink_config);
-        config.sinks["out"].buffer = BufferConfig::Disk {
-            max_size,
-            when_full: Default::default(),
+        config.sinks["out"].buffer = BufferConfig {
+            stages: vec![BufferType::Disk {
+                max_size,
+                when_full: Default::default(),
+            }],
         };
         config.global.data_dir = Some(data_dir.clone());
         config.build().unwrap()
@@ -98,9 +100,11 @@ fn test_buffering() {
         let mut config = config::Config::builder();
         config.add_source("in", source_config);
         config.add_sink("out", &["in"], sink_config);
-        config.sinks["out"].buffer = BufferConfig::Disk {
-            max_size,
-            when_full: Default::default(),
+        config.sinks["out"].buffer = BufferConfig {
+            stages: vec![BufferType::Disk {
+                max_size,
+                when_full: Default::default(),
+            }],
         };
         config.global.data_dir = Some(data_dir);
         config.build().unwrap()
@@ -11,7 +11,8 @@ async fn load(config: &str, format: config::FormatHint) -> Result<Vec<String>, V
             let c2 = config::load_from_str(config, format).unwrap();
             match (
                 config::warnings(&c2.into()),
-                topology::builder::build_pieces(&c, &diff, HashMap::new()).await,
+                topology::builder::build_pieces(&c, &diff, HashMap::new(), &Default::default())
+                    .await,
             ) {
                 (warnings, Ok(_pieces)) => Ok(warnings),
                 (_, Err(errors)) => Err(errors),
@@ -258,6 +259,48 @@ async fn duplicate_name() {
     );
 }
 
+#[cfg(all(
+    feature = "sources-socket",
+    feature = "transforms-dedupe",
+    feature = "sinks-socket"
+))]
+#[tokio::test]
+async fn duplicate_dedupe_persistence_key() {
+    let err = load(
+        r#"
+        [sources.in]
+        type = "socket"
+        mode = "tcp"
+        address = "127.0.0.1:1234"
+
+        [transforms.first]
+        type = "dedupe"
+        inputs = ["in"]
+        fields.match = ["message"]
+        cache.persistence.key = "shared"
+
+        [transforms.second]
+        type = "dedupe"
+        inputs = ["first"]
+        fields.match = ["host"]
+        cache.persistence.key = "shared"
+
+        [sinks.out]
+        type = "socket"
+        mode = "tcp"
+        inputs = ["second"]
+        encoding = "text"
+        address = "127.0.0.1:9999"
+        "#,
+        Some(Format::Toml),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(err.len(), 1);
+    assert!(err[0].starts_with("Resource `data directory \"shared\" of the global data_dir` is claimed by multiple components"));
+}
+
 #[cfg(all(
     feature = "sources-socket",
     feature = "transforms-sample",
@@ -831,3 +874,202 @@ async fn route() {
 
     assert_eq!(0, warnings.len());
 }
+
+#[cfg(all(
+    feature = "sources-socket",
+    feature = "transforms-remap",
+    feature = "sinks-socket"
+))]
+#[tokio::test]
+async fn pipeline() {
+    let config = r#"
+        [sources.nginx]
+        type = "socket"
+        mode = "tcp"
+        address = "127.0.0.1:1235"
+
+        [pipelines.tagging]
+        params.env = "production"
+
+        [pipelines.tagging.transforms.service]
+        type = "remap"
+        source = '.service = "${params.service}"'
+
+        [pipelines.tagging.transforms.env]
+        type = "remap"
+        source = '.env = "${params.env}"'
+
+        [transforms.nginx_tagging]
+        type = "pipeline"
+        inputs = ["nginx"]
+        pipeline = "tagging"
+        params.service = "nginx"
+
+        [sinks.out]
+        type = "socket"
+        mode = "tcp"
+        inputs = ["nginx_tagging"]
+        encoding = "text"
+        address = "127.0.0.1:9999"
+        "#;
+
+    let warnings = load(config, Some(Format::Toml)).await.unwrap();
+    assert_eq!(0, warnings.len());
+
+    let config = config::load_from_str(config, Some(Format::Toml)).unwrap();
+    assert_eq!(
+        config.transforms.keys().collect::<Vec<_>>(),
+        vec!["nginx_tagging.service", "nginx_tagging.env"]
+    );
+    assert_eq!(
+        config.transforms["nginx_tagging.service"].inputs,
+        vec!["nginx"]
+    );
+    assert_eq!(
+        config.transforms["nginx_tagging.env"].inputs,
+        vec!["nginx_tagging.service"]
+    );
+    assert_eq!(config.sinks["out"].inputs, vec!["nginx_tagging.env"]);
+    assert_eq!(
+        config.pipelines["nginx_tagging"].transforms,
+        vec!["nginx_tagging.service", "nginx_tagging.env"]
+    );
+
+    let source = |name: &str| {
+        serde_json::to_value(&config.transforms[name].inner).unwrap()["source"].clone()
+    };
+    assert_eq!(source("nginx_tagging.service"), r#".service = "nginx""#);
+    assert_eq!(source("nginx_tagging.env"), r#".env = "production""#);
+}
+
+#[cfg(all(feature = "sources-socket", feature = "sinks-socket"))]
+#[tokio::test]
+async fn pipeline_colliding_with_component() {
+    let err = load(
+        r#"
+        [sources.in]
+        type = "socket"
+        mode = "tcp"
+        address = "127.0.0.1:1235"
+
+        [pipelines.tagging.transforms.service]
+        type = "add_fields"
+        fields.service = "nginx"
+
+        [transforms.tagging]
+        type = "pipeline"
+        inputs = ["in"]
+        pipeline = "tagging"
+
+        [transforms."tagging.service"]
+        type = "add_fields"
+        inputs = ["in"]
+        fields.env = "production"
+
+        [sinks.out]
+        type = "socket"
+        mode = "tcp"
+        inputs = ["tagging", "tagging.service"]
+        encoding = "text"
+        address = "127.0.0.1:9999"
+        "#,
+        Some(Format::Toml),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        vec![
+            r#"failed to expand pipeline "tagging" for transform "tagging": "tagging.service" is already the name of a component or output."#
+        ]
+    );
+}
+
+#[cfg(all(
+    feature = "sources-socket",
+    feature = "transforms-remap",
+    feature = "sinks-socket"
+))]
+#[tokio::test]
+async fn pipeline_colliding_with_named_output() {
+    let err = load(
+        r#"
+        [sources.in]
+        type = "socket"
+        mode = "tcp"
+        address = "127.0.0.1:1235"
+
+        [pipelines.tagging.transforms."parse.dropped"]
+        type = "add_fields"
+        fields.service = "nginx"
+
+        [transforms.nginx]
+        type = "pipeline"
+        inputs = ["in"]
+        pipeline = "tagging"
+
+        [transforms."nginx.parse"]
+        type = "remap"
+        inputs = ["in"]
+        source = ". = parse_json!(.message)"
+        reroute_dropped = true
+
+        [sinks.out]
+        type = "socket"
+        mode = "tcp"
+        inputs = ["nginx", "nginx.parse"]
+        encoding = "text"
+        address = "127.0.0.1:9999"
+        "#,
+        Some(Format::Toml),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        vec![
+            r#"failed to expand pipeline "tagging" for transform "nginx": "nginx.parse.dropped" is already the name of a component or output."#
+        ]
+    );
+}
+
+#[cfg(all(feature = "sources-socket", feature = "sinks-socket"))]
+#[tokio::test]
+async fn pipeline_with_unset_param() {
+    let err = load(
+        r#"
+        [sources.in]
+        type = "socket"
+        mode = "tcp"
+        address = "127.0.0.1:1235"
+
+        [pipelines.tagging.transforms.service]
+        type = "add_fields"
+        fields.service = "${params.service}"
+
+        [transforms.tagging]
+        type = "pipeline"
+        inputs = ["in"]
+        pipeline = "tagging"
+
+        [sinks.out]
+        type = "socket"
+        mode = "tcp"
+        inputs = ["tagging"]
+        encoding = "text"
+        address = "127.0.0.1:9999"
+        "#,
+        Some(Format::Toml),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        vec![
+            r#"failed to expand pipeline "tagging" for transform "tagging": Transform "service": Parameter "service" is not set."#
+        ]
+    );
+}
@@ -30,8 +30,8 @@ use tracing::{error, info};
 use vector::{
     buffers::Acker,
     config::{
-        DataType, GlobalOptions, SinkConfig, SinkContext, SourceConfig, SourceContext,
-        TransformConfig,
+        DataType, SinkConfig, SinkContext, SourceConfig, SourceContext, TransformConfig,
+        TransformContext,
     },
     event::{
         metric::{self, MetricData, MetricValue},
@@ -223,6 +223,7 @@ impl FunctionTransform for MockTransform {
                     }
                     MetricValue::AggregatedHistogram { .. } => None,
                     MetricValue::AggregatedSummary { .. } => None,
+                    MetricValue::Sketch { .. } => None,
                     MetricValue::Set { .. } => {
                         let mut values = BTreeSet::new();
                         values.insert(self.suffix.clone());
@@ -237,6 +238,7 @@ impl FunctionTransform for MockTransform {
                     }));
                 }
             }
+            Event::Trace(_) => {}
         };
         output.push(event);
     }
@@ -257,7 +259,7 @@ impl MockTransformConfig {
 #[async_trait]
 #[typetag::serde(name = "mock")]
 impl TransformConfig for MockTransformConfig {
-    async fn build(&self, _globals: &GlobalOptions) -> Result<Transform, vector::Error> {
+    async fn build(&self, _context: &TransformContext) -> Result<Transform, vector::Error> {
         Ok(Transform::function(MockTransform {
             suffix: self.suffix.clone(),
             increase: self.increase,
@@ -287,6 +289,8 @@ where
     sink: Option<T>,
     #[serde(skip)]
     healthy: bool,
+    #[serde(skip)]
+    data_type: Option<DataType>,
 }
 
 impl<T> MockSinkConfig<T>
@@ -298,8 +302,13 @@ where
         Self {
             sink: Some(sink),
             healthy,
+            data_type: Some(DataType::Any),
         }
     }
+
+    pub fn set_data_type(&mut self, data_type: DataType) {
+        self.data_type = Some(data_type)
+    }
 }
 
 #[derive(Debug, Snafu)]
@@ -331,7 +340,7 @@ where
     }
 
     fn input_type(&self) -> DataType {
-        DataType::Any
+        self.data_type.unwrap()
     }
 
     fn sink_type(&self) -> &'static str {
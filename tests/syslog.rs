@@ -5,7 +5,10 @@ use rand::{thread_rng, Rng};
 use serde::Deserialize;
 use serde_json::Value;
 use sinks::socket::{self, SocketSinkConfig};
-use sinks::util::{encoding::EncodingConfig, tcp::TcpSinkConfig, Encoding};
+use sinks::util::{
+    encoding::{EncodingConfig, StandardEncodings},
+    tcp::TcpSinkConfig,
+};
 use std::{collections::HashMap, fmt, str::FromStr};
 #[cfg(unix)]
 use tokio::io::AsyncWriteExt;
@@ -343,6 +346,6 @@ fn encode_priority(severity: Severity, facility: Facility) -> u8 {
 fn tcp_json_sink(address: String) -> SocketSinkConfig {
     SocketSinkConfig::new(
         socket::Mode::Tcp(TcpSinkConfig::from_address(address)),
-        EncodingConfig::from(Encoding::Json),
+        EncodingConfig::from(StandardEncodings::Json),
     )
 }
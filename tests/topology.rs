@@ -11,7 +11,14 @@ use std::{
     },
 };
 use tokio::time::{sleep, Duration};
-use vector::{config::Config, event::Event, test_util::start_topology, topology};
+#[cfg(feature = "transforms-json_parser")]
+use vector::transforms::json_parser::JsonParserConfig;
+use vector::{
+    config::{Config, DataType},
+    event::{Event, EventStatus, LogEvent, TraceEvent},
+    test_util::start_topology,
+    topology,
+};
 
 fn basic_config() -> Config {
     let mut config = Config::builder();
@@ -167,6 +174,30 @@ async fn topology_multiple_sinks() {
     assert_eq!(vec![event], res2);
 }
 
+#[tokio::test]
+async fn topology_filters_traces_from_log_sinks() {
+    let (mut in1, source1) = source();
+    let (out1, mut sink1) = sink(10);
+    sink1.set_data_type(DataType::Log);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_sink("out1", &["in1"], sink1);
+
+    let (topology, _crash) = start_topology(config.build().unwrap(), false).await;
+
+    let trace = Event::from(TraceEvent::from(LogEvent::from("trace")));
+    let log = Event::from("log");
+    in1.send(trace).await.unwrap();
+    in1.send(log.clone()).await.unwrap();
+
+    topology.stop().await;
+
+    let res = out1.collect::<Vec<_>>().await;
+
+    assert_eq!(vec![log], res);
+}
+
 #[tokio::test]
 async fn topology_transform_chain() {
     let (mut in1, source1) = source();
@@ -193,6 +224,74 @@ async fn topology_transform_chain() {
     assert_eq!(vec!["this first second"], res);
 }
 
+#[cfg(feature = "transforms-json_parser")]
+#[tokio::test]
+async fn topology_transform_dropped_output() {
+    let (mut in1, source1) = source();
+    let (out1, sink1) = sink(10);
+    let (out2, sink2) = sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_transform(
+        "parse",
+        &["in1"],
+        JsonParserConfig {
+            drop_invalid: true,
+            reroute_dropped: true,
+            ..Default::default()
+        },
+    );
+    config.add_sink("out1", &["parse"], sink1);
+    config.add_sink("out2", &["parse.dropped"], sink2);
+
+    let (topology, _crash) = start_topology(config.build().unwrap(), false).await;
+
+    in1.send(Event::from(r#"{"message": "parsed"}"#))
+        .await
+        .unwrap();
+    in1.send(Event::from("not json")).await.unwrap();
+
+    topology.stop().await;
+
+    let res1 = out1.map(into_message).collect::<Vec<_>>().await;
+    let res2 = out2.map(into_message).collect::<Vec<_>>().await;
+
+    assert_eq!(vec!["parsed"], res1);
+    assert_eq!(vec!["not json"], res2);
+}
+
+#[tokio::test]
+async fn topology_sink_dropped_output() {
+    let (mut in1, source1) = source();
+    let (mut out1, sink1) = sink(10);
+    let (out2, sink2) = sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_sink("out1", &["in1"], sink1);
+    config.sinks["out1"].reroute_dropped = true;
+    config.add_sink("out2", &["out1.dropped"], sink2);
+
+    let (topology, _crash) = start_topology(config.build().unwrap(), false).await;
+
+    in1.send(Event::from("delivered")).await.unwrap();
+    in1.send(Event::from("rejected")).await.unwrap();
+
+    let delivered = out1.next().await.unwrap();
+    delivered.metadata().update_status(EventStatus::Delivered);
+    drop(delivered);
+    let rejected = out1.next().await.unwrap();
+    rejected.metadata().update_status(EventStatus::Failed);
+    drop(rejected);
+
+    topology.stop().await;
+
+    let res = out2.map(into_message).collect::<Vec<_>>().await;
+
+    assert_eq!(vec!["rejected"], res);
+}
+
 #[tokio::test]
 async fn topology_remove_one_source() {
     let (mut in1, source1) = source();